@@ -13,6 +13,7 @@ end
 function on_target_select(parent, ability, targets)
   local points = targets:affected_points()
   local surface = parent:create_surface(ability:name(), points, ability:duration())
+  surface:set_hazard()
   
   local stats = parent:stats()
   local bonus = (10 + stats.caster_level / 2 + stats.wisdom_bonus / 4) * 0.03
@@ -1,5 +1,6 @@
 function fire_surface(parent, ability, points, duration)
   local surf = parent:create_surface("Fire", points, duration)
+  surf:set_hazard()
   surf:set_squares_to_fire_on_moved(3)
   
   local cb = ability:create_callback(parent)
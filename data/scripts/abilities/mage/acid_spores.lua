@@ -97,6 +97,7 @@ end
 function create_acid_surface(parent, ability, targets)
   local points = targets:random_affected_points(0.5)
   local surf = parent:create_surface(ability:name(), points, 3)
+  surf:set_hazard()
   surf:set_squares_to_fire_on_moved(3)
   
   local cb = ability:create_callback(parent)
@@ -28,13 +28,22 @@ function on_target_select(parent, ability, targets)
   game:play_sfx("sfx/shot2")
 end
 
-function attack_target(parent, ability, targets)
-  local target = targets:first()
+function preview(parent, ability)
+  local min_dmg, max_dmg = damage(parent)
+  return string.format("Damage: %d - %d Crushing", math.floor(min_dmg), math.floor(max_dmg))
+end
 
+function damage(parent)
   local stats = parent:stats()
   local min_dmg = 15 + stats.caster_level / 2 + stats.intellect_bonus / 4
   local max_dmg = 25 + stats.intellect_bonus / 2 + stats.caster_level
-  
+  return min_dmg, max_dmg
+end
+
+function attack_target(parent, ability, targets)
+  local target = targets:first()
+
+  local min_dmg, max_dmg = damage(parent)
   parent:special_attack(target, "Reflex", "Spell", min_dmg, max_dmg, 0, "Crushing")
 end
 
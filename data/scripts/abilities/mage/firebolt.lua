@@ -3,6 +3,7 @@ function on_activate(parent, ability)
   
   local targeter = parent:create_targeter(ability)
   targeter:set_selection_visible()
+  targeter:set_damage_kind("Fire")
   targeter:add_all_selectable(targets)
   targeter:add_all_effectable(targets)
   targeter:activate()
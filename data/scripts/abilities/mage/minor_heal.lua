@@ -8,11 +8,19 @@ function on_activate(parent, ability)
   targeter:activate()
 end
 
+function preview(parent, ability)
+  return string.format("Heals: %d", math.floor(heal_amount(parent)))
+end
+
+function heal_amount(parent)
+  local stats = parent:stats()
+  return 12 + stats.caster_level + stats.intellect_bonus / 4
+end
+
 function on_target_select(parent, ability, targets)
   local target = targets:first()
-  local stats = parent:stats()
-  
-  local amount = 12 + stats.caster_level + stats.intellect_bonus / 4
+
+  local amount = heal_amount(parent)
   target:heal_damage(amount)
 
   local anim = target:create_particle_generator("heal", 1.0)
@@ -16,6 +16,7 @@ function on_target_select(parent, ability, targets)
 
   local points = targets:affected_points()
   local surface = parent:create_surface(ability:name(), points, ability:duration())
+  surface:set_hazard()
   surface:set_squares_to_fire_on_moved(6)
   
   local cb = ability:create_callback(parent)
@@ -17,6 +17,7 @@ function on_target_select(parent, ability, targets)
 
   local points = targets:affected_points()
   local surface = parent:create_surface(ability:name(), points, ability:duration())
+  surface:set_hazard()
   surface:add_attribute_bonus("Intellect", -4)
   surface:add_attribute_bonus("Perception", -4)
   
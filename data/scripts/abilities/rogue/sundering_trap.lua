@@ -20,6 +20,8 @@ function on_target_select(parent, ability, targets)
   local points = targets:affected_points()
 
   local surf = parent:create_surface(ability:name(), points)
+  surf:set_hazard()
+  surf:set_hidden()
   surf:set_tag("trap")
   surf:set_squares_to_fire_on_moved(1)
   
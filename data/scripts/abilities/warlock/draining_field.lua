@@ -13,6 +13,7 @@ end
 function on_target_select(parent, ability, targets)
   local points = targets:affected_points()
   local surface = parent:create_surface(ability:name(), points, ability:duration())
+  surface:set_hazard()
   
   local cb = ability:create_callback(parent)
   cb:set_on_surface_round_elapsed_fn("apply_damage")
@@ -22,6 +22,9 @@ function ai_action(parent, params)
     game:log("AI turn for " .. parent:id())
     game:log("  Current AP " .. tostring(parent:stats().current_ap))
 
+    -- party members played during assisted combat follow the player's settings
+    local behavior = parent:ai_behavior()
+
     local abilities = parent:abilities():can_activate():remove_kind("Special")
     abilities:sort_by_priority()
 
@@ -74,7 +77,7 @@ function ai_action(parent, params)
             return parent:state_wait(WAIT_TIME)
         end
 
-        if abilities:is_empty() then
+        if abilities:is_empty() or behavior.conserve_abilities then
             break
         end
 
@@ -115,6 +118,9 @@ function ai_action(parent, params)
     end
 
     local max_retries = params["MeleeAttackMoveTries"]
+    if behavior.defensive then
+        max_retries = 0
+    end
 
     for retry = 0, max_retries do
         game:log("Trying to find attack target attempt " .. tostring(retry))
@@ -122,7 +128,7 @@ function ai_action(parent, params)
             local target = targets[i]
             game:log("  Checking for attack against " .. target:id())
 
-            local result = check_move_for_attack(parent, target, retry)
+            local result = check_move_for_attack(parent, target, retry, behavior.defensive)
             if result.attack then
                 game:log("  Perform attack")
                 parent:anim_weapon_attack(target, nil, true)
@@ -188,7 +194,7 @@ function attempt_run_away(parent, hostiles)
     end
 end
 
-function check_move_for_attack(parent, target, attempt)
+function check_move_for_attack(parent, target, attempt, hold_position)
     if not parent:stats().attack_is_ranged then
         game:log("    Melee attack")
         if parent:is_within_attack_dist(target) then
@@ -196,6 +202,10 @@ function check_move_for_attack(parent, target, attempt)
             return { attack=true }
         end
 
+        if hold_position then
+            return { attack=false }
+        end
+
         local increase = attempt * math.max(parent:width(), parent:height())
 
         game:log("    Attempt move towards target")
@@ -219,6 +229,11 @@ function check_move_for_attack(parent, target, attempt)
         local target_dist = parent:stats().attack_distance - 1
 
         game:log("At dist " .. tostring(dist) .. " target dist is " .. tostring(target_dist))
+        if hold_position then
+            local in_range = parent:is_within_attack_dist(target) and parent:has_visibility(target)
+            return { attack=in_range }
+        end
+
         if dist > target_dist then
             game:log("    Attempt move towards target")
             if not check_move_towards(parent, target, target_dist).done then
@@ -22,12 +22,13 @@ use std::cell::RefCell;
 
 use log::{error, info};
 
+use sulis_core::config::Config;
 use sulis_core::resource::ResourceSet;
 use sulis_core::io::{DisplayConfiguration, System, ControlFlowUpdater};
 use sulis_core::ui::{self, Cursor, Widget};
 use sulis_core::util::{self, ActiveResources};
 use sulis_module::{Actor, Module};
-use sulis_state::{GameState, NextGameStep, SaveState};
+use sulis_state::{script_test, GameState, NextGameStep, Presence, SaveState};
 use sulis_view::{main_menu::{self, MainMenu}, RootView, trigger_activator};
 
 struct GameControlFlowUpdater {
@@ -100,6 +101,8 @@ impl GameControlFlowUpdater {
     }
 
     fn main_menu(&mut self) {
+        Presence::clear();
+        Module::set_saved_rules(None);
         let view = main_menu::MainMenu::new(
             self.display_configurations.clone(),
             sulis_core::io::audio::get_audio_devices(),
@@ -137,8 +140,8 @@ impl GameControlFlowUpdater {
         match step {
             Exit => {
                 self.exit = true;
-            }, NewCampaign { pc_actor } => {
-                self.new_campaign(pc_actor, Vec::new(), HashMap::new());
+            }, NewCampaign { pc_actor, party_actors } => {
+                self.new_campaign(pc_actor, party_actors, HashMap::new());
             }, LoadCampaign { save_state } => {
                 self.load_campaign(*save_state);
             }, LoadModuleAndNewCampaign { pc_actor, party_actors, flags, module_dir } => {
@@ -223,16 +226,115 @@ fn load_resources() {
     info!("Loaded all resources in {}s", util::format_elapsed_secs(start.elapsed()));
 }
 
+fn run_script_tests(path: Option<&String>) {
+    let path = match path {
+        None => {
+            eprintln!("Usage: --test-scripts <scenario file>");
+            std::process::exit(2);
+        }
+        Some(path) => path,
+    };
+
+    info!("Running script tests from '{}'", path);
+    match script_test::run_file(std::path::Path::new(path)) {
+        Err(e) => {
+            eprintln!("Unable to run script tests from '{path}': {e}");
+            std::process::exit(2);
+        }
+        Ok(results) => {
+            println!("{}", script_test::summary(&results));
+            let code = if results.iter().all(|r| r.passed()) { 0 } else { 1 };
+            std::process::exit(code);
+        }
+    }
+}
+
+fn package_module(args: &[String]) {
+    let index = match args.iter().position(|arg| arg == "--package-module") {
+        None => return,
+        Some(index) => index,
+    };
+
+    let (dir, version) = match (args.get(index + 1), args.get(index + 2)) {
+        (Some(dir), Some(version)) => (dir, version),
+        _ => {
+            eprintln!("Usage: sulis --package-module <module directory> <version>");
+            std::process::exit(2);
+        }
+    };
+
+    match Module::package(dir, version) {
+        Err(e) => {
+            eprintln!("Unable to package module '{dir}': {e}");
+            std::process::exit(1);
+        }
+        Ok(path) => {
+            println!("Wrote module archive to {}", path.to_string_lossy());
+            std::process::exit(0);
+        }
+    }
+}
+
+fn select_profile(args: &[String]) {
+    if args.iter().any(|arg| arg == "--list-profiles") {
+        for profile in Config::profiles() {
+            println!("{profile}");
+        }
+        std::process::exit(0);
+    }
+
+    let index = match args.iter().position(|arg| arg == "--profile") {
+        None => return,
+        Some(index) => index,
+    };
+
+    let name = match args.get(index + 1) {
+        None => {
+            eprintln!("Usage: sulis --profile <name>");
+            std::process::exit(2);
+        }
+        Some(name) => name,
+    };
+
+    if let Err(e) = Config::set_profile(name) {
+        eprintln!("{e}");
+        std::process::exit(2);
+    }
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    // the profile and safe mode must be set before the CONFIG is first accessed
+    select_profile(&args);
+    if args.iter().any(|arg| arg == "--safe-mode") {
+        Config::enable_safe_mode();
+    }
+
     // CONFIG will be lazily initialized here; if it fails it
     // prints an error and exits.  Don't drop the returned handle
     // while the program is running
     let _logger_handle = util::setup_logger();
     info!("=========Initializing=========");
-    info!("Setup Logger and read configuration from 'config.yml'");
+    if Config::is_safe_mode() {
+        info!("Setup Logger in safe mode, ignoring 'config.yml'");
+    } else {
+        info!("Setup Logger and read configuration from 'config.yml'");
+    }
+    if let Some(profile) = Config::profile() {
+        info!("Using profile '{}'", profile);
+    }
+
+    package_module(&args);
 
     load_resources();
 
+    if let Some(index) = args.iter().position(|arg| arg == "--test-scripts") {
+        run_script_tests(args.get(index + 1));
+    }
+
+    Presence::init();
+
     let system = create_io();
 
     let flow_controller = GameControlFlowUpdater::new(&system);
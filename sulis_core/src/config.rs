@@ -20,6 +20,8 @@ use std::fs::{self, File};
 use std::io::{Error, ErrorKind, Read};
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 use lazy_static::lazy_static;
 use serde::{Deserialize, Deserializer};
@@ -28,6 +30,9 @@ use log::{Level, LevelFilter};
 use crate::io::keyboard_event::Key;
 use crate::io::{event::ClickKind, InputActionKind, InputAction, KeyboardEvent};
 
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+static PROFILE: Mutex<Option<String>> = Mutex::new(None);
+
 thread_local! {
     static CONFIG: RefCell<Config> = RefCell::new(Config::init());
     static OLD_CONFIG: RefCell<Option<Config>> = RefCell::new(None);
@@ -48,6 +53,15 @@ pub struct Config {
     pub logging: LoggingConfig,
     pub editor: EditorConfig,
 
+    #[serde(default)]
+    pub accessibility: AccessibilityConfig,
+
+    #[serde(default)]
+    pub gameplay: GameplayConfig,
+
+    #[serde(default)]
+    pub presence: PresenceConfig,
+
     #[serde(default)]
     pub debug: DebugConfig,
 }
@@ -67,6 +81,68 @@ impl Config {
         CONFIG.with(|c| c.borrow().clone())
     }
 
+    /// Returns a clone of the current config, or `None` if it is not
+    /// currently accessible.  Used when reporting crashes
+    pub fn try_get_clone() -> Option<Config> {
+        CONFIG
+            .try_with(|c| c.try_borrow().ok().map(|c| c.clone()))
+            .ok()
+            .flatten()
+    }
+
+    /// Enables safe mode.  This must be called prior to the config being
+    /// accessed for the first time.  In safe mode, the user config file is
+    /// ignored and the sample config is used with minimal display settings
+    pub fn enable_safe_mode() {
+        SAFE_MODE.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_safe_mode() -> bool {
+        SAFE_MODE.load(Ordering::Relaxed)
+    }
+
+    /// Selects the named user profile.  This must be called prior to the
+    /// config or `USER_DIR` being accessed for the first time.  Each profile
+    /// has its own config, saves, and logs in a subdirectory of the profiles
+    /// directory.  Returns an error if the name is not a valid profile name
+    pub fn set_profile(name: &str) -> Result<(), Error> {
+        let valid = name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+        if name.is_empty() || !valid {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid profile name '{name}'.  Use only letters, numbers, _ and -"),
+            ));
+        }
+
+        *PROFILE.lock().unwrap() = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Returns the name of the active user profile, or `None` if the
+    /// default profile is in use
+    pub fn profile() -> Option<String> {
+        PROFILE.lock().unwrap().clone()
+    }
+
+    /// Returns the names of all existing user profiles
+    pub fn profiles() -> Vec<String> {
+        let dir = match fs::read_dir(get_profiles_dir()) {
+            Err(_) => return Vec::new(),
+            Ok(dir) => dir,
+        };
+
+        let mut profiles: Vec<String> = dir
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        profiles.sort();
+        profiles
+    }
+
     pub fn display_resolution() -> (u32, u32) {
         CONFIG.with(|c| {
             let c = c.borrow();
@@ -99,16 +175,44 @@ impl Config {
     }
 
     pub fn ui_width() -> i32 {
-        CONFIG.with(|c| c.borrow().display.width)
+        CONFIG.with(|c| {
+            let c = c.borrow();
+            c.display.computed_width.unwrap_or(c.display.width)
+        })
     }
 
     pub fn ui_size() -> (i32, i32) {
         CONFIG.with(|c| {
             let c = c.borrow();
-            (c.display.width, c.display.height)
+            let width = c.display.computed_width.unwrap_or(c.display.width);
+            (width, c.display.height)
         })
     }
 
+    pub fn safe_area_max_aspect() -> f32 {
+        CONFIG.with(|c| c.borrow().display.safe_area_max_aspect)
+    }
+
+    /// Updates the UI width for a window of the specified size in pixels.
+    /// When `auto_ui_width` is set, the width matches the window's aspect
+    /// ratio, limited to between 4:3 and 21:9.  Otherwise, the configured
+    /// width is used.
+    pub fn set_window_size(width_pixels: u32, height_pixels: u32) {
+        CONFIG.with(|c| {
+            let mut c = c.borrow_mut();
+            if !c.display.auto_ui_width || width_pixels == 0 || height_pixels == 0 {
+                c.display.computed_width = None;
+                return;
+            }
+
+            let aspect = width_pixels as f32 / height_pixels as f32;
+            let aspect = aspect.clamp(4.0 / 3.0, 21.0 / 9.0);
+            let width = (c.display.height as f32 * aspect).round() as i32;
+            info!("Computed UI width {} for window {}x{}", width, width_pixels, height_pixels);
+            c.display.computed_width = Some(width);
+        });
+    }
+
     pub fn frame_rate() -> u32 {
         CONFIG.with(|c| c.borrow().display.frame_rate)
     }
@@ -125,6 +229,30 @@ impl Config {
         CONFIG.with(|c| c.borrow().logging.clone())
     }
 
+    pub fn accessibility() -> AccessibilityConfig {
+        CONFIG.with(|c| c.borrow().accessibility.clone())
+    }
+
+    pub fn auto_pause() -> AutoPauseConfig {
+        CONFIG.with(|c| c.borrow().gameplay.auto_pause)
+    }
+
+    pub fn combat_animation_speed() -> u32 {
+        CONFIG.with(|c| c.borrow().gameplay.combat_animation_speed.max(1))
+    }
+
+    pub fn avoid_hazards() -> bool {
+        CONFIG.with(|c| c.borrow().gameplay.avoid_hazards)
+    }
+
+    pub fn difficulty() -> Difficulty {
+        CONFIG.with(|c| c.borrow().gameplay.difficulty)
+    }
+
+    pub fn presence() -> PresenceConfig {
+        CONFIG.with(|c| c.borrow().presence.clone())
+    }
+
     pub fn debug() -> DebugConfig {
         CONFIG.with(|c| c.borrow().debug.clone())
     }
@@ -177,6 +305,16 @@ impl Config {
         CONFIG.with(|c| c.borrow().input.crit_screen_shake)
     }
 
+    pub fn content_filter() -> bool {
+        CONFIG.with(|c| c.borrow().display.content_filter)
+    }
+
+    /// The approximate amount of texture memory, in megabytes, that the renderer
+    /// keeps loaded before evicting least recently used spritesheets and fonts
+    pub fn texture_memory_budget() -> u32 {
+        CONFIG.with(|c| c.borrow().display.texture_memory_budget)
+    }
+
     pub fn scroll_to_active() -> bool {
         CONFIG.with(|c| c.borrow().display.scroll_to_active)
     }
@@ -186,11 +324,101 @@ impl Config {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AccessibilityConfig {
+    pub screen_reader: bool,
+    pub speech: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GameplayConfig {
+    #[serde(default)]
+    pub difficulty: Difficulty,
+
+    #[serde(default)]
+    pub auto_pause: AutoPauseConfig,
+
+    /// Multiplies the speed of movement and attack animations during combat.
+    /// Zero is treated as one
+    #[serde(default)]
+    pub combat_animation_speed: u32,
+
+    /// Whether party members path around known hazards, such as fire or traps
+    #[serde(default = "default_avoid_hazards")]
+    pub avoid_hazards: bool,
+}
+
+impl Default for GameplayConfig {
+    fn default() -> Self {
+        GameplayConfig {
+            difficulty: Difficulty::default(),
+            auto_pause: AutoPauseConfig::default(),
+            combat_animation_speed: 0,
+            avoid_hazards: true,
+        }
+    }
+}
+
+fn default_avoid_hazards() -> bool {
+    true
+}
+
+/// Events which automatically pause the game, giving the player time to
+/// react.  The game resumes with the `TogglePause` keybinding.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct AutoPauseConfig {
+    /// A party member is damaged to below a quarter of their hit points
+    pub low_health: bool,
+
+    /// A hostile creature notices the party
+    pub enemy_sighted: bool,
+
+    /// A script reports that a trap was detected
+    pub trap_detected: bool,
+
+    /// A creature outside the party finishes using an ability
+    pub spell_cast: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn iter() -> impl Iterator<Item = &'static Difficulty> {
+        use Difficulty::*;
+        [Easy, Normal, Hard].iter()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PresenceConfig {
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub discord_client_id: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct DebugConfig {
     pub encounter_spawning: bool,
     pub limit_line_of_sight: bool,
+
+    #[serde(default)]
+    pub entity_overlay: bool,
+
+    #[serde(default)]
+    pub time_controls: bool,
 }
 
 impl Default for DebugConfig {
@@ -198,6 +426,8 @@ impl Default for DebugConfig {
         DebugConfig {
             encounter_spawning: true,
             limit_line_of_sight: true,
+            entity_overlay: false,
+            time_controls: false,
         }
     }
 }
@@ -212,6 +442,13 @@ pub struct EditorConfig {
     #[serde(deserialize_with = "de_non_empty_vec")]
     pub transition_sizes: Vec<String>,
     pub area: EditorAreaConfig,
+
+    #[serde(default = "default_autosave_interval")]
+    pub autosave_interval: u32,
+}
+
+fn default_autosave_interval() -> u32 {
+    120
 }
 
 fn de_non_empty_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -244,6 +481,9 @@ pub struct EditorAreaConfig {
     #[serde(deserialize_with = "de_non_empty_vec")]
     pub elev_tiles: Vec<String>,
     pub entity_layer: usize,
+
+    #[serde(default)]
+    pub over_layers: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -254,6 +494,9 @@ pub struct LoggingConfig {
     pub bench_log_level: Level,
     pub use_timestamps: bool,
     pub append: bool,
+
+    #[serde(default)]
+    pub export_combat_logs: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -282,6 +525,30 @@ pub struct DisplayConfig {
     pub default_cursor: String,
     pub scroll_to_active: bool,
     pub vsync_enabled: bool,
+
+    #[serde(default)]
+    pub content_filter: bool,
+
+    #[serde(default = "default_texture_memory_budget")]
+    pub texture_memory_budget: u32,
+
+    #[serde(default)]
+    pub auto_ui_width: bool,
+
+    #[serde(default = "default_safe_area_max_aspect")]
+    pub safe_area_max_aspect: f32,
+
+    /// The UI width computed from the window size when `auto_ui_width` is set
+    #[serde(skip)]
+    pub computed_width: Option<i32>,
+}
+
+fn default_safe_area_max_aspect() -> f32 {
+    16.0 / 9.0
+}
+
+fn default_texture_memory_budget() -> u32 {
+    512
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Copy)]
@@ -326,8 +593,27 @@ impl RawClick {
     }
 }
 
-#[cfg(not(target_os = "windows"))]
+const PROFILES_DIR: &str = "profiles";
+
+fn get_profiles_dir() -> PathBuf {
+    let mut path = get_base_user_dir();
+    path.push(PROFILES_DIR);
+    path
+}
+
 fn get_user_dir() -> PathBuf {
+    match Config::profile() {
+        None => get_base_user_dir(),
+        Some(profile) => {
+            let mut path = get_profiles_dir();
+            path.push(profile);
+            path
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn get_base_user_dir() -> PathBuf {
     let mut path = match ::std::env::var("XDG_CONFIG_HOME") {
         Ok(path_str) => PathBuf::from(path_str),
         Err(_) => {
@@ -341,7 +627,7 @@ fn get_user_dir() -> PathBuf {
 }
 
 #[cfg(target_os = "windows")]
-fn get_user_dir() -> PathBuf {
+fn get_base_user_dir() -> PathBuf {
     let mut path = get_home_dir();
     path.push("My Documents");
     path.push("My Games");
@@ -368,18 +654,24 @@ pub fn create_dir_and_warn(path: &Path) {
 
 impl Config {
     fn init() -> Config {
-        let revision = match Config::new(Path::new(CONFIG_BASE), 0) {
-            Ok(config) => config.revision,
+        let sample = match Config::new(Path::new(CONFIG_BASE), 0) {
+            Ok(config) => config,
             Err(orig_e) => match Config::new(&Path::new("../").join(CONFIG_BASE), 0) {
                 Err(_) => {
                     eprintln!("{orig_e}");
                     eprintln!("Unable to parse revision from config.sample");
                     std::process::exit(1);
                 }
-                Ok(config) => config.revision,
+                Ok(config) => config,
             },
         };
 
+        if Config::is_safe_mode() {
+            return Config::safe_mode(sample);
+        }
+
+        let revision = sample.revision;
+
         let mut config_path = USER_DIR.clone();
         config_path.push(CONFIG_FILENAME);
         let config_path = config_path.as_path();
@@ -410,6 +702,17 @@ impl Config {
         }
     }
 
+    fn safe_mode(mut config: Config) -> Config {
+        eprintln!("Starting in safe mode, ignoring '{CONFIG_FILENAME}'");
+        config.display.mode = DisplayMode::Window;
+        config.display.monitor = 0;
+        config.display.width_pixels = 1280;
+        config.display.height_pixels = 720;
+        config.display.frame_rate = 60;
+        config.display.vsync_enabled = true;
+        config
+    }
+
     fn create_config_from_sample(config_path: &Path) {
         let config_base_path = Path::new(CONFIG_BASE);
 
@@ -14,6 +14,8 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
 use crate::image::Image;
@@ -89,6 +91,21 @@ impl LayeredImage {
         draw_list
     }
 
+    /// Returns a key for the images, positions and colors making up this
+    /// image.  Two images with the same key are drawn identically
+    pub fn layers_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hue.map(f32::to_bits).hash(&mut hasher);
+        for layer in self.layers.iter() {
+            layer.x.to_bits().hash(&mut hasher);
+            layer.y.to_bits().hash(&mut hasher);
+            let color = layer.color.map(|c| [c.r, c.g, c.b, c.a].map(f32::to_bits));
+            color.hash(&mut hasher);
+            layer.image.id().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
     pub fn draw_to_texture(
         &self,
         renderer: &mut dyn GraphicsRenderer,
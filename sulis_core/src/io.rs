@@ -32,6 +32,8 @@ use std::cell::RefCell;
 use std::io::Error;
 use std::rc::Rc;
 
+use rayon::prelude::*;
+
 use crate::extern_image::{ImageBuffer, Rgba};
 
 use crate::config::Config;
@@ -83,11 +85,22 @@ pub trait GraphicsRenderer {
 
     fn has_texture(&self, id: &str) -> bool;
 
+    fn texture_cache_stats(&self) -> TextureCacheStats;
+
     fn set_scissor(&mut self, pos: Point, size: Size);
 
     fn clear_scissor(&mut self);
 }
 
+/// Current usage of the renderer's texture cache, with sizes in bytes
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TextureCacheStats {
+    pub textures: usize,
+    pub bytes: usize,
+    pub budget: usize,
+    pub evictions: usize,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum TextureMagFilter {
     Nearest,
@@ -181,40 +194,30 @@ impl DrawList {
 
     #[inline]
     pub fn from_texture_id(id: &str, tex_coords: &[f32; 8], rect: Rect) -> DrawList {
-        let x_min = rect.x;
-        let y_max = Config::ui_height() as f32 - rect.y;
-        let x_max = x_min + rect.w;
-        let y_min = y_max - rect.h;
-        let tc = tex_coords;
-
-        let centroid = Some([(x_min + x_max) / 2.0, (y_min + y_max) / 2.0]);
-
-        let quads = vec![
-            Vertex {
-                position: [x_min, y_max],
-                tex_coords: [tc[0], tc[1]],
-            },
-            Vertex {
-                position: [x_min, y_min],
-                tex_coords: [tc[2], tc[3]],
-            },
-            Vertex {
-                position: [x_max, y_max],
-                tex_coords: [tc[4], tc[5]],
-            },
-            Vertex {
-                position: [x_max, y_min],
-                tex_coords: [tc[6], tc[7]],
-            },
-            Vertex {
-                position: [x_min, y_min],
-                tex_coords: [tc[2], tc[3]],
-            },
-            Vertex {
-                position: [x_max, y_max],
-                tex_coords: [tc[4], tc[5]],
-            },
-        ];
+        let ui_height = Config::ui_height() as f32;
+
+        DrawList {
+            texture: id.to_string(),
+            quads: quad_vertices(ui_height, tex_coords, rect).to_vec(),
+            centroid: Some(quad_centroid(ui_height, rect)),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a DrawList for the texture with the specified `id`, containing
+    /// all of the specified quads in order.  The vertices are generated across
+    /// the rayon thread pool, which is useful for large, static lists such as
+    /// an entire area layer.
+    pub fn from_texture_quads(id: &str, quads: &[TexturedQuad]) -> DrawList {
+        // the config is thread local, so it must be read before entering the pool
+        let ui_height = Config::ui_height() as f32;
+
+        let centroid = quads.first().map(|quad| quad_centroid(ui_height, quad.rect));
+        let quads = quads
+            .par_iter()
+            .with_min_len(PARALLEL_QUADS_MIN_LEN)
+            .flat_map_iter(|quad| quad_vertices(ui_height, &quad.tex_coords, quad.rect))
+            .collect();
 
         DrawList {
             texture: id.to_string(),
@@ -321,6 +324,57 @@ impl DrawList {
     }
 }
 
+const PARALLEL_QUADS_MIN_LEN: usize = 256;
+
+/// A single rectangle of a texture, used to build a DrawList from
+/// many quads at once with `DrawList::from_texture_quads`
+#[derive(Debug, Copy, Clone)]
+pub struct TexturedQuad {
+    pub tex_coords: [f32; 8],
+    pub rect: Rect,
+}
+
+#[inline]
+fn quad_centroid(ui_height: f32, rect: Rect) -> [f32; 2] {
+    let y_max = ui_height - rect.y;
+    [rect.x + rect.w / 2.0, y_max - rect.h / 2.0]
+}
+
+#[inline]
+fn quad_vertices(ui_height: f32, tc: &[f32; 8], rect: Rect) -> [Vertex; 6] {
+    let x_min = rect.x;
+    let y_max = ui_height - rect.y;
+    let x_max = x_min + rect.w;
+    let y_min = y_max - rect.h;
+
+    [
+        Vertex {
+            position: [x_min, y_max],
+            tex_coords: [tc[0], tc[1]],
+        },
+        Vertex {
+            position: [x_min, y_min],
+            tex_coords: [tc[2], tc[3]],
+        },
+        Vertex {
+            position: [x_max, y_max],
+            tex_coords: [tc[4], tc[5]],
+        },
+        Vertex {
+            position: [x_max, y_min],
+            tex_coords: [tc[6], tc[7]],
+        },
+        Vertex {
+            position: [x_min, y_min],
+            tex_coords: [tc[2], tc[3]],
+        },
+        Vertex {
+            position: [x_max, y_max],
+            tex_coords: [tc[4], tc[5]],
+        },
+    ]
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Vertex {
     pub position: [f32; 2],
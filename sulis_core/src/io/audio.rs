@@ -39,6 +39,7 @@ thread_local! {
 enum QueueKind {
     Ambient,
     StopAmbient,
+    AmbientLoops(Vec<SoundSource>),
     Music,
     StopMusic,
     Sfx,
@@ -66,6 +67,12 @@ impl Audio {
         Audio::enqueue(sound, kind);
     }
 
+    /// Replaces the ambient loops currently playing, which are layered on top of
+    /// the main ambient sound.  Loops that are already playing continue uninterrupted.
+    pub fn change_ambient_loops(sounds: Vec<SoundSource>) {
+        Audio::enqueue(None, QueueKind::AmbientLoops(sounds));
+    }
+
     pub fn change_music(sound: Option<SoundSource>) {
         let kind = if sound.is_some() {
             QueueKind::Music
@@ -268,6 +275,10 @@ impl AudioSink {
         }
     }
 
+    fn is_playing(&self) -> bool {
+        !self.cur_id.is_empty()
+    }
+
     fn stop_play(&mut self) {
         self.queue.push_back(SinkQueueEntry::FadeOut(FADE_TIME));
         self.queue.push_back(SinkQueueEntry::Stop);
@@ -309,6 +320,7 @@ pub struct AudioDevice {
     config: AudioConfig,
     music: AudioSink,
     ambient: AudioSink,
+    ambient_loops: Vec<AudioSink>,
 }
 
 fn new_device(device: Device, name: String, mut config: AudioConfig) -> Result<AudioDevice, String> {
@@ -338,6 +350,7 @@ fn new_device(device: Device, name: String, mut config: AudioConfig) -> Result<A
         config,
         music,
         ambient,
+        ambient_loops: Vec::new(),
     })
 }
 
@@ -365,6 +378,9 @@ impl AudioDevice {
     fn update(&mut self, elapsed_millis: u32) {
         self.music.update(&self.stream_handle, elapsed_millis);
         self.ambient.update(&self.stream_handle, elapsed_millis);
+        for sink in self.ambient_loops.iter_mut() {
+            sink.update(&self.stream_handle, elapsed_millis);
+        }
     }
 
     fn play(&mut self, entry: QueueEntry) {
@@ -374,6 +390,7 @@ impl AudioDevice {
             QueueKind::Sfx => self.play_sfx(entry.sound.unwrap()),
             QueueKind::Ambient => self.play_ambient(entry.sound.unwrap()),
             QueueKind::StopAmbient => self.stop_ambient(),
+            QueueKind::AmbientLoops(sounds) => self.play_ambient_loops(sounds),
         }
     }
 
@@ -387,6 +404,29 @@ impl AudioDevice {
 
     fn stop_ambient(&mut self) {
         self.ambient.stop_play();
+        self.play_ambient_loops(Vec::new());
+    }
+
+    fn play_ambient_loops(&mut self, sounds: Vec<SoundSource>) {
+        let count = sounds.len();
+        for (index, sound) in sounds.into_iter().enumerate() {
+            if index == self.ambient_loops.len() {
+                match AudioSink::new(&self.stream_handle, self.config.ambient_volume) {
+                    Ok(sink) => self.ambient_loops.push(sink),
+                    Err(e) => {
+                        warn!("Unable to create ambient loop sink: {}", e);
+                        break;
+                    }
+                }
+            }
+            self.ambient_loops[index].switch_to_source(sound);
+        }
+
+        for sink in self.ambient_loops.iter_mut().skip(count) {
+            if sink.is_playing() {
+                sink.stop_play();
+            }
+        }
     }
 
     fn play_ambient(&mut self, sound: SoundSource) {
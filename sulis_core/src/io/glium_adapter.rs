@@ -15,6 +15,7 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use std::time;
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::io::{Error, ErrorKind};
 
@@ -110,11 +111,59 @@ pub struct GliumDisplay {
     matrix: [[f32; 4]; 4],
     textures: HashMap<String, GliumTexture>,
     scale_factor: f64,
+    frame: u64,
+    evictions: usize,
 }
 
 struct GliumTexture {
     texture: SrgbTexture2d,
     sampler_fn: Box<dyn Fn(Sampler<SrgbTexture2d>) -> Sampler<SrgbTexture2d>>,
+    bytes: usize,
+    last_used: Cell<u64>,
+
+    // spritesheets and fonts may be recreated from the resource set at any
+    // time.  other textures are render targets that cannot be recreated
+    evictable: bool,
+}
+
+impl GliumDisplay {
+    fn texture_bytes(&self) -> usize {
+        self.textures.values().map(|texture| texture.bytes).sum()
+    }
+
+    fn texture_budget_bytes() -> usize {
+        Config::texture_memory_budget() as usize * 1024 * 1024
+    }
+
+    /// Unloads least recently used spritesheets and fonts until the textures
+    /// fit within the configured budget.  Textures used during the current
+    /// frame are never unloaded.
+    fn evict_textures(&mut self) {
+        let budget = GliumDisplay::texture_budget_bytes();
+        let mut total = self.texture_bytes();
+        if total <= budget {
+            return;
+        }
+
+        let mut candidates: Vec<(u64, usize, String)> = self
+            .textures
+            .iter()
+            .filter(|(_, tex)| tex.evictable && tex.last_used.get() < self.frame)
+            .map(|(id, tex)| (tex.last_used.get(), tex.bytes, id.to_string()))
+            .collect();
+        candidates.sort();
+
+        for (_, bytes, id) in candidates {
+            if total <= budget {
+                break;
+            }
+
+            debug!("Evicting texture '{}' to free {} bytes", id, bytes);
+            self.textures.remove(&id);
+            total -= bytes;
+            self.evictions += 1;
+        }
+    }
 }
 
 pub struct GliumRenderer<'a> {
@@ -155,11 +204,45 @@ impl<'a> GliumRenderer<'a> {
             DrawListKind::Font => ResourceSet::font(texture_id).unwrap().image.clone(),
         };
 
-        self.register_texture(
+        self.insert_texture(
             texture_id,
             image,
             draw_list.texture_min_filter,
             draw_list.texture_mag_filter,
+            true,
+        );
+        self.display.evict_textures();
+    }
+
+    fn insert_texture(
+        &mut self,
+        id: &str,
+        image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+        min_filter: TextureMinFilter,
+        mag_filter: TextureMagFilter,
+        evictable: bool,
+    ) {
+        let dims = image.dimensions();
+        trace!("Registering texture '{}', {}x{}", id, dims.0, dims.1);
+        let image = RawImage2d::from_raw_rgba_reversed(&image.into_raw(), dims);
+        let texture = SrgbTexture2d::new(&self.display.display, image).unwrap();
+
+        let sampler_fn: Box<dyn Fn(Sampler<SrgbTexture2d>) -> Sampler<SrgbTexture2d>> =
+            Box::new(move |sampler| {
+                sampler
+                    .magnify_filter(get_mag_filter(mag_filter))
+                    .minify_filter(get_min_filter(min_filter))
+            });
+
+        self.display.textures.insert(
+            id.to_string(),
+            GliumTexture {
+                texture,
+                sampler_fn,
+                bytes: dims.0 as usize * dims.1 as usize * 4,
+                last_used: Cell::new(self.display.frame),
+                evictable,
+            },
         );
     }
 }
@@ -174,6 +257,7 @@ fn draw_to_surface<T: glium::Surface>(
         None => return,
         Some(texture) => texture,
     };
+    glium_texture.last_used.set(display.frame);
 
     let uniforms = uniform! {
         matrix: display.matrix,
@@ -231,25 +315,8 @@ impl<'a> GraphicsRenderer for GliumRenderer<'a> {
         min_filter: TextureMinFilter,
         mag_filter: TextureMagFilter,
     ) {
-        let dims = image.dimensions();
-        trace!("Registering texture '{}', {}x{}", id, dims.0, dims.1);
-        let image = RawImage2d::from_raw_rgba_reversed(&image.into_raw(), dims);
-        let texture = SrgbTexture2d::new(&self.display.display, image).unwrap();
-
-        let sampler_fn: Box<dyn Fn(Sampler<SrgbTexture2d>) -> Sampler<SrgbTexture2d>> =
-            Box::new(move |sampler| {
-                sampler
-                    .magnify_filter(get_mag_filter(mag_filter))
-                    .minify_filter(get_min_filter(min_filter))
-            });
-
-        self.display.textures.insert(
-            id.to_string(),
-            GliumTexture {
-                texture,
-                sampler_fn,
-            },
-        );
+        self.insert_texture(id, image, min_filter, mag_filter, false);
+        self.display.evict_textures();
     }
 
     fn clear_texture_region(&mut self, id: &str, min_x: i32, min_y: i32, max_x: i32, max_y: i32) {
@@ -282,6 +349,15 @@ impl<'a> GraphicsRenderer for GliumRenderer<'a> {
         self.display.textures.contains_key(id)
     }
 
+    fn texture_cache_stats(&self) -> TextureCacheStats {
+        TextureCacheStats {
+            textures: self.display.textures.len(),
+            bytes: self.display.texture_bytes(),
+            budget: GliumDisplay::texture_budget_bytes(),
+            evictions: self.display.evictions,
+        }
+    }
+
     fn draw_to_texture(&mut self, texture_id: &str, draw_list: DrawList) {
         self.create_texture_if_missing(&draw_list.texture, &draw_list);
         let texture = self.display.textures.get(texture_id).unwrap();
@@ -469,6 +545,10 @@ impl GliumDisplay {
 
         display.gl_window().window().set_cursor_visible(false);
 
+        let window_size = display.gl_window().window().inner_size();
+        Config::set_window_size(window_size.width, window_size.height);
+        Cursor::update_max();
+
         let (ui_x, ui_y) = Config::ui_size();
 
         Ok((GliumDisplay {
@@ -484,6 +564,8 @@ impl GliumDisplay {
             ],
             textures: HashMap::new(),
             scale_factor,
+            frame: 0,
+            evictions: 0,
         }, event_loop))
     }
 
@@ -519,6 +601,7 @@ impl GliumDisplay {
     }
 
     fn render_output(&mut self, root: &Widget, millis: u32) {
+        self.frame += 1;
         let mut target = self.display.draw();
         target.clear_color(0.0, 0.0, 0.0, 1.0);
         {
@@ -784,6 +867,8 @@ fn process_keyboard_input(input: KeyboardInput) -> Option<KeyboardEvent> {
         Period => KeyPeriod,
         Slash => KeySlash,
         Backslash => KeyBackslash,
+        LShift | RShift => KeyShift,
+        LAlt | RAlt => KeyAlt,
         Home => KeyHome,
         End => KeyEnd,
         Insert => KeyInsert,
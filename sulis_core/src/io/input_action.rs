@@ -16,6 +16,7 @@
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::str::FromStr;
 
 use crate::io::event::{ClickKind, Kind};
 use crate::io::{keyboard_event::Key, Event};
@@ -42,6 +43,16 @@ pub enum InputActionKind {
     ToggleCharacter,
     ToggleMap,
     ToggleJournal,
+    ToggleRecords,
+    ToggleLog,
+    ToggleDebugOverlay,
+    TogglePause,
+    DebugPause,
+    DebugStepTurn,
+    DebugSlowMotion,
+    FocusNext,
+    FocusPrevious,
+    ActivateFocused,
     ToggleFormation,
     Back,
     EndTurn,
@@ -54,7 +65,12 @@ pub enum InputActionKind {
     ZoomOut,
     QuickSave,
     SelectAll,
+    SplitParty,
+    SelectNextGroup,
     SwapWeapons,
+    QueueAction,
+    HighlightInteractables,
+    ToggleRadialMenu,
     SelectPartyMember1,
     SelectPartyMember2,
     SelectPartyMember3,
@@ -97,6 +113,16 @@ impl std::hash::Hash for InputActionKind {
     }
 }
 
+impl FromStr for InputActionKind {
+    type Err = serde_yaml::Error;
+
+    /// Parses the name of one of the keybindable actions, i.e. the variants
+    /// without data
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_yaml::from_str(s)
+    }
+}
+
 impl InputAction {
     pub fn mouse_move(x: f32, y: f32) -> InputAction {
         InputAction {
@@ -178,6 +204,18 @@ impl InputAction {
             RawKey(key) => {
                 Widget::dispatch_event(root, Event::new(Kind::RawKey(key)));
             }
+            FocusNext | FocusPrevious => {
+                if let InputActionState::Started = self.state {
+                    Widget::move_nav_focus(root, matches!(self.kind, FocusNext));
+                }
+            }
+            ActivateFocused => {
+                if let InputActionState::Started = self.state {
+                    if !Widget::activate_nav_focus(root) {
+                        Widget::dispatch_event(root, Event::new(Kind::KeyPress(self.kind)));
+                    }
+                }
+            }
             _ => {
                 let kind = match self.state {
                     InputActionState::Started => Kind::KeyPress(self.kind),
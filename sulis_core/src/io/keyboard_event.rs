@@ -49,6 +49,8 @@ pub enum Key {
     KeyPeriod,
     KeySlash,
     KeyBackslash,
+    KeyShift,
+    KeyAlt,
 
     KeyUp,
     KeyDown,
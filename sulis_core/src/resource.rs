@@ -20,6 +20,9 @@ pub use self::resource_builder_set::{
     write_json_to_file, write_to_file,
 };
 
+pub mod module_archive;
+pub use self::module_archive::{ArchiveKind, ArchiveManifest, ModuleArchive};
+
 pub mod sound_set;
 pub use self::sound_set::{SoundSetBuilder, SoundSet};
 
@@ -30,6 +33,9 @@ pub use self::spritesheet::Spritesheet;
 mod font;
 pub use self::font::Font;
 
+mod string_table;
+pub use self::string_table::StringTable;
+
 pub mod yaml_resource_set;
 pub use self::yaml_resource_set::YamlResourceKind;
 pub use self::yaml_resource_set::YamlResourceSet;
@@ -66,6 +72,7 @@ pub struct ResourceSet {
     pub(crate) spritesheets: HashMap<String, Rc<Spritesheet>>,
     pub(crate) fonts: HashMap<String, Rc<Font>>,
     pub(crate) sound_sets: HashMap<String, Rc<SoundSet>>,
+    pub(crate) strings: StringTable,
 }
 
 impl ResourceSet {
@@ -127,6 +134,13 @@ impl ResourceSet {
 
             set.themes = builder_set.theme_builder.create_theme_set()?;
 
+            set.strings = StringTable::default();
+            let mut tables: Vec<_> = builder_set.string_tables.into_iter().collect();
+            tables.sort_by(|a, b| a.0.cmp(&b.0));
+            for (_, table) in tables {
+                set.strings.merge(table);
+            }
+
             let sound_start = std::time::Instant::now();
             for (id, sounds) in builder_set.sound_set_builders {
                 insert_if_ok_boxed("sound_set", id, SoundSet::new(sounds), &mut set.sound_sets);
@@ -213,6 +227,23 @@ impl ResourceSet {
         })
     }
 
+    /// Looks up the specified display text in the loaded string tables,
+    /// returning the module override if there is one.  If the content filter
+    /// is enabled, filtered words are then replaced.
+    pub fn localize(text: &str) -> String {
+        RESOURCE_SET.with(|set| {
+            let set = set.borrow();
+            if set.strings.is_empty() {
+                return text.to_string();
+            }
+
+            match set.strings.lookup(text, Config::content_filter()) {
+                None => text.to_string(),
+                Some(text) => text,
+            }
+        })
+    }
+
     pub fn image_else_empty(id: &str) -> Rc<dyn Image> {
         RESOURCE_SET.with(|r| match get_resource(id, &r.borrow().images) {
             None => {
@@ -0,0 +1,497 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+//! Module archives bundle the files of a campaign or modification directory
+//! into a single zip file, along with a manifest identifying the module and
+//! its version.  Archives placed in the campaigns or mods directories are
+//! listed directly from the archive, and extracted to a cache in the user
+//! directory when the module is loaded.
+
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+
+use crate::config;
+use crate::util::invalid_data_error;
+
+/// The file extension for module archives
+pub const ARCHIVE_EXTENSION: &str = "zip";
+
+/// The name of the manifest file at the root of each archive
+pub const MANIFEST_FILE: &str = "sulis_manifest.yml";
+
+/// The current archive format.  Archives written with a newer format are
+/// rejected when read.
+pub const ARCHIVE_FORMAT: u32 = 1;
+
+const CACHE_DIR: &str = "module_cache";
+
+const LOCAL_HEADER_SIG: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIG: u32 = 0x0201_4b50;
+const END_OF_DIR_SIG: u32 = 0x0605_4b50;
+const END_OF_DIR_LEN: usize = 22;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+
+// general purpose flag indicating UTF-8 file names
+const FLAG_UTF8: u16 = 0x0800;
+
+// version 2.0, the minimum supporting deflate and directories
+const ZIP_VERSION: u16 = 20;
+
+// 1980-01-01 00:00, the earliest MS-DOS date
+const DOS_DATE: u16 = 0x0021;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub enum ArchiveKind {
+    Campaign,
+    Modification,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ArchiveManifest {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub kind: ArchiveKind,
+    pub format: u32,
+}
+
+struct Entry {
+    name: String,
+    method: u16,
+    crc: u32,
+    compressed_size: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// A zip archive read fully into memory
+pub struct ModuleArchive {
+    data: Vec<u8>,
+    entries: Vec<Entry>,
+}
+
+impl ModuleArchive {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<ModuleArchive, Error> {
+        let data = fs::read(path)?;
+
+        if data.len() < END_OF_DIR_LEN {
+            return invalid_data_error("Archive is too short");
+        }
+
+        // the end of directory record is followed only by a comment of up to u16::MAX bytes
+        let min_start = data.len().saturating_sub(END_OF_DIR_LEN + u16::MAX as usize);
+        let mut end = None;
+        for start in (min_start..=data.len() - END_OF_DIR_LEN).rev() {
+            if read_u32(&data, start)? == END_OF_DIR_SIG {
+                end = Some(start);
+                break;
+            }
+        }
+
+        let end = match end {
+            None => return invalid_data_error("Unable to locate archive directory"),
+            Some(end) => end,
+        };
+
+        let count = read_u16(&data, end + 10)? as usize;
+        let mut pos = read_u32(&data, end + 16)? as usize;
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if read_u32(&data, pos)? != CENTRAL_HEADER_SIG {
+                return invalid_data_error("Invalid archive directory entry");
+            }
+
+            let name_len = read_u16(&data, pos + 28)? as usize;
+            let extra_len = read_u16(&data, pos + 30)? as usize;
+            let comment_len = read_u16(&data, pos + 32)? as usize;
+            let name = match data.get(pos + 46..pos + 46 + name_len) {
+                None => return invalid_data_error("Archive entry name out of bounds"),
+                Some(name) => String::from_utf8_lossy(name).to_string(),
+            };
+
+            entries.push(Entry {
+                name,
+                method: read_u16(&data, pos + 10)?,
+                crc: read_u32(&data, pos + 16)?,
+                compressed_size: read_u32(&data, pos + 20)?,
+                size: read_u32(&data, pos + 24)?,
+                offset: read_u32(&data, pos + 42)?,
+            });
+
+            pos += 46 + name_len + extra_len + comment_len;
+        }
+
+        Ok(ModuleArchive { data, entries })
+    }
+
+    /// Returns the manifest stored in this archive, checking that the
+    /// archive format is supported and that the ID and version are usable
+    /// as a single path component
+    pub fn manifest(&self) -> Result<ArchiveManifest, Error> {
+        let manifest: ArchiveManifest = self.read_yaml(MANIFEST_FILE)?;
+        if manifest.format > ARCHIVE_FORMAT {
+            return invalid_data_error(&format!(
+                "Archive format {} is newer than the supported format {}",
+                manifest.format, ARCHIVE_FORMAT
+            ));
+        }
+
+        if !is_file_name(&manifest.id) {
+            return invalid_data_error(&format!("Invalid archive ID '{}'", manifest.id));
+        }
+
+        if !is_file_name(&manifest.version) {
+            return invalid_data_error(&format!(
+                "Invalid archive version '{}'",
+                manifest.version
+            ));
+        }
+
+        Ok(manifest)
+    }
+
+    pub fn read_yaml<T: serde::de::DeserializeOwned>(&self, name: &str) -> Result<T, Error> {
+        let data = self.read(name)?;
+        match serde_yaml::from_slice(&data) {
+            Ok(result) => Ok(result),
+            Err(e) => invalid_data_error(&format!("Error reading '{name}': {e}")),
+        }
+    }
+
+    /// Reads the contents of the file `name` in this archive
+    pub fn read(&self, name: &str) -> Result<Vec<u8>, Error> {
+        match self.entries.iter().find(|entry| entry.name == name) {
+            None => Err(Error::new(ErrorKind::NotFound, format!("No file '{name}' in archive"))),
+            Some(entry) => self.read_entry(entry),
+        }
+    }
+
+    fn read_entry(&self, entry: &Entry) -> Result<Vec<u8>, Error> {
+        let pos = entry.offset as usize;
+        if read_u32(&self.data, pos)? != LOCAL_HEADER_SIG {
+            return invalid_data_error(&format!("Invalid header for '{}'", entry.name));
+        }
+
+        let name_len = read_u16(&self.data, pos + 26)? as usize;
+        let extra_len = read_u16(&self.data, pos + 28)? as usize;
+        let start = pos + 30 + name_len + extra_len;
+        let compressed = match self.data.get(start..start + entry.compressed_size as usize) {
+            None => return invalid_data_error(&format!("'{}' is out of bounds", entry.name)),
+            Some(compressed) => compressed,
+        };
+
+        let data = match entry.method {
+            METHOD_STORED => compressed.to_vec(),
+            METHOD_DEFLATE => {
+                let mut data = Vec::with_capacity(entry.size as usize);
+                DeflateDecoder::new(compressed).read_to_end(&mut data)?;
+                data
+            }
+            method => {
+                return invalid_data_error(&format!(
+                    "Unsupported compression method {} for '{}'",
+                    method, entry.name
+                ))
+            }
+        };
+
+        let mut crc = Crc::new();
+        crc.update(&data);
+        if crc.sum() != entry.crc || data.len() != entry.size as usize {
+            return invalid_data_error(&format!("'{}' is corrupt", entry.name));
+        }
+
+        Ok(data)
+    }
+
+    /// Writes all files in this archive under `dir`
+    pub fn extract_to(&self, dir: &Path) -> Result<(), Error> {
+        for entry in self.entries.iter() {
+            let relative = Path::new(&entry.name);
+            if !is_relative_path(relative) {
+                return invalid_data_error(&format!("Invalid file path '{}'", entry.name));
+            }
+
+            let path = dir.join(relative);
+            if entry.name.ends_with('/') {
+                fs::create_dir_all(&path)?;
+                continue;
+            }
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, self.read_entry(entry)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns true if `path` has the module archive extension
+pub fn is_archive<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref();
+    path.is_file() && path.extension().is_some_and(|ext| ext == ARCHIVE_EXTENSION)
+}
+
+/// Returns all module archives directly within `dir`
+pub fn archives_in<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, Error> {
+    let mut result = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if is_archive(&path) {
+            result.push(path);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Returns true if `path` only descends from the directory it is joined to,
+/// without any root, prefix, or parent components
+fn is_relative_path(path: &Path) -> bool {
+    path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Returns true if `name` is exactly one normal path component
+fn is_file_name(name: &str) -> bool {
+    let path = Path::new(name);
+    is_relative_path(path) && path.file_name() == Some(OsStr::new(name))
+}
+
+/// Returns the directory that the resources for the archive at `path` are
+/// read from, extracting the archive into the module cache if it has not
+/// been already or if the archive is newer than the cached copy
+pub fn extracted_dir<P: AsRef<Path>>(path: P) -> Result<PathBuf, Error> {
+    let path = path.as_ref();
+    let archive = ModuleArchive::open(path)?;
+    let manifest = archive.manifest()?;
+
+    let mut cache_dir = config::USER_DIR.clone();
+    cache_dir.push(CACHE_DIR);
+    let dir = cache_dir.join(format!("{}-{}", manifest.id, manifest.version));
+    if !dir.starts_with(&cache_dir) {
+        return invalid_data_error(&format!("Invalid module cache dir {dir:?}"));
+    }
+
+    let cached_manifest = dir.join(MANIFEST_FILE);
+    let is_current = match (fs::metadata(path), fs::metadata(&cached_manifest)) {
+        (Ok(archive), Ok(cached)) => match (archive.modified(), cached.modified()) {
+            (Ok(archive), Ok(cached)) => cached >= archive,
+            _ => false,
+        },
+        _ => false,
+    };
+
+    if !is_current {
+        info!("Extracting module archive {:?} to {:?}", path, dir);
+        if dir.is_dir() {
+            fs::remove_dir_all(&dir)?;
+        }
+        archive.extract_to(&dir)?;
+    }
+
+    Ok(dir)
+}
+
+/// Writes all files under `dir` to a new archive at `out`, along with the
+/// specified `manifest`
+pub fn write_archive(dir: &Path, out: &Path, manifest: &ArchiveManifest) -> Result<(), Error> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+
+    let manifest_data = match serde_yaml::to_string(manifest) {
+        Err(e) => return invalid_data_error(&format!("{e}")),
+        Ok(data) => data.into_bytes(),
+    };
+
+    let mut writer = ArchiveWriter::default();
+    writer.add(MANIFEST_FILE, &manifest_data)?;
+    for (name, path) in files {
+        if name == MANIFEST_FILE {
+            continue;
+        }
+        writer.add(&name, &fs::read(path)?)?;
+    }
+
+    let mut file = File::create(out)?;
+    file.write_all(&writer.finish()?)?;
+    Ok(())
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<(String, PathBuf)>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, out)?;
+            continue;
+        }
+
+        let relative = match path.strip_prefix(root) {
+            Err(_) => continue,
+            Ok(relative) => relative,
+        };
+
+        let name: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        out.push((name.join("/"), path));
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct ArchiveWriter {
+    data: Vec<u8>,
+    directory: Vec<u8>,
+    count: u16,
+}
+
+impl ArchiveWriter {
+    fn add(&mut self, name: &str, contents: &[u8]) -> Result<(), Error> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(contents)?;
+        let compressed = encoder.finish()?;
+
+        let mut crc = Crc::new();
+        crc.update(contents);
+
+        let offset = to_u32(self.data.len())?;
+        let compressed_size = to_u32(compressed.len())?;
+        let size = to_u32(contents.len())?;
+        let name_len = match u16::try_from(name.len()) {
+            Err(_) => return invalid_data_error(&format!("File name '{name}' is too long")),
+            Ok(len) => len,
+        };
+
+        let d = &mut self.data;
+        put_u32(d, LOCAL_HEADER_SIG);
+        put_u16(d, ZIP_VERSION);
+        put_u16(d, FLAG_UTF8);
+        put_u16(d, METHOD_DEFLATE);
+        put_u16(d, 0);
+        put_u16(d, DOS_DATE);
+        put_u32(d, crc.sum());
+        put_u32(d, compressed_size);
+        put_u32(d, size);
+        put_u16(d, name_len);
+        put_u16(d, 0);
+        d.extend_from_slice(name.as_bytes());
+        d.extend_from_slice(&compressed);
+
+        let d = &mut self.directory;
+        put_u32(d, CENTRAL_HEADER_SIG);
+        put_u16(d, ZIP_VERSION);
+        put_u16(d, ZIP_VERSION);
+        put_u16(d, FLAG_UTF8);
+        put_u16(d, METHOD_DEFLATE);
+        put_u16(d, 0);
+        put_u16(d, DOS_DATE);
+        put_u32(d, crc.sum());
+        put_u32(d, compressed_size);
+        put_u32(d, size);
+        put_u16(d, name_len);
+        put_u16(d, 0); // extra length
+        put_u16(d, 0); // comment length
+        put_u16(d, 0); // disk number
+        put_u16(d, 0); // internal attributes
+        put_u32(d, 0); // external attributes
+        put_u32(d, offset);
+        d.extend_from_slice(name.as_bytes());
+
+        self.count = match self.count.checked_add(1) {
+            None => return invalid_data_error("Too many files for archive"),
+            Some(count) => count,
+        };
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<u8>, Error> {
+        let offset = to_u32(self.data.len())?;
+        let size = to_u32(self.directory.len())?;
+        self.data.append(&mut self.directory);
+
+        let d = &mut self.data;
+        put_u32(d, END_OF_DIR_SIG);
+        put_u16(d, 0);
+        put_u16(d, 0);
+        put_u16(d, self.count);
+        put_u16(d, self.count);
+        put_u32(d, size);
+        put_u32(d, offset);
+        put_u16(d, 0);
+
+        Ok(self.data)
+    }
+}
+
+fn to_u32(len: usize) -> Result<u32, Error> {
+    match u32::try_from(len) {
+        Err(_) => invalid_data_error("Archive is too large"),
+        Ok(len) => Ok(len),
+    }
+}
+
+fn put_u16(data: &mut Vec<u8>, value: u16) {
+    data.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&value.to_le_bytes());
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Result<u16, Error> {
+    match data.get(pos..pos + 2) {
+        None => invalid_data_error("Unexpected end of archive"),
+        Some(bytes) => Ok(u16::from_le_bytes([bytes[0], bytes[1]])),
+    }
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Result<u32, Error> {
+    match data.get(pos..pos + 4) {
+        None => invalid_data_error("Unexpected end of archive"),
+        Some(bytes) => Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_names_are_single_components() {
+        assert!(is_file_name("twin_expanse"));
+        assert!(is_file_name("1.0.2"));
+
+        for name in ["", ".", "..", "a/b", "/a", "../a", "a/..", "a/", "/../../Documents"] {
+            assert!(!is_file_name(name), "{name}");
+        }
+    }
+}
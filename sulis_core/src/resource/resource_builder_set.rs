@@ -41,6 +41,7 @@ pub struct ResourceBuilderSet {
     pub spritesheet_builders: HashMap<String, SpritesheetBuilder>,
     pub font_builders: HashMap<String, FontBuilder>,
     pub sound_set_builders: HashMap<String, SoundSetBuilder>,
+    pub string_tables: HashMap<String, StringTable>,
 }
 
 impl ResourceBuilderSet {
@@ -71,6 +72,7 @@ impl ResourceBuilderSet {
             animated_builders: read_builders(resources, AnimatedImage)?,
             spritesheet_builders: read_builders_insert_dirs(resources, Spritesheet)?,
             sound_set_builders: read_builders_insert_dirs(resources, SoundSet)?,
+            string_tables: read_builders(resources, Strings)?,
         })
     }
 }
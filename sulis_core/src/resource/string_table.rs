@@ -0,0 +1,115 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+use std::collections::HashMap;
+
+/// A table of text replacements applied to displayed text.  `overrides`
+/// replaces an entire string, allowing a module to rename items, abilities,
+/// and other content without touching the base data files.  `filter`
+/// replaces individual words and is only applied when the content filter
+/// is enabled in the config.
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct StringTable {
+    pub id: String,
+
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+
+    #[serde(default)]
+    pub filter: HashMap<String, String>,
+}
+
+impl StringTable {
+    pub(crate) fn merge(&mut self, other: StringTable) {
+        self.overrides.extend(other.overrides);
+        self.filter.extend(other.filter);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty() && self.filter.is_empty()
+    }
+
+    /// Returns the override for the specified text if there is one, and then
+    /// applies the word filter if `apply_filter` is true.  Returns `None`
+    /// if no changes were made.
+    pub fn lookup(&self, text: &str, apply_filter: bool) -> Option<String> {
+        let overridden = self.overrides.get(text);
+        let text = overridden.map_or(text, String::as_str);
+
+        if !apply_filter || self.filter.is_empty() {
+            return overridden.cloned();
+        }
+
+        let mut out = String::with_capacity(text.len());
+        let mut changed = overridden.is_some();
+        let mut word_start = None;
+        for (index, c) in text.char_indices() {
+            if is_word_char(c) {
+                if word_start.is_none() {
+                    word_start = Some(index);
+                }
+                continue;
+            }
+
+            if let Some(start) = word_start.take() {
+                changed |= self.push_filtered(&text[start..index], &mut out);
+            }
+            out.push(c);
+        }
+
+        if let Some(start) = word_start {
+            changed |= self.push_filtered(&text[start..], &mut out);
+        }
+
+        if changed {
+            Some(out)
+        } else {
+            None
+        }
+    }
+
+    fn push_filtered(&self, word: &str, out: &mut String) -> bool {
+        if let Some(replacement) = self.filter.get(word) {
+            out.push_str(replacement);
+            return true;
+        }
+
+        let replacement = match self.filter.get(&word.to_lowercase()) {
+            None => {
+                out.push_str(word);
+                return false;
+            }
+            Some(replacement) => replacement,
+        };
+
+        // keep the capitalization of the first letter, i.e. at sentence start
+        let mut chars = replacement.chars();
+        match chars.next() {
+            Some(first) if word.starts_with(char::is_uppercase) => {
+                out.extend(first.to_uppercase());
+                out.push_str(chars.as_str());
+            }
+            _ => out.push_str(replacement),
+        }
+        true
+    }
+}
+
+// underscores and slashes are treated as part of a word so that resource
+// ids passed as text args, such as image names, are never filtered
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '/' || c == '\''
+}
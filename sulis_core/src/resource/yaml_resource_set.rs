@@ -43,19 +43,24 @@ pub enum YamlResourceKind {
     WindowImage,
     Spritesheet,
     SoundSet,
+    Strings,
 
     Ability,
     AbilityList,
+    Achievement,
     Actor,
     AiTemplate,
     Area,
+    Banter,
     Class,
     Conversation,
     Cutscene,
     Encounter,
     Item,
     ItemAdjective,
+    ItemSet,
     LootList,
+    LoreEntry,
     Prop,
     Quest,
     Race,
@@ -93,19 +98,24 @@ impl YamlResourceKind {
             "images/window" | "images\\window" => WindowImage,
             "spritesheets" => Spritesheet,
             "sounds" => SoundSet,
+            "strings" => Strings,
 
             "abilities" => Ability,
             "ability_lists" => AbilityList,
+            "achievements" => Achievement,
             "actors" => Actor,
             "ai" => AiTemplate,
             "areas" => Area,
+            "banters" => Banter,
             "classes" => Class,
             "conversations" => Conversation,
             "cutscenes" => Cutscene,
             "encounters" => Encounter,
             "items" => Item,
             "item_adjectives" => ItemAdjective,
+            "item_sets" => ItemSet,
             "loot_lists" => LootList,
+            "lore" => LoreEntry,
             "props" => Prop,
             "quests" => Quest,
             "races" => Race,
@@ -41,6 +41,9 @@ pub use self::color::Color;
 mod cursor;
 pub use self::cursor::Cursor;
 
+mod harness;
+pub use self::harness::UiHarness;
+
 mod font_renderer;
 pub use self::font_renderer::FontRenderer;
 pub use self::font_renderer::LineRenderer;
@@ -48,6 +51,9 @@ pub use self::font_renderer::LineRenderer;
 mod layout_kind;
 pub use self::layout_kind::LayoutKind;
 
+mod narrator;
+pub use self::narrator::Narrator;
+
 pub mod theme;
 pub use self::theme::{Theme, ThemeSet};
 
@@ -67,6 +73,7 @@ pub use self::widget_state::WidgetState;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::config::Config;
 use crate::util::{Point, Size};
 
 pub fn create_ui_tree(kind: Rc<RefCell<dyn WidgetKind>>) -> Rc<RefCell<Widget>> {
@@ -77,15 +84,29 @@ pub fn create_ui_tree(kind: Rc<RefCell<dyn WidgetKind>>) -> Rc<RefCell<Widget>>
 }
 
 const SCALE_Y_BASE: f32 = 3200.0;
-const SCALE_X_BASE: f32 = SCALE_Y_BASE * 16.0 / 9.0;
 
 pub fn compute_area_scaling(pixel_size: Point) -> (f32, f32) {
-    let scale_x = SCALE_X_BASE / (pixel_size.x as f32);
+    // keep tiles square regardless of the UI aspect ratio
+    let (ui_x, ui_y) = Config::ui_size();
+    let scale_x_base = SCALE_Y_BASE * ui_x as f32 / ui_y as f32;
+
+    let scale_x = scale_x_base / (pixel_size.x as f32);
     let scale_y = SCALE_Y_BASE / (pixel_size.y as f32);
 
     (scale_x, scale_y)
 }
 
+/// Returns the position and size of the UI safe area, which docked widgets
+/// are placed within.  This is the full height of the UI, and is centered
+/// and limited in width to the configured `safe_area_max_aspect`.
+pub fn safe_area() -> (Point, Size) {
+    let (ui_x, ui_y) = Config::ui_size();
+    let max_width = (ui_y as f32 * Config::safe_area_max_aspect()).round() as i32;
+    let width = std::cmp::min(ui_x, max_width);
+
+    (Point::new((ui_x - width) / 2, 0), Size::new(width, ui_y))
+}
+
 #[derive(Copy, Clone)]
 pub struct Scrollable {
     x: f32,
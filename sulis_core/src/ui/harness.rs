@@ -0,0 +1,250 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::io::Error;
+use std::rc::Rc;
+
+use crate::io::event::{ClickKind, Kind};
+use crate::io::{keyboard_event::Key, Event, InputActionKind};
+use crate::ui::{create_ui_tree, Cursor, Widget, WidgetKind};
+
+/// Drives a widget tree without a display.  The tree is created and laid out
+/// exactly as it would be in game, and synthetic mouse and keyboard input is
+/// fed through the same dispatch paths used by the IO backend.  Resources and
+/// `Config` must be initialized beforehand; themes that are not loaded fall
+/// back to the default theme, so widget sizes may be zero in that case.
+///
+/// Widgets are found by path, a `.` separated list of theme subnames.  Each
+/// segment is searched for among all descendants of the previous match, so
+/// intermediate containers may be omitted, i.e. `"options_window.apply"`.
+pub struct UiHarness {
+    root: Rc<RefCell<Widget>>,
+    millis: u32,
+}
+
+impl UiHarness {
+    /// Creates a new UI tree with the specified root kind and performs the
+    /// initial setup and layout.
+    pub fn new(kind: Rc<RefCell<dyn WidgetKind>>) -> Result<UiHarness, Error> {
+        let root = create_ui_tree(kind);
+        let mut harness = UiHarness { root, millis: 0 };
+        harness.update(0)?;
+        Ok(harness)
+    }
+
+    pub fn root(&self) -> &Rc<RefCell<Widget>> {
+        &self.root
+    }
+
+    /// Total time in millis the harness has been advanced via `update`
+    pub fn millis(&self) -> u32 {
+        self.millis
+    }
+
+    /// Advances the tree by the specified time, running widget updates,
+    /// removals, and layout.
+    pub fn update(&mut self, millis: u32) -> Result<(), Error> {
+        self.millis += millis;
+        Widget::update(&self.root, millis)
+    }
+
+    /// Finds the widget at the specified path, if it exists
+    pub fn find(&self, path: &str) -> Option<Rc<RefCell<Widget>>> {
+        let mut cur = Rc::clone(&self.root);
+        for name in path.split('.') {
+            cur = find_descendant(&cur, name)?;
+        }
+        Some(cur)
+    }
+
+    /// Finds the widget at the specified path, panicking if it does not exist
+    pub fn get(&self, path: &str) -> Rc<RefCell<Widget>> {
+        match self.find(path) {
+            None => panic!("No widget found at '{}'", path),
+            Some(widget) => widget,
+        }
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        self.find(path).is_some()
+    }
+
+    pub fn has_modal(&self) -> bool {
+        self.root.borrow().has_modal()
+    }
+
+    pub fn is_visible(&self, path: &str) -> bool {
+        self.get(path).borrow().state.is_visible()
+    }
+
+    pub fn is_enabled(&self, path: &str) -> bool {
+        self.get(path).borrow().state.is_enabled()
+    }
+
+    pub fn is_active(&self, path: &str) -> bool {
+        self.get(path).borrow().state.is_active()
+    }
+
+    pub fn text(&self, path: &str) -> String {
+        self.get(path).borrow().state.text.clone()
+    }
+
+    /// Moves the cursor to the specified position in UI coordinates
+    pub fn move_cursor(&mut self, x: f32, y: f32) {
+        Cursor::move_to(&self.root, x, y);
+    }
+
+    /// Presses and releases the specified mouse button at the specified
+    /// position, then updates the tree so callbacks take effect.
+    pub fn click_at(&mut self, x: f32, y: f32, kind: ClickKind) -> Result<(), Error> {
+        self.move_cursor(x, y);
+        Cursor::press(&self.root, kind);
+        Cursor::release(&self.root, kind);
+        self.update(0)
+    }
+
+    /// Left clicks the center of the widget at the specified path.  Note that
+    /// the click is dispatched by position, so a modal or overlapping widget
+    /// will receive it instead, as it would in game.
+    pub fn click(&mut self, path: &str) -> Result<(), Error> {
+        let (x, y) = {
+            let widget = self.get(path);
+            let widget = widget.borrow();
+            let pos = widget.state.position();
+            let size = widget.state.size();
+            (
+                pos.x as f32 + size.width as f32 / 2.0,
+                pos.y as f32 + size.height as f32 / 2.0,
+            )
+        };
+        self.click_at(x, y, ClickKind::Primary)
+    }
+
+    /// Presses and releases the specified input action
+    pub fn key_press(&mut self, action: InputActionKind) -> Result<(), Error> {
+        Widget::dispatch_event(&self.root, Event::new(Kind::KeyPress(action)));
+        Widget::dispatch_event(&self.root, Event::new(Kind::KeyRelease(action)));
+        self.update(0)
+    }
+
+    /// Sends the specified key as a raw key event, as used by key binding and
+    /// text entry widgets.
+    pub fn raw_key(&mut self, key: Key) -> Result<(), Error> {
+        Widget::dispatch_event(&self.root, Event::new(Kind::RawKey(key)));
+        self.update(0)
+    }
+
+    /// Types each character of the specified text
+    pub fn type_text(&mut self, text: &str) -> Result<(), Error> {
+        for c in text.chars() {
+            Widget::dispatch_event(&self.root, Event::new(Kind::CharTyped(c)));
+        }
+        self.update(0)
+    }
+
+    pub fn assert_exists(&self, path: &str) {
+        assert!(self.exists(path), "Expected widget '{}' to exist", path);
+    }
+
+    pub fn assert_not_exists(&self, path: &str) {
+        assert!(!self.exists(path), "Expected widget '{}' to not exist", path);
+    }
+
+    pub fn assert_visible(&self, path: &str, visible: bool) {
+        assert_eq!(self.is_visible(path), visible, "Visibility of '{}'", path);
+    }
+
+    pub fn assert_enabled(&self, path: &str, enabled: bool) {
+        assert_eq!(self.is_enabled(path), enabled, "Enabled state of '{}'", path);
+    }
+
+    pub fn assert_active(&self, path: &str, active: bool) {
+        assert_eq!(self.is_active(path), active, "Active state of '{}'", path);
+    }
+
+    pub fn assert_modal(&self, modal: bool) {
+        assert_eq!(self.has_modal(), modal, "Modal state of root");
+    }
+
+    pub fn assert_text(&self, path: &str, text: &str) {
+        assert_eq!(self.text(path), text, "Text of '{}'", path);
+    }
+}
+
+fn find_descendant(parent: &Rc<RefCell<Widget>>, name: &str) -> Option<Rc<RefCell<Widget>>> {
+    let parent = parent.borrow();
+    for child in parent.children.iter() {
+        if child.borrow().theme_subname == name {
+            return Some(Rc::clone(child));
+        }
+    }
+
+    for child in parent.children.iter() {
+        if let Some(widget) = find_descendant(child, name) {
+            return Some(widget);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::resource::ResourceSet;
+    use crate::widgets::Label;
+
+    struct Counter {
+        presses: u32,
+    }
+
+    impl WidgetKind for Counter {
+        widget_kind!("counter");
+
+        fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+            let label = Widget::with_theme(Label::empty(), "presses");
+            let presses = self.presses.to_string();
+            label.borrow_mut().state.add_text_arg("presses", &presses);
+            vec![label]
+        }
+
+        fn on_key_press(&mut self, widget: &Rc<RefCell<Widget>>, _key: InputActionKind) -> bool {
+            self.presses += 1;
+            widget.borrow_mut().invalidate_children();
+            true
+        }
+    }
+
+    #[test]
+    fn key_press_rebuilds_children() {
+        Config::enable_safe_mode();
+        ResourceSet::load_resources(vec!["../data".to_string()]).unwrap();
+        let counter = Rc::new(RefCell::new(Counter { presses: 0 }));
+        let mut harness = UiHarness::new(counter.clone()).unwrap();
+        harness.assert_exists("presses");
+        harness.assert_not_exists("missing");
+
+        harness.key_press(InputActionKind::EndTurn).unwrap();
+        harness.key_press(InputActionKind::EndTurn).unwrap();
+
+        assert_eq!(counter.borrow().presses, 2);
+        let label = harness.get("presses");
+        assert_eq!(label.borrow().state.get_text_arg("presses"), Some("2"));
+    }
+}
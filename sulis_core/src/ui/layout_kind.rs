@@ -18,8 +18,8 @@ use std::cmp;
 use std::rc::Rc;
 
 use crate::config::Config;
-use crate::ui::theme::Theme;
-use crate::ui::{Cursor, Size, Widget};
+use crate::ui::theme::{Dock, Theme};
+use crate::ui::{safe_area, Cursor, Size, Widget};
 
 #[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LayoutKind {
@@ -141,6 +141,11 @@ impl LayoutKind {
     }
 
     fn child_layout_normal(widget: &Widget, child: &mut Widget) {
+        if let Some(dock) = child.theme.dock {
+            LayoutKind::child_layout_docked(child, dock);
+            return;
+        }
+
         let theme = Rc::clone(&child.theme);
         let size = Size::new(
             LayoutKind::width_recursive(child, widget.state.inner_width()),
@@ -170,6 +175,40 @@ impl LayoutKind {
             .set_position(x + theme.position.x, y + theme.position.y);
     }
 
+    fn child_layout_docked(child: &mut Widget, dock: Dock) {
+        let theme = Rc::clone(&child.theme);
+        let (pos, area) = safe_area();
+        let size = Size::new(
+            LayoutKind::width_recursive(child, area.width),
+            LayoutKind::height_recursive(child, area.height),
+        );
+
+        child.state.set_size(size);
+
+        use crate::ui::theme::Dock::*;
+        let left = pos.x;
+        let center_x = pos.x + (area.width - size.width) / 2;
+        let right = pos.x + area.width - size.width;
+        let top = pos.y;
+        let center_y = pos.y + (area.height - size.height) / 2;
+        let bottom = pos.y + area.height - size.height;
+        let (x, y) = match dock {
+            Left => (left, center_y),
+            Right => (right, center_y),
+            Top => (center_x, top),
+            Bottom => (center_x, bottom),
+            TopLeft => (left, top),
+            TopRight => (right, top),
+            BottomLeft => (left, bottom),
+            BottomRight => (right, bottom),
+            Center => (center_x, center_y),
+        };
+
+        child
+            .state
+            .set_position(x + theme.position.x, y + theme.position.y);
+    }
+
     fn height_recursive(widget: &Widget, parent_inner_height: i32) -> i32 {
         let theme = &widget.theme;
         let mut height = theme.size.height;
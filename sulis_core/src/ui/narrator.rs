@@ -0,0 +1,153 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::config::Config;
+use crate::ui::{theme, Widget};
+
+const MAX_LINES: usize = 50;
+
+/// Accessibility readout.  When the screen reader is enabled in the config,
+/// textual descriptions of focused widgets and game events are collected here
+/// so they can be shown in a readout pane and, with the `tts` feature, spoken
+/// aloud using the operating system's speech synthesizer.
+pub struct Narrator {
+    lines: VecDeque<String>,
+    generation: u64,
+}
+
+thread_local! {
+    static NARRATOR: RefCell<Narrator> = const {
+        RefCell::new(Narrator {
+            lines: VecDeque::new(),
+            generation: 0,
+        })
+    };
+}
+
+impl Narrator {
+    pub fn is_enabled() -> bool {
+        Config::accessibility().screen_reader
+    }
+
+    /// Adds the specified description to the readout.  Does nothing if the
+    /// screen reader is not enabled.
+    pub fn say(text: &str) {
+        if !Narrator::is_enabled() {
+            return;
+        }
+
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        info!(target: "narrator", "{}", text);
+
+        NARRATOR.with(|narrator| {
+            let mut narrator = narrator.borrow_mut();
+            if narrator.lines.len() == MAX_LINES {
+                narrator.lines.pop_front();
+            }
+            narrator.lines.push_back(text.to_string());
+            narrator.generation += 1;
+        });
+
+        if Config::accessibility().speech {
+            speak(text);
+        }
+    }
+
+    /// Returns a counter that is incremented each time a new line is added,
+    /// allowing readout widgets to check whether they need to update
+    pub fn generation() -> u64 {
+        NARRATOR.with(|narrator| narrator.borrow().generation)
+    }
+
+    /// Returns up to `count` of the most recent lines, oldest first
+    pub fn recent_lines(count: usize) -> Vec<String> {
+        NARRATOR.with(|narrator| {
+            let narrator = narrator.borrow();
+            let skip = narrator.lines.len().saturating_sub(count);
+            narrator.lines.iter().skip(skip).cloned().collect()
+        })
+    }
+
+    pub fn clear() {
+        NARRATOR.with(|narrator| {
+            let mut narrator = narrator.borrow_mut();
+            narrator.lines.clear();
+            narrator.generation += 1;
+        });
+    }
+
+    /// Computes a textual description of the specified widget.  The widget's
+    /// text is used if present, followed by a theme `description` or `tooltip`,
+    /// and finally the widget's theme name.
+    pub fn describe(widget: &Widget) -> String {
+        let mut desc = widget.state.text.trim().to_string();
+
+        if desc.is_empty() {
+            let custom = &widget.theme.custom;
+            if let Some(text) = custom.get("description").or_else(|| custom.get("tooltip")) {
+                desc = theme::expand_text_args(text, &widget.state);
+            }
+        }
+
+        if desc.is_empty() {
+            desc = widget.theme_subname.replace('_', " ");
+        }
+
+        if widget.state.is_active() {
+            desc.push_str(", selected");
+        }
+
+        desc
+    }
+}
+
+#[cfg(feature = "tts")]
+fn speak(text: &str) {
+    use std::process::{Command, Stdio};
+
+    let mut cmd = if cfg!(target_os = "windows") {
+        let script = format!(
+            "Add-Type -AssemblyName System.Speech; \
+             (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak('{}')",
+            text.replace('\'', "''")
+        );
+        let mut cmd = Command::new("powershell");
+        cmd.args(["-NoProfile", "-Command", &script]);
+        cmd
+    } else if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("say");
+        cmd.arg(text);
+        cmd
+    } else {
+        let mut cmd = Command::new("spd-say");
+        cmd.args(["--cancel", text]);
+        cmd
+    };
+
+    cmd.stdout(Stdio::null()).stderr(Stdio::null());
+    if let Err(e) = cmd.spawn() {
+        warn!("Unable to start speech synthesis: {}", e);
+    }
+}
+
+#[cfg(not(feature = "tts"))]
+fn speak(_text: &str) {}
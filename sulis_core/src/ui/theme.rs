@@ -65,6 +65,24 @@ pub enum PositionRelative {
     Mouse,
 }
 
+/// Places a widget against an edge, corner, or the center of the UI safe
+/// area rather than within its parent, so that HUD elements stay in view
+/// at any aspect ratio.  `Max` sizes of docked widgets are relative to the
+/// safe area, and the theme `position` is applied as an offset.
+#[derive(Deserialize, Debug, Clone, Copy, Eq, Hash, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub enum Dock {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Relative {
     pub x: PositionRelative,
@@ -127,6 +145,7 @@ pub struct Theme {
     pub size: Size,
     pub position: Point,
     pub relative: Relative,
+    pub dock: Option<Dock>,
 
     pub text: Option<String>,
     pub text_params: TextParams,
@@ -149,6 +168,7 @@ impl Default for Theme {
             size: Size::default(),
             position: Point::default(),
             relative: Relative::default(),
+            dock: None,
             text: None,
             text_params: TextParams::default(),
             background: None,
@@ -139,6 +139,7 @@ pub struct ThemeBuilder {
     size: Option<Size>,
     position: Option<Point>,
     relative: Option<RelativeBuilder>,
+    dock: Option<Dock>,
 
     text: Option<String>,
     text_params: Option<TextParamsBuilder>,
@@ -170,6 +171,7 @@ impl ThemeBuilder {
                 None => Relative::default(),
                 Some(rb) => rb.build(),
             },
+            dock: self.dock,
             text: self.text,
             text_params: match self.text_params {
                 None => TextParams::default(),
@@ -356,6 +358,7 @@ impl ThemeBuilderSet {
         to.border = to.border.or(from.border);
         to.size = to.size.or(from.size);
         to.position = to.position.or(from.position);
+        to.dock = to.dock.or(from.dock);
         to.kind = to.kind.or(from.kind);
 
         if to.text.is_none() {
@@ -22,7 +22,8 @@ use std::rc::Rc;
 use crate::config::Config;
 use crate::io::{event, Event, GraphicsRenderer};
 use crate::resource::ResourceSet;
-use crate::ui::{theme, Cursor, EmptyWidget, Theme, WidgetKind, WidgetState};
+use crate::ui::{animation_state, theme, Cursor, EmptyWidget, Narrator, Theme, WidgetKind,
+    WidgetState};
 use crate::util::{Point, Rect, Size};
 use crate::widgets::Label;
 
@@ -37,6 +38,7 @@ pub struct Widget {
     mouse_drag_child: Option<Rc<RefCell<Widget>>>,
     modal_child: Option<Rc<RefCell<Widget>>>,
     pub(crate) keyboard_focus_child: Option<Rc<RefCell<Widget>>>,
+    nav_focus_child: Option<Rc<RefCell<Widget>>>,
     parent: Option<Rc<RefCell<Widget>>>,
 
     marked_for_removal: bool,
@@ -199,6 +201,7 @@ impl Widget {
             modal_child: None,
             mouse_drag_child: None,
             keyboard_focus_child: None,
+            nav_focus_child: None,
             parent: None,
             marked_for_layout: true,
             theme: ResourceSet::default_theme(),
@@ -426,6 +429,95 @@ impl Widget {
         root.keyboard_focus_child = None;
     }
 
+    /// Moves navigation focus to the next navigable widget, or the previous one
+    /// if `forward` is false.  Only widgets within the current modal widget are
+    /// considered when a modal is present.  The newly focused widget is described
+    /// to the `Narrator`
+    pub fn move_nav_focus(root: &Rc<RefCell<Widget>>, forward: bool) {
+        let root = Widget::get_root(root);
+        let candidates = Widget::navigable_widgets(&root);
+
+        let old = root.borrow_mut().nav_focus_child.take();
+        if let Some(ref old) = old {
+            old.borrow_mut().state.animation_state.remove(animation_state::Kind::Hover);
+        }
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        let len = candidates.len();
+        let index = match old.and_then(|old| candidates.iter().position(|c| Rc::ptr_eq(c, &old))) {
+            None if forward => 0,
+            None => len - 1,
+            Some(index) if forward => (index + 1) % len,
+            Some(index) => (index + len - 1) % len,
+        };
+
+        let focus = Rc::clone(&candidates[index]);
+        focus.borrow_mut().state.animation_state.add(animation_state::Kind::Hover);
+        trace!("Navigation focus to {}", focus.borrow().theme_id);
+        let desc = Narrator::describe(&focus.borrow());
+        Narrator::say(&format!("{}, {} of {}", desc, index + 1, len));
+        root.borrow_mut().nav_focus_child = Some(focus);
+    }
+
+    /// Fires the callback of the widget with navigation focus, if there is one.
+    /// Returns true if a widget was activated, false otherwise
+    pub fn activate_nav_focus(root: &Rc<RefCell<Widget>>) -> bool {
+        let root = Widget::get_root(root);
+        let focus = match root.borrow().nav_focus_child {
+            None => return false,
+            Some(ref focus) => Rc::clone(focus),
+        };
+
+        // the focused widget may have been removed or disabled since gaining focus
+        let candidates = Widget::navigable_widgets(&root);
+        if !candidates.iter().any(|c| Rc::ptr_eq(c, &focus)) {
+            root.borrow_mut().nav_focus_child = None;
+            return false;
+        }
+
+        trace!("Activating navigation focus {}", focus.borrow().theme_id);
+        let kind = Rc::clone(&focus.borrow().kind);
+        Widget::fire_callback(&focus, &mut *kind.borrow_mut());
+        true
+    }
+
+    fn navigable_widgets(root: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let start = match root.borrow().modal_child {
+            None => Rc::clone(root),
+            Some(ref modal) => Rc::clone(modal),
+        };
+
+        let mut candidates = Vec::new();
+        Widget::find_navigable(&start, &mut candidates);
+        candidates
+    }
+
+    fn find_navigable(widget: &Rc<RefCell<Widget>>, out: &mut Vec<Rc<RefCell<Widget>>>) {
+        let widget_ref = widget.borrow();
+        if !widget_ref.state.visible
+            || widget_ref.state.is_mouse_over
+            || widget_ref.marked_for_removal
+        {
+            return;
+        }
+
+        let navigable = match widget_ref.kind.try_borrow() {
+            Err(_) => false,
+            Ok(kind) => kind.is_navigable(),
+        };
+
+        if navigable && widget_ref.state.is_enabled() {
+            out.push(Rc::clone(widget));
+        }
+
+        for child in widget_ref.children.iter() {
+            Widget::find_navigable(child, out);
+        }
+    }
+
     pub fn fire_callback(widget: &Rc<RefCell<Widget>>, kind: &mut dyn WidgetKind) {
         let cb = match widget.borrow().state.callback {
             None => return,
@@ -109,6 +109,13 @@ pub trait WidgetKind {
         false
     }
 
+    /// Whether this Widget kind can receive navigation focus via the `FocusNext` and
+    /// `FocusPrevious` actions.  Navigable widgets are activated with `ActivateFocused`,
+    /// which fires the widget's callback as if it had been clicked.
+    fn is_navigable(&self) -> bool {
+        false
+    }
+
     /// This method is called before this WidgetKind is added to its parent widget.
     /// It returns a vector of 'Widget's that will be added as children to the
     /// parent widget.  If you implement this but do not need to add any children,
@@ -22,7 +22,7 @@ use crate::ui::theme::TextParams;
 use crate::ui::{animation_state, AnimationState, Border, Callback, FontRenderer, Size};
 
 use crate::image::Image;
-use crate::resource::Font;
+use crate::resource::{Font, ResourceSet};
 use crate::util::Point;
 
 pub struct WidgetState {
@@ -197,6 +197,14 @@ impl WidgetState {
         self.text_args.insert(id.to_string(), param.to_string());
     }
 
+    /// Adds a text argument as with `add_text_arg`, first passing it through
+    /// `ResourceSet::localize` to apply any string overrides and the content
+    /// filter.  Use this for displayed module text, such as names and
+    /// descriptions, rather than numbers or IDs
+    pub fn add_display_text_arg(&mut self, id: &str, text: &str) {
+        self.text_args.insert(id.to_string(), ResourceSet::localize(text));
+    }
+
     /// clears all current text params, see `add_text_param`
     pub fn clear_text_args(&mut self) {
         self.text_args.clear();
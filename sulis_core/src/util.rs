@@ -14,6 +14,13 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
+mod crash_report;
+
+pub mod hex;
+
+pub mod log_buffer;
+pub use self::log_buffer::LogEntry;
+
 mod point;
 pub use self::point::{Offset, Point, Rect, Scale};
 
@@ -36,7 +43,7 @@ use rand::{self, distributions::uniform::{SampleUniform}, seq::SliceRandom, Rng}
 use rand_pcg::Pcg64Mcg;
 
 use crate::config::{self, Config};
-use crate::resource::write_to_file;
+use crate::resource::{module_archive, write_to_file};
 
 const MAX_ULPS: i32 = 100;
 const MAX_DIFF: f32 = 2.0 * std::f32::EPSILON;
@@ -165,17 +172,33 @@ impl ActiveResources {
         let mut dirs = vec![Config::resources_config().directory];
 
         if let Some(ref dir) = self.campaign {
-            dirs.push(dir.to_string());
+            dirs.push(resource_dir(dir));
         }
 
         for mod_dir in self.mods.iter() {
-            dirs.push(mod_dir.to_string());
+            dirs.push(resource_dir(mod_dir));
         }
 
         dirs
     }
 }
 
+/// Returns the directory to read resources from for the campaign or mod at
+/// `path`, which may be either a directory or a module archive
+fn resource_dir(path: &str) -> String {
+    if !module_archive::is_archive(path) {
+        return path.to_string();
+    }
+
+    match module_archive::extracted_dir(path) {
+        Ok(dir) => dir.to_string_lossy().to_string(),
+        Err(e) => {
+            warn!("Unable to extract module archive '{}': {}", path, e);
+            path.to_string()
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(deny_unknown_fields, untagged)]
 pub enum ExtInt {
@@ -379,10 +402,11 @@ pub fn setup_logger() -> LoggerHandle {
     };
 
     let logger = Logger::with(log_builder.finalize())
-        .log_to_file(
+        .log_to_file_and_writer(
             FileSpec::default()
             .directory(log_dir)
-            .use_timestamp(log_config.use_timestamps)
+            .use_timestamp(log_config.use_timestamps),
+            Box::new(log_buffer::BufferWriter),
         )
         .print_message()
         .duplicate_to_stderr(dup)
@@ -397,17 +421,25 @@ pub fn setup_logger() -> LoggerHandle {
     });
 
     panic::set_hook(Box::new(|p| {
-        if let Some(s) = p.payload().downcast_ref::<String>() {
-            error!("Thread main panic with: '{}'", s);
+        let message = if let Some(s) = p.payload().downcast_ref::<String>() {
+            s.to_string()
         } else if let Some(s) = p.payload().downcast_ref::<&str>() {
-            error!("Thread main panic with: '{}'", s);
+            s.to_string()
         } else {
-            error!("Thread main panic");
-        }
+            String::new()
+        };
+        error!("Thread main panic with: '{}'", message);
         warn!("at {:?}", p.location());
 
         let bt = std::backtrace::Backtrace::force_capture();
         warn!("{:?}", bt);
+
+        let location = format!("{:?}", p.location());
+        if let Some(path) = crash_report::write(&message, &location, &bt.to_string()) {
+            error!("Wrote crash report to {:?}", path);
+            eprintln!("Sulis has crashed.  A crash report was written to {path:?}");
+            eprintln!("If the game fails to start, try launching with --safe-mode");
+        }
     }));
 
     create_user_dirs();
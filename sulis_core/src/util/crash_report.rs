@@ -0,0 +1,104 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+use std::fmt::Write as FmtWrite;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::{self, Config};
+use crate::util::ActiveResources;
+
+const LOG_TAIL_LINES: usize = 200;
+
+/// Writes a crash report with the panic message, backtrace, current config,
+/// active module, and the tail of the most recent log file to the
+/// `crashes` directory in the user dir.  Returns the path of the report
+pub(crate) fn write(message: &str, location: &str, backtrace: &str) -> Option<PathBuf> {
+    let mut dir = config::USER_DIR.clone();
+    dir.push("crashes");
+    if let Err(e) = fs::create_dir_all(&dir) {
+        warn!("Unable to create crash report dir {:?}: {}", dir, e);
+        return None;
+    }
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    let _ = writeln!(out, "Sulis crash report");
+    let _ = writeln!(out, "Version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(out, "Time: {secs}");
+    let _ = writeln!(out, "Safe mode: {}", Config::is_safe_mode());
+    let _ = writeln!(out, "Panic: {message}");
+    let _ = writeln!(out, "Location: {location}");
+
+    let active = ActiveResources::read();
+    let _ = writeln!(out, "Module: {}", active.campaign.as_deref().unwrap_or("none"));
+    let _ = writeln!(out, "Mods: {:?}", active.mods);
+
+    let _ = writeln!(out, "\n== Backtrace ==\n{backtrace}");
+
+    let _ = writeln!(out, "\n== Config ==");
+    match Config::try_get_clone().map(|c| serde_yaml::to_string(&c)) {
+        Some(Ok(yaml)) => out.push_str(&yaml),
+        Some(Err(e)) => {
+            let _ = writeln!(out, "Unable to serialize config: {e}");
+        }
+        None => out.push_str("Config unavailable\n"),
+    }
+
+    let _ = writeln!(out, "\n== Log (last {LOG_TAIL_LINES} lines) ==");
+    let mut log_dir = config::USER_DIR.clone();
+    log_dir.push("log");
+    match log_tail(&log_dir) {
+        Some(tail) => out.push_str(&tail),
+        None => out.push_str("No log file found\n"),
+    }
+
+    dir.push(format!("crash_{secs}.txt"));
+    match fs::write(&dir, out) {
+        Ok(()) => Some(dir),
+        Err(e) => {
+            warn!("Unable to write crash report {:?}: {}", dir, e);
+            None
+        }
+    }
+}
+
+fn log_tail(log_dir: &Path) -> Option<String> {
+    let newest = fs::read_dir(log_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .max_by_key(|(modified, _)| *modified)?;
+
+    let data = fs::read_to_string(newest.1).ok()?;
+    let lines: Vec<_> = data.lines().collect();
+    let start = lines.len().saturating_sub(LOG_TAIL_LINES);
+
+    let mut out = String::new();
+    for line in &lines[start..] {
+        out.push_str(line);
+        out.push('\n');
+    }
+    Some(out)
+}
@@ -0,0 +1,119 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+//! Hex grid math for modules using a hexagonal grid.  Hexes are addressed with
+//! the same integer `x`, `y` coordinates as square tiles, in an "odd-q" offset
+//! layout: columns are vertical, and each odd column is shifted down by half
+//! a tile relative to the even columns.
+
+use crate::util::Point;
+
+/// Returns the cube coordinates of the hex at the specified offset coordinates.
+pub fn to_cube(x: i32, y: i32) -> (i32, i32, i32) {
+    let q = x;
+    let r = y - (x - (x & 1)) / 2;
+    (q, r, -q - r)
+}
+
+/// Returns the number of steps between the two hexes.
+pub fn distance(x1: i32, y1: i32, x2: i32, y2: i32) -> i32 {
+    let (q1, r1, s1) = to_cube(x1, y1);
+    let (q2, r2, s2) = to_cube(x2, y2);
+
+    (q1 - q2).abs().max((r1 - r2).abs()).max((s1 - s2).abs())
+}
+
+/// Returns the six hexes adjacent to the hex at `x`, `y`.  Some of these
+/// may lie outside the area.
+pub fn neighbors(x: i32, y: i32) -> [Point; 6] {
+    // odd columns sit half a tile lower, so their diagonal neighbors are
+    // on the same and following row rather than the preceding row
+    let dy = x & 1;
+    [
+        Point::new(x, y - 1),
+        Point::new(x, y + 1),
+        Point::new(x - 1, y - 1 + dy),
+        Point::new(x - 1, y + dy),
+        Point::new(x + 1, y - 1 + dy),
+        Point::new(x + 1, y + dy),
+    ]
+}
+
+/// The vertical offset, in tiles, at which the column `x` is drawn.
+pub fn column_offset(x: i32) -> f32 {
+    if x & 1 == 1 {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+/// Returns the position of the center of the hex at `x`, `y`, in tiles.
+pub fn center(x: i32, y: i32) -> (f32, f32) {
+    (x as f32, y as f32 + column_offset(x))
+}
+
+/// Returns the distance from the hex at `from` to the nearest hex covered by
+/// the rectangle at `x`, `y` with size `w`, `h`.  Like the edge distance on a
+/// square grid, adjacent hexes are half a tile apart.
+pub fn dist_to_rect(from: Point, x: f32, y: f32, w: f32, h: f32) -> f32 {
+    let min_x = x.floor() as i32;
+    let min_y = y.floor() as i32;
+    let max_x = ((x + w).ceil() as i32 - 1).max(min_x);
+    let max_y = ((y + h).ceil() as i32 - 1).max(min_y);
+
+    let mut best = i32::MAX;
+    for cell_y in min_y..=max_y {
+        for cell_x in min_x..=max_x {
+            best = best.min(distance(from.x, from.y, cell_x, cell_y));
+        }
+    }
+
+    (best as f32 - 0.5).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_counts_hex_steps() {
+        assert_eq!(distance(0, 0, 0, 0), 0);
+        assert_eq!(distance(0, 0, 1, 0), 1);
+        assert_eq!(distance(0, 0, 1, 1), 2);
+        assert_eq!(distance(0, 0, 2, 0), 2);
+        assert_eq!(distance(1, 0, 1, 3), 3);
+        assert_eq!(distance(3, 2, 0, 0), distance(0, 0, 3, 2));
+    }
+
+    #[test]
+    fn neighbors_are_one_step_away() {
+        for &(x, y) in &[(2, 2), (3, 2)] {
+            let neighbors = neighbors(x, y);
+            for (i, p) in neighbors.iter().enumerate() {
+                assert_eq!(distance(x, y, p.x, p.y), 1, "{p:?} next to ({x}, {y})");
+                assert!(!neighbors[i + 1..].contains(p));
+            }
+        }
+    }
+
+    #[test]
+    fn adjacent_rect_is_half_a_tile_away() {
+        assert_eq!(dist_to_rect(Point::new(0, 0), 1.0, 0.0, 1.0, 1.0), 0.5);
+        assert_eq!(dist_to_rect(Point::new(0, 0), 0.0, 0.0, 2.0, 2.0), 0.0);
+        assert_eq!(dist_to_rect(Point::new(0, 0), 3.0, 0.0, 1.0, 1.0), 2.5);
+    }
+}
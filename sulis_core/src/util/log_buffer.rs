@@ -0,0 +1,94 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use flexi_logger::writers::LogWriter;
+use flexi_logger::DeferredNow;
+use lazy_static::lazy_static;
+use log::{Level, Record};
+
+const MAX_ENTRIES: usize = 1000;
+
+lazy_static! {
+    static ref ENTRIES: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+}
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+/// Returns a counter that is incremented each time a log entry is added,
+/// allowing views to check whether they need to refresh
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Relaxed)
+}
+
+/// Returns the buffered log entries at `max_level` or more severe, and which
+/// contain `search` in either the target or message, case insensitive.
+pub fn entries(max_level: Level, search: &str) -> Vec<LogEntry> {
+    let search = search.trim().to_lowercase();
+    let entries = match ENTRIES.lock() {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .iter()
+        .filter(|entry| entry.level <= max_level)
+        .filter(|entry| {
+            search.is_empty()
+                || entry.message.to_lowercase().contains(&search)
+                || entry.target.to_lowercase().contains(&search)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Log writer that keeps the most recent log messages in memory so they
+/// can be displayed in game
+pub(crate) struct BufferWriter;
+
+impl LogWriter for BufferWriter {
+    fn write(&self, _now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        let entry = LogEntry {
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+
+        // don't log on failure here, as that would recursively call this writer
+        if let Ok(mut entries) = ENTRIES.lock() {
+            if entries.len() == MAX_ENTRIES {
+                entries.pop_front();
+            }
+            entries.push_back(entry);
+            GENERATION.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
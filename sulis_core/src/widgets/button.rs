@@ -61,6 +61,10 @@ impl Button {
 impl WidgetKind for Button {
     widget_kind!["button"];
 
+    fn is_navigable(&self) -> bool {
+        true
+    }
+
     fn update(&mut self, widget: &Rc<RefCell<Widget>>, _millis: u32) {
         if self.repeat_time == 0 {
             return;
@@ -90,7 +94,7 @@ impl WidgetKind for Button {
 
     fn layout(&mut self, widget: &mut Widget) {
         if let Some(ref text) = self.label.borrow().text {
-            widget.state.add_text_arg("0", text);
+            widget.state.add_display_text_arg("0", text);
         }
         widget.do_base_layout();
 
@@ -126,7 +130,7 @@ impl WidgetKind for Button {
         }
 
         let tooltip = Widget::with_theme(TextArea::empty(), "tooltip");
-        tooltip.borrow_mut().state.add_text_arg("0", &self.tooltip);
+        tooltip.borrow_mut().state.add_display_text_arg("0", &self.tooltip);
 
         let (x, y) = {
             let state = &widget.borrow().state;
@@ -24,6 +24,7 @@ use crate::widgets::{Button, Label};
 
 pub struct ConfirmationWindow {
     accept_callback: Callback,
+    cancel_callback: Option<Callback>,
     title: Rc<RefCell<Widget>>,
     accept: Rc<RefCell<Widget>>,
     cancel: Rc<RefCell<Widget>>,
@@ -37,12 +38,19 @@ impl ConfirmationWindow {
 
         Rc::new(RefCell::new(ConfirmationWindow {
             accept_callback,
+            cancel_callback: None,
             title,
             accept,
             cancel,
         }))
     }
 
+    /// Sets the callback for the cancel button, in place of simply closing
+    /// the window.  The callback is responsible for closing the window
+    pub fn set_cancel_callback(&mut self, callback: Callback) {
+        self.cancel_callback = Some(callback);
+    }
+
     pub fn add_accept_text_arg(&self, key: &str, value: &str) {
         self.accept.borrow_mut().state.add_text_arg(key, value);
     }
@@ -69,13 +77,14 @@ impl WidgetKind for ConfirmationWindow {
     }
 
     fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
-        self.cancel
-            .borrow_mut()
-            .state
-            .add_callback(Callback::new(Rc::new(|widget, _| {
+        let cancel_callback = match &self.cancel_callback {
+            Some(cb) => cb.clone(),
+            None => Callback::new(Rc::new(|widget, _| {
                 let (parent, _) = Widget::parent::<ConfirmationWindow>(widget);
                 parent.borrow_mut().mark_for_removal();
-            })));
+            })),
+        };
+        self.cancel.borrow_mut().state.add_callback(cancel_callback);
         self.accept
             .borrow_mut()
             .state
@@ -106,7 +106,7 @@ impl WidgetKind for Label {
         }
 
         let tooltip = Widget::with_theme(TextArea::empty(), "tooltip");
-        tooltip.borrow_mut().state.add_text_arg("0", &self.tooltip);
+        tooltip.borrow_mut().state.add_display_text_arg("0", &self.tooltip);
 
         let (x, y) = {
             let state = &widget.borrow().state;
@@ -120,7 +120,7 @@ impl WidgetKind for Label {
 
     fn layout(&mut self, widget: &mut Widget) {
         if let Some(ref text) = self.text {
-            widget.state.add_text_arg("0", text);
+            widget.state.add_display_text_arg("0", text);
         }
 
         widget.do_base_layout();
@@ -100,7 +100,7 @@ impl WidgetKind for ProgressBar {
         }
 
         let tooltip = Widget::with_theme(TextArea::empty(), "tooltip");
-        tooltip.borrow_mut().state.add_text_arg("0", &self.tooltip);
+        tooltip.borrow_mut().state.add_display_text_arg("0", &self.tooltip);
 
         let (x, y) = {
             let state = &widget.borrow().state;
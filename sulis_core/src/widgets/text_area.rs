@@ -99,7 +99,7 @@ impl WidgetKind for TextArea {
 
     fn layout(&mut self, widget: &mut Widget) {
         if let Some(ref text) = self.text {
-            widget.state.add_text_arg("0", text);
+            widget.state.add_display_text_arg("0", text);
         }
 
         widget.do_base_layout();
@@ -106,12 +106,16 @@ impl ActorCreatorWindow {
             hue: Some(self.selected_hue),
             hair_color: None,
             skin_color: None,
+            custom_images: HashMap::new(),
+            custom_colors: HashMap::new(),
             inventory: InventoryBuilder::default(),
             levels,
             xp: None,
             reward: None,
             abilities: Vec::new(),
+            approval_abilities: Vec::new(),
             ai: None,
+            script: None,
         };
 
         match write_to_file(&filename, &actor) {
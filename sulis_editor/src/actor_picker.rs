@@ -24,24 +24,33 @@ use sulis_core::util::{Offset, Point, Scale};
 use sulis_core::widgets::{Button, ScrollDirection, ScrollPane};
 use sulis_module::{Actor, Module};
 
-use crate::{AreaModel, EditorMode};
+use crate::actor_window::ActorWindow;
+use crate::{AreaEditor, AreaModel, EditorMode};
 
 const NAME: &str = "actor_picker";
 
 pub struct ActorPicker {
+    area_editor: Rc<RefCell<AreaEditor>>,
     cur_actor: Option<Rc<Actor>>,
     removal_actors: Vec<(Point, Rc<Actor>)>,
     cursor_pos: Option<Point>,
+    edit_actor: Option<usize>,
 }
 
 impl ActorPicker {
-    pub fn new() -> Rc<RefCell<ActorPicker>> {
+    pub fn new(area_editor: Rc<RefCell<AreaEditor>>) -> Rc<RefCell<ActorPicker>> {
         Rc::new(RefCell::new(ActorPicker {
+            area_editor,
             cur_actor: None,
             removal_actors: Vec::new(),
             cursor_pos: None,
+            edit_actor: None,
         }))
     }
+
+    pub fn select(&mut self, actor: Rc<Actor>) {
+        self.cur_actor = Some(actor);
+    }
 }
 
 impl EditorMode for ActorPicker {
@@ -110,6 +119,13 @@ impl EditorMode for ActorPicker {
     }
 
     fn left_click(&mut self, model: &mut AreaModel, x: i32, y: i32) {
+        // clicking an already placed actor edits its placement on the next
+        // update, once the area editor is no longer borrowed
+        if let Some(index) = model.actor_at(x, y) {
+            self.edit_actor = Some(index);
+            return;
+        }
+
         let actor = match self.cur_actor {
             None => return,
             Some(ref actor) => actor,
@@ -142,6 +158,18 @@ impl WidgetKind for ActorPicker {
         self
     }
 
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, _millis: u32) {
+        let index = match self.edit_actor.take() {
+            None => return,
+            Some(index) => index,
+        };
+
+        let root = Widget::get_root(widget);
+        let window = Widget::with_defaults(ActorWindow::new(Rc::clone(&self.area_editor), index));
+        window.borrow_mut().state.set_modal(true);
+        Widget::add_child_to(&root, window);
+    }
+
     fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
         let mut all_actors = Module::all_actors();
         all_actors.sort_by(|a, b| a.id.cmp(&b.id));
@@ -150,6 +178,10 @@ impl WidgetKind for ActorPicker {
         for actor in all_actors {
             let button = Widget::with_theme(Button::empty(), "actor_button");
             button.borrow_mut().state.add_text_arg("name", &actor.id);
+            if let Some(cur_actor) = self.cur_actor.as_ref() {
+                let active = Rc::ptr_eq(cur_actor, &actor);
+                button.borrow_mut().state.set_active(active);
+            }
             button
                 .borrow_mut()
                 .state
@@ -0,0 +1,251 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::util::Point;
+use sulis_core::widgets::{Button, InputField, Label, ScrollDirection, ScrollPane, Spinner};
+use sulis_module::{area::ActorData, area::MAX_AREA_SIZE, Facing, Faction, Module};
+
+use crate::AreaEditor;
+
+pub const NAME: &str = "actor_window";
+
+/// Edits the per placement overrides of a single actor placed in the area
+pub struct ActorWindow {
+    area_editor: Rc<RefCell<AreaEditor>>,
+    index: usize,
+}
+
+impl ActorWindow {
+    pub fn new(area_editor: Rc<RefCell<AreaEditor>>, index: usize) -> Rc<RefCell<ActorWindow>> {
+        Rc::new(RefCell::new(ActorWindow { area_editor, index }))
+    }
+
+    fn modify<F: Fn(&mut ActorData)>(widget: &Rc<RefCell<Widget>>, f: F) {
+        let (parent, window) = Widget::parent_mut::<ActorWindow>(widget);
+        parent.borrow_mut().invalidate_children();
+
+        let mut area_editor = window.area_editor.borrow_mut();
+        f(area_editor.model.actor_data_mut(window.index));
+    }
+
+    fn option_button<F>(text: &str, active: bool, f: F) -> Rc<RefCell<Widget>>
+    where
+        F: Fn(&mut ActorData) + 'static,
+    {
+        let button = Widget::with_theme(Button::empty(), "option");
+        button.borrow_mut().state.add_text_arg("name", text);
+        button.borrow_mut().state.set_active(active);
+        button
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                ActorWindow::modify(widget, &f);
+            })));
+        button
+    }
+
+    fn patrol_point(&self, point_index: usize, point: Point) -> Rc<RefCell<Widget>> {
+        let widget = Widget::empty("point");
+
+        let max = MAX_AREA_SIZE - 1;
+        let x = Widget::with_theme(Spinner::new(point.x, 0, max), "x");
+        let y = Widget::with_theme(Spinner::new(point.y, 0, max), "y");
+        for (spinner, is_x) in [(&x, true), (&y, false)] {
+            let area_editor_ref = Rc::clone(&self.area_editor);
+            let index = self.index;
+            spinner
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |_, kind| {
+                    let spinner = match kind.as_any_mut().downcast_mut::<Spinner>() {
+                        Some(widget) => widget,
+                        None => panic!("Failed to downcast to Spinner"),
+                    };
+
+                    let mut area_editor = area_editor_ref.borrow_mut();
+                    let point = &mut area_editor.model.actor_data_mut(index).patrol[point_index];
+                    if is_x {
+                        point.x = spinner.value();
+                    } else {
+                        point.y = spinner.value();
+                    }
+                })));
+        }
+
+        let remove = Widget::with_theme(Button::empty(), "remove");
+        remove
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                ActorWindow::modify(widget, |data| {
+                    data.patrol.remove(point_index);
+                });
+            })));
+
+        Widget::add_children_to(&widget, vec![x, y, remove]);
+        widget
+    }
+}
+
+impl WidgetKind for ActorWindow {
+    fn get_name(&self) -> &str {
+        NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<ActorWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let (actor, data) = self.area_editor.borrow().model.actor(self.index).clone();
+
+        let actor_label = Widget::with_theme(Label::empty(), "actor_label");
+        actor_label.borrow_mut().state.add_text_arg("id", &actor.id);
+        actor_label
+            .borrow_mut()
+            .state
+            .add_text_arg("faction", &actor.faction().to_str());
+
+        let unique_id_text = data.unique_id.clone().unwrap_or_default();
+        let unique_id = Widget::with_theme(InputField::new(&unique_id_text), "unique_id");
+        let area_editor_ref = Rc::clone(&self.area_editor);
+        let index = self.index;
+        unique_id
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |_, kind| {
+                let input_field = match kind.as_any_mut().downcast_mut::<InputField>() {
+                    Some(input_field) => input_field,
+                    None => panic!("Failed to downcast to InputField"),
+                };
+
+                let text = input_field.text.to_string();
+                let mut area_editor = area_editor_ref.borrow_mut();
+                area_editor.model.actor_data_mut(index).unique_id = if text.is_empty() {
+                    None
+                } else {
+                    Some(text)
+                };
+            })));
+
+        let factions = Widget::empty("factions");
+        Widget::add_child_to(
+            &factions,
+            ActorWindow::option_button("Default", data.faction.is_none(), |data| {
+                data.faction = None;
+            }),
+        );
+        for faction in Faction::iter() {
+            let faction = *faction;
+            let active = data.faction == Some(faction);
+            Widget::add_child_to(
+                &factions,
+                ActorWindow::option_button(&faction.to_str(), active, move |data| {
+                    data.faction = Some(faction);
+                }),
+            );
+        }
+
+        let facings = Widget::empty("facings");
+        Widget::add_child_to(
+            &facings,
+            ActorWindow::option_button("Default", data.facing.is_none(), |data| {
+                data.facing = None;
+            }),
+        );
+        for facing in Facing::iter() {
+            let facing = *facing;
+            let active = data.facing == Some(facing);
+            Widget::add_child_to(
+                &facings,
+                ActorWindow::option_button(facing.to_str(), active, move |data| {
+                    data.facing = Some(facing);
+                }),
+            );
+        }
+
+        let mut all_ai = Module::all_ai_templates();
+        all_ai.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let ai = ScrollPane::new(ScrollDirection::Vertical);
+        ai.borrow().add_to_content(ActorWindow::option_button(
+            "Default",
+            data.ai.is_none(),
+            |data| data.ai = None,
+        ));
+        for template in all_ai {
+            let active = data.ai.as_deref() == Some(template.id.as_str());
+            let id = template.id.to_string();
+            ai.borrow()
+                .add_to_content(ActorWindow::option_button(&template.id, active, move |data| {
+                    data.ai = Some(id.to_string());
+                }));
+        }
+
+        let patrol = ScrollPane::new(ScrollDirection::Vertical);
+        for (point_index, point) in data.patrol.iter().enumerate() {
+            patrol
+                .borrow()
+                .add_to_content(self.patrol_point(point_index, *point));
+        }
+
+        let add_point = Widget::with_theme(Button::empty(), "add_point");
+        let location = data.location;
+        add_point
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                ActorWindow::modify(widget, |data| {
+                    let point = data.patrol.last().copied().unwrap_or(location);
+                    data.patrol.push(point);
+                });
+            })));
+
+        vec![
+            close,
+            actor_label,
+            Widget::with_theme(Label::empty(), "unique_id_label"),
+            unique_id,
+            Widget::with_theme(Label::empty(), "faction_label"),
+            factions,
+            Widget::with_theme(Label::empty(), "facing_label"),
+            facings,
+            Widget::with_theme(Label::empty(), "ai_label"),
+            Widget::with_theme(ai, "ai"),
+            Widget::with_theme(Label::empty(), "patrol_label"),
+            Widget::with_theme(patrol, "patrol"),
+            add_point,
+        ]
+    }
+}
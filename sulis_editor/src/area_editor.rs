@@ -17,11 +17,12 @@
 use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Instant;
 
 use sulis_core::io::event::ClickKind;
 use sulis_core::io::{GraphicsRenderer, InputActionKind};
 use sulis_core::ui::{compute_area_scaling, Cursor, Scrollable, Widget, WidgetKind};
-use sulis_core::util::{Offset, Point, Scale};
+use sulis_core::util::{self, Offset, Point, Scale};
 use sulis_module::area::MAX_AREA_SIZE;
 
 use crate::{AreaModel, EditorMode};
@@ -38,6 +39,10 @@ pub struct AreaEditor {
     scale: (f32, f32),
 
     last_click_position: Option<Point>,
+
+    // whether the model has been edited since it was last saved or backed up
+    modified: bool,
+    last_backup: Instant,
 }
 
 impl AreaEditor {
@@ -48,6 +53,8 @@ impl AreaEditor {
             scroll: Scrollable::default(),
             scale: (1.0, 1.0),
             last_click_position: None,
+            modified: false,
+            last_backup: Instant::now(),
         }))
     }
 
@@ -55,6 +62,20 @@ impl AreaEditor {
         self.model = AreaModel::default();
         self.scroll = Scrollable::default();
         self.cur_editor = None;
+        self.modified = false;
+    }
+
+    pub fn save_area(&mut self, filename_prefix: &str) {
+        self.model.save(filename_prefix);
+        self.modified = false;
+    }
+
+    /// Loads the specified area.  If `recovered` is true, the area is treated
+    /// as having unsaved changes, so it will continue to be backed up.
+    pub fn load_area(&mut self, filename_prefix: &str, filename: &str, recovered: bool) {
+        self.model.load(filename_prefix, filename);
+        self.modified = recovered;
+        self.last_backup = Instant::now();
     }
 
     pub fn set_editor(&mut self, editor: EditorModeRef) {
@@ -143,6 +164,23 @@ impl WidgetKind for AreaEditor {
         }
     }
 
+    fn update(&mut self, _widget: &Rc<RefCell<Widget>>, _millis: u32) {
+        let interval = self.model.config.autosave_interval;
+        if !self.modified || interval == 0 {
+            return;
+        }
+
+        let elapsed = util::get_elapsed_millis(self.last_backup.elapsed());
+        if elapsed < interval * 1000 {
+            return;
+        }
+
+        info!("Writing backup of area '{}'", self.model.filename());
+        self.model.save_backup();
+        self.modified = false;
+        self.last_backup = Instant::now();
+    }
+
     fn on_key_press(&mut self, _widget: &Rc<RefCell<Widget>>, key: InputActionKind) -> bool {
         let delta = match key {
             InputActionKind::ZoomIn => 1,
@@ -167,6 +205,7 @@ impl WidgetKind for AreaEditor {
         };
 
         self.last_click_position = Some(Point::new(x, y));
+        self.modified = true;
         match kind {
             ClickKind::Primary => editor.borrow_mut().left_click(&mut self.model, x, y),
             ClickKind::Secondary => editor.borrow_mut().right_click(&mut self.model, x, y),
@@ -216,6 +255,7 @@ impl WidgetKind for AreaEditor {
         }
 
         self.last_click_position = Some(Point::new(x, y));
+        self.modified = true;
         match kind {
             ClickKind::Primary => editor.borrow_mut().left_click(&mut self.model, x, y),
             ClickKind::Secondary => editor.borrow_mut().right_click(&mut self.model, x, y),
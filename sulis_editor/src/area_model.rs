@@ -16,10 +16,12 @@
 
 use std::cmp;
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::slice::Iter;
 
-use sulis_core::config::{Config, EditorConfig};
+use sulis_core::config::{self, Config, EditorConfig};
 use sulis_core::io::{DrawList, GraphicsRenderer};
 use sulis_core::resource::{read_single_resource, write_to_file, ResourceSet, Sprite};
 use sulis_core::ui::{animation_state, LineRenderer};
@@ -28,15 +30,20 @@ use sulis_module::area::*;
 use sulis_module::generator::{is_removal, TilesModel};
 use sulis_module::{Actor, Encounter, Module, Prop};
 
+use crate::map_image;
+
 pub struct AreaModel {
     pub config: EditorConfig,
 
     tiles: TilesModel,
-    actors: Vec<(Point, Rc<Actor>, Option<String>)>,
+    actors: Vec<(Rc<Actor>, ActorData)>,
     props: Vec<PropData>,
     encounters: Vec<EncounterData>,
     transitions: Vec<Transition>,
     triggers: Vec<TriggerBuilder>,
+    traversals: Vec<TraversalPoint>,
+    tile_overrides: Vec<TileOverride>,
+    over_layers: Vec<String>,
 
     encounter_sprite: Option<Rc<Sprite>>,
     font_renderer: Option<LineRenderer>,
@@ -49,10 +56,13 @@ pub struct AreaModel {
     pub world_map_location: Option<String>,
     pub location_kind: LocationKind,
     pub on_rest: OnRest,
+    level: Option<u32>,
+    script: Option<AreaScript>,
 
-    ambient_sound: Option<String>,
-    default_music: Option<String>,
-    default_combat_music: Option<String>,
+    pub ambient_sound: Option<String>,
+    pub default_music: Option<String>,
+    pub default_combat_music: Option<String>,
+    pub ambient_loops: Vec<AmbientLoop>,
 }
 
 impl Default for AreaModel {
@@ -78,6 +88,7 @@ impl Default for AreaModel {
         let id = config.area.id.clone();
         let name = config.area.name.clone();
         let filename = config.area.filename.clone();
+        let over_layers = config.area.over_layers.clone();
 
         let tiles = TilesModel::new();
 
@@ -89,6 +100,9 @@ impl Default for AreaModel {
             encounters: Vec::new(),
             transitions: Vec::new(),
             triggers: Vec::new(),
+            traversals: Vec::new(),
+            tile_overrides: Vec::new(),
+            over_layers,
             encounter_sprite,
             font_renderer,
             id,
@@ -100,10 +114,13 @@ impl Default for AreaModel {
             ambient_sound: None,
             default_music: None,
             default_combat_music: None,
+            ambient_loops: Vec::new(),
             location_kind: LocationKind::Outdoors,
             on_rest: OnRest::Disabled {
                 message: "<PLACEHOLDER>".to_string(),
             },
+            level: None,
+            script: None,
         }
     }
 }
@@ -154,6 +171,56 @@ impl AreaModel {
         });
     }
 
+    pub fn add_traversal(&mut self, traversal: TraversalPoint) {
+        let (from, to) = (traversal.from, traversal.to);
+        if from.x < 0 || from.y < 0 || to.x < 0 || to.y < 0 {
+            return;
+        }
+
+        self.traversals.push(traversal);
+    }
+
+    /// Overrides the passability and movement cost of the top most tile
+    /// covering `x`, `y`, replacing any existing override for that tile.
+    pub fn set_tile_override(
+        &mut self,
+        x: i32,
+        y: i32,
+        passable: Option<bool>,
+        movement_cost: u32,
+    ) {
+        let (pos, tile) = match self.tile_at(x, y) {
+            None => return,
+            Some(tile) => tile,
+        };
+
+        self.tile_overrides.retain(|o| o.location != pos || o.tile != tile.id);
+        self.tile_overrides.push(TileOverride {
+            tile: tile.id.to_string(),
+            location: pos,
+            passable,
+            movement_cost,
+        });
+    }
+
+    pub fn tile_overrides(&self) -> &[TileOverride] {
+        &self.tile_overrides
+    }
+
+    fn tile_at(&self, x: i32, y: i32) -> Option<(Point, Rc<Tile>)> {
+        self.tiles
+            .all()
+            .filter(|(pos, tile)| is_removal(*pos, tile.width, tile.height, x, y, 1, 1))
+            .last()
+            .map(|(pos, tile)| (*pos, Rc::clone(tile)))
+    }
+
+    fn has_tile(&self, tile_override: &TileOverride) -> bool {
+        self.tiles
+            .all()
+            .any(|(pos, tile)| *pos == tile_override.location && tile.id == tile_override.tile)
+    }
+
     pub fn add_encounter(&mut self, encounter: Rc<Encounter>, x: i32, y: i32, w: i32, h: i32) {
         if x < 0 || y < 0 {
             return;
@@ -172,13 +239,38 @@ impl AreaModel {
             return;
         }
 
-        self.actors.push((Point::new(x, y), actor, None));
+        let data = ActorData {
+            id: actor.id.to_string(),
+            location: Point::new(x, y),
+            unique_id: None,
+            faction: None,
+            facing: None,
+            ai: None,
+            patrol: Vec::new(),
+        };
+        self.actors.push((actor, data));
+    }
+
+    /// Returns the index of the first placed actor covering `x`, `y`
+    pub fn actor_at(&self, x: i32, y: i32) -> Option<usize> {
+        self.actors.iter().position(|(actor, data)| {
+            let size = &actor.race.size;
+            is_removal(data.location, size.width, size.height, x, y, 1, 1)
+        })
+    }
+
+    pub fn actor(&self, index: usize) -> &(Rc<Actor>, ActorData) {
+        &self.actors[index]
+    }
+
+    pub fn actor_data_mut(&mut self, index: usize) -> &mut ActorData {
+        &mut self.actors[index].1
     }
 
     pub fn remove_actors_within(&mut self, x: i32, y: i32, width: i32, height: i32) {
-        self.actors.retain(|&(pos, ref actor, _)| {
+        self.actors.retain(|(actor, data)| {
             !is_removal(
-                pos,
+                data.location,
                 actor.race.size.width,
                 actor.race.size.height,
                 x,
@@ -197,7 +289,8 @@ impl AreaModel {
         height: i32,
     ) -> Vec<(Point, Rc<Actor>)> {
         let mut actors = Vec::new();
-        for &(pos, ref actor, _) in self.actors.iter() {
+        for (actor, data) in self.actors.iter() {
+            let pos = data.location;
             if !is_removal(
                 pos,
                 actor.race.size.width,
@@ -216,9 +309,9 @@ impl AreaModel {
         actors
     }
 
-    pub fn add_prop(&mut self, prop: Rc<Prop>, x: i32, y: i32) {
+    pub fn add_prop(&mut self, prop: Rc<Prop>, x: i32, y: i32) -> Option<usize> {
         if x < 0 || y < 0 {
-            return;
+            return None;
         }
 
         let prop_data = PropData {
@@ -226,9 +319,25 @@ impl AreaModel {
             enabled: true,
             location: Point::new(x, y),
             items: Vec::new(),
+            loot: None,
             hover_text: None,
+            owner: None,
+            lock: None,
         };
         self.props.push(prop_data);
+        Some(self.props.len() - 1)
+    }
+
+    pub fn props_iter(&self) -> Iter<'_, PropData> {
+        self.props.iter()
+    }
+
+    pub fn prop(&self, index: usize) -> &PropData {
+        &self.props[index]
+    }
+
+    pub fn prop_mut(&mut self, index: usize) -> &mut PropData {
+        &mut self.props[index]
     }
 
     pub fn remove_props_within(&mut self, x: i32, y: i32, width: i32, height: i32) {
@@ -263,6 +372,23 @@ impl AreaModel {
         });
     }
 
+    pub fn remove_traversals_within(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.traversals.retain(|t| {
+            !is_removal(t.from, 1, 1, x, y, width, height)
+                && !is_removal(t.to, 1, 1, x, y, width, height)
+        });
+    }
+
+    pub fn remove_tile_overrides_within(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.tile_overrides.retain(|o| {
+            let (w, h) = match Module::tile(&o.tile) {
+                None => (1, 1),
+                Some(tile) => (tile.width, tile.height),
+            };
+            !is_removal(o.location, w, h, x, y, width, height)
+        });
+    }
+
     pub fn remove_encounters_within(&mut self, x: i32, y: i32, width: i32, height: i32) {
         self.encounters.retain(|enc_data| {
             let w = enc_data.size.width;
@@ -297,6 +423,7 @@ impl AreaModel {
             hover_text: "<<PLACEHOLDER>>".to_string(),
             size,
             image_display: sprite,
+            thumbnail: None,
         });
 
         Some(self.transitions.len() - 1)
@@ -326,6 +453,10 @@ impl AreaModel {
 
     pub fn shift_tiles(&mut self, delta_x: i32, delta_y: i32) {
         self.tiles.shift(delta_x, delta_y);
+        for tile_override in self.tile_overrides.iter_mut() {
+            tile_override.location.x += delta_x;
+            tile_override.location.y += delta_y;
+        }
     }
 
     pub fn remove_all_tiles(&mut self, x: i32, y: i32, width: i32, height: i32) {
@@ -399,7 +530,8 @@ impl AreaModel {
             renderer.draw(draw_list);
         }
 
-        for &(pos, ref actor, _) in self.actors.iter() {
+        for (actor, data) in self.actors.iter() {
+            let pos = data.location;
             let w = actor.race.size.width as f32 / 2.0;
             let h = actor.race.size.height as f32 / 2.0;
             actor.draw(
@@ -495,6 +627,29 @@ impl AreaModel {
             draw_list.set_scale(scale);
             renderer.draw(draw_list);
         }
+
+        for traversal in self.traversals.iter() {
+            let text = format!("{:?}", traversal.kind);
+            for marker in &[traversal.from, traversal.to] {
+                let offset = Offset {
+                    x: marker.x as f32 + offset.x,
+                    y: marker.y as f32 + offset.y,
+                };
+                let rect = Rect {
+                    x: offset.x,
+                    y: offset.y,
+                    w: 1.0,
+                    h: 1.0,
+                };
+                let mut draw_list = DrawList::from_sprite_f32(encounter_sprite, rect);
+                draw_list.set_scale(scale);
+                renderer.draw(draw_list);
+
+                let (mut draw_list, _) = font_renderer.get_draw_list(&text, offset, 1.0);
+                draw_list.set_scale(scale);
+                renderer.draw(draw_list);
+            }
+        }
     }
 
     pub fn load(&mut self, filename_prefix: &str, filename: &str) {
@@ -518,9 +673,12 @@ impl AreaModel {
         self.world_map_location = area_builder.world_map_location.clone();
         self.on_rest = area_builder.on_rest.clone();
         self.location_kind = area_builder.location_kind;
+        self.level = area_builder.level;
+        self.script = area_builder.script.clone();
         self.ambient_sound = area_builder.ambient_sound;
         self.default_music = area_builder.default_music;
         self.default_combat_music = area_builder.default_combat_music;
+        self.ambient_loops = area_builder.ambient_loops;
 
         let width = area_builder.width as i32;
 
@@ -542,6 +700,18 @@ impl AreaModel {
         self.triggers.clear();
         self.triggers.append(&mut area_builder.triggers);
 
+        trace!("Loading area traversal points.");
+        self.traversals.clear();
+        self.traversals.append(&mut area_builder.traversals);
+
+        trace!("Loading area tile overrides.");
+        self.tile_overrides.clear();
+        self.tile_overrides.append(&mut area_builder.tile_overrides);
+
+        if !area_builder.over_layers.is_empty() {
+            self.over_layers = area_builder.over_layers;
+        }
+
         trace!("Loading area elevation.");
         let elev = &area_builder.elevation;
         let dest_elev = self.tiles.raw_elevation();
@@ -572,12 +742,26 @@ impl AreaModel {
                 Some(prop) => prop,
             };
 
+            let loot = match prop_builder.loot {
+                None => None,
+                Some(ref id) => {
+                    let loot = Module::loot_list(id);
+                    if loot.is_none() {
+                        warn!("No loot list with ID {} found", id);
+                    }
+                    loot
+                }
+            };
+
             let prop_data = PropData {
                 prop,
-                enabled: prop_builder.enabled.unwrap_or(true),
+                enabled: prop_builder.enabled.unwrap_or(prop_builder.lock.is_none()),
                 location: prop_builder.location,
                 items: prop_builder.items,
+                loot,
                 hover_text: prop_builder.hover_text,
+                owner: prop_builder.owner,
+                lock: prop_builder.lock,
             };
 
             self.props.push(prop_data);
@@ -613,6 +797,7 @@ impl AreaModel {
                 size,
                 hover_text: transition_builder.hover_text,
                 image_display: image,
+                thumbnail: transition_builder.thumbnail,
             });
         }
     }
@@ -676,8 +861,13 @@ impl AreaModel {
                 Some(actor) => actor,
             };
 
-            self.actors
-                .push((actor_data.location, actor, actor_data.unique_id));
+            if let Some(ref id) = actor_data.ai {
+                if Module::ai_template(id).is_none() {
+                    warn!("No AI template with ID {} found", id);
+                }
+            }
+
+            self.actors.push((actor, actor_data));
         }
     }
 
@@ -739,12 +929,77 @@ impl AreaModel {
 
     pub fn save(&self, filename_prefix: &str) {
         let filename = format!("{}/{}.yml", filename_prefix, self.filename);
+        if self.write(&filename) {
+            remove_backup(&self.filename);
+        }
+    }
+
+    /// Writes the current area state to the backup directory, without
+    /// touching the saved area file
+    pub fn save_backup(&self) {
+        let dir = backup_dir();
+        if let Err(e) = fs::create_dir_all(&dir) {
+            warn!("Unable to create editor backup dir {:?}: {}", dir, e);
+            return;
+        }
+
+        let filename = format!("{}/{}.yml", dir.to_string_lossy(), self.filename);
+        self.write(&filename);
+    }
+
+    /// Renders the tiles and props of this area to a PNG image at `filename`,
+    /// with each grid square taking `scale` pixels
+    pub fn export_image(&self, filename: &Path, scale: u32) -> bool {
+        let (width, height) = self.dimensions();
+        if width == 0 || height == 0 {
+            warn!("Unable to export image of an empty area");
+            return false;
+        }
+
+        let mut draw_lists = Vec::new();
+        for (_, tiles) in self.tiles.iter() {
+            let mut draw_list = DrawList::empty_sprite();
+            for &(pos, ref tile) in tiles {
+                let rect = Rect {
+                    x: pos.x as f32,
+                    y: pos.y as f32,
+                    w: tile.width as f32,
+                    h: tile.height as f32,
+                };
+                draw_list.append(&mut DrawList::from_sprite_f32(&tile.image_display, rect));
+            }
+            draw_lists.push(draw_list);
+        }
+
+        for prop_data in self.props.iter() {
+            let offset = Offset {
+                x: prop_data.location.x as f32,
+                y: prop_data.location.y as f32,
+            };
+            let mut draw_list = DrawList::empty_sprite();
+            prop_data.prop.append_to_draw_list(&mut draw_list, &animation_state::NORMAL, offset, 0);
+            draw_lists.push(draw_list);
+        }
+
+        let image = map_image::render(&draw_lists, width, height, scale);
+        match image.save(filename) {
+            Ok(()) => {
+                info!("Exported area image to {:?}", filename);
+                true
+            }
+            Err(e) => {
+                warn!("Unable to export area image to {:?}: {}", filename, e);
+                false
+            }
+        }
+    }
+
+    fn write(&self, filename: &str) -> bool {
         debug!("Saving current area state to {}", filename);
         let visibility_tile = self.config.area.visibility_tile.clone();
         let explored_tile = self.config.area.explored_tile.clone();
 
-        let mut width = 0;
-        let mut height = 0;
+        let (width, height) = self.dimensions();
         let mut layers: Vec<String> = Vec::new();
         let mut layer_set: HashMap<String, Vec<Vec<u16>>> = HashMap::new();
 
@@ -752,9 +1007,6 @@ impl AreaModel {
         for (layer_id, tiles) in self.tiles.iter() {
             layers.push(layer_id.to_string());
             for &(position, ref tile) in tiles.iter() {
-                width = cmp::max(width, position.x + tile.width);
-                height = cmp::max(height, position.y + tile.height);
-
                 if position.x >= MAX_AREA_SIZE || position.y >= MAX_AREA_SIZE {
                     continue;
                 }
@@ -764,19 +1016,10 @@ impl AreaModel {
                 tiles_vec.push(vec![position.x as u16, position.y as u16]);
             }
         }
-        width = cmp::min(width, MAX_AREA_SIZE);
-        height = cmp::min(height, MAX_AREA_SIZE);
         let entity_layer = self.config.area.entity_layer;
 
         trace!("Saving actors.");
-        let mut actors: Vec<ActorData> = Vec::new();
-        for &(pos, ref actor, ref unique_id) in self.actors.iter() {
-            actors.push(ActorData {
-                id: actor.id.to_string(),
-                unique_id: unique_id.clone(),
-                location: pos,
-            });
-        }
+        let actors: Vec<ActorData> = self.actors.iter().map(|(_, data)| data.clone()).collect();
 
         trace!("Saving props.");
         let mut props: Vec<PropDataBuilder> = Vec::new();
@@ -786,7 +1029,10 @@ impl AreaModel {
                 enabled: Some(prop_data.enabled),
                 location: prop_data.location,
                 items: prop_data.items.clone(),
+                loot: prop_data.loot.as_ref().map(|loot| loot.id.to_string()),
                 hover_text: prop_data.hover_text.clone(),
+                owner: prop_data.owner.clone(),
+                lock: prop_data.lock.clone(),
             };
             props.push(builder);
         }
@@ -811,6 +1057,7 @@ impl AreaModel {
                 to: transition.to.clone(),
                 hover_text: transition.hover_text.to_string(),
                 image_display: self.config.transition_image.clone(),
+                thumbnail: transition.thumbnail.clone(),
             });
         }
 
@@ -833,10 +1080,19 @@ impl AreaModel {
             }
         }
 
+        let tile_overrides = self
+            .tile_overrides
+            .iter()
+            .filter(|o| self.has_tile(o))
+            .cloned()
+            .collect();
+
         let area_builder = AreaBuilder {
             id: self.id.clone(),
             name: self.name.clone(),
             location_kind: self.location_kind,
+            level: self.level,
+            script: self.script.clone(),
             elevation,
             terrain,
             walls,
@@ -853,20 +1109,255 @@ impl AreaModel {
             encounters,
             transitions,
             triggers: self.triggers.clone(),
+            traversals: self.traversals.clone(),
+            tile_overrides,
+            over_layers: self.over_layers.clone(),
             max_vis_distance: self.max_vis_distance,
             max_vis_up_one_distance: self.max_vis_up_one_distance,
             world_map_location: self.world_map_location.clone(),
             ambient_sound: self.ambient_sound.clone(),
             default_music: self.default_music.clone(),
             default_combat_music: self.default_combat_music.clone(),
+            ambient_loops: self.ambient_loops.clone(),
             on_rest: self.on_rest.clone(),
         };
 
         trace!("Writing to file {}", filename);
-        if let Err(e) = write_to_file(&filename, &area_builder) {
+        if let Err(e) = write_to_file(filename, &area_builder) {
             error!("Unable to save area state to file {}", filename);
             error!("{}", e);
+            return false;
         }
+
+        true
+    }
+
+    /// Checks the area for problems that would leave it broken or unplayable
+    /// once saved.  Returns an empty list if no problems were found.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let (width, height) = self.dimensions();
+        if width == 0 || height == 0 {
+            issues.push(ValidationIssue::error("The area does not contain any tiles"));
+            return issues;
+        }
+
+        let in_bounds = |x: i32, y: i32| x >= 0 && y >= 0 && x < width && y < height;
+        let dim = (width * height) as usize;
+
+        let mut covered = vec![false; dim];
+        for (_, tiles) in self.tiles.iter() {
+            for &(pos, ref tile) in tiles.iter() {
+                for y in pos.y..(pos.y + tile.height) {
+                    for x in pos.x..(pos.x + tile.width) {
+                        if in_bounds(x, y) {
+                            covered[(x + y * width) as usize] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let uncovered: Vec<usize> = (0..dim).filter(|i| !covered[*i]).collect();
+        if let Some(&first) = uncovered.first() {
+            let (x, y) = (first as i32 % width, first as i32 / width);
+            issues.push(ValidationIssue::warning(&format!(
+                "{} locations are not covered by any layer, starting at {},{}",
+                uncovered.len(),
+                x,
+                y
+            )));
+        }
+
+        for prop_data in self.props.iter() {
+            let prop = &prop_data.prop;
+            let loc = prop_data.location;
+            if !in_bounds(loc.x, loc.y)
+                || !in_bounds(loc.x + prop.size.width - 1, loc.y + prop.size.height - 1)
+            {
+                issues.push(ValidationIssue::error(&format!(
+                    "Prop '{}' at {},{} extends outside the area bounds",
+                    prop.id, loc.x, loc.y
+                )));
+            }
+        }
+
+        let passable = self.passable_grid(width, height);
+
+        let steep = self.steep_elevation_points();
+        if let Some(first) = steep.first() {
+            issues.push(ValidationIssue::warning(&format!(
+                "{} passable locations differ from a neighbor by more than one elevation \
+                level, starting at {},{}",
+                steep.len(),
+                first.x,
+                first.y
+            )));
+        }
+
+        for (actor, data) in self.actors.iter() {
+            let pos = data.location;
+            let blocked = actor
+                .race
+                .size
+                .points(pos.x, pos.y)
+                .any(|p| !in_bounds(p.x, p.y) || !passable[(p.x + p.y * width) as usize]);
+            if blocked {
+                issues.push(ValidationIssue::error(&format!(
+                    "Actor '{}' at {},{} is placed on impassable or out of bounds tiles",
+                    actor.id, pos.x, pos.y
+                )));
+            }
+        }
+
+        // flood fill the passable region containing the first usable transition,
+        // so transitions that cannot be walked to from it can be reported
+        let mut reachable = vec![false; dim];
+        let mut seeded = false;
+        for transition in self.transitions.iter() {
+            let from = transition.from;
+            let points: Vec<Point> = transition
+                .size
+                .points(from.x, from.y)
+                .filter(|p| in_bounds(p.x, p.y) && passable[(p.x + p.y * width) as usize])
+                .collect();
+
+            if points.is_empty() {
+                issues.push(ValidationIssue::error(&format!(
+                    "Transition at {},{} is not on any passable tiles",
+                    from.x, from.y
+                )));
+                continue;
+            }
+
+            if !seeded {
+                seeded = true;
+                fill_reachable(&points, &passable, &mut reachable, width, height);
+            } else if !points.iter().any(|p| reachable[(p.x + p.y * width) as usize]) {
+                issues.push(ValidationIssue::warning(&format!(
+                    "Transition at {},{} cannot be reached from the other transitions",
+                    from.x, from.y
+                )));
+            }
+
+            if let ToKind::Area { ref id, .. } = transition.to {
+                if *id != self.id && Module::area(id).is_none() {
+                    issues.push(ValidationIssue::warning(&format!(
+                        "Transition at {},{} leads to unknown area '{}'",
+                        from.x, from.y, id
+                    )));
+                }
+            }
+        }
+
+        let sounds = [
+            ("Ambient sound", &self.ambient_sound),
+            ("Music", &self.default_music),
+            ("Combat music", &self.default_combat_music),
+        ];
+        let sounds = sounds
+            .into_iter()
+            .filter_map(|(kind, id)| id.as_ref().map(|id| (kind, id)))
+            .chain(self.ambient_loops.iter().map(|l| ("Ambient loop", &l.id)));
+        for (kind, id) in sounds {
+            if ResourceSet::sound(id).is_err() {
+                issues.push(ValidationIssue::error(&format!(
+                    "{kind} refers to unknown sound '{id}'"
+                )));
+            }
+        }
+
+        issues
+    }
+
+    /// Returns all passable points that are directly adjacent to another passable
+    /// point more than one elevation level above or below them.  Changes in
+    /// elevation must be made through ramps that rise one level at a time.
+    pub fn steep_elevation_points(&self) -> Vec<Point> {
+        let (width, height) = self.dimensions();
+        let passable = self.passable_grid(width, height);
+
+        let mut points = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if !passable[(x + y * width) as usize] {
+                    continue;
+                }
+
+                let elev = self.tiles.elevation(x, y) as i32;
+                let steep = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|(dx, dy)| {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                        return false;
+                    }
+
+                    passable[(nx + ny * width) as usize]
+                        && (self.tiles.elevation(nx, ny) as i32 - elev).abs() > 1
+                });
+
+                if steep {
+                    points.push(Point::new(x, y));
+                }
+            }
+        }
+
+        points
+    }
+
+    // computes passability the same way as the area layer set
+    fn passable_grid(&self, width: i32, height: i32) -> Vec<bool> {
+        let in_bounds = |x: i32, y: i32| x >= 0 && y >= 0 && x < width && y < height;
+        let mut passable = vec![true; (width * height) as usize];
+        let impass = |pos: Point, points: &[Point], passable: &mut [bool]| {
+            for p in points {
+                let (x, y) = (pos.x + p.x, pos.y + p.y);
+                if in_bounds(x, y) {
+                    passable[(x + y * width) as usize] = false;
+                }
+            }
+        };
+
+        let mut overrides = Vec::new();
+        for (_, tiles) in self.tiles.iter() {
+            for &(pos, ref tile) in tiles.iter() {
+                impass(pos, &tile.impass, &mut passable);
+
+                if tile.override_impass {
+                    overrides.push((pos, tile));
+                }
+            }
+        }
+
+        for (pos, tile) in overrides {
+            for y in pos.y..(pos.y + tile.height) {
+                for x in pos.x..(pos.x + tile.width) {
+                    if in_bounds(x, y) {
+                        passable[(x + y * width) as usize] = true;
+                    }
+                }
+            }
+
+            impass(pos, &tile.impass, &mut passable);
+        }
+
+        for prop_data in self.props.iter() {
+            impass(prop_data.location, &prop_data.prop.impass, &mut passable);
+        }
+
+        passable
+    }
+
+    fn dimensions(&self) -> (i32, i32) {
+        let mut width = 0;
+        let mut height = 0;
+        for (_, tiles) in self.tiles.iter() {
+            for &(position, ref tile) in tiles.iter() {
+                width = cmp::max(width, position.x + tile.width);
+                height = cmp::max(height, position.y + tile.height);
+            }
+        }
+
+        (cmp::min(width, MAX_AREA_SIZE), cmp::min(height, MAX_AREA_SIZE))
     }
 
     fn save_terrain(&self, width: i32, height: i32) -> (Vec<u8>, Vec<Option<String>>) {
@@ -898,3 +1389,94 @@ impl AreaModel {
         (elevation, terrain)
     }
 }
+
+/// The directory that automatic backups of areas in the current editor
+/// module are written to
+pub fn export_dir() -> PathBuf {
+    let mut dir = config::USER_DIR.clone();
+    dir.push("editor_exports");
+    dir.push(Config::editor_config().module);
+    dir
+}
+
+pub fn backup_dir() -> PathBuf {
+    let mut dir = config::USER_DIR.clone();
+    dir.push("editor_backups");
+    dir.push(Config::editor_config().module);
+    dir
+}
+
+/// Returns true if a backup of the specified area exists and was written
+/// more recently than the saved area file
+pub fn has_newer_backup(filename_prefix: &str, filename: &str) -> bool {
+    let mut backup = backup_dir();
+    backup.push(format!("{filename}.yml"));
+    let saved = Path::new(filename_prefix).join(format!("{filename}.yml"));
+
+    let modified = |path: &Path| fs::metadata(path).and_then(|data| data.modified()).ok();
+    match (modified(&backup), modified(&saved)) {
+        (Some(backup), Some(saved)) => backup > saved,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+pub fn remove_backup(filename: &str) {
+    let mut backup = backup_dir();
+    backup.push(format!("{filename}.yml"));
+    if backup.is_file() {
+        if let Err(e) = fs::remove_file(&backup) {
+            warn!("Unable to remove editor backup {:?}: {}", backup, e);
+        }
+    }
+}
+
+fn fill_reachable(start: &[Point], passable: &[bool], reachable: &mut [bool], w: i32, h: i32) {
+    let mut stack: Vec<Point> = start.to_vec();
+    while let Some(p) = stack.pop() {
+        let index = (p.x + p.y * w) as usize;
+        if reachable[index] {
+            continue;
+        }
+        reachable[index] = true;
+
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let (x, y) = (p.x + dx, p.y + dy);
+            if x < 0 || y < 0 || x >= w || y >= h {
+                continue;
+            }
+
+            let index = (x + y * w) as usize;
+            if passable[index] && !reachable[index] {
+                stack.push(Point::new(x, y));
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn error(message: &str) -> ValidationIssue {
+        ValidationIssue {
+            severity: Severity::Error,
+            message: message.to_string(),
+        }
+    }
+
+    fn warning(message: &str) -> ValidationIssue {
+        ValidationIssue {
+            severity: Severity::Warning,
+            message: message.to_string(),
+        }
+    }
+}
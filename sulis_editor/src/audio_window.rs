@@ -0,0 +1,184 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, InputField, Label, Spinner};
+use sulis_module::area::AmbientLoop;
+
+use crate::{AreaEditor, AreaModel};
+
+pub const NAME: &str = "audio_window";
+
+/// Edits the music and ambient sounds of the current area
+pub struct AudioWindow {
+    area_editor: Rc<RefCell<AreaEditor>>,
+}
+
+impl AudioWindow {
+    pub fn new(area_editor: Rc<RefCell<AreaEditor>>) -> Rc<RefCell<AudioWindow>> {
+        Rc::new(RefCell::new(AudioWindow { area_editor }))
+    }
+
+    fn sound_field(
+        &self,
+        theme_id: &str,
+        sound: fn(&mut AreaModel) -> &mut Option<String>,
+    ) -> Rc<RefCell<Widget>> {
+        let text = sound(&mut self.area_editor.borrow_mut().model).clone();
+        let field = Widget::with_theme(InputField::new(&text.unwrap_or_default()), theme_id);
+
+        let area_editor_ref = Rc::clone(&self.area_editor);
+        field
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |_, kind| {
+                let input_field = match kind.as_any_mut().downcast_mut::<InputField>() {
+                    Some(input_field) => input_field,
+                    None => panic!("Failed to downcast to InputField"),
+                };
+                let text = input_field.text.trim();
+                let value = if text.is_empty() {
+                    None
+                } else {
+                    Some(text.to_string())
+                };
+                *sound(&mut area_editor_ref.borrow_mut().model) = value;
+            })));
+        field
+    }
+
+    fn loop_row(&self, index: usize, ambient_loop: &AmbientLoop) -> Rc<RefCell<Widget>> {
+        let row = Widget::empty("loop");
+
+        let id = Widget::with_theme(InputField::new(&ambient_loop.id), "id");
+        let area_editor_ref = Rc::clone(&self.area_editor);
+        id.borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |_, kind| {
+                let input_field = match kind.as_any_mut().downcast_mut::<InputField>() {
+                    Some(input_field) => input_field,
+                    None => panic!("Failed to downcast to InputField"),
+                };
+                let mut area_editor = area_editor_ref.borrow_mut();
+                area_editor.model.ambient_loops[index].id = input_field.text.trim().to_string();
+            })));
+
+        let volume_label = Widget::with_theme(Label::empty(), "volume_label");
+
+        let percent = (ambient_loop.volume * 100.0).round() as i32;
+        let volume = Widget::with_theme(Spinner::new(percent, 0, 200), "volume");
+        let area_editor_ref = Rc::clone(&self.area_editor);
+        volume
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |_, kind| {
+                let spinner = match kind.as_any_mut().downcast_mut::<Spinner>() {
+                    Some(widget) => widget,
+                    None => panic!("Failed to downcast to Spinner"),
+                };
+                let mut area_editor = area_editor_ref.borrow_mut();
+                area_editor.model.ambient_loops[index].volume = spinner.value() as f32 / 100.0;
+            })));
+
+        let remove = Widget::with_theme(Button::empty(), "remove");
+        let area_editor_ref = Rc::clone(&self.area_editor);
+        remove
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                area_editor_ref.borrow_mut().model.ambient_loops.remove(index);
+                let (parent, _) = Widget::parent::<AudioWindow>(widget);
+                parent.borrow_mut().invalidate_children();
+            })));
+
+        Widget::add_children_to(&row, vec![id, volume_label, volume, remove]);
+        row
+    }
+}
+
+impl WidgetKind for AudioWindow {
+    fn get_name(&self) -> &str {
+        NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<AudioWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let music_label = Widget::with_theme(Label::empty(), "music_label");
+        let music = self.sound_field("music", |model| &mut model.default_music);
+
+        let combat_music_label = Widget::with_theme(Label::empty(), "combat_music_label");
+        let combat_music =
+            self.sound_field("combat_music", |model| &mut model.default_combat_music);
+
+        let ambient_label = Widget::with_theme(Label::empty(), "ambient_label");
+        let ambient = self.sound_field("ambient", |model| &mut model.ambient_sound);
+
+        let loops_label = Widget::with_theme(Label::empty(), "loops_label");
+
+        let add_loop = Widget::with_theme(Button::empty(), "add_loop");
+        let area_editor_ref = Rc::clone(&self.area_editor);
+        add_loop
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                area_editor_ref.borrow_mut().model.ambient_loops.push(AmbientLoop {
+                    id: String::new(),
+                    volume: 1.0,
+                });
+                let (parent, _) = Widget::parent::<AudioWindow>(widget);
+                parent.borrow_mut().invalidate_children();
+            })));
+
+        let loops = Widget::empty("loops");
+        let ambient_loops = self.area_editor.borrow().model.ambient_loops.clone();
+        for (index, ambient_loop) in ambient_loops.iter().enumerate() {
+            Widget::add_child_to(&loops, self.loop_row(index, ambient_loop));
+        }
+
+        vec![
+            close,
+            music_label,
+            music,
+            combat_music_label,
+            combat_music,
+            ambient_label,
+            ambient,
+            loops_label,
+            add_loop,
+            loops,
+        ]
+    }
+}
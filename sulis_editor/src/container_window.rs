@@ -0,0 +1,260 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{list_box, Button, Label, ListBox, ScrollDirection, ScrollPane};
+use sulis_module::{prop::Interactive, ItemListEntrySaveState, ItemSaveState, Module, Prop};
+
+use crate::AreaEditor;
+
+pub const NAME: &str = "container_window";
+
+pub fn is_container(prop: &Prop) -> bool {
+    matches!(prop.interactive, Interactive::Container { .. })
+}
+
+pub struct ContainerWindow {
+    area_editor: Rc<RefCell<AreaEditor>>,
+    top_bar: Rc<RefCell<Widget>>,
+    selected_prop: Option<usize>,
+}
+
+impl ContainerWindow {
+    pub fn new(
+        area_editor: Rc<RefCell<AreaEditor>>,
+        top_bar: Rc<RefCell<Widget>>,
+        selected_prop: Option<usize>,
+    ) -> Rc<RefCell<ContainerWindow>> {
+        Rc::new(RefCell::new(ContainerWindow {
+            area_editor,
+            top_bar,
+            selected_prop,
+        }))
+    }
+
+    fn modify_items<F: Fn(&mut Vec<ItemListEntrySaveState>)>(widget: &Rc<RefCell<Widget>>, f: F) {
+        let (parent, window) = Widget::parent_mut::<ContainerWindow>(widget);
+        parent.borrow_mut().invalidate_children();
+
+        let index = match window.selected_prop {
+            None => return,
+            Some(index) => index,
+        };
+
+        let mut area_editor = window.area_editor.borrow_mut();
+        f(&mut area_editor.model.prop_mut(index).items);
+    }
+
+    fn add_contents(&self, index: usize, widgets: &mut Vec<Rc<RefCell<Widget>>>) {
+        let area_editor = self.area_editor.borrow();
+        let prop_data = area_editor.model.prop(index);
+
+        let contents = ScrollPane::new(ScrollDirection::Vertical);
+        for (entry_index, entry) in prop_data.items.iter().enumerate() {
+            let name = match Module::item(&entry.item.id) {
+                None => entry.item.id.to_string(),
+                Some(item) => item.name.to_string(),
+            };
+
+            let widget = Widget::empty("entry");
+            let label = Widget::with_theme(Label::empty(), "name");
+            label.borrow_mut().state.add_text_arg("name", &name);
+            label
+                .borrow_mut()
+                .state
+                .add_text_arg("quantity", &entry.quantity.to_string());
+
+            let remove = Widget::with_theme(Button::empty(), "remove");
+            remove
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    ContainerWindow::modify_items(widget, |items| {
+                        if items[entry_index].quantity > 1 {
+                            items[entry_index].quantity -= 1;
+                        } else {
+                            items.remove(entry_index);
+                        }
+                    });
+                })));
+
+            let add = Widget::with_theme(Button::empty(), "add");
+            add.borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    ContainerWindow::modify_items(widget, |items| {
+                        items[entry_index].quantity += 1;
+                    });
+                })));
+
+            Widget::add_children_to(&widget, vec![label, remove, add]);
+            contents.borrow().add_to_content(widget);
+        }
+        widgets.push(Widget::with_theme(contents, "contents"));
+
+        let mut all_items = Module::all_items();
+        all_items.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let items = ScrollPane::new(ScrollDirection::Vertical);
+        for item in all_items {
+            let button = Widget::with_theme(Button::empty(), "item_button");
+            button.borrow_mut().state.add_text_arg("name", &item.id);
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    ContainerWindow::modify_items(widget, |items| {
+                        let existing = items.iter_mut().find(|entry| {
+                            entry.item.id == item.id
+                                && entry.item.adjectives.is_empty()
+                                && entry.item.variant.is_none()
+                        });
+
+                        match existing {
+                            Some(entry) => entry.quantity += 1,
+                            None => items.push(ItemListEntrySaveState {
+                                quantity: 1,
+                                item: ItemSaveState {
+                                    id: item.id.to_string(),
+                                    adjectives: Vec::new(),
+                                    variant: None,
+                                },
+                            }),
+                        }
+                    });
+                })));
+            items.borrow().add_to_content(button);
+        }
+        widgets.push(Widget::with_theme(items, "items"));
+
+        let mut all_loot = Module::all_loot_lists();
+        all_loot.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let cur_loot = prop_data.loot.as_ref().map(|loot| loot.id.to_string());
+        let loot_lists = ScrollPane::new(ScrollDirection::Vertical);
+        let default = Widget::with_theme(Button::empty(), "default_button");
+        default.borrow_mut().state.set_active(cur_loot.is_none());
+        default
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let (parent, window) = Widget::parent_mut::<ContainerWindow>(widget);
+                parent.borrow_mut().invalidate_children();
+
+                if let Some(index) = window.selected_prop {
+                    window.area_editor.borrow_mut().model.prop_mut(index).loot = None;
+                }
+            })));
+        loot_lists.borrow().add_to_content(default);
+
+        for loot in all_loot {
+            let button = Widget::with_theme(Button::empty(), "loot_button");
+            button.borrow_mut().state.add_text_arg("name", &loot.id);
+            button
+                .borrow_mut()
+                .state
+                .set_active(cur_loot.as_deref() == Some(loot.id.as_str()));
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let (parent, window) = Widget::parent_mut::<ContainerWindow>(widget);
+                    parent.borrow_mut().invalidate_children();
+
+                    if let Some(index) = window.selected_prop {
+                        let mut area_editor = window.area_editor.borrow_mut();
+                        area_editor.model.prop_mut(index).loot = Some(Rc::clone(&loot));
+                    }
+                })));
+            loot_lists.borrow().add_to_content(button);
+        }
+        widgets.push(Widget::with_theme(loot_lists, "loot_lists"));
+
+        widgets.push(Widget::with_theme(Label::empty(), "contents_label"));
+        widgets.push(Widget::with_theme(Label::empty(), "items_label"));
+        widgets.push(Widget::with_theme(Label::empty(), "loot_label"));
+    }
+}
+
+impl WidgetKind for ContainerWindow {
+    fn get_name(&self) -> &str {
+        NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_remove(&mut self, _widget: &Rc<RefCell<Widget>>) {
+        self.top_bar.borrow_mut().state.set_enabled(true);
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        self.top_bar.borrow_mut().state.set_enabled(false);
+        let mut widgets: Vec<Rc<RefCell<Widget>>> = Vec::new();
+
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<ContainerWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+        widgets.push(close);
+
+        if let Some(index) = self.selected_prop {
+            self.add_contents(index, &mut widgets);
+        }
+
+        let mut entries: Vec<list_box::Entry<String>> = Vec::new();
+        for (index, prop_data) in self.area_editor.borrow().model.props_iter().enumerate() {
+            if !is_container(&prop_data.prop) {
+                continue;
+            }
+
+            let cb = Callback::new(Rc::new(move |widget, _| {
+                let (parent, window) = Widget::parent_mut::<ContainerWindow>(widget);
+                parent.borrow_mut().invalidate_children();
+
+                window.selected_prop = Some(index);
+            }));
+
+            let loc = prop_data.location;
+            let text = format!("{} ({}, {})", prop_data.prop.id, loc.x, loc.y);
+            let entry = if self.selected_prop == Some(index) {
+                list_box::Entry::with_active(text, Some(cb))
+            } else {
+                list_box::Entry::new(text, Some(cb))
+            };
+
+            entries.push(entry);
+        }
+
+        let containers_box = Widget::with_theme(ListBox::new(entries), "containers_list");
+        widgets.push(containers_box);
+
+        widgets
+    }
+}
@@ -23,20 +23,34 @@ use sulis_core::io::{DrawList, GraphicsRenderer};
 use sulis_core::resource::{ResourceSet, Sprite};
 use sulis_core::ui::{Callback, Color, Widget, WidgetKind};
 use sulis_core::util::{Offset, Point, Rect, Scale};
-use sulis_core::widgets::{Label, Spinner};
+use sulis_core::widgets::{Button, Label, Spinner};
 use sulis_module::area::MAX_AREA_SIZE;
 
 use crate::{AreaModel, EditorMode};
 
 const NAME: &str = "elevation_picker";
 
+const STEEP_CHECK_MILLIS: u32 = 500;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BrushMode {
+    Set,
+    Raise,
+    Lower,
+}
+
 pub struct ElevPicker {
     cursor_sprite: Rc<Sprite>,
     cursor_pos: Option<Point>,
     elev_tiles: Vec<Rc<Sprite>>,
 
     brush_size: i32,
+    brush_mode: BrushMode,
     set_elev_to: u8,
+    shaded: bool,
+
+    steep_points: Vec<Point>,
+    last_steep_check: Option<u32>,
 }
 
 impl ElevPicker {
@@ -60,19 +74,24 @@ impl ElevPicker {
             cursor_pos: None,
             elev_tiles,
             brush_size: 4,
+            brush_mode: BrushMode::Set,
             set_elev_to: 1,
+            shaded: false,
+            steep_points: Vec::new(),
+            last_steep_check: None,
         }))
     }
-}
 
-impl EditorMode for ElevPicker {
-    fn draw_mode(
-        &mut self,
+    fn max_elev(&self) -> u8 {
+        (self.elev_tiles.len().max(1) - 1) as u8
+    }
+
+    fn draw_tiles(
+        &self,
         renderer: &mut dyn GraphicsRenderer,
         model: &AreaModel,
         offset: Offset,
         scale: Scale,
-        _millis: u32,
     ) {
         let mut draw_list = DrawList::empty_sprite();
         for y in 0..MAX_AREA_SIZE {
@@ -96,6 +115,98 @@ impl EditorMode for ElevPicker {
         draw_list.set_scale(scale);
 
         renderer.draw(draw_list);
+    }
+
+    fn draw_shaded(
+        &self,
+        renderer: &mut dyn GraphicsRenderer,
+        model: &AreaModel,
+        offset: Offset,
+        scale: Scale,
+    ) {
+        let max = self.max_elev();
+        let mut levels: Vec<DrawList> = (0..=max).map(|_| DrawList::empty_sprite()).collect();
+        for y in 0..MAX_AREA_SIZE {
+            for x in 0..MAX_AREA_SIZE {
+                let elev = model.tiles().elevation(x, y).min(max) as usize;
+                let rect = Rect {
+                    x: x as f32 + offset.x,
+                    y: y as f32 + offset.y,
+                    w: 1.0,
+                    h: 1.0,
+                };
+                levels[elev].append(&mut DrawList::from_sprite_f32(&self.cursor_sprite, rect));
+            }
+        }
+
+        for (elev, mut draw_list) in levels.into_iter().enumerate() {
+            if draw_list.quads.is_empty() {
+                continue;
+            }
+
+            // shade from dark at the lowest level to light at the highest
+            let value = 0.1 + 0.9 * elev as f32 / max.max(1) as f32;
+            draw_list.set_scale(scale);
+            draw_list.set_color(Color::new(value, value, value, 0.6));
+            renderer.draw(draw_list);
+        }
+    }
+
+    fn paint(&self, model: &mut AreaModel, x: i32, y: i32, mode: BrushMode) {
+        let max = self.max_elev();
+        for y in y..(y + self.brush_size) {
+            for x in x..(x + self.brush_size) {
+                let cur = model.tiles().elevation(x, y);
+                let elev = match mode {
+                    BrushMode::Set => self.set_elev_to,
+                    BrushMode::Raise => cur.saturating_add(1).min(max),
+                    BrushMode::Lower => cur.saturating_sub(1),
+                };
+                model.set_elevation(elev, x, y);
+            }
+        }
+    }
+}
+
+impl EditorMode for ElevPicker {
+    fn draw_mode(
+        &mut self,
+        renderer: &mut dyn GraphicsRenderer,
+        model: &AreaModel,
+        offset: Offset,
+        scale: Scale,
+        millis: u32,
+    ) {
+        if self.shaded {
+            self.draw_shaded(renderer, model, offset, scale);
+        } else {
+            self.draw_tiles(renderer, model, offset, scale);
+        }
+
+        let recheck = match self.last_steep_check {
+            None => true,
+            Some(last) => millis.saturating_sub(last) >= STEEP_CHECK_MILLIS,
+        };
+        if recheck {
+            self.steep_points = model.steep_elevation_points();
+            self.last_steep_check = Some(millis);
+        }
+
+        if !self.steep_points.is_empty() {
+            let mut draw_list = DrawList::empty_sprite();
+            for p in self.steep_points.iter() {
+                let rect = Rect {
+                    x: p.x as f32 + offset.x,
+                    y: p.y as f32 + offset.y,
+                    w: 1.0,
+                    h: 1.0,
+                };
+                draw_list.append(&mut DrawList::from_sprite_f32(&self.cursor_sprite, rect));
+            }
+            draw_list.set_scale(scale);
+            draw_list.set_color(Color::from_string("F008"));
+            renderer.draw(draw_list);
+        }
 
         let mut draw_list = DrawList::empty_sprite();
         if let Some(pos) = self.cursor_pos {
@@ -125,19 +236,27 @@ impl EditorMode for ElevPicker {
     }
 
     fn left_click(&mut self, model: &mut AreaModel, x: i32, y: i32) {
-        for y in y..(y + self.brush_size) {
-            for x in x..(x + self.brush_size) {
-                model.set_elevation(self.set_elev_to, x, y);
-            }
-        }
+        self.paint(model, x, y, self.brush_mode);
+        self.last_steep_check = None;
     }
 
     fn right_click(&mut self, model: &mut AreaModel, x: i32, y: i32) {
-        for y in y..(y + self.brush_size) {
-            for x in x..(x + self.brush_size) {
-                model.set_elevation(0, x, y);
+        // right click undoes the current brush
+        let mode = match self.brush_mode {
+            BrushMode::Raise => BrushMode::Lower,
+            BrushMode::Lower => BrushMode::Raise,
+            BrushMode::Set => {
+                for y in y..(y + self.brush_size) {
+                    for x in x..(x + self.brush_size) {
+                        model.set_elevation(0, x, y);
+                    }
+                }
+                self.last_steep_check = None;
+                return;
             }
-        }
+        };
+        self.paint(model, x, y, mode);
+        self.last_steep_check = None;
     }
 }
 
@@ -195,6 +314,54 @@ impl WidgetKind for ElevPicker {
 
         let elev_label = Widget::with_theme(Label::empty(), "elev_label");
 
-        vec![brush_size, brush_size_label, elev, elev_label]
+        let modes = Widget::empty("brush_modes");
+        for (mode, theme) in [
+            (BrushMode::Set, "set"),
+            (BrushMode::Raise, "raise"),
+            (BrushMode::Lower, "lower"),
+        ] {
+            let button = Widget::with_theme(Button::empty(), theme);
+            button
+                .borrow_mut()
+                .state
+                .set_active(mode == self.brush_mode);
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let parent = Widget::direct_parent(widget);
+                    for child in parent.borrow().children.iter() {
+                        child.borrow_mut().state.set_active(false);
+                    }
+                    widget.borrow_mut().state.set_active(true);
+
+                    let (_, picker) = Widget::parent_mut::<ElevPicker>(widget);
+                    picker.brush_mode = mode;
+                })));
+            Widget::add_child_to(&modes, button);
+        }
+
+        let shaded = Widget::with_theme(Button::empty(), "shaded");
+        shaded.borrow_mut().state.set_active(self.shaded);
+        shaded
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (_, picker) = Widget::parent_mut::<ElevPicker>(widget);
+                picker.shaded = !picker.shaded;
+                widget.borrow_mut().state.set_active(picker.shaded);
+            })));
+
+        let steep_label = Widget::with_theme(Label::empty(), "steep_label");
+
+        vec![
+            brush_size,
+            brush_size_label,
+            elev,
+            elev_label,
+            modes,
+            shaded,
+            steep_label,
+        ]
     }
 }
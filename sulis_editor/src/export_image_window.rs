@@ -0,0 +1,93 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label, Spinner};
+
+use crate::{area_model, AreaEditor};
+
+pub const NAME: &str = "export_image_window";
+
+const DEFAULT_SCALE: i32 = 16;
+
+pub struct ExportImageWindow {
+    area_editor: Rc<RefCell<AreaEditor>>,
+}
+
+impl ExportImageWindow {
+    pub fn new(area_editor: Rc<RefCell<AreaEditor>>) -> Rc<RefCell<ExportImageWindow>> {
+        Rc::new(RefCell::new(ExportImageWindow { area_editor }))
+    }
+}
+
+impl WidgetKind for ExportImageWindow {
+    fn get_name(&self) -> &str {
+        NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<ExportImageWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let scale_label = Widget::with_theme(Label::empty(), "scale_label");
+        let scale_spinner = Spinner::new(DEFAULT_SCALE, 1, 64);
+        let scale_spinner_widget = Widget::with_theme(scale_spinner.clone(), "scale_spinner");
+
+        let area_editor_ref = Rc::clone(&self.area_editor);
+        let export = Widget::with_theme(Button::empty(), "export_button");
+        export
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let (parent, _) = Widget::parent::<ExportImageWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+
+                let dir = area_model::export_dir();
+                if let Err(e) = fs::create_dir_all(&dir) {
+                    warn!("Unable to create editor export dir {:?}: {}", dir, e);
+                    return;
+                }
+
+                let area_editor = area_editor_ref.borrow();
+                let mut path = dir;
+                path.push(format!("{}.png", area_editor.model.filename()));
+
+                let scale = scale_spinner.borrow().value() as u32;
+                area_editor.model.export_image(&path, scale);
+            })));
+
+        vec![close, scale_label, scale_spinner_widget, export]
+    }
+}
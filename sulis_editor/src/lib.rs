@@ -20,6 +20,8 @@
 mod actor_picker;
 use crate::actor_picker::ActorPicker;
 
+mod actor_window;
+
 mod actor_creator_window;
 use crate::actor_creator_window::ActorCreatorWindow;
 
@@ -29,27 +31,44 @@ use crate::area_editor::AreaEditor;
 mod area_model;
 use crate::area_model::AreaModel;
 
+mod audio_window;
+use crate::audio_window::AudioWindow;
+
+mod container_window;
+use crate::container_window::ContainerWindow;
+
 mod elev_picker;
 use crate::elev_picker::ElevPicker;
 
 mod encounter_picker;
 use crate::encounter_picker::EncounterPicker;
 
+mod export_image_window;
+use crate::export_image_window::ExportImageWindow;
+
 mod feature_picker;
 use crate::feature_picker::FeaturePicker;
 
 mod load_window;
 use crate::load_window::LoadWindow;
 
+mod map_image;
+
 mod pass_picker;
 use crate::pass_picker::PassPicker;
 
 mod prop_picker;
 use crate::prop_picker::PropPicker;
 
+mod recovery_window;
+use crate::recovery_window::RecoveryWindow;
+
 mod save_window;
 use crate::save_window::SaveWindow;
 
+mod search_window;
+use crate::search_window::SearchWindow;
+
 mod shift_tiles_window;
 use crate::shift_tiles_window::ShiftTilesWindow;
 
@@ -62,9 +81,15 @@ use crate::tile_picker::TilePicker;
 mod transition_window;
 use crate::transition_window::TransitionWindow;
 
+mod traversal_picker;
+use crate::traversal_picker::TraversalPicker;
+
 mod trigger_picker;
 use crate::trigger_picker::TriggerPicker;
 
+mod validation_window;
+use crate::validation_window::ValidationWindow;
+
 mod vis_picker;
 use crate::vis_picker::VisPicker;
 
@@ -82,6 +107,8 @@ use sulis_core::io::{GraphicsRenderer, InputActionKind, ControlFlowUpdater};
 use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::util::{Offset, Scale};
 use sulis_core::widgets::{list_box, Button, ConfirmationWindow, DropDown};
+use sulis_module::area::Tile;
+use sulis_module::{Actor, Prop};
 
 thread_local! {
     static EXIT: Cell<bool> = Cell::new(false);
@@ -142,6 +169,53 @@ pub trait EditorMode: WidgetKind {
     fn mouse_scroll(&mut self, _model: &mut AreaModel, _delta: i32) {}
 }
 
+/// The set of pickers used to edit the current area, at most one of which
+/// is active at a time
+pub struct EditorModes {
+    area_editor: Rc<RefCell<AreaEditor>>,
+    names: Vec<&'static str>,
+    pickers: Vec<Rc<RefCell<Widget>>>,
+    picker_kinds: Vec<Rc<RefCell<dyn EditorMode>>>,
+    tile_picker: Rc<RefCell<TilePicker>>,
+    actor_picker: Rc<RefCell<ActorPicker>>,
+    prop_picker: Rc<RefCell<PropPicker>>,
+}
+
+impl EditorModes {
+    pub fn activate(&self, index: usize) {
+        self.pickers
+            .iter()
+            .for_each(|p| p.borrow_mut().state.set_visible(false));
+        self.pickers[index].borrow_mut().state.set_visible(true);
+        self.pickers[index].borrow_mut().invalidate_children();
+        self.area_editor
+            .borrow_mut()
+            .set_editor(self.picker_kinds[index].clone());
+    }
+
+    fn activate_named(&self, name: &str) {
+        match self.names.iter().position(|n| *n == name) {
+            None => warn!("No editor mode named '{}'", name),
+            Some(index) => self.activate(index),
+        }
+    }
+
+    pub fn select_tile(&self, tile: Rc<Tile>) {
+        self.tile_picker.borrow_mut().select(tile);
+        self.activate_named("Tiles");
+    }
+
+    pub fn select_actor(&self, actor: Rc<Actor>) {
+        self.actor_picker.borrow_mut().select(actor);
+        self.activate_named("Actors");
+    }
+
+    pub fn select_prop(&self, prop: Rc<Prop>) {
+        self.prop_picker.borrow_mut().select(prop);
+        self.activate_named("Props");
+    }
+}
+
 const NAME: &str = "editor";
 
 pub struct EditorView {}
@@ -234,6 +308,23 @@ impl WidgetKind for EditorView {
             );
             entries.push(load);
 
+            let area_editor_kind_ref = Rc::clone(&area_editor_kind);
+            let export_image = list_box::Entry::new(
+                "Export Image".to_string(),
+                Some(Callback::with_widget(Rc::new(move |widget| {
+                    let root = Widget::get_root(widget);
+                    let export_window = Widget::with_defaults(ExportImageWindow::new(
+                        Rc::clone(&area_editor_kind_ref),
+                    ));
+                    export_window.borrow_mut().state.set_modal(true);
+                    Widget::add_child_to(&root, export_window);
+
+                    let parent = Widget::direct_parent(widget);
+                    parent.borrow_mut().mark_for_removal();
+                }))),
+            );
+            entries.push(export_image);
+
             let quit = list_box::Entry::new(
                 "Quit".to_string(),
                 Some(Callback::with_widget(Rc::new(move |widget| {
@@ -272,6 +363,24 @@ impl WidgetKind for EditorView {
                     Widget::add_child_to(&root, transition_window);
                 })));
 
+            let containers = Widget::with_theme(Button::empty(), "containers");
+
+            let top_bar_ref = Rc::clone(&top_bar);
+            let area_editor_kind_ref = Rc::clone(&area_editor_kind);
+            containers
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let root = Widget::get_root(widget);
+                    let container_window = Widget::with_defaults(ContainerWindow::new(
+                        Rc::clone(&area_editor_kind_ref),
+                        Rc::clone(&top_bar_ref),
+                        None,
+                    ));
+                    container_window.borrow_mut().state.set_modal(true);
+                    Widget::add_child_to(&root, container_window);
+                })));
+
             let area_editor_kind_ref = Rc::clone(&area_editor_kind);
             let shift_tiles = Widget::with_theme(Button::empty(), "shift_tiles");
             shift_tiles
@@ -301,17 +410,19 @@ impl WidgetKind for EditorView {
             Widget::add_child_to(&top_bar, transitions);
             Widget::add_child_to(&top_bar, shift_tiles);
             Widget::add_child_to(&top_bar, actor_creator);
+            Widget::add_child_to(&top_bar, containers);
         }
 
         let tile_picker_kind = TilePicker::new();
         let terrain_picker_kind = TerrainPicker::new();
         let wall_picker_kind = WallPicker::new();
         let feature_picker_kind = FeaturePicker::new();
-        let actor_picker_kind = ActorPicker::new();
-        let prop_picker_kind = PropPicker::new();
+        let actor_picker_kind = ActorPicker::new(Rc::clone(&area_editor_kind));
+        let prop_picker_kind = PropPicker::new(Rc::clone(&area_editor_kind), Rc::clone(&top_bar));
         let elev_picker_kind = ElevPicker::new();
         let encounter_picker_kind = EncounterPicker::new();
         let trigger_picker_kind = TriggerPicker::new();
+        let traversal_picker_kind = TraversalPicker::new();
         let pass_picker_kind = PassPicker::new();
         let vis_picker_kind = VisPicker::new();
 
@@ -325,6 +436,7 @@ impl WidgetKind for EditorView {
             Widget::with_defaults(elev_picker_kind.clone()),
             Widget::with_defaults(encounter_picker_kind.clone()),
             Widget::with_defaults(trigger_picker_kind.clone()),
+            Widget::with_defaults(traversal_picker_kind.clone()),
             Widget::with_defaults(pass_picker_kind.clone()),
             Widget::with_defaults(vis_picker_kind.clone()),
         ];
@@ -333,15 +445,16 @@ impl WidgetKind for EditorView {
         }
 
         let picker_kinds: Vec<Rc<RefCell<dyn EditorMode>>> = vec![
-            tile_picker_kind,
+            tile_picker_kind.clone(),
             terrain_picker_kind,
             wall_picker_kind,
             feature_picker_kind,
-            actor_picker_kind,
-            prop_picker_kind,
+            actor_picker_kind.clone(),
+            prop_picker_kind.clone(),
             elev_picker_kind,
             encounter_picker_kind,
             trigger_picker_kind,
+            traversal_picker_kind,
             pass_picker_kind,
             vis_picker_kind,
         ];
@@ -356,6 +469,7 @@ impl WidgetKind for EditorView {
             "Elevation",
             "Encounters",
             "Triggers",
+            "Traversals",
             "Passability",
             "Visibility",
         ];
@@ -364,22 +478,23 @@ impl WidgetKind for EditorView {
         assert!(names.len() == picker_kinds.len());
         assert!(names.len() == pickers.len());
 
+        let modes = Rc::new(EditorModes {
+            area_editor: Rc::clone(&area_editor_kind),
+            names: names.clone(),
+            pickers: pickers.clone(),
+            picker_kinds,
+            tile_picker: tile_picker_kind,
+            actor_picker: actor_picker_kind,
+            prop_picker: prop_picker_kind,
+        });
+
         let mut entries: Vec<list_box::Entry<String>> = Vec::new();
         for (index, name) in names.into_iter().enumerate() {
-            let pickers_ref = pickers.clone();
-            let picker_kinds_ref = picker_kinds.clone();
-            let area_editor_ref = Rc::clone(&area_editor_kind);
+            let modes_ref = Rc::clone(&modes);
             entries.push(list_box::Entry::new(
                 name.to_string(),
                 Some(Callback::new(Rc::new(move |widget, _| {
-                    pickers_ref
-                        .iter()
-                        .for_each(|p| p.borrow_mut().state.set_visible(false));
-                    pickers_ref[index].borrow_mut().state.set_visible(true);
-                    pickers_ref[index].borrow_mut().invalidate_children();
-                    area_editor_ref
-                        .borrow_mut()
-                        .set_editor(picker_kinds_ref[index].clone());
+                    modes_ref.activate(index);
 
                     let parent = Widget::direct_parent(widget);
                     parent.borrow_mut().mark_for_removal();
@@ -387,8 +502,34 @@ impl WidgetKind for EditorView {
             ));
         }
         let drop_down = DropDown::new(entries, "modes_list");
-        let modes = Widget::with_theme(drop_down, "modes");
-        Widget::add_child_to(&top_bar, modes);
+        let modes_widget = Widget::with_theme(drop_down, "modes");
+        Widget::add_child_to(&top_bar, modes_widget);
+
+        let search = Widget::with_theme(Button::empty(), "search");
+        search
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let root = Widget::get_root(widget);
+                let window = Widget::with_defaults(SearchWindow::new(Rc::clone(&modes)));
+                window.borrow_mut().state.set_modal(true);
+                Widget::add_child_to(&root, window);
+            })));
+        Widget::add_child_to(&top_bar, search);
+
+        let area_editor_kind_ref = Rc::clone(&area_editor_kind);
+        let audio = Widget::with_theme(Button::empty(), "audio");
+        audio
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let root = Widget::get_root(widget);
+                let window =
+                    Widget::with_defaults(AudioWindow::new(Rc::clone(&area_editor_kind_ref)));
+                window.borrow_mut().state.set_modal(true);
+                Widget::add_child_to(&root, window);
+            })));
+        Widget::add_child_to(&top_bar, audio);
 
         let area_editor = Widget::with_defaults(area_editor_kind);
 
@@ -25,7 +25,7 @@ use sulis_core::config::Config;
 use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::widgets::{list_box, Button, ListBox, ScrollDirection, ScrollPane};
 
-use crate::AreaEditor;
+use crate::{area_model, AreaEditor, RecoveryWindow};
 
 pub const NAME: &str = "load_window";
 
@@ -65,12 +65,7 @@ impl WidgetKind for LoadWindow {
         let load = Widget::with_theme(Button::empty(), "load_button");
         load.borrow_mut().state.set_enabled(false);
 
-        let campaigns_dir = Config::resources_config().campaigns_directory;
-        let dir_str = format!(
-            "../{}/{}/areas/",
-            campaigns_dir,
-            Config::editor_config().module
-        );
+        let dir_str = areas_dir();
         let areas = get_area_entries(&dir_str);
 
         let load_ref = Rc::clone(&load);
@@ -109,10 +104,10 @@ impl WidgetKind for LoadWindow {
                 let area = &active_child.borrow().state.text;
                 info!("Selected area to load: {}", area);
 
-                area_editor_ref.borrow_mut().model.load(&dir_str, area);
-
                 let (parent, _) = Widget::parent::<LoadWindow>(widget);
                 parent.borrow_mut().mark_for_removal();
+
+                open_area(widget, &area_editor_ref, &dir_str, area);
             })));
         scrollpane.borrow().add_to_content(areas_list);
 
@@ -120,7 +115,40 @@ impl WidgetKind for LoadWindow {
     }
 }
 
-fn get_area_entries(dir_str: &str) -> Vec<String> {
+/// The directory containing the area files of the module being edited
+pub fn areas_dir() -> String {
+    let campaigns_dir = Config::resources_config().campaigns_directory;
+    format!(
+        "../{}/{}/areas/",
+        campaigns_dir,
+        Config::editor_config().module
+    )
+}
+
+/// Loads the specified area file into the area editor, first asking whether
+/// to recover a newer backup of it if one exists
+pub fn open_area(
+    widget: &Rc<RefCell<Widget>>,
+    area_editor: &Rc<RefCell<AreaEditor>>,
+    dir_str: &str,
+    area: &str,
+) {
+    if !area_model::has_newer_backup(dir_str, area) {
+        area_editor.borrow_mut().load_area(dir_str, area, false);
+        return;
+    }
+
+    let root = Widget::get_root(widget);
+    let window = Widget::with_defaults(RecoveryWindow::new(
+        Rc::clone(area_editor),
+        dir_str.to_string(),
+        area.to_string(),
+    ));
+    window.borrow_mut().state.set_modal(true);
+    Widget::add_child_to(&root, window);
+}
+
+pub fn get_area_entries(dir_str: &str) -> Vec<String> {
     let mut areas: Vec<String> = Vec::new();
 
     debug!("Reading area files from {}", dir_str);
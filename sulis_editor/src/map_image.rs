@@ -0,0 +1,112 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use sulis_core::config::Config;
+use sulis_core::extern_image::{Rgba, RgbaImage};
+use sulis_core::io::{DrawList, DrawListKind};
+use sulis_core::resource::ResourceSet;
+
+/// Rasterizes the sprite draw lists, specified in grid units, onto a new
+/// image of `width` by `height` grid squares, each taking `scale` pixels.
+/// Draw lists are composited in order.  This mirrors what the renderer
+/// does for axis aligned quads, without needing a graphics context.
+pub fn render(draw_lists: &[DrawList], width: i32, height: i32, scale: u32) -> RgbaImage {
+    let mut image = RgbaImage::new(width as u32 * scale, height as u32 * scale);
+    let ui_height = Config::ui_height() as f32;
+
+    for draw_list in draw_lists {
+        if matches!(draw_list.kind, DrawListKind::Font) {
+            continue;
+        }
+
+        let sheet = match ResourceSet::spritesheet(&draw_list.texture) {
+            None => {
+                warn!("Unable to find spritesheet '{}' for export", draw_list.texture);
+                continue;
+            }
+            Some(sheet) => sheet,
+        };
+
+        for quad in draw_list.quads.chunks_exact(6) {
+            // the first and fourth vertices are opposite corners of the quad
+            let (a, b) = (quad[0], quad[3]);
+            let rect = [
+                a.position[0],
+                ui_height - a.position[1],
+                b.position[0],
+                ui_height - b.position[1],
+            ];
+            let tex = [a.tex_coords[0], a.tex_coords[1], b.tex_coords[0], b.tex_coords[1]];
+            draw_quad(&mut image, &sheet.image, rect, tex, scale, draw_list.color_filter);
+        }
+    }
+
+    image
+}
+
+fn draw_quad(
+    dest: &mut RgbaImage,
+    src: &RgbaImage,
+    rect: [f32; 4],
+    tex: [f32; 4],
+    scale: u32,
+    color: [f32; 4],
+) {
+    let scale = scale as f32;
+    let (src_w, src_h) = (src.width() as f32, src.height() as f32);
+    let (quad_w, quad_h) = (rect[2] - rect[0], rect[3] - rect[1]);
+    if quad_w <= 0.0 || quad_h <= 0.0 {
+        return;
+    }
+
+    let min_x = (rect[0] * scale).floor().max(0.0) as u32;
+    let min_y = (rect[1] * scale).floor().max(0.0) as u32;
+    let max_x = ((rect[2] * scale).ceil() as u32).min(dest.width());
+    let max_y = ((rect[3] * scale).ceil() as u32).min(dest.height());
+
+    for y in min_y..max_y {
+        let frac_y = ((y as f32 + 0.5) / scale - rect[1]) / quad_h;
+        let tex_y = tex[1] + frac_y * (tex[3] - tex[1]);
+        // texture coordinates have their origin at the bottom of the image
+        let src_y = ((1.0 - tex_y) * src_h).floor().clamp(0.0, src_h - 1.0) as u32;
+
+        for x in min_x..max_x {
+            let frac_x = ((x as f32 + 0.5) / scale - rect[0]) / quad_w;
+            let tex_x = tex[0] + frac_x * (tex[2] - tex[0]);
+            let src_x = (tex_x * src_w).floor().clamp(0.0, src_w - 1.0) as u32;
+
+            let pixel = src.get_pixel(src_x, src_y);
+            blend(dest.get_pixel_mut(x, y), pixel, color);
+        }
+    }
+}
+
+fn blend(dest: &mut Rgba<u8>, src: &Rgba<u8>, color: [f32; 4]) {
+    let alpha = src[3] as f32 / 255.0 * color[3];
+    if alpha <= 0.0 {
+        return;
+    }
+
+    let dest_alpha = dest[3] as f32 / 255.0;
+    let out_alpha = alpha + dest_alpha * (1.0 - alpha);
+    for i in 0..3 {
+        let src_c = src[i] as f32 * color[i];
+        let dest_c = dest[i] as f32;
+        let out = (src_c * alpha + dest_c * dest_alpha * (1.0 - alpha)) / out_alpha;
+        dest[i] = out.round().clamp(0.0, 255.0) as u8;
+    }
+    dest[3] = (out_alpha * 255.0).round() as u8;
+}
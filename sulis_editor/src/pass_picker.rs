@@ -14,6 +14,7 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
+
 use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -21,22 +22,45 @@ use std::rc::Rc;
 use sulis_core::config::Config;
 use sulis_core::io::{DrawList, GraphicsRenderer};
 use sulis_core::resource::{ResourceSet, Sprite};
-use sulis_core::ui::{Color, Widget, WidgetKind};
-use sulis_core::util::{Offset, Rect, Scale};
+use sulis_core::ui::{Callback, Color, Widget, WidgetKind};
+use sulis_core::util::{Offset, Point, Rect, Scale};
+use sulis_core::widgets::{Button, Label, Spinner};
+use sulis_module::Module;
 
 use crate::{AreaModel, EditorMode};
 
-const NAME: &str = "elevation_picker";
+const NAME: &str = "pass_picker";
 
+/// Shows the impassable points of the area's tiles, and allows overriding
+/// the passability and movement cost of individual placed tiles.  Left
+/// click sets an override on the top most tile under the cursor, right
+/// click removes it.
 pub struct PassPicker {
     cursor_sprite: Rc<Sprite>,
+    cursor_pos: Option<Point>,
+
+    passable: Option<bool>,
+    movement_cost: u32,
 }
 
 impl PassPicker {
     pub fn new() -> Rc<RefCell<PassPicker>> {
         let cursor_sprite = ResourceSet::panic_or_sprite(&Config::editor_config().cursor);
 
-        Rc::new(RefCell::new(PassPicker { cursor_sprite }))
+        Rc::new(RefCell::new(PassPicker {
+            cursor_sprite,
+            cursor_pos: None,
+            passable: None,
+            movement_cost: 1,
+        }))
+    }
+
+    fn next_passable(&mut self) {
+        self.passable = match self.passable {
+            None => Some(false),
+            Some(false) => Some(true),
+            Some(true) => None,
+        };
     }
 }
 
@@ -65,19 +89,54 @@ impl EditorMode for PassPicker {
         }
         draw_list.set_scale(scale);
         draw_list.set_color(Color::from_string("F008"));
+        renderer.draw(draw_list);
 
+        let mut draw_list = DrawList::empty_sprite();
+        for tile_override in model.tile_overrides() {
+            let (w, h) = match Module::tile(&tile_override.tile) {
+                None => (1.0, 1.0),
+                Some(tile) => (tile.width as f32, tile.height as f32),
+            };
+            let rect = Rect {
+                x: offset.x + tile_override.location.x as f32,
+                y: offset.y + tile_override.location.y as f32,
+                w,
+                h,
+            };
+            draw_list.append(&mut DrawList::from_sprite_f32(&self.cursor_sprite, rect));
+        }
+        draw_list.set_scale(scale);
+        draw_list.set_color(Color::from_string("00F6"));
         renderer.draw(draw_list);
+
+        if let Some(pos) = self.cursor_pos {
+            let rect = Rect {
+                x: offset.x + pos.x as f32,
+                y: offset.y + pos.y as f32,
+                w: 1.0,
+                h: 1.0,
+            };
+            let mut draw_list = DrawList::from_sprite_f32(&self.cursor_sprite, rect);
+            draw_list.set_scale(scale);
+            renderer.draw(draw_list);
+        }
     }
 
     fn cursor_size(&self) -> (i32, i32) {
         (1, 1)
     }
 
-    fn mouse_move(&mut self, _model: &mut AreaModel, _x: i32, _y: i32) {}
+    fn mouse_move(&mut self, _model: &mut AreaModel, x: i32, y: i32) {
+        self.cursor_pos = Some(Point::new(x, y));
+    }
 
-    fn left_click(&mut self, _model: &mut AreaModel, _x: i32, _y: i32) {}
+    fn left_click(&mut self, model: &mut AreaModel, x: i32, y: i32) {
+        model.set_tile_override(x, y, self.passable, self.movement_cost);
+    }
 
-    fn right_click(&mut self, _model: &mut AreaModel, _x: i32, _y: i32) {}
+    fn right_click(&mut self, model: &mut AreaModel, x: i32, y: i32) {
+        model.remove_tile_overrides_within(x, y, 1, 1);
+    }
 }
 
 impl WidgetKind for PassPicker {
@@ -94,6 +153,39 @@ impl WidgetKind for PassPicker {
     }
 
     fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
-        Vec::new()
+        let passable = Widget::with_theme(Button::empty(), "passable");
+        let passable_text = match self.passable {
+            None => "Unchanged",
+            Some(true) => "Passable",
+            Some(false) => "Impassable",
+        };
+        passable.borrow_mut().state.add_text_arg("passable", passable_text);
+        passable
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, picker) = Widget::parent_mut::<PassPicker>(widget);
+                picker.next_passable();
+                parent.borrow_mut().invalidate_children();
+            })));
+
+        let cost = Widget::with_theme(Spinner::new(self.movement_cost as i32, 0, 10), "cost");
+        cost.borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, kind| {
+                let (_, picker) = Widget::parent_mut::<PassPicker>(widget);
+
+                let spinner = match kind.as_any().downcast_ref::<Spinner>() {
+                    None => panic!("Unable to downcast to spinner"),
+                    Some(widget) => widget,
+                };
+
+                picker.movement_cost = spinner.value() as u32;
+            })));
+
+        let cost_label = Widget::with_theme(Label::empty(), "cost_label");
+        let help = Widget::with_theme(Label::empty(), "help");
+
+        vec![passable, cost_label, cost, help]
     }
 }
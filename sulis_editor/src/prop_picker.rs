@@ -24,24 +24,38 @@ use sulis_core::util::{Offset, Point, Scale};
 use sulis_core::widgets::{Button, ScrollDirection, ScrollPane};
 use sulis_module::{Module, Prop};
 
-use crate::{AreaModel, EditorMode};
+use crate::container_window::{is_container, ContainerWindow};
+use crate::{AreaEditor, AreaModel, EditorMode};
 
 const NAME: &str = "prop_picker";
 
 pub struct PropPicker {
+    area_editor: Rc<RefCell<AreaEditor>>,
+    top_bar: Rc<RefCell<Widget>>,
     cur_prop: Option<Rc<Prop>>,
     removal_props: Vec<(Point, Rc<Prop>)>,
     cursor_pos: Option<Point>,
+    placed_container: Option<usize>,
 }
 
 impl PropPicker {
-    pub fn new() -> Rc<RefCell<PropPicker>> {
+    pub fn new(
+        area_editor: Rc<RefCell<AreaEditor>>,
+        top_bar: Rc<RefCell<Widget>>,
+    ) -> Rc<RefCell<PropPicker>> {
         Rc::new(RefCell::new(PropPicker {
+            area_editor,
+            top_bar,
             cur_prop: None,
             removal_props: Vec::new(),
             cursor_pos: None,
+            placed_container: None,
         }))
     }
+
+    pub fn select(&mut self, prop: Rc<Prop>) {
+        self.cur_prop = Some(prop);
+    }
 }
 
 impl EditorMode for PropPicker {
@@ -110,7 +124,13 @@ impl EditorMode for PropPicker {
             Some(ref prop) => prop,
         };
 
-        model.add_prop(Rc::clone(prop), x, y);
+        let index = model.add_prop(Rc::clone(prop), x, y);
+
+        // the contents window is opened on the next update, once the area
+        // editor is no longer borrowed
+        if is_container(prop) {
+            self.placed_container = index;
+        }
     }
 
     fn right_click(&mut self, model: &mut AreaModel, x: i32, y: i32) {
@@ -137,6 +157,22 @@ impl WidgetKind for PropPicker {
         self
     }
 
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, _millis: u32) {
+        let index = match self.placed_container.take() {
+            None => return,
+            Some(index) => index,
+        };
+
+        let root = Widget::get_root(widget);
+        let window = Widget::with_defaults(ContainerWindow::new(
+            Rc::clone(&self.area_editor),
+            Rc::clone(&self.top_bar),
+            Some(index),
+        ));
+        window.borrow_mut().state.set_modal(true);
+        Widget::add_child_to(&root, window);
+    }
+
     fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
         let mut all_props = Module::all_props();
         all_props.sort_by(|a, b| a.id.cmp(&b.id));
@@ -145,6 +181,10 @@ impl WidgetKind for PropPicker {
         for prop in all_props {
             let button = Widget::with_theme(Button::empty(), "prop_button");
             button.borrow_mut().state.add_text_arg("name", &prop.id);
+            if let Some(cur_prop) = self.cur_prop.as_ref() {
+                let active = Rc::ptr_eq(cur_prop, &prop);
+                button.borrow_mut().state.set_active(active);
+            }
             button
                 .borrow_mut()
                 .state
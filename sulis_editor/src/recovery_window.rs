@@ -0,0 +1,115 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label};
+
+use crate::{area_model, AreaEditor};
+
+pub const NAME: &str = "recovery_window";
+
+/// Shown when loading an area that has an automatic backup newer than its
+/// saved file.  Allows the user to load either the backup or the saved file.
+pub struct RecoveryWindow {
+    area_editor: Rc<RefCell<AreaEditor>>,
+    filename_prefix: String,
+    filename: String,
+}
+
+impl RecoveryWindow {
+    pub fn new(
+        area_editor: Rc<RefCell<AreaEditor>>,
+        filename_prefix: String,
+        filename: String,
+    ) -> Rc<RefCell<RecoveryWindow>> {
+        Rc::new(RefCell::new(RecoveryWindow {
+            area_editor,
+            filename_prefix,
+            filename,
+        }))
+    }
+}
+
+impl WidgetKind for RecoveryWindow {
+    fn get_name(&self) -> &str {
+        NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<RecoveryWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let message = Widget::with_theme(Label::empty(), "message");
+        message
+            .borrow_mut()
+            .state
+            .add_text_arg("filename", &self.filename);
+
+        let recover = Widget::with_theme(Button::empty(), "recover");
+        let area_editor = Rc::clone(&self.area_editor);
+        let filename = self.filename.clone();
+        recover
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let (parent, _) = Widget::parent::<RecoveryWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+
+                info!("Recovering area '{}' from backup", filename);
+                let dir = area_model::backup_dir();
+                area_editor
+                    .borrow_mut()
+                    .load_area(&dir.to_string_lossy(), &filename, true);
+            })));
+
+        let discard = Widget::with_theme(Button::empty(), "discard");
+        let area_editor = Rc::clone(&self.area_editor);
+        let filename_prefix = self.filename_prefix.clone();
+        let filename = self.filename.clone();
+        discard
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let (parent, _) = Widget::parent::<RecoveryWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+
+                area_model::remove_backup(&filename);
+                area_editor
+                    .borrow_mut()
+                    .load_area(&filename_prefix, &filename, false);
+            })));
+
+        vec![close, message, recover, discard]
+    }
+}
@@ -23,7 +23,7 @@ use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::widgets::{Button, InputField, Label, Spinner};
 use sulis_module::area::{LocationKind, OnRest};
 
-use crate::AreaEditor;
+use crate::{AreaEditor, ValidationWindow};
 
 pub const NAME: &str = "save_window";
 
@@ -71,8 +71,22 @@ impl WidgetKind for SaveWindow {
                     Config::resources_config().campaigns_directory,
                     Config::editor_config().module
                 );
-                area_editor_kind_ref.borrow().model.save(&filename_prefix);
                 parent.borrow_mut().mark_for_removal();
+
+                let issues = area_editor_kind_ref.borrow().model.validate();
+                if issues.is_empty() {
+                    area_editor_kind_ref.borrow_mut().save_area(&filename_prefix);
+                    return;
+                }
+
+                let root = Widget::get_root(widget);
+                let window = Widget::with_defaults(ValidationWindow::new(
+                    Rc::clone(&area_editor_kind_ref),
+                    filename_prefix,
+                    issues,
+                ));
+                window.borrow_mut().state.set_modal(true);
+                Widget::add_child_to(&root, window);
             })));
 
         let content = Widget::empty("content");
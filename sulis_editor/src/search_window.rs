@@ -0,0 +1,407 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::resource::read_single_resource;
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, InputField, Label, ScrollDirection, ScrollPane};
+use sulis_module::area::{AreaBuilder, ToKind};
+use sulis_module::Module;
+
+use crate::load_window::{areas_dir, get_area_entries, open_area};
+use crate::EditorModes;
+
+pub const NAME: &str = "search_window";
+
+const MAX_RESULTS: usize = 100;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ResourceKind {
+    Tile,
+    Actor,
+    Prop,
+    Item,
+    Area,
+}
+
+impl ResourceKind {
+    fn to_str(self) -> &'static str {
+        use ResourceKind::*;
+        match self {
+            Tile => "Tile",
+            Actor => "Actor",
+            Prop => "Prop",
+            Item => "Item",
+            Area => "Area",
+        }
+    }
+}
+
+struct SearchEntry {
+    kind: ResourceKind,
+    id: String,
+    name: String,
+}
+
+impl SearchEntry {
+    fn new(kind: ResourceKind, id: &str, name: &str) -> SearchEntry {
+        SearchEntry {
+            kind,
+            id: id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        self.id.to_lowercase().contains(query) || self.name.to_lowercase().contains(query)
+    }
+
+    /// Returns true if the specified area makes use of this resource
+    fn referenced_by(&self, area: &AreaBuilder) -> bool {
+        use ResourceKind::*;
+        match self.kind {
+            Tile => area.layer_set.contains_key(&self.id),
+            Actor => area.actors.iter().any(|actor| actor.id == self.id),
+            Prop => area.props.iter().any(|prop| prop.id == self.id),
+            Item => area
+                .props
+                .iter()
+                .any(|prop| prop.items.iter().any(|entry| entry.item.id == self.id)),
+            Area => area.transitions.iter().any(|transition| match &transition.to {
+                ToKind::Area { id, .. } => *id == self.id,
+                _ => false,
+            }),
+        }
+    }
+}
+
+/// An area file in the module, read once when the search window is opened
+struct AreaFile {
+    filename: String,
+    builder: AreaBuilder,
+}
+
+/// Searches all tiles, actors, props, items, and areas of the module by id
+/// or name, allowing the selected resource to be placed with its picker,
+/// opened, or have the areas referencing it listed
+pub struct SearchWindow {
+    modes: Rc<EditorModes>,
+}
+
+impl SearchWindow {
+    pub fn new(modes: Rc<EditorModes>) -> Rc<RefCell<SearchWindow>> {
+        Rc::new(RefCell::new(SearchWindow { modes }))
+    }
+}
+
+impl WidgetKind for SearchWindow {
+    fn get_name(&self) -> &str {
+        NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<SearchWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let results_kind = SearchResults::new(Rc::clone(&self.modes));
+        let results = Widget::with_defaults(results_kind.clone());
+
+        let query = Widget::with_theme(InputField::new(""), "query");
+        let results_ref = Rc::clone(&results);
+        query
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |_, kind| {
+                let input_field = match kind.as_any_mut().downcast_mut::<InputField>() {
+                    Some(input_field) => input_field,
+                    None => panic!("Failed to downcast to InputField"),
+                };
+
+                results_kind.borrow_mut().set_query(&input_field.text);
+                results_ref.borrow_mut().invalidate_children();
+            })));
+
+        vec![close, query, results]
+    }
+}
+
+struct SearchResults {
+    modes: Rc<EditorModes>,
+    entries: Vec<SearchEntry>,
+    areas: Vec<AreaFile>,
+    query: String,
+    selected: Option<usize>,
+    references: Option<Vec<usize>>,
+}
+
+impl SearchResults {
+    fn new(modes: Rc<EditorModes>) -> Rc<RefCell<SearchResults>> {
+        let dir_str = areas_dir();
+        let mut areas = Vec::new();
+        for filename in get_area_entries(&dir_str) {
+            let path = format!("{dir_str}/{filename}");
+            match read_single_resource(&path) {
+                Err(e) => {
+                    warn!("Unable to read area from {}", path);
+                    warn!("{}", e);
+                }
+                Ok(builder) => areas.push(AreaFile { filename, builder }),
+            }
+        }
+        areas.sort_by(|a, b| a.builder.id.cmp(&b.builder.id));
+
+        use ResourceKind::*;
+        let mut entries = Vec::new();
+        let mut tiles = Module::all_tiles();
+        tiles.sort_by(|a, b| a.id.cmp(&b.id));
+        entries.extend(tiles.iter().map(|t| SearchEntry::new(Tile, &t.id, "")));
+
+        let mut actors = Module::all_actors();
+        actors.sort_by(|a, b| a.id.cmp(&b.id));
+        entries.extend(actors.iter().map(|a| SearchEntry::new(Actor, &a.id, &a.name)));
+
+        let mut props = Module::all_props();
+        props.sort_by(|a, b| a.id.cmp(&b.id));
+        entries.extend(props.iter().map(|p| SearchEntry::new(Prop, &p.id, &p.name)));
+
+        let mut items = Module::all_items();
+        items.sort_by(|a, b| a.id.cmp(&b.id));
+        entries.extend(items.iter().map(|i| SearchEntry::new(Item, &i.id, &i.name)));
+
+        entries.extend(
+            areas
+                .iter()
+                .map(|a| SearchEntry::new(Area, &a.builder.id, &a.builder.name)),
+        );
+
+        Rc::new(RefCell::new(SearchResults {
+            modes,
+            entries,
+            areas,
+            query: String::new(),
+            selected: None,
+            references: None,
+        }))
+    }
+
+    fn set_query(&mut self, query: &str) {
+        self.query = query.trim().to_lowercase();
+        self.selected = None;
+        self.references = None;
+    }
+
+    fn find_references(&self, entry: &SearchEntry) -> Vec<usize> {
+        self.areas
+            .iter()
+            .enumerate()
+            .filter(|(_, area)| entry.referenced_by(&area.builder))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Places the resource with the picker for its kind.  Returns false if
+    /// there is no picker for this kind of resource
+    fn insert(&self, entry: &SearchEntry) -> bool {
+        use ResourceKind::*;
+        match entry.kind {
+            Tile => match Module::tile(&entry.id) {
+                None => return false,
+                Some(tile) => self.modes.select_tile(tile),
+            },
+            Actor => match Module::actor(&entry.id) {
+                None => return false,
+                Some(actor) => self.modes.select_actor(actor),
+            },
+            Prop => match Module::prop(&entry.id) {
+                None => return false,
+                Some(prop) => self.modes.select_prop(prop),
+            },
+            Item | Area => return false,
+        }
+        true
+    }
+
+    fn area_filename(&self, id: &str) -> Option<&str> {
+        self.areas
+            .iter()
+            .find(|area| area.builder.id == id)
+            .map(|area| &area.filename[..])
+    }
+}
+
+impl WidgetKind for SearchResults {
+    fn get_name(&self) -> &str {
+        "results"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let matches: Vec<usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.matches(&self.query))
+            .map(|(index, _)| index)
+            .take(MAX_RESULTS)
+            .collect();
+
+        let count = Widget::with_theme(Label::empty(), "count");
+        count
+            .borrow_mut()
+            .state
+            .add_text_arg("count", &matches.len().to_string());
+
+        let scrollpane = ScrollPane::new(ScrollDirection::Vertical);
+        for index in matches {
+            let entry = &self.entries[index];
+            let button = Widget::with_theme(Button::empty(), "result_button");
+            {
+                let state = &mut button.borrow_mut().state;
+                state.add_text_arg("kind", entry.kind.to_str());
+                state.add_text_arg("id", &entry.id);
+                state.add_text_arg("name", &entry.name);
+                state.set_active(self.selected == Some(index));
+            }
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let (parent, results) = Widget::parent_mut::<SearchResults>(widget);
+                    results.selected = Some(index);
+                    results.references = None;
+                    parent.borrow_mut().invalidate_children();
+                })));
+            scrollpane.borrow().add_to_content(button);
+        }
+        let results_list = Widget::with_theme(scrollpane, "results_list");
+
+        let selected = self.selected.map(|index| &self.entries[index]);
+        let kind = selected.map(|entry| entry.kind);
+
+        let insert = Widget::with_theme(Button::empty(), "insert");
+        let can_insert = matches!(
+            kind,
+            Some(ResourceKind::Tile) | Some(ResourceKind::Actor) | Some(ResourceKind::Prop)
+        );
+        insert.borrow_mut().state.set_enabled(can_insert);
+        insert
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (_, results) = Widget::parent::<SearchResults>(widget);
+                let inserted = match results.selected {
+                    None => false,
+                    Some(index) => results.insert(&results.entries[index]),
+                };
+
+                if inserted {
+                    let (window, _) = Widget::parent::<SearchWindow>(widget);
+                    window.borrow_mut().mark_for_removal();
+                }
+            })));
+
+        let open = Widget::with_theme(Button::empty(), "open");
+        open.borrow_mut()
+            .state
+            .set_enabled(kind == Some(ResourceKind::Area));
+        if let Some(entry) = selected {
+            if let Some(filename) = self.area_filename(&entry.id) {
+                add_open_area_callback(&open, &self.modes, filename);
+            }
+        }
+
+        let references = Widget::with_theme(Button::empty(), "references");
+        references.borrow_mut().state.set_enabled(selected.is_some());
+        references
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, results) = Widget::parent_mut::<SearchResults>(widget);
+                let index = match results.selected {
+                    None => return,
+                    Some(index) => index,
+                };
+                results.references = Some(results.find_references(&results.entries[index]));
+                parent.borrow_mut().invalidate_children();
+            })));
+
+        let mut widgets = vec![count, results_list, insert, open, references];
+
+        if let (Some(entry), Some(refs)) = (selected, self.references.as_ref()) {
+            let title = Widget::with_theme(Label::empty(), "references_title");
+            title.borrow_mut().state.add_text_arg("id", &entry.id);
+            title
+                .borrow_mut()
+                .state
+                .add_text_arg("count", &refs.len().to_string());
+
+            let scrollpane = ScrollPane::new(ScrollDirection::Vertical);
+            for &index in refs.iter() {
+                let area = &self.areas[index];
+                let button = Widget::with_theme(Button::empty(), "reference_button");
+                button
+                    .borrow_mut()
+                    .state
+                    .add_text_arg("id", &area.builder.id);
+                add_open_area_callback(&button, &self.modes, &area.filename);
+                scrollpane.borrow().add_to_content(button);
+            }
+
+            widgets.push(title);
+            widgets.push(Widget::with_theme(scrollpane, "references_list"));
+        }
+
+        widgets
+    }
+}
+
+fn add_open_area_callback(button: &Rc<RefCell<Widget>>, modes: &EditorModes, filename: &str) {
+    let area_editor = Rc::clone(&modes.area_editor);
+    let filename = filename.to_string();
+    button
+        .borrow_mut()
+        .state
+        .add_callback(Callback::new(Rc::new(move |widget, _| {
+            let (window, _) = Widget::parent::<SearchWindow>(widget);
+            window.borrow_mut().mark_for_removal();
+
+            open_area(widget, &area_editor, &areas_dir(), &filename);
+        })));
+}
@@ -45,6 +45,11 @@ impl TilePicker {
             removal_tiles: Vec::new(),
         }))
     }
+
+    pub fn select(&mut self, tile: Rc<Tile>) {
+        self.cur_layer = Some(tile.layer.clone());
+        self.cur_tile = Some(tile);
+    }
 }
 
 impl EditorMode for TilePicker {
@@ -195,6 +200,10 @@ impl WidgetKind for TilePicker {
                 .borrow_mut()
                 .state
                 .add_text_arg("icon", &tile.image_display.full_id());
+            if let Some(cur_tile) = self.cur_tile.as_ref() {
+                let active = Rc::ptr_eq(cur_tile, &tile);
+                button.borrow_mut().state.set_active(active);
+            }
 
             let cb: Callback = Callback::new(Rc::new(move |widget, _kind| {
                 let parent = Widget::direct_parent(widget);
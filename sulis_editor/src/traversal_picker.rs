@@ -0,0 +1,203 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::config::Config;
+use sulis_core::io::{DrawList, GraphicsRenderer};
+use sulis_core::resource::{ResourceSet, Sprite};
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::util::{Offset, Point, Rect, Scale};
+use sulis_core::widgets::{Button, Label, Spinner};
+use sulis_module::area::{TraversalKind, TraversalPoint};
+use sulis_module::rules::Attribute;
+
+use crate::{AreaModel, EditorMode};
+
+const NAME: &str = "traversal_picker";
+
+/// Places traversal points as pairs of markers.  The first left click sets
+/// the starting marker and the second completes the pair.
+pub struct TraversalPicker {
+    cursor_sprite: Rc<Sprite>,
+    cursor_pos: Option<Point>,
+    pending_from: Option<Point>,
+
+    kind: TraversalKind,
+    attribute: Option<Attribute>,
+    min_attribute: u8,
+}
+
+impl TraversalPicker {
+    pub fn new() -> Rc<RefCell<TraversalPicker>> {
+        let cursor_sprite = ResourceSet::panic_or_sprite(&Config::editor_config().cursor);
+
+        Rc::new(RefCell::new(TraversalPicker {
+            cursor_sprite,
+            cursor_pos: None,
+            pending_from: None,
+            kind: TraversalKind::Climb,
+            attribute: None,
+            min_attribute: 10,
+        }))
+    }
+
+    fn next_attribute(&mut self) {
+        let attrs: Vec<Attribute> = Attribute::iter().copied().collect();
+        self.attribute = match self.attribute {
+            None => attrs.first().copied(),
+            Some(cur) => {
+                let index = attrs.iter().position(|a| *a == cur).unwrap_or(0);
+                attrs.get(index + 1).copied()
+            }
+        };
+    }
+}
+
+impl EditorMode for TraversalPicker {
+    fn draw_mode(
+        &mut self,
+        renderer: &mut dyn GraphicsRenderer,
+        _model: &AreaModel,
+        offset: Offset,
+        scale: Scale,
+        _millis: u32,
+    ) {
+        let mut draw_list = DrawList::empty_sprite();
+        for pos in self.cursor_pos.iter().chain(self.pending_from.iter()) {
+            let rect = Rect {
+                x: offset.x + pos.x as f32,
+                y: offset.y + pos.y as f32,
+                w: 1.0,
+                h: 1.0,
+            };
+            draw_list.append(&mut DrawList::from_sprite_f32(&self.cursor_sprite, rect));
+        }
+        draw_list.set_scale(scale);
+        renderer.draw(draw_list);
+    }
+
+    fn cursor_size(&self) -> (i32, i32) {
+        (1, 1)
+    }
+
+    fn mouse_move(&mut self, _model: &mut AreaModel, x: i32, y: i32) {
+        self.cursor_pos = Some(Point::new(x, y));
+    }
+
+    fn left_click(&mut self, model: &mut AreaModel, x: i32, y: i32) {
+        let from = match self.pending_from.take() {
+            None => {
+                self.pending_from = Some(Point::new(x, y));
+                return;
+            }
+            Some(from) => from,
+        };
+
+        let to = Point::new(x, y);
+        if from == to {
+            return;
+        }
+
+        model.add_traversal(TraversalPoint {
+            kind: self.kind,
+            from,
+            to,
+            attribute: self.attribute,
+            min_attribute: self.min_attribute,
+        });
+    }
+
+    fn right_click(&mut self, model: &mut AreaModel, x: i32, y: i32) {
+        if self.pending_from.take().is_some() {
+            return;
+        }
+
+        model.remove_traversals_within(x, y, 1, 1);
+    }
+}
+
+impl WidgetKind for TraversalPicker {
+    fn get_name(&self) -> &str {
+        NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let kinds = Widget::empty("kinds");
+        for (kind, theme) in [(TraversalKind::Climb, "climb"), (TraversalKind::Jump, "jump")] {
+            let button = Widget::with_theme(Button::empty(), theme);
+            button.borrow_mut().state.set_active(kind == self.kind);
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let parent = Widget::direct_parent(widget);
+                    for child in parent.borrow().children.iter() {
+                        child.borrow_mut().state.set_active(false);
+                    }
+                    widget.borrow_mut().state.set_active(true);
+
+                    let (_, picker) = Widget::parent_mut::<TraversalPicker>(widget);
+                    picker.kind = kind;
+                })));
+            Widget::add_child_to(&kinds, button);
+        }
+
+        let attribute = Widget::with_theme(Button::empty(), "attribute");
+        let attr_name = match &self.attribute {
+            None => "None",
+            Some(attr) => attr.name(),
+        };
+        attribute.borrow_mut().state.add_text_arg("attribute", attr_name);
+        attribute
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, picker) = Widget::parent_mut::<TraversalPicker>(widget);
+                picker.next_attribute();
+                parent.borrow_mut().invalidate_children();
+            })));
+
+        let min = Widget::with_theme(Spinner::new(self.min_attribute as i32, 1, 30), "min");
+        min.borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, kind| {
+                let (_, picker) = Widget::parent_mut::<TraversalPicker>(widget);
+
+                let spinner = match kind.as_any().downcast_ref::<Spinner>() {
+                    None => panic!("Unable to downcast to spinner"),
+                    Some(widget) => widget,
+                };
+
+                picker.min_attribute = spinner.value() as u8;
+            })));
+
+        let min_label = Widget::with_theme(Label::empty(), "min_label");
+        let help = Widget::with_theme(Label::empty(), "help");
+
+        vec![kinds, attribute, min, min_label, help]
+    }
+}
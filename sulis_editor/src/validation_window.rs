@@ -0,0 +1,138 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label, ScrollDirection, ScrollPane, TextArea};
+
+use crate::area_model::{Severity, ValidationIssue};
+use crate::AreaEditor;
+
+pub const NAME: &str = "validation_window";
+
+/// Lists the problems found when validating an area prior to saving, and
+/// allows the user to either go back and fix them or save anyway.
+pub struct ValidationWindow {
+    area_editor: Rc<RefCell<AreaEditor>>,
+    filename_prefix: String,
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationWindow {
+    pub fn new(
+        area_editor: Rc<RefCell<AreaEditor>>,
+        filename_prefix: String,
+        issues: Vec<ValidationIssue>,
+    ) -> Rc<RefCell<ValidationWindow>> {
+        Rc::new(RefCell::new(ValidationWindow {
+            area_editor,
+            filename_prefix,
+            issues,
+        }))
+    }
+}
+
+impl WidgetKind for ValidationWindow {
+    fn get_name(&self) -> &str {
+        NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<ValidationWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let cancel = Widget::with_theme(Button::empty(), "cancel");
+        cancel
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<ValidationWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let save = Widget::with_theme(Button::empty(), "save_anyway");
+        let area_editor = Rc::clone(&self.area_editor);
+        let filename_prefix = self.filename_prefix.clone();
+        save.borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let (parent, _) = Widget::parent::<ValidationWindow>(widget);
+                area_editor.borrow_mut().save_area(&filename_prefix);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let errors = self
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .count();
+        let summary = Widget::with_theme(Label::empty(), "summary");
+        {
+            let state = &mut summary.borrow_mut().state;
+            state.add_text_arg("errors", &errors.to_string());
+            state.add_text_arg("warnings", &(self.issues.len() - errors).to_string());
+        }
+
+        let mut text = Vec::new();
+        for issue in self.issues.iter() {
+            let color = match issue.severity {
+                Severity::Error => "f00",
+                Severity::Warning => "ff0",
+            };
+            text.push(format!("[c={}|{}]", color, escape(&issue.message)));
+        }
+
+        let scroll = ScrollPane::new(ScrollDirection::Vertical);
+        let issues = Widget::with_theme(scroll.clone(), "issues");
+        let text_area = Widget::with_theme(TextArea::empty(), "text");
+        text_area
+            .borrow_mut()
+            .state
+            .add_text_arg("issues", &text.join("\n"));
+        scroll.borrow().add_to_content(text_area);
+
+        vec![close, cancel, save, summary, issues]
+    }
+}
+
+// escape characters that would otherwise be parsed as markup
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | '|') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
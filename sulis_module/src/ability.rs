@@ -70,6 +70,26 @@ pub struct Active {
     pub requires_shield: bool,
     pub requires_ranged: bool,
     pub requires_active_mode: Vec<String>,
+
+    /// Requirements the user must meet each time the ability is activated,
+    /// as opposed to `prereqs` which only apply when learning it
+    pub requirements: Option<PrereqList>,
+
+    /// Cursor image shown in place of the default while this ability's
+    /// targeter is active
+    pub targeting_cursor: Option<Rc<dyn Image>>,
+    pub targeting_sounds: TargetingSounds,
+}
+
+/// Sound effects played by an ability's targeter.  `valid` is played when
+/// the cursor moves onto a valid selection, `invalid` when attempting to
+/// activate without one, and `activate` when the targeter fires.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TargetingSounds {
+    pub valid: Option<String>,
+    pub invalid: Option<String>,
+    pub activate: Option<String>,
 }
 
 #[derive(Debug)]
@@ -127,6 +147,11 @@ impl Ability {
                     Some(c) => c,
                 };
 
+                let requirements = match active.requirements {
+                    None => None,
+                    Some(requirements) => Some(PrereqList::new(requirements)?),
+                };
+
                 let group = match AbilityGroup::new(module, &active.group) {
                     None => {
                         warn!("Unable to find ability group '{}'", active.group);
@@ -135,6 +160,25 @@ impl Ability {
                     Some(group) => group,
                 };
 
+                let targeting_cursor = match active.targeting_cursor {
+                    None => None,
+                    Some(ref id) => match ResourceSet::image(id) {
+                        None => {
+                            warn!("No image found for targeting cursor '{}'", id);
+                            return unable_to_create_error("ability", &builder.id);
+                        }
+                        Some(image) => Some(image),
+                    },
+                };
+
+                let sounds = &active.targeting_sounds;
+                let sound_ids = [&sounds.valid, &sounds.invalid, &sounds.activate];
+                for id in sound_ids.into_iter().flatten() {
+                    if ResourceSet::sound(id).is_err() {
+                        warn!("No sound found for targeting sound '{}'", id);
+                    }
+                }
+
                 Some(Active {
                     script: active.script,
                     ap: active.ap,
@@ -151,6 +195,9 @@ impl Ability {
                     requires_shield: active.requires_shield,
                     requires_ranged: active.requires_ranged,
                     requires_active_mode: active.requires_active_mode,
+                    requirements,
+                    targeting_cursor,
+                    targeting_sounds: active.targeting_sounds,
                 })
             }
         };
@@ -197,6 +244,16 @@ impl Ability {
             Some(ref prereqs) => prereqs.meets(actor),
         }
     }
+
+    /// Returns the reason the actor may not currently activate this ability
+    /// due to its activation requirements, or None if it may
+    pub fn unmet_requirement(&self, actor: &Actor) -> Option<String> {
+        self.active
+            .as_ref()?
+            .requirements
+            .as_ref()?
+            .unmet_reason(actor)
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -259,6 +316,13 @@ pub struct ActiveBuilder {
 
     #[serde(default)]
     requires_active_mode: Vec<String>,
+
+    requirements: Option<PrereqListBuilder>,
+
+    targeting_cursor: Option<String>,
+
+    #[serde(default)]
+    targeting_sounds: TargetingSounds,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -0,0 +1,34 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+/// An achievement defined by a module.  Achievements are awarded via script
+/// with `game:award_achievement(id)` and are stored per user profile, so
+/// they persist across saved games.  Hidden achievements do not show their
+/// name or description until awarded.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    pub icon: String,
+
+    #[serde(default)]
+    pub hidden: bool,
+}
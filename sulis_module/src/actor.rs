@@ -14,7 +14,7 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::io::Error;
 use std::rc::Rc;
@@ -27,8 +27,8 @@ use sulis_core::ui::Color;
 use sulis_core::util::{unable_to_create_error, Offset, Scale};
 
 use crate::{
-    AITemplate, Ability, Class, Conversation, ImageLayer, ImageLayerSet, InventoryBuilder,
-    LootList, Module, Race, RaceBuilder,
+    AITemplate, Ability, Appearance, Class, Conversation, ImageLayer, ImageLayerSet,
+    InventoryBuilder, LootList, Module, Race, RaceBuilder,
 };
 
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -85,6 +85,41 @@ impl Faction {
     }
 }
 
+/// The horizontal direction an actor is drawn facing.  Actor images face
+/// right, and are mirrored when facing left.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub enum Facing {
+    #[default]
+    Right,
+    Left,
+}
+
+impl Facing {
+    pub fn iter() -> impl Iterator<Item = &'static Facing> {
+        [Facing::Right, Facing::Left].iter()
+    }
+
+    pub fn option_from_str(val: &str) -> Option<Facing> {
+        match val {
+            "Right" => Some(Facing::Right),
+            "Left" => Some(Facing::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Facing::Right => "Right",
+            Facing::Left => "Left",
+        }
+    }
+
+    pub fn is_mirrored(self) -> bool {
+        self == Facing::Left
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Reward {
     pub xp: u32,
@@ -144,6 +179,13 @@ pub struct Actor {
     pub hue: Option<f32>,
     pub hair_color: Option<Color>,
     pub skin_color: Option<Color>,
+
+    /// Selected images for race custom layers, by layer ID
+    pub custom_images: HashMap<String, String>,
+
+    /// Selected colors for race color channels, by channel ID
+    pub custom_colors: HashMap<String, Color>,
+    appearance: Appearance,
     image_layers: ImageLayerSet,
     image: LayeredImage,
 
@@ -153,7 +195,15 @@ pub struct Actor {
     pub reward: Option<Reward>,
     pub abilities: Vec<OwnedAbility>,
 
+    /// Abilities granted to this actor, as a companion, once the party's
+    /// approval with them reaches each threshold
+    pub approval_abilities: Vec<ApprovalAbility>,
+
     pub ai: Option<Rc<AITemplate>>,
+
+    /// A script attached to every entity created from this actor, with
+    /// lifecycle hooks independent of any abilities or AI
+    pub script: Option<EntityScript>,
 }
 
 impl PartialEq for Actor {
@@ -189,7 +239,7 @@ impl Actor {
         }
 
         let image_layers = other.image_layers.clone();
-        let images_list = image_layers.get_list(other.sex, other.hair_color, other.skin_color);
+        let images_list = image_layers.get_list(other.sex, &other.appearance);
         let image = LayeredImage::new(images_list, other.hue);
 
         let mut abilities = other.abilities.clone();
@@ -228,13 +278,61 @@ impl Actor {
             hue: other.hue,
             hair_color: other.hair_color,
             skin_color: other.skin_color,
+            custom_images: other.custom_images.clone(),
+            custom_colors: other.custom_colors.clone(),
+            appearance: other.appearance.clone(),
             image_layers,
             image,
             builder_images: other.builder_images.clone(),
             reward: other.reward.clone(),
             abilities,
+            approval_abilities: other.approval_abilities.clone(),
             ai: other.ai.clone(),
+            script: other.script.clone(),
+        }
+    }
+
+    /// Creates a copy of `other` returned to the first level of its base class.
+    /// Abilities and upgrades chosen from the base class ability lists after the
+    /// first level are removed, while starting abilities are kept.  The actor
+    /// keeps the specified `xp`, so its levels may be chosen again
+    pub fn retrained(other: &Actor, xp: u32) -> Actor {
+        let base_class = other.base_class();
+
+        let mut listed = HashSet::new();
+        for level in 2..=other.total_level {
+            for list in base_class.ability_choices(level) {
+                listed.extend(list.iter().map(|entry| entry.ability.id.to_string()));
+            }
+        }
+
+        let mut kept: HashSet<String> = base_class
+            .starting_abilities()
+            .chain(base_class.kits.iter().flat_map(|kit| kit.starting_abilities.iter()))
+            .map(|ability| ability.id.to_string())
+            .collect();
+        for list in base_class.ability_choices(1) {
+            kept.extend(list.iter().map(|entry| entry.ability.id.to_string()));
+        }
+
+        let to_remove = listed.difference(&kept).cloned().collect();
+        let mut actor = Actor::from(
+            other,
+            None,
+            xp,
+            Vec::new(),
+            to_remove,
+            other.inventory.clone(),
+        );
+
+        for owned in actor.abilities.iter_mut() {
+            if listed.contains(&owned.ability.id) {
+                owned.level = 0;
+            }
         }
+        actor.levels = vec![(base_class, 1)];
+        actor.total_level = 1;
+        actor
     }
 
     pub fn new(builder: ActorBuilder, resources: &mut Module) -> Result<Actor, Error> {
@@ -300,7 +398,13 @@ impl Actor {
 
         let image_layers =
             ImageLayerSet::merge(race.default_images(), sex, builder.images.clone())?;
-        let images_list = image_layers.get_list(sex, builder.hair_color, builder.skin_color);
+        let appearance = race.appearance(
+            builder.hair_color,
+            builder.skin_color,
+            &builder.custom_images,
+            &builder.custom_colors,
+        );
+        let images_list = image_layers.get_list(sex, &appearance);
         let image = LayeredImage::new(images_list, builder.hue);
 
         let reward = match builder.reward {
@@ -350,6 +454,13 @@ impl Actor {
             }
         }
 
+        for approval_ability in builder.approval_abilities.iter() {
+            if !resources.abilities.contains_key(&approval_ability.ability) {
+                warn!("No ability found for '{}'", approval_ability.ability);
+                return unable_to_create_error("actor", &builder.id);
+            }
+        }
+
         let ai = match builder.ai {
             None => None,
             Some(id) => match resources.ai_templates.get(&id) {
@@ -381,8 +492,13 @@ impl Actor {
             hue: builder.hue,
             skin_color: builder.skin_color,
             hair_color: builder.hair_color,
+            custom_images: builder.custom_images,
+            custom_colors: builder.custom_colors,
+            appearance,
             abilities,
+            approval_abilities: builder.approval_abilities,
             ai,
+            script: builder.script,
         })
     }
 
@@ -438,6 +554,10 @@ impl Actor {
         &self.image_layers
     }
 
+    pub fn appearance(&self) -> &Appearance {
+        &self.appearance
+    }
+
     pub fn draw(
         &self,
         renderer: &mut dyn GraphicsRenderer,
@@ -449,6 +569,48 @@ impl Actor {
     }
 }
 
+#[derive(Deserialize, Debug, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ApprovalAbility {
+    /// The approval at or above which the ability is granted
+    pub approval: i32,
+    pub ability: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialOrd, Ord, Hash, PartialEq, Eq, Debug)]
+#[serde(deny_unknown_fields)]
+pub enum EntityScriptHook {
+    /// Called once, the first time the entity is added to an area
+    OnSpawn,
+
+    /// Called whenever the entity loses hit points
+    OnDamaged,
+
+    /// Called when the entity is reduced to zero hit points
+    OnDeath,
+
+    /// Called once, the first time a party member moves within the
+    /// script's `approach_distance` of the entity
+    OnPlayerApproach,
+}
+
+#[derive(Deserialize, Debug, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EntityScript {
+    /// The ID of the module script containing the hook functions
+    pub id: String,
+
+    /// The function called for each hook.  Hooks not listed are not called.
+    pub hooks: HashMap<EntityScriptHook, String>,
+
+    #[serde(default = "default_approach_distance")]
+    pub approach_distance: f32,
+}
+
+fn default_approach_distance() -> f32 {
+    6.0
+}
+
 #[derive(Deserialize, Debug, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct RewardBuilder {
@@ -485,6 +647,13 @@ pub struct ActorBuilder {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skin_color: Option<Color>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_images: HashMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_colors: HashMap<String, Color>,
+
     #[serde(default)]
     pub inventory: InventoryBuilder,
     pub levels: HashMap<String, u32>,
@@ -495,5 +664,11 @@ pub struct ActorBuilder {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reward: Option<RewardBuilder>,
     pub abilities: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approval_abilities: Vec<ApprovalAbility>,
     pub ai: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<EntityScript>,
 }
@@ -29,6 +29,7 @@ pub use self::path_finder_grid::PathFinderGrid;
 pub mod tile;
 pub use self::tile::Tile;
 pub use self::tile::Tileset;
+pub use self::tile::Traversal;
 
 use std::collections::{HashMap, HashSet};
 use std::io::{Error, ErrorKind};
@@ -45,7 +46,11 @@ use sulis_core::util::{unable_to_create_error, Point, Size};
 use sulis_core::io::SoundSource;
 
 use crate::generator::{EncounterParams, EncounterParamsBuilder, PropParams, PropParamsBuilder};
-use crate::{Encounter, ItemListEntrySaveState, Module, ObjectSize, OnTrigger, Prop};
+use crate::rules::Attribute;
+use crate::{
+    Encounter, Facing, Faction, ItemListEntrySaveState, LootList, Module, ObjectSize, OnTrigger,
+    Prop,
+};
 
 pub const MAX_AREA_SIZE: i32 = 128;
 
@@ -73,6 +78,79 @@ pub struct Transition {
     pub to: ToKind,
     pub hover_text: String,
     pub image_display: Rc<dyn Image>,
+    pub thumbnail: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub enum TraversalKind {
+    Climb,
+    Jump,
+}
+
+/// A pair of markers that creatures may cross between, in either direction,
+/// by climbing or jumping, such as over a wall or across a gap
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TraversalPoint {
+    pub kind: TraversalKind,
+    pub from: Point,
+    pub to: Point,
+
+    /// The attribute a creature must have at least `min_attribute` of to
+    /// use this traversal.  If not set, any creature may use it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<Attribute>,
+
+    #[serde(default)]
+    pub min_attribute: u8,
+}
+
+impl TraversalPoint {
+    /// Returns the marker at the opposite end of the traversal from `start`
+    pub fn other_end(&self, start: Point) -> Point {
+        if start == self.from {
+            self.to
+        } else {
+            self.from
+        }
+    }
+}
+
+/// Overrides the passability and movement cost of a single placed tile,
+/// identified by its tile ID and location.  Applies to every square the
+/// tile covers, without changing other placements of the same tile.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TileOverride {
+    pub tile: String,
+    pub location: Point,
+
+    /// If set, the covered squares are made passable or impassable,
+    /// replacing the tile's own impass points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passable: Option<bool>,
+
+    /// Additional squares of movement charged for entering each covered
+    /// square, on top of the normal cost of one square
+    #[serde(default)]
+    pub movement_cost: u32,
+}
+
+/// A sound looped in the background of an area, layered with the area's
+/// other ambient loops and its `ambient_sound`
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AmbientLoop {
+    pub id: String,
+
+    /// Multiplies the volume defined for the sound
+    #[serde(default = "default_loop_volume")]
+    pub volume: f32,
+}
+
+fn default_loop_volume() -> f32 {
+    1.0
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -83,6 +161,21 @@ pub struct ActorData {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unique_id: Option<String>,
+
+    /// Overrides the faction of the actor for this placement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faction: Option<Faction>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facing: Option<Facing>,
+
+    /// Overrides the AI template of the actor for this placement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai: Option<String>,
+
+    /// Points the actor patrols between, available to its AI script
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patrol: Vec<Point>,
 }
 
 #[derive(Clone)]
@@ -90,8 +183,23 @@ pub struct PropData {
     pub prop: Rc<Prop>,
     pub location: Point,
     pub items: Vec<ItemListEntrySaveState>,
+    pub loot: Option<Rc<LootList>>,
     pub enabled: bool,
     pub hover_text: Option<String>,
+    pub owner: Option<String>,
+    pub lock: Option<PropLock>,
+}
+
+/// A lock on a door or container, opened by a key item in the party keyring
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PropLock {
+    /// The ID of the key item that opens this lock
+    pub key: String,
+
+    /// Whether the key is removed from the keyring once used
+    #[serde(default)]
+    pub consume_key: bool,
 }
 
 #[derive(Clone)]
@@ -114,6 +222,8 @@ pub struct Area {
     pub transitions: Vec<Transition>,
     pub encounters: Vec<EncounterData>,
     pub triggers: Vec<Trigger>,
+    pub traversals: Vec<TraversalPoint>,
+    pub over_layers: Vec<String>,
     pub vis_dist: i32,
     pub vis_dist_squared: i32,
     pub vis_dist_up_one_squared: i32,
@@ -121,9 +231,12 @@ pub struct Area {
     pub ambient_sound: Option<SoundSource>,
     pub default_music: Option<SoundSource>,
     pub default_combat_music: Option<SoundSource>,
+    pub ambient_loops: Vec<SoundSource>,
     pub on_rest: OnRest,
     pub location_kind: LocationKind,
+    pub level: u32,
     pub generator: Option<GeneratorParams>,
+    pub script: Option<AreaScript>,
     pub builder: AreaBuilder,
 }
 
@@ -149,6 +262,12 @@ impl Area {
 
         let (triggers, encounters) = Area::read_triggers_and_encounters(&builder)?;
 
+        for layer_id in builder.over_layers.iter() {
+            if !builder.layers.contains(layer_id) {
+                warn!("In '{}': Over layer '{}' is not an area layer", builder.id, layer_id);
+            }
+        }
+
         let visibility_tile = ResourceSet::sprite(&builder.visibility_tile)?;
         let explored_tile = ResourceSet::sprite(&builder.explored_tile)?;
 
@@ -172,6 +291,13 @@ impl Area {
             Some(id) => Some(ResourceSet::sound(id)?),
         };
 
+        let mut ambient_loops = Vec::new();
+        for ambient_loop in &builder.ambient_loops {
+            let mut sound = ResourceSet::sound(&ambient_loop.id)?;
+            sound.mult_volume(ambient_loop.volume);
+            ambient_loops.push(sound);
+        }
+
         Ok(Area {
             id: builder.id.to_string(),
             name: builder.name.to_string(),
@@ -184,6 +310,8 @@ impl Area {
             explored_tile,
             transitions,
             triggers,
+            traversals: builder.traversals.clone(),
+            over_layers: builder.over_layers.clone(),
             vis_dist: builder.max_vis_distance,
             vis_dist_squared: builder.max_vis_distance * builder.max_vis_distance,
             vis_dist_up_one_squared: builder.max_vis_up_one_distance
@@ -192,8 +320,11 @@ impl Area {
             ambient_sound,
             default_music,
             default_combat_music,
+            ambient_loops,
             on_rest: builder.on_rest.clone(),
             location_kind: builder.location_kind,
+            level: builder.level.unwrap_or(0),
+            script: builder.script.clone(),
             generator,
             builder,
         })
@@ -305,12 +436,21 @@ impl Area {
                 t_builder.to, t_builder.from.x, t_builder.from.y
             );
 
+            let thumbnail = match t_builder.thumbnail {
+                Some(ref id) if ResourceSet::image(id).is_none() => {
+                    warn!("Thumbnail image '{}' not found for transition.", id);
+                    None
+                }
+                ref thumbnail => thumbnail.clone(),
+            };
+
             let transition = Transition {
                 from: t_builder.from,
                 to: t_builder.to.clone(),
                 hover_text: t_builder.hover_text.clone(),
                 size,
                 image_display: image,
+                thumbnail,
             };
             transitions.push(transition);
         }
@@ -330,6 +470,30 @@ impl Area {
     }
 }
 
+/// A script called at points in the lifecycle of an area.  Each function is
+/// optional, and is called with the player as its argument.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AreaScript {
+    pub id: String,
+
+    /// Called each time the area becomes the current area, including on load
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_load: Option<String>,
+
+    /// Called the first time the party enters the area
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_first_enter: Option<String>,
+
+    /// Called when the party leaves the area for another area
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_exit: Option<String>,
+
+    /// Called each round while the area is the current area
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_round_elapsed: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct AreaBuilder {
@@ -345,19 +509,44 @@ pub struct AreaBuilder {
     pub ambient_sound: Option<String>,
     pub default_music: Option<String>,
     pub default_combat_music: Option<String>,
+
+    /// Additional sounds looped while the area is loaded, each at its own volume
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ambient_loops: Vec<AmbientLoop>,
+
     pub on_rest: OnRest,
     pub location_kind: LocationKind,
 
+    /// The level used to scale loot generated in this area
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<u32>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub generator: Option<GeneratorParamsBuilder>,
     pub layers: Vec<String>,
     pub entity_layer: usize,
+
+    /// Layers drawn above all creatures, such as roofs and bridges.  Each
+    /// connected group of tiles in these layers fades out while a party
+    /// member stands beneath it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub over_layers: Vec<String>,
+
     pub actors: Vec<ActorData>,
     pub props: Vec<PropDataBuilder>,
     pub encounters: Vec<EncounterDataBuilder>,
     pub transitions: Vec<TransitionBuilder>,
     pub triggers: Vec<TriggerBuilder>,
 
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub traversals: Vec<TraversalPoint>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tile_overrides: Vec<TileOverride>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<AreaScript>,
+
     #[serde(serialize_with = "ser_terrain", deserialize_with = "de_terrain")]
     pub terrain: Vec<Option<String>>,
 
@@ -701,6 +890,10 @@ pub struct TransitionBuilder {
     pub to: ToKind,
     pub hover_text: String,
     pub image_display: String,
+
+    /// An optional image shown alongside the destination when hovering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -718,9 +911,24 @@ pub struct PropDataBuilder {
     pub location: Point,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub items: Vec<ItemListEntrySaveState>,
+
+    /// The loot list used to generate this container's contents, overriding
+    /// the default loot list of the prop
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loot: Option<String>,
     pub enabled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hover_text: Option<String>,
+
+    /// The owner of this prop's contents.  Taking items from an owned prop
+    /// is theft
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// A lock on this prop.  A locked prop is disabled until the party
+    /// opens it with the key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<PropLock>,
 }
 
 pub fn create_prop(builder: &PropDataBuilder) -> Result<PropData, Error> {
@@ -731,13 +939,24 @@ pub fn create_prop(builder: &PropDataBuilder) -> Result<PropData, Error> {
 
     let location = builder.location;
 
-    let enabled = builder.enabled.unwrap_or(true);
+    let enabled = builder.enabled.unwrap_or(builder.lock.is_none());
+
+    let loot = match builder.loot {
+        None => None,
+        Some(ref id) => match Module::loot_list(id) {
+            None => return unable_to_create_error("loot_list", id),
+            Some(loot) => Some(loot),
+        },
+    };
 
     Ok(PropData {
         prop,
         location,
         items: builder.items.clone(),
+        loot,
         enabled,
         hover_text: builder.hover_text.clone(),
+        owner: builder.owner.clone(),
+        lock: builder.lock.clone(),
     })
 }
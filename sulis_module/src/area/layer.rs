@@ -28,6 +28,7 @@ pub struct Layer {
     display: Vec<Vec<Rc<Tile>>>,
     passable: Vec<bool>,
     visible: Vec<bool>,
+    swim: Vec<bool>,
     spritesheet_id: Option<String>,
     pub(in crate) impass_override_tiles: Vec<(Point, Rc<Tile>)>,
 }
@@ -45,6 +46,7 @@ impl Layer {
         let mut display: Vec<Vec<Rc<Tile>>> = vec![Vec::new(); dim];
         let mut passable: Vec<bool> = vec![true; dim];
         let mut visible: Vec<bool> = vec![true; dim];
+        let mut swim: Vec<bool> = vec![false; dim];
         let mut spritesheet_id: Option<String> = None;
 
         trace!("Creating layer '{}' with size: {} x {}", id, width, height);
@@ -84,6 +86,14 @@ impl Layer {
                     visible[p_index] = false;
                 }
 
+                for p in tile.swim.iter() {
+                    let p_index = (base_x + p.x + (base_y + p.y) * width) as usize;
+                    if p_index >= dim {
+                        continue;
+                    }
+                    swim[p_index] = true;
+                }
+
                 if base_x + tile.width > width || base_y + tile.height > height {
                     return invalid_data_error(&format!(
                         "Tile '{}' at [{}, {}] extends past area boundary.",
@@ -104,11 +114,29 @@ impl Layer {
             display,
             passable,
             visible,
+            swim,
             spritesheet_id,
             impass_override_tiles: impass_overrides,
         })
     }
 
+    /// Creates a copy of this layer with the tiles at `x`, `y` replaced by the
+    /// specified `tile`, or removed if `None`
+    pub fn with_tile(&self, x: i32, y: i32, tile: Option<Rc<Tile>>) -> Result<Layer, Error> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return invalid_data_error(&format!("Tile location {x},{y} is out of bounds"));
+        }
+
+        let mut tiles = self.display.clone();
+        let index = (x + y * self.width) as usize;
+        tiles[index].clear();
+        if let Some(tile) = tile {
+            tiles[index].push(tile);
+        }
+
+        Layer::new(self.width, self.height, self.id.to_string(), tiles)
+    }
+
     pub fn get_spritesheet(&self) -> Option<Rc<Spritesheet>> {
         match self.spritesheet_id {
             None => None,
@@ -132,6 +160,10 @@ impl Layer {
         self.passable[index]
     }
 
+    pub fn is_swim_index(&self, index: usize) -> bool {
+        self.swim[index]
+    }
+
     pub fn tiles_at(&self, x: i32, y: i32) -> &Vec<Rc<Tile>> {
         &self.display[(x + y * self.width) as usize]
     }
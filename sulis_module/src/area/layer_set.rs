@@ -20,7 +20,7 @@ use std::rc::Rc;
 
 use sulis_core::util::invalid_data_error;
 
-use crate::area::{AreaBuilder, Layer, PropData, Tile};
+use crate::area::{AreaBuilder, Layer, PropData, Tile, TileOverride, Traversal};
 use crate::Module;
 
 pub struct LayerSet {
@@ -31,6 +31,16 @@ pub struct LayerSet {
     elevation: Vec<u8>,
     pub passable: Vec<bool>,
     visible: Vec<bool>,
+    swim: Vec<bool>,
+    movement_cost: Vec<u32>,
+    tile_overrides: Vec<TileOverride>,
+}
+
+struct Grids {
+    passable: Vec<bool>,
+    visible: Vec<bool>,
+    swim: Vec<bool>,
+    movement_cost: Vec<u32>,
 }
 
 impl LayerSet {
@@ -98,10 +108,117 @@ impl LayerSet {
             builder.id,
             layers.len()
         );
+        let tile_overrides = builder.tile_overrides.clone();
+        for tile_override in tile_overrides.iter() {
+            if LayerSet::find_tile(&layers, tile_override).is_none() {
+                let loc = tile_override.location;
+                warn!(
+                    "In '{}': No tile '{}' at {},{} for tile override",
+                    builder.id, tile_override.tile, loc.x, loc.y
+                );
+            }
+        }
+
+        let grids = LayerSet::compute_grids(&layers, &tile_overrides, props, width, height);
+
+        if entity_layer_index >= layers.len() {
+            return invalid_data_error(&format!(
+                "Entity layer of {entity_layer_index} is invalid."
+            ));
+        }
+
+        let elevation;
+        if builder.elevation.is_empty() {
+            elevation = vec![0; dim];
+        } else if builder.elevation.len() != dim {
+            warn!(
+                "In '{}': Elevation array must be zero or dimensions length*width",
+                builder.id
+            );
+            elevation = vec![0; dim];
+        } else {
+            elevation = builder.elevation.clone();
+        }
+
+        Ok(LayerSet {
+            width,
+            height,
+            layers,
+            entity_layer_index,
+            elevation,
+            passable: grids.passable,
+            visible: grids.visible,
+            swim: grids.swim,
+            movement_cost: grids.movement_cost,
+            tile_overrides,
+        })
+    }
+
+    /// Replaces the tiles at `x`, `y` in the layer with the specified ID with
+    /// `tile`, or removes them if `None`, and recomputes the passable, visible
+    /// and swim grids.  `props` are the props the area was created with.
+    pub fn set_tile(
+        &mut self,
+        layer_id: &str,
+        x: i32,
+        y: i32,
+        tile: Option<Rc<Tile>>,
+        props: &[PropData],
+    ) -> Result<(), Error> {
+        let index = match self.layers.iter().position(|layer| layer.id == layer_id) {
+            None => return invalid_data_error(&format!("No layer with ID '{layer_id}'")),
+            Some(index) => index,
+        };
+
+        self.layers[index] = self.layers[index].with_tile(x, y, tile)?;
+
+        let grids = LayerSet::compute_grids(
+            &self.layers,
+            &self.tile_overrides,
+            props,
+            self.width,
+            self.height,
+        );
+        self.passable = grids.passable;
+        self.visible = grids.visible;
+        self.swim = grids.swim;
+        self.movement_cost = grids.movement_cost;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_passable(&mut self, x: i32, y: i32, passable: bool) {
+        self.passable[(x + y * self.width) as usize] = passable;
+    }
+
+    fn find_tile(layers: &[Layer], tile_override: &TileOverride) -> Option<Rc<Tile>> {
+        let (x, y) = (tile_override.location.x, tile_override.location.y);
+        layers
+            .iter()
+            .filter(|layer| x >= 0 && y >= 0 && x < layer.width && y < layer.height)
+            .flat_map(|layer| layer.tiles_at(x, y).iter())
+            .find(|tile| tile.id == tile_override.tile)
+            .cloned()
+    }
+
+    fn compute_grids(
+        layers: &[Layer],
+        tile_overrides: &[TileOverride],
+        props: &[PropData],
+        width: i32,
+        height: i32,
+    ) -> Grids {
+        let dim = (width * height) as usize;
         let mut passable = vec![true; dim];
         let mut visible = vec![true; dim];
+        let mut swim = vec![false; dim];
+        let mut movement_cost = vec![0; dim];
         for layer in layers.iter() {
             for index in 0..dim {
+                if layer.is_swim_index(index) {
+                    swim[index] = true;
+                }
+
                 if !layer.is_passable_index(index) {
                     passable[index] = false;
                 }
@@ -122,9 +239,16 @@ impl LayerSet {
                 for y in start_y..end_y {
                     for x in start_x..end_x {
                         passable[(x + y * width) as usize] = true;
+                        swim[(x + y * width) as usize] = false;
                     }
                 }
 
+                for p in tile.swim.iter() {
+                    let x = p.x + start_x;
+                    let y = p.y + start_y;
+                    swim[(x + y * width) as usize] = true;
+                }
+
                 for p in tile.impass.iter() {
                     let x = p.x + start_x;
                     let y = p.y + start_y;
@@ -133,8 +257,34 @@ impl LayerSet {
             }
         }
 
+        for tile_override in tile_overrides.iter() {
+            let tile = match LayerSet::find_tile(layers, tile_override) {
+                None => continue,
+                Some(tile) => tile,
+            };
+
+            let start_x = tile_override.location.x;
+            let start_y = tile_override.location.y;
+            for y in start_y..(start_y + tile.height).min(height) {
+                for x in start_x..(start_x + tile.width).min(width) {
+                    let index = (x + y * width) as usize;
+                    if let Some(pass) = tile_override.passable {
+                        passable[index] = pass;
+                    }
+                    movement_cost[index] = movement_cost[index].max(tile_override.movement_cost);
+                }
+            }
+        }
+
         for prop_data in props.iter() {
             let prop = &prop_data.prop;
+
+            // destructible props are tracked by the area state, so that
+            // destroying them clears the squares they block
+            if prop.destructible.is_some() {
+                continue;
+            }
+
             let start_x = prop_data.location.x as usize;
             let start_y = prop_data.location.y as usize;
 
@@ -151,34 +301,12 @@ impl LayerSet {
             }
         }
 
-        if entity_layer_index >= layers.len() {
-            return invalid_data_error(&format!(
-                "Entity layer of {entity_layer_index} is invalid."
-            ));
-        }
-
-        let elevation;
-        if builder.elevation.is_empty() {
-            elevation = vec![0; dim];
-        } else if builder.elevation.len() != dim {
-            warn!(
-                "In '{}': Elevation array must be zero or dimensions length*width",
-                builder.id
-            );
-            elevation = vec![0; dim];
-        } else {
-            elevation = builder.elevation.clone();
-        }
-
-        Ok(LayerSet {
-            width,
-            height,
-            layers,
-            entity_layer_index,
-            elevation,
+        Grids {
             passable,
             visible,
-        })
+            swim,
+            movement_cost,
+        }
     }
 
     fn validate_tiles(builder: &AreaBuilder) -> Result<(), Error> {
@@ -225,6 +353,35 @@ impl LayerSet {
         self.passable[index]
     }
 
+    #[inline]
+    pub fn is_swim_index(&self, index: usize) -> bool {
+        self.swim[index]
+    }
+
+    /// Returns the additional squares of movement charged for entering the
+    /// specified index, as set by any tile overrides
+    #[inline]
+    pub fn movement_cost_index(&self, index: usize) -> u32 {
+        self.movement_cost[index]
+    }
+
+    pub fn tile_overrides(&self) -> &[TileOverride] {
+        &self.tile_overrides
+    }
+
+    /// Returns how the terrain at the specified point may be crossed, not
+    /// taking props or creatures into account
+    pub fn traversal(&self, x: i32, y: i32) -> Traversal {
+        let index = (x + y * self.width) as usize;
+        if !self.passable[index] {
+            Traversal::Impassable
+        } else if self.swim[index] {
+            Traversal::Swim
+        } else {
+            Traversal::Walk
+        }
+    }
+
     #[inline]
     pub fn is_visible(&self, x: i32, y: i32) -> bool {
         self.visible[(x + y * self.width) as usize]
@@ -20,7 +20,7 @@ use std::time;
 use std::{f32, ptr};
 
 use crate::MOVE_TO_THRESHOLD;
-use sulis_core::util::{self, Point};
+use sulis_core::util::{self, hex, Point};
 
 const MAX_ITERATIONS: i32 = 2_000;
 
@@ -107,6 +107,7 @@ pub struct PathFinder {
     parent_h_over2: f32,
 
     max_iterations: i32,
+    hex_grid: bool,
 }
 
 impl PathFinder {
@@ -127,9 +128,16 @@ impl PathFinder {
             parent_w_over2: 0.0,
             parent_h_over2: 0.0,
             max_iterations: MAX_ITERATIONS,
+            hex_grid: false,
         }
     }
 
+    /// Sets whether paths move between the six neighbors of each hex, rather
+    /// than the four neighbors of each square
+    pub fn set_hex_grid(&mut self, hex_grid: bool) {
+        self.hex_grid = hex_grid;
+    }
+
     pub fn set_max_iterations(&mut self, iterations: i32) {
         self.max_iterations = iterations;
     }
@@ -331,7 +339,11 @@ impl PathFinder {
 
     #[inline]
     // using an array here instead of a vec is much faster
-    fn get_neighbors(&self, point: i32) -> [i32; 4] {
+    fn get_neighbors(&self, point: i32) -> [i32; 6] {
+        if self.hex_grid {
+            return self.get_hex_neighbors(point);
+        }
+
         let width = self.width;
         let height = self.height;
 
@@ -340,7 +352,7 @@ impl PathFinder {
         let left = point - 1;
         let bottom = point + width;
 
-        let mut neighbors = [-1; 4];
+        let mut neighbors = [-1; 6];
         if top > 0 {
             neighbors[0] = top;
         }
@@ -358,6 +370,18 @@ impl PathFinder {
         neighbors
     }
 
+    #[inline]
+    fn get_hex_neighbors(&self, point: i32) -> [i32; 6] {
+        let mut neighbors = [-1; 6];
+        let (x, y) = (point % self.width, point / self.width);
+        for (i, p) in hex::neighbors(x, y).iter().enumerate() {
+            if p.x >= 0 && p.y >= 0 && p.x < self.width && p.y < self.height {
+                neighbors[i] = p.x + p.y * self.width;
+            }
+        }
+        neighbors
+    }
+
     #[inline]
     fn push_to_open_set(&mut self, index: i32, f_score: i32) {
         if self.open_set.contains(&index) {
@@ -380,6 +404,18 @@ impl PathFinder {
         let s_x = (start % self.width) as f32 + self.parent_w_over2;
         let s_y = (start / self.width) as f32 + self.parent_h_over2;
 
+        if self.hex_grid {
+            let from = Point::new(s_x as i32, s_y as i32);
+            let dist = hex::dist_to_rect(
+                from,
+                self.dest_x - self.dest_w_over2,
+                self.dest_y - self.dest_h_over2,
+                self.dest_w_over2 * 2.0,
+                self.dest_h_over2 * 2.0,
+            );
+            return (dist * dist) as i32;
+        }
+
         // closest distance from s_x, s_y to axis aligned dest
         // rect
 
@@ -102,6 +102,8 @@ pub struct UniformSet {
     pub sprite_prefix: String,
     pub impass: Vec<Vec<usize>>,
     pub invis: Vec<Vec<usize>>,
+    #[serde(default)]
+    pub swim: Vec<Vec<usize>>,
     pub tiles: Vec<String>,
 }
 
@@ -119,6 +121,8 @@ pub struct NonUniformSet {
 pub struct ImpassInvis {
     pub impass: Option<Vec<Vec<usize>>>,
     pub invis: Option<Vec<Vec<usize>>>,
+    #[serde(default)]
+    pub swim: Option<Vec<Vec<usize>>>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -132,6 +136,10 @@ pub struct TileBuilder {
     pub pass: Option<Vec<Vec<usize>>>,
     pub vis: Option<Vec<Vec<usize>>>,
     pub override_impass: Option<bool>,
+
+    /// Points that are water, which may only be crossed by swimming
+    #[serde(default)]
+    pub swim: Option<Vec<Vec<usize>>>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -247,6 +255,11 @@ impl Tileset {
             } else {
                 Some(uniform.invis.clone())
             };
+            let swim = if uniform.swim.is_empty() {
+                None
+            } else {
+                Some(uniform.swim.clone())
+            };
             for tile_id in uniform.tiles.iter() {
                 let id = format!("{prefix}{tile_id}");
                 let tile = TileBuilder {
@@ -258,6 +271,7 @@ impl Tileset {
                     pass: None,
                     vis: None,
                     override_impass: None,
+                    swim: swim.clone(),
                 };
 
                 self.tiles.insert(id, tile);
@@ -281,6 +295,7 @@ impl Tileset {
                     pass: None,
                     vis: None,
                     override_impass: None,
+                    swim: impass_invis.swim.clone(),
                 };
 
                 self.tiles.insert(id, tile);
@@ -289,6 +304,14 @@ impl Tileset {
     }
 }
 
+/// How creatures may cross a given point of terrain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Traversal {
+    Walk,
+    Swim,
+    Impassable,
+}
+
 #[derive(Debug)]
 pub struct Tile {
     pub id: String,
@@ -298,6 +321,7 @@ pub struct Tile {
     pub image_display: Rc<Sprite>,
     pub impass: Vec<Point>,
     pub invis: Vec<Point>,
+    pub swim: Vec<Point>,
     pub override_impass: bool,
 }
 
@@ -365,6 +389,12 @@ impl Tile {
             }
         }
 
+        let mut swim_points: Vec<Point> = Vec::new();
+        for p in builder.swim.unwrap_or_default() {
+            let (x, y) = verify_point("swim", width, height, p)?;
+            swim_points.push(Point::new(x, y));
+        }
+
         let sprite = ResourceSet::sprite(&builder.sprite)?;
 
         Ok(Tile {
@@ -375,6 +405,7 @@ impl Tile {
             image_display: sprite,
             impass: impass_points,
             invis: invis_points,
+            swim: swim_points,
             override_impass: builder.override_impass.unwrap_or(false),
         })
     }
@@ -0,0 +1,57 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use crate::OnTrigger;
+
+/// A short exchange between party members, shown as floating text over
+/// each speaker in turn while the party is exploring.  Banters are
+/// started automatically when their conditions are met, no more often
+/// than the rules `banter_cooldown`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Banter {
+    pub id: String,
+
+    /// If not empty, the banter may only start in one of these areas
+    #[serde(default)]
+    pub areas: Vec<String>,
+
+    /// Conditions that must match, checked against the player
+    #[serde(default)]
+    pub to_view: Vec<OnTrigger>,
+
+    /// The number of rounds before this banter may play again.  If not
+    /// specified, the banter only plays once
+    #[serde(default)]
+    pub cooldown: Option<u32>,
+
+    pub lines: Vec<BanterLine>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BanterLine {
+    /// The actor ID of the party member speaking this line, or `player`
+    pub speaker: String,
+    pub text: String,
+}
+
+impl Banter {
+    /// Returns true if this banter may start in the area with the specified ID
+    pub fn allowed_in_area(&self, area_id: &str) -> bool {
+        self.areas.is_empty() || self.areas.iter().any(|area| area == area_id)
+    }
+}
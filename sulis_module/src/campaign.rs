@@ -25,7 +25,8 @@ use sulis_core::image::Image;
 use sulis_core::resource::ResourceSet;
 use sulis_core::util::{unable_to_create_error, Point};
 
-use crate::{on_trigger, Conversation, Module};
+use crate::area::PropDataBuilder;
+use crate::{on_trigger, Actor, Conversation, ItemListEntrySaveState, Module};
 
 pub struct WorldMap {
     pub size: (f32, f32),
@@ -47,6 +48,143 @@ pub struct WorldMapLocation {
     pub travel_times: HashMap<String, u32>,
 }
 
+/// The party's stronghold.  Props placed in the housing area, including
+/// furnishings added by upgrades, persist for the rest of the campaign.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Housing {
+    pub area: String,
+
+    /// Where companions sent to the housing wait while not in the party
+    #[serde(default)]
+    pub idle_locations: Vec<Point>,
+
+    #[serde(default)]
+    pub upgrades: HashMap<String, HousingUpgrade>,
+}
+
+/// An event, such as a festival or invasion, that runs a script at a game
+/// date and optionally repeats at a fixed interval after that
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct WorldEvent {
+    pub id: String,
+
+    /// The game date the event first fires
+    pub at: Time,
+
+    /// If set, the event fires again each time this much game time elapses
+    #[serde(default)]
+    pub repeat: Option<Time>,
+
+    /// The event stops firing once this quest is complete
+    #[serde(default)]
+    pub unless_quest_complete: Option<String>,
+
+    /// Called with the event ID each time the event fires
+    pub script: on_trigger::ScriptData,
+}
+
+/// The starting conditions for a new game in this campaign.
+pub struct NewGame {
+    /// If set, this actor is always the player character and character
+    /// selection is skipped
+    pub player: Option<Rc<Actor>>,
+
+    /// Companions that join the party alongside the player character
+    pub party: Vec<Rc<Actor>>,
+
+    /// Coins added to the party stash, in addition to those of the player character
+    pub coins: i32,
+
+    /// Items added to the party stash, in addition to those of the player character
+    pub items: Vec<ItemListEntrySaveState>,
+
+    /// Cutscene shown once the starting area has loaded
+    pub intro_cutscene: Option<String>,
+
+    /// Script called once the starting area has loaded, after the intro cutscene
+    pub intro_script: Option<on_trigger::ScriptData>,
+
+    /// IDs of earlier campaigns whose world export is imported when starting
+    /// a new game, in order of preference
+    pub import_from: Vec<String>,
+}
+
+impl NewGame {
+    fn new(builder: NewGameBuilder) -> Result<NewGame, Error> {
+        let player = match builder.player {
+            None => None,
+            Some(id) => match Module::actor(&id) {
+                None => {
+                    warn!("New game player actor '{}' not found", id);
+                    return unable_to_create_error("new_game", &id);
+                }
+                Some(actor) => Some(actor),
+            },
+        };
+
+        let mut party = Vec::new();
+        for id in builder.party {
+            match Module::actor(&id) {
+                None => {
+                    warn!("New game party actor '{}' not found", id);
+                    return unable_to_create_error("new_game", &id);
+                }
+                Some(actor) => party.push(actor),
+            }
+        }
+
+        if let Some(ref id) = builder.intro_cutscene {
+            if Module::cutscene(id).is_none() {
+                warn!("New game intro cutscene '{}' not found", id);
+                return unable_to_create_error("new_game", id);
+            }
+        }
+
+        Ok(NewGame {
+            player,
+            party,
+            coins: builder.coins,
+            items: builder.items,
+            intro_cutscene: builder.intro_cutscene,
+            intro_script: builder.intro_script,
+            import_from: builder.import_from,
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct NewGameBuilder {
+    #[serde(default)]
+    pub player: Option<String>,
+    #[serde(default)]
+    pub party: Vec<String>,
+    #[serde(default)]
+    pub coins: i32,
+    #[serde(default)]
+    pub items: Vec<ItemListEntrySaveState>,
+    #[serde(default)]
+    pub intro_cutscene: Option<String>,
+    #[serde(default)]
+    pub intro_script: Option<on_trigger::ScriptData>,
+    #[serde(default)]
+    pub import_from: Vec<String>,
+}
+
+/// A set of furnishings that scripts may add to the housing area
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HousingUpgrade {
+    /// Props at these locations are removed before the new props are placed
+    #[serde(default)]
+    pub remove: Vec<Point>,
+
+    #[serde(default)]
+    pub props: Vec<PropDataBuilder>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct CampaignGroup {
@@ -91,10 +229,20 @@ pub struct Campaign {
     pub backstory_conversation: Rc<Conversation>,
     pub max_starting_level: u32,
     pub on_party_death_script: on_trigger::ScriptData,
+
+    /// If set, this script is called when the whole party is defeated, after the
+    /// party is revived, instead of showing the game over window
+    pub on_party_defeated_script: Option<on_trigger::ScriptData>,
     pub on_tick_script: Option<on_trigger::ScriptData>,
     pub on_round_elapsed_script: Option<on_trigger::ScriptData>,
+    pub on_loot_generated_script: Option<on_trigger::ScriptData>,
+    pub on_theft_script: Option<on_trigger::ScriptData>,
+    pub on_attack_roll_script: Option<on_trigger::ScriptData>,
     pub world_map: WorldMap,
+    pub housing: Option<Housing>,
+    pub world_events: Vec<WorldEvent>,
     pub group: Option<CampaignGroup>,
+    pub new_game: NewGame,
 }
 
 impl Campaign {
@@ -110,6 +258,8 @@ impl Campaign {
             Some(convo) => convo,
         };
 
+        let new_game = NewGame::new(builder.new_game)?;
+
         let mut locations = Vec::new();
         for (id, location) in builder.world_map.locations {
             let image = match ResourceSet::image(&location.icon) {
@@ -144,13 +294,20 @@ impl Campaign {
             id: builder.id,
             max_starting_level: builder.max_starting_level,
             on_party_death_script: builder.on_party_death_script,
+            on_party_defeated_script: builder.on_party_defeated_script,
             on_tick_script: builder.on_tick_script,
             on_round_elapsed_script: builder.on_round_elapsed_script,
+            on_loot_generated_script: builder.on_loot_generated_script,
+            on_theft_script: builder.on_theft_script,
+            on_attack_roll_script: builder.on_attack_roll_script,
             world_map: WorldMap {
                 size: builder.world_map.size,
                 offset: builder.world_map.offset,
                 locations,
             },
+            housing: builder.housing,
+            world_events: builder.world_events,
+            new_game,
         })
     }
 }
@@ -168,9 +325,20 @@ pub struct CampaignBuilder {
     pub backstory_conversation: String,
     pub max_starting_level: u32,
     pub on_party_death_script: on_trigger::ScriptData,
+    #[serde(default)]
+    pub on_party_defeated_script: Option<on_trigger::ScriptData>,
     pub on_tick_script: Option<on_trigger::ScriptData>,
     pub on_round_elapsed_script: Option<on_trigger::ScriptData>,
+    pub on_loot_generated_script: Option<on_trigger::ScriptData>,
+    pub on_theft_script: Option<on_trigger::ScriptData>,
+    pub on_attack_roll_script: Option<on_trigger::ScriptData>,
     pub world_map: WorldMapBuilder,
+    #[serde(default)]
+    pub housing: Option<Housing>,
+    #[serde(default)]
+    pub world_events: Vec<WorldEvent>,
+    #[serde(default)]
+    pub new_game: NewGameBuilder,
 }
 
 #[derive(Deserialize, Debug)]
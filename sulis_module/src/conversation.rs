@@ -35,6 +35,19 @@ pub struct Response {
     pub to_view: Vec<OnTrigger>,
 }
 
+/// A party member interrupting a conversation.  When a node with an
+/// interjection is reached, the conversation instead moves to the `to`
+/// node, spoken by the party member with the actor ID `speaker`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Interjection {
+    pub speaker: String,
+    pub to: String,
+
+    #[serde(default)]
+    pub to_view: Vec<OnTrigger>,
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 struct Node {
@@ -43,6 +56,9 @@ struct Node {
     #[serde(default)]
     switch_speaker: Option<String>,
 
+    #[serde(default)]
+    interjections: Vec<Interjection>,
+
     #[serde(default)]
     on_view: Vec<OnTrigger>,
     responses: Vec<Response>,
@@ -87,6 +103,16 @@ impl Conversation {
                     }
                 }
             }
+
+            for interjection in node.interjections.iter() {
+                if !builder.nodes.contains_key(&interjection.to) {
+                    warn!(
+                        "Invalid to '{}' for node interjection.  Must be a node ID",
+                        interjection.to
+                    );
+                    return unable_to_create_error("conversation", &builder.id);
+                }
+            }
         }
 
         Ok(Conversation {
@@ -116,6 +142,13 @@ impl Conversation {
         }
     }
 
+    pub fn interjections(&self, node: &str) -> &Vec<Interjection> {
+        match self.nodes.get(node) {
+            None => panic!("Invalid node"),
+            Some(node) => &node.interjections,
+        }
+    }
+
     pub fn text(&self, node: &str) -> &str {
         match self.nodes.get(node) {
             None => panic!("Invalid node"),
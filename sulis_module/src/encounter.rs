@@ -18,13 +18,20 @@ use std::collections::{HashMap, HashSet};
 use std::io::Error;
 use std::rc::Rc;
 
+use crate::on_trigger::ScriptData;
 use crate::{Actor, Module};
+use sulis_core::config::Difficulty;
 use sulis_core::io::SoundSource;
 use sulis_core::resource::ResourceSet;
 use sulis_core::util::{gen_rand, unable_to_create_error};
 
+/// The party size encounters are designed for.  Encounters with
+/// `adds_per_party_member` generate additional actors for larger parties.
+const BASE_PARTY_SIZE: u32 = 4;
+
 struct Entry {
     actor: Rc<Actor>,
+    elite: Option<Rc<Actor>>,
     unique_id: Option<String>,
     weight: u32,
     always: bool,
@@ -37,8 +44,13 @@ pub struct Encounter {
     pub auto_spawn: bool,
     min_gen_actors: u32,
     max_gen_actors: u32,
+    adds_per_party_member: u32,
     entries: Vec<Entry>,
     total_weight: u32,
+
+    /// Called when the encounter spawns, with the generated actors, allowing
+    /// the composition to be modified
+    pub on_spawn_script: Option<ScriptData>,
 }
 
 impl Encounter {
@@ -70,6 +82,17 @@ impl Encounter {
                 Some(actor) => Rc::clone(actor),
             };
 
+            let elite = match entry.elite {
+                None => None,
+                Some(ref id) => match module.actors.get(id) {
+                    None => {
+                        warn!("no elite actor '{}' found", id);
+                        return unable_to_create_error("encounter", &builder.id);
+                    }
+                    Some(actor) => Some(Rc::clone(actor)),
+                },
+            };
+
             if entry.always && entry.limit.is_some() {
                 warn!("Cannot set a limit on an always generated entry.");
                 return unable_to_create_error("encounter", &builder.id);
@@ -83,6 +106,7 @@ impl Encounter {
             total_weight += entry.weight;
             entries.push(Entry {
                 actor,
+                elite,
                 unique_id: entry.unique_id,
                 weight: entry.weight,
                 limit: entry.limit,
@@ -101,12 +125,18 @@ impl Encounter {
             auto_spawn: builder.auto_spawn,
             min_gen_actors: builder.min_gen_actors,
             max_gen_actors: builder.max_gen_actors,
+            adds_per_party_member: builder.adds_per_party_member,
             entries,
             total_weight,
+            on_spawn_script: builder.on_spawn_script,
         })
     }
 
-    fn gen_actor(&self, count: &mut HashMap<String, u32>) -> Option<(Rc<Actor>, Option<String>)> {
+    fn gen_actor(
+        &self,
+        count: &mut HashMap<String, u32>,
+        difficulty: Difficulty,
+    ) -> Option<(Rc<Actor>, Option<String>)> {
         if self.total_weight == 0 {
             return None;
         }
@@ -128,7 +158,7 @@ impl Encounter {
                 count.insert(entry.actor.id.to_string(), cur_count + 1);
             }
 
-            return Some((Rc::clone(&entry.actor), entry.unique_id.clone()));
+            return Some((entry.actor_for(difficulty), entry.unique_id.clone()));
         }
 
         warn!("Unable to generate a valid actor after max attempts");
@@ -148,16 +178,29 @@ impl Encounter {
         None
     }
 
-    pub fn gen_actors(&self) -> Vec<(Rc<Actor>, Option<String>)> {
+    /// Generates the actors for this encounter.  Parties larger than four
+    /// get `adds_per_party_member` extra actors for each additional member.
+    /// On `Easy` difficulty the minimum number of actors is generated, and
+    /// on `Hard` entries with an elite variant use it.
+    pub fn gen_actors(
+        &self,
+        party_size: u32,
+        difficulty: Difficulty,
+    ) -> Vec<(Rc<Actor>, Option<String>)> {
         let mut actors = Vec::new();
 
-        let total_num = gen_rand(self.min_gen_actors, self.max_gen_actors + 1);
+        let adds = self.adds_per_party_member * party_size.saturating_sub(BASE_PARTY_SIZE);
+        let base_num = match difficulty {
+            Difficulty::Easy => self.min_gen_actors,
+            _ => gen_rand(self.min_gen_actors, self.max_gen_actors + 1),
+        };
+        let total_num = base_num + adds;
 
         let mut count = HashMap::new();
         let mut cur_num = 0;
 
         while cur_num < total_num {
-            let actor = match self.gen_actor(&mut count) {
+            let actor = match self.gen_actor(&mut count, difficulty) {
                 None => {
                     warn!("Unable to generate actor for encounter '{}'", self.id);
                     return actors;
@@ -172,7 +215,7 @@ impl Encounter {
 
         for entry in self.entries.iter() {
             if entry.always {
-                actors.push((Rc::clone(&entry.actor), entry.unique_id.clone()));
+                actors.push((entry.actor_for(difficulty), entry.unique_id.clone()));
             }
         }
 
@@ -180,6 +223,15 @@ impl Encounter {
     }
 }
 
+impl Entry {
+    fn actor_for(&self, difficulty: Difficulty) -> Rc<Actor> {
+        match (&self.elite, difficulty) {
+            (Some(elite), Difficulty::Hard) => Rc::clone(elite),
+            _ => Rc::clone(&self.actor),
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct EncounterBuilder {
@@ -188,6 +240,13 @@ pub struct EncounterBuilder {
     pub auto_spawn: bool,
     min_gen_actors: u32,
     max_gen_actors: u32,
+
+    #[serde(default)]
+    adds_per_party_member: u32,
+
+    #[serde(default)]
+    on_spawn_script: Option<ScriptData>,
+
     entries: Vec<EntryBuilder>,
 }
 
@@ -206,4 +265,7 @@ pub struct EntryBuilder {
 
     #[serde(default)]
     unique_id: Option<String>,
+
+    #[serde(default)]
+    elite: Option<String>,
 }
@@ -89,8 +89,11 @@ impl<'a, 'b> PropGen<'a, 'b> {
                 id: prop.prop.id.to_string(),
                 location: Point::new(prop.x, prop.y),
                 items: Vec::new(),
+                loot: None,
                 enabled: None,
                 hover_text: None,
+                owner: None,
+                lock: None,
             });
         }
         out
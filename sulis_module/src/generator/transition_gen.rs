@@ -92,6 +92,7 @@ impl<'a> TransitionGen<'a> {
                     },
                     hover_text: transition.hover_text.to_string(),
                     image_display: "empty".to_string(),
+                    thumbnail: None,
                 };
                 out.push(TransitionOutput {
                     transition: transition_out,
@@ -90,6 +90,52 @@ impl ImageLayer {
     }
 }
 
+/// The colors and race defined custom layers chosen for an actor, applied
+/// when composing its layered image
+#[derive(Clone, Debug, Default)]
+pub struct Appearance {
+    pub hair: Option<Color>,
+    pub skin: Option<Color>,
+
+    /// Colors from race color channels, taking precedence over the hair
+    /// and skin colors for these layers
+    pub layer_colors: HashMap<ImageLayer, Color>,
+
+    /// Images for race custom layers, each drawn directly above the
+    /// specified layer with an optional color
+    pub custom: Vec<(ImageLayer, Rc<dyn Image>, Option<Color>)>,
+}
+
+impl Appearance {
+    pub fn new(hair: Option<Color>, skin: Option<Color>) -> Appearance {
+        Appearance {
+            hair,
+            skin,
+            ..Default::default()
+        }
+    }
+
+    pub fn color(&self, layer: ImageLayer) -> Option<Color> {
+        if let Some(color) = self.layer_colors.get(&layer) {
+            return Some(*color);
+        }
+
+        match layer {
+            Hair | Beard => self.hair,
+            Background | Foreground | Ears => self.skin,
+            _ => None,
+        }
+    }
+
+    fn push_custom(&self, list: &mut Vec<Layer>, layer: ImageLayer, x: f32, y: f32) {
+        for (above, image, color) in self.custom.iter() {
+            if *above == layer {
+                list.push(Layer::new(x, y, *color, Rc::clone(image)));
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ImageLayerSet {
     images: HashMap<Sex, HashMap<ImageLayer, Rc<dyn Image>>>,
@@ -129,21 +175,21 @@ impl ImageLayerSet {
     }
 
     /// Gets the list of images from this ImageLayerSet for the given Sex.
-    /// The images are ordered based on the iteration order of ImageLayer
-    pub fn get_list(&self, sex: Sex, hair: Option<Color>, skin: Option<Color>) -> Vec<Layer> {
+    /// The images are ordered based on the iteration order of ImageLayer,
+    /// with any custom layers of the appearance above their base layer
+    pub fn get_list(&self, sex: Sex, appearance: &Appearance) -> Vec<Layer> {
         let mut list = Vec::new();
 
         match self.images.get(&sex) {
             None => return list,
             Some(sex_map) => {
                 for layer in ImageLayer::iter() {
-                    let image = match sex_map.get(layer) {
-                        None => continue,
-                        Some(image) => Rc::clone(image),
-                    };
+                    if let Some(image) = sex_map.get(layer) {
+                        let color = appearance.color(*layer);
+                        list.push(Layer::new(0.0, 0.0, color, Rc::clone(image)));
+                    }
 
-                    let out = Layer::new(0.0, 0.0, get_color(*layer, hair, skin), image);
-                    list.push(out);
+                    appearance.push_custom(&mut list, *layer, 0.0, 0.0);
                 }
             }
         }
@@ -157,39 +203,29 @@ impl ImageLayerSet {
         &self,
         sex: Sex,
         race: &Rc<Race>,
-        hair: Option<Color>,
-        skin: Option<Color>,
+        appearance: &Appearance,
         insert: HashMap<ImageLayer, Rc<dyn Image>>,
     ) -> Vec<Layer> {
         let mut list = Vec::new();
 
-        match self.images.get(&sex) {
-            Some(sex_map) => {
-                for layer in ImageLayer::iter() {
-                    let mut base_size = None;
-                    if let Some(image) = sex_map.get(layer) {
-                        base_size = Some((image.get_width_f32(), image.get_height_f32()));
-                    }
+        let sex_map = self.images.get(&sex);
+        for layer in ImageLayer::iter() {
+            let (x, y) = match race.get_image_layer_offset(*layer) {
+                None => continue,
+                Some((x, y)) => (*x, *y),
+            };
 
-                    if insert_for_race_sex(&mut list, &insert, sex, race, *layer, base_size) {
-                        continue;
-                    }
+            let base = sex_map.and_then(|sex_map| sex_map.get(layer));
+            let base_size = base.map(|image| (image.get_width_f32(), image.get_height_f32()));
 
-                    let (x, y) = match race.get_image_layer_offset(*layer) {
-                        None => continue,
-                        Some((x, y)) => (*x, *y),
-                    };
-                    if let Some(image) = sex_map.get(layer) {
-                        let out = Layer::new(x, y, get_color(*layer, hair, skin), Rc::clone(image));
-                        list.push(out);
-                    }
-                }
-            }
-            None => {
-                for layer in ImageLayer::iter() {
-                    insert_for_race_sex(&mut list, &insert, sex, race, *layer, None);
+            if !insert_for_race_sex(&mut list, &insert, sex, race, *layer, base_size) {
+                if let Some(image) = base {
+                    let color = appearance.color(*layer);
+                    list.push(Layer::new(x, y, color, Rc::clone(image)));
                 }
             }
+
+            appearance.push_custom(&mut list, *layer, x, y);
         }
 
         list
@@ -220,14 +256,6 @@ impl ImageLayerSet {
     }
 }
 
-fn get_color(layer: ImageLayer, hair: Option<Color>, skin: Option<Color>) -> Option<Color> {
-    match layer {
-        Hair | Beard => hair,
-        Background | Foreground | Ears => skin,
-        _ => None,
-    }
-}
-
 fn insert_for_race_sex(
     list: &mut Vec<Layer>,
     insert: &HashMap<ImageLayer, Rc<dyn Image>>,
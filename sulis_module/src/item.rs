@@ -68,8 +68,15 @@ pub struct Item {
     pub value: i32,
     pub weight: i32,
     pub quest: bool,
+
+    /// Key items are kept in the party keyring rather than the stash, and
+    /// open props locked with their ID
+    pub key: bool,
     pub usable: Option<Usable>,
 
+    /// The ID of the `ItemSet` this item belongs to, if any
+    pub set: Option<String>,
+
     // original values from before any adjectives are applied
     pub original_id: String,
     original_value: i32,
@@ -157,7 +164,9 @@ impl Item {
             value,
             weight: item.weight,
             quest: item.quest,
+            key: item.key,
             usable: item.usable.clone(),
+            set: item.set.clone(),
             prereqs,
             original_id: item.original_id.clone(),
             original_value: item.original_value,
@@ -201,6 +210,13 @@ impl Item {
             }
         };
 
+        if let Some(set) = &builder.set {
+            if !module.item_sets.contains_key(set) {
+                warn!("No item set found with id '{}'", set);
+                return unable_to_create_error("item", &builder.id);
+            }
+        }
+
         let prereqs = match builder.prereqs {
             None => None,
             Some(list) => Some(PrereqList::new(list)?),
@@ -252,7 +268,9 @@ impl Item {
             value,
             weight: builder.weight as i32,
             quest: builder.quest,
+            key: builder.key,
             usable,
+            set: builder.set,
             prereqs,
             original_id: builder.id,
             original_value: builder.value as i32,
@@ -282,6 +300,12 @@ impl Item {
         }
     }
 
+    /// Returns the reason the actor does not meet this item's prereqs,
+    /// or None if it does
+    pub fn unmet_prereq(&self, actor: &Actor) -> Option<String> {
+        self.prereqs.as_ref()?.unmet_reason(actor)
+    }
+
     pub fn icon(&self, variant: Option<usize>) -> Rc<dyn Image> {
         Rc::clone(match variant {
             None => &self.icon,
@@ -438,6 +462,11 @@ pub struct ItemBuilder {
     #[serde(default)]
     quest: bool,
 
+    #[serde(default)]
+    key: bool,
+
+    set: Option<String>,
+
     #[serde(default)]
     variants: Vec<VariantBuilder>,
 }
@@ -0,0 +1,50 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use crate::rules::BonusList;
+
+/// A named set of items.  Items belong to a set via their `set` tag, and
+/// equipping several different items from the same set grants the bonuses of
+/// each tier whose piece count has been reached.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ItemSet {
+    pub id: String,
+    pub name: String,
+
+    pub tiers: Vec<ItemSetTier>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ItemSetTier {
+    /// The number of distinct set items that must be equipped
+    pub pieces: u32,
+
+    /// A short description of the bonuses, shown in item tooltips
+    pub description: String,
+
+    #[serde(default)]
+    pub bonuses: BonusList,
+}
+
+impl ItemSet {
+    /// Returns all tiers granted by having `pieces` distinct items from
+    /// this set equipped
+    pub fn active_tiers(&self, pieces: u32) -> impl Iterator<Item = &ItemSetTier> {
+        self.tiers.iter().filter(move |tier| tier.pieces <= pieces)
+    }
+}
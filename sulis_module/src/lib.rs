@@ -22,15 +22,21 @@ extern crate log;
 extern crate serde_derive;
 
 pub mod ability;
-pub use self::ability::Ability;
+pub use self::ability::{Ability, TargetingSounds};
 
 pub mod ability_list;
 pub use self::ability_list::AbilityList;
 
+pub mod achievement;
+pub use self::achievement::Achievement;
+
 pub mod actor;
 pub use self::actor::Actor;
 pub use self::actor::ActorBuilder;
+pub use self::actor::ApprovalAbility;
+pub use self::actor::{EntityScript, EntityScriptHook};
 pub use self::actor::Faction;
+pub use self::actor::Facing;
 pub use self::actor::Sex;
 
 pub mod ai;
@@ -39,6 +45,9 @@ pub use self::ai::AITemplate;
 pub mod area;
 pub use self::area::Area;
 
+pub mod banter;
+pub use self::banter::Banter;
+
 pub mod class;
 pub use self::class::Class;
 
@@ -62,11 +71,13 @@ pub use self::encounter::Encounter;
 pub mod campaign;
 pub use self::campaign::Campaign;
 pub use self::campaign::CampaignGroup;
+pub use self::campaign::NewGame;
 
 pub mod generator;
 use self::generator::{AreaGenerator, GeneratorBuilder};
 
 pub mod image_layer;
+pub use self::image_layer::Appearance;
 pub use self::image_layer::ImageLayer;
 pub use self::image_layer::ImageLayerSet;
 
@@ -86,9 +97,15 @@ pub use self::item_state::ItemState;
 pub mod item_adjective;
 pub use self::item_adjective::{ItemAdjective, ItemAdjectiveBuilder};
 
+pub mod item_set;
+pub use self::item_set::{ItemSet, ItemSetTier};
+
 pub mod loot_list;
 pub use self::loot_list::LootList;
 
+pub mod lore_entry;
+pub use self::lore_entry::LoreEntry;
+
 pub mod modification;
 pub use self::modification::ModificationInfo;
 
@@ -97,7 +114,7 @@ pub use self::prereq_list::PrereqList;
 pub use self::prereq_list::PrereqListBuilder;
 
 pub mod prop;
-pub use self::prop::Prop;
+pub use self::prop::{Destructible, Prop};
 
 pub mod quest;
 pub use self::quest::Quest;
@@ -108,9 +125,10 @@ pub use self::race::Race;
 pub mod rules;
 pub use self::rules::bonus;
 pub use self::rules::{
-    AccuracyKind, Armor, ArmorKind, Attack, AttackBonuses, AttackKind, Attribute, AttributeList,
-    Bonus, BonusKind, BonusList, Damage, DamageKind, DamageList, HitFlags, HitKind, ItemKind,
-    QuickSlot, Resistance, Rules, Slot, StatList, Time, WeaponKind, WeaponStyle, ROUND_TIME_MILLIS,
+    AccuracyKind, Armor, ArmorKind, Attack, AttackBonuses, AttackKind, AttackRoll, Attribute,
+    AttributeList, Bonus, BonusKind, BonusList, Damage, DamageKind, DamageList, GridKind, HitFlags,
+    HitKind, ItemKind, QuickSlot, Resistance, Rules, Slot, StatList, Time, WeaponKind, WeaponStyle,
+    ROUND_TIME_MILLIS,
 };
 
 use std::cell::RefCell;
@@ -119,7 +137,7 @@ use std::ffi::OsStr;
 use std::fmt::{self, Display};
 use std::fs;
 use std::io::Error;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::time;
 
@@ -155,19 +173,24 @@ thread_local! {
 #[derive(Default)]
 pub struct Module {
     rules: Option<Rc<Rules>>,
+    saved_rules: Option<Rc<Rules>>,
     campaign: Option<Rc<Campaign>>,
     abilities: HashMap<String, Rc<Ability>>,
     ability_lists: HashMap<String, Rc<AbilityList>>,
+    achievements: HashMap<String, Rc<Achievement>>,
     actors: HashMap<String, Rc<Actor>>,
     ai_templates: HashMap<String, Rc<AITemplate>>,
     areas: HashMap<String, Rc<Area>>,
+    banters: HashMap<String, Rc<Banter>>,
     classes: HashMap<String, Rc<Class>>,
     conversations: HashMap<String, Rc<Conversation>>,
     cutscenes: HashMap<String, Rc<Cutscene>>,
     encounters: HashMap<String, Rc<Encounter>>,
     items: HashMap<String, Rc<Item>>,
     item_adjectives: HashMap<String, Rc<ItemAdjective>>,
+    item_sets: HashMap<String, Rc<ItemSet>>,
     loot_lists: HashMap<String, Rc<LootList>>,
+    lore_entries: HashMap<String, Rc<LoreEntry>>,
     props: HashMap<String, Rc<Prop>>,
     quests: HashMap<String, Rc<Quest>>,
     races: HashMap<String, Rc<Race>>,
@@ -202,7 +225,26 @@ impl ModuleInfo {
         debug!("Checking module at '{}'", path_str);
 
         let campaign: CampaignBuilder = read_single_resource(&format!("{path_str}/campaign"))?;
+        Ok(ModuleInfo::new(campaign, path_str))
+    }
+
+    /// Reads the module info from the campaign file in a module archive,
+    /// without extracting it
+    fn from_archive(path: PathBuf) -> Result<ModuleInfo, Error> {
+        let path_str = path.to_string_lossy().to_string();
+        debug!("Checking module archive at '{}'", path_str);
+
+        let archive = ModuleArchive::open(&path)?;
+        let manifest = archive.manifest()?;
+        if manifest.kind != ArchiveKind::Campaign {
+            return invalid_data_error("Archive does not contain a campaign");
+        }
 
+        let campaign: CampaignBuilder = archive.read_yaml("campaign.yml")?;
+        Ok(ModuleInfo::new(campaign, path_str))
+    }
+
+    fn new(campaign: CampaignBuilder, dir: String) -> ModuleInfo {
         let group = match campaign.group {
             None => CampaignGroup {
                 id: campaign.id.to_string(),
@@ -212,13 +254,13 @@ impl ModuleInfo {
             Some(group) => group,
         };
 
-        Ok(ModuleInfo {
+        ModuleInfo {
             id: campaign.id,
-            dir: path_str,
+            dir,
             name: campaign.name,
             description: campaign.description,
             group,
-        })
+        }
     }
 }
 
@@ -274,9 +316,52 @@ impl Module {
             }
         }
 
+        for archive in module_archive::archives_in(&root_dir)
+            .into_iter()
+            .chain(module_archive::archives_in(&user_dir))
+            .flatten()
+        {
+            match ModuleInfo::from_archive(archive.clone()) {
+                Ok(module) => modules.push(module),
+                Err(e) => warn!("Error reading module archive '{:?}': {}", archive, e),
+            }
+        }
+
         modules
     }
 
+    /// Bundles the campaign or modification at `dir` into a module archive
+    /// with the specified `version`, written to the current directory.
+    /// Returns the path of the written archive
+    pub fn package(dir: &str, version: &str) -> Result<PathBuf, Error> {
+        let (kind, id, name) = match read_single_resource::<CampaignBuilder>(
+            &format!("{dir}/campaign"),
+        ) {
+            Ok(campaign) => (ArchiveKind::Campaign, campaign.id, campaign.name),
+            Err(_) => {
+                let info = ModificationInfo::from_dir(PathBuf::from(dir))?;
+                (ArchiveKind::Modification, info.id, info.name)
+            }
+        };
+
+        let manifest = ArchiveManifest {
+            id,
+            name,
+            version: version.to_string(),
+            kind,
+            format: module_archive::ARCHIVE_FORMAT,
+        };
+
+        let out = PathBuf::from(format!(
+            "{}-{}.{}",
+            manifest.id,
+            manifest.version,
+            module_archive::ARCHIVE_EXTENSION
+        ));
+        module_archive::write_archive(Path::new(dir), &out, &manifest)?;
+        Ok(out)
+    }
+
     pub fn delete_character(id: &str) {
         // TODO don't assume ID = filename
         let mut path = config::USER_DIR.clone();
@@ -422,16 +507,20 @@ impl Module {
             let mut module = module.borrow_mut();
             module.abilities.clear();
             module.ability_lists.clear();
+            module.achievements.clear();
             module.actors.clear();
             module.ai_templates.clear();
             module.areas.clear();
+            module.banters.clear();
             module.classes.clear();
             module.conversations.clear();
             module.cutscenes.clear();
             module.encounters.clear();
             module.items.clear();
             module.item_adjectives.clear();
+            module.item_sets.clear();
             module.loot_lists.clear();
+            module.lore_entries.clear();
             module.quests.clear();
             module.props.clear();
             module.races.clear();
@@ -446,6 +535,7 @@ impl Module {
             module.wall_kinds.clear();
 
             module.rules = Some(Rc::new(rules));
+            module.saved_rules = None;
             module.scripts = read_to_string(&dirs, "scripts");
             expand_include_directives(&mut module.scripts);
 
@@ -469,6 +559,42 @@ impl Module {
                 module.quests.insert(id, Rc::new(quest));
             }
 
+            for (id, achievement) in builder_set.achievements {
+                trace!(
+                    "Inserting resource of type achievement with key {} \
+                     into module.",
+                    id
+                );
+                module.achievements.insert(id, Rc::new(achievement));
+            }
+
+            for (id, item_set) in builder_set.item_sets {
+                trace!(
+                    "Inserting resource of type item set with key {} \
+                     into module.",
+                    id
+                );
+                module.item_sets.insert(id, Rc::new(item_set));
+            }
+
+            for (id, lore_entry) in builder_set.lore_entries {
+                trace!(
+                    "Inserting resource of type lore entry with key {} \
+                     into module.",
+                    id
+                );
+                module.lore_entries.insert(id, Rc::new(lore_entry));
+            }
+
+            for (id, banter) in builder_set.banters {
+                trace!(
+                    "Inserting resource of type banter with key {} \
+                     into module.",
+                    id
+                );
+                module.banters.insert(id, Rc::new(banter));
+            }
+
             for (id, builder) in builder_set.size_builders {
                 insert_if_ok("size", id, ObjectSize::new(builder), &mut module.sizes);
             }
@@ -679,10 +805,27 @@ impl Module {
         MODULE.with(|m| Rc::clone(m.borrow().campaign.as_ref().unwrap()))
     }
 
+    /// Returns the rules in effect for the current game.  This is the snapshot
+    /// stored in the loaded save file, if there is one, or the module's rules
     pub fn rules() -> Rc<Rules> {
+        MODULE.with(|m| {
+            let m = m.borrow();
+            Rc::clone(m.saved_rules.as_ref().or(m.rules.as_ref()).unwrap())
+        })
+    }
+
+    /// Returns the rules as currently defined by the module, ignoring any
+    /// snapshot from a loaded save
+    pub fn module_rules() -> Rc<Rules> {
         MODULE.with(|m| Rc::clone(m.borrow().rules.as_ref().unwrap()))
     }
 
+    /// Sets the rules snapshot from a save file to use in place of the
+    /// module's rules, or clears it with `None`
+    pub fn set_saved_rules(rules: Option<Rules>) {
+        MODULE.with(|m| m.borrow_mut().saved_rules = rules.map(Rc::new));
+    }
+
     pub fn wall_rules() -> WallRules {
         MODULE.with(|m| m.borrow().wall_rules.as_ref().unwrap().clone())
     }
@@ -746,16 +889,20 @@ impl Module {
     getters!(
         ability, abilities, Ability;
         ability_list, ability_lists, AbilityList;
+        achievement, achievements, Achievement;
         actor, actors, Actor;
         ai_template, ai_templates, AITemplate;
         area, areas, Area;
+        banter, banters, Banter;
         class, classes, Class;
         conversation, conversations, Conversation;
         cutscene, cutscenes, Cutscene;
         encounter, encounters, Encounter;
         item, items, Item;
         item_adjective, item_adjectives, ItemAdjective;
+        item_set, item_sets, ItemSet;
         loot_list, loot_lists, LootList;
+        lore_entry, lore_entries, LoreEntry;
         object_size, sizes, ObjectSize;
         quest, quests, Quest;
         prop, props, Prop;
@@ -784,10 +931,18 @@ impl Module {
         })
     }
 
+    pub fn all_achievements() -> Vec<Rc<Achievement>> {
+        MODULE.with(|r| all_resources(&r.borrow().achievements))
+    }
+
     pub fn all_actors() -> Vec<Rc<Actor>> {
         MODULE.with(|r| all_resources(&r.borrow().actors))
     }
 
+    pub fn all_ai_templates() -> Vec<Rc<AITemplate>> {
+        MODULE.with(|r| all_resources(&r.borrow().ai_templates))
+    }
+
     pub fn all_object_sizes() -> Vec<Rc<ObjectSize>> {
         MODULE.with(|r| {
             r.borrow()
@@ -798,6 +953,10 @@ impl Module {
         })
     }
 
+    pub fn all_banters() -> Vec<Rc<Banter>> {
+        MODULE.with(|r| all_resources(&r.borrow().banters))
+    }
+
     pub fn all_classes() -> Vec<Rc<Class>> {
         MODULE.with(|r| all_resources(&r.borrow().classes))
     }
@@ -810,6 +969,18 @@ impl Module {
         MODULE.with(|r| all_resources(&r.borrow().features))
     }
 
+    pub fn all_items() -> Vec<Rc<Item>> {
+        MODULE.with(|r| all_resources(&r.borrow().items))
+    }
+
+    pub fn all_loot_lists() -> Vec<Rc<LootList>> {
+        MODULE.with(|r| all_resources(&r.borrow().loot_lists))
+    }
+
+    pub fn all_lore_entries() -> Vec<Rc<LoreEntry>> {
+        MODULE.with(|r| all_resources(&r.borrow().lore_entries))
+    }
+
     pub fn all_props() -> Vec<Rc<Prop>> {
         MODULE.with(|r| all_resources(&r.borrow().props))
     }
@@ -845,7 +1016,11 @@ struct ModuleBuilder {
     tile_builders: HashMap<String, Tileset>,
     generator_builders: HashMap<String, GeneratorBuilder>,
 
+    achievements: HashMap<String, Achievement>,
+    banters: HashMap<String, Banter>,
     item_adjectives: HashMap<String, ItemAdjectiveBuilder>,
+    item_sets: HashMap<String, ItemSet>,
+    lore_entries: HashMap<String, LoreEntry>,
     quests: HashMap<String, Quest>,
 }
 
@@ -855,16 +1030,20 @@ impl ModuleBuilder {
         Ok(ModuleBuilder {
             ability_builders: read_builders(resources, Ability)?,
             ability_list_builders: read_builders(resources, AbilityList)?,
+            achievements: read_builders(resources, Achievement)?,
             actor_builders: read_builders(resources, Actor)?,
             ai_builders: read_builders(resources, AiTemplate)?,
             area_builders: read_builders(resources, Area)?,
+            banters: read_builders(resources, Banter)?,
             class_builders: read_builders(resources, Class)?,
             conversation_builders: read_builders(resources, Conversation)?,
             cutscene_builders: read_builders(resources, Cutscene)?,
             encounter_builders: read_builders(resources, Encounter)?,
             item_builders: read_builders(resources, Item)?,
             item_adjectives: read_builders(resources, ItemAdjective)?,
+            item_sets: read_builders(resources, ItemSet)?,
             loot_builders: read_builders(resources, LootList)?,
+            lore_entries: read_builders(resources, LoreEntry)?,
             prop_builders: read_builders(resources, Prop)?,
             quests: read_builders(resources, Quest)?,
             race_builders: read_builders(resources, Race)?,
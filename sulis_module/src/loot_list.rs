@@ -16,10 +16,11 @@
 
 use std::collections::HashMap;
 use std::io::Error;
+use std::rc::Rc;
 
 use sulis_core::util::{gen_rand, unable_to_create_error};
 
-use crate::{ItemState, Module};
+use crate::{Item, ItemState, Module};
 
 const MAX_DEPTH: u32 = 10;
 
@@ -208,20 +209,27 @@ impl LootList {
         })
     }
 
-    pub fn generate_with_chance(&self, chance: u32) -> Vec<(u32, ItemState)> {
+    pub fn generate_with_chance(&self, chance: u32, level: u32) -> Vec<(u32, ItemState)> {
         let roll = gen_rand(1, 101);
         if chance >= roll {
-            self.generate_internal(0)
+            self.generate_internal(0, level)
         } else {
             Vec::new()
         }
     }
 
     pub fn generate(&self) -> Vec<(u32, ItemState)> {
-        self.generate_internal(0)
+        self.generate_internal(0, 0)
     }
 
-    fn generate_internal(&self, depth: u32) -> Vec<(u32, ItemState)> {
+    /// Generates loot for an area of the specified level.  Higher levels
+    /// improve item quality and coin amounts as specified by the
+    /// `loot_level_scaling` rules.
+    pub fn generate_at_level(&self, level: u32) -> Vec<(u32, ItemState)> {
+        self.generate_internal(0, level)
+    }
+
+    fn generate_internal(&self, depth: u32, level: u32) -> Vec<(u32, ItemState)> {
         if depth >= MAX_DEPTH {
             warn!(
                 "Exceeded maximum sub list depth of {}.  \
@@ -236,7 +244,7 @@ impl LootList {
         let mut items = Vec::new();
         if num_items > 0 {
             for _ in 0..num_items {
-                if let Some(item) = self.gen_item() {
+                if let Some(item) = self.gen_item(level) {
                     items.push(item);
                 }
             }
@@ -245,25 +253,9 @@ impl LootList {
         for entry in self.probability_entries.iter() {
             let roll = gen_rand(0, 100);
             if roll < entry.weight {
-                let quantity = if entry.quantity[0] == entry.quantity[1] {
-                    entry.quantity[0]
-                } else {
-                    gen_rand(entry.quantity[0], entry.quantity[1] + 1)
-                };
-
-                let adjectives = self.gen_adjectives(entry);
-                let item = match Module::create_get_item(&entry.id, &adjectives) {
-                    None => {
-                        warn!(
-                            "Unable to create item '{}' with '{:?}'",
-                            entry.id, adjectives
-                        );
-                        continue;
-                    }
-                    Some(item) => item,
-                };
-                let variant = self.gen_variant(entry);
-                items.push((quantity, ItemState::new(item, variant)));
+                if let Some(item) = self.gen_entry_item(entry, level) {
+                    items.push(item);
+                }
             }
         }
 
@@ -288,7 +280,7 @@ impl LootList {
                 };
 
                 for _ in 0..times {
-                    let subitems = sub_list.generate_internal(depth + 1);
+                    let subitems = sub_list.generate_internal(depth + 1, level);
                     for (quantity, item) in subitems {
                         items.push((quantity, item));
                     }
@@ -345,38 +337,69 @@ impl LootList {
         None
     }
 
-    fn gen_item(&self) -> Option<(u32, ItemState)> {
+    fn gen_item(&self, level: u32) -> Option<(u32, ItemState)> {
         let roll = gen_rand(0, self.total_entries_weight);
 
         let mut cur_weight = 0;
         for entry in self.weighted_entries.iter() {
             cur_weight += entry.weight;
             if roll < cur_weight {
-                let quantity = if entry.quantity[0] == entry.quantity[1] {
-                    entry.quantity[0]
-                } else {
-                    gen_rand(entry.quantity[0], entry.quantity[1] + 1)
-                };
-
-                let adjectives = self.gen_adjectives(entry);
-                let item = match Module::create_get_item(&entry.id, &adjectives) {
-                    None => {
-                        warn!(
-                            "Unable to create item '{}' with '{:?}'",
-                            entry.id, adjectives
-                        );
-                        continue;
-                    }
-                    Some(item) => item,
-                };
-                let variant = self.gen_variant(entry);
-                return Some((quantity, ItemState::new(item, variant)));
+                match self.gen_entry_item(entry, level) {
+                    None => continue,
+                    Some(item) => return Some(item),
+                }
             }
         }
 
         None
     }
 
+    fn gen_entry_item(&self, entry: &Entry, level: u32) -> Option<(u32, ItemState)> {
+        let rules = Module::rules();
+        let scaling = rules.loot_level_scaling;
+
+        let mut quantity = if entry.quantity[0] == entry.quantity[1] {
+            entry.quantity[0]
+        } else {
+            gen_rand(entry.quantity[0], entry.quantity[1] + 1)
+        };
+
+        if entry.id == rules.coins_item {
+            let mult = 1.0 + level as f32 * scaling.coins_per_level;
+            quantity = (quantity as f32 * mult).round().max(0.0) as u32;
+        }
+
+        let mut item = self.gen_entry_item_type(entry)?;
+
+        // higher level areas get a chance to reroll the adjectives,
+        // keeping the more valuable result
+        let quality_chance = level * scaling.quality_chance_per_level;
+        if quality_chance > 0 && gen_rand(0, 100) < quality_chance {
+            if let Some(reroll) = self.gen_entry_item_type(entry) {
+                if reroll.value > item.value {
+                    item = reroll;
+                }
+            }
+        }
+
+        let variant = self.gen_variant(entry);
+        Some((quantity, ItemState::new(item, variant)))
+    }
+
+    fn gen_entry_item_type(&self, entry: &Entry) -> Option<Rc<Item>> {
+        let adjectives = self.gen_adjectives(entry);
+        match Module::create_get_item(&entry.id, &adjectives) {
+            None => {
+                warn!(
+                    "Unable to create item '{}' with '{:?}'",
+                    entry.id, adjectives
+                );
+                None
+            }
+            Some(item) => Some(item),
+        }
+    }
+
     fn gen_num_items(&self) -> u32 {
         if self.total_generate_weight == 0 {
             return 0;
@@ -0,0 +1,37 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+/// A single entry in the in game records window.  Lore entries are hidden
+/// until unlocked, either via script with `game:unlock_lore(id)` or by
+/// being marked as `initially_unlocked`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LoreEntry {
+    pub id: String,
+    pub name: String,
+
+    #[serde(default = "default_category")]
+    pub category: String,
+
+    pub text: String,
+
+    #[serde(default)]
+    pub initially_unlocked: bool,
+}
+
+fn default_category() -> String {
+    "General".to_string()
+}
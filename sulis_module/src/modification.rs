@@ -19,7 +19,10 @@ use std::io::Error;
 use std::path::PathBuf;
 
 use sulis_core::config::{self, Config};
-use sulis_core::resource::{read_single_resource, subdirs};
+use sulis_core::resource::{
+    module_archive, read_single_resource, subdirs, ArchiveKind, ModuleArchive,
+};
+use sulis_core::util::invalid_data_error;
 
 pub fn get_available_modifications() -> Vec<ModificationInfo> {
     let root_dir = Config::resources_config().mods_directory;
@@ -46,15 +49,69 @@ pub fn get_available_modifications() -> Vec<ModificationInfo> {
         }
     }
 
+    for archive in module_archive::archives_in(&root_dir)
+        .into_iter()
+        .chain(module_archive::archives_in(&user_dir))
+        .flatten()
+    {
+        match ModificationInfo::from_archive(archive.clone()) {
+            Ok(modi) => mods.push(modi),
+            Err(e) => warn!("Error reading mod archive '{:?}': {}", archive, e),
+        }
+    }
+
     mods
 }
 
+/// Checks the specified list of active mods, in load order, for missing or
+/// misordered requirements, conflicts, and mods not marked as compatible with
+/// the specified campaign.  Returns a human readable warning for each problem.
+pub fn compatibility_warnings(mods: &[ModificationInfo], campaign: Option<&str>) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for (index, modif) in mods.iter().enumerate() {
+        for required in modif.requires.iter() {
+            match mods.iter().position(|m| &m.id == required) {
+                None => warnings.push(format!(
+                    "{} requires the mod '{}', which is not active.",
+                    modif.name, required
+                )),
+                Some(req_index) if req_index > index => warnings.push(format!(
+                    "{} must be loaded after {}.",
+                    modif.name, mods[req_index].name
+                )),
+                Some(_) => (),
+            }
+        }
+
+        for other in mods[index + 1..].iter() {
+            if modif.conflicts.contains(&other.id) || other.conflicts.contains(&modif.id) {
+                warnings.push(format!("{} conflicts with {}.", modif.name, other.name));
+            }
+        }
+
+        if let Some(campaign) = campaign {
+            if !modif.is_compatible_with(campaign) {
+                warnings.push(format!(
+                    "{} is not marked as compatible with this campaign.",
+                    modif.name
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
 #[derive(Debug, Clone)]
 pub struct ModificationInfo {
     pub id: String,
     pub name: String,
     pub description: String,
     pub dir: String,
+    pub requires: Vec<String>,
+    pub conflicts: Vec<String>,
+    pub campaigns: Vec<String>,
 }
 
 impl Display for ModificationInfo {
@@ -68,12 +125,44 @@ impl ModificationInfo {
         let path_str = path.to_string_lossy().to_string();
         let builder: ModificationInfoBuilder = read_single_resource(&format!("{path_str}/mod"))?;
 
-        Ok(ModificationInfo {
+        Ok(ModificationInfo::new(builder, path_str))
+    }
+
+    /// Reads the mod info from a module archive, without extracting it
+    pub fn from_archive(path: PathBuf) -> Result<ModificationInfo, Error> {
+        let path_str = path.to_string_lossy().to_string();
+        let archive = ModuleArchive::open(&path)?;
+        let manifest = archive.manifest()?;
+        if manifest.kind != ArchiveKind::Modification {
+            return invalid_data_error("Archive does not contain a mod");
+        }
+
+        let builder: ModificationInfoBuilder = archive.read_yaml("mod.yml")?;
+
+        Ok(ModificationInfo::new(builder, path_str))
+    }
+
+    fn new(builder: ModificationInfoBuilder, dir: String) -> ModificationInfo {
+        ModificationInfo {
             name: builder.name,
             description: builder.description,
             id: builder.id,
-            dir: path_str,
-        })
+            dir,
+            requires: builder.requires,
+            conflicts: builder.conflicts,
+            campaigns: builder.campaigns,
+        }
+    }
+
+    /// Returns true if this mod was loaded from a module archive rather than
+    /// a directory
+    pub fn is_archive(&self) -> bool {
+        module_archive::is_archive(&self.dir)
+    }
+
+    /// Mods that do not list any campaigns are assumed to work with all of them
+    pub fn is_compatible_with(&self, campaign_id: &str) -> bool {
+        self.campaigns.is_empty() || self.campaigns.iter().any(|id| id == campaign_id)
     }
 }
 
@@ -83,4 +172,13 @@ pub struct ModificationInfoBuilder {
     pub id: String,
     pub name: String,
     pub description: String,
+
+    #[serde(default)]
+    pub requires: Vec<String>,
+
+    #[serde(default)]
+    pub conflicts: Vec<String>,
+
+    #[serde(default)]
+    pub campaigns: Vec<String>,
 }
@@ -16,6 +16,8 @@
 
 use std::collections::HashMap;
 
+use sulis_core::io::InputActionKind;
+
 use crate::rules::Time;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -83,6 +85,13 @@ pub struct NumFlagData {
     pub val: f32,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ApprovalData {
+    pub companion: String,
+    pub amount: i32,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum QuestEntryState {
     Hidden,
@@ -114,6 +123,79 @@ pub struct QuestStateData {
     pub state: QuestEntryState,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ScrollViewData {
+    pub x: i32,
+    pub y: i32,
+    pub millis: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ShakeViewData {
+    pub intensity: f32,
+    pub millis: u32,
+}
+
+/// How the player moves on from a tutorial step
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub enum TutorialContinue {
+    /// Clicking the continue button shown with the step text
+    Button,
+
+    /// Performing the step's allowed action
+    Action,
+
+    /// Clicking within the highlighted widget or region
+    Click,
+
+    /// The step remains until a script shows another step or ends the tutorial
+    Script,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TutorialStepData {
+    pub text: String,
+
+    /// The theme ID of the widget to highlight, such as `inventory_button`.
+    /// The end of the full theme ID of the widget must match
+    #[serde(default)]
+    pub highlight_widget: Option<String>,
+
+    /// The screen region to highlight, as `[x, y, width, height]` in UI
+    /// coordinates, if no widget is highlighted
+    #[serde(default)]
+    pub highlight_region: Option<[i32; 4]>,
+
+    /// The only key action the player may perform during this step.  Mouse
+    /// input is only allowed within the highlight
+    #[serde(default)]
+    pub allowed_action: Option<InputActionKind>,
+
+    pub continue_on: TutorialContinue,
+
+    /// Called when the player moves on from this step
+    #[serde(default)]
+    pub on_continue: Option<ScriptData>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EpilogueSlide {
+    /// The image shown above the text, if any
+    #[serde(default)]
+    pub image: Option<String>,
+    pub text: String,
+
+    /// Conditions, such as `player_flag`, which must all match when the
+    /// epilogue is shown for this slide to be included
+    #[serde(default)]
+    pub conditions: Vec<OnTrigger>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct ModuleLoadData {
@@ -147,12 +229,21 @@ pub enum OnTrigger {
     GameOverWindow(String),
     ExitToMenu,
     ScrollView(i32, i32),
+    ScrollViewTimed(ScrollViewData),
+    LockView, // lock the view onto the target
+    UnlockView,
     ScreenShake,
+    ShakeView(ShakeViewData),
     LoadModule(ModuleLoadData),
     ShowConfirm(DialogData),
     ShowMenu(MenuData),
     QuestState(QuestStateData),
     NotQuestState(QuestStateData),
+    Approval(ApprovalData), // adds approval, or as a condition requires at least the amount
+    NotApproval(ApprovalData),
     FadeOutIn,
     CheckEndTurn,
+    TutorialStep(TutorialStepData),
+    EndTutorial,
+    ShowEpilogue(Vec<EpilogueSlide>),
 }
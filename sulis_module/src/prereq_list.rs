@@ -136,15 +136,22 @@ impl PrereqList {
     }
 
     pub fn meets(&self, actor: &Rc<Actor>) -> bool {
+        self.unmet_reason(actor).is_none()
+    }
+
+    /// Returns a description of the first prereq in this list that the
+    /// actor does not meet, or None if all prereqs are met
+    pub fn unmet_reason(&self, actor: &Actor) -> Option<String> {
         if let Some(ref attrs) = self.attributes {
             for &(attr, amount) in attrs.iter() {
                 if actor.attributes.get(attr) < amount {
-                    return false;
+                    return Some(format!("Requires {} {}", attr.name(), amount));
                 }
             }
         }
 
         let mut class_level_met = self.levels.is_empty();
+        let mut class_names = Vec::new();
         for &(ref class_id, level) in self.levels.iter() {
             let class = match Module::class(class_id) {
                 None => {
@@ -157,31 +164,35 @@ impl PrereqList {
                 class_level_met = true;
                 break;
             }
+            class_names.push(format!("{} level {}", class.name, level));
         }
 
         if !class_level_met {
-            return false;
+            return Some(format!("Requires {}", class_names.join(" or ")));
         }
 
         if let Some(total_level) = self.total_level {
             if actor.total_level < total_level {
-                return false;
+                return Some(format!("Requires level {total_level}"));
             }
         }
 
         if let Some(ref race) = self.race {
-            if &actor.race.name != race {
-                return false;
+            if &actor.race.id != race {
+                let name = Module::race(race).map_or(race.to_string(), |r| r.name.to_string());
+                return Some(format!("Requires race {name}"));
             }
         }
 
         for ability_id in self.abilities.iter() {
             if !actor.has_ability_with_id(ability_id) {
-                return false;
+                let name = Module::ability(ability_id)
+                    .map_or(ability_id.to_string(), |a| a.name.to_string());
+                return Some(format!("Requires ability {name}"));
             }
         }
 
-        true
+        None
     }
 }
 
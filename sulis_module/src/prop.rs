@@ -42,6 +42,41 @@ pub enum Interactive {
     Hover,
 }
 
+/// A named visual state of a prop, such as closed, open or broken
+#[derive(Debug)]
+pub struct PropVisualState {
+    pub id: String,
+    pub image: Rc<dyn Image>,
+    pub transition: Option<Rc<dyn Image>>,
+    pub transition_millis: u32,
+    pub impass: Vec<Point>,
+    pub invis: Vec<Point>,
+}
+
+/// Allows a prop, such as a crate or a section of wall, to be attacked
+/// and destroyed
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Destructible {
+    /// The damage the prop can take before being destroyed
+    pub hp: u32,
+
+    /// Subtracted from the damage of each hit on the prop
+    #[serde(default)]
+    pub armor: u32,
+
+    /// The ID of a prop, such as rubble, placed at the same location once
+    /// this prop is destroyed
+    #[serde(default)]
+    pub debris: Option<String>,
+
+    /// The ID of a module script.  Its `on_prop_destroyed` function is called
+    /// with the destroying entity and the debris prop, if any, when this prop
+    /// is destroyed
+    #[serde(default)]
+    pub script: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct Prop {
     pub id: String,
@@ -55,6 +90,9 @@ pub struct Prop {
     pub interactive: Interactive,
     pub aerial: bool,
     pub status_text: Option<String>,
+    pub states: Vec<PropVisualState>,
+    pub initial_state: Option<usize>,
+    pub destructible: Option<Destructible>,
 }
 
 impl Prop {
@@ -125,6 +163,61 @@ impl Prop {
             }
         }
 
+        let mut states = Vec::new();
+        for state in builder.states {
+            let image = match ResourceSet::image(&state.image) {
+                None => {
+                    warn!("No image found for state '{}': '{}'", state.id, state.image);
+                    return unable_to_create_error("prop", &builder.id);
+                }
+                Some(image) => image,
+            };
+
+            let transition = match state.transition {
+                None => None,
+                Some(ref id) => match ResourceSet::image(id) {
+                    None => {
+                        warn!("No image found for state '{}' transition: '{}'", state.id, id);
+                        return unable_to_create_error("prop", &builder.id);
+                    }
+                    Some(image) => Some(image),
+                },
+            };
+
+            let (width, height) = (size.width as usize, size.height as usize);
+            let mut impass = Vec::new();
+            for p in state.impass {
+                let (x, y) = verify_point("impass", width, height, p)?;
+                impass.push(Point::new(x, y));
+            }
+
+            let mut invis = Vec::new();
+            for p in state.invis {
+                let (x, y) = verify_point("invis", width, height, p)?;
+                invis.push(Point::new(x, y));
+            }
+
+            states.push(PropVisualState {
+                id: state.id,
+                image,
+                transition,
+                transition_millis: state.transition_millis,
+                impass,
+                invis,
+            });
+        }
+
+        let initial_state = match builder.initial_state {
+            None => None,
+            Some(ref id) => match states.iter().position(|state| &state.id == id) {
+                None => {
+                    warn!("Initial state '{}' is not one of the prop's states", id);
+                    return unable_to_create_error("prop", &builder.id);
+                }
+                Some(index) => Some(index),
+            },
+        };
+
         let interactive = match builder.interactive {
             InteractiveBuilder::Not => Interactive::Not,
             InteractiveBuilder::Hover => Interactive::Hover,
@@ -168,15 +261,44 @@ impl Prop {
             interactive,
             aerial: builder.aerial,
             status_text: builder.status_text,
+            states,
+            initial_state,
+            destructible: builder.destructible,
         })
     }
 
+    /// Returns the index of the visual state with the given ID, if any
+    pub fn state_index(&self, id: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.id == id)
+    }
+
+    /// Returns the image for the visual state at `index`, or the base image
+    /// if there is no such state
+    pub fn state_image(&self, index: Option<usize>) -> &Rc<dyn Image> {
+        match index.and_then(|index| self.states.get(index)) {
+            None => &self.image,
+            Some(state) => &state.image,
+        }
+    }
+
     pub fn append_to_draw_list(
         &self,
         draw_list: &mut DrawList,
         state: &AnimationState,
         offset: Offset,
         millis: u32,
+    ) {
+        let image = self.state_image(self.initial_state);
+        self.append_image_to_draw_list(image, draw_list, state, offset, millis);
+    }
+
+    pub fn append_image_to_draw_list(
+        &self,
+        image: &Rc<dyn Image>,
+        draw_list: &mut DrawList,
+        state: &AnimationState,
+        offset: Offset,
+        millis: u32,
     ) {
         let rect = Rect {
             x: offset.x,
@@ -185,8 +307,7 @@ impl Prop {
             h: self.size.height as f32,
         };
 
-        self.image
-            .append_to_draw_list(draw_list, state, rect, millis);
+        image.append_to_draw_list(draw_list, state, rect, millis);
     }
 }
 
@@ -229,4 +350,30 @@ pub struct PropBuilder {
     pub aerial: bool,
     pub interactive: InteractiveBuilder,
     pub status_text: Option<String>,
+    #[serde(default)]
+    pub states: Vec<PropVisualStateBuilder>,
+    pub initial_state: Option<String>,
+    #[serde(default)]
+    pub destructible: Option<Destructible>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PropVisualStateBuilder {
+    pub id: String,
+    pub image: String,
+
+    /// Image played once when switching into this state, before `image`
+    pub transition: Option<String>,
+
+    #[serde(default)]
+    pub transition_millis: u32,
+
+    /// Impassable points while in this state, in addition to the prop's own
+    #[serde(default)]
+    pub impass: Vec<Vec<usize>>,
+
+    /// Points blocking visibility while in this state, in addition to the prop's own
+    #[serde(default)]
+    pub invis: Vec<Vec<usize>>,
 }
@@ -27,7 +27,38 @@ use sulis_core::util::{gen_rand, unable_to_create_error, Point};
 
 use crate::actor::Sex;
 
-use crate::{ImageLayer, ImageLayerSet, Module, ObjectSize, Prop};
+use crate::{Appearance, ImageLayer, ImageLayerSet, Module, ObjectSize, Prop};
+
+/// An additional image layer defined by a race, such as horns or a tail,
+/// with the images that may be selected for it in character creation
+#[derive(Debug)]
+pub struct CustomLayer {
+    pub id: String,
+    pub name: String,
+
+    /// The layer this is drawn directly above, sharing its offset
+    pub above: ImageLayer,
+
+    /// The ID of the color channel used to color this layer
+    pub color: Option<String>,
+
+    /// Whether no image may be selected for this layer
+    pub optional: bool,
+    pub selections: Vec<String>,
+}
+
+/// A set of colors defined by a race, selected in character creation and
+/// applied to custom layers and to any of the built in layers
+#[derive(Debug)]
+pub struct ColorChannel {
+    pub id: String,
+    pub name: String,
+    pub colors: Vec<Color>,
+
+    /// Built in layers colored by this channel instead of the hair or
+    /// skin color
+    pub layers: Vec<ImageLayer>,
+}
 
 #[derive(Debug)]
 pub struct Race {
@@ -48,7 +79,13 @@ pub struct Race {
     pub disabled_slots: Vec<Slot>,
     pub hair_colors: Vec<Color>,
     pub skin_colors: Vec<Color>,
+    pub custom_layers: Vec<CustomLayer>,
+    pub color_channels: Vec<ColorChannel>,
     pub ticker_offset: (f32, f32),
+
+    /// The square region of the entity image, as x, y, and size relative
+    /// to the entity position, shown in portraits generated from the image
+    pub portrait_region: (f32, f32, f32),
     default_images: ImageLayerSet,
     image_layer_offsets: HashMap<ImageLayer, (f32, f32)>,
     image_layer_postfix: HashMap<Sex, String>,
@@ -84,6 +121,19 @@ impl Race {
             offsets.insert(layer, (p.x as f32 / scale, p.y as f32 / scale));
         }
 
+        // by default, show a square the width of the race from the top of the head
+        let portrait_region = match builder.portrait_region {
+            Some(region) => (
+                region.position.x as f32 / scale,
+                region.position.y as f32 / scale,
+                region.size as f32 / scale,
+            ),
+            None => {
+                let head_y = offsets.get(&ImageLayer::Head).map_or(0.0, |(_, y)| *y);
+                (0.0, head_y, size.width as f32)
+            }
+        };
+
         let default_images = if !builder.default_images.is_empty() {
             let default_images = builder.default_images.clone();
             let images = Sex::iter().map(|s| (*s, default_images.clone())).collect();
@@ -110,6 +160,55 @@ impl Race {
             skin_colors.push(Color::from_string(color_str));
         }
 
+        let mut color_channels: Vec<ColorChannel> = Vec::new();
+        for channel in builder.color_channels {
+            if color_channels.iter().any(|c| c.id == channel.id) {
+                warn!("Duplicate color channel '{}'", channel.id);
+                return unable_to_create_error("race", &builder.id);
+            }
+
+            color_channels.push(ColorChannel {
+                id: channel.id,
+                name: channel.name,
+                colors: channel.colors.iter().map(|c| Color::from_string(c)).collect(),
+                layers: channel.layers,
+            });
+        }
+
+        let mut custom_layers: Vec<CustomLayer> = Vec::new();
+        for layer in builder.custom_layers {
+            if custom_layers.iter().any(|l| l.id == layer.id) {
+                warn!("Duplicate custom layer '{}'", layer.id);
+                return unable_to_create_error("race", &builder.id);
+            }
+
+            if let Some(color) = &layer.color {
+                if !color_channels.iter().any(|c| &c.id == color) {
+                    warn!("No color channel '{}' for custom layer '{}'", color, layer.id);
+                    return unable_to_create_error("race", &builder.id);
+                }
+            }
+
+            if let Some(id) = layer.selections.iter().find(|id| ResourceSet::image(id).is_none()) {
+                warn!("No image found with id '{}' for custom layer '{}'", id, layer.id);
+                return unable_to_create_error("race", &builder.id);
+            }
+
+            if layer.selections.is_empty() && !layer.optional {
+                warn!("Custom layer '{}' must be optional or have selections", layer.id);
+                return unable_to_create_error("race", &builder.id);
+            }
+
+            custom_layers.push(CustomLayer {
+                id: layer.id,
+                name: layer.name,
+                above: layer.above,
+                color: layer.color,
+                optional: layer.optional,
+                selections: layer.selections,
+            });
+        }
+
         let mut editor_creator_images = Vec::new();
         for (layer, vec) in builder.editor_creator_images {
             let mut images = Vec::new();
@@ -156,7 +255,10 @@ impl Race {
             female_random_names: builder.female_random_names,
             hair_colors,
             skin_colors,
+            custom_layers,
+            color_channels,
             ticker_offset: builder.ticker_offset,
+            portrait_region,
             editor_creator_images,
             pc_death_prop,
         })
@@ -180,6 +282,59 @@ impl Race {
         &names[index]
     }
 
+    /// Computes the appearance for a member of this race with the specified
+    /// colors and custom layer images.  Channels and layers without a
+    /// selection use their first entry, unless optional
+    pub fn appearance(
+        &self,
+        hair: Option<Color>,
+        skin: Option<Color>,
+        images: &HashMap<String, String>,
+        colors: &HashMap<String, Color>,
+    ) -> Appearance {
+        let mut appearance = Appearance::new(hair, skin);
+
+        let channel_color = |id: &str| -> Option<Color> {
+            let channel = self.color_channels.iter().find(|c| c.id == id)?;
+            colors.get(id).or_else(|| channel.colors.first()).copied()
+        };
+
+        for channel in self.color_channels.iter() {
+            let color = match channel_color(&channel.id) {
+                None => continue,
+                Some(color) => color,
+            };
+
+            for layer in channel.layers.iter() {
+                appearance.layer_colors.insert(*layer, color);
+            }
+        }
+
+        for layer in self.custom_layers.iter() {
+            let image_id = match images.get(&layer.id) {
+                Some(id) => id,
+                None if layer.optional => continue,
+                None => match layer.selections.first() {
+                    None => continue,
+                    Some(id) => id,
+                },
+            };
+
+            let image = match ResourceSet::image(image_id) {
+                None => {
+                    warn!("No image found with id '{}' for layer '{}'", image_id, layer.id);
+                    continue;
+                }
+                Some(image) => image,
+            };
+
+            let color = layer.color.as_ref().and_then(|id| channel_color(id));
+            appearance.custom.push((layer.above, image, color));
+        }
+
+        appearance
+    }
+
     pub fn has_editor_creator_images(&self) -> bool {
         !self.editor_creator_images.is_empty()
     }
@@ -262,9 +417,55 @@ pub struct RaceBuilder {
     #[serde(default)]
     image_layer_postfix: HashMap<Sex, String>,
 
+    #[serde(default)]
+    portrait_region: Option<PortraitRegion>,
+
     #[serde(default)]
     editor_creator_images: HashMap<ImageLayer, Vec<String>>,
 
     #[serde(default)]
     disabled_slots: Vec<Slot>,
+
+    #[serde(default)]
+    custom_layers: Vec<CustomLayerBuilder>,
+
+    #[serde(default)]
+    color_channels: Vec<ColorChannelBuilder>,
+}
+
+/// The region of the entity image used for generated portraits, in the same
+/// units as the image layer offsets
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PortraitRegion {
+    pub position: Point,
+    pub size: i32,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CustomLayerBuilder {
+    pub id: String,
+    pub name: String,
+    pub above: ImageLayer,
+
+    #[serde(default)]
+    pub color: Option<String>,
+
+    #[serde(default)]
+    pub optional: bool,
+
+    #[serde(default)]
+    pub selections: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ColorChannelBuilder {
+    pub id: String,
+    pub name: String,
+    pub colors: Vec<String>,
+
+    #[serde(default)]
+    pub layers: Vec<ImageLayer>,
 }
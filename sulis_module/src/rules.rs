@@ -48,14 +48,18 @@ pub use self::damage::DamageList;
 pub mod resistance;
 pub use self::resistance::Resistance;
 
+pub mod stat_formula;
+pub use self::stat_formula::{FormulaVars, StatFormula};
+
 pub mod stat_list;
-pub use self::stat_list::StatList;
+pub use self::stat_list::{AttackRoll, StatList};
 
-use crate::area::LocationKind;
+use crate::area::{LocationKind, TraversalKind};
+use sulis_core::serde_json;
 use sulis_core::ui::{color, Color};
-use sulis_core::util::{gen_rand, invalid_data_error};
+use sulis_core::util::{gen_rand, hex, invalid_data_error};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Rules {
     pub id: String,
@@ -117,10 +121,444 @@ pub struct Rules {
 
     pub hints: Vec<String>,
 
+    /// The number of kills of a given creature needed to reveal each successive
+    /// tier of information about it in the records window.
+    pub bestiary_reveal_kills: Vec<u32>,
+
+    /// The minimum number of rounds between companion banters
+    #[serde(default)]
+    pub banter_cooldown: u32,
+
+    /// Dexterity caps and movement penalties applied for each equipped
+    /// piece of armor of the given kind.
+    #[serde(default)]
+    pub armor_categories: HashMap<ArmorKind, ArmorCategory>,
+
+    /// How generated loot improves with the level of the area it is
+    /// generated in.
+    #[serde(default)]
+    pub loot_level_scaling: LootLevelScaling,
+
+    /// Weather that scripts may transition to, keyed by ID
+    #[serde(default)]
+    pub weather: HashMap<String, Weather>,
+
+    /// Bounties for witnessed crimes and the guard response to them
+    #[serde(default)]
+    pub crime: CrimeRules,
+
+    /// Which creatures may cross swimmable water and what it costs them
+    #[serde(default)]
+    pub swimming: SwimRules,
+
+    /// Extra path cost for each square covered by a hazard surface, such as
+    /// fire or a trap, for creatures that avoid hazards
+    #[serde(default)]
+    pub hazard_path_cost: i32,
+
+    /// The AP used to climb or jump across area traversal points
+    #[serde(default)]
+    pub traversal_ap: TraversalRules,
+
+    /// When to confirm leaving an area, and whether the party may flee combat
+    #[serde(default)]
+    pub transitions: TransitionRules,
+
+    /// Rations eaten while traveling and resting, and the effect of running out
+    #[serde(default)]
+    pub supplies: SupplyRules,
+
+    /// The AI template used for party members without their own AI during
+    /// assisted combat
+    #[serde(default)]
+    pub party_ai: Option<String>,
+
+    /// Which combats the party may resolve without fighting them out, and
+    /// what doing so costs
+    #[serde(default)]
+    pub auto_resolve: AutoResolveRules,
+
+    /// Costs of the healing and retraining services NPCs may offer the party
+    #[serde(default)]
+    pub services: ServiceRules,
+
+    /// Whether and how equipped items wear with use, and how they are repaired
+    #[serde(default)]
+    pub durability: DurabilityRules,
+
+    /// The attribute check allowing the party to haggle with merchants
+    #[serde(default)]
+    pub haggling: HaggleRules,
+
+    /// How attributes contribute to derived stats such as accuracy and defense
+    #[serde(default)]
+    pub derived_stats: DerivedStatRules,
+
+    /// Whether areas are laid out as square tiles or as hexes
+    #[serde(default)]
+    pub grid: GridKind,
+
     pub main_menu_music: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub enum GridKind {
+    /// Each tile is adjacent to the four tiles sharing an edge with it
+    #[default]
+    Square,
+
+    /// Each tile is a hex adjacent to six others, with odd columns shifted
+    /// down by half a tile.  See `sulis_core::util::hex`
+    Hex,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ArmorCategory {
+    /// The maximum dexterity bonus that applies to defense and reflex
+    #[serde(default)]
+    pub max_dex_bonus: Option<i32>,
+
+    /// Extra AP used per square moved
+    #[serde(default)]
+    pub movement_ap: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+pub struct Weather {
+    /// Multiplied with the ambient light of outdoor areas at full intensity
+    pub light: Color,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub struct LootLevelScaling {
+    /// The fractional increase in generated coins per area level
+    #[serde(default)]
+    pub coins_per_level: f32,
+
+    /// The percentage chance per area level for a generated item to
+    /// reroll its adjectives, keeping the more valuable result
+    #[serde(default)]
+    pub quality_chance_per_level: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SwimRules {
+    /// The attribute checked to determine whether a creature can swim.  If not
+    /// set, any creature may swim
+    #[serde(default)]
+    pub attribute: Option<Attribute>,
+
+    /// Creatures with less than this value of the attribute cannot enter water
+    #[serde(default)]
+    pub min_attribute: u8,
+
+    /// Extra AP used per square swum
+    #[serde(default)]
+    pub movement_ap: u32,
+
+    /// Extra path finding cost per square of water, so creatures prefer
+    /// to walk around water when a reasonable path exists
+    #[serde(default)]
+    pub path_cost: i32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TransitionRules {
+    /// Party members further than this from a transition are listed in a
+    /// confirmation before the party leaves.  If not set, no confirmation is shown
+    #[serde(default)]
+    pub split_party_distance: Option<f32>,
+
+    /// During combat, the party may only use a transition if no active hostile
+    /// is within this distance of any party member.  If not set, transitions
+    /// cannot be used in combat
+    #[serde(default)]
+    pub flee_hostile_distance: Option<f32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TraversalRules {
+    #[serde(default)]
+    pub climb: u32,
+
+    #[serde(default)]
+    pub jump: u32,
+}
+
+impl TraversalRules {
+    pub fn ap(&self, kind: TraversalKind) -> u32 {
+        match kind {
+            TraversalKind::Climb => self.climb,
+            TraversalKind::Jump => self.jump,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SupplyRules {
+    /// The item eaten as rations.  If not set, supplies are not used
+    #[serde(default)]
+    pub ration_item: Option<String>,
+
+    /// Each party member eats one ration per this many hours of world map
+    /// travel, rounded up.  If zero, travel does not use rations
+    #[serde(default)]
+    pub travel_hours_per_ration: u32,
+
+    /// The number of rations each party member eats when the party rests
+    #[serde(default)]
+    pub rations_per_rest: u32,
+
+    /// The name of the effect applied to party members that go without rations
+    #[serde(default = "starvation_name")]
+    pub starvation_name: String,
+
+    #[serde(default)]
+    pub starvation_icon: Option<String>,
+
+    /// Bonuses applied to starving party members until they next eat
+    #[serde(default)]
+    pub starvation_bonuses: BonusList,
+}
+
+fn starvation_name() -> String {
+    "Starving".to_string()
+}
+
+impl Default for SupplyRules {
+    fn default() -> SupplyRules {
+        SupplyRules {
+            ration_item: None,
+            travel_hours_per_ration: 0,
+            rations_per_rest: 0,
+            starvation_name: starvation_name(),
+            starvation_icon: None,
+            starvation_bonuses: BonusList::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AutoResolveRules {
+    /// Combat may be auto-resolved when the total level of the active hostiles
+    /// is at most this fraction of the party's total level.  If not set,
+    /// combat cannot be auto-resolved
+    #[serde(default)]
+    pub max_difficulty: Option<f32>,
+
+    /// The fraction of their maximum hit points each party member loses
+    /// when resolving a combat at the maximum difficulty.  Easier combats
+    /// cost proportionally less
+    #[serde(default)]
+    pub hp_cost: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceRules {
+    /// The coins charged for each hit point restored by healing
+    #[serde(default)]
+    pub healing_cost_per_hp: u32,
+
+    /// Effects with this tag are injuries, which may be paid to be removed
+    #[serde(default = "injury_tag")]
+    pub injury_tag: String,
+
+    /// The coins charged for each injury removed
+    #[serde(default)]
+    pub injury_cost: u32,
+
+    /// The coins charged to retrain a party member
+    #[serde(default)]
+    pub retrain_base_cost: u32,
+
+    /// Additional coins charged to retrain for each level above the first
+    #[serde(default)]
+    pub retrain_cost_per_level: u32,
+
+    /// The coins charged to repair each condition an equipped item has worn
+    #[serde(default)]
+    pub repair_cost: u32,
+}
+
+fn injury_tag() -> String {
+    "injury".to_string()
+}
+
+impl Default for ServiceRules {
+    fn default() -> ServiceRules {
+        ServiceRules {
+            healing_cost_per_hp: 0,
+            injury_tag: injury_tag(),
+            injury_cost: 0,
+            retrain_base_cost: 0,
+            retrain_cost_per_level: 0,
+            repair_cost: 0,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DurabilityRules {
+    /// If not set, items never wear and the rest of these rules are ignored
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Item adjective IDs for each condition of a worn item, from least to
+    /// most worn.  The adjectives should scale down the item's bonuses
+    #[serde(default)]
+    pub conditions: Vec<String>,
+
+    /// The percentage chance for a party member's weapon to wear on each attack
+    #[serde(default)]
+    pub attack_wear_chance: u32,
+
+    /// The additional percentage chance for the weapon to wear when the attack misses
+    #[serde(default)]
+    pub miss_wear_chance: u32,
+
+    /// The percentage chance for one of a party member's armor pieces to wear
+    /// each time they are hit by an attack
+    #[serde(default)]
+    pub hit_wear_chance: u32,
+
+    /// While any party member has at least `repair_min_attribute` of this
+    /// attribute, resting repairs one condition of each equipped item
+    #[serde(default)]
+    pub repair_attribute: Option<Attribute>,
+
+    #[serde(default)]
+    pub repair_min_attribute: u8,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub struct HaggleRules {
+    /// The attribute added to a d20 roll when haggling.  If not set, the
+    /// party may not haggle
+    #[serde(default)]
+    pub attribute: Option<Attribute>,
+
+    /// The total of the roll and attribute needed to improve prices at all
+    #[serde(default)]
+    pub difficulty: i32,
+
+    /// The fraction prices change by for each point the check is made or
+    /// missed by
+    #[serde(default)]
+    pub price_per_point: f32,
+
+    /// The largest fraction buy prices may drop, and sell prices rise, by
+    #[serde(default)]
+    pub max_discount: f32,
+
+    /// The largest fraction buy prices may rise, and sell prices drop, by
+    /// after a failed check
+    #[serde(default)]
+    pub max_markup: f32,
+}
+
+/// Formulas for the part of each derived stat computed from an actor's
+/// attributes.  Base values from the rules and bonuses are added separately.
+/// Any formula not specified uses the default
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct DerivedStatRules {
+    pub initiative: StatFormula,
+    pub melee_accuracy: StatFormula,
+    pub ranged_accuracy: StatFormula,
+    pub spell_accuracy: StatFormula,
+    pub defense: StatFormula,
+    pub fortitude: StatFormula,
+    pub reflex: StatFormula,
+    pub will: StatFormula,
+    pub max_hp: StatFormula,
+    pub graze_multiplier: StatFormula,
+    pub hit_multiplier: StatFormula,
+    pub crit_multiplier: StatFormula,
+}
+
+impl DerivedStatRules {
+    fn integer_formulas(&self) -> [(&str, &StatFormula); 9] {
+        [
+            ("initiative", &self.initiative),
+            ("melee_accuracy", &self.melee_accuracy),
+            ("ranged_accuracy", &self.ranged_accuracy),
+            ("spell_accuracy", &self.spell_accuracy),
+            ("defense", &self.defense),
+            ("fortitude", &self.fortitude),
+            ("reflex", &self.reflex),
+            ("will", &self.will),
+            ("max_hp", &self.max_hp),
+        ]
+    }
+}
+
+impl Default for DerivedStatRules {
+    fn default() -> DerivedStatRules {
+        let formula = |source| StatFormula::parse(source).unwrap();
+        DerivedStatRules {
+            initiative: formula("dex / 2 + per / 2"),
+            melee_accuracy: formula("per + str * 2"),
+            ranged_accuracy: formula("per + dex * 2"),
+            spell_accuracy: formula("wis + int * 2"),
+            defense: formula("armored_dex * 2"),
+            fortitude: formula("end * 2"),
+            reflex: formula("armored_dex * 2"),
+            will: formula("wis * 2"),
+            max_hp: formula("level * end / 3"),
+            graze_multiplier: formula("0.02 * (melee * str + ranged * dex)"),
+            hit_multiplier: formula("0.03 * (melee * str + ranged * dex)"),
+            crit_multiplier: formula("0.06 * (melee * str + ranged * dex)"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CrimeRules {
+    /// The bounty added each time the party is seen stealing
+    #[serde(default)]
+    pub theft_bounty: u32,
+
+    /// Once the bounty reaches this amount, guards will attempt an arrest
+    #[serde(default)]
+    pub arrest_bounty: u32,
+
+    /// Actor IDs of the guards that respond to the party's bounty
+    #[serde(default)]
+    pub guards: Vec<String>,
+
+    /// The conversation a guard starts with the party when arresting them.
+    /// No arrests are made if this is not set
+    #[serde(default)]
+    pub arrest_conversation: Option<String>,
+}
+
 impl Rules {
+    pub fn is_hex_grid(&self) -> bool {
+        self.grid == GridKind::Hex
+    }
+
+    /// The vertical offset, in tiles, at which objects in column `x` are drawn
+    pub fn grid_y_offset(&self, x: i32) -> f32 {
+        if self.is_hex_grid() {
+            hex::column_offset(x)
+        } else {
+            0.0
+        }
+    }
+
     pub fn play_main_menu_music(&self) {
         if let Some(music) = self.main_menu_music.as_ref() {
             sulis_core::io::Audio::play_music(music, 1.0);
@@ -139,6 +577,19 @@ impl Rules {
         .to_string()
     }
 
+    /// Returns the number of bestiary information tiers that are revealed
+    /// for a creature that has been killed `kills` times.
+    pub fn bestiary_reveal_level(&self, kills: u32) -> usize {
+        self.bestiary_reveal_kills
+            .iter()
+            .filter(|&&needed| kills >= needed)
+            .count()
+    }
+
+    pub fn armor_category(&self, kind: ArmorKind) -> ArmorCategory {
+        self.armor_categories.get(&kind).copied().unwrap_or_default()
+    }
+
     pub fn to_display_ap(&self, ap: i32) -> i32 {
         ap / self.display_ap as i32
     }
@@ -192,9 +643,26 @@ impl Rules {
             }
         }
 
+        for (name, formula) in self.derived_stats.integer_formulas() {
+            if !formula.is_integral() {
+                return invalid_data_error(&format!(
+                    "Derived stat formula for '{name}' must only use whole numbers: '{formula}'"
+                ));
+            }
+        }
+
         Ok(())
     }
 
+    /// Returns true if every value of these rules is equal to the
+    /// corresponding value in `other`
+    pub fn same_values(&self, other: &Rules) -> bool {
+        match (serde_json::to_value(self), serde_json::to_value(other)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+
     pub fn compute_millis(&self, time: Time) -> usize {
         let mut millis = time.millis as usize;
 
@@ -600,7 +1068,7 @@ impl FromStr for WeaponKind {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
 pub enum ArmorKind {
     Light,
     Medium,
@@ -66,6 +66,7 @@ pub enum BonusKind {
     FlankedImmunity,
     SneakAttackImmunity,
     CritImmunity,
+    WaterWalking,
     GroupUsesPerEncounter { group: String, amount: ExtInt },
     GroupUsesPerDay { group: String, amount: ExtInt },
     ClassStat { id: String, amount: i32 },
@@ -236,6 +237,7 @@ fn apply_modifiers(bonus: &mut Bonus, neg: f32, pos: f32) {
         | FlankedImmunity
         | SneakAttackImmunity
         | CritImmunity
+        | WaterWalking
         | AbilitiesDisabled
         | FreeAbilityGroupUse => return,
     };
@@ -337,6 +339,7 @@ pub fn merge_if_dup(first: &Bonus, sec: &Bonus) -> Option<Bonus> {
         FlankedImmunity => merge_dup!(FlankedImmunity: sec, when),
         SneakAttackImmunity => merge_dup!(SneakAttackImmunity: sec, when),
         CritImmunity => merge_dup!(CritImmunity: sec, when),
+        WaterWalking => merge_dup!(WaterWalking: sec, when),
         FreeAbilityGroupUse => merge_dup!(FreeAbilityGroupUse: sec, when),
 
         GroupUsesPerEncounter { ref group, amount } => {
@@ -459,6 +462,21 @@ pub struct AttackBuilder {
 
     #[serde(default)]
     pub sounds: HitSounds,
+
+    /// The AP cost of attacking with this weapon.  If not specified,
+    /// the rules `attack_ap` is used
+    #[serde(default)]
+    pub ap: Option<u32>,
+
+    /// How many of the highest attack rolls, out of 100, are critical hits
+    /// with this weapon, replacing the rules `crit_chance`
+    #[serde(default)]
+    pub crit_range: Option<u32>,
+
+    /// The critical hit damage multiplier for this weapon, replacing the
+    /// rules `crit_damage_multiplier`
+    #[serde(default)]
+    pub crit_multiplier: Option<f32>,
 }
 
 impl AttackBuilder {
@@ -475,6 +493,9 @@ impl AttackBuilder {
             kind: self.kind.clone(),
             bonuses: self.bonuses.clone(),
             sounds: self.sounds.clone(),
+            ap: self.ap,
+            crit_range: self.crit_range,
+            crit_multiplier: self.crit_multiplier,
         }
     }
 
@@ -0,0 +1,332 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::fmt;
+
+use crate::rules::Attribute;
+
+/// The values a `StatFormula` may refer to, computed for a specific actor
+pub struct FormulaVars {
+    /// The bonus of each attribute over the rules base attribute, indexed
+    /// in the order of `Attribute::iter`
+    pub attributes: [i32; 6],
+
+    /// The dexterity bonus, limited by the max dexterity bonus of equipped armor
+    pub armored_dex: i32,
+    pub level: i32,
+    pub melee: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Var {
+    Attribute(usize),
+    ArmoredDex,
+    Level,
+    Melee,
+    Ranged,
+}
+
+impl Var {
+    fn parse(name: &str) -> Option<Var> {
+        if let Some(index) = Attribute::iter().position(|attr| attr.short_name() == name) {
+            return Some(Var::Attribute(index));
+        }
+
+        Some(match name {
+            "armored_dex" => Var::ArmoredDex,
+            "level" => Var::Level,
+            "melee" => Var::Melee,
+            "ranged" => Var::Ranged,
+            _ => return None,
+        })
+    }
+
+    fn value(self, vars: &FormulaVars) -> i32 {
+        match self {
+            Var::Attribute(index) => vars.attributes[index],
+            Var::ArmoredDex => vars.armored_dex,
+            Var::Level => vars.level,
+            Var::Melee => vars.melee as i32,
+            Var::Ranged => !vars.melee as i32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Num(f32),
+    Var(Var),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval_i32(&self, vars: &FormulaVars) -> i32 {
+        match self {
+            Expr::Num(value) => *value as i32,
+            Expr::Var(var) => var.value(vars),
+            Expr::Neg(expr) => -expr.eval_i32(vars),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.eval_i32(vars), right.eval_i32(vars));
+                match op {
+                    Op::Add => left + right,
+                    Op::Sub => left - right,
+                    Op::Mul => left * right,
+                    Op::Div => left.checked_div(right).unwrap_or(0),
+                }
+            }
+        }
+    }
+
+    fn eval_f32(&self, vars: &FormulaVars) -> f32 {
+        match self {
+            Expr::Num(value) => *value,
+            Expr::Var(var) => var.value(vars) as f32,
+            Expr::Neg(expr) => -expr.eval_f32(vars),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.eval_f32(vars), right.eval_f32(vars));
+                match op {
+                    Op::Add => left + right,
+                    Op::Sub => left - right,
+                    Op::Mul => left * right,
+                    Op::Div if right == 0.0 => 0.0,
+                    Op::Div => left / right,
+                }
+            }
+        }
+    }
+
+    fn is_integral(&self) -> bool {
+        match self {
+            Expr::Num(value) => value.fract() == 0.0,
+            Expr::Var(_) => true,
+            Expr::Neg(expr) => expr.is_integral(),
+            Expr::Binary(_, left, right) => left.is_integral() && right.is_integral(),
+        }
+    }
+}
+
+/// A simple arithmetic expression computing part of a derived stat from an
+/// actor's attributes.  Formulas are written in the rules as strings such as
+/// `"per + str * 2"`, supporting numbers, `+`, `-`, `*`, `/`, and parentheses.
+/// The attribute bonuses are referred to by their short names (`str`, `dex`,
+/// `end`, `per`, `int`, and `wis`).  `armored_dex` is the dexterity bonus
+/// limited by equipped armor, `level` is the actor's total level, and
+/// `melee` and `ranged` are 1 or 0 depending on the actor's attack.
+///
+/// Integer stats are computed with integer arithmetic, so each division
+/// rounds toward zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct StatFormula {
+    source: String,
+    expr: Expr,
+}
+
+impl StatFormula {
+    pub fn parse(source: &str) -> Result<StatFormula, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.expr()?;
+        if parser.pos < parser.tokens.len() {
+            let token = &parser.tokens[parser.pos];
+            return Err(format!("Unexpected '{token}' in formula '{source}'"));
+        }
+
+        Ok(StatFormula {
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    pub fn eval_i32(&self, vars: &FormulaVars) -> i32 {
+        self.expr.eval_i32(vars)
+    }
+
+    pub fn eval_f32(&self, vars: &FormulaVars) -> f32 {
+        self.expr.eval_f32(vars)
+    }
+
+    /// Returns true if this formula only contains whole numbers, and so may
+    /// be used for an integer stat
+    pub fn is_integral(&self) -> bool {
+        self.expr.is_integral()
+    }
+}
+
+impl fmt::Display for StatFormula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl TryFrom<String> for StatFormula {
+    type Error = String;
+
+    fn try_from(source: String) -> Result<StatFormula, String> {
+        StatFormula::parse(&source)
+    }
+}
+
+impl From<StatFormula> for String {
+    fn from(formula: StatFormula) -> String {
+        formula.source
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f32),
+    Ident(String),
+    Op(Op),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Num(value) => write!(f, "{value}"),
+            Token::Ident(name) => write!(f, "{name}"),
+            Token::Op(Op::Add) => write!(f, "+"),
+            Token::Op(Op::Sub) => write!(f, "-"),
+            Token::Op(Op::Mul) => write!(f, "*"),
+            Token::Op(Op::Div) => write!(f, "/"),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c.is_ascii_digit() || c == '.' {
+            let mut text = String::new();
+            while let Some(&c) = chars.peek() {
+                if !c.is_ascii_digit() && c != '.' {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+            let value = text
+                .parse::<f32>()
+                .map_err(|_| format!("Invalid number '{text}' in formula '{source}'"))?;
+            tokens.push(Token::Num(value));
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() || c == '_' {
+            let mut text = String::new();
+            while let Some(&c) = chars.peek() {
+                if !c.is_ascii_alphanumeric() && c != '_' {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Ident(text));
+            continue;
+        }
+
+        let token = match c {
+            '+' => Token::Op(Op::Add),
+            '-' => Token::Op(Op::Sub),
+            '*' => Token::Op(Op::Mul),
+            '/' => Token::Op(Op::Div),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            _ => return Err(format!("Unexpected '{c}' in formula '{source}'")),
+        };
+        tokens.push(token);
+        chars.next();
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_op(&self, ops: &[Op]) -> Option<Op> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) if ops.contains(op) => Some(*op),
+            _ => None,
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+        while let Some(op) = self.peek_op(&[Op::Add, Op::Sub]) {
+            self.pos += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.term()?));
+        }
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut expr = self.factor()?;
+        while let Some(op) = self.peek_op(&[Op::Mul, Op::Div]) {
+            self.pos += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.factor()?));
+        }
+        Ok(expr)
+    }
+
+    fn factor(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Num(value)) => Ok(Expr::Num(value)),
+            Some(Token::Ident(name)) => match Var::parse(&name) {
+                None => Err(format!("Unknown value '{name}' in formula")),
+                Some(var) => Ok(Expr::Var(var)),
+            },
+            Some(Token::Op(Op::Sub)) => Ok(Expr::Neg(Box::new(self.factor()?))),
+            Some(Token::Open) => {
+                let expr = self.expr()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("Missing ')' in formula".to_string()),
+                }
+            }
+            Some(token) => Err(format!("Unexpected '{token}' in formula")),
+            None => Err("Unexpected end of formula".to_string()),
+        }
+    }
+}
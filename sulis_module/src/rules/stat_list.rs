@@ -19,13 +19,22 @@ use std::rc::Rc;
 
 use crate::rules::bonus::{AttackBonuses, AttackBuilder, Bonus, BonusKind, BonusList};
 use crate::rules::{
-    AccuracyKind, Armor, ArmorKind, Attack, AttributeList, Damage, HitKind, Resistance, Slot,
-    WeaponKind, WeaponStyle,
+    AccuracyKind, Armor, ArmorKind, Attack, Attribute, AttributeList, Damage, FormulaVars,
+    HitKind, Resistance, Slot, WeaponKind, WeaponStyle,
 };
 use crate::{Actor, Module};
 use sulis_core::image::Image;
 use sulis_core::util::{gen_rand, ExtInt};
 
+/// The result of a single attack roll, along with the values it was rolled against
+#[derive(Debug, Clone, Copy)]
+pub struct AttackRoll {
+    pub roll: i32,
+    pub accuracy: i32,
+    pub defense: i32,
+    pub hit_kind: HitKind,
+}
+
 #[derive(Clone)]
 pub struct StatList {
     attack_range: f32,
@@ -71,6 +80,8 @@ pub struct StatList {
     pub crit_multiplier: f32,
     pub movement_rate: f32,
     pub move_anim_rate: f32,
+    pub armor_movement_ap: u32,
+    pub armor_max_dex_bonus: Option<i32>,
     pub attack_cost: i32,
     pub move_disabled: bool,
     pub attack_disabled: bool,
@@ -79,6 +90,7 @@ pub struct StatList {
     pub flanked_immunity: bool,
     pub sneak_attack_immunity: bool,
     pub crit_immunity: bool,
+    pub water_walking: bool,
     pub free_ability_group_use: bool,
     pub caster_level: i32,
     has_shield: bool,
@@ -126,6 +138,8 @@ impl StatList {
             crit_multiplier: 0.0,
             movement_rate: 0.0,
             move_anim_rate: 0.0,
+            armor_movement_ap: 0,
+            armor_max_dex_bonus: None,
             attack_cost: 0,
             move_disabled: false,
             attack_disabled: false,
@@ -134,6 +148,7 @@ impl StatList {
             flanked_immunity: false,
             sneak_attack_immunity: false,
             crit_immunity: false,
+            water_walking: false,
             free_ability_group_use: false,
             caster_level: 0,
             has_shield: false,
@@ -186,7 +201,7 @@ impl StatList {
         crit_immunity: bool,
         defense: i32,
         bonuses: &AttackBonuses,
-    ) -> HitKind {
+    ) -> AttackRoll {
         let accuracy = match accuracy_kind {
             AccuracyKind::Melee => self.melee_accuracy + bonuses.melee_accuracy,
             AccuracyKind::Ranged => self.ranged_accuracy + bonuses.ranged_accuracy,
@@ -198,10 +213,28 @@ impl StatList {
             roll, accuracy, defense
         );
 
-        if roll + accuracy < defense {
-            return HitKind::Miss;
+        let hit_kind = if roll + accuracy < defense {
+            HitKind::Miss
+        } else {
+            self.hit_kind(roll, accuracy, defense, crit_immunity, bonuses)
+        };
+
+        AttackRoll {
+            roll,
+            accuracy,
+            defense,
+            hit_kind,
         }
+    }
 
+    fn hit_kind(
+        &self,
+        roll: i32,
+        accuracy: i32,
+        defense: i32,
+        crit_immunity: bool,
+        bonuses: &AttackBonuses,
+    ) -> HitKind {
         let result = roll + accuracy - defense;
 
         if !crit_immunity && (100 - roll) < self.crit_chance + bonuses.crit_chance {
@@ -382,6 +415,7 @@ impl StatList {
             FlankedImmunity => self.flanked_immunity = true,
             SneakAttackImmunity => self.sneak_attack_immunity = true,
             CritImmunity => self.crit_immunity = true,
+            WaterWalking => self.water_walking = true,
             GroupUsesPerEncounter { group, amount } => {
                 self.add_single_group_uses_per_encounter(group, *amount)
             }
@@ -452,9 +486,24 @@ impl StatList {
         };
         let is_melee = attacks[0].0.is_melee();
 
+        // when dual wielding, the slower weapon determines the attack cost
+        let weapon_attack_ap = attacks
+            .iter()
+            .map(|(builder, _)| builder.ap.unwrap_or(rules.attack_ap))
+            .max()
+            .unwrap_or(rules.attack_ap);
+
         let mut attack_range = None;
         for (builder, weapon_kind) in attacks {
-            let attack = Attack::new(builder, self, weapon_kind).mult(multiplier);
+            let mut attack = Attack::new(builder, self, weapon_kind).mult(multiplier);
+
+            // weapon crit values replace the rules values, so apply the difference
+            if let Some(crit_range) = builder.crit_range {
+                attack.bonuses.crit_chance += crit_range as i32 - rules.crit_chance as i32;
+            }
+            if let Some(crit_multiplier) = builder.crit_multiplier {
+                attack.bonuses.crit_multiplier += crit_multiplier - rules.crit_damage_multiplier;
+            }
 
             if attack_range.is_none() {
                 attack_range = Some(attack.distance());
@@ -471,33 +520,48 @@ impl StatList {
         self.attack_range = attack_range.unwrap_or(0.0);
         self.armor.finalize();
 
+        for kind in equipped_armor.values() {
+            let category = rules.armor_category(*kind);
+            self.armor_movement_ap += category.movement_ap;
+            if let Some(cap) = category.max_dex_bonus {
+                let cur = self.armor_max_dex_bonus.unwrap_or(cap);
+                self.armor_max_dex_bonus = Some(cur.min(cap));
+            }
+        }
+
         let base_accuracy = rules.base_accuracy as i32;
         let base_defense = rules.base_defense as i32;
         let base_attr = rules.base_attribute;
 
-        use crate::rules::Attribute::*;
-        let attrs = &self.attributes;
-        let str_bonus = attrs.bonus(Strength, base_attr);
-        let dex_bonus = attrs.bonus(Dexterity, base_attr);
-        let end_bonus = attrs.bonus(Endurance, base_attr);
-        let per_bonus = attrs.bonus(Perception, base_attr);
-        let int_bonus = attrs.bonus(Intellect, base_attr);
-        let wis_bonus = attrs.bonus(Wisdom, base_attr);
-        self.initiative += dex_bonus / 2 + per_bonus / 2;
-        self.melee_accuracy += base_accuracy + per_bonus + str_bonus * 2;
-        self.ranged_accuracy += base_accuracy + per_bonus + dex_bonus * 2;
-        self.spell_accuracy += base_accuracy + wis_bonus + int_bonus * 2;
-        self.defense += base_defense + dex_bonus * 2;
-        self.fortitude += base_defense + end_bonus * 2;
-        self.reflex += base_defense + dex_bonus * 2;
-        self.will += base_defense + wis_bonus * 2;
-        self.max_hp += (actor.total_level as i32 * end_bonus) / 3;
-
-        let damage_stat_bonus = if is_melee { str_bonus } else { dex_bonus } as f32;
-
-        self.graze_multiplier += 0.02 * damage_stat_bonus;
-        self.hit_multiplier += 0.03 * damage_stat_bonus;
-        self.crit_multiplier += 0.06 * damage_stat_bonus;
+        let mut attributes = [0; 6];
+        for (value, attr) in attributes.iter_mut().zip(Attribute::iter()) {
+            *value = self.attributes.bonus(*attr, base_attr);
+        }
+        let dex_bonus = self.attributes.bonus(Attribute::Dexterity, base_attr);
+        let vars = FormulaVars {
+            attributes,
+            armored_dex: match self.armor_max_dex_bonus {
+                None => dex_bonus,
+                Some(cap) => dex_bonus.min(cap),
+            },
+            level: actor.total_level as i32,
+            melee: is_melee,
+        };
+
+        let formulas = &rules.derived_stats;
+        self.initiative += formulas.initiative.eval_i32(&vars);
+        self.melee_accuracy += base_accuracy + formulas.melee_accuracy.eval_i32(&vars);
+        self.ranged_accuracy += base_accuracy + formulas.ranged_accuracy.eval_i32(&vars);
+        self.spell_accuracy += base_accuracy + formulas.spell_accuracy.eval_i32(&vars);
+        self.defense += base_defense + formulas.defense.eval_i32(&vars);
+        self.fortitude += base_defense + formulas.fortitude.eval_i32(&vars);
+        self.reflex += base_defense + formulas.reflex.eval_i32(&vars);
+        self.will += base_defense + formulas.will.eval_i32(&vars);
+        self.max_hp += formulas.max_hp.eval_i32(&vars);
+
+        self.graze_multiplier += formulas.graze_multiplier.eval_f32(&vars);
+        self.hit_multiplier += formulas.hit_multiplier.eval_f32(&vars);
+        self.crit_multiplier += formulas.crit_multiplier.eval_f32(&vars);
 
         if self.hit_multiplier < self.graze_multiplier {
             self.hit_multiplier = self.graze_multiplier;
@@ -524,7 +588,7 @@ impl StatList {
         self.crit_multiplier += rules.crit_damage_multiplier;
         self.movement_rate += actor.race.movement_rate;
         self.move_anim_rate += actor.race.move_anim_rate;
-        self.attack_cost += rules.attack_ap as i32;
+        self.attack_cost += weapon_attack_ap as i32;
 
         let size_bonus = actor.race.size.diagonal / 2.0;
         self.touch_range = self.bonus_reach + size_bonus;
@@ -21,7 +21,7 @@ use crate::{ChangeListenerList, GameState};
 use sulis_core::util::ExtInt;
 use sulis_module::{ability::Duration, Ability, Module, StatList, ROUND_TIME_MILLIS};
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum DisabledReason {
     Enabled,
     AbilitiesDisabled,
@@ -29,6 +29,7 @@ pub enum DisabledReason {
     NotEnoughAP,
     NoAbilityGroupUses,
     NotEnoughClassStat,
+    RequirementsNotMet,
     RequiresShield,
     RequiresMelee,
     RequiresRanged,
@@ -46,7 +47,7 @@ pub struct AbilityState {
     pub requires_ranged: bool,
     pub requires_shield: bool,
     pub requires_active_mode: Vec<Rc<Ability>>,
-    cur_duration: u32,
+    pub(crate) cur_duration: u32,
     pub listeners: ChangeListenerList<AbilityState>,
     pub newly_added_ability: bool,
 }
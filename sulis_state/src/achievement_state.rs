@@ -0,0 +1,142 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use chrono::prelude::*;
+
+use sulis_core::config;
+use sulis_core::resource::{read_single_resource_path, write_to_file};
+use sulis_module::{Achievement, Module};
+
+/// The achievements awarded in each campaign for the current user profile.
+/// Unlike most state, this is not stored in save files, but in the
+/// `achievements.yml` file in the user directory.
+pub struct AchievementState;
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct AwardedAchievements {
+    campaigns: HashMap<String, Vec<AwardedAchievement>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AwardedAchievement {
+    pub id: String,
+    pub date: String,
+}
+
+thread_local! {
+    static AWARDED: RefCell<Option<AwardedAchievements>> = const { RefCell::new(None) };
+    static NEW_AWARDS: RefCell<VecDeque<Rc<Achievement>>> = const {
+        RefCell::new(VecDeque::new())
+    };
+}
+
+fn file_path() -> PathBuf {
+    let mut path = config::USER_DIR.clone();
+    path.push("achievements.yml");
+    path
+}
+
+fn with_awarded<T, F: FnOnce(&mut AwardedAchievements) -> T>(f: F) -> T {
+    AWARDED.with(|awarded| {
+        let mut awarded = awarded.borrow_mut();
+        let awarded = awarded.get_or_insert_with(|| {
+            let path = file_path();
+            if !path.is_file() {
+                return AwardedAchievements::default();
+            }
+
+            match read_single_resource_path(&path) {
+                Ok(awarded) => awarded,
+                Err(e) => {
+                    warn!("Error reading achievements from '{:?}'", path);
+                    warn!("{}", e);
+                    AwardedAchievements::default()
+                }
+            }
+        });
+        f(awarded)
+    })
+}
+
+impl AchievementState {
+    /// Awards the achievement with the specified ID in the current campaign,
+    /// saving it to disk and queueing it to be shown to the player.  Returns
+    /// false if the achievement does not exist or was already awarded.
+    pub fn award(id: &str) -> bool {
+        let achievement = match Module::achievement(id) {
+            None => {
+                warn!("Attempted to award invalid achievement '{}'", id);
+                return false;
+            }
+            Some(achievement) => achievement,
+        };
+
+        let campaign = Module::campaign().id.to_string();
+        let awarded = with_awarded(|awarded| {
+            let list = awarded.campaigns.entry(campaign).or_default();
+            if list.iter().any(|a| a.id == id) {
+                return false;
+            }
+
+            list.push(AwardedAchievement {
+                id: id.to_string(),
+                date: Local::now().format("%c").to_string(),
+            });
+
+            if let Err(e) = write_to_file(file_path(), awarded) {
+                warn!("Error writing achievements file");
+                warn!("{}", e);
+            }
+            true
+        });
+
+        if awarded {
+            info!("Awarded achievement '{}'", id);
+            NEW_AWARDS.with(|new| new.borrow_mut().push_back(achievement));
+        }
+
+        awarded
+    }
+
+    /// Returns the award details for the achievement with the specified ID in
+    /// the current campaign, or `None` if it has not been awarded
+    pub fn awarded(id: &str) -> Option<AwardedAchievement> {
+        let campaign = Module::campaign().id.to_string();
+        with_awarded(|awarded| {
+            awarded
+                .campaigns
+                .get(&campaign)
+                .and_then(|list| list.iter().find(|a| a.id == id).cloned())
+        })
+    }
+
+    pub fn is_awarded(id: &str) -> bool {
+        AchievementState::awarded(id).is_some()
+    }
+
+    /// Removes and returns the oldest achievement that was awarded but has
+    /// not yet been shown to the player
+    pub fn take_new_award() -> Option<Rc<Achievement>> {
+        NEW_AWARDS.with(|new| new.borrow_mut().pop_front())
+    }
+}
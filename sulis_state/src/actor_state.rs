@@ -14,21 +14,31 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, Ref, RefCell};
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::io::Error;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
+use crate::inventory::has_proficiency;
 use crate::save_state::ActorSaveState;
+use crate::script::Script;
 use crate::{
     ability_state::DisabledReason, AbilityState, ChangeListenerList, Effect, EntityState,
-    GameState, Inventory, PStats,
+    GameMessage, GameState, Inventory, Loadout, PStats, PartyStash,
 };
 use sulis_core::image::{Image, LayeredImage};
 use sulis_core::io::GraphicsRenderer;
+use sulis_core::ui::Narrator;
 use sulis_core::util::{invalid_data_error, ExtInt, Offset, Scale};
 use sulis_module::{Ability, Actor, ActorBuilder, Faction, ImageLayer, Module};
 use sulis_module::{BonusList, ItemKind, ItemState, QuickSlot, Slot, StatList};
+use sulis_module::area::TraversalPoint;
+
+/// Composed images of actors that have not been drawn for this long are
+/// released, to be composed again when next needed
+const IMAGE_RELEASE_TIME: Duration = Duration::from_secs(30);
 
 pub struct ActorState {
     pub actor: Rc<Actor>,
@@ -36,7 +46,10 @@ pub struct ActorState {
     pub listeners: ChangeListenerList<ActorState>,
     inventory: Inventory,
     effects: Vec<(usize, BonusList)>,
-    image: LayeredImage,
+    image: RefCell<Option<LayeredImage>>,
+    image_last_used: Cell<Instant>,
+    image_generation: u32,
+    image_key: u64,
     pub(crate) ability_states: HashMap<String, AbilityState>,
     texture_cache_invalid: bool,
     anim_image_layers: HashMap<ImageLayer, Rc<dyn Image>>,
@@ -56,13 +69,6 @@ impl ActorState {
 
         let attrs = actor.attributes;
 
-        let image = LayeredImage::new(
-            actor
-                .image_layers()
-                .get_list(actor.sex, actor.hair_color, actor.skin_color),
-            actor.hue,
-        );
-
         let mut ability_states = HashMap::new();
         for ability in actor.abilities.iter() {
             if ability.ability.active.is_none() {
@@ -75,6 +81,7 @@ impl ActorState {
                 None => (),
                 Some(ability_save) => {
                     ability_state.remaining_duration = ability_save.remaining_duration;
+                    ability_state.cur_duration = ability_save.cur_duration;
                 }
             }
 
@@ -95,11 +102,13 @@ impl ActorState {
 
             let mut ability_state = AbilityState::new(&ability);
             ability_state.remaining_duration = state.remaining_duration;
+            ability_state.cur_duration = state.cur_duration;
             ability_states.insert(ability_id, ability_state);
         }
 
         let mut inventory = Inventory::empty();
         inventory.load(save.equipped, save.quick)?;
+        inventory.load_loadouts(save.loadouts);
 
         save.p_stats.load(actor.base_class());
 
@@ -108,7 +117,10 @@ impl ActorState {
             inventory,
             stats: StatList::new(attrs),
             listeners: ChangeListenerList::default(),
-            image,
+            image: RefCell::new(None),
+            image_last_used: Cell::new(Instant::now()),
+            image_generation: 0,
+            image_key: 0,
             effects: Vec::new(),
             ability_states,
             texture_cache_invalid: false,
@@ -122,12 +134,6 @@ impl ActorState {
         trace!("Creating new actor state for {}", actor.id);
         let inventory = Inventory::empty();
 
-        let image = LayeredImage::new(
-            actor
-                .image_layers()
-                .get_list(actor.sex, actor.hair_color, actor.skin_color),
-            actor.hue,
-        );
         let attrs = actor.attributes;
 
         let mut ability_states = HashMap::new();
@@ -145,7 +151,10 @@ impl ActorState {
             inventory,
             stats: StatList::new(attrs),
             listeners: ChangeListenerList::default(),
-            image,
+            image: RefCell::new(None),
+            image_last_used: Cell::new(Instant::now()),
+            image_generation: 0,
+            image_key: 0,
             effects: Vec::new(),
             ability_states,
             texture_cache_invalid: false,
@@ -387,11 +396,26 @@ impl ActorState {
                     return NotEnoughClassStat;
                 }
 
+                if state.ability.unmet_requirement(&self.actor).is_some() {
+                    return RequirementsNotMet;
+                }
+
                 state.is_available(&self.stats, &self.current_active_modes())
             }
         }
     }
 
+    /// Returns the AP cost for this actor to activate the `ability`, after any
+    /// bonuses to ability AP cost.  Passive abilities cost nothing
+    pub fn ability_ap_cost(&self, ability: &Ability) -> u32 {
+        let base_ap = match &ability.active {
+            None => return 0,
+            Some(active) => active.ap as i32,
+        };
+        let bonus = self.stats.bonus_ability_action_point_cost;
+        cmp::max(0, base_ap - bonus) as u32
+    }
+
     pub fn can_activate(&self, id: &str) -> bool {
         if self.stats.abilities_disabled {
             return false;
@@ -412,6 +436,10 @@ impl ActorState {
                     return false;
                 }
 
+                if state.ability.unmet_requirement(&self.actor).is_some() {
+                    return false;
+                }
+
                 state.is_available(&self.stats, &self.current_active_modes())
                     == DisabledReason::Enabled
             }
@@ -533,7 +561,53 @@ impl ActorState {
         scale: Scale,
         millis: u32,
     ) {
-        self.image.draw(renderer, offset, scale, millis);
+        self.image().draw(renderer, offset, scale, millis);
+    }
+
+    /// Returns the composed image of this actor, composing it first if it
+    /// has not been drawn yet or was released
+    fn image(&self) -> Ref<'_, LayeredImage> {
+        self.image_last_used.set(Instant::now());
+        if self.image.borrow().is_none() {
+            *self.image.borrow_mut() = Some(self.compose_image());
+        }
+
+        Ref::map(self.image.borrow(), |image| image.as_ref().unwrap())
+    }
+
+    fn compose_image(&self) -> LayeredImage {
+        let mut layers_override = self.inventory().get_image_layers();
+        for (layer, image) in self.anim_image_layers.iter() {
+            layers_override.insert(*layer, Rc::clone(image));
+        }
+
+        let layers = self.actor.image_layers().get_list_with(
+            self.actor.sex,
+            &self.actor.race,
+            self.actor.appearance(),
+            layers_override,
+        );
+        LayeredImage::new(layers, self.actor.hue)
+    }
+
+    /// Marks the composed image as in use, such as when the actor is visible
+    /// but drawn from a cached texture
+    pub(crate) fn mark_image_used(&self) {
+        self.image_last_used.set(Instant::now());
+    }
+
+    /// Releases the composed image if it has not been used recently
+    pub(crate) fn release_unused_image(&mut self, now: Instant) {
+        let image = self.image.get_mut();
+        if image.is_some() && now - self.image_last_used.get() > IMAGE_RELEASE_TIME {
+            *image = None;
+        }
+    }
+
+    /// Returns a value that changes each time the appearance of this actor's
+    /// image changes, such as when equipment is changed
+    pub fn image_generation(&self) -> u32 {
+        self.image_generation
     }
 
     pub fn draw_to_texture(
@@ -543,7 +617,7 @@ impl ActorState {
         offset: Offset,
         scale: Scale,
     ) {
-        self.image
+        self.image()
             .draw_to_texture(renderer, texture_id, offset, scale);
     }
 
@@ -573,6 +647,40 @@ impl ActorState {
         item
     }
 
+    /// Removes the items held in this actor's hands, including the alternate
+    /// weapon set, returning them
+    #[must_use]
+    pub fn disarm(&mut self) -> Vec<ItemState> {
+        let mut items = Vec::new();
+        for slot in &[Slot::HeldMain, Slot::HeldOff] {
+            items.extend(self.inventory.unequip(*slot));
+        }
+        for slot in &[QuickSlot::AltHeldMain, QuickSlot::AltHeldOff] {
+            items.extend(self.inventory.clear_quickslot(*slot));
+        }
+
+        self.compute_stats();
+        self.texture_cache_invalid = true;
+        self.listeners.notify(self);
+        items
+    }
+
+    /// Removes every equipped and quick slot item from this actor, returning them
+    pub fn unequip_all(&mut self) -> Vec<ItemState> {
+        let mut items = Vec::new();
+        for slot in Slot::iter() {
+            items.extend(self.inventory.unequip(*slot));
+        }
+        for slot in QuickSlot::iter() {
+            items.extend(self.inventory.clear_quickslot(*slot));
+        }
+
+        self.compute_stats();
+        self.texture_cache_invalid = true;
+        self.listeners.notify(self);
+        items
+    }
+
     /// Should only be called by swap_weapon_set in EntityState
     pub(crate) fn do_swap_weapons(&mut self) -> bool {
         let swap_ap = Module::rules().swap_weapons_ap;
@@ -606,6 +714,24 @@ impl ActorState {
         unequipped
     }
 
+    /// Returns true if this actor meets the prereqs, proficiency, and race
+    /// requirements to use or equip the item, ignoring AP and the current
+    /// state of the inventory
+    pub fn meets_item_requirements(&self, item: &ItemState) -> bool {
+        if !item.item.meets_prereqs(&self.actor) {
+            return false;
+        }
+
+        if !has_proficiency(item, &self.stats) {
+            return false;
+        }
+
+        match &item.item.equippable {
+            Some(equip) => !self.actor.race.is_disabled(equip.slot),
+            None => item.item.usable.is_some(),
+        }
+    }
+
     pub fn can_equip(&self, item: &ItemState) -> bool {
         if self.p_stats.is_inventory_locked() {
             return false;
@@ -614,6 +740,89 @@ impl ActorState {
         self.inventory.can_equip(item, &self.stats, &self.actor)
     }
 
+    pub fn can_apply_loadout(&self, loadout: Loadout) -> bool {
+        if self.p_stats.is_inventory_locked() {
+            return false;
+        }
+
+        !GameState::is_combat_active() && self.inventory.has_loadout(loadout)
+    }
+
+    /// Records the currently equipped items as the specified loadout
+    pub fn save_loadout(&mut self, loadout: Loadout) {
+        self.inventory.save_loadout(loadout);
+        self.listeners.notify(self);
+    }
+
+    /// Equips the items saved in the specified loadout, taking them from the
+    /// stash and placing any unequipped items in it.  Items that are no longer
+    /// in the stash or that can no longer be equipped in their saved slot are
+    /// skipped.  Stats are only recomputed once all items are in place.
+    pub fn apply_loadout(&mut self, loadout: Loadout, stash: &mut PartyStash) {
+        if !self.can_apply_loadout(loadout) {
+            return;
+        }
+
+        let items = match self.inventory.loadout(loadout) {
+            None => return,
+            Some(items) => items.clone(),
+        };
+
+        for slot in Slot::iter() {
+            let keep = match (self.inventory.equipped(*slot), items.get(slot)) {
+                (Some(cur), Some(item)) => cur == item,
+                _ => false,
+            };
+
+            if keep {
+                continue;
+            }
+
+            if let Some(item) = self.inventory.unequip(*slot) {
+                stash.add_item(1, item);
+            }
+        }
+
+        for slot in Slot::iter() {
+            let item = match items.get(slot) {
+                None => continue,
+                Some(item) => item,
+            };
+
+            if self.inventory.equipped(*slot).is_some() {
+                continue;
+            }
+
+            let legal_slot = match &item.item.equippable {
+                None => false,
+                Some(equip) => equip.slot == *slot || equip.alternate_slot == Some(*slot),
+            };
+
+            if !legal_slot || !self.inventory.can_equip(item, &self.stats, &self.actor) {
+                warn!("Unable to equip '{}' from {:?} loadout", item.item.id, loadout);
+                continue;
+            }
+
+            let item = match stash.items().find_index(item) {
+                None => {
+                    info!("'{}' from {:?} loadout is not in the stash", item.item.id, loadout);
+                    continue;
+                }
+                Some(index) => match stash.remove_item(index) {
+                    None => continue,
+                    Some(item) => item,
+                },
+            };
+
+            for unequipped in self.inventory.equip(item, Some(*slot)) {
+                stash.add_item(1, unequipped);
+            }
+        }
+
+        self.compute_stats();
+        self.texture_cache_invalid = true;
+    }
+
     pub fn can_unequip(&self, _slot: Slot) -> bool {
         if self.p_stats.is_inventory_locked() {
             return false;
@@ -634,6 +843,14 @@ impl ActorState {
         item
     }
 
+    /// Replaces the item equipped in `slot` with `item`, such as a worn or
+    /// repaired copy of it
+    pub(crate) fn replace_equipped(&mut self, slot: Slot, item: ItemState) {
+        self.inventory.replace_equipped(slot, item);
+        self.compute_stats();
+        self.texture_cache_invalid = true;
+    }
+
     pub fn inventory(&self) -> &Inventory {
         &self.inventory
     }
@@ -647,6 +864,13 @@ impl ActorState {
             return;
         }
 
+        Narrator::say(&format!("{} has fallen", target.borrow().actor.actor.name));
+        GameState::add_message(GameMessage::default().entity(target).text(" has fallen"));
+
+        if parent.borrow().is_party_member() && !target.borrow().is_party_member() {
+            GameState::record_creature_kill(&target.borrow().actor.actor.id);
+        }
+
         let area_state = GameState::area_state();
 
         let reward = {
@@ -666,6 +890,10 @@ impl ActorState {
             for member in GameState::party().iter() {
                 member.borrow_mut().add_xp(reward.xp);
             }
+            if reward.xp > 0 {
+                let text = format!("The party gained {} XP", reward.xp);
+                GameState::add_message(GameMessage::new(&text));
+            }
         } else {
             parent.borrow_mut().add_xp(reward.xp);
         }
@@ -676,7 +904,13 @@ impl ActorState {
         };
 
         trace!("Checking for loot drop.");
-        let items = loot.generate_with_chance(reward.loot_chance);
+        let level = area_state.borrow().area.area.level;
+        let items = loot.generate_with_chance(reward.loot_chance, level);
+        if items.is_empty() {
+            return;
+        }
+
+        let items = Script::loot_generated(level, items);
         if items.is_empty() {
             return;
         }
@@ -729,7 +963,39 @@ impl ActorState {
 
     pub fn get_move_ap_cost(&self, squares: u32) -> u32 {
         let rules = Module::rules();
-        (((rules.movement_ap as f32) / self.stats.movement_rate) as u32 * squares).max(1)
+        let ap = rules.movement_ap + self.stats.armor_movement_ap;
+        (((ap as f32) / self.stats.movement_rate) as u32 * squares).max(1)
+    }
+
+    /// Returns true if this actor is able to enter swimmable water, either by
+    /// passing the swim check in the rules or by water walking
+    pub fn can_swim(&self) -> bool {
+        if self.stats.water_walking {
+            return true;
+        }
+
+        let rules = Module::rules().swimming;
+        match rules.attribute {
+            None => true,
+            Some(attr) => self.stats.attributes.get(attr) >= rules.min_attribute,
+        }
+    }
+
+    /// Returns true if this actor meets the attribute requirement of the
+    /// traversal and, in combat, has the AP to use it
+    pub fn can_traverse(&self, traversal: &TraversalPoint) -> bool {
+        if self.stats.move_disabled {
+            return false;
+        }
+
+        if let Some(attr) = traversal.attribute {
+            if self.stats.attributes.get(attr) < traversal.min_attribute {
+                return false;
+            }
+        }
+
+        let ap = Module::rules().traversal_ap.ap(traversal.kind);
+        !GameState::is_combat_active() || self.ap() >= ap
     }
 
     pub fn set_overflow_ap(&mut self, ap: i32) {
@@ -805,6 +1071,14 @@ impl ActorState {
         self.compute_stats();
     }
 
+    /// Returns this actor to the first level of its base class, keeping its XP so
+    /// that its levels and their ability choices may be chosen again
+    pub fn retrain(&mut self) {
+        let actor = Actor::retrained(&self.actor, self.xp());
+        self.replace_actor(actor);
+        self.init_day();
+    }
+
     pub fn init_day(&mut self) {
         self.p_stats.init_day(&self.stats);
         self.listeners.notify(self);
@@ -834,23 +1108,39 @@ impl ActorState {
         self.listeners.notify(self);
     }
 
+    /// Returns the number of distinct items from each item set that this
+    /// actor currently has equipped
+    pub fn equipped_set_pieces(&self) -> HashMap<String, u32> {
+        let mut pieces: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for item_state in self.inventory.equipped_iter() {
+            if let Some(set) = &item_state.item.set {
+                pieces.entry(set).or_default().insert(&item_state.item.original_id);
+            }
+        }
+
+        pieces
+            .into_iter()
+            .map(|(set, items)| (set.to_string(), items.len() as u32))
+            .collect()
+    }
+
     pub fn compute_stats(&mut self) {
         debug!("Compute stats for '{}'", self.actor.name);
         self.stats = StatList::new(self.actor.attributes);
 
-        let mut layers_override = self.inventory().get_image_layers();
-        for (layer, image) in self.anim_image_layers.iter() {
-            layers_override.insert(*layer, Rc::clone(image));
+        // composing only collects the shared layer images, so compose to
+        // check for appearance changes, but only keep the image if it is in
+        // use.  otherwise it is composed again when next drawn
+        let image = self.compose_image();
+        let key = image.layers_key();
+        if key != self.image_key {
+            self.image_key = key;
+            self.image_generation = self.image_generation.wrapping_add(1);
+        }
+        let cur = self.image.get_mut();
+        if cur.is_some() {
+            *cur = Some(image);
         }
-
-        let layers = self.actor.image_layers().get_list_with(
-            self.actor.sex,
-            &self.actor.race,
-            self.actor.hair_color,
-            self.actor.skin_color,
-            layers_override,
-        );
-        self.image = LayeredImage::new(layers, self.actor.hue);
 
         self.stats.add(&self.actor.race.base_stats);
 
@@ -900,6 +1190,17 @@ impl ActorState {
             self.stats.add(&equippable.bonuses);
         }
 
+        for (set_id, pieces) in self.equipped_set_pieces() {
+            let set = match Module::item_set(&set_id) {
+                None => continue,
+                Some(set) => set,
+            };
+
+            for tier in set.active_tiers(pieces) {
+                self.stats.add(&tier.bonuses);
+            }
+        }
+
         for (_, ref bonuses) in self.effects.iter() {
             self.stats.add(bonuses);
         }
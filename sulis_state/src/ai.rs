@@ -22,6 +22,24 @@ use crate::{animation::Anim, EntityState, GameState, Script};
 use sulis_module::ai::FuncKind;
 use sulis_core::config::Config;
 
+/// How the AI plays a party member during assisted combat.  By default, party
+/// members are played aggressively
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AIBehavior {
+    /// Holds position and only attacks hostiles that are already in reach
+    #[serde(default)]
+    pub defensive: bool,
+
+    /// Uses items and standard attacks, but not abilities
+    #[serde(default)]
+    pub conserve_abilities: bool,
+
+    /// Never played by the AI, even during assisted combat
+    #[serde(default)]
+    pub manual_only: bool,
+}
+
 pub struct AI {
     ai: Option<EntityAI>,
     next_state: State,
@@ -46,7 +64,7 @@ impl AI {
             return;
         }
 
-        if entity.borrow().is_party_member() {
+        if entity.borrow().is_party_member() && !GameState::is_ai_assisted(&entity) {
             self.ai = None;
             return;
         }
@@ -147,9 +165,9 @@ impl EntityAI {
             return State::End;
         }
 
-        let ai_template = match &self.entity.borrow().actor.actor.ai {
+        let ai_template = match self.entity.borrow().ai_template() {
             None => return State::End,
-            Some(template) => Rc::clone(template),
+            Some(template) => template,
         };
 
         let func = ai_template.hooks.get(&FuncKind::AiAction).map(|f| f.as_str()).unwrap_or("ai_action");
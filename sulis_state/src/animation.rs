@@ -42,11 +42,12 @@ use self::move_animation::MoveAnimModel;
 use self::particle_generator::Param;
 use self::particle_generator::{GeneratorModel, GeneratorState};
 use self::ranged_attack_animation::RangedAttackAnimModel;
-use crate::{ChangeListener, Effect, EntityState, ScriptCallback};
+use crate::{ChangeListener, Effect, EntityState, GameState, ScriptCallback};
 use sulis_core::{
+    config::Config,
     image::Image,
     io::GraphicsRenderer,
-    util::{self, ExtInt, Offset, Scale},
+    util::{self, ExtInt, Offset, Point, Scale},
 };
 use sulis_module::ImageLayer;
 
@@ -177,6 +178,21 @@ impl AnimState {
         }
     }
 
+    /// Returns the remaining path of each entity that is currently moving
+    pub fn move_paths(&self) -> Vec<(Rc<RefCell<EntityState>>, Vec<Point>)> {
+        self.below_anims
+            .iter()
+            .chain(self.no_draw_anims.iter())
+            .filter_map(|anim| match anim.kind {
+                AnimKind::Move { ref model } => Some((
+                    Rc::clone(&anim.owner),
+                    move_animation::remaining_path(model),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn has_any_blocking_anims(&self) -> bool {
         AnimState::has_any_blocking_vec(&self.no_draw_anims)
             || AnimState::has_any_blocking_vec(&self.below_anims)
@@ -202,6 +218,27 @@ impl AnimState {
         AnimState::clear_blocking_vec(&mut self.above_anims, entity);
     }
 
+    /// Stops the in progress moves of all party members, leaving each at its
+    /// current position.  Returns true if any moves were stopped.
+    pub fn stop_party_moves(&mut self) -> bool {
+        let mut stopped = false;
+        for anim in self.below_anims.iter_mut().chain(self.no_draw_anims.iter_mut()) {
+            if !matches!(anim.kind, AnimKind::Move { .. }) {
+                continue;
+            }
+            if !anim.owner.borrow().is_party_member() || anim.marked_for_removal.get() {
+                continue;
+            }
+
+            anim.mark_for_removal();
+            // the move did not complete, so don't fire any follow up actions
+            anim.completion_callbacks.clear();
+            anim.update_callbacks.clear();
+            stopped = true;
+        }
+        stopped
+    }
+
     pub fn clear_all_blocking_anims(&mut self) {
         AnimState::clear_all_blocking_vec(&mut self.no_draw_anims);
         AnimState::clear_all_blocking_vec(&mut self.below_anims);
@@ -515,7 +552,7 @@ impl Anim {
             return false;
         }
 
-        let elapsed = self.elapsed + millis;
+        let elapsed = self.elapsed + millis * self.speed_multiplier();
         self.elapsed = elapsed;
 
         self.update_kind(elapsed);
@@ -545,6 +582,21 @@ impl Anim {
         }
     }
 
+    /// Movement and attack animations play faster in combat, based on the
+    /// player's `combat_animation_speed`.  Other animations, which may be
+    /// tied to effect durations, are never scaled
+    fn speed_multiplier(&self) -> u32 {
+        use self::AnimKind::*;
+        match self.kind {
+            MeleeAttack { .. } | RangedAttack { .. } | Move { .. }
+                if GameState::is_combat_active() =>
+            {
+                Config::combat_animation_speed()
+            }
+            _ => 1,
+        }
+    }
+
     fn ok_to_remove(&self) -> bool {
         use self::AnimKind::*;
         match self.kind {
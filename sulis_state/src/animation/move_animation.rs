@@ -16,9 +16,11 @@
 
 use std::cell::{Cell, RefCell};
 use std::cmp;
+use std::collections::HashSet;
 use std::rc::Rc;
 
-use crate::{animation::Anim, EntityState, GameState, animation::particle_generator::Param};
+use crate::{animation::Anim, EntityState, GameMessage, GameState};
+use crate::animation::particle_generator::Param;
 use sulis_core::io::{DrawList, GraphicsRenderer};
 use sulis_core::ui::animation_state;
 use sulis_core::util::{Offset, Point, Rect, Scale, ExtInt};
@@ -94,9 +96,49 @@ pub(in crate::animation) fn update(
 
     if frame_index == model.path.len() - 1 {
         marked_for_removal.set(true);
+        return;
+    }
+
+    if let Some(ref seen) = model.hostiles_seen {
+        let sighted = visible_hostiles(mover).into_iter().find(|i| !seen.contains(i));
+        if let Some(index) = sighted {
+            let hostile = GameState::turn_manager().borrow().entity(index);
+            let message = GameMessage::default()
+                .entity(mover)
+                .text(" stopped after sighting ")
+                .entity(&hostile);
+            GameState::add_message(message);
+            GameState::set_stop_party_moves();
+            model.hostiles_seen = None;
+        }
     }
 }
 
+/// Returns the indices of the living, non hidden hostiles in the mover's area
+/// that are currently visible to the party
+fn visible_hostiles(mover: &Rc<RefCell<EntityState>>) -> HashSet<usize> {
+    let area = GameState::get_area_state(&mover.borrow().location.area_id).unwrap();
+    let area = area.borrow();
+    let mgr = GameState::turn_manager();
+    let mgr = mgr.borrow();
+    let mover = mover.borrow();
+
+    area.entity_iter()
+        .copied()
+        .filter(|index| {
+            let entity = match mgr.entity_checked(*index) {
+                None => return false,
+                Some(entity) => entity,
+            };
+            let entity = entity.borrow();
+            !entity.actor.is_dead()
+                && !entity.actor.stats.hidden
+                && entity.is_hostile(&mover)
+                && area.is_pc_visible(entity.location.x, entity.location.y)
+        })
+        .collect()
+}
+
 pub(in crate::animation) fn draw(
     model: &MoveAnimModel,
     renderer: &mut dyn GraphicsRenderer,
@@ -128,6 +170,11 @@ pub(in crate::animation) fn draw(
     renderer.draw(draw_list);
 }
 
+pub(in crate::animation) fn remaining_path(model: &MoveAnimModel) -> Vec<Point> {
+    let start = cmp::max(model.last_frame_index, 0) as usize;
+    model.path.iter().skip(start).copied().collect()
+}
+
 pub(in crate::animation) fn cleanup(mover: &Rc<RefCell<EntityState>>, model: &mut MoveAnimModel) {
     let old_pos = mover.borrow().location.to_point();
 
@@ -213,8 +260,15 @@ pub fn new(mover: &Rc<RefCell<EntityState>>, path: Vec<Point>, base_time_millis:
 
     let duration_millis = frame_time_millis * path.len() as u32;
     let area_id = mover.borrow().location.area_id.to_string();
+    let combat_mode = GameState::is_combat_active();
+    let hostiles_seen = if !combat_mode && mover.borrow().is_party_member() {
+        Some(visible_hostiles(mover))
+    } else {
+        None
+    };
     let model = MoveAnimModel {
-        combat_mode: GameState::is_combat_active(),
+        combat_mode,
+        hostiles_seen,
         area_id,
         path,
         last_frame_index: 0,
@@ -230,6 +284,9 @@ pub struct MoveAnimModel {
     area_id: String,
     combat_mode: bool, // whether this move was created in or out of combat.  a change in
     // this status will cancel the move
+    // hostiles visible when an out of combat party move started.  sighting any
+    // others stops the party
+    hostiles_seen: Option<HashSet<usize>>,
     pub(in crate::animation) path: Vec<Point>,
     pub(in crate::animation) last_frame_index: i32,
     frame_time_millis: u32,
@@ -14,23 +14,36 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
+mod interactables;
+pub use interactables::{Interactable, InteractableKind};
+
 mod prop_handler;
 use prop_handler::PropHandler;
 
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::io::Error;
 use std::rc::Rc;
 use std::time;
 
+use crate::area_feedback_text::ColorKind;
 use crate::save_state::AreaSaveState;
-use crate::script::AreaTargeter;
+use crate::script::{
+    script_cache, AreaTargeter, FuncKind, ScriptEntity, ScriptProp, TriggeredCallback,
+};
 use crate::*;
 use sulis_core::io::Audio;
 use sulis_core::config::Config;
 use sulis_core::util::{self, gen_rand, invalid_data_error, Point, Size};
-use sulis_module::area::{Transition, TriggerKind, Trigger};
-use sulis_module::{Actor, Area, LootList, Module, ObjectSize, Time};
+use sulis_module::area::{ActorData, PropData, Transition, TriggerKind, Trigger};
+use sulis_module::on_trigger::ScriptData;
+use sulis_module::{
+    actor::Faction, Actor, Area, EntityScriptHook, LootList, Module, ObjectSize, OnTrigger, Time,
+};
+
+/// Custom flag set on entities whose `OnPlayerApproach` hook has fired
+const APPROACHED_FLAG: &str = "__player_approached";
 
 pub struct TriggerState {
     pub(crate) fired: bool,
@@ -45,6 +58,49 @@ impl TriggerState {
     }
 }
 
+/// Changes made to the terrain of an area by scripts, which are saved and
+/// reapplied when the area is loaded
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AreaDelta {
+    tiles: Vec<TileDelta>,
+    passable: Vec<PassableDelta>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct TileDelta {
+    layer: String,
+    x: i32,
+    y: i32,
+    tile: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct PassableDelta {
+    x: i32,
+    y: i32,
+    passable: bool,
+}
+
+impl AreaDelta {
+    fn passable_points(&self) -> Vec<(Point, bool)> {
+        self.passable
+            .iter()
+            .map(|delta| (Point::new(delta.x, delta.y), delta.passable))
+            .collect()
+    }
+}
+
+/// A lifecycle function of an area `script`
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum AreaScriptFunc {
+    Load,
+    FirstEnter,
+    Exit,
+}
+
 #[derive(Clone, Copy)]
 pub enum PCVisRedraw {
     Full,
@@ -66,6 +122,7 @@ pub struct AreaState {
 
     pub(crate) entity_grid: Vec<Vec<usize>>,
     surface_grid: Vec<Vec<usize>>,
+    hazards: HashMap<usize, Option<Faction>>,
     transition_grid: Vec<Option<usize>>,
     trigger_grid: Vec<Option<usize>>,
 
@@ -74,6 +131,10 @@ pub struct AreaState {
     pc_vis_redraw: PCVisRedraw,
     pc_vis: Vec<bool>,
 
+    pub(crate) delta: AreaDelta,
+    terrain_changed: bool,
+    vis_cache: RefCell<VisCache>,
+
     feedback_text: Vec<AreaFeedbackText>,
     scroll_to_callback: Option<Rc<RefCell<EntityState>>>,
 
@@ -123,10 +184,14 @@ impl AreaState {
             transition_grid,
             entity_grid,
             surface_grid,
+            hazards: HashMap::new(),
             trigger_grid,
             pc_vis,
             pc_explored,
             pc_vis_redraw: PCVisRedraw::Not,
+            delta: AreaDelta::default(),
+            terrain_changed: false,
+            vis_cache: RefCell::new(VisCache::default()),
             feedback_text: Vec::new(),
             scroll_to_callback: None,
             targeter: None,
@@ -161,6 +226,12 @@ impl AreaState {
 
         area_state.props.load(save.props)?;
 
+        for tile in save.delta.tiles.iter() {
+            area_state.apply_tile(&tile.layer, tile.x, tile.y, tile.tile.as_deref())?;
+        }
+        area_state.area.set_passable(&save.delta.passable_points());
+        area_state.delta = save.delta;
+
         for (index, trigger_save) in save.triggers.into_iter().enumerate() {
             if index >= area_state.area.area.triggers.len() {
                 return invalid_data_error("Too many triggers defined in save");
@@ -184,6 +255,93 @@ impl AreaState {
         Ok(area_state)
     }
 
+    /// Replaces the tiles at `x`, `y` in the specified layer with the tile with
+    /// ID `tile_id`, or removes them if `None`.  The change is saved with the area
+    pub fn set_tile(
+        &mut self,
+        layer: &str,
+        x: i32,
+        y: i32,
+        tile_id: Option<&str>,
+    ) -> Result<(), Error> {
+        self.apply_tile(layer, x, y, tile_id)?;
+        // setting the tile recomputes passability, so reapply any overrides
+        self.area.set_passable(&self.delta.passable_points());
+
+        self.delta
+            .tiles
+            .retain(|t| t.layer != layer || t.x != x || t.y != y);
+        self.delta.tiles.push(TileDelta {
+            layer: layer.to_string(),
+            x,
+            y,
+            tile: tile_id.map(|id| id.to_string()),
+        });
+
+        self.terrain_changed();
+        Ok(())
+    }
+
+    fn apply_tile(
+        &mut self,
+        layer: &str,
+        x: i32,
+        y: i32,
+        tile_id: Option<&str>,
+    ) -> Result<(), Error> {
+        let tile = match tile_id {
+            None => None,
+            Some(id) => match Module::tile(id) {
+                None => return invalid_data_error(&format!("No tile with ID '{id}'")),
+                Some(tile) if tile.layer != layer => {
+                    return invalid_data_error(&format!("Tile '{id}' is not in layer '{layer}'"))
+                }
+                Some(tile) => Some(tile),
+            },
+        };
+
+        self.area.set_tile(layer, x, y, tile)
+    }
+
+    /// Overrides whether the terrain at `x`, `y` is passable, regardless of
+    /// the tiles there.  The change is saved with the area
+    pub fn set_passable(&mut self, x: i32, y: i32, passable: bool) -> Result<(), Error> {
+        if !self.area.area.coords_valid(x, y) {
+            return invalid_data_error(&format!("Passable location {x},{y} is out of bounds"));
+        }
+
+        self.area.set_passable(&[(Point::new(x, y), passable)]);
+
+        self.delta.passable.retain(|p| p.x != x || p.y != y);
+        self.delta.passable.push(PassableDelta { x, y, passable });
+
+        self.terrain_changed();
+        Ok(())
+    }
+
+    /// Returns true if the terrain has changed since the last call, meaning
+    /// any cached drawing of the area's layers must be redone
+    pub fn take_terrain_changed(&mut self) -> bool {
+        let changed = self.terrain_changed;
+        self.terrain_changed = false;
+        changed
+    }
+
+    fn terrain_changed(&mut self) {
+        self.terrain_changed = true;
+        self.vis_cache.borrow_mut().clear();
+
+        let area = Rc::clone(&self.area.area);
+        for member in GameState::party() {
+            if member.borrow().location.area_id != area.id {
+                continue;
+            }
+            self.compute_pc_visibility(&member, 0, 0);
+        }
+        self.update_view_visibility();
+        self.pc_vis_full_redraw();
+    }
+
     pub fn props(&self) -> &PropHandler {
         &self.props
     }
@@ -238,6 +396,27 @@ impl AreaState {
         }
     }
 
+    /// Applies the per placement overrides from the area definition to the
+    /// newly created `entity`
+    fn apply_placement(entity: &mut EntityState, data: &ActorData, area_id: &str) {
+        if let Some(faction) = data.faction {
+            entity.actor.set_faction(faction);
+        }
+
+        if let Some(facing) = data.facing {
+            entity.set_facing(facing);
+        }
+
+        if let Some(ref id) = data.ai {
+            match Module::ai_template(id) {
+                None => warn!("No AI template '{}' found for actor in area '{}'", id, area_id),
+                Some(ai) => entity.set_ai_override(ai),
+            }
+        }
+
+        entity.set_patrol_route(data.patrol.clone());
+    }
+
     /// Adds entities defined in the area definition to this area state
     pub fn populate(&mut self) {
         let area = Rc::clone(&self.area.area);
@@ -260,11 +439,13 @@ impl AreaState {
 
             let location = Location::from_point(actor_data.location, &area);
             debug!("Adding actor '{}' at '{:?}'", actor.id, location);
-            match self.add_actor(actor, location, Some(unique_id), false, None) {
-                Ok(_) => (),
-                Err(e) => {
-                    warn!("Error adding actor to area: {}", e);
-                }
+            let mut entity =
+                EntityState::new(actor, Some(unique_id), location.clone(), false, None);
+            Self::apply_placement(&mut entity, actor_data, &area.id);
+
+            let entity = Rc::new(RefCell::new(entity));
+            if let Err(e) = self.add_entity(&entity, location) {
+                warn!("Error adding actor to area: {}", e);
             }
         }
 
@@ -345,6 +526,7 @@ impl AreaState {
         // TODO support time specific ambient sounds
 
         Audio::change_ambient(self.area.area.ambient_sound.clone());
+        Audio::change_ambient_loops(self.area.area.ambient_loops.clone());
     }
 
     pub fn range_indicators(&mut self) -> &mut RangeIndicatorHandler {
@@ -361,9 +543,13 @@ impl AreaState {
 
     pub(crate) fn set_targeter(&mut self, mut targeter: AreaTargeter) {
         self.range_indicators.remove_targeter();
+        if let Some(old) = self.targeter.take() {
+            old.borrow().clear_cursor();
+        }
         if targeter.parent().borrow().is_party_member() {
             self.range_indicators.add(targeter.take_range_indicator());
         }
+        targeter.apply_cursor();
         self.targeter = Some(Rc::new(RefCell::new(targeter)));
     }
 
@@ -435,6 +621,33 @@ impl AreaState {
         }
     }
 
+    /// Queues the specified function of this area's script, if the area has
+    /// a script defining it, to be called with the player on the next update
+    pub(crate) fn fire_script(&self, func: AreaScriptFunc) {
+        let script = match &self.area.area.script {
+            None => return,
+            Some(script) => script,
+        };
+
+        let func = match func {
+            AreaScriptFunc::Load => &script.on_load,
+            AreaScriptFunc::FirstEnter => &script.on_first_enter,
+            AreaScriptFunc::Exit => &script.on_exit,
+        };
+
+        let func = match func {
+            None => return,
+            Some(func) => func.to_string(),
+        };
+
+        let pc = GameState::player();
+        let data = ScriptData {
+            id: script.id.to_string(),
+            func,
+        };
+        GameState::add_ui_callback(vec![OnTrigger::FireScript(data)], &pc, &pc);
+    }
+
     pub fn fire_on_encounter_cleared(&mut self, index: usize, target: &Rc<RefCell<EntityState>>) {
         info!("OnEncounterCleared for {}", index);
 
@@ -482,8 +695,11 @@ impl AreaState {
                 return;
             }
             let encounter = &enc_data.encounter;
+            let difficulty = GameState::difficulty();
+            let party_size = GameState::party().len() as u32;
+            let actors = encounter.gen_actors(party_size, difficulty);
             (
-                encounter.gen_actors(),
+                Script::encounter_spawned(encounter, difficulty, party_size, actors),
                 enc_data.location,
                 enc_data.size,
                 ai_group,
@@ -599,11 +815,41 @@ impl AreaState {
             return false;
         }
 
+        if !requester.actor.can_swim() && self.is_in_water(requester, new_x, new_y) {
+            return false;
+        }
+
         requester
             .points(new_x, new_y)
             .all(|p| self.point_entities_passable(entities_to_ignore, p.x, p.y))
     }
 
+    /// Returns true if any of the points the `entity` would occupy at the
+    /// specified coordinates are swimmable water
+    pub fn is_in_water(&self, entity: &EntityState, x: i32, y: i32) -> bool {
+        let width = self.area.width;
+        entity
+            .points(x, y)
+            .any(|p| self.area.layer_set.is_swim_index((p.x + p.y * width) as usize))
+    }
+
+    /// Returns the additional squares of movement the `entity` is charged for
+    /// moving to the specified coordinates, due to tile overrides
+    pub fn movement_cost(&self, entity: &EntityState, x: i32, y: i32) -> u32 {
+        let width = self.area.width;
+        entity
+            .points(x, y)
+            .map(|p| self.area.layer_set.movement_cost_index((p.x + p.y * width) as usize))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns true if the `entity` would need to swim at the specified
+    /// coordinates, i.e. it is in water and cannot water walk
+    pub fn is_swimming(&self, entity: &EntityState, x: i32, y: i32) -> bool {
+        !entity.actor.stats.water_walking && self.is_in_water(entity, x, y)
+    }
+
     pub fn is_passable_for_entity(&self, requester: &EntityState, x: i32, y: i32) -> bool {
         self.is_passable(
             requester,
@@ -644,20 +890,199 @@ impl AreaState {
         self.area.transitions.get(index)
     }
 
+    /// Returns the index of the traversal point with a marker at or next to the
+    /// specified coordinates, along with that marker, if any
+    pub fn get_traversal_at(&self, x: i32, y: i32) -> Option<(usize, Point)> {
+        for (index, traversal) in self.area.area.traversals.iter().enumerate() {
+            for marker in &[traversal.from, traversal.to] {
+                if (marker.x - x).abs() <= 1 && (marker.y - y).abs() <= 1 {
+                    return Some((index, *marker));
+                }
+            }
+        }
+        None
+    }
+
     pub fn toggle_prop_active(&mut self, index: usize) {
         if !self.props.toggle_active(index) {
             return;
         }
+        self.vis_cache.borrow_mut().clear();
+
+        self.pc_vis_partial_redraw(0, 0);
+        for member in GameState::party().iter() {
+            self.compute_pc_visibility(member, 0, 0);
+        }
+        self.update_view_visibility();
+    }
+
+    /// Removes the prop at `index`, updating passability and visibility
+    pub fn remove_prop(&mut self, index: usize) {
+        self.props.remove(index);
+        self.props_changed();
+    }
+
+    /// Recomputes party visibility after props have been added or removed
+    pub(crate) fn props_changed(&mut self) {
+        self.vis_cache.borrow_mut().clear();
 
         self.pc_vis_partial_redraw(0, 0);
         for member in GameState::party().iter() {
+            if member.borrow().location.area_id != self.area.area.id {
+                continue;
+            }
             self.compute_pc_visibility(member, 0, 0);
         }
         self.update_view_visibility();
     }
 
+    /// Opens the lock on the prop at `index` with a key from the party
+    /// keyring, using up the key if the lock consumes it.  Returns false if
+    /// the prop is not locked or the party does not hold the key.
+    pub fn unlock_prop(&mut self, index: usize) -> bool {
+        let prop = self.props.get(index);
+        let lock = match prop.lock() {
+            Some(lock) if prop.is_locked() => lock.clone(),
+            _ => return false,
+        };
+
+        let stash = GameState::party_stash();
+        let key = if lock.consume_key {
+            stash.borrow_mut().remove_key(&lock.key)
+        } else {
+            let stash = stash.borrow();
+            let key = stash.keys().iter().find(|(_, item)| item.item.id == lock.key);
+            key.map(|(_, item)| item.clone())
+        };
+
+        let key = match key {
+            None => return false,
+            Some(key) => key,
+        };
+
+        let prop = self.props.get_mut(index);
+        prop.unlock();
+
+        let mut message = GameMessage::new(&format!("Unlocked {} with ", prop.name())).item(&key);
+        if lock.consume_key {
+            message = message.text(", which was used up");
+        }
+        GameState::add_message(message);
+        true
+    }
+
+    /// Switches the prop at `index` to the visual state with the given `id`,
+    /// updating passability and visibility.  Returns false if the prop has
+    /// no such state.
+    pub fn set_prop_state(&mut self, index: usize, id: &str) -> bool {
+        if !self.props.set_visual_state(index, id) {
+            return false;
+        }
+
+        self.props_changed();
+        true
+    }
+
+    /// Deals `amount` damage from `attacker` to the destructible prop at `index`.
+    /// If the prop's hit points are exhausted, it is removed, replaced by its
+    /// debris prop, and its `on_prop_destroyed` script is called.
+    pub fn damage_prop(
+        area_state: &Rc<RefCell<AreaState>>,
+        attacker: &Rc<RefCell<EntityState>>,
+        index: usize,
+        amount: u32,
+    ) {
+        let (destructible, location) = {
+            let mut area = area_state.borrow_mut();
+            if !area.props.index_valid(index) {
+                return;
+            }
+
+            let prop = area.props.get_mut(index);
+            let destructible = match prop.prop.destructible {
+                None => return,
+                Some(ref destructible) => destructible.clone(),
+            };
+            let damage = prop.take_damage(amount);
+            let hp = prop.hp().unwrap_or(0);
+            let location = prop.location.clone();
+            let size = Rc::clone(&prop.prop.size);
+
+            let pos_x = location.x as f32 + size.width as f32 / 2.0;
+            let pos_y = location.y as f32 - 1.5;
+            let mut text = AreaFeedbackText::new(location.to_point(), pos_x, pos_y, 3.0);
+            text.add_entry(format!("{damage}"), ColorKind::Hit);
+            area.add_feedback_text(text);
+
+            if hp > 0 {
+                return;
+            }
+
+            (destructible, location)
+        };
+
+        info!("Prop at {},{} destroyed", location.x, location.y);
+        let debris = {
+            let mut area = area_state.borrow_mut();
+            area.props.remove(index);
+
+            let debris = destructible.debris.as_ref().and_then(|id| {
+                let prop = Module::prop(id);
+                if prop.is_none() {
+                    warn!("Invalid debris prop '{}'", id);
+                }
+                prop
+            });
+
+            let debris_index = debris.and_then(|prop| {
+                let data = PropData {
+                    prop,
+                    location: location.to_point(),
+                    items: Vec::new(),
+                    loot: None,
+                    enabled: true,
+                    hover_text: None,
+                    owner: None,
+                    lock: None,
+                };
+                match area.props.add(&data, location.clone(), false) {
+                    Err(e) => {
+                        warn!("Unable to add debris prop: {}", e);
+                        None
+                    }
+                    Ok(index) => Some(index),
+                }
+            });
+
+            area.props_changed();
+
+            debris_index.map(|index| {
+                let id = &area.props.get(index).prop.id;
+                ScriptProp::new(&area.area.area.id, index, id)
+            })
+        };
+
+        if let Some(ref script) = destructible.script {
+            let parent = ScriptEntity::from(attacker);
+            let func = "on_prop_destroyed";
+            if let Err(e) = script_cache::trigger_script(script, func, (parent, debris)) {
+                warn!("Error in prop destroyed script '{}/{}': {}", script, func, e);
+            }
+        }
+    }
+
     pub fn has_visibility(&self, parent: &EntityState, target: &EntityState) -> bool {
-        has_visibility(&self.area, self.props.entire_vis_grid(), parent, target)
+        self.vis_cache.borrow_mut().has_visibility(
+            &self.area,
+            self.props.entire_vis_grid(),
+            parent,
+            target,
+        )
+    }
+
+    /// Returns true if either of the two entities can see the other
+    pub fn has_visibility_either(&self, a: &EntityState, b: &EntityState) -> bool {
+        self.has_visibility(a, b) || self.has_visibility(b, a)
     }
 
     pub fn compute_pc_visibility(
@@ -708,6 +1133,27 @@ impl AreaState {
         }
     }
 
+    /// Updates the view visibility only within the region that `entity` could
+    /// have seen before or after moving by `delta_x`, `delta_y`.  The rest of
+    /// the area is unaffected by the move.
+    fn update_view_visibility_near(&mut self, entity: &EntityState, delta_x: i32, delta_y: i32) {
+        let max_dist = self.area.area.vis_dist;
+        let x = entity.location.x + entity.size.width / 2;
+        let y = entity.location.y + entity.size.height / 2;
+        let min_x = cmp::max(0, x - max_dist + cmp::min(delta_x, 0));
+        let max_x = cmp::min(self.area.width, x + max_dist + cmp::max(delta_x, 0));
+        let min_y = cmp::max(0, y - max_dist + cmp::min(delta_y, 0));
+        let max_y = cmp::min(self.area.height, y + max_dist + cmp::max(delta_y, 0));
+
+        let party = GameState::party();
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let index = (x + y * self.area.width) as usize;
+                self.pc_vis[index] = party.iter().any(|member| member.borrow().pc_vis()[index]);
+            }
+        }
+    }
+
     pub fn set_trigger_enabled_at(&mut self, x: i32, y: i32, enabled: bool) -> bool {
         if !self.area.area.coords_valid(x, y) {
             warn!("Invalid coords to enable trigger at {},{}", x, y);
@@ -745,6 +1191,49 @@ impl AreaState {
         );
     }
 
+    /// Fires the `OnPlayerApproach` entity script hook for any entities the
+    /// specified party member has moved near, once per entity
+    fn check_player_approach(&self, mover: &Rc<RefCell<EntityState>>, mgr: &mut TurnManager) {
+        let mover_index = mover.borrow().index();
+        let mover_id = mover.borrow().id();
+        for index in self.entities.iter() {
+            if *index == mover_index {
+                continue;
+            }
+
+            let entity = mgr.entity(*index);
+            let cb = {
+                let mut entity = entity.borrow_mut();
+                let approach_distance = match &entity.actor.actor.script {
+                    None => continue,
+                    Some(script) => {
+                        if !script.hooks.contains_key(&EntityScriptHook::OnPlayerApproach) {
+                            continue;
+                        }
+                        script.approach_distance
+                    }
+                };
+
+                if entity.actor.is_dead() || entity.get_custom_flag(APPROACHED_FLAG).is_some() {
+                    continue;
+                }
+
+                if dist(&*mover.borrow(), &*entity) > approach_distance {
+                    continue;
+                }
+
+                entity.set_custom_flag(APPROACHED_FLAG, "true");
+                match entity.script_callbacks() {
+                    None => continue,
+                    Some(cb) => cb,
+                }
+            };
+
+            let func = FuncKind::OnPlayerApproach;
+            mgr.add_triggered_cb(TriggeredCallback::with_target(cb, func, mover_id));
+        }
+    }
+
     /// whether the pc has current visibility to the specified coordinations
     /// No bounds checking is done on the `x` and `y` arguments
     pub fn is_pc_visible(&self, x: i32, y: i32) -> bool {
@@ -847,13 +1336,23 @@ impl AreaState {
         }
 
         self.surfaces.retain(|i| *i != index);
+        self.hazards.remove(&index);
 
         entities
     }
 
     #[must_use]
-    pub(crate) fn add_surface(&mut self, index: usize, points: &[Point]) -> HashSet<usize> {
+    pub(crate) fn add_surface(
+        &mut self,
+        index: usize,
+        points: &[Point],
+        hazard: bool,
+        hidden_by: Option<Faction>,
+    ) -> HashSet<usize> {
         self.surfaces.push(index);
+        if hazard {
+            self.hazards.insert(index, hidden_by);
+        }
 
         let mut entities = HashSet::new();
         for p in points {
@@ -871,14 +1370,40 @@ impl AreaState {
         entities
     }
 
+    /// Returns true if the square at the specified grid index is covered by
+    /// a hazard surface known to the specified faction.  Hidden hazards are
+    /// only known to the faction that hid them
+    pub fn is_hazard_index(&self, index: usize, faction: Faction) -> bool {
+        if self.hazards.is_empty() {
+            return false;
+        }
+
+        self.surface_grid[index].iter().any(|i| match self.hazards.get(i) {
+            None => false,
+            Some(None) => true,
+            Some(Some(hidden_by)) => *hidden_by == faction,
+        })
+    }
+
+    /// Makes the hazard surface at `index` known to all factions
+    pub(crate) fn reveal_hazard(&mut self, index: usize) {
+        if let Some(hidden_by) = self.hazards.get_mut(&index) {
+            *hidden_by = None;
+        }
+    }
+
     pub(crate) fn load_entity(
         &mut self,
         entity: &Rc<RefCell<EntityState>>,
+        id: Option<EntityId>,
         location: Location,
         is_dead: bool,
     ) -> Result<usize, Error> {
         let mgr = GameState::turn_manager();
-        let index = mgr.borrow_mut().add_entity(entity, is_dead);
+        let index = match id {
+            None => mgr.borrow_mut().add_entity(entity, is_dead),
+            Some(id) => mgr.borrow_mut().load_entity(entity, id, is_dead),
+        };
 
         if is_dead {
             Ok(index)
@@ -892,8 +1417,15 @@ impl AreaState {
         entity: &Rc<RefCell<EntityState>>,
         location: Location,
     ) -> Result<usize, Error> {
-        let result = self.load_entity(entity, location, false);
+        let result = self.load_entity(entity, None, location, false);
         entity.borrow_mut().actor.init_day();
+
+        if result.is_ok() {
+            if let Some(cb) = entity.borrow().script_callbacks() {
+                let mgr = GameState::turn_manager();
+                mgr.borrow_mut().add_triggered_cb(TriggeredCallback::new(cb, FuncKind::OnSpawn));
+            }
+        }
         result
     }
 
@@ -984,7 +1516,13 @@ impl AreaState {
     ) -> bool {
         let old_x = entity.borrow().location.x;
         let old_y = entity.borrow().location.y;
-        if !entity.borrow_mut().move_to(x, y, squares) {
+        let swimming = self.is_swimming(&entity.borrow(), x, y);
+        let squares = if squares > 0 {
+            squares + self.movement_cost(&entity.borrow(), x, y)
+        } else {
+            0
+        };
+        if !entity.borrow_mut().move_to(x, y, squares, swimming) {
             return false;
         }
 
@@ -1019,7 +1557,12 @@ impl AreaState {
                 p.x -= d_x;
                 p.y -= d_y;
             }
-            let new_entities = self.add_surface(aura_index, &surface.points);
+            let new_entities = self.add_surface(
+                aura_index,
+                &surface.points,
+                surface.hazard,
+                surface.hidden_by,
+            );
 
             debug!("Update aura: {}: {}", aura_index, aura.name);
 
@@ -1055,11 +1598,15 @@ impl AreaState {
         let is_pc = entity.borrow().is_party_member();
 
         if is_pc {
-            self.pc_vis_partial_redraw(d_x, d_y);
-            self.compute_pc_visibility(entity, d_x, d_y);
-            self.update_view_visibility();
+            // only a move to a new square can change what the party sees
+            if d_x != 0 || d_y != 0 {
+                self.pc_vis_partial_redraw(d_x, d_y);
+                self.compute_pc_visibility(entity, d_x, d_y);
+                self.update_view_visibility_near(&entity.borrow(), d_x, d_y);
+            }
 
             self.check_trigger_grid(entity);
+            self.check_player_approach(entity, mgr);
         }
 
         mgr.fire_on_moved_next_update(entity_index);
@@ -1112,7 +1659,9 @@ impl AreaState {
         };
 
         if remove_targeter {
-            self.targeter.take();
+            if let Some(targeter) = self.targeter.take() {
+                targeter.borrow().clear_cursor();
+            }
             self.range_indicators.remove_targeter();
         }
     }
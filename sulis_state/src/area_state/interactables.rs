@@ -0,0 +1,121 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use sulis_core::util::Point;
+
+use crate::{AreaState, GameState};
+
+/// The kind of an `Interactable`, which determines how it is highlighted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InteractableKind {
+    Prop,
+    Transition,
+    Npc,
+}
+
+/// Something in an area the player may interact with, along with the tiles
+/// it covers
+#[derive(Clone, Debug)]
+pub struct Interactable {
+    pub kind: InteractableKind,
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl Interactable {
+    fn overlaps(&self, min: Point, max: Point) -> bool {
+        self.x < max.x && self.x + self.w > min.x && self.y < max.y && self.y + self.h > min.y
+    }
+}
+
+impl AreaState {
+    /// Returns the containers that might hold items, usable doors, transitions,
+    /// and NPCs with dialog overlapping the rectangle from `min` to `max`.  Props
+    /// and transitions must have been explored by the party, while NPCs must be
+    /// currently visible
+    pub fn interactables_in(&self, min: Point, max: Point) -> Vec<Interactable> {
+        let mut result = Vec::new();
+
+        for prop in self.props.iter() {
+            if !prop.is_enabled() || !self.is_pc_explored(prop.location.x, prop.location.y) {
+                continue;
+            }
+            let has_loot = prop.is_container() && prop.might_contain_items();
+            if !prop.is_door() && !has_loot {
+                continue;
+            }
+
+            result.push(Interactable {
+                kind: InteractableKind::Prop,
+                name: prop.name().to_string(),
+                x: prop.location.x,
+                y: prop.location.y,
+                w: prop.prop.size.width,
+                h: prop.prop.size.height,
+            });
+        }
+
+        for transition in self.area.transitions.iter() {
+            if !self.is_pc_explored(transition.from.x, transition.from.y) {
+                continue;
+            }
+
+            result.push(Interactable {
+                kind: InteractableKind::Transition,
+                name: transition.hover_text.to_string(),
+                x: transition.from.x,
+                y: transition.from.y,
+                w: transition.size.width,
+                h: transition.size.height,
+            });
+        }
+
+        let mgr = GameState::turn_manager();
+        let mgr = mgr.borrow();
+        for index in self.entity_iter() {
+            let entity = match mgr.entity_checked(*index) {
+                None => continue,
+                Some(entity) => entity,
+            };
+            let entity = entity.borrow();
+
+            if entity.is_party_member() || entity.actor.is_dead() {
+                continue;
+            }
+            if entity.actor.actor.conversation.is_none() || entity.actor.stats.hidden {
+                continue;
+            }
+            if !self.is_pc_visible(entity.location.x, entity.location.y) {
+                continue;
+            }
+
+            result.push(Interactable {
+                kind: InteractableKind::Npc,
+                name: entity.actor.actor.name.to_string(),
+                x: entity.location.x,
+                y: entity.location.y,
+                w: entity.size.width,
+                h: entity.size.height,
+            });
+        }
+
+        result.retain(|interactable| interactable.overlaps(min, max));
+        result
+    }
+}
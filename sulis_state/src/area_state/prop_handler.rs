@@ -71,15 +71,22 @@ impl PropHandler {
             prop,
             location: data.location,
             items: Vec::new(),
+            loot: None,
             enabled: data.enabled,
             hover_text: None,
+            owner: data.owner,
+            lock: data.lock,
         };
 
         let index = self.add(&prop_data, location, false)?;
-        self.props[index]
-            .as_mut()
-            .unwrap()
-            .load_interactive(data.interactive)?;
+        let prop = self.props[index].as_mut().unwrap();
+        prop.load_interactive(data.interactive)?;
+        prop.set_damage_taken(data.damage);
+        if let Some(ref state) = data.state {
+            if !prop.set_visual_state(state, false) {
+                warn!("Prop '{}' no longer has state '{}'", data.id, state);
+            }
+        }
 
         self.update_vis_pass_grid(index);
         Ok(())
@@ -140,13 +147,27 @@ impl PropHandler {
         let end_x = start_x + prop.prop.size.width as usize;
         let end_y = start_y + prop.prop.size.height as usize;
 
+        let mut overlapping = Vec::new();
         for y in start_y..end_y {
             for x in start_x..end_x {
-                self.prop_grid[x + y * self.area.width as usize].retain(|i| *i != index);
+                let grid_index = x + y * self.area.width as usize;
+                self.prop_grid[grid_index].retain(|i| *i != index);
+
+                // a removed prop no longer blocks its squares, but other props
+                // there may still
+                self.prop_vis_grid[grid_index] = true;
+                self.prop_pass_grid[grid_index] = true;
+                overlapping.extend_from_slice(&self.prop_grid[grid_index]);
             }
         }
 
         self.props[index] = None;
+
+        overlapping.sort_unstable();
+        overlapping.dedup();
+        for other in overlapping {
+            self.update_vis_pass_grid(other);
+        }
     }
 
     #[must_use]
@@ -169,7 +190,10 @@ impl PropHandler {
             enabled: true,
             location: location.to_point(),
             items: Vec::new(),
+            loot: None,
             hover_text: None,
+            owner: None,
+            lock: None,
         };
 
         match self.add(&data, location, true) {
@@ -196,7 +220,10 @@ impl PropHandler {
             enabled,
             location: Point::new(x, y),
             items: Vec::new(),
+            loot: None,
             hover_text,
+            owner: None,
+            lock: None,
         };
 
         if let Err(e) = self.add(&data, location, true) {
@@ -230,7 +257,7 @@ impl PropHandler {
             ));
         }
 
-        let state = PropState::new(prop_data, location, temporary);
+        let state = PropState::new(prop_data, location, temporary, self.area.level);
 
         let start_x = state.location.x as usize;
         let start_y = state.location.y as usize;
@@ -364,6 +391,17 @@ impl PropHandler {
         true
     }
 
+    // This method must be called by the owning AreaState in order
+    // to compute visibility correctly
+    pub(in crate::area_state) fn set_visual_state(&mut self, index: usize, id: &str) -> bool {
+        if !self.get_mut(index).set_visual_state(id, true) {
+            return false;
+        }
+
+        self.update_vis_pass_grid(index);
+        true
+    }
+
     fn find_index_to_add(&mut self) -> usize {
         for (index, item) in self.props.iter().enumerate() {
             if item.is_none() {
@@ -379,7 +417,7 @@ impl PropHandler {
         let prop = self.props[index].as_mut();
         let state = prop.unwrap();
 
-        if !state.is_door() {
+        if !state.is_door() && state.prop.states.is_empty() && !state.is_destructible() {
             return;
         }
 
@@ -389,25 +427,47 @@ impl PropHandler {
         let end_x = start_x + state.prop.size.width;
         let end_y = start_y + state.prop.size.height;
 
-        if state.is_active() {
-            for y in start_y..end_y {
-                for x in start_x..end_x {
-                    let idx = (x + y * width) as usize;
-                    self.prop_vis_grid[idx] = true;
-                    self.prop_pass_grid[idx] = true;
+        for y in start_y..end_y {
+            for x in start_x..end_x {
+                let idx = (x + y * width) as usize;
+                self.prop_vis_grid[idx] = true;
+                self.prop_pass_grid[idx] = true;
+            }
+        }
+
+        if !state.is_active() {
+            if let Interactive::Door {
+                ref closed_invis,
+                ref closed_impass,
+                ..
+            } = state.prop.interactive
+            {
+                for p in closed_invis {
+                    self.prop_vis_grid[(p.x + start_x + (p.y + start_y) * width) as usize] = false;
+                }
+
+                for p in closed_impass {
+                    self.prop_pass_grid[(p.x + start_x + (p.y + start_y) * width) as usize] = false;
                 }
             }
-        } else if let Interactive::Door {
-            ref closed_invis,
-            ref closed_impass,
-            ..
-        } = state.prop.interactive
-        {
-            for p in closed_invis {
+        }
+
+        if state.is_destructible() {
+            for p in state.prop.invis.iter() {
+                self.prop_vis_grid[(p.x + start_x + (p.y + start_y) * width) as usize] = false;
+            }
+
+            for p in state.prop.impass.iter() {
+                self.prop_pass_grid[(p.x + start_x + (p.y + start_y) * width) as usize] = false;
+            }
+        }
+
+        if let Some(visual) = state.visual_state() {
+            for p in visual.invis.iter() {
                 self.prop_vis_grid[(p.x + start_x + (p.y + start_y) * width) as usize] = false;
             }
 
-            for p in closed_impass {
+            for p in visual.impass.iter() {
                 self.prop_pass_grid[(p.x + start_x + (p.y + start_y) * width) as usize] = false;
             }
         }
@@ -437,3 +497,50 @@ impl<'a> Iterator for PropIterator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sulis_core::config::Config;
+    use sulis_core::resource::ResourceSet;
+
+    #[test]
+    fn removing_a_door_clears_its_squares() {
+        Config::enable_safe_mode();
+        let dirs = vec![
+            "../data".to_string(),
+            "../campaigns/twin_expanse_prologue".to_string(),
+        ];
+        let yaml = ResourceSet::load_resources(dirs.clone()).unwrap();
+        Module::load_resources(yaml, dirs).unwrap();
+
+        let area = Module::area("wellswood_road_south").unwrap();
+        let mut handler = PropHandler::new((area.width * area.height) as usize, &area);
+        let data = PropData {
+            prop: Module::prop("building_door_01").unwrap(),
+            location: Point::new(1, 1),
+            items: Vec::new(),
+            loot: None,
+            enabled: true,
+            hover_text: None,
+            owner: None,
+            lock: None,
+        };
+        let location = Location::from_point(data.location, &area);
+        let index = handler.add(&data, location, false).unwrap();
+
+        // the closed door blocks its 2 by 3 squares
+        let width = area.width;
+        let squares: Vec<usize> = (1..4)
+            .flat_map(|y| (1..3).map(move |x| (x + y * width) as usize))
+            .collect();
+        assert!(squares.iter().all(|i| !handler.pass_grid(*i)));
+
+        handler.remove(index);
+        assert!(!handler.index_valid(index));
+        for i in squares {
+            assert!(handler.pass_grid(i) && handler.vis_grid(i));
+            assert!(handler.grid()[i].is_empty());
+        }
+    }
+}
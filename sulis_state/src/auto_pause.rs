@@ -0,0 +1,121 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+
+//! Automatic pausing of the game in response to combat events, configured in
+//! the `auto_pause` gameplay config.  While paused, the game update loop does
+//! not run, but the player may still inspect the party and queue up actions.
+
+use std::cell::Cell;
+
+use sulis_core::config::Config;
+use sulis_core::ui::Narrator;
+
+use crate::{GameMessage, GameState, MessageChannel};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoPauseTrigger {
+    LowHealth,
+    EnemySighted,
+    TrapDetected,
+    SpellCast,
+
+    /// Paused directly by the player
+    Manual,
+}
+
+impl AutoPauseTrigger {
+    pub fn from_id(id: &str) -> Option<AutoPauseTrigger> {
+        use AutoPauseTrigger::*;
+        match id {
+            "low_health" => Some(LowHealth),
+            "enemy_sighted" => Some(EnemySighted),
+            "trap_detected" => Some(TrapDetected),
+            "spell_cast" => Some(SpellCast),
+            _ => None,
+        }
+    }
+
+    fn enabled(self) -> bool {
+        let config = Config::auto_pause();
+        use AutoPauseTrigger::*;
+        match self {
+            LowHealth => config.low_health,
+            EnemySighted => config.enemy_sighted,
+            TrapDetected => config.trap_detected,
+            SpellCast => config.spell_cast,
+            Manual => true,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        use AutoPauseTrigger::*;
+        match self {
+            LowHealth => "Party member badly hurt",
+            EnemySighted => "Enemy sighted",
+            TrapDetected => "Trap detected",
+            SpellCast => "Ability used",
+            Manual => "Paused",
+        }
+    }
+}
+
+pub struct AutoPause;
+
+thread_local! {
+    static PAUSED: Cell<Option<AutoPauseTrigger>> = const { Cell::new(None) };
+}
+
+impl AutoPause {
+    /// Pauses the game if the specified trigger is enabled in the config and
+    /// the game is not already paused
+    pub fn trigger(trigger: AutoPauseTrigger) {
+        if !trigger.enabled() || AutoPause::is_paused() {
+            return;
+        }
+
+        info!("Auto pausing: {:?}", trigger);
+        PAUSED.with(|p| p.set(Some(trigger)));
+
+        let text = format!("Game paused: {}", trigger.description());
+        Narrator::say(&text);
+        GameState::add_message(GameMessage::new(&text).channel(MessageChannel::Notify));
+    }
+
+    /// Pauses the game if it is running, or resumes it otherwise
+    pub fn toggle() {
+        if AutoPause::is_paused() {
+            AutoPause::resume();
+        } else {
+            AutoPause::trigger(AutoPauseTrigger::Manual);
+        }
+    }
+
+    pub fn resume() {
+        if PAUSED.with(|p| p.take()).is_some() {
+            info!("Resuming from pause");
+        }
+    }
+
+    pub fn is_paused() -> bool {
+        PAUSED.with(|p| p.get().is_some())
+    }
+
+    /// Returns the reason the game is currently paused, if it is
+    pub fn reason() -> Option<AutoPauseTrigger> {
+        PAUSED.with(|p| p.get())
+    }
+}
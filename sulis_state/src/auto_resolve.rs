@@ -0,0 +1,156 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_module::{DamageKind, HitKind, Module};
+
+use crate::{ActorState, EntityState, GameState};
+
+/// The outcome of an auto-resolved combat
+pub struct AutoResolveSummary {
+    /// Names of the hostiles defeated
+    pub defeated: Vec<String>,
+
+    /// The XP gained by each party member
+    pub xp: u32,
+
+    /// Names of each party member and the hit points they lost
+    pub hp_lost: Vec<(String, u32)>,
+}
+
+/// Estimates the difficulty of the current combat as the total level of the
+/// active hostiles divided by the total level of the party.  Returns `None`
+/// if combat is not active
+pub fn difficulty() -> Option<f32> {
+    if !GameState::is_combat_active() {
+        return None;
+    }
+
+    let party_level: u32 = GameState::party()
+        .iter()
+        .map(|member| member.borrow().actor.actor.total_level)
+        .sum();
+    if party_level == 0 {
+        return None;
+    }
+
+    let hostile_level: u32 = active_hostiles()
+        .iter()
+        .map(|entity| entity.borrow().actor.actor.total_level)
+        .sum();
+
+    Some(hostile_level as f32 / party_level as f32)
+}
+
+/// Returns true if the current combat is easy enough to be auto-resolved
+/// under the module rules
+pub fn can_auto_resolve() -> bool {
+    let max = match Module::rules().auto_resolve.max_difficulty {
+        None => return false,
+        Some(max) => max,
+    };
+
+    match difficulty() {
+        None => false,
+        Some(difficulty) => difficulty <= max,
+    }
+}
+
+/// Returns the fraction of their maximum hit points each party member is
+/// expected to lose by auto-resolving the current combat
+pub fn hp_cost() -> f32 {
+    let rules = Module::rules();
+    let max = match rules.auto_resolve.max_difficulty {
+        None => return 0.0,
+        Some(max) if max <= 0.0 => return 0.0,
+        Some(max) => max,
+    };
+
+    let difficulty = difficulty().unwrap_or(0.0);
+    rules.auto_resolve.hp_cost * (difficulty / max).min(1.0)
+}
+
+/// Ends the current combat by defeating all active hostiles, granting their
+/// XP and loot as if the party had killed them.  Each party member loses hit
+/// points according to the estimated difficulty, but is never brought below
+/// one hit point.  Does nothing and returns `None` if the combat may not be
+/// auto-resolved
+pub fn resolve() -> Option<AutoResolveSummary> {
+    if !can_auto_resolve() {
+        return None;
+    }
+
+    let cost = hp_cost();
+    let pc = GameState::player();
+
+    let mut summary = AutoResolveSummary {
+        defeated: Vec::new(),
+        xp: 0,
+        hp_lost: Vec::new(),
+    };
+
+    for hostile in active_hostiles() {
+        let (hp, name, xp) = {
+            let hostile = hostile.borrow();
+            let xp = hostile.actor.actor.reward.as_ref().map_or(0, |r| r.xp);
+            (hostile.actor.hp(), hostile.actor.actor.name.to_string(), xp)
+        };
+
+        let damage = vec![(DamageKind::Raw, hp.max(1) as u32)];
+        EntityState::remove_hp(&hostile, &pc, HitKind::Hit, damage);
+        ActorState::check_death(&pc, &hostile);
+
+        summary.defeated.push(name);
+        summary.xp += xp;
+    }
+
+    for member in GameState::party() {
+        let mut member = member.borrow_mut();
+        let max_hp = member.actor.stats.max_hp;
+        let hp = member.actor.hp();
+        let lost = (cost * max_hp as f32).round() as i32;
+        let lost = lost.min(hp - 1).max(0) as u32;
+        if lost > 0 {
+            member.actor.remove_hp(lost);
+        }
+        summary
+            .hp_lost
+            .push((member.actor.actor.name.to_string(), lost));
+    }
+
+    GameState::turn_manager().borrow_mut().stop_combat();
+
+    Some(summary)
+}
+
+fn active_hostiles() -> Vec<Rc<RefCell<EntityState>>> {
+    let pc = GameState::player();
+    let mgr = GameState::turn_manager();
+    let mgr = mgr.borrow();
+    let hostiles = mgr
+        .entity_iter()
+        .filter(|entity| {
+            let entity = entity.borrow();
+            !entity.is_party_member()
+                && entity.is_ai_active()
+                && entity.actor.hp() > 0
+                && entity.is_hostile(&pc.borrow())
+        })
+        .collect();
+    hostiles
+}
@@ -0,0 +1,55 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::HashMap;
+
+use sulis_module::{Banter, Module};
+
+/// Tracks the round in which each companion banter was last played, so
+/// that banters respect both their own cooldown and the rules
+/// `banter_cooldown` between any two banters.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BanterState {
+    played: HashMap<String, u32>,
+    last_round: Option<u32>,
+}
+
+impl BanterState {
+    /// Returns true if enough time has passed since the last banter for
+    /// another to start in `round`
+    pub fn is_ready(&self, round: u32) -> bool {
+        match self.last_round {
+            None => true,
+            Some(last) => round >= last + Module::rules().banter_cooldown,
+        }
+    }
+
+    /// Returns true if the specified banter has either never been played,
+    /// or its cooldown has elapsed by `round`
+    pub fn is_available(&self, banter: &Banter, round: u32) -> bool {
+        match (self.played.get(&banter.id), banter.cooldown) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(played), Some(cooldown)) => round >= played + cooldown,
+        }
+    }
+
+    pub fn set_played(&mut self, id: &str, round: u32) {
+        self.played.insert(id.to_string(), round);
+        self.last_round = Some(round);
+    }
+}
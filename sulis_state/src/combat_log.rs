@@ -0,0 +1,358 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+//! Structured record of combat encounters.  While combat is active, attack
+//! rolls, damage, effect applications, and deaths are recorded as events.
+//! When combat ends the encounter is finished, and may be exported as text
+//! and JSON for balance analysis and bug reports, either automatically with
+//! the `export_combat_logs` logging option or via script.
+
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::fs;
+use std::io::Error;
+use std::path::PathBuf;
+
+use chrono::prelude::*;
+
+use sulis_core::config::{self, Config};
+use sulis_core::resource::write_json_to_file;
+use sulis_core::util::ExtInt;
+use sulis_module::{AttackRoll, DamageKind, HitFlags, HitKind};
+
+use crate::{EntityState, GameState};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DamageEntry {
+    pub kind: String,
+    pub amount: u32,
+}
+
+impl DamageEntry {
+    fn list(damage: &[(DamageKind, u32)]) -> Vec<DamageEntry> {
+        damage
+            .iter()
+            .map(|(kind, amount)| DamageEntry {
+                kind: format!("{kind:?}"),
+                amount: *amount,
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CombatEventKind {
+    Attack {
+        attacker: String,
+        target: String,
+        roll: Option<i32>,
+        accuracy: Option<i32>,
+        defense: Option<i32>,
+        result: String,
+        flags: Vec<String>,
+    },
+    Damage {
+        attacker: String,
+        target: String,
+        result: String,
+        damage: Vec<DamageEntry>,
+        total: u32,
+        remaining_hp: i32,
+    },
+    Effect {
+        target: String,
+        effect: String,
+        duration: String,
+    },
+    Death {
+        entity: String,
+    },
+}
+
+impl CombatEventKind {
+    fn to_text(&self) -> String {
+        match self {
+            CombatEventKind::Attack {
+                attacker,
+                target,
+                roll,
+                accuracy,
+                defense,
+                result,
+                flags,
+            } => {
+                let mut text = format!("{attacker} attacks {target}: {result}");
+                if let (Some(roll), Some(accuracy), Some(defense)) = (roll, accuracy, defense) {
+                    text.push_str(&format!(" (roll {roll} + accuracy {accuracy} vs {defense})"));
+                }
+                if !flags.is_empty() {
+                    text.push_str(&format!(" [{}]", flags.join(", ")));
+                }
+                text
+            }
+            CombatEventKind::Damage {
+                attacker,
+                target,
+                result,
+                damage,
+                total,
+                remaining_hp,
+            } => {
+                let parts: Vec<_> = damage
+                    .iter()
+                    .map(|d| format!("{} {}", d.amount, d.kind))
+                    .collect();
+                format!(
+                    "{target} takes {total} damage from {attacker} ({result}: {}), \
+                     {remaining_hp} HP left",
+                    parts.join(", ")
+                )
+            }
+            CombatEventKind::Effect {
+                target,
+                effect,
+                duration,
+            } => format!("{target} gains {effect} for {duration} rounds"),
+            CombatEventKind::Death { entity } => format!("{entity} dies"),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CombatEvent {
+    pub round: u32,
+
+    #[serde(flatten)]
+    pub kind: CombatEventKind,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CombatEncounter {
+    pub area: String,
+    pub started: String,
+    pub rounds: u32,
+    pub events: Vec<CombatEvent>,
+}
+
+impl CombatEncounter {
+    /// Formats this encounter as human readable text, one line per event
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Combat in {} at {}", self.area, self.started);
+        let _ = writeln!(out, "{} rounds, {} events", self.rounds, self.events.len());
+
+        let mut round = None;
+        for event in self.events.iter() {
+            if round != Some(event.round) {
+                round = Some(event.round);
+                let _ = writeln!(out, "\nRound {}", event.round + 1);
+            }
+
+            let _ = writeln!(out, "  {}", event.kind.to_text());
+        }
+
+        out
+    }
+}
+
+#[derive(Default)]
+pub struct CombatLog {
+    current: Option<CombatEncounter>,
+    last: Option<CombatEncounter>,
+    round: u32,
+}
+
+thread_local! {
+    static COMBAT_LOG: RefCell<CombatLog> = RefCell::new(CombatLog::default());
+}
+
+fn describe(entity: &EntityState) -> String {
+    format!("{} ({})", entity.actor.actor.name, entity.unique_id())
+}
+
+impl CombatLog {
+    /// Begins recording a new encounter in the current area
+    pub(crate) fn start() {
+        let area = GameState::area_state().borrow().area.area.name.to_string();
+        COMBAT_LOG.with(|log| {
+            let mut log = log.borrow_mut();
+            log.round = 0;
+            log.current = Some(CombatEncounter {
+                area,
+                started: Local::now().format("%c").to_string(),
+                rounds: 1,
+                events: Vec::new(),
+            });
+        });
+    }
+
+    /// Finishes the encounter currently being recorded, exporting it if
+    /// enabled in the config
+    pub(crate) fn finish() {
+        let finished = COMBAT_LOG.with(|log| {
+            let mut log = log.borrow_mut();
+            let encounter = log.current.take();
+            if encounter.is_some() {
+                log.last = encounter;
+            }
+            log.last.is_some()
+        });
+
+        if finished && Config::logging_config().export_combat_logs {
+            if let Err(e) = CombatLog::export_last() {
+                warn!("Unable to export combat log: {}", e);
+            }
+        }
+    }
+
+    pub(crate) fn next_round() {
+        COMBAT_LOG.with(|log| {
+            let mut log = log.borrow_mut();
+            log.round += 1;
+            let round = log.round;
+            if let Some(encounter) = log.current.as_mut() {
+                encounter.rounds = round + 1;
+            }
+        });
+    }
+
+    fn record(kind: CombatEventKind) {
+        COMBAT_LOG.with(|log| {
+            let mut log = log.borrow_mut();
+            let round = log.round;
+            if let Some(encounter) = log.current.as_mut() {
+                encounter.events.push(CombatEvent { round, kind });
+            }
+        });
+    }
+
+    fn is_recording() -> bool {
+        COMBAT_LOG.with(|log| log.borrow().current.is_some())
+    }
+
+    pub(crate) fn record_attack(
+        attacker: &EntityState,
+        target: &EntityState,
+        roll: Option<AttackRoll>,
+        hit_kind: HitKind,
+        flags: HitFlags,
+    ) {
+        if !CombatLog::is_recording() {
+            return;
+        }
+
+        let mut flag_names = Vec::new();
+        if flags.flanking {
+            flag_names.push("flanking".to_string());
+        }
+        if flags.sneak_attack {
+            flag_names.push("sneak attack".to_string());
+        }
+        if flags.concealment {
+            flag_names.push("concealment".to_string());
+        }
+
+        CombatLog::record(CombatEventKind::Attack {
+            attacker: describe(attacker),
+            target: describe(target),
+            roll: roll.map(|r| r.roll),
+            accuracy: roll.map(|r| r.accuracy),
+            defense: roll.map(|r| r.defense),
+            result: format!("{hit_kind:?}"),
+            flags: flag_names,
+        });
+    }
+
+    pub(crate) fn record_damage(
+        attacker: &EntityState,
+        target: &EntityState,
+        hit_kind: HitKind,
+        damage: &[(DamageKind, u32)],
+    ) {
+        if !CombatLog::is_recording() {
+            return;
+        }
+
+        CombatLog::record(CombatEventKind::Damage {
+            attacker: describe(attacker),
+            target: describe(target),
+            result: format!("{hit_kind:?}"),
+            damage: DamageEntry::list(damage),
+            total: damage.iter().map(|(_, amount)| amount).sum(),
+            remaining_hp: target.actor.hp(),
+        });
+    }
+
+    pub(crate) fn record_effect(target: &EntityState, effect: &str, duration: ExtInt) {
+        if !CombatLog::is_recording() {
+            return;
+        }
+
+        CombatLog::record(CombatEventKind::Effect {
+            target: describe(target),
+            effect: effect.to_string(),
+            duration: duration.to_string(),
+        });
+    }
+
+    pub(crate) fn record_death(entity: &EntityState) {
+        if !CombatLog::is_recording() {
+            return;
+        }
+
+        CombatLog::record(CombatEventKind::Death {
+            entity: describe(entity),
+        });
+    }
+
+    /// Returns the most recently finished encounter, if any
+    pub fn last_encounter() -> Option<CombatEncounter> {
+        COMBAT_LOG.with(|log| log.borrow().last.clone())
+    }
+
+    /// Writes the most recently finished encounter to the `combat_logs`
+    /// user directory, as both a text and a JSON file.  Returns the path of
+    /// the text file
+    pub fn export_last() -> Result<PathBuf, Error> {
+        let encounter = match CombatLog::last_encounter() {
+            None => {
+                return Err(Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "No finished combat encounter to export",
+                ))
+            }
+            Some(encounter) => encounter,
+        };
+
+        let mut dir = config::USER_DIR.clone();
+        dir.push("combat_logs");
+        fs::create_dir_all(&dir)?;
+
+        let name = format!("combat_{}", Local::now().format("%Y%m%d-%H%M%S"));
+
+        let mut json_path = dir.clone();
+        json_path.push(format!("{name}.json"));
+        write_json_to_file(&json_path, &encounter)?;
+
+        let mut text_path = dir;
+        text_path.push(format!("{name}.txt"));
+        fs::write(&text_path, encounter.to_text())?;
+
+        info!("Exported combat log to '{:?}'", text_path);
+        Ok(text_path)
+    }
+}
@@ -0,0 +1,162 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use sulis_module::{ItemListEntrySaveState, ItemState, Module, OnTrigger};
+
+use crate::script::ScriptCallback;
+use crate::{is_within, EntityState, GameState};
+
+thread_local! {
+    static ARRESTING_GUARD: RefCell<Option<Rc<RefCell<EntityState>>>> =
+        const { RefCell::new(None) };
+    static LAST_CHECK_ROUND: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
+/// The party's current bounty and any items confiscated from them.  Once
+/// the bounty reaches the rules `arrest_bounty`, guards that see the party
+/// approach and start the arrest conversation.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CrimeState {
+    bounty: u32,
+    confiscated: Vec<ItemListEntrySaveState>,
+}
+
+impl CrimeState {
+    pub fn bounty(&self) -> u32 {
+        self.bounty
+    }
+
+    /// Adds the specified `amount`, which may be negative, to the bounty.
+    /// The bounty is never less than zero
+    pub fn add_bounty(&mut self, amount: i32) {
+        self.bounty = (self.bounty as i32 + amount).max(0) as u32;
+    }
+
+    pub(crate) fn confiscate(&mut self, quantity: u32, item: &ItemState) {
+        self.confiscated.push(ItemListEntrySaveState::new(quantity, item));
+    }
+
+    pub(crate) fn take_confiscated(&mut self) -> Vec<(u32, ItemState)> {
+        let mut items = Vec::new();
+        for entry in self.confiscated.drain(..) {
+            let item = entry.item;
+            match Module::create_get_item(&item.id, &item.adjectives) {
+                None => warn!("Unable to return confiscated item '{}'", item.id),
+                Some(created) => {
+                    items.push((entry.quantity, ItemState::new(created, item.variant)))
+                }
+            }
+        }
+        items
+    }
+}
+
+/// Checks, at most once per round and only outside of combat, whether a guard
+/// should approach the party to arrest them
+pub(crate) fn update_guards() {
+    let rules = Module::rules();
+    let crime = &rules.crime;
+    let convo = match &crime.arrest_conversation {
+        None => return,
+        Some(convo) => convo,
+    };
+
+    if GameState::is_combat_active() {
+        return;
+    }
+
+    // a guard's move is cancelled without completing if it is interrupted
+    let approaching = ARRESTING_GUARD.with(|g| match g.borrow().as_ref() {
+        None => false,
+        Some(guard) => GameState::has_blocking_animations(guard),
+    });
+    if approaching {
+        return;
+    }
+
+    if GameState::crime().bounty() < crime.arrest_bounty {
+        return;
+    }
+
+    let mgr = GameState::turn_manager();
+    let round = mgr.borrow().current_round();
+    if LAST_CHECK_ROUND.with(|r| r.replace(Some(round))) == Some(round) {
+        return;
+    }
+
+    let player = GameState::player();
+    let guard = {
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let player = player.borrow();
+        let guard = area_state.entity_iter().map(|index| mgr.borrow().entity(*index)).find(|e| {
+            let entity = e.borrow();
+            crime.guards.contains(&entity.actor.actor.id)
+                && !entity.actor.is_dead()
+                && !entity.is_hostile(&player)
+                && area_state.has_visibility(&entity, &player)
+        });
+        guard
+    };
+
+    let guard = match guard {
+        None => return,
+        Some(guard) => guard,
+    };
+
+    info!("Guard '{}' is arresting the party", guard.borrow().unique_id());
+    ARRESTING_GUARD.with(|g| g.replace(Some(Rc::clone(&guard))));
+
+    let max_dist = rules.max_dialog_distance;
+    if is_within(&*guard.borrow(), &*player.borrow(), max_dist) {
+        start_arrest(&guard, convo);
+        return;
+    }
+
+    let mut dest = GameState::get_target_dest(&guard.borrow(), &player.borrow());
+    dest.dist = max_dist;
+    let cb = ArrestCallback {
+        guard: Rc::clone(&guard),
+        convo: convo.to_string(),
+    };
+
+    let to_ignore = vec![guard.borrow().index()];
+    if !GameState::move_towards_dest(&guard, &to_ignore, dest, Some(Box::new(cb))) {
+        ARRESTING_GUARD.with(|g| g.replace(None));
+    }
+}
+
+fn start_arrest(guard: &Rc<RefCell<EntityState>>, convo: &str) {
+    ARRESTING_GUARD.with(|g| g.replace(None));
+    let pc = GameState::player();
+    let cb = OnTrigger::StartConversation(convo.to_string());
+    GameState::add_ui_callback(vec![cb], &pc, guard);
+}
+
+struct ArrestCallback {
+    guard: Rc<RefCell<EntityState>>,
+    convo: String,
+}
+
+impl ScriptCallback for ArrestCallback {
+    fn on_anim_complete(&self) {
+        start_arrest(&self.guard, &self.convo);
+    }
+}
@@ -0,0 +1,159 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+
+//! Developer controls over the passage of game time.  When `time_controls`
+//! is enabled in the debug config, the game update loop may be paused,
+//! advanced by a single turn, and slowed down, to help diagnose the ordering
+//! of animations and script callbacks.
+
+use std::cell::RefCell;
+
+use sulis_core::config::Config;
+
+use crate::GameState;
+
+/// The available time scales, cycled through in order
+const TIME_SCALES: [f32; 4] = [1.0, 0.5, 0.25, 0.1];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct TurnMarker {
+    entity: Option<usize>,
+    round: u32,
+}
+
+impl TurnMarker {
+    fn current() -> TurnMarker {
+        let mgr = GameState::turn_manager();
+        let mgr = mgr.borrow();
+        TurnMarker {
+            entity: mgr.current().map(|entity| entity.borrow().index()),
+            round: mgr.current_round(),
+        }
+    }
+}
+
+pub struct DebugTime {
+    paused: bool,
+    step_from: Option<TurnMarker>,
+    scale_index: usize,
+    remainder: f32,
+}
+
+thread_local! {
+    static DEBUG_TIME: RefCell<DebugTime> = const {
+        RefCell::new(DebugTime {
+            paused: false,
+            step_from: None,
+            scale_index: 0,
+            remainder: 0.0,
+        })
+    };
+}
+
+impl DebugTime {
+    fn enabled() -> bool {
+        Config::debug().time_controls
+    }
+
+    /// Pauses or resumes the game update loop
+    pub fn toggle_pause() {
+        if !DebugTime::enabled() {
+            return;
+        }
+
+        DEBUG_TIME.with(|t| {
+            let mut t = t.borrow_mut();
+            t.paused = !t.paused;
+            t.step_from = None;
+            info!("Debug time {}", if t.paused { "paused" } else { "resumed" });
+        });
+    }
+
+    /// While paused, runs the game update loop until the current turn ends,
+    /// either by the active entity changing in combat or by a round elapsing
+    pub fn step_turn() {
+        if !DebugTime::enabled() {
+            return;
+        }
+
+        let marker = TurnMarker::current();
+        DEBUG_TIME.with(|t| {
+            let mut t = t.borrow_mut();
+            if t.paused {
+                t.step_from = Some(marker);
+            }
+        });
+    }
+
+    /// Switches to the next slower time scale, wrapping back to normal speed
+    pub fn cycle_time_scale() {
+        if !DebugTime::enabled() {
+            return;
+        }
+
+        DEBUG_TIME.with(|t| {
+            let mut t = t.borrow_mut();
+            t.scale_index = (t.scale_index + 1) % TIME_SCALES.len();
+            t.remainder = 0.0;
+            info!("Debug time scale set to {}", TIME_SCALES[t.scale_index]);
+        });
+    }
+
+    /// Returns a short description of the current time controls, or `None`
+    /// if time is passing normally
+    pub fn status() -> Option<String> {
+        DEBUG_TIME.with(|t| {
+            let t = t.borrow();
+            let scale = TIME_SCALES[t.scale_index];
+            match (t.paused, t.step_from.is_some()) {
+                (true, true) => Some(format!("Stepping turn at {scale}x")),
+                (true, false) => Some("Paused".to_string()),
+                (false, _) if scale < 1.0 => Some(format!("Time {scale}x")),
+                (false, _) => None,
+            }
+        })
+    }
+
+    /// Returns the number of millis the game should be updated by this frame,
+    /// or `None` if the update loop is paused
+    pub(crate) fn scale_millis(millis: u32) -> Option<u32> {
+        DEBUG_TIME.with(|t| {
+            let mut t = t.borrow_mut();
+            if t.paused && t.step_from.is_none() {
+                return None;
+            }
+
+            let scaled = millis as f32 * TIME_SCALES[t.scale_index] + t.remainder;
+            t.remainder = scaled.fract();
+            Some(scaled as u32)
+        })
+    }
+
+    /// Called at the end of each update, to pause again once a single turn
+    /// step has completed
+    pub(crate) fn check_step() {
+        let step_from = DEBUG_TIME.with(|t| t.borrow().step_from);
+        let step_from = match step_from {
+            None => return,
+            Some(marker) => marker,
+        };
+
+        if TurnMarker::current() != step_from {
+            DEBUG_TIME.with(|t| t.borrow_mut().step_from = None);
+        }
+    }
+}
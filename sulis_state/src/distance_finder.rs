@@ -15,8 +15,8 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use crate::{EntityState, PropState};
-use sulis_core::util::Point;
-use sulis_module::area::Transition;
+use sulis_core::util::{hex, Point};
+use sulis_module::{area::Transition, Module};
 
 pub trait Locatable {
     fn size(&self) -> (f32, f32);
@@ -87,6 +87,13 @@ pub fn center<T: Locatable>(target: &T) -> (f32, f32) {
 pub fn dist(parent: &impl Locatable, target: &impl Locatable) -> f32 {
     let (cx, cy) = center(parent);
 
+    if Module::rules().is_hex_grid() {
+        let (x, y) = target.pos();
+        let (w, h) = target.size();
+        let from = Point::new(cx.floor() as i32, cy.floor() as i32);
+        return hex::dist_to_rect(from, x, y, w, h);
+    }
+
     let (tx, ty) = center(target);
     let (w, h) = target.size();
 
@@ -0,0 +1,228 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::util::gen_rand;
+use sulis_module::{ItemState, Module, Slot};
+
+use crate::{EntityState, GameMessage, GameState};
+
+const WEAPON_SLOTS: [Slot; 2] = [Slot::HeldMain, Slot::HeldOff];
+
+const ARMOR_SLOTS: [Slot; 6] = [
+    Slot::Head,
+    Slot::Torso,
+    Slot::Hands,
+    Slot::Legs,
+    Slot::Feet,
+    Slot::HeldOff,
+];
+
+/// Returns true if the rules enable item durability
+pub fn is_enabled() -> bool {
+    let rules = Module::rules();
+    rules.durability.enabled && !rules.durability.conditions.is_empty()
+}
+
+/// Returns the condition of `item`, which is zero if it is not worn, up to the
+/// number of conditions in the rules if it is as worn as possible
+pub fn condition(item: &ItemState) -> usize {
+    let rules = Module::rules();
+    let conditions = &rules.durability.conditions;
+    item.item
+        .added_adjectives
+        .iter()
+        .filter_map(|adj| conditions.iter().position(|id| *id == adj.id))
+        .map(|index| index + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the total condition worn by all of the items `entity` has equipped
+pub fn equipped_wear(entity: &EntityState) -> u32 {
+    entity
+        .actor
+        .inventory()
+        .equipped_iter()
+        .map(|item| condition(item) as u32)
+        .sum()
+}
+
+/// Randomly wears the weapon of a party member after they attack with it, with
+/// a greater chance if the attack `missed`
+pub(crate) fn wear_weapon(entity: &Rc<RefCell<EntityState>>, missed: bool) {
+    if !is_enabled() || !entity.borrow().is_party_member() {
+        return;
+    }
+
+    let rules = Module::rules();
+    let mut chance = rules.durability.attack_wear_chance;
+    if missed {
+        chance += rules.durability.miss_wear_chance;
+    }
+    if !roll(chance) {
+        return;
+    }
+
+    let slot = {
+        let entity = entity.borrow();
+        let inventory = entity.actor.inventory();
+        WEAPON_SLOTS.iter().copied().find(|slot| match inventory.equipped(*slot) {
+            None => false,
+            Some(item) => item.item.equippable.as_ref().is_some_and(|e| e.attack.is_some()),
+        })
+    };
+
+    if let Some(slot) = slot {
+        wear(entity, slot);
+    }
+}
+
+/// Randomly wears one of the armor pieces of a party member after they are hit
+pub(crate) fn wear_armor(entity: &Rc<RefCell<EntityState>>) {
+    if !is_enabled() || !entity.borrow().is_party_member() {
+        return;
+    }
+
+    if !roll(Module::rules().durability.hit_wear_chance) {
+        return;
+    }
+
+    let slots: Vec<Slot> = {
+        let entity = entity.borrow();
+        let inventory = entity.actor.inventory();
+        ARMOR_SLOTS
+            .iter()
+            .copied()
+            .filter(|slot| match inventory.equipped(*slot) {
+                None => false,
+                Some(item) => item.item.equippable.as_ref().is_some_and(|e| e.attack.is_none()),
+            })
+            .collect()
+    };
+
+    if slots.is_empty() {
+        return;
+    }
+
+    let slot = slots[gen_rand(0, slots.len())];
+    wear(entity, slot);
+}
+
+/// Repairs up to `amount` conditions of each item `entity` has equipped
+pub(crate) fn repair(entity: &Rc<RefCell<EntityState>>, amount: usize) {
+    let items: Vec<(Slot, ItemState)> = Slot::iter()
+        .filter_map(|slot| {
+            let entity = entity.borrow();
+            let item = entity.actor.inventory().equipped(*slot)?;
+            Some((*slot, item.clone()))
+        })
+        .collect();
+
+    for (slot, item) in items {
+        let cur = condition(&item);
+        if cur == 0 {
+            continue;
+        }
+
+        if let Some(repaired) = with_condition(&item, cur.saturating_sub(amount)) {
+            entity.borrow_mut().actor.replace_equipped(slot, repaired);
+        }
+    }
+}
+
+/// Repairs one condition of each party member's equipped items when the party
+/// rests, if any party member has the attribute required by the rules
+pub(crate) fn repair_on_rest() {
+    if !is_enabled() {
+        return;
+    }
+
+    let rules = Module::rules();
+    let attr = match rules.durability.repair_attribute {
+        None => return,
+        Some(attr) => attr,
+    };
+
+    let party = GameState::party();
+    let min = rules.durability.repair_min_attribute;
+    if !party
+        .iter()
+        .any(|member| member.borrow().actor.stats.attributes.get(attr) >= min)
+    {
+        return;
+    }
+
+    for member in party {
+        repair(&member, 1);
+    }
+}
+
+fn roll(chance: u32) -> bool {
+    chance > 0 && gen_rand(0, 100) < chance
+}
+
+fn wear(entity: &Rc<RefCell<EntityState>>, slot: Slot) {
+    let item = match entity.borrow().actor.inventory().equipped(slot) {
+        None => return,
+        Some(item) => item.clone(),
+    };
+
+    let cur = condition(&item);
+    if cur >= Module::rules().durability.conditions.len() {
+        return;
+    }
+
+    if let Some(worn) = with_condition(&item, cur + 1) {
+        info!("'{}' has worn to '{}'", item.item.name, worn.item.name);
+        let message = GameMessage::default()
+            .entity(entity)
+            .text("'s ")
+            .item(&item)
+            .text(" was damaged");
+        GameState::add_message(message);
+        entity.borrow_mut().actor.replace_equipped(slot, worn);
+    }
+}
+
+fn with_condition(item: &ItemState, condition: usize) -> Option<ItemState> {
+    let rules = Module::rules();
+    let conditions = &rules.durability.conditions;
+
+    let mut adjectives: Vec<String> = item
+        .item
+        .added_adjectives
+        .iter()
+        .map(|adj| adj.id.to_string())
+        .filter(|id| !conditions.contains(id))
+        .collect();
+    if condition > 0 {
+        adjectives.push(conditions[condition - 1].to_string());
+    }
+
+    match Module::create_get_item(&item.item.original_id, &adjectives) {
+        None => {
+            warn!(
+                "Unable to create '{}' with adjectives {:?}",
+                item.item.original_id, adjectives
+            );
+            None
+        }
+        Some(new_item) => Some(ItemState::new(new_item, item.variant)),
+    }
+}
@@ -22,7 +22,7 @@ use std::rc::Rc;
 use crate::script::{script_callback::FuncKind, CallbackData};
 use crate::{save_state::EffectSaveState, ChangeListenerList, EntityState};
 use sulis_core::util::{invalid_data_error, ExtInt, Point};
-use sulis_module::{BonusList, ROUND_TIME_MILLIS};
+use sulis_module::{actor::Faction, BonusList, ROUND_TIME_MILLIS};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -33,6 +33,14 @@ pub struct Surface {
 
     #[serde(default)]
     pub(crate) aura: Option<usize>,
+
+    #[serde(default)]
+    pub(crate) hazard: bool,
+
+    /// The faction of the creature that hid this hazard.  Until it is
+    /// revealed, only creatures of that faction path around it
+    #[serde(default)]
+    pub(crate) hidden_by: Option<Faction>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -164,6 +172,8 @@ impl Effect {
         points: &[Point],
         squares_to_fire_on_moved: u32,
         aura: Option<usize>,
+        hazard: bool,
+        hidden_by: Option<Faction>,
     ) {
         let area_id = area.to_string();
         self.surface = Some(Surface {
@@ -171,6 +181,8 @@ impl Effect {
             points: points.to_vec(),
             squares_to_fire_on_moved,
             aura,
+            hazard,
+            hidden_by,
         })
     }
 
@@ -197,6 +209,32 @@ impl Effect {
         }
     }
 
+    /// Whether this is a surface that pathing should treat as a soft obstacle
+    pub fn is_hazard(&self) -> bool {
+        match &self.surface {
+            None => false,
+            Some(surface) => surface.hazard,
+        }
+    }
+
+    /// The faction that knows about this hazard, if it has not been revealed
+    /// to everyone
+    pub fn hazard_hidden_by(&self) -> Option<Faction> {
+        match &self.surface {
+            None => None,
+            Some(surface) => surface.hidden_by,
+        }
+    }
+
+    /// Reveals this hazard to all creatures.  Returns the faction it was
+    /// hidden by, if any
+    pub(crate) fn reveal_hazard(&mut self) -> Option<Faction> {
+        match &mut self.surface {
+            None => None,
+            Some(surface) => surface.hidden_by.take(),
+        }
+    }
+
     pub fn surface(&self) -> Option<(&str, &Vec<Point>)> {
         self.surface.as_ref().map(|s| (s.area_id.as_str(), &s.points))
     }
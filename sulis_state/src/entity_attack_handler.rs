@@ -18,7 +18,10 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use sulis_core::io::Audio;
-use crate::{center, is_threat, ActorState, EntityState, GameState};
+use crate::{
+    center, durability, is_threat, ActorState, AreaState, CombatLog, EntityState, GameState,
+    Script,
+};
 use sulis_module::{AccuracyKind, Attack, AttackKind, DamageKind, HitFlags, HitKind, Module,
     OnTrigger};
 
@@ -78,6 +81,26 @@ fn is_flanking(parent: &EntityState, target: &EntityState) -> bool {
 
 type AttackResult = Vec<(HitKind, HitFlags, Vec<(DamageKind, u32)>)>;
 
+/// Attacks the destructible prop at `index` in the parent's area with each of
+/// the parent's weapon attacks.  Attacks on props always hit.
+pub fn prop_attack(parent: &Rc<RefCell<EntityState>>, index: usize) {
+    let area_state = match GameState::get_area_state(&parent.borrow().location.area_id) {
+        None => return,
+        Some(area_state) => area_state,
+    };
+
+    let attacks = parent.borrow().actor.stats.attacks.clone();
+    for attack in attacks {
+        let amount = attack.damage.iter().map(|damage| damage.roll()).sum();
+
+        if let Some(sound_id) = attack.sounds.sound(HitKind::Hit) {
+            Audio::play_sfx(sound_id, 1.0);
+        }
+
+        AreaState::damage_prop(&area_state, parent, index, amount);
+    }
+}
+
 pub fn weapon_attack(
     parent: &Rc<RefCell<EntityState>>,
     target: &Rc<RefCell<EntityState>>,
@@ -125,6 +148,12 @@ pub fn weapon_attack(
         GameState::add_ui_callback(vec![OnTrigger::ScreenShake], parent, target);
     }
 
+    let missed = result.iter().any(|(kind, _, _)| *kind == HitKind::Miss);
+    durability::wear_weapon(parent, missed);
+    if result.iter().any(|(kind, _, _)| *kind != HitKind::Miss) {
+        durability::wear_armor(target);
+    }
+
     ActorState::check_death(parent, target);
     result
 }
@@ -171,24 +200,28 @@ fn attack_internal(
 
     if !rules.concealment_roll(concealment) {
         debug!("Concealment miss");
-        return (
+        let hit_flags = HitFlags {
+            concealment: true,
+            ..Default::default()
+        };
+        CombatLog::record_attack(
+            &parent.borrow(),
+            &target.borrow(),
+            None,
             HitKind::Miss,
-            HitFlags {
-                concealment: true,
-                ..Default::default()
-            },
-            Vec::new(),
+            hit_flags,
         );
+        return (HitKind::Miss, hit_flags, Vec::new());
     }
 
-    let (accuracy_kind, defense) = {
+    let (accuracy_kind, defense, kind) = {
         let target_stats = &target.borrow().actor.stats;
         match attack.kind {
-            AttackKind::Fortitude { accuracy } => (accuracy, target_stats.fortitude),
-            AttackKind::Reflex { accuracy } => (accuracy, target_stats.reflex),
-            AttackKind::Will { accuracy } => (accuracy, target_stats.will),
-            AttackKind::Melee { .. } => (AccuracyKind::Melee, target_stats.defense),
-            AttackKind::Ranged { .. } => (AccuracyKind::Ranged, target_stats.defense),
+            AttackKind::Fortitude { accuracy } => (accuracy, target_stats.fortitude, "Fortitude"),
+            AttackKind::Reflex { accuracy } => (accuracy, target_stats.reflex, "Reflex"),
+            AttackKind::Will { accuracy } => (accuracy, target_stats.will, "Will"),
+            AttackKind::Melee { .. } => (AccuracyKind::Melee, target_stats.defense, "Melee"),
+            AttackKind::Ranged { .. } => (AccuracyKind::Ranged, target_stats.defense, "Ranged"),
             AttackKind::Dummy => {
                 return (HitKind::Hit, HitFlags::default(), Vec::new());
             }
@@ -206,6 +239,15 @@ fn attack_internal(
         attack.bonuses.spell_accuracy += rules.hidden_accuracy_bonus;
     }
 
+    let defense = Script::attack_roll(
+        parent,
+        target,
+        kind,
+        accuracy_kind,
+        defense,
+        &mut attack.bonuses,
+    );
+
     let hit_flags = HitFlags {
         flanking,
         sneak_attack,
@@ -214,8 +256,16 @@ fn attack_internal(
 
     let (hit_kind, damage_multiplier) = {
         let parent_stats = &parent.borrow().actor.stats;
-        let hit_kind =
+        let roll =
             parent_stats.attack_roll(accuracy_kind, crit_immunity, defense, &attack.bonuses);
+        let hit_kind = roll.hit_kind;
+        CombatLog::record_attack(
+            &parent.borrow(),
+            &target.borrow(),
+            Some(roll),
+            hit_kind,
+            hit_flags,
+        );
         let damage_multiplier = match hit_kind {
             HitKind::Miss => {
                 debug!("Miss");
@@ -0,0 +1,61 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer};
+
+/// A stable handle to an entity owned by the `TurnManager`.  The index is the
+/// entity's slot, which is preserved when the game is saved and loaded.  The
+/// generation is incremented each time the entity in a slot is removed, so a
+/// handle to an entity that is no longer present never resolves to the
+/// entity that later takes its place.
+#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EntityId {
+    pub index: usize,
+    pub generation: u32,
+}
+
+impl EntityId {
+    pub fn new(index: usize, generation: u32) -> EntityId {
+        EntityId { index, generation }
+    }
+}
+
+impl fmt::Display for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.index, self.generation)
+    }
+}
+
+// Saves from before entity generations were stored reference entities by
+// their bare index, which is always the first generation
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EntityIdSaveState {
+    Index(usize),
+    Id { index: usize, generation: u32 },
+}
+
+impl<'de> Deserialize<'de> for EntityId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<EntityId, D::Error> {
+        let id = match EntityIdSaveState::deserialize(deserializer)? {
+            EntityIdSaveState::Index(index) => EntityId::new(index, 0),
+            EntityIdSaveState::Id { index, generation } => EntityId::new(index, generation),
+        };
+        Ok(id)
+    }
+}
@@ -25,18 +25,20 @@ use sulis_core::config::Config;
 
 use crate::animation::{self, Anim};
 use crate::save_state::EntitySaveState;
-use crate::script::{self, CallbackData, ScriptEntitySet};
+use crate::script::{self, CallbackData, ScriptEntity, ScriptEntitySet};
 use crate::{
-    entity_attack_handler::weapon_attack, entity_texture_cache::Slot, is_within_attack_dist,
-    ActorState, AreaState, ChangeListenerList, EntityTextureCache, EntityTextureSlot, GameState,
-    Location, ScriptCallback, TurnManager,
+    entity_attack_handler::{self, weapon_attack}, entity_texture_cache::Slot, is_within_attack_dist,
+    AIBehavior, ActorState, AreaState, AutoPause, AutoPauseTrigger, ChangeListenerList, CombatLog,
+    EntityId, EntityTextureCache, EntityTextureSlot, GameState, Location, ScriptCallback,
+    TurnManager,
 };
 use sulis_core::io::GraphicsRenderer;
 use sulis_core::ui::{color, Color};
 use sulis_core::util::{invalid_data_error, Offset, Scale, Size, Point};
 use sulis_module::area::MAX_AREA_SIZE;
 use sulis_module::{
-    actor::Faction, ai, Actor, DamageKind, HitKind, Module, ObjectSize, ObjectSizeIterator,
+    actor::Faction, ai, AITemplate, Actor, EntityScriptHook, Facing, DamageKind, HitKind, Module,
+    ObjectSize, ObjectSizeIterator,
 };
 
 enum AIState {
@@ -56,15 +58,25 @@ pub struct EntityState {
 
     ai_state: AIState,
     ai_callbacks: Option<Rc<CallbackData>>,
+    script_callbacks: Option<Rc<CallbackData>>,
     pub(crate) marked_for_removal: bool,
     texture_cache_slot: Option<EntityTextureSlot>,
 
     custom_flags: HashMap<String, String>,
 
     index: usize,      // index in vec of the owning manager
+    generation: u32,   // generation of the manager slot at index
     unique_id: String, // assigned when setting the index and persisted on save
 
     collapsed_groups: Vec<String>,
+    ai_behavior: AIBehavior,
+
+    facing: Facing,
+    ai_override: Option<Rc<AITemplate>>,
+    patrol_route: Vec<Point>,
+    avoid_hazards: Option<bool>,
+    party_group: usize,
+    tint: Color,
 }
 
 impl PartialEq for EntityState {
@@ -108,12 +120,22 @@ impl EntityState {
 
         let actor = ActorState::load(save.actor, save.actor_base)?;
 
+        let ai_override = match save.ai_template {
+            None => None,
+            Some(id) => match Module::ai_template(&id) {
+                None => invalid_data_error(&format!("Invalid AI template '{id}' for entity")),
+                Some(ai) => Ok(Some(ai)),
+            }?,
+        };
+
         Ok(EntityState {
             actor,
             ai_callbacks: None,
+            script_callbacks: None,
             location,
             size,
             index: save.index,
+            generation: save.generation,
             unique_id: save.unique_id,
             sub_pos: (0.0, 0.0),
             color: color::WHITE,
@@ -125,6 +147,13 @@ impl EntityState {
             texture_cache_slot: None,
             custom_flags: save.custom_flags,
             collapsed_groups: save.collapsed_groups,
+            ai_behavior: save.ai_behavior,
+            facing: save.facing,
+            ai_override,
+            patrol_route: save.patrol_route,
+            avoid_hazards: save.avoid_hazards,
+            party_group: save.party_group,
+            tint: save.tint.unwrap_or(color::WHITE),
         })
     }
 
@@ -159,6 +188,7 @@ impl EntityState {
         EntityState {
             actor: actor_state,
             ai_callbacks: None,
+            script_callbacks: None,
             location,
             sub_pos: (0.0, 0.0),
             color: color::WHITE,
@@ -166,6 +196,7 @@ impl EntityState {
             scale: 1.0,
             size,
             index: usize::MAX,
+            generation: 0,
             unique_id,
             listeners: ChangeListenerList::default(),
             marked_for_removal: false,
@@ -173,9 +204,108 @@ impl EntityState {
             texture_cache_slot: None,
             custom_flags: HashMap::new(),
             collapsed_groups: Vec::new(),
+            ai_behavior: AIBehavior::default(),
+            facing: Facing::default(),
+            ai_override: None,
+            patrol_route: Vec::new(),
+            avoid_hazards: None,
+            party_group: 0,
+            tint: color::WHITE,
         }
     }
 
+    /// The persistent tint of this entity, multiplied with its animated color
+    /// when drawing.  White unless set by a script
+    pub fn tint(&self) -> Color {
+        self.tint
+    }
+
+    pub fn set_tint(&mut self, tint: Color) {
+        self.tint = tint;
+    }
+
+    pub fn facing(&self) -> Facing {
+        self.facing
+    }
+
+    pub fn set_facing(&mut self, facing: Facing) {
+        self.facing = facing;
+    }
+
+    /// The points set for this entity to patrol between in its area placement
+    pub fn patrol_route(&self) -> &[Point] {
+        &self.patrol_route
+    }
+
+    pub(crate) fn set_patrol_route(&mut self, route: Vec<Point>) {
+        self.patrol_route = route;
+    }
+
+    /// Whether pathing for this entity routes around hazard surfaces.  Unless
+    /// set by a script, only party members avoid hazards, and only when the
+    /// player has `avoid_hazards` enabled
+    pub fn avoids_hazards(&self) -> bool {
+        self.avoid_hazards
+            .unwrap_or_else(|| self.is_party_member() && Config::avoid_hazards())
+    }
+
+    pub(crate) fn avoid_hazards_override(&self) -> Option<bool> {
+        self.avoid_hazards
+    }
+
+    pub(crate) fn set_avoid_hazards(&mut self, avoid: bool) {
+        self.avoid_hazards = Some(avoid);
+    }
+
+    /// The group this entity belongs to while the party is split.  All party
+    /// members are in group zero when the party is together
+    pub fn party_group(&self) -> usize {
+        self.party_group
+    }
+
+    pub(crate) fn set_party_group(&mut self, group: usize) {
+        self.party_group = group;
+    }
+
+    /// Overrides the actor's AI template for this entity.  This must be set
+    /// before the entity is added to an area, as its AI hooks are registered then
+    pub(crate) fn set_ai_override(&mut self, ai: Rc<AITemplate>) {
+        self.ai_override = Some(ai);
+    }
+
+    pub(crate) fn ai_override_id(&self) -> Option<String> {
+        self.ai_override.as_ref().map(|ai| ai.id.to_string())
+    }
+
+    /// Returns the AI template set for this entity or its actor, ignoring
+    /// the rules `party_ai`
+    pub(crate) fn own_ai_template(&self) -> Option<&Rc<AITemplate>> {
+        self.ai_override.as_ref().or(self.actor.actor.ai.as_ref())
+    }
+
+    pub fn ai_behavior(&self) -> AIBehavior {
+        self.ai_behavior
+    }
+
+    pub fn set_ai_behavior(&mut self, behavior: AIBehavior) {
+        self.ai_behavior = behavior;
+    }
+
+    /// Returns the AI template used to play this entity.  Party members without
+    /// their own AI use the rules `party_ai` during assisted combat
+    pub fn ai_template(&self) -> Option<Rc<AITemplate>> {
+        if let Some(ai) = self.own_ai_template() {
+            return Some(Rc::clone(ai));
+        }
+
+        if !self.is_party_member() {
+            return None;
+        }
+
+        let rules = Module::rules();
+        rules.party_ai.as_ref().and_then(|id| Module::ai_template(id))
+    }
+
     pub fn add_collapsed_group(&mut self, group: String) {
         self.collapsed_groups.push(group);
     }
@@ -196,10 +326,15 @@ impl EntityState {
         self.index
     }
 
-    pub fn set_index(&mut self, index: usize) {
-        self.index = index;
-        if let Some(ref ai) = self.actor.actor.ai {
-            let mut cbs = CallbackData::new_entity(self.index);
+    pub fn id(&self) -> EntityId {
+        EntityId::new(self.index, self.generation)
+    }
+
+    pub fn set_id(&mut self, id: EntityId) {
+        self.index = id.index;
+        self.generation = id.generation;
+        if let Some(ai) = self.own_ai_template() {
+            let mut cbs = CallbackData::new_entity(self.id());
             for (kind, func) in ai.hooks.iter() {
                 let func = func.to_string();
                 match kind {
@@ -220,8 +355,24 @@ impl EntityState {
             self.ai_callbacks = Some(Rc::new(cbs));
         }
 
+        if let Some(script) = &self.actor.actor.script {
+            let mut cbs = CallbackData::new_trigger(self.id(), script.id.to_string());
+            for (hook, func) in script.hooks.iter() {
+                let func = func.to_string();
+                match hook {
+                    EntityScriptHook::OnSpawn => cbs.add_func(script::FuncKind::OnSpawn, func),
+                    EntityScriptHook::OnDamaged => cbs.add_func(script::FuncKind::OnDamaged, func),
+                    EntityScriptHook::OnDeath => cbs.add_func(script::FuncKind::OnDeath, func),
+                    EntityScriptHook::OnPlayerApproach => {
+                        cbs.add_func(script::FuncKind::OnPlayerApproach, func)
+                    }
+                }
+            }
+            self.script_callbacks = Some(Rc::new(cbs));
+        }
+
         if self.unique_id.is_empty() {
-            self.unique_id = format!("__uid__{}{}", self.actor.actor.id, index);
+            self.unique_id = format!("__uid__{}{}", self.actor.actor.id, self.index);
         }
     }
 
@@ -229,6 +380,11 @@ impl EntityState {
         self.ai_callbacks.clone()
     }
 
+    /// The callbacks for this entity's actor `script`, if it has one
+    pub fn script_callbacks(&self) -> Option<Rc<CallbackData>> {
+        self.script_callbacks.clone()
+    }
+
     pub fn callbacks(&self, mgr: &TurnManager) -> Vec<Rc<CallbackData>> {
         let mut result: Vec<_> = self
             .actor
@@ -246,6 +402,10 @@ impl EntityState {
             result.push(Rc::clone(cb));
         }
 
+        if let Some(ref cb) = self.script_callbacks {
+            result.push(Rc::clone(cb));
+        }
+
         result
     }
 
@@ -480,6 +640,16 @@ impl EntityState {
         entity.borrow().explore_self_location();
     }
 
+    /// Attacks the destructible prop at `index` in this entity's area
+    pub fn attack_prop(entity: &Rc<RefCell<EntityState>>, index: usize, remove_ap: bool) {
+        entity_attack_handler::prop_attack(entity, index);
+
+        if remove_ap {
+            let attack_ap = entity.borrow().actor.stats.attack_cost;
+            entity.borrow_mut().actor.remove_ap(attack_ap as u32);
+        }
+    }
+
     pub fn add_xp(&mut self, xp: u32) {
         self.actor.add_xp(xp);
     }
@@ -491,7 +661,17 @@ impl EntityState {
         damage: Vec<(DamageKind, u32)>,
     ) {
         let hp_amount = damage.iter().map(|(_, amount)| amount).sum();
+        let hp_before = entity.borrow().actor.hp();
         entity.borrow_mut().actor.remove_hp(hp_amount);
+        {
+            let entity = entity.borrow();
+            let threshold = entity.actor.stats.max_hp / 4;
+            if entity.is_party_member() && hp_before >= threshold && entity.actor.hp() < threshold
+            {
+                AutoPause::trigger(AutoPauseTrigger::LowHealth);
+            }
+        }
+        CombatLog::record_damage(&attacker.borrow(), &entity.borrow(), hit_kind, &damage);
 
         let targets = ScriptEntitySet::from_pair(entity, attacker);
 
@@ -503,6 +683,10 @@ impl EntityState {
 
         let hp = entity.borrow().actor.hp();
         if hp <= 0 {
+            CombatLog::record_death(&entity.borrow());
+            if hp_before > 0 {
+                cbs.iter().for_each(|cb| cb.on_death(ScriptEntity::from(attacker)));
+            }
             debug!(
                 "Entity '{}' has zero hit points.  Playing death animation",
                 entity.borrow().actor.actor.name
@@ -514,7 +698,9 @@ impl EntityState {
         }
     }
 
-    pub fn move_to(&mut self, x: i32, y: i32, squares: u32) -> bool {
+    /// Moves this entity to the specified coordinates, using AP for the number
+    /// of `squares` moved if in combat.  Swimming uses additional AP
+    pub fn move_to(&mut self, x: i32, y: i32, squares: u32, swimming: bool) -> bool {
         trace!("Move to {},{}", x, y);
         if !self.location.coords_valid(x, y) {
             return false;
@@ -532,7 +718,10 @@ impl EntityState {
 
         let mgr = GameState::turn_manager();
         if mgr.borrow().is_combat_active() && squares > 0 {
-            let ap_cost = self.actor.get_move_ap_cost(squares);
+            let mut ap_cost = self.actor.get_move_ap_cost(squares);
+            if swimming {
+                ap_cost += Module::rules().swimming.movement_ap * squares;
+            }
             if self.actor.ap() < ap_cost {
                 return false;
             }
@@ -567,8 +756,12 @@ impl EntityState {
         scale: Scale,
         alpha: f32,
     ) {
-        let a = self.color.a * alpha;
-        let color = Color::new(self.color.r, self.color.g, self.color.b, a);
+        let color = Color::new(
+            self.color.r * self.tint.r,
+            self.color.g * self.tint.g,
+            self.color.b * self.tint.b,
+            self.color.a * self.tint.a * alpha,
+        );
         if let Some(ref slot) = self.texture_cache_slot {
             let slot_loc = Slot {
                 x: offset.x,
@@ -616,11 +809,17 @@ impl AreaDrawable for EntityState {
         renderer: &mut dyn GraphicsRenderer,
         texture_cache: &mut EntityTextureCache,
     ) {
+        self.actor.mark_image_used();
         if self.texture_cache_slot.is_none() {
             self.texture_cache_slot = Some(texture_cache.add_entity(self, renderer));
             self.actor.check_texture_cache_invalid();
         }
 
+        let mirrored = self.facing.is_mirrored();
+        if let Some(slot) = self.texture_cache_slot.as_mut() {
+            slot.set_mirrored(mirrored);
+        }
+
         if self.actor.check_texture_cache_invalid() {
             let slot = &self.texture_cache_slot.as_ref().unwrap();
             slot.redraw_entity(self, renderer);
@@ -647,14 +846,15 @@ impl AreaDrawable for EntityState {
 
         let offset_x = (self.scale - 1.0) * self.size.width as f32 / 2.0;
         let offset_y = (self.scale - 1.0) * self.size.height as f32 / 2.0;
+        let grid_y = Module::rules().grid_y_offset(self.location.x);
         let x = x + self.location.x as f32 + self.sub_pos.0;
-        let y = y + self.location.y as f32 + self.sub_pos.1;
+        let y = y + self.location.y as f32 + self.sub_pos.1 + grid_y;
 
         let color = Color::new(
-            self.color.r * color.r,
-            self.color.g * color.g,
-            self.color.b * color.b,
-            self.color.a * color.a,
+            self.color.r * self.tint.r * color.r,
+            self.color.g * self.tint.g * color.g,
+            self.color.b * self.tint.b * color.b,
+            self.color.a * self.tint.a * color.a,
         );
         let offset = Offset {
             x: offset_x,
@@ -41,9 +41,15 @@ pub struct EntityTextureSlot {
     tex_coords: [f32; 8],
     slots_dim: usize,
     slot_size: u32,
+    mirrored: bool,
 }
 
 impl EntityTextureSlot {
+    /// Sets whether the entity image is drawn mirrored horizontally
+    pub fn set_mirrored(&mut self, mirrored: bool) {
+        self.mirrored = mirrored;
+    }
+
     pub fn redraw_entity(&self, entity: &EntityState, renderer: &mut dyn GraphicsRenderer) {
         let scale = self.slot_size as i32;
         renderer.clear_texture_region(
@@ -86,7 +92,14 @@ impl EntityTextureSlot {
             h: self.h as f32 + offset.y * 2.0,
         };
 
-        let mut list = DrawList::from_texture_id(self.texture_id, &self.tex_coords, rect);
+        let tex_coords = if self.mirrored {
+            let c = &self.tex_coords;
+            [c[4], c[5], c[6], c[7], c[0], c[1], c[2], c[3]]
+        } else {
+            self.tex_coords
+        };
+
+        let mut list = DrawList::from_texture_id(self.texture_id, &tex_coords, rect);
 
         list.set_scale(scale);
         list.set_color(color);
@@ -198,6 +211,7 @@ impl EntityTextureCache {
                     texture_id: self.texture_id,
                     slots_dim: self.slots_dim,
                     slot_size: self.slot_size,
+                    mirrored: false,
                 });
                 return self.entity_slots.len() - 1;
             }
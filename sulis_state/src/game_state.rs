@@ -19,21 +19,27 @@ use std::collections::HashMap;
 use std::io::{Error, ErrorKind};
 use std::rc::Rc;
 
-use sulis_core::config::Config;
+use sulis_core::config::{Config, Difficulty};
 use sulis_core::io::GraphicsRenderer;
+use sulis_core::ui::Narrator;
 use sulis_core::util::{invalid_data_error, ExtInt, Offset, Point, Scale};
-use sulis_module::on_trigger::QuestEntryState;
+use sulis_module::on_trigger::{EpilogueSlide, QuestEntryState, ScriptData};
 use sulis_module::{
-    area::{Destination, PathFinder, Trigger, TriggerKind},
-    Actor, ItemState, Module, OnTrigger, Time, MOVE_TO_THRESHOLD,
+    area::{Destination, PathFinder, TraversalKind, Trigger, TriggerKind},
+    Ability, Actor, ItemState, Module, OnTrigger, Rules, Time, MOVE_TO_THRESHOLD,
 };
 
 use crate::animation::{particle_generator::Param, Anim, AnimSaveState, AnimState};
-use crate::script::{script_cache, script_callback, Script, ScriptCallback, ScriptEntity};
+use crate::area_state::AreaScriptFunc;
+use crate::script::{
+    script_cache, script_callback, Script, ScriptCallback, ScriptEntity, ScriptEntitySet,
+};
 use crate::{
-    path_finder, transition_handler, AreaState, ChangeListener, ChangeListenerList, Effect,
-    EntityState, Formation, ItemList, Location, PartyStash, QuestStateSet, SaveState, TurnManager,
-    UICallback, WorldMapState, AI,
+    crime_state, durability, housing_state, is_within, path_finder, supplies, transition_handler,
+    AreaState, AutoPause, BanterState, ChangeListener, ChangeListenerList, CrimeState, DebugTime,
+    Effect, EntityState, Formation, GameMessage, HousingState, ItemList, Location, MapPin,
+    MapPinState, MessageLog, PartyStash, Presence, QuestStateSet, RecordsState, SaveState,
+    TurnManager, UICallback, WeatherState, WorldEventState, WorldExport, WorldMapState, AI,
 };
 
 thread_local! {
@@ -41,17 +47,37 @@ thread_local! {
     static STATE: RefCell<Option<GameState>> = RefCell::new(None);
     static AI: RefCell<AI> = RefCell::new(AI::new());
     static CLEAR_ANIMS: Cell<bool> = Cell::new(false);
+    static STOP_PARTY_MOVES: Cell<bool> = const { Cell::new(false) };
     static MODAL_LOCKED: Cell<bool> = Cell::new(false);
     static ANIMATIONS: RefCell<AnimState> = RefCell::new(AnimState::new());
     static ANIMS_TO_ADD: RefCell<Vec<Anim>> = RefCell::new(Vec::new());
     static COMBAT_INACTIVE_TIME: Cell<u32> = Cell::new(0);
+    static MESSAGE_LOG: Rc<RefCell<MessageLog>> = Rc::new(RefCell::new(MessageLog::default()));
+    static DIFFICULTY: Cell<Difficulty> = Cell::new(Difficulty::default());
+    static IMAGE_RELEASE_TIMER: Cell<u32> = const { Cell::new(0) };
 }
 
+/// How often entities are checked for composed images that can be released
+const IMAGE_RELEASE_INTERVAL: u32 = 2000;
+
 pub struct GameState {
     areas: HashMap<String, Rc<RefCell<AreaState>>>,
     area_state: Rc<RefCell<AreaState>>,
     world_map: WorldMapState,
     quests: QuestStateSet,
+    records: RecordsState,
+    banters: BanterState,
+    approval: HashMap<String, i32>,
+    weather: WeatherState,
+    thefts: HashMap<String, u32>,
+    crime: CrimeState,
+    housing: HousingState,
+    world_events: WorldEventState,
+    map_pins: MapPinState,
+    area_swaps: HashMap<String, String>,
+    world_import: Option<WorldExport>,
+    assisted_combat: bool,
+    epilogue: Vec<EpilogueSlide>,
     selected: Vec<Rc<RefCell<EntityState>>>,
     user_zoom: f32,
     party: Vec<Rc<RefCell<EntityState>>>,
@@ -72,13 +98,22 @@ const MIN_ZOOM: f32 = 0.7;
 const MAX_ZOOM: f32 = 2.0;
 
 impl GameState {
-    pub fn load(save_state: SaveState) -> Result<(), Error> {
+    pub fn load(mut save_state: SaveState) -> Result<(), Error> {
+        GameState::load_rules(save_state.rules.take());
+        let difficulty = save_state.difficulty.unwrap_or_else(Config::difficulty);
+        DIFFICULTY.with(|d| d.set(difficulty));
+
         TURN_MANAGER.with(|mgr| {
-            mgr.borrow_mut().load(save_state.total_elapsed_millis);
+            let mut mgr = mgr.borrow_mut();
+            mgr.load(save_state.total_elapsed_millis);
+            mgr.load_entity_generations(save_state.manager.entity_generations.clone());
         });
         ANIMATIONS.with(|anims| anims.borrow_mut().clear());
+        MESSAGE_LOG.with(|log| log.borrow_mut().clear());
         STATE.with(|state| *state.borrow_mut() = None);
         CLEAR_ANIMS.with(|c| c.set(false));
+        AutoPause::resume();
+        STOP_PARTY_MOVES.with(|c| c.set(false));
         MODAL_LOCKED.with(|c| c.set(false));
         ANIMS_TO_ADD.with(|anims| anims.borrow_mut().clear());
         AI.with(|ai| *ai.borrow_mut() = AI::new());
@@ -102,7 +137,8 @@ impl GameState {
 
             let width = area_state.borrow().area.area.width;
             let height = area_state.borrow().area.area.height;
-            let path_finder = PathFinder::new(width, height);
+            let mut path_finder = PathFinder::new(width, height);
+            path_finder.set_hex_grid(Module::rules().is_hex_grid());
 
             let mut entities = HashMap::new();
             let mut selected = Vec::new();
@@ -130,7 +166,12 @@ impl GameState {
                 }
             }
 
-            for entity in entities.values() {
+            // load in index order so the turn order is deterministic
+            let mut indices: Vec<usize> = entities.keys().copied().collect();
+            indices.sort_unstable();
+            for index in indices {
+                let entity = &entities[&index];
+                let id = entity.borrow().id();
                 let area_state = match areas.get(&entity.borrow().location.area_id) {
                     Some(state) => state,
                     None => unreachable!(),
@@ -140,7 +181,7 @@ impl GameState {
                 let location = entity.borrow().location.clone();
                 area_state
                     .borrow_mut()
-                    .load_entity(entity, location, is_dead)?;
+                    .load_entity(entity, Some(id), location, is_dead)?;
             }
 
             let mut effects = HashMap::new();
@@ -218,12 +259,27 @@ impl GameState {
                 mgr.borrow_mut().add_removal_listener_for_effect(index, vec);
             }
 
+            let mut triggered_cbs = Vec::new();
+            for cb in save_state.manager.triggered_callbacks {
+                triggered_cbs.push(cb.load(&entities, &effects)?);
+            }
+            mgr.borrow_mut().load_triggered_cbs(triggered_cbs);
+
+            if save_state.manager.combat_active {
+                mgr.borrow_mut().load_combat(
+                    save_state.manager.order,
+                    save_state.manager.delayed_turns,
+                    save_state.manager.engaged_groups,
+                    &effects,
+                );
+            }
+
             let formation = save_state.formation;
 
             let party_coins = save_state.coins;
 
             let mut stash = ItemList::default();
-            for item_save in save_state.stash {
+            for item_save in save_state.stash.into_iter().chain(save_state.keys) {
                 let item = &item_save.item;
                 let item = match Module::create_get_item(&item.id, &item.adjectives) {
                     None => invalid_data_error(&format!("No item with ID '{}'", item_save.item.id)),
@@ -236,6 +292,7 @@ impl GameState {
             }
 
             let quests = QuestStateSet::load(save_state.quests);
+            let records = RecordsState::load(save_state.records);
             let mut world_map = save_state.world_map;
             world_map.load();
 
@@ -258,6 +315,19 @@ impl GameState {
                 ui_callbacks: Vec::new(),
                 world_map,
                 quests,
+                records,
+                banters: save_state.banters,
+                approval: save_state.approval,
+                weather: save_state.weather,
+                thefts: save_state.thefts,
+                crime: save_state.crime,
+                housing: save_state.housing,
+                world_events: save_state.world_events,
+                map_pins: save_state.map_pins,
+                area_swaps: save_state.area_swaps,
+                world_import: save_state.world_import,
+                assisted_combat: save_state.assisted_combat,
+                epilogue: save_state.epilogue,
             })
         };
 
@@ -271,6 +341,7 @@ impl GameState {
         let mut area_state = area_state.borrow_mut();
         area_state.update_view_visibility();
         area_state.push_scroll_to_callback(pc);
+        area_state.fire_script(AreaScriptFunc::Load);
 
         Ok(())
     }
@@ -281,10 +352,15 @@ impl GameState {
         flags: HashMap<String, String>,
     ) -> Result<(), Error> {
         ANIMATIONS.with(|anims| anims.borrow_mut().clear());
+        MESSAGE_LOG.with(|log| log.borrow_mut().clear());
         CLEAR_ANIMS.with(|c| c.set(false));
+        AutoPause::resume();
+        STOP_PARTY_MOVES.with(|c| c.set(false));
         MODAL_LOCKED.with(|c| c.set(false));
         ANIMS_TO_ADD.with(|anims| anims.borrow_mut().clear());
         AI.with(|ai| *ai.borrow_mut() = AI::new());
+        Module::set_saved_rules(None);
+        DIFFICULTY.with(|d| d.set(Config::difficulty()));
 
         TURN_MANAGER.with(|mgr| {
             let rules = Module::rules();
@@ -306,6 +382,8 @@ impl GameState {
             .push_scroll_to_callback(Rc::clone(&pc));
         area_state.borrow_mut().on_load_fired = true;
         let area_state = area_state.borrow();
+        area_state.fire_script(AreaScriptFunc::Load);
+        area_state.fire_script(AreaScriptFunc::FirstEnter);
         GameState::add_ui_callbacks_of_kind(
             &area_state.area.area.triggers,
             TriggerKind::OnCampaignStart,
@@ -319,6 +397,18 @@ impl GameState {
             &pc,
         );
 
+        let new_game = &Module::campaign().new_game;
+        let mut intro = Vec::new();
+        if let Some(ref cutscene) = new_game.intro_cutscene {
+            intro.push(OnTrigger::ShowCutscene(cutscene.to_string()));
+        }
+        if let Some(ref script) = new_game.intro_script {
+            intro.push(OnTrigger::FireScript(script.clone()));
+        }
+        if !intro.is_empty() {
+            GameState::add_ui_callback(intro, &pc, &pc);
+        }
+
         let mgr = GameState::turn_manager();
         area_state.update_ambient_audio(&mgr.borrow().current_time());
         area_state.update_music(false, None);
@@ -331,13 +421,24 @@ impl GameState {
         party_actors: Vec<Rc<Actor>>,
         flags: HashMap<String, String>,
     ) -> Result<GameState, Error> {
-        let party_coins = pc.inventory.pc_starting_coins();
+        let campaign = Module::campaign();
+        let new_game = &campaign.new_game;
+
+        let party_coins = pc.inventory.pc_starting_coins() + new_game.coins;
         let mut party_stash = ItemList::default();
         for (qty, item) in pc.inventory.pc_starting_item_iter() {
             party_stash.add_quantity(qty, item);
         }
-
-        let campaign = Module::campaign();
+        for entry in &new_game.items {
+            let id = &entry.item.id;
+            match Module::create_get_item(id, &entry.item.adjectives) {
+                None => warn!("New game starting item '{}' not found", id),
+                Some(item) => {
+                    let item = ItemState::new(item, entry.item.variant);
+                    party_stash.add_quantity(entry.quantity, item);
+                }
+            }
+        }
 
         let area_state = GameState::setup_area_state(&campaign.starting_area)?;
 
@@ -399,6 +500,16 @@ impl GameState {
             party.push(member);
         }
 
+        let world_import = WorldExport::find(&new_game.import_from);
+        if let Some(ref import) = world_import {
+            info!("Importing world export from '{}'", import.campaign);
+            for (flag, value) in &import.flags {
+                if !flags.contains_key(flag) {
+                    pc_state.borrow_mut().set_custom_flag(flag, value);
+                }
+            }
+        }
+
         for (flag, value) in &flags {
             pc_state.borrow_mut().set_custom_flag(flag, value);
         }
@@ -406,7 +517,8 @@ impl GameState {
         let width = area_state.borrow().area.area.width;
         let height = area_state.borrow().area.area.height;
 
-        let path_finder = PathFinder::new(width, height);
+        let mut path_finder = PathFinder::new(width, height);
+        path_finder.set_hex_grid(Module::rules().is_hex_grid());
 
         let mut areas: HashMap<String, Rc<RefCell<AreaState>>> = HashMap::new();
         areas.insert(campaign.starting_area.to_string(), Rc::clone(&area_state));
@@ -428,9 +540,72 @@ impl GameState {
             ui_callbacks: Vec::new(),
             world_map: WorldMapState::new(),
             quests: QuestStateSet::default(),
+            records: RecordsState::default(),
+            banters: BanterState::default(),
+            approval: HashMap::new(),
+            weather: WeatherState::default(),
+            thefts: HashMap::new(),
+            crime: CrimeState::default(),
+            housing: HousingState::default(),
+            world_events: WorldEventState::default(),
+            map_pins: MapPinState::default(),
+            area_swaps: HashMap::new(),
+            world_import,
+            assisted_combat: false,
+            epilogue: Vec::new(),
         })
     }
 
+    fn load_rules(rules: Option<Rules>) {
+        let rules = rules.filter(|rules| match rules.validate() {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Invalid rules in save file, using the module rules instead");
+                warn!("{}", e);
+                false
+            }
+        });
+        Module::set_saved_rules(rules);
+    }
+
+    /// Returns the difficulty the current game is played at.  This is set from
+    /// the options when a game is started, and stored in its save files
+    pub fn difficulty() -> Difficulty {
+        DIFFICULTY.with(|d| d.get())
+    }
+
+    /// Returns true if the AI plays party members' combat turns, other than
+    /// the player character and members set to `manual_only`
+    pub fn is_assisted_combat() -> bool {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.assisted_combat
+        })
+    }
+
+    pub fn set_assisted_combat(assisted: bool) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.assisted_combat = assisted;
+        })
+    }
+
+    /// Returns true if the AI should play the combat turns of the specified
+    /// party member
+    pub fn is_ai_assisted(entity: &Rc<RefCell<EntityState>>) -> bool {
+        if !GameState::is_assisted_combat() || !GameState::is_combat_active() {
+            return false;
+        }
+
+        if Rc::ptr_eq(entity, &GameState::player()) {
+            return false;
+        }
+
+        !entity.borrow().ai_behavior().manual_only
+    }
+
     pub fn set_world_map_location_visible(location: &str, visible: bool) {
         STATE.with(|state| {
             let mut state = state.borrow_mut();
@@ -458,6 +633,42 @@ impl GameState {
         })
     }
 
+    pub fn map_pins() -> MapPinState {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.map_pins.clone()
+        })
+    }
+
+    /// Places the specified `pin` on the map, replacing any pin with the same ID
+    pub fn add_map_pin(pin: MapPin) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.map_pins.add(pin);
+        })
+    }
+
+    /// Adds a player note with the specified `text` at the given location in
+    /// the current area
+    pub fn add_map_note(x: i32, y: i32, text: &str) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            let area = state.area_state.borrow().area.area.id.to_string();
+            state.map_pins.add_note(&area, x, y, text);
+        })
+    }
+
+    pub fn remove_map_pin(id: &str) -> bool {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.map_pins.remove(id)
+        })
+    }
+
     pub fn quest_state() -> QuestStateSet {
         STATE.with(|state| {
             let state = state.borrow();
@@ -508,6 +719,414 @@ impl GameState {
         })
     }
 
+    pub fn records() -> RecordsState {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+
+            state.records.clone()
+        })
+    }
+
+    pub fn add_records_change_listener(listener: ChangeListener<RecordsState>) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+
+            state.records.listeners.add(listener);
+        })
+    }
+
+    pub fn unlock_lore(id: &str) -> bool {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.records.unlock_lore(id)
+        })
+    }
+
+    pub fn is_lore_unlocked(id: &str) -> bool {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.records.is_lore_unlocked(id)
+        })
+    }
+
+    pub fn banters() -> BanterState {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+
+            state.banters.clone()
+        })
+    }
+
+    /// Records that the specified banter has been played in the current round
+    pub fn set_banter_played(id: &str) {
+        let round = GameState::turn_manager().borrow().current_round();
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.banters.set_played(id, round);
+        })
+    }
+
+    pub fn weather() -> WeatherState {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.weather.clone()
+        })
+    }
+
+    /// Smoothly changes the weather to the weather with the specified `id`, or
+    /// clears it if `None`, over `duration_millis` of game time
+    pub fn transition_weather(id: Option<String>, intensity: f32, duration_millis: usize) {
+        let millis = GameState::turn_manager().borrow().total_elapsed_millis();
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.weather.transition(id, intensity, duration_millis, millis);
+        })
+    }
+
+    pub fn all_thefts() -> HashMap<String, u32> {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.thefts.clone()
+        })
+    }
+
+    /// Returns the number of thefts from the specified `owner` that have been
+    /// witnessed
+    pub fn witnessed_thefts(owner: &str) -> u32 {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.thefts.get(owner).copied().unwrap_or(0)
+        })
+    }
+
+    /// Called when `thief` takes items from a prop belonging to `owner`.  Any
+    /// living, non-hostile creatures in the area that can see the thief witness
+    /// the theft, which is then recorded against the owner.  The campaign
+    /// `on_theft_script`, if any, is run with the witnesses and the owner.
+    pub fn report_theft(thief: &Rc<RefCell<EntityState>>, owner: &str) {
+        let area_state = GameState::area_state();
+        let mgr = GameState::turn_manager();
+
+        let mut witnesses = Vec::new();
+        {
+            let area_state = area_state.borrow();
+            let thief = thief.borrow();
+            for index in area_state.entity_iter() {
+                let entity = mgr.borrow().entity(*index);
+                {
+                    let entity = entity.borrow();
+                    if entity.is_party_member() || entity.actor.is_dead() {
+                        continue;
+                    }
+
+                    if entity.is_hostile(&thief) || !area_state.has_visibility(&entity, &thief) {
+                        continue;
+                    }
+                }
+                witnesses.push(Some(entity));
+            }
+        }
+
+        info!("Theft from '{}' seen by {} witnesses", owner, witnesses.len());
+        if !witnesses.is_empty() {
+            STATE.with(|state| {
+                let mut state = state.borrow_mut();
+                let state = state.as_mut().unwrap();
+                *state.thefts.entry(owner.to_string()).or_insert(0) += 1;
+                let bounty = Module::rules().crime.theft_bounty;
+                state.crime.add_bounty(bounty as i32);
+            });
+        }
+
+        let script_data = match Module::campaign().on_theft_script {
+            None => return,
+            Some(ref data) => data.clone(),
+        };
+
+        let witnesses = ScriptEntitySet::new(thief, &witnesses);
+        Script::trigger(&script_data.id, &script_data.func, (witnesses, owner.to_string()));
+    }
+
+    pub fn crime() -> CrimeState {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.crime.clone()
+        })
+    }
+
+    /// Adds the specified `amount`, which may be negative, to the party's bounty
+    pub fn add_bounty(amount: i32) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.crime.add_bounty(amount);
+        })
+    }
+
+    /// Removes all held weapons and shields from every party member.  The
+    /// items are kept until returned with `return_confiscated_items`
+    pub fn disarm_party() {
+        let mut items = Vec::new();
+        for member in GameState::party() {
+            items.append(&mut member.borrow_mut().actor.disarm());
+        }
+
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            for item in items.iter() {
+                state.crime.confiscate(1, item);
+            }
+        });
+    }
+
+    /// Removes all equipped items from every party member, along with the
+    /// entire contents of the party stash.  The items are kept until returned
+    /// with `return_confiscated_items`
+    pub fn confiscate_party_items() {
+        let mut items = Vec::new();
+        for member in GameState::party() {
+            let unequipped = member.borrow_mut().actor.unequip_all();
+            items.extend(unequipped.into_iter().map(|item| (1, item)));
+        }
+        items.append(&mut GameState::party_stash().borrow_mut().remove_all());
+
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            for (qty, item) in items.iter() {
+                state.crime.confiscate(*qty, item);
+            }
+        });
+    }
+
+    pub fn world_events() -> WorldEventState {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.world_events.clone()
+        })
+    }
+
+    /// Prevents the campaign world event with the specified `id` from firing
+    /// again
+    pub fn cancel_world_event(id: &str) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.world_events.cancel(id);
+        })
+    }
+
+    fn fire_world_events() {
+        let millis = GameState::turn_manager().borrow().total_elapsed_millis();
+        let events = STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.world_events.take_due(millis, &state.quests)
+        });
+
+        for event in events {
+            info!("Firing world event '{}'", event.id);
+            Script::trigger(&event.script.id, &event.script.func, event.id);
+        }
+    }
+
+    /// Causes transitions to the area with the specified `id` to lead to the
+    /// `replacement` area instead, or restores the original area if `None`.
+    /// The state of each area is kept, so swapping back returns the party to
+    /// the area as they left it.
+    pub fn swap_area(id: &str, replacement: Option<String>) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            match replacement {
+                None => state.area_swaps.remove(id),
+                Some(replacement) => state.area_swaps.insert(id.to_string(), replacement),
+            };
+        })
+    }
+
+    /// Returns the ID of the area that transitions to the area with the
+    /// specified `id` lead to, taking any `swap_area` into account
+    pub fn swapped_area_id(id: &str) -> String {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            match state.area_swaps.get(id) {
+                None => id.to_string(),
+                Some(replacement) => replacement.to_string(),
+            }
+        })
+    }
+
+    pub(crate) fn all_area_swaps() -> HashMap<String, String> {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.area_swaps.clone()
+        })
+    }
+
+    /// Queues a slide to be shown in the epilogue at the end of the campaign
+    pub fn add_epilogue_slide(slide: EpilogueSlide) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.epilogue.push(slide);
+        })
+    }
+
+    /// Removes and returns all queued epilogue slides
+    pub fn take_epilogue() -> Vec<EpilogueSlide> {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            std::mem::take(&mut state.epilogue)
+        })
+    }
+
+    pub(crate) fn epilogue() -> Vec<EpilogueSlide> {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.epilogue.clone()
+        })
+    }
+
+    /// Returns the world export imported from an earlier campaign when this
+    /// game was started, if any
+    pub fn world_import() -> Option<WorldExport> {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.world_import.clone()
+        })
+    }
+
+    pub fn housing() -> HousingState {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.housing.clone()
+        })
+    }
+
+    /// Adds the campaign housing upgrade with the specified `id`, placing its
+    /// furnishings in the housing area.  Each upgrade may only be added once.
+    /// Returns true if the upgrade was added
+    pub fn add_housing_upgrade(id: &str) -> bool {
+        if GameState::housing().has_upgrade(id) {
+            return false;
+        }
+
+        if !housing_state::apply_upgrade(id) {
+            return false;
+        }
+
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.housing.add_upgrade(id);
+        });
+        true
+    }
+
+    /// Moves `entity` out of the party and into the campaign housing, where
+    /// they idle until added back to the party.  Returns false if the campaign
+    /// has no housing
+    pub fn send_to_housing(entity: &Rc<RefCell<EntityState>>) -> bool {
+        housing_state::send_to_housing(entity)
+    }
+
+    /// Places all items confiscated by `disarm_party` or `confiscate_party_items`
+    /// in the party stash
+    pub fn return_confiscated_items() {
+        let items = STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.crime.take_confiscated()
+        });
+
+        let stash = GameState::party_stash();
+        let mut stash = stash.borrow_mut();
+        for (qty, item) in items {
+            stash.add_item(qty, item);
+        }
+    }
+
+    pub fn all_approval() -> HashMap<String, i32> {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.approval.clone()
+        })
+    }
+
+    /// Returns the party's approval with the companion with the specified actor ID
+    pub fn approval(companion: &str) -> i32 {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+            state.approval.get(companion).copied().unwrap_or(0)
+        })
+    }
+
+    /// Adjusts the party's approval with the companion with the specified actor ID
+    /// by `amount`, granting any of the companion's approval abilities now reached
+    pub fn add_approval(companion: &str, amount: i32) {
+        let approval = STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            let approval = state.approval.entry(companion.to_string()).or_insert(0);
+            *approval += amount;
+            *approval
+        });
+
+        for entity in GameState::party() {
+            if entity.borrow().actor.actor.id == companion {
+                grant_approval_abilities(&entity, approval);
+            }
+        }
+    }
+
+    // records may be updated while the game state is still being
+    // constructed, so these silently do nothing in that case
+    pub(crate) fn record_creature_seen(actor_id: &str) {
+        STATE.with(|state| {
+            if let Some(state) = state.borrow_mut().as_mut() {
+                state.records.add_creature_seen(actor_id);
+            }
+        })
+    }
+
+    pub(crate) fn record_creature_kill(actor_id: &str) {
+        STATE.with(|state| {
+            if let Some(state) = state.borrow_mut().as_mut() {
+                state.records.add_creature_kill(actor_id);
+            }
+        })
+    }
+
+    pub(crate) fn record_item(item_id: &str) {
+        STATE.with(|state| {
+            if let Some(state) = state.borrow_mut().as_mut() {
+                state.records.add_item(item_id);
+            }
+        })
+    }
+
     pub fn set_user_zoom(mut zoom: f32) {
         STATE.with(|state| {
             let mut state = state.borrow_mut();
@@ -531,6 +1150,15 @@ impl GameState {
         TURN_MANAGER.with(Rc::clone)
     }
 
+    pub fn message_log() -> Rc<RefCell<MessageLog>> {
+        MESSAGE_LOG.with(Rc::clone)
+    }
+
+    /// Adds the `message` to the game message log shown in the status bar
+    pub fn add_message(message: GameMessage) {
+        MESSAGE_LOG.with(|log| log.borrow_mut().add(message));
+    }
+
     pub fn set_selected_party_member(entity: Rc<RefCell<EntityState>>) {
         GameState::select_party_members(vec![entity]);
     }
@@ -602,7 +1230,9 @@ impl GameState {
             let state = state.as_mut().unwrap();
 
             entity.borrow_mut().remove_from_party();
+            entity.borrow_mut().set_party_group(0);
             state.party.retain(|e| !Rc::ptr_eq(e, &entity));
+            renumber_party_groups(&state.party);
 
             state.selected.retain(|e| !Rc::ptr_eq(e, &entity));
 
@@ -711,9 +1341,50 @@ impl GameState {
         })
     }
 
+    /// Returns true if the specified party has been defeated, meaning the
+    /// player character has died or every party member is dead
+    pub fn is_defeated(party: &[Rc<RefCell<EntityState>>]) -> bool {
+        if party.is_empty() {
+            return true;
+        }
+
+        {
+            let player = &party[0].borrow().actor;
+            if player.is_dead() && !player.is_disabled() {
+                return true;
+            }
+        }
+
+        party.iter().all(|member| member.borrow().actor.is_dead())
+    }
+
+    /// Ends combat and revives every dead party member with one hit point,
+    /// then calls the campaign `on_party_defeated_script` with the player
+    fn recover_from_defeat(script: &ScriptData) {
+        info!("Party defeated; firing campaign defeat script");
+        GameState::turn_manager().borrow_mut().stop_combat();
+
+        for member in GameState::party() {
+            let mut member = member.borrow_mut();
+            if member.actor.is_dead() {
+                member.actor.set_disabled(true);
+            }
+        }
+        GameState::add_disabled_party_members();
+
+        let pc = GameState::player();
+        Script::trigger(&script.id, &script.func, ScriptEntity::from(&pc));
+    }
+
     pub fn handle_disabled_party_members() {
         let update = GameState::remove_disabled_party_members();
 
+        if update && GameState::is_defeated(&GameState::party()) {
+            if let Some(script) = &Module::campaign().on_party_defeated_script {
+                GameState::recover_from_defeat(script);
+            }
+        }
+
         if !GameState::is_combat_active() {
             GameState::add_disabled_party_members();
         }
@@ -747,6 +1418,10 @@ impl GameState {
             let state = state.as_mut().unwrap();
 
             entity.borrow_mut().add_to_party(show_portrait);
+            if let Some(player) = state.party.first() {
+                let group = player.borrow().party_group();
+                entity.borrow_mut().set_party_group(group);
+            }
             state
                 .area_state
                 .borrow_mut()
@@ -757,6 +1432,9 @@ impl GameState {
             state.party_listeners.notify(&entity);
         });
 
+        let approval = GameState::approval(&entity.borrow().actor.actor.id);
+        grant_approval_abilities(&entity, approval);
+
         let area_state = GameState::area_state();
         area_state.borrow_mut().update_view_visibility();
     }
@@ -796,10 +1474,160 @@ impl GameState {
         })
     }
 
+    /// Moves the specified party members into a new group, which may be moved
+    /// independently of the rest of the party.  When combat starts, only the
+    /// groups seen by hostiles take part.  Returns false if the party may not
+    /// be split this way, such as during combat or if no one would be left
+    pub fn split_party(members: &[Rc<RefCell<EntityState>>]) -> bool {
+        if members.is_empty() || GameState::is_combat_active() {
+            return false;
+        }
+
+        let party = GameState::party();
+        let remaining = party
+            .iter()
+            .filter(|e| !members.iter().any(|m| Rc::ptr_eq(e, m)))
+            .count();
+        if remaining == 0 {
+            return false;
+        }
+
+        let group = party.iter().map(|e| e.borrow().party_group()).max().unwrap_or(0) + 1;
+        for member in members {
+            if member.borrow().is_party_member() {
+                member.borrow_mut().set_party_group(group);
+            }
+        }
+        renumber_party_groups(&party);
+        GameState::select_party_members(GameState::selected());
+        true
+    }
+
+    /// Brings all party groups back together
+    pub fn rejoin_party() {
+        if !GameState::is_party_split() {
+            return;
+        }
+
+        for member in GameState::party() {
+            member.borrow_mut().set_party_group(0);
+        }
+        GameState::select_party_members(GameState::selected());
+    }
+
+    pub fn is_party_split() -> bool {
+        GameState::party().iter().any(|e| e.borrow().party_group() != 0)
+    }
+
+    pub fn party_group(group: usize) -> Vec<Rc<RefCell<EntityState>>> {
+        GameState::party()
+            .into_iter()
+            .filter(|e| e.borrow().party_group() == group)
+            .collect()
+    }
+
+    /// Selects the living members of the group after the one currently
+    /// selected, returning the first of them
+    pub fn select_next_party_group() -> Option<Rc<RefCell<EntityState>>> {
+        let party = GameState::party();
+        let num_groups = party.iter().map(|e| e.borrow().party_group()).max()? + 1;
+        let cur = GameState::selected().first().map_or(0, |e| e.borrow().party_group());
+
+        for offset in 1..=num_groups {
+            let members: Vec<_> = GameState::party_group((cur + offset) % num_groups)
+                .into_iter()
+                .filter(|e| !e.borrow().actor.is_dead())
+                .collect();
+            if let Some(first) = members.first().map(Rc::clone) {
+                GameState::select_party_members(members);
+                return Some(first);
+            }
+        }
+        None
+    }
+
     pub fn transition_to(area_id: Option<&str>, p: Option<Point>, offset: Point, time: Time) {
+        GameState::rejoin_party();
         transition_handler::transition_to(area_id, p, offset, time);
     }
 
+    /// Travels across the world map to the specified area, taking `hours` of game
+    /// time.  Party members eat rations for the journey if the rules use supplies
+    pub fn travel_to(area_id: &str, p: Point, hours: u32) {
+        supplies::consume_rations(supplies::travel_rations(hours));
+
+        let mut time = Time::from_hours(hours);
+        Module::rules().canonicalize_time(&mut time);
+        GameState::transition_to(Some(area_id), Some(p), Point::default(), time);
+    }
+
+    /// Restores all party members for a new day, as when the party rests.  Each
+    /// member first eats the rules `rations_per_rest`, if the rules use supplies
+    pub fn init_party_day() {
+        supplies::consume_rations(Module::rules().supplies.rations_per_rest);
+        durability::repair_on_rest();
+
+        for member in GameState::party() {
+            member.borrow_mut().actor.init_day();
+        }
+    }
+
+    /// Moves the `entity` from the `start` marker of the traversal point with
+    /// the specified index in the current area to its other marker, using the
+    /// traversal AP if in combat.  Returns false if the entity is not close
+    /// enough to `start`, cannot use the traversal, or the destination is blocked.
+    pub fn traverse(entity: &Rc<RefCell<EntityState>>, index: usize, start: Point) -> bool {
+        let area_state = GameState::area_state();
+        let traversal = match area_state.borrow().area.area.traversals.get(index) {
+            None => return false,
+            Some(traversal) => traversal.clone(),
+        };
+
+        let rules = Module::rules();
+        if !is_within(&*entity.borrow(), &start, rules.max_prop_distance) {
+            return false;
+        }
+
+        if !entity.borrow().actor.can_traverse(&traversal) {
+            return false;
+        }
+
+        let dest = traversal.other_end(start);
+        let (x, y) = {
+            let entity = entity.borrow();
+            (dest.x - (entity.size.width - 1) / 2, dest.y - (entity.size.height - 1) / 2)
+        };
+        let index = entity.borrow().index();
+        if !area_state.borrow().is_passable(&entity.borrow(), &[index], x, y) {
+            return false;
+        }
+
+        let old_pos = entity.borrow().location.to_point();
+        if !area_state.borrow_mut().move_entity(entity, x, y, 0) {
+            return false;
+        }
+
+        if GameState::is_combat_active() {
+            entity.borrow_mut().actor.remove_ap(rules.traversal_ap.ap(traversal.kind));
+        }
+
+        // slide the entity visually from its old position to the new one
+        let (dx, dy) = ((x - old_pos.x) as f32, (y - old_pos.y) as f32);
+        let squares = dx.abs().max(dy.abs()).max(1.0) as u32;
+        let speed_factor = match traversal.kind {
+            TraversalKind::Climb => 3,
+            TraversalKind::Jump => 1,
+        };
+        let duration = Config::animation_base_time_millis() * squares * speed_factor;
+        let frac = 1000.0 / duration as f32;
+        let x = Param::with_speed(-dx, dx * frac);
+        let y = Param::with_speed(-dy, dy * frac);
+        entity.borrow_mut().sub_pos = (-dx, -dy);
+        let anim = Anim::new_entity_subpos(entity, ExtInt::Int(duration), x, y);
+        GameState::add_animation(anim);
+        true
+    }
+
     pub(crate) fn preload_area(area_id: &str) -> Result<(), Error> {
         if GameState::get_area_state(area_id).is_some() {
             return Ok(());
@@ -828,9 +1656,11 @@ impl GameState {
 
             let width = area.borrow().area.area.width;
             let height = area.borrow().area.area.height;
-            let path_finder = PathFinder::new(width, height);
+            let mut path_finder = PathFinder::new(width, height);
+            path_finder.set_hex_grid(Module::rules().is_hex_grid());
             state.path_finder = path_finder;
             state.area_state = Rc::clone(area);
+            Narrator::say(&format!("Entered {}", area.borrow().area.area.name));
             true
         })
     }
@@ -911,6 +1741,19 @@ impl GameState {
         CLEAR_ANIMS.with(|c| c.set(true));
     }
 
+    /// Stops any in progress moves of party members, with each stopping at its
+    /// current position.  Only the AP for the tiles already moved is used.
+    /// Returns true if any moves were stopped.
+    pub fn stop_party_moves() -> bool {
+        ANIMATIONS.with(|a| a.borrow_mut().stop_party_moves())
+    }
+
+    /// Stops party moves on the next update, for use while animations are
+    /// being updated
+    pub(crate) fn set_stop_party_moves() {
+        STOP_PARTY_MOVES.with(|c| c.set(true));
+    }
+
     pub fn area_state_ids() -> Vec<String> {
         STATE.with(|s| {
             s.borrow()
@@ -939,6 +1782,15 @@ impl GameState {
             state.ui_callbacks.pop()
         });
 
+        if AutoPause::is_paused() {
+            return ui_cb;
+        }
+
+        let millis = match DebugTime::scale_millis(millis) {
+            None => return ui_cb,
+            Some(millis) => millis,
+        };
+
         let to_add: Vec<Anim> = ANIMS_TO_ADD.with(|a| {
             let mut anims = a.borrow_mut();
             anims.drain(0..).collect()
@@ -960,6 +1812,14 @@ impl GameState {
         let cbs = mgr.borrow_mut().update_entity_move_callbacks();
         script_callback::fire_on_moved(cbs);
 
+        let release_timer = IMAGE_RELEASE_TIMER.with(|t| t.get()) + millis;
+        if release_timer > IMAGE_RELEASE_INTERVAL {
+            mgr.borrow().release_unused_images();
+            IMAGE_RELEASE_TIMER.with(|t| t.set(0));
+        } else {
+            IMAGE_RELEASE_TIMER.with(|t| t.set(release_timer));
+        }
+
         {
             let area_state = GameState::area_state();
             let mut area_state = area_state.borrow_mut();
@@ -970,6 +1830,10 @@ impl GameState {
             ANIMATIONS.with(|a| a.borrow_mut().clear_all_blocking_anims());
         }
 
+        if STOP_PARTY_MOVES.with(|c| c.replace(false)) {
+            GameState::stop_party_moves();
+        }
+
         let current = mgr.borrow().current();
         if let Some(entity) = current.as_ref() {
             AI.with(|ai| {
@@ -1006,6 +1870,8 @@ impl GameState {
         COMBAT_INACTIVE_TIME.with(|c| c.set(inactive_time));
 
         GameState::handle_disabled_party_members();
+        crime_state::update_guards();
+        GameState::fire_world_events();
 
         let campaign = Module::campaign();
         if let Some(script_data) = &campaign.on_tick_script {
@@ -1015,9 +1881,16 @@ impl GameState {
             script_cache::set_report_enabled(true);
         }
 
+        Presence::update(millis);
+        DebugTime::check_step();
+
         ui_cb
     }
 
+    pub fn move_paths() -> Vec<(Rc<RefCell<EntityState>>, Vec<Point>)> {
+        ANIMATIONS.with(|a| a.borrow().move_paths())
+    }
+
     pub fn draw_above_entities(
         renderer: &mut dyn GraphicsRenderer,
         offset: Offset,
@@ -1054,6 +1927,17 @@ impl GameState {
         ANIMATIONS.with(|a| a.borrow().has_blocking_anims(entity))
     }
 
+    /// Returns true if the entity has blocking animations, including any that
+    /// have been added but not yet started
+    pub fn has_blocking_or_pending_animations(entity: &Rc<RefCell<EntityState>>) -> bool {
+        let pending = ANIMS_TO_ADD.with(|a| {
+            a.borrow()
+                .iter()
+                .any(|anim| anim.is_blocking() && Rc::ptr_eq(anim.owner(), entity))
+        });
+        pending || GameState::has_blocking_animations(entity)
+    }
+
     pub fn remove_blocking_animations(entity: &Rc<RefCell<EntityState>>) {
         ANIMATIONS.with(|a| a.borrow_mut().clear_blocking_anims(entity));
     }
@@ -1074,6 +1958,18 @@ impl GameState {
         ANIMATIONS.with(|a| a.borrow().save_anims())
     }
 
+    /// Returns true if the game may currently be saved.  During combat, saves
+    /// are only possible on a party member's turn, once any in progress attack
+    /// and movement animations have completed.  Remaining animations that cannot
+    /// be saved, such as waits, are dropped from the save.
+    pub fn can_save() -> bool {
+        if !GameState::is_combat_active() {
+            return true;
+        }
+
+        GameState::is_pc_current() && !GameState::has_any_blocking_animations()
+    }
+
     /// Returns true if the game is currently in turn mode, false otherwise
     pub fn is_combat_active() -> bool {
         let mgr = GameState::turn_manager();
@@ -1081,6 +1977,15 @@ impl GameState {
         mgr.is_combat_active()
     }
 
+    /// Returns true if the party may leave the current area, either because
+    /// combat is not active or because the party is far enough from hostiles
+    /// to flee
+    pub fn can_use_transitions() -> bool {
+        let mgr = GameState::turn_manager();
+        let mgr = mgr.borrow();
+        !mgr.is_combat_active() || mgr.can_flee()
+    }
+
     /// Returns true if the PC has the current turn, false otherwise
     pub fn is_pc_current() -> bool {
         let mgr = GameState::turn_manager();
@@ -1228,3 +2133,57 @@ impl GameState {
         })
     }
 }
+
+fn grant_approval_abilities(entity: &Rc<RefCell<EntityState>>, approval: i32) {
+    let to_add: Vec<Rc<Ability>> = {
+        let actor = &entity.borrow().actor.actor;
+        actor
+            .approval_abilities
+            .iter()
+            .filter(|entry| approval >= entry.approval)
+            .filter(|entry| {
+                !actor
+                    .abilities
+                    .iter()
+                    .any(|owned| owned.ability.id == entry.ability)
+            })
+            .filter_map(|entry| Module::ability(&entry.ability))
+            .collect()
+    };
+
+    if to_add.is_empty() {
+        return;
+    }
+
+    let actor = {
+        let state = &entity.borrow().actor;
+        Actor::from(
+            &state.actor,
+            None,
+            state.xp(),
+            to_add,
+            Vec::new(),
+            state.actor.inventory.clone(),
+        )
+    };
+
+    entity.borrow_mut().actor.replace_actor(actor);
+}
+
+/// Renumbers the party groups in order of their first member, so the group
+/// containing the player is always zero
+fn renumber_party_groups(party: &[Rc<RefCell<EntityState>>]) {
+    let mut order: Vec<usize> = Vec::new();
+    for member in party {
+        let group = member.borrow().party_group();
+        if !order.contains(&group) {
+            order.push(group);
+        }
+    }
+
+    for member in party {
+        let group = member.borrow().party_group();
+        let new_group = order.iter().position(|g| *g == group).unwrap_or(0);
+        member.borrow_mut().set_party_group(new_group);
+    }
+}
@@ -19,7 +19,7 @@ use std::io::{Error, ErrorKind};
 use std::rc::Rc;
 
 use sulis_core::resource::ResourceSet;
-use sulis_core::util::{self, unable_to_create_error, ReproducibleRandom};
+use sulis_core::util::{self, unable_to_create_error, Point, ReproducibleRandom};
 use sulis_module::area::{
     create_prop, Area, EncounterData, LayerSet, PathFinderGrid, PropData, Tile, Transition,
     TransitionBuilder,
@@ -100,16 +100,7 @@ impl GeneratedArea {
 
         let layer_set = LayerSet::new(&area.builder, &props, layers)?;
 
-        let mut path_grids = HashMap::new();
-        for size in Module::all_sizes() {
-            let path_grid = PathFinderGrid::new(
-                Rc::clone(&size),
-                layer_set.width,
-                layer_set.height,
-                &layer_set.passable,
-            );
-            path_grids.insert(size.id.to_string(), path_grid);
-        }
+        let path_grids = create_path_grids(&layer_set);
 
         let mut transitions = Vec::new();
         for (index, t_builder) in transition_builders.into_iter().enumerate() {
@@ -146,6 +137,7 @@ impl GeneratedArea {
                 hover_text: t_builder.hover_text.clone(),
                 size,
                 image_display: image,
+                thumbnail: t_builder.thumbnail.clone(),
             };
             transitions.push(transition);
         }
@@ -169,6 +161,42 @@ impl GeneratedArea {
     pub fn path_grid(&self, size_id: &str) -> &PathFinderGrid {
         &self.path_grids[size_id]
     }
+
+    /// Replaces the tiles at `x`, `y` in the specified layer with `tile`, or
+    /// removes them if `None`, updating passability and path finding
+    pub fn set_tile(
+        &mut self,
+        layer_id: &str,
+        x: i32,
+        y: i32,
+        tile: Option<Rc<Tile>>,
+    ) -> Result<(), Error> {
+        self.layer_set.set_tile(layer_id, x, y, tile, &self.props)?;
+        self.path_grids = create_path_grids(&self.layer_set);
+        Ok(())
+    }
+
+    /// Overrides the terrain passability of each of the specified `points`
+    pub fn set_passable(&mut self, points: &[(Point, bool)]) {
+        for (p, passable) in points {
+            self.layer_set.set_passable(p.x, p.y, *passable);
+        }
+        self.path_grids = create_path_grids(&self.layer_set);
+    }
+}
+
+fn create_path_grids(layer_set: &LayerSet) -> HashMap<String, PathFinderGrid> {
+    let mut path_grids = HashMap::new();
+    for size in Module::all_sizes() {
+        let path_grid = PathFinderGrid::new(
+            Rc::clone(&size),
+            layer_set.width,
+            layer_set.height,
+            &layer_set.passable,
+        );
+        path_grids.insert(size.id.to_string(), path_grid);
+    }
+    path_grids
 }
 
 pub struct PregenOutput {
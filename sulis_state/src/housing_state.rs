@@ -0,0 +1,147 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_module::{area::create_prop, Module};
+
+use crate::{transition_handler, EntityState, GameState, Location};
+
+/// The upgrades that have been added to the campaign housing.  The props
+/// themselves are saved with the housing area like any other props.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct HousingState {
+    upgrades: Vec<String>,
+}
+
+impl HousingState {
+    pub fn has_upgrade(&self, id: &str) -> bool {
+        self.upgrades.iter().any(|upgrade| upgrade == id)
+    }
+
+    pub(crate) fn add_upgrade(&mut self, id: &str) {
+        self.upgrades.push(id.to_string());
+    }
+}
+
+/// Places the props of the housing upgrade with the specified `id`,
+/// first removing any props it replaces.  Returns false if the campaign has
+/// no housing or the upgrade does not exist
+pub(crate) fn apply_upgrade(id: &str) -> bool {
+    let campaign = Module::campaign();
+    let housing = match campaign.housing {
+        None => {
+            warn!("Unable to add upgrade '{}': campaign has no housing", id);
+            return false;
+        }
+        Some(ref housing) => housing,
+    };
+
+    let upgrade = match housing.upgrades.get(id) {
+        None => {
+            warn!("No housing upgrade with ID '{}'", id);
+            return false;
+        }
+        Some(upgrade) => upgrade,
+    };
+
+    if let Err(e) = GameState::preload_area(&housing.area) {
+        warn!("Unable to load housing area '{}'", housing.area);
+        warn!("{}", e);
+        return false;
+    }
+    let area_state = GameState::get_area_state(&housing.area).unwrap();
+    let mut area_state = area_state.borrow_mut();
+
+    for p in upgrade.remove.iter() {
+        if let Some(index) = area_state.props().index_at(p.x, p.y) {
+            area_state.props_mut().remove(index);
+        }
+    }
+
+    for builder in upgrade.props.iter() {
+        let data = match create_prop(builder) {
+            Err(e) => {
+                warn!("Unable to create prop '{}' for upgrade '{}'", builder.id, id);
+                warn!("{}", e);
+                continue;
+            }
+            Ok(data) => data,
+        };
+
+        let location = Location::from_point(data.location, &area_state.area.area);
+        if let Err(e) = area_state.props_mut().add(&data, location, false) {
+            warn!("Unable to add prop '{}' for upgrade '{}'", builder.id, id);
+            warn!("{}", e);
+        }
+    }
+
+    area_state.props_changed();
+    true
+}
+
+/// Removes `entity` from the party, if needed, and moves them to the first
+/// free idle location in the housing area, where they wait until added back.
+/// Returns false if the campaign has no housing or `entity` is the player
+pub(crate) fn send_to_housing(entity: &Rc<RefCell<EntityState>>) -> bool {
+    if Rc::ptr_eq(entity, &GameState::player()) {
+        warn!("Unable to send the player character to housing");
+        return false;
+    }
+
+    let campaign = Module::campaign();
+    let housing = match campaign.housing {
+        None => {
+            warn!("Unable to send entity to housing: campaign has no housing");
+            return false;
+        }
+        Some(ref housing) => housing,
+    };
+
+    if housing.idle_locations.is_empty() {
+        warn!("Unable to send entity to housing: no idle locations");
+        return false;
+    }
+
+    if let Err(e) = GameState::preload_area(&housing.area) {
+        warn!("Unable to load housing area '{}'", housing.area);
+        warn!("{}", e);
+        return false;
+    }
+    let area_state = GameState::get_area_state(&housing.area).unwrap();
+
+    if entity.borrow().is_party_member() {
+        GameState::remove_party_member(Rc::clone(entity));
+    }
+
+    let p = {
+        let area_state = area_state.borrow();
+        let size = &entity.borrow().size;
+        let free = housing
+            .idle_locations
+            .iter()
+            .find(|p| area_state.is_passable_size(size, p.x, p.y));
+        match free {
+            Some(p) => *p,
+            None => housing.idle_locations[0],
+        }
+    };
+
+    transition_handler::move_entity_to(entity, &area_state, p);
+    true
+}
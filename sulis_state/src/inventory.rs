@@ -26,10 +26,28 @@ use sulis_module::{
     QuickSlot, Slot, StatList, WeaponStyle,
 };
 
+/// A named set of equipment that can be saved and then equipped in one step
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub enum Loadout {
+    Exploration,
+    Battle,
+    Social,
+}
+
+impl Loadout {
+    pub fn iter() -> Iter<'static, Loadout> {
+        LOADOUTS_LIST.iter()
+    }
+}
+
+const LOADOUTS_LIST: [Loadout; 3] = [Loadout::Exploration, Loadout::Battle, Loadout::Social];
+
 #[derive(Clone)]
 pub struct Inventory {
     pub equipped: HashMap<Slot, ItemState>,
     pub quick: HashMap<QuickSlot, ItemState>,
+    pub(crate) loadouts: HashMap<Loadout, HashMap<Slot, ItemState>>,
 }
 
 impl Inventory {
@@ -37,9 +55,42 @@ impl Inventory {
         Inventory {
             equipped: HashMap::new(),
             quick: HashMap::new(),
+            loadouts: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn load_loadouts(
+        &mut self,
+        loadouts: HashMap<Loadout, HashMap<Slot, ItemSaveState>>,
+    ) {
+        for (loadout, items) in loadouts {
+            let mut result = HashMap::new();
+            for (slot, item) in items {
+                match Module::create_get_item(&item.id, &item.adjectives) {
+                    None => warn!("No item with ID '{}' for {:?} loadout", item.id, loadout),
+                    Some(created) => {
+                        result.insert(slot, ItemState::new(created, item.variant));
+                    }
+                }
+            }
+            self.loadouts.insert(loadout, result);
         }
     }
 
+    pub fn has_loadout(&self, loadout: Loadout) -> bool {
+        self.loadouts.contains_key(&loadout)
+    }
+
+    pub fn loadout(&self, loadout: Loadout) -> Option<&HashMap<Slot, ItemState>> {
+        self.loadouts.get(&loadout)
+    }
+
+    /// Records the currently equipped items as the specified loadout,
+    /// replacing anything previously saved there
+    pub fn save_loadout(&mut self, loadout: Loadout) {
+        self.loadouts.insert(loadout, self.equipped.clone());
+    }
+
     pub fn load(
         &mut self,
         equipped: Vec<Option<ItemSaveState>>,
@@ -396,6 +447,21 @@ impl Inventory {
         self.quick.remove(&quick_slot)
     }
 
+    /// Replaces the item equipped in `slot` with `item`, such as a worn or
+    /// repaired copy, also replacing the previous item in any saved loadouts
+    pub(crate) fn replace_equipped(&mut self, slot: Slot, item: ItemState) {
+        let old = match self.equipped.get_mut(&slot) {
+            None => return,
+            Some(cur) => std::mem::replace(cur, item.clone()),
+        };
+
+        for items in self.loadouts.values_mut() {
+            if items.get(&slot) == Some(&old) {
+                items.insert(slot, item.clone());
+            }
+        }
+    }
+
     #[must_use]
     pub fn unequip(&mut self, slot: Slot) -> Option<ItemState> {
         let result = self.equipped.remove(&slot);
@@ -22,11 +22,14 @@ extern crate log;
 extern crate serde_derive;
 
 mod ai;
-pub use self::ai::AI;
+pub use self::ai::{AIBehavior, AI};
 
 pub mod ability_state;
 pub use self::ability_state::AbilityState;
 
+mod achievement_state;
+pub use self::achievement_state::{AchievementState, AwardedAchievement};
+
 mod actor_state;
 pub use self::actor_state::ActorState;
 
@@ -38,21 +41,46 @@ pub use self::area_feedback_text::AreaFeedbackText;
 pub mod area_state;
 pub use self::area_state::AreaState;
 
+pub mod auto_pause;
+pub use self::auto_pause::{AutoPause, AutoPauseTrigger};
+
+pub mod auto_resolve;
+
+mod banter_state;
+pub use self::banter_state::BanterState;
+
+pub mod combat_log;
+pub use self::combat_log::CombatLog;
+
 mod change_listener;
 pub use self::change_listener::ChangeListener;
 pub use self::change_listener::ChangeListenerList;
 
+mod crime_state;
+pub use self::crime_state::CrimeState;
+
+pub mod debug_time;
+pub use self::debug_time::DebugTime;
+
 mod distance_finder;
 pub use self::distance_finder::{
     can_attack, center, center_i32, dist, is_threat, is_within, is_within_attack_dist,
     is_within_touch_dist, Locatable,
 };
 
+pub mod durability;
+
 mod effect;
 pub use self::effect::Effect;
 
+mod housing_state;
+pub use self::housing_state::HousingState;
+
 mod entity_attack_handler;
 
+mod entity_id;
+pub use self::entity_id::EntityId;
+
 mod entity_state;
 pub use self::entity_state::AreaDrawable;
 pub use self::entity_state::EntityState;
@@ -71,7 +99,7 @@ mod generated_area;
 pub use self::generated_area::{GeneratedArea, PregenOutput};
 
 pub mod inventory;
-pub use self::inventory::Inventory;
+pub use self::inventory::{Inventory, Loadout};
 
 pub mod item_list;
 pub use self::item_list::ItemList;
@@ -82,10 +110,17 @@ pub use self::location::Location;
 mod los_calculator;
 pub use self::los_calculator::calculate_los;
 pub use self::los_calculator::has_visibility;
+pub use self::los_calculator::VisCache;
+
+mod map_pin_state;
+pub use self::map_pin_state::{MapPin, MapPinState};
 
 mod merchant_state;
 pub use self::merchant_state::MerchantState;
 
+pub mod message_log;
+pub use self::message_log::{GameMessage, MessageChannel, MessageLog};
+
 mod path_finder;
 
 mod party_bump_handler;
@@ -93,6 +128,9 @@ mod party_bump_handler;
 mod party_stash;
 pub use self::party_stash::PartyStash;
 
+pub mod presence;
+pub use self::presence::{Presence, PresenceProvider, PresenceStatus};
+
 mod prop_state;
 pub use self::prop_state::PropState;
 
@@ -106,6 +144,9 @@ pub use self::quest_state::QuestStateSet;
 mod range_indicator;
 pub use self::range_indicator::{RangeIndicator, RangeIndicatorHandler, RangeIndicatorImageSet};
 
+pub mod records_state;
+pub use self::records_state::RecordsState;
+
 pub mod save_file;
 pub use self::save_file::SaveFile;
 pub use self::save_file::SaveFileMetaData;
@@ -116,11 +157,26 @@ pub use self::save_state::SaveState;
 pub mod script;
 pub use self::script::{Script, ScriptCallback, ScriptState};
 
+pub mod script_test;
+
+pub mod services;
+
+pub mod supplies;
+
 mod transition_handler;
 
 mod turn_manager;
 pub(crate) use self::turn_manager::TurnManager;
 
+mod weather_state;
+pub use self::weather_state::WeatherState;
+
+mod world_event_state;
+pub use self::world_event_state::WorldEventState;
+
+pub mod world_export;
+pub use self::world_export::WorldExport;
+
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -132,6 +188,7 @@ pub enum NextGameStep {
     Exit,
     NewCampaign {
         pc_actor: Rc<Actor>,
+        party_actors: Vec<Rc<Actor>>,
     },
     LoadCampaign {
         save_state: Box<SaveState>,
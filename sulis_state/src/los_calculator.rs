@@ -15,9 +15,89 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use std::cmp;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::{EntityState, GeneratedArea};
+use sulis_core::util::Point;
+
+use crate::{EntityId, EntityState, GeneratedArea};
+
+/// Caches the results of `has_visibility` between pairs of entities.  Each
+/// result is only reused while both entities remain at the positions it was
+/// computed at, and the whole cache must be cleared whenever the visibility of
+/// the area itself changes, such as a door opening.  Entries are keyed by
+/// entity ID, so an entity reusing a freed slot never sees a stale result.
+#[derive(Default)]
+pub struct VisCache {
+    entries: HashMap<(EntityId, EntityId), VisEntry>,
+}
+
+struct VisEntry {
+    parent: Point,
+    target: Point,
+    visible: bool,
+}
+
+impl VisCache {
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns whether `parent` can see `target`, computing and caching the
+    /// result if it is not already known for their current positions
+    pub fn has_visibility(
+        &mut self,
+        area: &GeneratedArea,
+        prop_vis_grid: &[bool],
+        parent: &EntityState,
+        target: &EntityState,
+    ) -> bool {
+        let parent_pos = parent.location.to_point();
+        let target_pos = target.location.to_point();
+        let key = (parent.id(), target.id());
+
+        if let Some(entry) = self.entries.get(&key) {
+            if entry.parent == parent_pos && entry.target == target_pos {
+                return entry.visible;
+            }
+        }
+
+        let visible = has_visibility(area, prop_vis_grid, parent, target);
+        self.entries.insert(
+            key,
+            VisEntry {
+                parent: parent_pos,
+                target: target_pos,
+                visible,
+            },
+        );
+
+        // the ray between two single square entities at the same elevation is
+        // the same in either direction, so the result holds for the reverse pair
+        if is_symmetric(area, parent, target) {
+            self.entries.insert(
+                (target.id(), parent.id()),
+                VisEntry {
+                    parent: target_pos,
+                    target: parent_pos,
+                    visible,
+                },
+            );
+        }
+
+        visible
+    }
+}
+
+fn is_symmetric(area: &GeneratedArea, parent: &EntityState, target: &EntityState) -> bool {
+    let single = |e: &EntityState| e.size.width == 1 && e.size.height == 1;
+    if !single(parent) || !single(target) {
+        return false;
+    }
+
+    let parent_elev = area.layer_set.elevation(parent.location.x, parent.location.y);
+    let target_elev = area.layer_set.elevation(target.location.x, target.location.y);
+    parent_elev == target_elev
+}
 
 #[must_use]
 pub fn calculate_los(
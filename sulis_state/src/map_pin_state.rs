@@ -0,0 +1,86 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::HashMap;
+
+/// A labeled marker placed on the map at a location within an area.  Pins
+/// are either added by scripts, such as quest markers, or are free text
+/// notes written by the player, which the player may also remove.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MapPin {
+    pub id: String,
+    pub area: String,
+    pub x: i32,
+    pub y: i32,
+    pub label: String,
+
+    #[serde(default)]
+    pub note: bool,
+}
+
+/// All map pins currently placed, persisted with the save
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct MapPinState {
+    pins: Vec<MapPin>,
+    next_note: u32,
+}
+
+impl MapPinState {
+    pub fn iter(&self) -> impl Iterator<Item = &MapPin> {
+        self.pins.iter()
+    }
+
+    pub fn in_area<'a>(&'a self, area: &'a str) -> impl Iterator<Item = &'a MapPin> {
+        self.pins.iter().filter(move |pin| pin.area == area)
+    }
+
+    /// Returns the number of pins in each area that has at least one
+    pub fn counts_by_area(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for pin in self.pins.iter() {
+            *counts.entry(pin.area.to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Adds the specified pin, replacing any existing pin with the same ID
+    pub(crate) fn add(&mut self, pin: MapPin) {
+        self.remove(&pin.id);
+        self.pins.push(pin);
+    }
+
+    pub(crate) fn add_note(&mut self, area: &str, x: i32, y: i32, text: &str) {
+        let id = format!("__note_{}", self.next_note);
+        self.next_note += 1;
+
+        self.pins.push(MapPin {
+            id,
+            area: area.to_string(),
+            x,
+            y,
+            label: text.to_string(),
+            note: true,
+        });
+    }
+
+    pub(crate) fn remove(&mut self, id: &str) -> bool {
+        let len = self.pins.len();
+        self.pins.retain(|pin| pin.id != id);
+        self.pins.len() != len
+    }
+}
@@ -17,10 +17,10 @@
 use std::io::Error;
 use std::rc::Rc;
 
-use sulis_core::util::invalid_data_error;
+use sulis_core::util::{gen_rand, invalid_data_error};
 use sulis_module::{ItemState, LootList, Module, Time};
 
-use crate::{save_state::MerchantSaveState, ChangeListenerList, GameState, ItemList};
+use crate::{save_state::MerchantSaveState, ChangeListenerList, EntityState, GameState, ItemList};
 
 pub struct MerchantState {
     pub id: String,
@@ -32,6 +32,15 @@ pub struct MerchantState {
     pub loot_list_id: Option<String>,
     pub refresh_rate_millis: usize,
     pub last_refresh_millis: usize,
+
+    /// Items sold to this merchant since the game was loaded, along with the
+    /// price they were sold for.  These may be bought back at that price
+    buy_back: Vec<(i32, ItemState)>,
+
+    /// The fraction buy prices are reduced, and sell prices increased, by
+    /// as a result of haggling.  Negative after a failed haggle
+    haggle_frac: f32,
+    pub(crate) last_haggle_day: Option<u32>,
 }
 
 impl MerchantState {
@@ -57,6 +66,9 @@ impl MerchantState {
             items,
             refresh_rate_millis: save.refresh_rate_millis,
             last_refresh_millis: save.last_refresh_millis,
+            buy_back: Vec::new(),
+            haggle_frac: save.haggle_frac,
+            last_haggle_day: save.last_haggle_day,
         })
     }
 
@@ -86,6 +98,9 @@ impl MerchantState {
             listeners: ChangeListenerList::default(),
             last_refresh_millis,
             refresh_rate_millis,
+            buy_back: Vec::new(),
+            haggle_frac: 0.0,
+            last_haggle_day: None,
         }
     }
 
@@ -124,17 +139,20 @@ impl MerchantState {
         };
 
         self.items.clear();
+        self.buy_back.clear();
         for (qty, item) in loot_list.generate() {
             self.items.add_quantity(qty, item);
         }
     }
 
     pub fn get_buy_price(&self, item_state: &ItemState) -> i32 {
-        ((item_state.item.value as f32) * self.buy_frac).ceil() as i32
+        let frac = self.buy_frac * (1.0 - self.haggle_frac);
+        ((item_state.item.value as f32) * frac).ceil() as i32
     }
 
     pub fn get_sell_price(&self, item_state: &ItemState) -> i32 {
-        ((item_state.item.value as f32) * self.sell_frac).floor() as i32
+        let frac = self.sell_frac * (1.0 + self.haggle_frac);
+        ((item_state.item.value as f32) * frac).floor() as i32
     }
 
     pub fn add(&mut self, item_state: ItemState) {
@@ -143,6 +161,69 @@ impl MerchantState {
         self.listeners.notify(self);
     }
 
+    /// Sells the item to this merchant, adding it to the buy back list.
+    /// Returns the price paid for the item
+    pub fn sell(&mut self, item_state: ItemState) -> i32 {
+        let price = self.get_sell_price(&item_state);
+        self.buy_back.push((price, item_state));
+
+        self.listeners.notify(self);
+        price
+    }
+
+    pub fn buy_back_items(&self) -> &[(i32, ItemState)] {
+        &self.buy_back
+    }
+
+    /// Removes the buy back item at the specified index, returning it along
+    /// with the price it was sold for
+    pub fn remove_buy_back(&mut self, index: usize) -> Option<(i32, ItemState)> {
+        if index >= self.buy_back.len() {
+            return None;
+        }
+
+        let result = self.buy_back.remove(index);
+        self.listeners.notify(self);
+        Some(result)
+    }
+
+    pub fn haggle_frac(&self) -> f32 {
+        self.haggle_frac
+    }
+
+    /// Whether the party may currently haggle with this merchant, which is
+    /// possible once per day if the rules allow it at all
+    pub fn can_haggle(&self) -> bool {
+        if Module::rules().haggling.attribute.is_none() {
+            return false;
+        }
+
+        let day = GameState::turn_manager().borrow().current_time().day;
+        self.last_haggle_day != Some(day)
+    }
+
+    /// Performs the haggling check for the `entity`, adjusting prices by the
+    /// resulting fraction, which is returned.  Returns `None` if the party
+    /// may not currently haggle with this merchant
+    pub fn haggle(&mut self, entity: &EntityState) -> Option<f32> {
+        if !self.can_haggle() {
+            return None;
+        }
+
+        let rules = Module::rules().haggling;
+        let attr = rules.attribute?;
+        let roll = gen_rand(1, 21) + entity.actor.stats.attributes.get(attr) as i32;
+        let margin = roll - rules.difficulty;
+        let frac = (margin as f32 * rules.price_per_point)
+            .clamp(-rules.max_markup, rules.max_discount);
+        info!("Haggle with '{}': {} vs {}, price change {}", self.id, roll, rules.difficulty, frac);
+
+        self.haggle_frac = frac;
+        self.last_haggle_day = Some(GameState::turn_manager().borrow().current_time().day);
+        self.listeners.notify(self);
+        Some(frac)
+    }
+
     /// removes one copy of the item at the specified index
     pub fn remove(&mut self, index: usize) -> Option<ItemState> {
         let result = self.items.remove(index);
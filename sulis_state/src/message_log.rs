@@ -0,0 +1,192 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::rc::{Rc, Weak};
+use std::slice::Iter;
+
+use sulis_module::ItemState;
+
+use crate::{ChangeListenerList, EntityState};
+
+const MAX_MESSAGES: usize = 100;
+
+/// The channel of a game message, which determines its color and whether it
+/// is shown
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum MessageChannel {
+    #[default]
+    Game,
+    Notify,
+    Warning,
+    Debug,
+}
+
+const CHANNELS: [MessageChannel; 4] = [
+    MessageChannel::Game,
+    MessageChannel::Notify,
+    MessageChannel::Warning,
+    MessageChannel::Debug,
+];
+
+impl MessageChannel {
+    pub fn iter() -> Iter<'static, MessageChannel> {
+        CHANNELS.iter()
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            MessageChannel::Game => "game",
+            MessageChannel::Notify => "notify",
+            MessageChannel::Warning => "warning",
+            MessageChannel::Debug => "debug",
+        }
+    }
+}
+
+/// A game object referenced by part of a message, which the player may click
+/// to select or inspect
+#[derive(Clone)]
+pub enum MessageRef {
+    Entity(Weak<RefCell<EntityState>>),
+    Item(ItemState),
+}
+
+#[derive(Clone)]
+pub struct MessagePart {
+    pub text: String,
+    pub reference: Option<MessageRef>,
+}
+
+/// A game message, made up of plain text and parts that reference entities
+/// or items
+#[derive(Clone, Default)]
+pub struct GameMessage {
+    pub channel: MessageChannel,
+    pub parts: Vec<MessagePart>,
+}
+
+impl GameMessage {
+    /// Creates a message with only the specified plain `text`
+    pub fn new(text: &str) -> GameMessage {
+        GameMessage::default().text(text)
+    }
+
+    pub fn channel(mut self, channel: MessageChannel) -> GameMessage {
+        self.channel = channel;
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> GameMessage {
+        self.parts.push(MessagePart {
+            text: text.to_string(),
+            reference: None,
+        });
+        self
+    }
+
+    /// Adds a part naming the `entity`, which is clickable while the entity exists
+    pub fn entity(mut self, entity: &Rc<RefCell<EntityState>>) -> GameMessage {
+        self.parts.push(MessagePart {
+            text: entity.borrow().actor.actor.name.to_string(),
+            reference: Some(MessageRef::Entity(Rc::downgrade(entity))),
+        });
+        self
+    }
+
+    /// Adds a part naming the `item`, which is clickable to inspect it
+    pub fn item(mut self, item: &ItemState) -> GameMessage {
+        self.parts.push(MessagePart {
+            text: item.item.name.to_string(),
+            reference: Some(MessageRef::Item(item.clone())),
+        });
+        self
+    }
+
+    /// Returns the full text of this message, without references
+    pub fn plain_text(&self) -> String {
+        self.parts.iter().map(|part| part.text.as_str()).collect()
+    }
+
+    /// Returns the references in this message, in order
+    pub fn references(&self) -> impl Iterator<Item = &MessageRef> {
+        self.parts.iter().filter_map(|part| part.reference.as_ref())
+    }
+}
+
+/// The most recent game messages, shown in the status bar and its history
+pub struct MessageLog {
+    messages: VecDeque<GameMessage>,
+    hidden: HashSet<MessageChannel>,
+    pub listeners: ChangeListenerList<MessageLog>,
+}
+
+impl Default for MessageLog {
+    fn default() -> MessageLog {
+        let mut hidden = HashSet::new();
+        hidden.insert(MessageChannel::Debug);
+        MessageLog {
+            messages: VecDeque::new(),
+            hidden,
+            listeners: ChangeListenerList::default(),
+        }
+    }
+}
+
+impl MessageLog {
+    pub fn add(&mut self, message: GameMessage) {
+        debug!("Game message: {}", message.plain_text());
+        self.messages.push_back(message);
+        if self.messages.len() > MAX_MESSAGES {
+            self.messages.pop_front();
+        }
+        self.listeners.notify(self);
+    }
+
+    /// Returns the most recent message in a channel that is not hidden
+    pub fn latest(&self) -> Option<&GameMessage> {
+        self.iter().last()
+    }
+
+    /// Iterates over the messages in channels that are not hidden, from oldest
+    /// to newest
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &GameMessage> {
+        self.messages
+            .iter()
+            .filter(|message| !self.hidden.contains(&message.channel))
+    }
+
+    pub fn is_hidden(&self, channel: MessageChannel) -> bool {
+        self.hidden.contains(&channel)
+    }
+
+    /// Sets whether messages in the `channel` are shown.  Debug messages are
+    /// hidden by default
+    pub fn set_hidden(&mut self, channel: MessageChannel, hidden: bool) {
+        if hidden {
+            self.hidden.insert(channel);
+        } else {
+            self.hidden.remove(&channel);
+        }
+        self.listeners.notify(self);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.messages.clear();
+        self.listeners.notify(self);
+    }
+}
@@ -14,36 +14,80 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
-use crate::{ChangeListenerList, GameState, ItemList};
+use crate::{ChangeListenerList, GameMessage, GameState, ItemList};
 use sulis_module::{ItemListEntrySaveState, ItemState, Module};
 
 pub struct PartyStash {
     items: ItemList,
+    keys: ItemList,
     coins_id: String,
     pub listeners: ChangeListenerList<PartyStash>,
 }
 
 impl PartyStash {
-    pub(crate) fn new(items: ItemList) -> PartyStash {
+    /// Creates the stash, moving any key items in `items` to the keyring
+    pub(crate) fn new(mut items: ItemList) -> PartyStash {
         let coins_id = Module::rules().coins_item.to_string();
+        let mut keys = ItemList::default();
+
+        let mut index = 0;
+        while index < items.len() {
+            let is_key = items.get(index).is_some_and(|(_, item)| item.item.key);
+            if !is_key {
+                index += 1;
+                continue;
+            }
+
+            if let Some((qty, item)) = items.remove_all_at(index) {
+                keys.add_quantity(qty, item);
+            }
+        }
+
         PartyStash {
             items,
+            keys,
             coins_id,
             listeners: ChangeListenerList::default(),
         }
     }
 
     pub(crate) fn save(&self) -> Vec<ItemListEntrySaveState> {
-        self.items
-            .iter()
-            .map(|(q, ref i)| ItemListEntrySaveState::new(*q, i))
-            .collect()
+        save_list(&self.items)
+    }
+
+    pub(crate) fn save_keys(&self) -> Vec<ItemListEntrySaveState> {
+        save_list(&self.keys)
     }
 
     pub fn items(&self) -> &ItemList {
         &self.items
     }
 
+    /// The key items held in the party keyring
+    pub fn keys(&self) -> &ItemList {
+        &self.keys
+    }
+
+    /// Returns whether the keyring holds a key item with the specified ID
+    pub fn has_key(&self, id: &str) -> bool {
+        self.key_index(id).is_some()
+    }
+
+    fn key_index(&self, id: &str) -> Option<usize> {
+        self.keys.iter().position(|(_, item)| item.item.id == id)
+    }
+
+    /// Removes one key item with the specified ID from the keyring,
+    /// returning it if the party held it
+    pub fn remove_key(&mut self, id: &str) -> Option<ItemState> {
+        let index = self.key_index(id)?;
+        let result = self.keys.remove(index);
+
+        self.listeners.notify(self);
+
+        result
+    }
+
     pub fn add_item(&mut self, quantity: u32, item_state: ItemState) -> Option<usize> {
         if quantity == 0 {
             return None;
@@ -55,6 +99,13 @@ impl PartyStash {
             return None;
         }
 
+        GameState::record_item(&item_state.item.original_id);
+        if item_state.item.key {
+            self.keys.add_quantity(quantity, item_state);
+            self.listeners.notify(self);
+            return None;
+        }
+
         let index = self.items.add_quantity(quantity, item_state);
 
         self.listeners.notify(self);
@@ -62,8 +113,8 @@ impl PartyStash {
         Some(index)
     }
 
-    /// Returns whether or not this stash has at least one item
-    /// with the specified ID
+    /// Returns whether or not this stash or the keyring has at least
+    /// one item with the specified ID
     pub fn has_item(&self, id: &str) -> bool {
         for (_, ref item) in self.items.iter() {
             if item.item.id == id {
@@ -71,7 +122,7 @@ impl PartyStash {
             }
         }
 
-        false
+        self.has_key(id)
     }
 
     #[must_use]
@@ -85,6 +136,17 @@ impl PartyStash {
         result
     }
 
+    /// Removes every item from this stash, returning them along with their quantities
+    pub(crate) fn remove_all(&mut self) -> Vec<(u32, ItemState)> {
+        let mut items = Vec::new();
+        while let Some(entry) = self.items.remove_all_at(0) {
+            items.push(entry);
+        }
+
+        self.listeners.notify(self);
+        items
+    }
+
     /// Takes all items out of the specified prop and into this stash
     pub fn take_all(&mut self, prop_index: usize) {
         let area_state = GameState::area_state();
@@ -100,6 +162,7 @@ impl PartyStash {
             let mut i = num_items - 1;
             loop {
                 if let Some((qty, item_state)) = prop_state.remove_all_at(i) {
+                    add_take_message(qty, &item_state);
                     self.add_item(qty, item_state);
                 }
 
@@ -120,9 +183,63 @@ impl PartyStash {
         let prop_state = area_state.props_mut().get_mut(prop_index);
 
         if let Some((qty, item_state)) = prop_state.remove_all_at(item_index) {
+            add_take_message(qty, &item_state);
             self.add_item(qty, item_state);
         }
 
         self.listeners.notify(self);
     }
 }
+
+fn save_list(items: &ItemList) -> Vec<ItemListEntrySaveState> {
+    items
+        .iter()
+        .map(|(q, ref i)| ItemListEntrySaveState::new(*q, i))
+        .collect()
+}
+
+fn add_take_message(quantity: u32, item: &ItemState) {
+    let mut message = GameMessage::new("Took ").item(item);
+    if quantity > 1 {
+        message = message.text(&format!(" x{quantity}"));
+    }
+    GameState::add_message(message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sulis_core::config::Config;
+    use sulis_core::resource::ResourceSet;
+
+    fn item(id: &str) -> ItemState {
+        ItemState::new(Module::item(id).unwrap(), None)
+    }
+
+    #[test]
+    fn key_items_are_kept_in_the_keyring() {
+        Config::enable_safe_mode();
+        let dirs = vec![
+            "../data".to_string(),
+            "../campaigns/twin_expanse_prologue".to_string(),
+            "test_data/keyring".to_string(),
+        ];
+        let yaml = ResourceSet::load_resources(dirs.clone()).unwrap();
+        Module::load_resources(yaml, dirs).unwrap();
+
+        let mut items = ItemList::default();
+        items.add_quantity(2, item("craft_bottle"));
+        items.add_quantity(1, item("test_cellar_key"));
+        let mut stash = PartyStash::new(items);
+
+        assert_eq!(stash.items().len(), 1);
+        assert_eq!(stash.keys().len(), 1);
+        assert!(stash.has_key("test_cellar_key"));
+        assert!(stash.has_item("test_cellar_key"));
+        assert!(!stash.has_key("craft_bottle"));
+
+        assert!(stash.remove_key("test_cellar_key").is_some());
+        assert!(!stash.has_key("test_cellar_key"));
+        assert!(stash.remove_key("test_cellar_key").is_none());
+    }
+}
@@ -22,16 +22,22 @@ use sulis_core::{
     config::Config,
     util::{self, Point},
 };
-use sulis_module::area::{Destination, LocationChecker, PathFinder, PathFinderGrid};
+use sulis_module::area::{Destination, LayerSet, LocationChecker, PathFinder, PathFinderGrid};
+use sulis_module::Module;
 
 pub struct StateLocationChecker<'a, 'b> {
+    area_state: &'a AreaState,
     width: i32,
     grid: &'a PathFinderGrid,
     explored: Option<&'a [bool]>,
     prop_grid: &'a [bool],
     entity_grid: &'a [Vec<usize>],
+    layer_set: &'a LayerSet,
     requester: &'b EntityState,
     entities_to_ignore: &'b [usize],
+    can_swim: bool,
+    swim_cost: i32,
+    hazard_cost: i32,
 }
 
 impl<'a, 'b> StateLocationChecker<'a, 'b> {
@@ -45,22 +51,47 @@ impl<'a, 'b> StateLocationChecker<'a, 'b> {
         let grid = &area_state.area.path_grid(requester.size());
         let prop_grid = area_state.props().entire_pass_grid();
         let entity_grid = &area_state.entity_grid;
+        let layer_set = &area_state.area.layer_set;
         let explored = if use_explored {
             Some(area_state.pc_explored.as_slice())
         } else {
             None
         };
 
+        let swim_cost = if requester.actor.stats.water_walking {
+            0
+        } else {
+            Module::rules().swimming.path_cost
+        };
+
+        let hazard_cost = if requester.avoids_hazards() {
+            Module::rules().hazard_path_cost
+        } else {
+            0
+        };
+
         StateLocationChecker {
+            area_state,
             width,
             grid,
             explored,
             prop_grid,
             entity_grid,
+            layer_set,
             requester,
             entities_to_ignore,
+            can_swim: requester.actor.can_swim(),
+            swim_cost,
+            hazard_cost,
         }
     }
+
+    fn in_hazard(&self, x: i32, y: i32) -> bool {
+        let faction = self.requester.actor.faction();
+        self.requester.points(x, y).any(|p| {
+            self.area_state.is_hazard_index((p.x + p.y * self.width) as usize, faction)
+        })
+    }
 }
 
 impl<'a, 'b> LocationChecker for StateLocationChecker<'a, 'b> {
@@ -80,6 +111,10 @@ impl<'a, 'b> LocationChecker for StateLocationChecker<'a, 'b> {
                 return false;
             }
 
+            if !self.can_swim && self.layer_set.is_swim_index(index) {
+                return false;
+            }
+
             for i in self.entity_grid[index].iter() {
                 if !self.entities_to_ignore.contains(i) {
                     return false;
@@ -108,10 +143,38 @@ impl<'a, 'b> LocationChecker for StateLocationChecker<'a, 'b> {
     }
 
     fn get_cost(&self, _from: i32, to: i32) -> i32 {
-        if self.entity_grid[to as usize].is_empty() {
+        let base = if self.entity_grid[to as usize].is_empty() {
             10
         } else {
             11
+        };
+
+        let (x, y) = (to % self.width, to / self.width);
+        let extra_squares = self
+            .requester
+            .points(x, y)
+            .map(|p| self.layer_set.movement_cost_index((p.x + p.y * self.width) as usize))
+            .max()
+            .unwrap_or(0);
+        let base = base + 10 * extra_squares as i32;
+
+        let base = if self.hazard_cost > 0 && self.in_hazard(x, y) {
+            base + self.hazard_cost
+        } else {
+            base
+        };
+
+        if self.swim_cost == 0 {
+            return base;
+        }
+
+        let in_water = self.requester.points(x, y).any(|p| {
+            self.layer_set.is_swim_index((p.x + p.y * self.width) as usize)
+        });
+        if in_water {
+            base + self.swim_cost
+        } else {
+            base
         }
     }
 }
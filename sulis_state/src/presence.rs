@@ -0,0 +1,157 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+//! Rich presence integration.  When enabled in the config, a summary of the
+//! current game - the area, party level, and combat status - is periodically
+//! reported to a [`PresenceProvider`].  With the `discord` feature, a provider
+//! reporting to the local Discord client is installed by default.
+
+#[cfg(feature = "discord")]
+mod discord;
+#[cfg(feature = "discord")]
+pub use self::discord::DiscordPresence;
+
+use std::cell::RefCell;
+
+use sulis_core::config::Config;
+use sulis_module::Module;
+
+use crate::GameState;
+
+/// Time between checks of the game state for changes, in milliseconds
+const UPDATE_INTERVAL: u32 = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresenceStatus {
+    pub campaign: String,
+    pub area: String,
+    pub party_level: u32,
+    pub in_combat: bool,
+}
+
+impl PresenceStatus {
+    fn current() -> PresenceStatus {
+        let area = GameState::area_state().borrow().area.area.name.to_string();
+        let party_level = GameState::player().borrow().actor.actor.total_level;
+
+        PresenceStatus {
+            campaign: Module::campaign().name.to_string(),
+            area,
+            party_level,
+            in_combat: GameState::is_combat_active(),
+        }
+    }
+}
+
+/// A service that the current game status is reported to.  `update` is only
+/// called when the status has changed since the last report.
+pub trait PresenceProvider {
+    fn update(&mut self, status: &PresenceStatus);
+
+    /// Called when no game is in progress, such as at the main menu
+    fn clear(&mut self);
+}
+
+pub struct Presence {
+    provider: Option<Box<dyn PresenceProvider>>,
+    last_status: Option<PresenceStatus>,
+    elapsed: u32,
+}
+
+thread_local! {
+    static PRESENCE: RefCell<Presence> = const {
+        RefCell::new(Presence {
+            provider: None,
+            last_status: None,
+            elapsed: 0,
+        })
+    };
+}
+
+impl Presence {
+    /// Installs the default provider for this build, if presence reporting
+    /// is enabled in the config
+    pub fn init() {
+        let config = Config::presence();
+        if !config.enabled {
+            return;
+        }
+
+        #[cfg(feature = "discord")]
+        {
+            if config.discord_client_id.is_empty() {
+                warn!("Presence is enabled but no discord_client_id is configured");
+            } else {
+                Presence::set_provider(Box::new(DiscordPresence::new(
+                    config.discord_client_id,
+                )));
+            }
+        }
+
+        #[cfg(not(feature = "discord"))]
+        info!("Presence is enabled but this build has no presence provider");
+    }
+
+    /// Sets the provider that status is reported to, replacing any
+    /// existing provider
+    pub fn set_provider(provider: Box<dyn PresenceProvider>) {
+        PRESENCE.with(|p| {
+            let mut presence = p.borrow_mut();
+            presence.provider = Some(provider);
+            presence.last_status = None;
+            presence.elapsed = UPDATE_INTERVAL;
+        });
+    }
+
+    /// Checks the game state, reporting the status to the provider if it
+    /// has changed.  Called each frame while a game is in progress.
+    pub fn update(millis: u32) {
+        PRESENCE.with(|p| {
+            let mut presence = p.borrow_mut();
+            if presence.provider.is_none() {
+                return;
+            }
+
+            presence.elapsed += millis;
+            if presence.elapsed < UPDATE_INTERVAL {
+                return;
+            }
+            presence.elapsed = 0;
+
+            let status = PresenceStatus::current();
+            if presence.last_status.as_ref() == Some(&status) {
+                return;
+            }
+
+            if let Some(provider) = presence.provider.as_mut() {
+                provider.update(&status);
+            }
+            presence.last_status = Some(status);
+        });
+    }
+
+    /// Clears the reported status, such as when returning to the main menu
+    pub fn clear() {
+        PRESENCE.with(|p| {
+            let mut presence = p.borrow_mut();
+            presence.last_status = None;
+            presence.elapsed = UPDATE_INTERVAL;
+            if let Some(provider) = presence.provider.as_mut() {
+                provider.clear();
+            }
+        });
+    }
+}
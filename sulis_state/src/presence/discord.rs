@@ -0,0 +1,178 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use sulis_core::serde_json::{json, Value};
+
+use super::{PresenceProvider, PresenceStatus};
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+
+/// Time to wait before trying to reconnect after failing to reach the client
+const RETRY_TIME: Duration = Duration::from_secs(30);
+
+trait IpcStream: Read + Write {}
+
+impl<T: Read + Write> IpcStream for T {}
+
+/// Reports presence to a locally running Discord client, using its IPC socket
+pub struct DiscordPresence {
+    client_id: String,
+    stream: Option<Box<dyn IpcStream>>,
+    last_attempt: Option<Instant>,
+    start_time: u64,
+    nonce: u64,
+}
+
+impl DiscordPresence {
+    pub fn new(client_id: String) -> DiscordPresence {
+        let start_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        DiscordPresence {
+            client_id,
+            stream: None,
+            last_attempt: None,
+            start_time,
+            nonce: 0,
+        }
+    }
+
+    fn set_activity(&mut self, activity: Value) {
+        if self.stream.is_none() {
+            if let Some(time) = self.last_attempt {
+                if time.elapsed() < RETRY_TIME {
+                    return;
+                }
+            }
+            self.last_attempt = Some(Instant::now());
+
+            match self.connect() {
+                Ok(stream) => self.stream = Some(stream),
+                Err(e) => {
+                    info!("Unable to connect to Discord: {}", e);
+                    return;
+                }
+            }
+        }
+
+        self.nonce += 1;
+        let payload = json!({
+            "cmd": "SET_ACTIVITY",
+            "args": {
+                "pid": std::process::id(),
+                "activity": activity,
+            },
+            "nonce": self.nonce.to_string(),
+        });
+
+        let Some(stream) = self.stream.as_mut() else { return };
+        if let Err(e) = send(stream, OP_FRAME, &payload).and_then(|_| receive(stream)) {
+            warn!("Lost connection to Discord: {}", e);
+            self.stream = None;
+        }
+    }
+
+    fn connect(&self) -> io::Result<Box<dyn IpcStream>> {
+        let mut last_err = io::Error::new(io::ErrorKind::NotFound, "No Discord IPC socket");
+        for index in 0..10 {
+            match open_socket(index) {
+                Ok(mut stream) => {
+                    let handshake = json!({ "v": 1, "client_id": self.client_id });
+                    send(&mut stream, OP_HANDSHAKE, &handshake)?;
+                    receive(&mut stream)?;
+                    info!("Connected to Discord IPC socket {}", index);
+                    return Ok(stream);
+                }
+                Err(e) => last_err = e,
+            }
+        }
+
+        Err(last_err)
+    }
+}
+
+impl PresenceProvider for DiscordPresence {
+    fn update(&mut self, status: &PresenceStatus) {
+        let state = if status.in_combat {
+            format!("Level {} - In Combat", status.party_level)
+        } else {
+            format!("Level {} - Exploring", status.party_level)
+        };
+
+        let activity = json!({
+            "details": format!("{} - {}", status.campaign, status.area),
+            "state": state,
+            "timestamps": { "start": self.start_time },
+        });
+        self.set_activity(activity);
+    }
+
+    fn clear(&mut self) {
+        if self.stream.is_some() {
+            self.set_activity(Value::Null);
+        }
+    }
+}
+
+fn send(stream: &mut Box<dyn IpcStream>, op: u32, payload: &Value) -> io::Result<()> {
+    let data = payload.to_string().into_bytes();
+    let mut frame = Vec::with_capacity(8 + data.len());
+    frame.extend_from_slice(&op.to_le_bytes());
+    frame.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&data);
+    stream.write_all(&frame)?;
+    stream.flush()
+}
+
+/// Reads and discards the client's response to a frame
+fn receive(stream: &mut Box<dyn IpcStream>) -> io::Result<()> {
+    let mut header = [0; 8];
+    stream.read_exact(&mut header)?;
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    let mut data = vec![0; len as usize];
+    stream.read_exact(&mut data)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn open_socket(index: u32) -> io::Result<Box<dyn IpcStream>> {
+    use std::os::unix::net::UnixStream;
+
+    let dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .unwrap_or_else(|| "/tmp".to_string());
+
+    let stream = UnixStream::connect(format!("{dir}/discord-ipc-{index}"))?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(2)))?;
+    Ok(Box::new(stream))
+}
+
+#[cfg(windows)]
+fn open_socket(index: u32) -> io::Result<Box<dyn IpcStream>> {
+    let pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(format!(r"\\?\pipe\discord-ipc-{index}"))?;
+    Ok(Box::new(pipe))
+}
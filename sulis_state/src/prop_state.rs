@@ -17,15 +17,17 @@
 use std::fmt;
 use std::io::Error;
 use std::rc::Rc;
+use std::time::Instant;
 
 use sulis_core::io::{DrawList, GraphicsRenderer};
 use sulis_core::ui::{animation_state, AnimationState, Color};
 use sulis_core::util::{self, invalid_data_error, Offset, Scale, Size};
-use sulis_module::area::PropData;
+use sulis_module::area::{PropData, PropLock};
 use sulis_module::{prop, ItemState, LootList, Module, ObjectSizeIterator, Prop, OnTrigger};
 
 use crate::entity_state::AreaDrawable;
 use crate::save_state::PropInteractiveSaveState;
+use crate::script::Script;
 use crate::{GameState, ChangeListenerList, EntityTextureCache, ItemList, Location};
 
 #[derive(Debug)]
@@ -53,7 +55,13 @@ pub struct PropState {
     pub animation_state: AnimationState,
     pub listeners: ChangeListenerList<PropState>,
     pub(crate) interactive: Interactive,
+    pub owner: Option<String>,
     enabled: bool,
+    loot_level: u32,
+    visual_state: Option<usize>,
+    transition_start: Option<Instant>,
+    damage_taken: u32,
+    lock: Option<PropLock>,
 
     marked_for_removal: bool,
 
@@ -68,7 +76,12 @@ impl fmt::Debug for PropState {
 }
 
 impl PropState {
-    pub(crate) fn new(prop_data: &PropData, location: Location, temporary: bool) -> PropState {
+    pub(crate) fn new(
+        prop_data: &PropData,
+        location: Location,
+        temporary: bool,
+        loot_level: u32,
+    ) -> PropState {
         let mut items = ItemList::default();
         for item_save in prop_data.items.iter() {
             let quantity = item_save.quantity;
@@ -102,7 +115,7 @@ impl PropState {
             }
             prop::Interactive::Container { loot } => Interactive::Container {
                 items,
-                loot_to_generate: loot.clone(),
+                loot_to_generate: prop_data.loot.clone().or_else(|| loot.clone()),
                 temporary,
             },
             prop::Interactive::Door {
@@ -131,6 +144,12 @@ impl PropState {
         PropState {
             prop: Rc::clone(&prop_data.prop),
             enabled: prop_data.enabled,
+            owner: prop_data.owner.clone(),
+            loot_level,
+            visual_state: prop_data.prop.initial_state,
+            transition_start: None,
+            damage_taken: 0,
+            lock: prop_data.lock.clone(),
             location,
             interactive,
             animation_state: anim_state,
@@ -239,6 +258,36 @@ impl PropState {
         self.enabled
     }
 
+    /// Returns the current visual state of this prop, if it has any states
+    pub fn visual_state(&self) -> Option<&prop::PropVisualState> {
+        self.visual_state.and_then(|index| self.prop.states.get(index))
+    }
+
+    /// Switches this prop to the visual state with the given `id`, playing
+    /// the state's transition animation if `animate` is set.  Returns false
+    /// if the prop has no such state.  The owning `PropHandler` must update
+    /// passability and visibility afterwards.
+    pub(crate) fn set_visual_state(&mut self, id: &str, animate: bool) -> bool {
+        let index = match self.prop.state_index(id) {
+            None => return false,
+            Some(index) => index,
+        };
+
+        if self.visual_state == Some(index) {
+            return true;
+        }
+
+        self.visual_state = Some(index);
+        let has_transition = self.prop.states[index].transition.is_some();
+        self.transition_start = if animate && has_transition {
+            Some(Instant::now())
+        } else {
+            None
+        };
+        self.listeners.notify(self);
+        true
+    }
+
     pub fn location_points(&self) -> ObjectSizeIterator {
         self.prop.size.points(self.location.x, self.location.y)
     }
@@ -247,6 +296,21 @@ impl PropState {
         self.enabled = enabled;
     }
 
+    pub fn lock(&self) -> Option<&PropLock> {
+        self.lock.as_ref()
+    }
+
+    /// Whether this prop is disabled by a lock that a key could open
+    pub fn is_locked(&self) -> bool {
+        !self.enabled && self.lock.is_some()
+    }
+
+    /// Enables this prop and removes its lock
+    pub(crate) fn unlock(&mut self) {
+        self.enabled = true;
+        self.lock = None;
+    }
+
     pub(crate) fn is_marked_for_removal(&self) -> bool {
         self.marked_for_removal
     }
@@ -271,6 +335,41 @@ impl PropState {
         }
     }
 
+    pub fn is_destructible(&self) -> bool {
+        self.prop.destructible.is_some()
+    }
+
+    /// The remaining hit points of a destructible prop, or None if the prop
+    /// cannot be destroyed
+    pub fn hp(&self) -> Option<u32> {
+        self.prop
+            .destructible
+            .as_ref()
+            .map(|d| d.hp.saturating_sub(self.damage_taken))
+    }
+
+    pub(crate) fn damage_taken(&self) -> u32 {
+        self.damage_taken
+    }
+
+    pub(crate) fn set_damage_taken(&mut self, damage: u32) {
+        self.damage_taken = damage;
+    }
+
+    /// Applies `amount` damage, reduced by the prop's armor, to this prop.
+    /// Returns the damage actually taken, which is zero for props that are
+    /// not destructible
+    pub(crate) fn take_damage(&mut self, amount: u32) -> u32 {
+        let armor = match self.prop.destructible {
+            None => return 0,
+            Some(ref destructible) => destructible.armor,
+        };
+
+        let damage = amount.saturating_sub(armor);
+        self.damage_taken += damage;
+        damage
+    }
+
     pub fn is_door(&self) -> bool {
         matches!(self.interactive, Interactive::Door { .. })
     }
@@ -304,7 +403,8 @@ impl PropState {
                 };
 
                 info!("Generating loot for prop from '{}'", loot.id);
-                let generated_items = loot.generate();
+                let generated_items = loot.generate_at_level(self.loot_level);
+                let generated_items = Script::loot_generated(self.loot_level, generated_items);
                 for (qty, item) in generated_items {
                     items.add_quantity(qty, item);
                 }
@@ -411,8 +511,21 @@ impl PropState {
     }
 
     pub fn append_to_draw_list(&self, draw_list: &mut DrawList, offset: Offset, millis: u32) {
+        if let (Some(start), Some(state)) = (self.transition_start, self.visual_state()) {
+            let elapsed = util::get_elapsed_millis(start.elapsed());
+            if let Some(ref image) = state.transition {
+                if elapsed < state.transition_millis {
+                    let state = &self.animation_state;
+                    self.prop
+                        .append_image_to_draw_list(image, draw_list, state, offset, elapsed);
+                    return;
+                }
+            }
+        }
+
+        let image = self.prop.state_image(self.visual_state);
         self.prop
-            .append_to_draw_list(draw_list, &self.animation_state, offset, millis);
+            .append_image_to_draw_list(image, draw_list, &self.animation_state, offset, millis);
     }
 }
 
@@ -433,9 +546,10 @@ impl AreaDrawable for PropState {
         millis: u32,
         color: Color,
     ) {
+        let grid_y = Module::rules().grid_y_offset(self.location.x);
         let pos = Offset {
             x: x + self.location.x as f32,
-            y: y + self.location.y as f32,
+            y: y + self.location.y as f32 + grid_y,
         };
 
         let mut draw_list = DrawList::empty_sprite();
@@ -126,6 +126,15 @@ pub struct RangeIndicator {
 impl RangeIndicator {
     /// Creates an ability range indicator.  will panic if ability is not active.
     pub fn ability(parent: &Rc<RefCell<EntityState>>, ability: &Rc<Ability>) -> RangeIndicator {
+        let radius = RangeIndicator::ability_range(parent, ability);
+        let ability = Rc::clone(ability);
+        RangeIndicator::new(Kind::Ability(ability), radius, parent)
+    }
+
+    /// Computes the range of the `ability` for the `parent`, including any
+    /// increases from its upgrades and other abilities.  Will panic if the
+    /// ability is not active.
+    pub fn ability_range(parent: &Rc<RefCell<EntityState>>, ability: &Ability) -> f32 {
         let active = ability.active.as_ref().unwrap();
 
         let mut radius = match active.range {
@@ -161,8 +170,7 @@ impl RangeIndicator {
             }
         }
 
-        let ability = Rc::clone(ability);
-        RangeIndicator::new(Kind::Ability(ability), radius, parent)
+        radius
     }
 
     pub fn targeter(radius: f32, parent: &Rc<RefCell<EntityState>>) -> RangeIndicator {
@@ -0,0 +1,162 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::HashMap;
+
+use crate::ChangeListenerList;
+use sulis_module::Module;
+
+/// Tracks everything the party has encountered over the course of the
+/// campaign - creatures (and how many of each have been killed), items,
+/// and any lore entries that have been unlocked.
+pub struct RecordsState {
+    creatures: HashMap<String, CreatureRecord>,
+    items: Vec<String>,
+    lore: Vec<String>,
+
+    pub listeners: ChangeListenerList<RecordsState>,
+}
+
+impl Clone for RecordsState {
+    fn clone(&self) -> RecordsState {
+        RecordsState {
+            creatures: self.creatures.clone(),
+            items: self.items.clone(),
+            lore: self.lore.clone(),
+            listeners: ChangeListenerList::default(),
+        }
+    }
+}
+
+impl Default for RecordsState {
+    fn default() -> RecordsState {
+        let lore = Module::all_lore_entries()
+            .into_iter()
+            .filter(|entry| entry.initially_unlocked)
+            .map(|entry| entry.id.to_string())
+            .collect();
+
+        RecordsState {
+            creatures: HashMap::new(),
+            items: Vec::new(),
+            lore,
+            listeners: ChangeListenerList::default(),
+        }
+    }
+}
+
+impl RecordsState {
+    pub(crate) fn load(data: RecordsSaveState) -> RecordsState {
+        let mut state = RecordsState {
+            creatures: data.creatures,
+            items: data.items,
+            lore: data.lore,
+            listeners: ChangeListenerList::default(),
+        };
+
+        // pick up any entries added to the module since the save was made
+        for entry in Module::all_lore_entries() {
+            if entry.initially_unlocked && !state.is_lore_unlocked(&entry.id) {
+                state.lore.push(entry.id.to_string());
+            }
+        }
+
+        state
+    }
+
+    pub(crate) fn save(&self) -> RecordsSaveState {
+        RecordsSaveState {
+            creatures: self.creatures.clone(),
+            items: self.items.clone(),
+            lore: self.lore.clone(),
+        }
+    }
+
+    pub fn creature(&self, actor_id: &str) -> Option<&CreatureRecord> {
+        self.creatures.get(actor_id)
+    }
+
+    pub fn creatures_iter(&self) -> impl Iterator<Item = (&String, &CreatureRecord)> {
+        self.creatures.iter()
+    }
+
+    /// Returns the IDs of all items the party has acquired, in the
+    /// order they were first acquired
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Returns the IDs of all unlocked lore entries, in the order they
+    /// were unlocked
+    pub fn lore(&self) -> &[String] {
+        &self.lore
+    }
+
+    pub fn is_lore_unlocked(&self, id: &str) -> bool {
+        self.lore.iter().any(|entry| entry == id)
+    }
+
+    pub fn add_creature_seen(&mut self, actor_id: &str) {
+        if self.creatures.contains_key(actor_id) {
+            return;
+        }
+
+        self.creatures
+            .insert(actor_id.to_string(), CreatureRecord::default());
+        self.listeners.notify(self);
+    }
+
+    pub fn add_creature_kill(&mut self, actor_id: &str) {
+        let record = self.creatures.entry(actor_id.to_string()).or_default();
+        record.kills += 1;
+        self.listeners.notify(self);
+    }
+
+    pub fn add_item(&mut self, item_id: &str) {
+        if self.items.iter().any(|id| id == item_id) {
+            return;
+        }
+
+        self.items.push(item_id.to_string());
+        self.listeners.notify(self);
+    }
+
+    /// Unlocks the specified lore entry.  Returns true if the entry was
+    /// newly unlocked, false if it had already been unlocked
+    pub fn unlock_lore(&mut self, id: &str) -> bool {
+        if self.is_lore_unlocked(id) {
+            return false;
+        }
+
+        self.lore.push(id.to_string());
+        self.listeners.notify(self);
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CreatureRecord {
+    pub kills: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RecordsSaveState {
+    pub(crate) creatures: HashMap<String, CreatureRecord>,
+    pub(crate) items: Vec<String>,
+    pub(crate) lore: Vec<String>,
+}
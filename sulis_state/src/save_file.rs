@@ -14,15 +14,16 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
+use std::ffi::OsStr;
 use std::fs::{self, File};
-use std::io::{Error, Read};
+use std::io::{BufWriter, Error, Read, Write};
 use std::path::{Path, PathBuf};
 use std::time;
 
 use chrono::prelude::*;
 
 use crate::{GameState, SaveState};
-use sulis_core::resource::{read_single_resource_path, write_json_to_file};
+use sulis_core::resource::read_single_resource_path;
 use sulis_core::util::invalid_data_error;
 use sulis_core::{config, serde_json, util};
 use sulis_module::Module;
@@ -95,6 +96,8 @@ pub fn create_save() -> Result<(), Error> {
         fs::create_dir_all(path.clone())?;
     }
 
+    remove_incomplete_saves(&path);
+
     path.push(filename);
 
     let meta = create_meta_data(utc.format("%c").to_string());
@@ -113,7 +116,7 @@ pub fn create_save() -> Result<(), Error> {
         util::format_elapsed_secs(start_time.elapsed())
     );
 
-    let result = write_json_to_file(path.as_path(), &save);
+    let result = write_save_file(path.as_path(), &save);
 
     info!(
         "  Save to disk complete in {} secs",
@@ -177,6 +180,80 @@ pub fn has_available_save_files() -> bool {
     false
 }
 
+/// Removes temporary files in `dir` left over from saves that were interrupted
+/// before completing
+fn remove_incomplete_saves(dir: &Path) {
+    let dir_entries = match fs::read_dir(dir) {
+        Err(e) => {
+            warn!("Unable to read save dir {:?}: {}", dir, e);
+            return;
+        }
+        Ok(entries) => entries,
+    };
+
+    for entry in dir_entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() || path.extension() != Some(OsStr::new("tmp")) {
+            continue;
+        }
+
+        info!("Removing incomplete save file {:?}", path);
+        if let Err(e) = fs::remove_file(&path) {
+            warn!("Unable to remove incomplete save file: {}", e);
+        }
+    }
+}
+
+/// Writes the save to a temporary file next to `path`, flushes it to disk, and
+/// verifies that it parses before renaming it into place.  A save interrupted
+/// part way through therefore never leaves a half written file at `path`.
+fn write_save_file(path: &Path, save: &SaveFile) -> Result<(), Error> {
+    let temp_path = path.with_extension("json.tmp");
+
+    let result = write_and_verify(&temp_path, save);
+    if let Err(e) = result {
+        warn!("Save to {:?} failed, removing temporary file", temp_path);
+        if let Err(e) = fs::remove_file(&temp_path) {
+            warn!("Unable to remove temporary save file: {}", e);
+        }
+        return Err(e);
+    }
+
+    replace_file(&temp_path, path)
+}
+
+/// Renames `temp_path` to `path`, then flushes the containing directory so
+/// the rename itself survives a crash
+fn replace_file(temp_path: &Path, path: &Path) -> Result<(), Error> {
+    fs::rename(temp_path, path)?;
+
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+fn write_and_verify(temp_path: &Path, save: &SaveFile) -> Result<(), Error> {
+    let file = File::create(temp_path)?;
+    let mut writer = BufWriter::new(file);
+    if let Err(e) = serde_json::to_writer(&mut writer, save) {
+        return invalid_data_error(&format!("{e}"));
+    }
+    writer.flush()?;
+
+    let file = writer.into_inner().map_err(|e| e.into_error())?;
+    file.sync_all()?;
+
+    if let Err(e) = read_save_file(temp_path) {
+        warn!("Verification of written save file failed");
+        return Err(e);
+    }
+
+    Ok(())
+}
+
 fn read_save_file(path: &Path) -> Result<SaveFile, Error> {
     let mut file = File::open(path)?;
 
@@ -288,3 +365,44 @@ fn time_modified(data: &SaveFileMetaData) -> time::SystemTime {
         Err(_) => time::UNIX_EPOCH,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("sulis_save_file_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn incomplete_saves_are_removed() {
+        let dir = test_dir("incomplete");
+        fs::write(dir.join("save_1.json"), "{}").unwrap();
+        fs::write(dir.join("save_2.json.tmp"), "{").unwrap();
+
+        remove_incomplete_saves(&dir);
+
+        assert!(dir.join("save_1.json").is_file());
+        assert!(!dir.join("save_2.json.tmp").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn replace_file_overwrites_existing_save() {
+        let dir = test_dir("replace");
+        let path = dir.join("save_1.json");
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&path, "old").unwrap();
+        fs::write(&temp_path, "new").unwrap();
+
+        replace_file(&temp_path, &path).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert!(!temp_path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
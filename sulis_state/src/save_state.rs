@@ -20,18 +20,26 @@ use std::io::Error;
 use std::rc::Rc;
 use std::u64;
 
+use sulis_core::config::Difficulty;
+use sulis_core::ui::{color, Color};
 use sulis_core::util::{ExtInt, Point};
 use sulis_module::{
     actor::{ActorBuilder, RewardBuilder},
-    BonusList, ItemListEntrySaveState, ItemSaveState, QuickSlot, Slot,
+    area::PropLock,
+    on_trigger::EpilogueSlide,
+    BonusList, Facing, ItemListEntrySaveState, ItemSaveState, Module, QuickSlot, Rules, Slot,
 };
 
 use crate::animation::AnimSaveState;
-use crate::area_state::TriggerState;
-use crate::script::CallbackData;
+use crate::area_state::{AreaDelta, TriggerState};
+use crate::records_state::RecordsSaveState;
+use crate::script::{CallbackData, TriggeredCallbackSaveState};
+use crate::turn_manager::{EncounterRef, Entry};
 use crate::{
-    effect, prop_state::Interactive, turn_manager::EncounterRef, ActorState, Effect, EntityState,
-    Formation, GameState, Location, MerchantState, PStats, PropState, QuestState, WorldMapState,
+    effect, prop_state::Interactive, AIBehavior, ActorState, BanterState,
+    CrimeState, Effect, EntityState, Formation, GameState, HousingState, Loadout, Location,
+    MapPinState, MerchantState, PStats, PropState, QuestState, WeatherState, WorldEventState,
+    WorldExport, WorldMapState,
 };
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -41,6 +49,9 @@ pub struct SaveState {
     pub(crate) formation: Formation,
     pub(crate) coins: i32,
     pub(crate) stash: Vec<ItemListEntrySaveState>,
+
+    #[serde(default)]
+    pub(crate) keys: Vec<ItemListEntrySaveState>,
     pub(crate) selected: Vec<usize>,
 
     #[serde(default = "default_zoom")]
@@ -48,12 +59,60 @@ pub struct SaveState {
     pub(crate) current_area: String,
     pub(crate) world_map: WorldMapState,
     pub(crate) quests: QuestSaveState,
+
+    #[serde(default)]
+    pub(crate) records: RecordsSaveState,
+
+    #[serde(default)]
+    pub(crate) banters: BanterState,
+
+    #[serde(default)]
+    pub(crate) approval: HashMap<String, i32>,
+
+    #[serde(default)]
+    pub(crate) weather: WeatherState,
+
+    #[serde(default)]
+    pub(crate) thefts: HashMap<String, u32>,
+
+    #[serde(default)]
+    pub(crate) crime: CrimeState,
+
+    #[serde(default)]
+    pub(crate) housing: HousingState,
+
+    #[serde(default)]
+    pub(crate) world_events: WorldEventState,
+
+    #[serde(default)]
+    pub(crate) map_pins: MapPinState,
+
+    #[serde(default)]
+    pub(crate) area_swaps: HashMap<String, String>,
+
+    #[serde(default)]
+    pub(crate) world_import: Option<WorldExport>,
+
+    #[serde(default)]
+    pub(crate) assisted_combat: bool,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) epilogue: Vec<EpilogueSlide>,
     pub(crate) areas: HashMap<String, AreaSaveState>,
     pub(crate) manager: ManagerSaveState,
     pub(crate) anims: Vec<AnimSaveState>,
 
     #[serde(default)]
     pub(crate) total_elapsed_millis: usize,
+
+    /// The rules in effect when the game was saved, used in place of the
+    /// module's rules on load so that module updates don't change a game in
+    /// progress
+    #[serde(default)]
+    pub(crate) rules: Option<Rules>,
+
+    #[serde(default)]
+    pub(crate) difficulty: Option<Difficulty>,
 }
 
 fn default_zoom() -> f32 {
@@ -85,6 +144,7 @@ impl SaveState {
         let formation = formation.borrow().clone();
 
         let stash = GameState::party_stash();
+        let keys = stash.borrow().save_keys();
         let stash = stash.borrow().save();
 
         let quest_state = GameState::quest_state();
@@ -111,14 +171,45 @@ impl SaveState {
             formation,
             coins: GameState::party_coins(),
             stash,
+            keys,
             manager: ManagerSaveState::new(),
             anims: GameState::save_anims(),
             world_map: GameState::world_map(),
             quests: quest_state,
+            records: GameState::records().save(),
+            banters: GameState::banters(),
+            approval: GameState::all_approval(),
+            weather: GameState::weather(),
+            thefts: GameState::all_thefts(),
+            crime: GameState::crime(),
+            housing: GameState::housing(),
+            world_events: GameState::world_events(),
+            map_pins: GameState::map_pins(),
+            area_swaps: GameState::all_area_swaps(),
+            world_import: GameState::world_import(),
+            assisted_combat: GameState::is_assisted_combat(),
+            epilogue: GameState::epilogue(),
             total_elapsed_millis,
+            rules: Some(Module::rules().as_ref().clone()),
+            difficulty: Some(GameState::difficulty()),
+        }
+    }
+
+    /// Returns true if this save stores a rules snapshot that differs from
+    /// the rules currently defined by the module
+    pub fn rules_changed(&self) -> bool {
+        match &self.rules {
+            None => false,
+            Some(rules) => !rules.same_values(&Module::module_rules()),
         }
     }
 
+    /// Discards the stored rules snapshot, so the game uses the module's
+    /// current rules once loaded
+    pub fn use_module_rules(&mut self) {
+        self.rules = None;
+    }
+
     pub fn load(self) -> Result<(), Error> {
         GameState::load(self)
     }
@@ -138,6 +229,24 @@ pub struct ManagerSaveState {
     pub(crate) effects: Vec<EffectSaveState>,
     pub(crate) cur_ai_group_index: usize,
     pub(crate) ai_groups: HashMap<String, EncounterRef>,
+
+    #[serde(default)]
+    pub(crate) entity_generations: Vec<u32>,
+
+    #[serde(default)]
+    pub(crate) combat_active: bool,
+
+    #[serde(default)]
+    pub(crate) order: Vec<Entry>,
+
+    #[serde(default)]
+    pub(crate) delayed_turns: Vec<usize>,
+
+    #[serde(default)]
+    pub(crate) engaged_groups: Vec<usize>,
+
+    #[serde(default)]
+    pub(crate) triggered_callbacks: Vec<TriggeredCallbackSaveState>,
 }
 
 impl ManagerSaveState {
@@ -170,6 +279,12 @@ impl ManagerSaveState {
             effects,
             cur_ai_group_index,
             ai_groups,
+            entity_generations: mgr.entity_generations(),
+            combat_active: mgr.is_combat_active(),
+            order: mgr.save_order(),
+            delayed_turns: mgr.save_delayed_turns(),
+            engaged_groups: mgr.save_engaged_groups(),
+            triggered_callbacks: mgr.save_triggered_cbs(),
         }
     }
 }
@@ -235,6 +350,9 @@ pub struct AreaSaveState {
 
     #[serde(default)]
     pub(crate) seed: u128,
+
+    #[serde(default)]
+    pub(crate) delta: AreaDelta,
 }
 
 impl AreaSaveState {
@@ -286,6 +404,7 @@ impl AreaSaveState {
             triggers,
             merchants,
             seed: area_state.area_gen_seed,
+            delta: area_state.delta.clone(),
         }
     }
 }
@@ -298,6 +417,18 @@ pub struct PropSaveState {
     pub(crate) location: Point,
     pub(crate) active: bool,
     pub(crate) enabled: bool,
+
+    #[serde(default)]
+    pub(crate) owner: Option<String>,
+
+    #[serde(default)]
+    pub(crate) state: Option<String>,
+
+    #[serde(default)]
+    pub(crate) damage: u32,
+
+    #[serde(default)]
+    pub(crate) lock: Option<PropLock>,
 }
 
 impl PropSaveState {
@@ -335,6 +466,10 @@ impl PropSaveState {
             location,
             active: prop_state.is_active(),
             enabled: prop_state.is_enabled(),
+            owner: prop_state.owner.clone(),
+            state: prop_state.visual_state().map(|state| state.id.to_string()),
+            damage: prop_state.damage_taken(),
+            lock: prop_state.lock().cloned(),
         }
     }
 }
@@ -389,14 +524,28 @@ pub struct MerchantSaveState {
 
     #[serde(default)]
     pub(crate) loot_list_id: Option<String>,
+
+    #[serde(default)]
+    pub(crate) haggle_frac: f32,
+
+    #[serde(default)]
+    pub(crate) last_haggle_day: Option<u32>,
 }
 
 impl MerchantSaveState {
     pub fn new(merchant: &MerchantState) -> MerchantSaveState {
+        // buy back is not kept between sessions, so items not bought back
+        // return to the merchant's regular stock
         let items = merchant
             .items()
             .iter()
             .map(|(q, ref it)| ItemListEntrySaveState::new(*q, it))
+            .chain(
+                merchant
+                    .buy_back_items()
+                    .iter()
+                    .map(|(_, ref it)| ItemListEntrySaveState::new(1, it)),
+            )
             .collect();
 
         MerchantSaveState {
@@ -407,6 +556,8 @@ impl MerchantSaveState {
             items,
             refresh_rate_millis: merchant.refresh_rate_millis,
             last_refresh_millis: merchant.last_refresh_millis,
+            haggle_frac: merchant.haggle_frac(),
+            last_haggle_day: merchant.last_haggle_day,
         }
     }
 }
@@ -419,6 +570,10 @@ fn serde_true() -> bool {
 #[serde(deny_unknown_fields)]
 pub struct EntitySaveState {
     pub(crate) index: usize,
+
+    #[serde(default)]
+    pub(crate) generation: u32,
+
     pub(crate) unique_id: String,
     pub(crate) actor_base: Option<ActorBuilder>,
     pub(crate) actor: ActorSaveState,
@@ -433,6 +588,27 @@ pub struct EntitySaveState {
 
     #[serde(default)]
     pub(crate) collapsed_groups: Vec<String>,
+
+    #[serde(default)]
+    pub(crate) ai_behavior: AIBehavior,
+
+    #[serde(default)]
+    pub(crate) facing: Facing,
+
+    #[serde(default)]
+    pub(crate) ai_template: Option<String>,
+
+    #[serde(default)]
+    pub(crate) patrol_route: Vec<Point>,
+
+    #[serde(default)]
+    pub(crate) avoid_hazards: Option<bool>,
+
+    #[serde(default)]
+    pub(crate) party_group: usize,
+
+    #[serde(default)]
+    pub(crate) tint: Option<Color>,
 }
 
 impl EntitySaveState {
@@ -478,12 +654,16 @@ impl EntitySaveState {
                 hue: actor.hue,
                 hair_color: actor.hair_color,
                 skin_color: actor.skin_color,
+                custom_images: actor.custom_images.clone(),
+                custom_colors: actor.custom_colors.clone(),
                 inventory: actor.inventory.clone(),
                 levels,
                 xp: Some(actor.xp),
                 reward,
                 abilities,
+                approval_abilities: actor.approval_abilities.clone(),
                 ai,
+                script: actor.script.clone(),
             })
         } else {
             None
@@ -497,6 +677,7 @@ impl EntitySaveState {
         EntitySaveState {
             unique_id: entity.unique_id().to_string(),
             index: entity.index(),
+            generation: entity.id().generation,
             actor: ActorSaveState::new(&entity.actor),
             location: LocationSaveState::new(&entity.location),
             size: entity.size.id.clone(),
@@ -506,6 +687,17 @@ impl EntitySaveState {
             show_portrait: entity.show_portrait(),
             actor_base,
             collapsed_groups: entity.collapsed_groups(),
+            ai_behavior: entity.ai_behavior(),
+            facing: entity.facing(),
+            ai_template: entity.ai_override_id(),
+            patrol_route: entity.patrol_route().to_vec(),
+            avoid_hazards: entity.avoid_hazards_override(),
+            party_group: entity.party_group(),
+            tint: if entity.tint() == color::WHITE {
+                None
+            } else {
+                Some(entity.tint())
+            },
         }
     }
 }
@@ -536,6 +728,9 @@ pub struct ActorSaveState {
     pub(crate) quick: Vec<Option<ItemSaveState>>,
     pub(crate) ability_states: HashMap<String, AbilitySaveState>,
     pub(crate) p_stats: PStats,
+
+    #[serde(default)]
+    pub(crate) loadouts: HashMap<Loadout, HashMap<Slot, ItemSaveState>>,
 }
 
 impl ActorSaveState {
@@ -564,16 +759,29 @@ impl ActorSaveState {
                 id.to_string(),
                 AbilitySaveState {
                     remaining_duration: ability_state.remaining_duration(),
+                    cur_duration: ability_state.cur_duration,
                 },
             );
         }
 
+        let mut loadouts = HashMap::new();
+        for loadout in Loadout::iter() {
+            if let Some(items) = actor_state.inventory().loadout(*loadout) {
+                let items = items
+                    .iter()
+                    .map(|(slot, item)| (*slot, ItemSaveState::new(item)))
+                    .collect();
+                loadouts.insert(*loadout, items);
+            }
+        }
+
         ActorSaveState {
             id: actor_state.actor.id.to_string(),
             equipped,
             quick,
             ability_states,
             p_stats: actor_state.clone_p_stats(),
+            loadouts,
         }
     }
 }
@@ -582,4 +790,8 @@ impl ActorSaveState {
 #[serde(deny_unknown_fields)]
 pub struct AbilitySaveState {
     pub(crate) remaining_duration: ExtInt,
+
+    // elapsed time in the current cooldown, used to fire round boundaries
+    #[serde(default)]
+    pub(crate) cur_duration: u32,
 }
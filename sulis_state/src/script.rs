@@ -28,7 +28,9 @@
 //! 3. Ability Scripts: These are called when activating an ability or when an active ability
 //!    meets certain conditions.  The entry point for the script is `on_activate(parent, ability)`.
 //!    When using a targeter, `on_target_select(parent, ability, targets)` is the return from that
-//!    targeter.
+//!    targeter.  An ability script may also define `preview(parent, ability)`, returning a string
+//!    shown when hovering over the ability, such as the damage it would deal with the parent's
+//!    current stats.  The preview must not modify any game state.
 //! 4. Item Scripts: Similar to ability scripts, but called when using an item.  The entry point is
 //!    `on_activate(parent, item)`.
 //!
@@ -46,14 +48,21 @@ pub use self::area_targeter::AreaTargeter;
 mod module_export;
 pub use self::module_export::ModuleExport;
 
+mod script_world_export;
+pub use self::script_world_export::ScriptWorldExport;
+
 mod script_ability;
 pub use self::script_ability::{ScriptAbility, ScriptAbilitySet};
 
+mod script_area;
+pub use self::script_area::ScriptArea;
+
 pub mod script_cache;
 
 pub mod script_callback;
 pub use self::script_callback::{
     CallbackData, FuncKind, ScriptCallback, ScriptHitKind, TriggeredCallback,
+    TriggeredCallbackSaveState,
 };
 
 mod script_effect;
@@ -67,6 +76,9 @@ pub use self::script_entity::ScriptEntity;
 mod script_entity_set;
 pub use self::script_entity_set::ScriptEntitySet;
 
+mod script_encounter;
+pub use self::script_encounter::ScriptEncounter;
+
 mod script_interface;
 pub use self::script_interface::{entity_with_id, ScriptInterface};
 
@@ -76,6 +88,18 @@ pub use self::script_inventory::{ScriptInventory, ScriptStashItem, ScriptUsableI
 mod script_item;
 pub use self::script_item::{ScriptItem, ScriptItemKind};
 
+mod script_loot;
+pub use self::script_loot::ScriptLoot;
+
+mod script_attack_roll;
+pub use self::script_attack_roll::ScriptAttackRoll;
+
+mod script_prop;
+pub use self::script_prop::ScriptProp;
+
+mod script_error;
+pub use self::script_error::ScriptError;
+
 mod script_menu;
 pub use self::script_menu::ScriptMenu;
 
@@ -104,9 +128,15 @@ use std::time;
 
 use rlua::{self, FromLuaMulti, Function, Lua, ToLuaMulti};
 
-use crate::{ai, EntityState, GameState};
-use sulis_core::{config::Config, util::Point};
-use sulis_module::{Ability, DamageKind, HitKind, Module, QuickSlot};
+use crate::{ai, AutoPause, AutoPauseTrigger, EntityId, EntityState, GameState};
+use sulis_core::{
+    config::{Config, Difficulty},
+    util::Point,
+};
+use sulis_module::{
+    Ability, AccuracyKind, Actor, AttackBonuses, DamageKind, Encounter, HitKind, ItemState,
+    Module, QuickSlot,
+};
 
 pub type Result<T> = std::result::Result<T, rlua::Error>;
 
@@ -225,16 +255,31 @@ impl Script {
         }
     }
 
-    pub fn ability_on_deactivate(parent: usize, ability: &Rc<Ability>) {
+    pub fn ability_on_deactivate(parent: EntityId, ability: &Rc<Ability>) {
         if let Err(e) = script_cache::ability_on_deactivate(parent, ability) {
             warn!("Error in ability on_deactivate: {}", e);
         }
     }
 
-    pub fn ability_on_activate(parent: usize, func: String, ability: &Rc<Ability>) {
+    pub fn ability_on_activate(parent: EntityId, func: String, ability: &Rc<Ability>) {
         if let Err(e) = script_cache::ability_on_activate(parent, func, ability) {
             warn!("Error in ability on_activate: {}", e);
         }
+
+        let entity = match GameState::turn_manager().borrow().entity_by_id(parent) {
+            None => return,
+            Some(entity) => entity,
+        };
+
+        // abilities using a targeter finish once the target is selected
+        let area_state = GameState::area_state();
+        let targeting = match area_state.borrow().targeter() {
+            None => false,
+            Some(targeter) => Rc::ptr_eq(targeter.borrow().parent(), &entity),
+        };
+        if !targeting {
+            check_ability_auto_pause(&entity);
+        }
     }
 
     pub fn ability_on_target_select(
@@ -257,6 +302,8 @@ impl Script {
         ) {
             warn!("Error in ability on target select '{}': {}", func, e);
         }
+
+        check_ability_auto_pause(parent);
     }
 
     pub fn ability_with_attack_data(
@@ -299,6 +346,21 @@ impl Script {
         }
     }
 
+    /// Returns the text from the ability script's `preview` function for the
+    /// `parent`, or None if the script does not define it
+    pub fn ability_preview(
+        parent: &Rc<RefCell<EntityState>>,
+        ability: &Rc<Ability>,
+    ) -> Option<String> {
+        match script_cache::ability_preview(parent, ability) {
+            Err(e) => {
+                warn!("Error in ability preview: {}", e);
+                None
+            }
+            Ok(preview) => preview,
+        }
+    }
+
     pub fn trigger<Arg>(script_id: &str, func: &str, arg: Arg)
     where
         Arg: for<'a> ToLuaMulti<'a>,
@@ -307,6 +369,106 @@ impl Script {
             warn!("Error in trigger script '{}/{}': {}", script_id, func, e);
         }
     }
+
+    /// Passes newly generated loot through the campaign's
+    /// `on_loot_generated` script, if any, returning the modified loot.
+    pub fn loot_generated(level: u32, items: Vec<(u32, ItemState)>) -> Vec<(u32, ItemState)> {
+        let script_data = match Module::campaign().on_loot_generated_script {
+            None => return items,
+            Some(ref data) => data.clone(),
+        };
+
+        let loot = ScriptLoot::new(level, &items);
+        let (id, func) = (&script_data.id, &script_data.func);
+        if let Err(e) = script_cache::trigger_script(id, func, loot.clone()) {
+            warn!("Error in loot script '{}/{}': {}", id, func, e);
+            return items;
+        }
+        loot.create_items()
+    }
+
+    /// Passes the parameters of an attack through the campaign's
+    /// `on_attack_roll` script, if any, before the attack roll is made.
+    /// Any changes to accuracy and damage multipliers are applied to
+    /// `bonuses`.  Returns the defense to roll against.
+    pub fn attack_roll(
+        parent: &Rc<RefCell<EntityState>>,
+        target: &Rc<RefCell<EntityState>>,
+        kind: &str,
+        accuracy_kind: AccuracyKind,
+        defense: i32,
+        bonuses: &mut AttackBonuses,
+    ) -> i32 {
+        let script_data = match Module::campaign().on_attack_roll_script {
+            None => return defense,
+            Some(ref data) => data.clone(),
+        };
+
+        let (accuracy, multipliers) = {
+            let stats = &parent.borrow().actor.stats;
+            let accuracy = match accuracy_kind {
+                AccuracyKind::Melee => stats.melee_accuracy + bonuses.melee_accuracy,
+                AccuracyKind::Ranged => stats.ranged_accuracy + bonuses.ranged_accuracy,
+                AccuracyKind::Spell => stats.spell_accuracy + bonuses.spell_accuracy,
+            };
+            let multipliers = (
+                stats.graze_multiplier + bonuses.graze_multiplier,
+                stats.hit_multiplier + bonuses.hit_multiplier,
+                stats.crit_multiplier + bonuses.crit_multiplier,
+            );
+            (accuracy, multipliers)
+        };
+
+        let roll = ScriptAttackRoll::new(
+            ScriptEntity::from(parent),
+            ScriptEntity::from(target),
+            kind,
+            accuracy,
+            defense,
+            multipliers,
+        );
+        let (id, func) = (&script_data.id, &script_data.func);
+        if let Err(e) = script_cache::trigger_script(id, func, roll.clone()) {
+            warn!("Error in attack roll script '{}/{}': {}", id, func, e);
+            return defense;
+        }
+
+        let accuracy_delta = roll.accuracy() - accuracy;
+        match accuracy_kind {
+            AccuracyKind::Melee => bonuses.melee_accuracy += accuracy_delta,
+            AccuracyKind::Ranged => bonuses.ranged_accuracy += accuracy_delta,
+            AccuracyKind::Spell => bonuses.spell_accuracy += accuracy_delta,
+        }
+
+        let (graze, hit, crit) = roll.multipliers();
+        bonuses.graze_multiplier += graze - multipliers.0;
+        bonuses.hit_multiplier += hit - multipliers.1;
+        bonuses.crit_multiplier += crit - multipliers.2;
+
+        roll.defense()
+    }
+
+    /// Passes the actors generated for a spawning encounter through the
+    /// encounter's `on_spawn_script`, if any, returning the modified actors.
+    pub fn encounter_spawned(
+        encounter: &Encounter,
+        difficulty: Difficulty,
+        party_size: u32,
+        actors: Vec<(Rc<Actor>, Option<String>)>,
+    ) -> Vec<(Rc<Actor>, Option<String>)> {
+        let script_data = match encounter.on_spawn_script {
+            None => return actors,
+            Some(ref data) => data,
+        };
+
+        let enc = ScriptEncounter::new(&encounter.id, difficulty, party_size, &actors);
+        let (id, func) = (&script_data.id, &script_data.func);
+        if let Err(e) = script_cache::trigger_script(id, func, enc.clone()) {
+            warn!("Error in encounter script '{}/{}': {}", id, func, e);
+            return actors;
+        }
+        enc.create_actors()
+    }
 }
 
 const MEM_LIMIT: usize = 10_485_760;
@@ -447,6 +609,13 @@ impl ScriptState {
         result
     }
 
+    pub(in crate::script) fn has_func(&self, function: &str) -> bool {
+        self.lua.context(|lua| {
+            let value: rlua::Value = lua.globals().get(function).unwrap_or(rlua::Value::Nil);
+            matches!(value, rlua::Value::Function(_))
+        })
+    }
+
     pub fn console(&self, script: String, party: &[Rc<RefCell<EntityState>>]) -> Result<String> {
         assert!(!party.is_empty());
         self.reset_instruction_state();
@@ -467,6 +636,12 @@ impl ScriptState {
     }
 }
 
+fn check_ability_auto_pause(parent: &Rc<RefCell<EntityState>>) {
+    if !parent.borrow().is_party_member() {
+        AutoPause::trigger(AutoPauseTrigger::SpellCast);
+    }
+}
+
 fn get_targeter() -> Result<Rc<RefCell<AreaTargeter>>> {
     let area_state = GameState::area_state();
     let area_state = area_state.borrow();
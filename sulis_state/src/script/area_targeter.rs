@@ -19,15 +19,15 @@ use std::f32::consts::PI;
 use std::rc::Rc;
 
 use sulis_core::image::Image;
-use sulis_core::io::{DrawList, GraphicsRenderer};
-use sulis_core::ui::{animation_state, color, Cursor, LineRenderer};
-use sulis_core::util::{Offset, Point, Rect, Scale};
-use sulis_module::{Ability, Module, ObjectSize};
+use sulis_core::io::{Audio, DrawList, GraphicsRenderer};
+use sulis_core::ui::{animation_state, color, Color, Cursor, LineRenderer};
+use sulis_core::util::{hex, Offset, Point, Rect, Scale};
+use sulis_module::{Ability, DamageKind, Module, ObjectSize, OnTrigger, TargetingSounds};
 
 use crate::script::{targeter, ScriptItemKind, TargeterData};
 use crate::{
-    area_feedback_text::Params, center_i32, dist, is_within, AreaState, EntityState, GameState,
-    RangeIndicator, Script, TurnManager,
+    area_feedback_text::Params, center_i32, dist, is_within, AreaState, EntityId, EntityState,
+    GameState, RangeIndicator, Script, TurnManager,
 };
 
 #[derive(Clone)]
@@ -587,6 +587,7 @@ impl Shape {
         let pos = Point::new(origin_x.trunc() as i32, origin_y.trunc() as i32);
         let origin = (origin_x, origin_y);
         let angle = (to.y as f32 - origin_y).atan2(to.x as f32 - origin_x);
+        let hex_grid = Module::rules().is_hex_grid();
 
         let mut points = Vec::new();
 
@@ -599,12 +600,21 @@ impl Shape {
                 let y1 = y as i32 + pos.y - r;
                 let p = Point::new(x1, y1);
 
-                let dist = dist(&origin, &p);
+                let (dist, cur_angle) = if hex_grid {
+                    // measure angles between hex centers, as odd columns are offset
+                    let (cx, cy) = hex::center(p.x, p.y);
+                    let (ox, oy) = hex::center(pos.x, pos.y);
+                    let dist = hex::distance(pos.x, pos.y, p.x, p.y) as f32;
+                    (dist, (cy - oy).atan2(cx - ox))
+                } else {
+                    let cur_angle = (y as f32 - r as f32).atan2(x as f32 - r as f32);
+                    (dist(&origin, &p), cur_angle)
+                };
+
                 if dist > radius || dist < min_radius {
                     continue;
                 }
 
-                let cur_angle = (y as f32 - r as f32).atan2(x as f32 - r as f32);
                 let angle_diff = (angle - cur_angle + 3.0 * PI) % (2.0 * PI) - PI;
                 if angle_diff.abs() > angular_size / 2.0 {
                     continue;
@@ -626,6 +636,7 @@ impl Shape {
         _area_state: &AreaState,
     ) -> Vec<Point> {
         let origin = (pos.x as f32 - shift, pos.y as f32 - shift);
+        let hex_grid = Module::rules().is_hex_grid();
         let mut points = Vec::new();
 
         let r = (radius + 2.0).ceil() as i32;
@@ -637,7 +648,11 @@ impl Shape {
                 let y1 = y as i32 + pos.y - r;
                 let p = Point::new(x1, y1);
 
-                let dist = dist(&origin, &p);
+                let dist = if hex_grid {
+                    hex::distance(pos.x, pos.y, p.x, p.y) as f32
+                } else {
+                    dist(&origin, &p)
+                };
 
                 if dist <= radius && dist >= min_radius {
                     points.push(p);
@@ -680,6 +695,7 @@ pub struct AreaTargeter {
     selectable: Vec<Rc<RefCell<EntityState>>>,
     effectable: Vec<Rc<RefCell<EntityState>>>,
     max_effectable: Option<usize>,
+    multi_select: Option<usize>,
     shape: Shape,
     show_mouseover: bool,
     free_select: Option<f32>,
@@ -689,6 +705,13 @@ pub struct AreaTargeter {
     allow_affected_points_invis: bool,
     impass_blocks_affected_points: bool,
     invis_blocks_affected_points: bool,
+    penetrates_walls: bool,
+    selectable_color: Option<Color>,
+    effectable_color: Option<Color>,
+    invalid_message: Option<String>,
+    damage_kind: Option<DamageKind>,
+    cursor: Option<Rc<dyn Image>>,
+    sounds: TargetingSounds,
 
     free_select_valid: bool,
     cur_target: Option<Rc<RefCell<EntityState>>>,
@@ -697,21 +720,26 @@ pub struct AreaTargeter {
     cur_points: Vec<Point>,
     cur_effected: Vec<Rc<RefCell<EntityState>>>,
 
+    // accumulated state for multi select targeters
+    multi_selected: Vec<Rc<RefCell<EntityState>>>,
+    multi_effected: Vec<Rc<RefCell<EntityState>>>,
+    multi_points: Vec<Point>,
+
     cancel: bool,
 }
 
 fn create_entity_state_vec(
     mgr: &TurnManager,
-    input: &[Option<usize>],
+    input: &[Option<EntityId>],
 ) -> Vec<Rc<RefCell<EntityState>>> {
     let mut out = Vec::new();
-    for index in input.iter() {
-        let index = match index {
+    for id in input.iter() {
+        let id = match id {
             None => continue,
-            Some(index) => *index,
+            Some(id) => *id,
         };
 
-        match mgr.entity_checked(index) {
+        match mgr.entity_by_id(id) {
             None => (),
             Some(entity) => out.push(entity),
         }
@@ -735,7 +763,7 @@ impl AreaTargeter {
             },
         };
 
-        let parent = mgr.entity(data.parent);
+        let parent = mgr.entity(data.parent.index);
 
         let script_source = match &data.kind {
             targeter::Kind::Ability(ref id) => ScriptSource::Ability(Module::ability(id).unwrap()),
@@ -754,6 +782,17 @@ impl AreaTargeter {
             }
         };
 
+        let (cursor, sounds) = match &script_source {
+            ScriptSource::Ability(ability) => match &ability.active {
+                None => (None, TargetingSounds::default()),
+                Some(active) => (
+                    active.targeting_cursor.clone(),
+                    active.targeting_sounds.clone(),
+                ),
+            },
+            ScriptSource::Item { .. } => (None, TargetingSounds::default()),
+        };
+
         let range_indicator = match data.selection_area {
             targeter::SelectionArea::None => None,
             targeter::SelectionArea::Radius(radius) => {
@@ -778,13 +817,14 @@ impl AreaTargeter {
             on_target_select_func: data.on_target_select_func.to_string(),
             on_target_select_custom_target: match data.on_target_select_custom_target {
                 None => None,
-                Some(index) => mgr.entity_checked(index),
+                Some(id) => mgr.entity_by_id(id),
             },
             script_source,
             parent,
             selectable: create_entity_state_vec(&mgr, &data.selectable),
             effectable: create_entity_state_vec(&mgr, &data.effectable),
             max_effectable: data.max_effectable,
+            multi_select: data.multi_select,
             cancel: false,
             free_select: data.free_select,
             range_indicator,
@@ -793,6 +833,13 @@ impl AreaTargeter {
             allow_affected_points_invis: data.allow_affected_points_invis,
             impass_blocks_affected_points: data.impass_blocks_affected_points,
             invis_blocks_affected_points: data.invis_blocks_affected_points,
+            penetrates_walls: data.penetrates_walls,
+            selectable_color: data.selectable_color,
+            effectable_color: data.effectable_color,
+            invalid_message: data.invalid_message.clone(),
+            damage_kind: data.damage_kind,
+            cursor,
+            sounds,
             free_select_valid: false,
             show_mouseover: data.show_mouseover,
             cur_target: None,
@@ -800,6 +847,9 @@ impl AreaTargeter {
             cursor_offset: Point::default(),
             cur_points: Vec::new(),
             cur_effected: Vec::new(),
+            multi_selected: Vec::new(),
+            multi_effected: Vec::new(),
+            multi_points: Vec::new(),
             shape: data.shape.clone(),
         }
     }
@@ -820,9 +870,22 @@ impl AreaTargeter {
         DrawList::from_sprite_f32(&target.size.cursor_sprite, rect)
     }
 
+    fn invis_blocks(&self) -> bool {
+        if self.invis_blocks_affected_points {
+            return true;
+        }
+
+        // area shapes are clipped by walls unless the effect goes through them
+        match self.shape {
+            Shape::Circle { .. } | Shape::Cone { .. } => !self.penetrates_walls,
+            _ => false,
+        }
+    }
+
     fn calculate_points(&mut self) {
         self.cur_points.clear();
         self.cur_effected.clear();
+        let invis_blocks = self.invis_blocks();
 
         if self.free_select.is_none() {
             let target = match self.cur_target {
@@ -845,7 +908,7 @@ impl AreaTargeter {
                 self.allow_affected_points_impass,
                 self.allow_affected_points_invis,
                 self.impass_blocks_affected_points,
-                self.invis_blocks_affected_points,
+                invis_blocks,
             );
             self.cur_effected =
                 self.shape
@@ -862,7 +925,7 @@ impl AreaTargeter {
                 self.allow_affected_points_impass,
                 self.allow_affected_points_invis,
                 self.impass_blocks_affected_points,
-                self.invis_blocks_affected_points,
+                invis_blocks,
             );
             self.cur_effected =
                 self.shape
@@ -923,10 +986,40 @@ impl AreaTargeter {
         }
     }
 
+    /// The kind of damage dealt by the ability or item using this targeter, if known
+    pub fn damage_kind(&self) -> Option<DamageKind> {
+        self.damage_kind
+    }
+
     pub fn cancel(&self) -> bool {
         self.cancel
     }
 
+    /// Shows this targeter's custom cursor, if it has one and is being
+    /// used by the player
+    pub(crate) fn apply_cursor(&self) {
+        if self.cursor.is_some() && self.parent.borrow().is_party_member() {
+            Cursor::set_cursor_image(self.cursor.clone());
+        }
+    }
+
+    /// Restores the default cursor if this targeter replaced it
+    pub(crate) fn clear_cursor(&self) {
+        if self.cursor.is_some() && self.parent.borrow().is_party_member() {
+            Cursor::set_cursor_image(None);
+        }
+    }
+
+    fn play_sound(&self, sound: &Option<String>) {
+        if !self.parent.borrow().is_party_member() {
+            return;
+        }
+
+        if let Some(id) = sound {
+            Audio::play_sfx(id, 1.0);
+        }
+    }
+
     pub fn draw(
         &mut self,
         renderer: &mut dyn GraphicsRenderer,
@@ -948,6 +1041,9 @@ impl AreaTargeter {
 
         if !draw_list.is_empty() {
             draw_list.set_scale(scale);
+            if let Some(color) = self.selectable_color {
+                draw_list.set_color(color);
+            }
             renderer.draw(draw_list);
         }
 
@@ -955,12 +1051,18 @@ impl AreaTargeter {
         for target in self.cur_effected.iter() {
             draw_list.append(&mut self.draw_target(target, offset));
         }
+        for target in self.multi_effected.iter() {
+            if contains(target, &self.cur_effected) {
+                continue;
+            }
+            draw_list.append(&mut self.draw_target(target, offset));
+        }
         draw_list.set_scale(scale);
-        draw_list.set_color(color::RED);
+        draw_list.set_color(self.effectable_color.unwrap_or(color::RED));
         renderer.draw(draw_list);
 
         let mut draw_list = DrawList::empty_sprite();
-        for p in self.cur_points.iter() {
+        for p in self.cur_points.iter().chain(self.multi_points.iter()) {
             let rect = Rect {
                 x: p.x as f32 - offset.x,
                 y: p.y as f32 - offset.y,
@@ -970,6 +1072,9 @@ impl AreaTargeter {
             tile.append_to_draw_list(&mut draw_list, &animation_state::NORMAL, rect, millis);
         }
         draw_list.set_scale(scale);
+        if let Some(color) = self.effectable_color {
+            draw_list.set_color(color);
+        }
         renderer.draw(draw_list);
 
         if let ScriptSource::Ability(ability) = &self.script_source {
@@ -1031,7 +1136,8 @@ impl AreaTargeter {
     ) -> Option<&Rc<RefCell<EntityState>>> {
         self.cursor_pos = Point::new(cursor_x, cursor_y);
         self.cursor_offset = self.shape.get_cursor_offset();
-        self.cur_target = None;
+        let prev_target = self.cur_target.take();
+        let was_valid = self.free_select_valid;
 
         for target in self.selectable.iter() {
             {
@@ -1053,6 +1159,19 @@ impl AreaTargeter {
         self.free_select_valid = self.compute_free_select_valid();
         self.calculate_points();
 
+        let became_valid = if self.free_select.is_none() {
+            match (&prev_target, &self.cur_target) {
+                (_, None) => false,
+                (None, Some(_)) => true,
+                (Some(prev), Some(cur)) => !Rc::ptr_eq(prev, cur),
+            }
+        } else {
+            !was_valid && self.free_select_valid
+        };
+        if became_valid {
+            self.play_sound(&self.sounds.valid);
+        }
+
         let kind = if self.free_select.is_none() {
             match self.cur_target {
                 None => animation_state::Kind::MouseInvalid,
@@ -1076,6 +1195,54 @@ impl AreaTargeter {
         self.cancel = true;
     }
 
+    /// For multi select targeters, removes the most recent selection.  Otherwise,
+    /// or if nothing has been selected, cancels the targeter.
+    pub fn on_cancel_last(&mut self) {
+        if self.multi_select_max().is_none() || self.multi_selected.pop().is_none() {
+            self.on_cancel();
+            return;
+        }
+
+        // rebuild the accumulated state from the remaining selections
+        let selected = std::mem::take(&mut self.multi_selected);
+        self.multi_effected.clear();
+        self.multi_points.clear();
+        for target in selected {
+            self.select_target(&target);
+        }
+    }
+
+    fn multi_select_max(&self) -> Option<usize> {
+        if self.free_select.is_some() {
+            None
+        } else {
+            self.multi_select
+        }
+    }
+
+    fn select_target(&mut self, target: &Rc<RefCell<EntityState>>) {
+        let (prev_target, prev_points, prev_effected) = (
+            self.cur_target.replace(Rc::clone(target)),
+            std::mem::take(&mut self.cur_points),
+            std::mem::take(&mut self.cur_effected),
+        );
+        self.calculate_points();
+
+        for entity in self.cur_effected.drain(..) {
+            if !contains(&entity, &self.multi_effected) {
+                self.multi_effected.push(entity);
+            }
+        }
+        self.multi_points.append(&mut self.cur_points);
+        self.multi_points.sort();
+        self.multi_points.dedup();
+        self.multi_selected.push(Rc::clone(target));
+
+        self.cur_target = prev_target;
+        self.cur_points = prev_points;
+        self.cur_effected = prev_effected;
+    }
+
     pub fn is_free_select(&self) -> bool {
         self.free_select.is_some()
     }
@@ -1088,6 +1255,17 @@ impl AreaTargeter {
         &self.cur_effected
     }
 
+    fn show_invalid_message(&self) {
+        if self.cancel || !self.parent.borrow().is_party_member() {
+            return;
+        }
+
+        if let Some(message) = &self.invalid_message {
+            let cb = OnTrigger::SayLine(message.to_string());
+            GameState::add_ui_callback(vec![cb], &self.parent, &self.parent);
+        }
+    }
+
     pub fn is_valid_to_activate(&self) -> bool {
         if self.cancel() {
             return false;
@@ -1102,17 +1280,32 @@ impl AreaTargeter {
 
     pub fn on_activate(&mut self) {
         if !self.is_valid_to_activate() {
+            self.play_sound(&self.sounds.invalid);
+            self.show_invalid_message();
             return;
         }
 
+        let multi_select = self.multi_select_max();
+        if let Some(max) = multi_select {
+            // clicking a target that is already selected completes the selection
+            let target = Rc::clone(self.cur_target.as_ref().unwrap());
+            if !contains(&target, &self.multi_selected) {
+                self.select_target(&target);
+                if self.multi_selected.len() < max {
+                    return;
+                }
+            }
+        }
+
         self.parent.borrow().explore_self_location();
         self.cancel = true;
+        self.play_sound(&self.sounds.activate);
 
-        let affected = self
-            .cur_effected
-            .iter()
-            .map(|e| Some(Rc::clone(e)))
-            .collect();
+        let (effected, points) = match multi_select {
+            None => (&self.cur_effected, self.cur_points.clone()),
+            Some(_) => (&self.multi_effected, self.multi_points.clone()),
+        };
+        let affected = effected.iter().map(|e| Some(Rc::clone(e))).collect();
 
         let mut pos = self.cursor_pos;
         if let Some(ref size) = self.free_select_must_be_passable {
@@ -1120,7 +1313,6 @@ impl AreaTargeter {
             pos.y -= size.height / 2;
         }
 
-        let points = self.cur_points.clone();
         let func = &self.on_target_select_func;
         let custom_target = self.on_target_select_custom_target.clone();
         info!("on target select script");
@@ -15,13 +15,12 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use std::cell::RefCell;
-use std::cmp;
 use std::rc::Rc;
 
 use rlua::{self, Context, UserData, UserDataMethods};
 
 use crate::script::{CallbackData, ScriptEntity};
-use crate::{area_feedback_text::ColorKind, AreaFeedbackText, EntityState, GameState};
+use crate::{area_feedback_text::ColorKind, AreaFeedbackText, EntityId, EntityState, GameState};
 use sulis_module::{
     ability::{self, AIData, Range},
     Ability, Module,
@@ -96,13 +95,13 @@ type Result<T> = std::result::Result<T, rlua::Error>;
 /// ```
 #[derive(Clone)]
 pub struct ScriptAbilitySet {
-    pub parent: usize,
+    pub parent: EntityId,
     pub abilities: Vec<ScriptAbility>,
 }
 
 impl ScriptAbilitySet {
     pub fn from(entity: &Rc<RefCell<EntityState>>) -> ScriptAbilitySet {
-        let parent = entity.borrow().index();
+        let parent = entity.borrow().id();
         let mut abilities = Vec::new();
         for (id, _) in entity.borrow().actor.ability_states.iter() {
             let ability = Module::ability(id).unwrap();
@@ -297,7 +296,6 @@ pub struct ScriptAbility {
     pub id: String,
     name: String,
     duration: u32,
-    ap: u32,
     range: Range,
     ai_data: AIData,
 }
@@ -324,16 +322,15 @@ impl ScriptAbility {
             }
         };
 
-        let (range, ap) = match ability.active {
-            None => (Range::None, 0),
-            Some(ref active) => (active.range, active.ap),
+        let range = match ability.active {
+            None => Range::None,
+            Some(ref active) => active.range,
         };
 
         ScriptAbility {
             id: ability.id.to_string(),
             name: ability.name.to_string(),
             duration,
-            ap,
             ai_data,
             range,
         }
@@ -401,8 +398,8 @@ impl UserData for ScriptAbility {
 
         methods.add_method("create_callback", |_, ability, parent: ScriptEntity| {
             ability.error_if_not_active()?;
-            let index = parent.try_unwrap_index()?;
-            let cb_data = CallbackData::new_ability(index, &ability.id);
+            let id = parent.try_unwrap_id()?;
+            let cb_data = CallbackData::new_ability(id, &ability.id);
             Ok(cb_data)
         });
 
@@ -453,14 +450,12 @@ fn activate(
     ability.error_if_not_active()?;
     let entity = target.try_unwrap()?;
     let take_ap = take_ap.unwrap_or(true);
-    let base_ap = ability.ap as i32;
 
     let ability = ability.to_ability();
     let mgr = GameState::turn_manager();
     if take_ap && mgr.borrow().is_combat_active() {
-        let bonus = entity.borrow().actor.stats.bonus_ability_action_point_cost;
-        let ap = cmp::max(0, base_ap - bonus);
-        entity.borrow_mut().actor.remove_ap(ap as u32);
+        let ap = entity.borrow().actor.ability_ap_cost(&ability);
+        entity.borrow_mut().actor.remove_ap(ap);
         entity.borrow_mut().actor.remove_class_stats(&ability);
     }
 
@@ -0,0 +1,155 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rlua::{UserData, UserDataMethods};
+
+use crate::{script::ScriptError, AreaState, GameState, Location};
+use sulis_core::util::Point;
+use sulis_module::{area::PropData, Module};
+
+/// An area that scripts may modify.  Changes to terrain and props are saved
+/// with the area, and so persist for the rest of the campaign.  Normally
+/// created by `game:area()`
+///
+/// # `id() -> String`
+/// Returns the ID of this area.
+///
+/// # `set_tile(layer: String, x: Int, y: Int, tile_id: String (Optional))`
+/// Replaces the tiles in the specified `layer` at `x`, `y` with the tile with
+/// `tile_id`, or removes them if no tile is specified.  The tile must belong to
+/// the layer.  Passability and visibility are recomputed from the new tiles.
+///
+/// # `set_passable(x: Int, y: Int, passable: Bool)`
+/// Overrides whether creatures may move through `x`, `y`, regardless of the tiles
+/// there.  Use together with `set_tile` for collapsing bridges or secret walls.
+///
+/// # `add_prop(id: String, x: Int, y: Int, enabled: Bool (Optional)) -> Int`
+/// Places the prop with the specified `id` at `x`, `y`, enabled unless `enabled`
+/// is false, and returns its index.
+///
+/// # `remove_prop(index: Int)`
+/// Removes the prop with the specified `index` from this area.
+///
+/// # `prop_index_at(x: Int, y: Int) -> Int`
+/// Returns the index of the prop at `x`, `y`, or nil if there is none.
+#[derive(Clone)]
+pub struct ScriptArea {
+    id: String,
+}
+
+impl ScriptArea {
+    pub fn new(id: String) -> ScriptArea {
+        ScriptArea { id }
+    }
+
+    fn try_unwrap(&self) -> Result<Rc<RefCell<AreaState>>, rlua::Error> {
+        GameState::get_area_state(&self.id).ok_or_else(|| {
+            let msg = format!("The area '{}' is not loaded", self.id);
+            ScriptError::InvalidArgument(msg).into()
+        })
+    }
+}
+
+fn check_coords(area: &AreaState, x: i32, y: i32) -> Result<(), rlua::Error> {
+    if area.area.area.coords_valid(x, y) {
+        Ok(())
+    } else {
+        let msg = format!("{},{} is outside of area '{}'", x, y, area.area.area.id);
+        Err(ScriptError::OutOfRange(msg).into())
+    }
+}
+
+fn area_error(e: std::io::Error) -> rlua::Error {
+    rlua::Error::FromLuaConversionError {
+        from: "ScriptArea",
+        to: "AreaState",
+        message: Some(e.to_string()),
+    }
+}
+
+impl UserData for ScriptArea {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("id", |_, area, ()| Ok(area.id.to_string()));
+
+        methods.add_method(
+            "set_tile",
+            |_, area, (layer, x, y, tile_id): (String, i32, i32, Option<String>)| {
+                let area = area.try_unwrap()?;
+                let mut area = area.borrow_mut();
+                check_coords(&area, x, y)?;
+                area.set_tile(&layer, x, y, tile_id.as_deref())
+                    .map_err(area_error)
+            },
+        );
+
+        methods.add_method(
+            "set_passable",
+            |_, area, (x, y, passable): (i32, i32, bool)| {
+                let area = area.try_unwrap()?;
+                let mut area = area.borrow_mut();
+                check_coords(&area, x, y)?;
+                area.set_passable(x, y, passable).map_err(area_error)
+            },
+        );
+
+        methods.add_method(
+            "add_prop",
+            |_, area, (id, x, y, enabled): (String, i32, i32, Option<bool>)| {
+                let prop = Module::prop(&id).ok_or_else(|| {
+                    ScriptError::InvalidArgument(format!("No prop with ID '{id}'"))
+                })?;
+
+                let area = area.try_unwrap()?;
+                let mut area = area.borrow_mut();
+                check_coords(&area, x, y)?;
+                let data = PropData {
+                    prop,
+                    location: Point::new(x, y),
+                    items: Vec::new(),
+                    loot: None,
+                    enabled: enabled.unwrap_or(true),
+                    hover_text: None,
+                    owner: None,
+                    lock: None,
+                };
+                let location = Location::new(x, y, &area.area.area);
+                area.props_mut()
+                    .add(&data, location, false)
+                    .map_err(area_error)
+            },
+        );
+
+        methods.add_method("remove_prop", |_, area, index: usize| {
+            let area = area.try_unwrap()?;
+            let mut area = area.borrow_mut();
+            if !area.props().index_valid(index) {
+                warn!("Attempted to remove invalid prop index {}", index);
+                return Ok(());
+            }
+            area.remove_prop(index);
+            Ok(())
+        });
+
+        methods.add_method("prop_index_at", |_, area, (x, y): (i32, i32)| {
+            let area = area.try_unwrap()?;
+            let area = area.borrow();
+            Ok(area.props().index_at(x, y))
+        });
+    }
+}
@@ -0,0 +1,171 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::sync::{Arc, Mutex};
+
+use rlua::{UserData, UserDataMethods};
+
+use crate::script::ScriptEntity;
+
+#[derive(Clone, Copy)]
+struct RollParams {
+    accuracy: i32,
+    defense: i32,
+    graze_multiplier: f32,
+    hit_multiplier: f32,
+    crit_multiplier: f32,
+}
+
+/// The parameters of an attack, before the attack roll is made.  Passed
+/// to the campaign's `on_attack_roll` script, which may modify them to
+/// implement custom rules.  The script should not modify the parent or
+/// target directly, as the attack is already in progress.
+///
+/// # `parent() -> ScriptEntity`
+/// Returns the entity making the attack.
+///
+/// # `target() -> ScriptEntity`
+/// Returns the entity being attacked.
+///
+/// # `kind() -> String`
+/// Returns the kind of attack, one of `Melee`, `Ranged`, `Fortitude`,
+/// `Reflex`, or `Will`.
+///
+/// # `accuracy() -> Int`
+/// Returns the total accuracy of the attack, including all bonuses.
+///
+/// # `set_accuracy(value: Int)`
+/// Sets the total accuracy of the attack.
+///
+/// # `defense() -> Int`
+/// Returns the defense value the attack is rolled against.
+///
+/// # `set_defense(value: Int)`
+/// Sets the defense value the attack is rolled against.
+///
+/// # `graze_multiplier() -> Float`
+/// Returns the damage multiplier applied on a graze.
+///
+/// # `set_graze_multiplier(value: Float)`
+/// Sets the damage multiplier applied on a graze.
+///
+/// # `hit_multiplier() -> Float`
+/// Returns the damage multiplier applied on a hit.
+///
+/// # `set_hit_multiplier(value: Float)`
+/// Sets the damage multiplier applied on a hit.
+///
+/// # `crit_multiplier() -> Float`
+/// Returns the damage multiplier applied on a critical hit.
+///
+/// # `set_crit_multiplier(value: Float)`
+/// Sets the damage multiplier applied on a critical hit.
+#[derive(Clone)]
+pub struct ScriptAttackRoll {
+    parent: ScriptEntity,
+    target: ScriptEntity,
+    kind: String,
+    params: Arc<Mutex<RollParams>>,
+}
+
+impl ScriptAttackRoll {
+    pub fn new(
+        parent: ScriptEntity,
+        target: ScriptEntity,
+        kind: &str,
+        accuracy: i32,
+        defense: i32,
+        multipliers: (f32, f32, f32),
+    ) -> ScriptAttackRoll {
+        let (graze_multiplier, hit_multiplier, crit_multiplier) = multipliers;
+        let params = RollParams {
+            accuracy,
+            defense,
+            graze_multiplier,
+            hit_multiplier,
+            crit_multiplier,
+        };
+
+        ScriptAttackRoll {
+            parent,
+            target,
+            kind: kind.to_string(),
+            params: Arc::new(Mutex::new(params)),
+        }
+    }
+
+    pub fn accuracy(&self) -> i32 {
+        self.params.lock().unwrap().accuracy
+    }
+
+    pub fn defense(&self) -> i32 {
+        self.params.lock().unwrap().defense
+    }
+
+    /// Returns the (graze, hit, crit) damage multipliers
+    pub fn multipliers(&self) -> (f32, f32, f32) {
+        let params = self.params.lock().unwrap();
+        (
+            params.graze_multiplier,
+            params.hit_multiplier,
+            params.crit_multiplier,
+        )
+    }
+}
+
+impl UserData for ScriptAttackRoll {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("parent", |_, roll, ()| Ok(roll.parent.clone()));
+        methods.add_method("target", |_, roll, ()| Ok(roll.target.clone()));
+        methods.add_method("kind", |_, roll, ()| Ok(roll.kind.clone()));
+
+        methods.add_method("accuracy", |_, roll, ()| Ok(roll.accuracy()));
+        methods.add_method("set_accuracy", |_, roll, value: i32| {
+            roll.params.lock().unwrap().accuracy = value;
+            Ok(())
+        });
+
+        methods.add_method("defense", |_, roll, ()| Ok(roll.defense()));
+        methods.add_method("set_defense", |_, roll, value: i32| {
+            roll.params.lock().unwrap().defense = value;
+            Ok(())
+        });
+
+        methods.add_method("graze_multiplier", |_, roll, ()| {
+            Ok(roll.params.lock().unwrap().graze_multiplier)
+        });
+        methods.add_method("set_graze_multiplier", |_, roll, value: f32| {
+            roll.params.lock().unwrap().graze_multiplier = value;
+            Ok(())
+        });
+
+        methods.add_method("hit_multiplier", |_, roll, ()| {
+            Ok(roll.params.lock().unwrap().hit_multiplier)
+        });
+        methods.add_method("set_hit_multiplier", |_, roll, value: f32| {
+            roll.params.lock().unwrap().hit_multiplier = value;
+            Ok(())
+        });
+
+        methods.add_method("crit_multiplier", |_, roll, ()| {
+            Ok(roll.params.lock().unwrap().crit_multiplier)
+        });
+        methods.add_method("set_crit_multiplier", |_, roll, value: f32| {
+            roll.params.lock().unwrap().crit_multiplier = value;
+            Ok(())
+        });
+    }
+}
@@ -24,7 +24,7 @@ use rlua::{self, FromLuaMulti, ToLua, ToLuaMulti};
 use crate::script::{
     Result, ScriptAbility, ScriptEntity, ScriptEntitySet, ScriptItem, ScriptItemKind, ScriptState,
 };
-use crate::{ai, EntityState};
+use crate::{ai, EntityId, EntityState};
 use sulis_core::util::Point;
 use sulis_module::{ai::AITemplate, Ability, Item, Module};
 
@@ -35,7 +35,7 @@ thread_local! {
 
 pub fn setup() -> Result<()> {
     let start = Instant::now();
-    SCRIPT_CACHE.with(|cache| {
+    SCRIPT_CACHE.with(|cache| -> Result<()> {
         let mut cache = cache.borrow_mut();
 
         cache.clear();
@@ -158,7 +158,10 @@ where
 }
 
 pub fn ai_script(parent: &Rc<RefCell<EntityState>>, func: &str) -> Result<ai::State> {
-    let script_data = get_script_data_from_entity(parent)?;
+    let script_data = match parent.borrow().ai_template() {
+        None => get_script_data_from_entity(parent)?,
+        Some(template) => template,
+    };
     let parent = ScriptEntity::from(parent);
     exec_func(
         &script_data.script,
@@ -234,7 +237,7 @@ where
     exec_func(&script, func, (parent, item, targets, arg))
 }
 
-pub fn ability_on_activate(parent: usize, func: String, ability: &Rc<Ability>) -> Result<()> {
+pub fn ability_on_activate(parent: EntityId, func: String, ability: &Rc<Ability>) -> Result<()> {
     let script = get_ability_script_id(ability)?;
     let parent = ScriptEntity::new(parent);
     let ability = ScriptAbility::from(ability);
@@ -242,7 +245,7 @@ pub fn ability_on_activate(parent: usize, func: String, ability: &Rc<Ability>) -
     exec_func(&script, &func, (parent, ability))
 }
 
-pub fn ability_on_deactivate(parent: usize, ability: &Rc<Ability>) -> Result<()> {
+pub fn ability_on_deactivate(parent: EntityId, ability: &Rc<Ability>) -> Result<()> {
     let script_parent = ScriptEntity::new(parent).try_unwrap()?;
     match script_parent.borrow().actor.ability_states.get(&ability.id) {
         None => return Ok(()),
@@ -291,6 +294,24 @@ where
     exec_func(&script, func, (parent, ability, targets, arg))
 }
 
+pub fn ability_preview(
+    parent: &Rc<RefCell<EntityState>>,
+    ability: &Rc<Ability>,
+) -> Result<Option<String>> {
+    let script = get_ability_script_id(ability)?;
+    let has_preview = SCRIPT_CACHE.with(|cache| match cache.borrow().get(&script) {
+        None => false,
+        Some(state) => state.has_func("preview"),
+    });
+    if !has_preview {
+        return Ok(None);
+    }
+
+    let parent = ScriptEntity::from(parent);
+    let ability = ScriptAbility::from(ability);
+    exec_func(&script, "preview", (parent, ability)).map(Some)
+}
+
 pub fn trigger_script<Args>(script_id: &str, func: &str, args: Args) -> Result<()>
 where
     Args: for<'a> ToLuaMulti<'a>,
@@ -26,7 +26,7 @@ use crate::script::{
     script_entity, ScriptActiveSurface, ScriptAppliedEffect, ScriptEntity, ScriptEntitySet,
     ScriptItemKind, ScriptMenuSelection,
 };
-use crate::{EntityState, GameState, Script};
+use crate::{EntityId, EntityState, GameState, Script};
 use sulis_core::util::invalid_data_error;
 use sulis_module::{on_trigger::Kind, Ability, DamageKind, HitKind, Module};
 
@@ -39,7 +39,7 @@ pub fn fire_round_elapsed(cbs: Vec<Rc<CallbackData>>) {
 
 pub struct TriggeredCallback {
     cb: Rc<CallbackData>,
-    target: usize,
+    target: EntityId,
     func: FuncKind,
 }
 
@@ -47,14 +47,64 @@ impl TriggeredCallback {
     pub fn new(cb: Rc<CallbackData>, func: FuncKind) -> TriggeredCallback {
         TriggeredCallback {
             cb,
-            target: 0,
+            target: EntityId::new(0, 0),
             func,
         }
     }
 
-    pub fn with_target(cb: Rc<CallbackData>, func: FuncKind, target: usize) -> TriggeredCallback {
+    pub fn with_target(
+        cb: Rc<CallbackData>,
+        func: FuncKind,
+        target: EntityId,
+    ) -> TriggeredCallback {
         TriggeredCallback { cb, target, func }
     }
+
+    pub(crate) fn save(&self) -> TriggeredCallbackSaveState {
+        TriggeredCallbackSaveState {
+            cb: (*self.cb).clone(),
+            target: self.target,
+            func: self.func,
+        }
+    }
+}
+
+/// A triggered callback that had not yet fired when the game was saved
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TriggeredCallbackSaveState {
+    cb: CallbackData,
+    target: EntityId,
+    func: FuncKind,
+}
+
+impl TriggeredCallbackSaveState {
+    pub(crate) fn load(
+        mut self,
+        entities: &HashMap<usize, Rc<RefCell<EntityState>>>,
+        effects: &HashMap<usize, usize>,
+    ) -> result::Result<TriggeredCallback, Error> {
+        self.cb.update_entity_refs_on_load(entities)?;
+        if let Some(old_index) = self.cb.effect {
+            match effects.get(&old_index) {
+                None => {
+                    return invalid_data_error(&format!(
+                        "Invalid effect {old_index} for triggered callback"
+                    ));
+                }
+                Some(index) => self.cb.update_effect_index_on_load(*index),
+            }
+        }
+
+        let target = match entities.get(&self.target.index) {
+            Some(entity) if entity.borrow().id().generation == self.target.generation => {
+                entity.borrow().id()
+            }
+            _ => self.target,
+        };
+
+        Ok(TriggeredCallback::with_target(Rc::new(self.cb), self.func, target))
+    }
 }
 
 pub fn fire_cbs(cbs: Vec<TriggeredCallback>) {
@@ -70,6 +120,8 @@ pub fn fire_cbs(cbs: Vec<TriggeredCallback>) {
             FuncKind::OnMoved => cb.on_moved(),
             FuncKind::OnRoundElapsed => cb.on_round_elapsed(),
             FuncKind::OnSurfaceRoundElapsed => cb.on_surface_round_elapsed(),
+            FuncKind::OnSpawn => cb.on_spawn(),
+            FuncKind::OnPlayerApproach => cb.on_player_approach(target),
             FuncKind::OnActivated => match &cb.kind {
                 Kind::Ability(id) => {
                     let ability = Module::ability(id).unwrap();
@@ -107,7 +159,7 @@ fn get_on_activate_fn(cb: &CallbackData, ability: &Ability) -> String {
 
     let mgr = GameState::turn_manager();
     let mgr = mgr.borrow();
-    match mgr.entity_checked(cb.parent) {
+    match mgr.entity_by_id(cb.parent) {
         None => ON_ACTIVATE_DEFAULT.to_string(),
         Some(entity) => {
             if entity.borrow().is_party_member() {
@@ -196,6 +248,15 @@ pub enum FuncKind {
 
     /// Called whena an ability mode is deactivated
     OnDeactivated,
+
+    /// Called when an entity is first added to an area
+    OnSpawn,
+
+    /// Called when a parent entity is reduced to zero hit points
+    OnDeath,
+
+    /// Called the first time a party member moves near a parent entity
+    OnPlayerApproach,
 }
 
 /// A trait representing a callback that will fire a script when called.  In lua scripts,
@@ -247,11 +308,17 @@ pub trait ScriptCallback {
 
     fn on_surface_round_elapsed(&self) {}
 
-    fn on_moved_in_surface(&self, _target: usize) {}
+    fn on_moved_in_surface(&self, _target: EntityId) {}
+
+    fn on_entered_surface(&self, _target: EntityId) {}
+
+    fn on_exited_surface(&self, _target: EntityId) {}
 
-    fn on_entered_surface(&self, _target: usize) {}
+    fn on_spawn(&self) {}
 
-    fn on_exited_surface(&self, _target: usize) {}
+    fn on_death(&self, _killer: ScriptEntity) {}
+
+    fn on_player_approach(&self, _target: EntityId) {}
 }
 
 /// A callback that can be passed to various functions to be executed later.
@@ -296,7 +363,7 @@ pub trait ScriptCallback {
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct CallbackData {
-    parent: usize,
+    parent: EntityId,
     effect: Option<usize>,
     kind: Kind,
     targets: Option<ScriptEntitySet>,
@@ -312,7 +379,7 @@ impl CallbackData {
         self.kind.clone()
     }
 
-    pub fn parent(&self) -> usize {
+    pub fn parent(&self) -> EntityId {
         self.parent
     }
 
@@ -324,11 +391,11 @@ impl CallbackData {
         &mut self,
         entities: &HashMap<usize, Rc<RefCell<EntityState>>>,
     ) -> result::Result<(), Error> {
-        match entities.get(&self.parent) {
+        match entities.get(&self.parent.index) {
             None => {
                 return invalid_data_error(&format!("Invalid parent {} for callback", self.parent));
             }
-            Some(entity) => self.parent = entity.borrow().index(),
+            Some(entity) => self.parent = entity.borrow().id(),
         }
 
         if let Some(ref mut targets) = &mut self.targets {
@@ -341,7 +408,7 @@ impl CallbackData {
         self.effect = Some(index);
     }
 
-    pub fn new_ability(parent: usize, ability_id: &str) -> CallbackData {
+    pub fn new_ability(parent: EntityId, ability_id: &str) -> CallbackData {
         CallbackData {
             parent,
             effect: None,
@@ -351,7 +418,7 @@ impl CallbackData {
         }
     }
 
-    pub fn new_item(parent: usize, item_id: String) -> CallbackData {
+    pub fn new_item(parent: EntityId, item_id: String) -> CallbackData {
         CallbackData {
             parent,
             effect: None,
@@ -361,7 +428,7 @@ impl CallbackData {
         }
     }
 
-    pub fn new_entity(parent: usize) -> CallbackData {
+    pub fn new_entity(parent: EntityId) -> CallbackData {
         CallbackData {
             parent,
             effect: None,
@@ -371,7 +438,7 @@ impl CallbackData {
         }
     }
 
-    pub fn new_trigger(parent: usize, script: String) -> CallbackData {
+    pub fn new_trigger(parent: EntityId, script: String) -> CallbackData {
         CallbackData {
             parent,
             effect: None,
@@ -423,8 +490,10 @@ impl CallbackData {
             Some(func) => func.to_string(),
         };
 
-        let mgr = GameState::turn_manager();
-        let parent = mgr.borrow().entity(self.parent);
+        let parent = match self.parent_entity() {
+            None => return,
+            Some(parent) => parent,
+        };
 
         match &self.kind {
             Kind::Ability(ref id) => {
@@ -457,8 +526,10 @@ impl CallbackData {
             Some(func) => func.to_string(),
         };
 
-        let mgr = GameState::turn_manager();
-        let parent = mgr.borrow().entity(self.parent);
+        let parent = match self.parent_entity() {
+            None => return,
+            Some(parent) => parent,
+        };
 
         match &self.kind {
             Kind::Ability(ref id) => {
@@ -495,8 +566,10 @@ impl CallbackData {
             Some(func) => func.to_string(),
         };
 
-        let mgr = GameState::turn_manager();
-        let parent = mgr.borrow().entity(self.parent);
+        let parent = match self.parent_entity() {
+            None => return,
+            Some(parent) => parent,
+        };
 
         match &self.kind {
             Kind::Ability(ref id) => {
@@ -531,7 +604,16 @@ impl CallbackData {
         }
     }
 
-    fn exec_surface_script(&self, kind: FuncKind, target: Option<usize>) {
+    fn parent_entity(&self) -> Option<Rc<RefCell<EntityState>>> {
+        let mgr = GameState::turn_manager();
+        let parent = mgr.borrow().entity_by_id(self.parent);
+        if parent.is_none() {
+            debug!("Callback parent {} no longer exists", self.parent);
+        }
+        parent
+    }
+
+    fn exec_surface_script(&self, kind: FuncKind, target: Option<EntityId>) {
         if self.funcs.get(&kind).is_none() {
             return;
         }
@@ -599,15 +681,15 @@ impl ScriptCallback for CallbackData {
         self.exec_surface_script(FuncKind::OnSurfaceRoundElapsed, None);
     }
 
-    fn on_moved_in_surface(&self, target: usize) {
+    fn on_moved_in_surface(&self, target: EntityId) {
         self.exec_surface_script(FuncKind::OnMovedInSurface, Some(target));
     }
 
-    fn on_entered_surface(&self, target: usize) {
+    fn on_entered_surface(&self, target: EntityId) {
         self.exec_surface_script(FuncKind::OnEnteredSurface, Some(target));
     }
 
-    fn on_exited_surface(&self, target: usize) {
+    fn on_exited_surface(&self, target: EntityId) {
         // since it is called after the surface has been removed in some cases
         // we cannot preserve the surface info for on_exited_surface scripts
         if self.funcs.get(&FuncKind::OnExitedSurface).is_none() {
@@ -620,6 +702,22 @@ impl ScriptCallback for CallbackData {
         self.exec_standard_script(targets, FuncKind::OnExitedSurface);
     }
 
+    fn on_spawn(&self) {
+        self.exec_standard_script(self.get_or_create_targets(), FuncKind::OnSpawn);
+    }
+
+    fn on_death(&self, killer: ScriptEntity) {
+        self.exec_script_with_arg(self.get_or_create_targets(), killer, FuncKind::OnDeath);
+    }
+
+    fn on_player_approach(&self, target: EntityId) {
+        self.exec_script_with_arg(
+            self.get_or_create_targets(),
+            ScriptEntity::new(target),
+            FuncKind::OnPlayerApproach,
+        );
+    }
+
     fn after_defense(
         &self,
         targets: &ScriptEntitySet,
@@ -665,8 +763,8 @@ impl ScriptCallback for CallbackData {
 
 fn compute_surface_targets(
     effect: Option<usize>,
-    parent: usize,
-    target: Option<usize>,
+    parent: EntityId,
+    target: Option<EntityId>,
 ) -> Option<ScriptEntitySet> {
     let effect = match effect {
         None => {
@@ -702,7 +800,7 @@ fn compute_surface_targets(
                 targets.indices.push(Some(target));
             } else {
                 let inside = area.borrow().entities_with_points(points);
-                targets.indices = inside.into_iter().map(Some).collect();
+                targets.indices = inside.into_iter().map(|i| mgr.entity_id(i)).collect();
             }
         }
     }
@@ -717,9 +815,9 @@ impl UserData for CallbackData {
                 warn!("Setting targets on global generated callback will have no effect");
             }
             cb.create_targets_if_missing();
-            let index = target.try_unwrap_index()?;
+            let id = target.try_unwrap_id()?;
             if let Some(ref mut cb_targets) = cb.targets {
-                cb_targets.indices.push(Some(index));
+                cb_targets.indices.push(Some(id));
             }
             Ok(())
         });
@@ -21,6 +21,7 @@ use rlua::{Context, UserData, UserDataMethods};
 
 use sulis_core::util::{ExtInt, Point};
 use sulis_module::{
+    actor::Faction,
     bonus::{self, Contingent},
     ArmorKind, Attribute, Bonus, BonusKind, BonusList, Damage, DamageKind, Slot, WeaponKind,
     WeaponStyle, ROUND_TIME_MILLIS,
@@ -32,13 +33,18 @@ use crate::script::{
     ScriptCallback, ScriptColorAnimation, ScriptEntity, ScriptImageLayerAnimation,
     ScriptParticleGenerator, ScriptScaleAnimation, ScriptSubposAnimation,
 };
-use crate::{effect, Effect, GameState};
+use crate::{effect, AutoPause, AutoPauseTrigger, Effect, GameState};
 
 /// Represents a surface that already exists, and is being passed into
 /// a Lua script.  Not used during effect creation
 /// # `mark_for_removal()`
 /// Causes the referenced surface to be removed on the next frame.  This
 /// is an asynchronous function.
+///
+/// # `reveal()`
+/// Reveals this surface, if it is a hidden hazard, so that all creatures which
+/// avoid hazards path around it.  Fires the `trap_detected` auto pause trigger
+/// if the party did not already know about it.
 #[derive(Clone, Debug)]
 pub struct ScriptActiveSurface {
     pub index: usize,
@@ -59,6 +65,35 @@ impl UserData for ScriptActiveSurface {
             effect.mark_for_removal();
             Ok(())
         });
+        methods.add_method("reveal", |_, surface, _args: ()| {
+            let mgr = GameState::turn_manager();
+            let (area_id, hidden_by) = {
+                let mut mgr = mgr.borrow_mut();
+                let effect = match mgr.effect_mut_checked(surface.index) {
+                    None => {
+                        warn!("Effect index associated with ScriptSurface is invalid");
+                        return Ok(());
+                    }
+                    Some(effect) => effect,
+                };
+                let hidden_by = match effect.reveal_hazard() {
+                    None => return Ok(()),
+                    Some(faction) => faction,
+                };
+                match effect.surface() {
+                    None => return Ok(()),
+                    Some((area_id, _)) => (area_id.to_string(), hidden_by),
+                }
+            };
+
+            if let Some(area) = GameState::get_area_state(&area_id) {
+                area.borrow_mut().reveal_hazard(surface.index);
+            }
+            if hidden_by != Faction::Friendly {
+                AutoPause::trigger(AutoPauseTrigger::TrapDetected);
+            }
+            Ok(())
+        });
     }
 }
 
@@ -70,6 +105,9 @@ enum Kind {
         points: Vec<(i32, i32)>,
         squares_to_fire_on_moved: u32,
         aura: Option<usize>,
+        hazard: bool,
+        owner: Faction,
+        hidden: bool,
     },
 }
 
@@ -120,7 +158,8 @@ impl UserData for ScriptMenuSelection {
 /// `hit_threshold`, `graze_threshold`, `graze_multiplier`, `hit_multiplier`,
 /// `crit_multiplier`, `movement_rate`, `move_anim_rate`, `attack_cost`, `ability_ap_cost`,
 /// `hidden`, `free_ability_group_use`, abilities_disabled`, `move_disabled`,
-/// `attack_disabled`, `flanked_immunity`, `sneak_attack_immunity`, `crit_immunity`
+/// `attack_disabled`, `flanked_immunity`, `sneak_attack_immunity`, `crit_immunity`,
+/// `water_walking`
 ///
 /// # `mark_for_removal()`
 /// Marks this effect to be removed on the next update.  This is done asynchronously,
@@ -194,6 +233,7 @@ fn check_for_bonus(effect: &ScriptAppliedEffect, kind: String) -> bool {
         "flanked_immunity" => FlankedImmunity,
         "sneak_attack_immunity" => SneakAttackImmunity,
         "crit_immunity" => CritImmunity,
+        "water_walking" => WaterWalking,
         _ => {
             warn!("Attempted to add num bonus with invalid type '{}'", kind);
             return false;
@@ -304,6 +344,16 @@ impl UserData for ScriptAppliedEffect {
 /// Only has an effect on surfaces.  Sets whether this effect is an aura.  Auras
 /// are surfaces that move along with the parent.
 ///
+/// # `set_hazard()`
+/// Only has an effect on surfaces.  Marks this surface as a hazard, such as
+/// damaging fire or a trap.  Party members path around hazards where they can,
+/// as do creatures whose AI has called `set_avoid_hazards(true)`.
+///
+/// # `set_hidden()`
+/// Only has an effect on hazard surfaces.  Hides this hazard, such as a freshly
+/// set trap, so that only creatures of the same faction as its creator path
+/// around it.  Call `reveal()` on the active surface once it is spotted.
+///
 /// # `add_image_layer_anim(anim: ScriptImageLayerAnimation)`
 /// Adds the specified `anim` to this effect.  The anim will have `apply()` called
 /// when this effect has `apply()` called.  It will be removed when this effect is
@@ -388,6 +438,10 @@ impl UserData for ScriptAppliedEffect {
 /// # `add_crit_immunity(when: String (Optional))`
 /// Adds immunity to crits to this effect (all crits become hits).  See `add_num_bonus`
 ///
+/// # `add_water_walking(when: String (Optional))`
+/// Adds water walking to this effect, letting the parent cross swimmable water as if
+/// it were dry land.  See `add_num_bonus`
+///
 /// # `add_damage_of_kind(min: Float, max: Float, kind: String, ap: String (Optional),
 /// when: String (Optional))`
 /// Adds the specified amount (from `min` to `max` randomly, with `ap` armor piercing)
@@ -424,12 +478,20 @@ pub struct ScriptEffect {
 }
 
 impl ScriptEffect {
-    pub fn new_surface(points: Vec<(i32, i32)>, name: &str, duration: ExtInt) -> ScriptEffect {
+    pub fn new_surface(
+        points: Vec<(i32, i32)>,
+        name: &str,
+        duration: ExtInt,
+        owner: Faction,
+    ) -> ScriptEffect {
         ScriptEffect {
             kind: Kind::Surface {
                 points,
                 squares_to_fire_on_moved: 1,
                 aura: None,
+                hazard: false,
+                owner,
+                hidden: false,
             },
             name: name.to_string(),
             tag: "default".to_string(),
@@ -497,6 +559,24 @@ impl UserData for ScriptEffect {
             }
             Ok(())
         });
+        methods.add_method_mut("set_hazard", |_, effect, ()| {
+            match effect.kind {
+                Kind::Entity(_) => {
+                    warn!("Attempted to set hazard on non-surface effect.");
+                }
+                Kind::Surface { ref mut hazard, .. } => *hazard = true,
+            }
+            Ok(())
+        });
+        methods.add_method_mut("set_hidden", |_, effect, ()| {
+            match effect.kind {
+                Kind::Entity(_) => {
+                    warn!("Attempted to set hidden on non-surface effect.");
+                }
+                Kind::Surface { ref mut hidden, .. } => *hidden = true,
+            }
+            Ok(())
+        });
         methods.add_method_mut(
             "add_image_layer_anim",
             |_, effect, anim: ScriptImageLayerAnimation| {
@@ -605,6 +685,11 @@ impl UserData for ScriptEffect {
             add_bonus_to_effect(effect, kind, when);
             Ok(())
         });
+        methods.add_method_mut("add_water_walking", |_, effect, when: Option<String>| {
+            let kind = BonusKind::WaterWalking;
+            add_bonus_to_effect(effect, kind, when);
+            Ok(())
+        });
         methods.add_method_mut("add_damage_of_kind", |_, effect, (min, max, kind, ap, when):
                                (f32, f32, String, Option<f32>, Option<String>)| {
             let min = min as u32;
@@ -898,6 +983,9 @@ fn apply(effect_data: &ScriptEffect) -> Result<()> {
             points,
             squares_to_fire_on_moved,
             aura,
+            hazard,
+            owner,
+            hidden,
         } => {
             let points: Vec<_> = points.iter().map(|(x, y)| Point::new(*x, *y)).collect();
             for pgen in effect_data.pgens.iter() {
@@ -914,6 +1002,8 @@ fn apply(effect_data: &ScriptEffect) -> Result<()> {
                 &points,
                 *squares_to_fire_on_moved,
                 *aura,
+                *hazard,
+                if *hidden { Some(*owner) } else { None },
             );
             info!(
                 "Add surface to '{}' with duration {}",
@@ -0,0 +1,158 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use rlua::{UserData, UserDataMethods};
+
+use sulis_core::config::Difficulty;
+use sulis_module::{Actor, Module};
+
+/// The actors generated for an encounter as it spawns, before they are
+/// added to the area.  Passed to the encounter's `on_spawn_script`, which may
+/// modify the composition.  Since encounters spawn while the area is being
+/// updated, the script should only interact with this object.
+///
+/// # `id() -> String`
+/// Returns the ID of the encounter.
+///
+/// # `difficulty() -> String`
+/// Returns the current game difficulty, one of `Easy`, `Normal`, or `Hard`.
+///
+/// # `party_size() -> Int`
+/// Returns the number of members in the player's party.
+///
+/// # `actor_ids() -> Table`
+/// Returns a table of the IDs of each generated actor, in order.
+///
+/// # `add(id: String, quantity: Int (Optional))`
+/// Adds the specified `quantity`, or one if not specified, of the actor
+/// with the given `id`.
+///
+/// # `remove(id: String, quantity: Int (Optional))`
+/// Removes up to `quantity` of the actors with the given `id`, or all of
+/// them if not specified.
+///
+/// # `replace(id: String, new_id: String)`
+/// Replaces all actors with the given `id` with the actor `new_id`.
+#[derive(Clone)]
+pub struct ScriptEncounter {
+    id: String,
+    difficulty: Difficulty,
+    party_size: u32,
+    actors: Arc<Mutex<Vec<(String, Option<String>)>>>,
+}
+
+impl ScriptEncounter {
+    pub fn new(
+        id: &str,
+        difficulty: Difficulty,
+        party_size: u32,
+        actors: &[(Rc<Actor>, Option<String>)],
+    ) -> ScriptEncounter {
+        let actors = actors
+            .iter()
+            .map(|(actor, unique_id)| (actor.id.to_string(), unique_id.clone()))
+            .collect();
+
+        ScriptEncounter {
+            id: id.to_string(),
+            difficulty,
+            party_size,
+            actors: Arc::new(Mutex::new(actors)),
+        }
+    }
+
+    pub fn create_actors(&self) -> Vec<(Rc<Actor>, Option<String>)> {
+        let mut result = Vec::new();
+        for (id, unique_id) in self.actors.lock().unwrap().iter() {
+            match Module::actor(id) {
+                None => warn!("Unable to create encounter actor '{}'", id),
+                Some(actor) => result.push((actor, unique_id.clone())),
+            }
+        }
+        result
+    }
+}
+
+fn check_actor(id: &str) -> bool {
+    if Module::actor(id).is_none() {
+        warn!("Unable to add actor '{}' to encounter, it does not exist", id);
+        false
+    } else {
+        true
+    }
+}
+
+impl UserData for ScriptEncounter {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("id", |_, enc, ()| Ok(enc.id.to_string()));
+
+        methods.add_method("difficulty", |_, enc, ()| Ok(format!("{:?}", enc.difficulty)));
+
+        methods.add_method("party_size", |_, enc, ()| Ok(enc.party_size));
+
+        methods.add_method("actor_ids", |_, enc, ()| {
+            let ids: Vec<String> = enc
+                .actors
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(id, _)| id.to_string())
+                .collect();
+            Ok(ids)
+        });
+
+        methods.add_method("add", |_, enc, (id, quantity): (String, Option<u32>)| {
+            if !check_actor(&id) {
+                return Ok(());
+            }
+
+            let mut actors = enc.actors.lock().unwrap();
+            for _ in 0..quantity.unwrap_or(1) {
+                actors.push((id.to_string(), None));
+            }
+            Ok(())
+        });
+
+        methods.add_method("remove", |_, enc, (id, quantity): (String, Option<u32>)| {
+            let mut remaining = quantity.unwrap_or(u32::MAX);
+            enc.actors.lock().unwrap().retain(|(cur, _)| {
+                if *cur != id || remaining == 0 {
+                    return true;
+                }
+                remaining -= 1;
+                false
+            });
+            Ok(())
+        });
+
+        methods.add_method("replace", |_, enc, (id, new_id): (String, String)| {
+            if !check_actor(&new_id) {
+                return Ok(());
+            }
+
+            for (cur, _) in enc.actors.lock().unwrap().iter_mut() {
+                if *cur == id {
+                    *cur = new_id.to_string();
+                }
+            }
+            Ok(())
+        });
+    }
+}
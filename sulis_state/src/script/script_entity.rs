@@ -23,14 +23,17 @@ use std::{self, f32, u32};
 use rlua::{self, Context, UserData, UserDataMethods};
 
 use crate::{ability_state::DisabledReason, dist, is_within_attack_dist, is_within_touch_dist};
+use crate::animation::{particle_generator::Param, Anim};
 use crate::{ai, animation, entity_attack_handler, script::*, AreaFeedbackText};
-use crate::{area_feedback_text::ColorKind, EntityState, GameState, Location};
+use crate::{area_feedback_text::ColorKind, EntityId, EntityState, GameState, Location};
+use crate::services::{self, Service};
 use sulis_core::config::Config;
 use sulis_core::resource::ResourceSet;
+use sulis_core::ui::{color, Color};
 use sulis_core::util::ExtInt;
 use sulis_module::{
-    ability::AIData, Actor, Attack, AttackKind, Attribute, DamageKind, Faction, HitFlags, HitKind,
-    ImageLayer, InventoryBuilder, MOVE_TO_THRESHOLD, area::Destination,
+    ability::AIData, Actor, Attack, AttackKind, Attribute, DamageKind, Facing, Faction, HitFlags,
+    HitKind, ImageLayer, InventoryBuilder, MOVE_TO_THRESHOLD, area::Destination,
 };
 
 /// Represents a single entity for Lua scripts.  Also can represent an invalid,
@@ -174,6 +177,22 @@ use sulis_module::{
 /// Returns true if this entity has one or more active effects with the specified tag,
 /// false otherwise.
 ///
+/// # `ai_behavior() -> Table`
+/// Returns a table of the player's assisted combat settings for this entity, with
+/// boolean `defensive` and `conserve_abilities` entries.  Both are false for
+/// entities outside the party.
+///
+/// # `service_cost(service: String) -> Int (Optional)`
+/// Returns the cost in coins of performing the specified service on this entity, or
+/// nil if it would have no effect.  Valid services are `healing`, `injury_removal`,
+/// `retraining`, and `repair`.  Costs are set in the module rules.
+///
+/// # `purchase_service(service: String) -> Bool`
+/// Charges the party the cost of the specified service and performs it on this entity.
+/// Returns false without charging anything if the service would have no effect or the
+/// party cannot afford it.  Retraining returns a party member to level one, keeping
+/// their XP so that their levels may be chosen again.
+///
 /// # `get_effects_with_tag(tag: String) -> Table of ScriptAppliedEffect`
 /// Returns an array-like table containing all of the effects currently applied to this
 /// entity with the specified tag.
@@ -219,6 +238,19 @@ use sulis_module::{
 /// colors of the parent entity.  If `duration` is specified, lasts for that many seconds.
 /// Otherwise, will last forever, or more typically until the attached effect is removed.
 ///
+/// # `set_tint(r: Float, g: Float, b: Float, a: Float, duration: Float (Optional))`
+/// Tints this entity by multiplying its drawn color with the specified color components,
+/// each from 0.0 to 1.0.  If `duration` is specified, the tint is a temporary flash lasting
+/// that many seconds.  Otherwise, the tint persists, including across saves, until
+/// `clear_tint` is called.  A low alpha can be used to draw ghosts or translucent entities.
+///
+/// # `clear_tint()`
+/// Removes any persistent tint set with `set_tint` or `fade_out` from this entity.
+///
+/// # `fade_out(time: Float)`
+/// Fades this entity to fully transparent over `time` seconds.  The entity remains
+/// invisible afterwards until `clear_tint` is called.
+///
 /// # `create_particle_generator(image: String, duration: Float (Optional)) ->
 /// ScriptParticleGenerator`
 /// Creates a Particle Generator animation.  Despite the name, can also be used for more
@@ -287,6 +319,10 @@ use sulis_module::{
 /// # `can_move() -> Bool`
 /// Returns true if this entity can move at all (even 1 square), false otherwise.
 ///
+/// # `can_use(item: ScriptItem) -> Bool`
+/// Returns true if this entity meets all the requirements to equip or use the specified
+/// `item`, including prereqs, proficiencies, and race restrictions.  AP is not considered.
+///
 /// # `teleport_to(dest: Table)`
 /// Instantly moves this entity to the `dest`, which is a table of the form
 /// `{ x: x_coord, y: y_coord }`.  Will not move the entity if the dest
@@ -435,6 +471,21 @@ use sulis_module::{
 /// # `height() -> Int`
 /// Returns the height of this entity in tiles
 ///
+/// # `patrol_route() -> Table`
+/// Returns a list of tables with 'x' and 'y' entries for the patrol points
+/// set for this entity in its area placement.  The list is empty if none are set.
+///
+/// # `set_avoid_hazards(avoid: Bool)`
+/// Sets whether this entity paths around hazard surfaces, such as fire or
+/// traps, or walks straight through them.  By default, only party members
+/// avoid hazards.
+///
+/// # `facing() -> String`
+/// Returns the direction this entity is drawn facing, either "Left" or "Right".
+///
+/// # `set_facing(dir: String)`
+/// Sets the direction this entity is drawn facing, either "Left" or "Right".
+///
 /// # `x() -> Int`
 /// Returns the x coordinate of this entity's position in tiles
 ///
@@ -456,24 +507,33 @@ use sulis_module::{
 /// melee weapon, false otherwise
 #[derive(Clone, Debug)]
 pub struct ScriptEntity {
-    pub index: Option<usize>,
+    pub id: Option<EntityId>,
 }
 
 impl ScriptEntity {
     pub fn invalid() -> ScriptEntity {
-        ScriptEntity { index: None }
+        ScriptEntity { id: None }
     }
 
-    pub fn new(index: usize) -> ScriptEntity {
-        ScriptEntity { index: Some(index) }
+    pub fn new(id: EntityId) -> ScriptEntity {
+        ScriptEntity { id: Some(id) }
+    }
+
+    pub fn with_id(id: Option<EntityId>) -> ScriptEntity {
+        ScriptEntity { id }
     }
 
     pub fn from(entity: &Rc<RefCell<EntityState>>) -> ScriptEntity {
         ScriptEntity {
-            index: Some(entity.borrow().index()),
+            id: Some(entity.borrow().id()),
         }
     }
 
+    /// The turn manager index of the referenced entity, if any
+    pub fn index(&self) -> Option<usize> {
+        self.id.map(|id| id.index)
+    }
+
     pub fn is_party_member(&self) -> bool {
         let entity = match self.try_unwrap() {
             Ok(entity) => entity,
@@ -484,7 +544,7 @@ impl ScriptEntity {
     }
 
     pub fn check_not_equal(&self, other: &ScriptEntity) -> Result<()> {
-        if self.index == other.index {
+        if self.id == other.id {
             warn!("Parent and target must not refer to the same entity for this method");
             Err(rlua::Error::FromLuaConversionError {
                 from: "ScriptEntity",
@@ -497,34 +557,33 @@ impl ScriptEntity {
     }
 
     pub fn try_unwrap_index(&self) -> Result<usize> {
-        match self.index {
-            None => Err(rlua::Error::FromLuaConversionError {
-                from: "ScriptEntity",
-                to: "EntityState",
-                message: Some("ScriptEntity does not have a valid index".to_string()),
-            }),
-            Some(index) => Ok(index),
+        self.try_unwrap_id().map(|id| id.index)
+    }
+
+    pub fn try_unwrap_id(&self) -> Result<EntityId> {
+        match self.id {
+            None => Err(ScriptError::EntityNotFound(
+                "ScriptEntity does not have a valid index".to_string(),
+            )
+            .into()),
+            Some(id) => Ok(id),
         }
     }
 
     pub fn try_unwrap(&self) -> Result<Rc<RefCell<EntityState>>> {
-        match self.index {
-            None => Err(rlua::Error::FromLuaConversionError {
-                from: "ScriptEntity",
-                to: "EntityState",
-                message: Some("ScriptEntity does not have a valid index".to_string()),
-            }),
-            Some(index) => {
+        match self.id {
+            None => Err(ScriptError::EntityNotFound(
+                "ScriptEntity does not have a valid index".to_string(),
+            )
+            .into()),
+            Some(id) => {
                 let mgr = GameState::turn_manager();
                 let mgr = mgr.borrow();
-                match mgr.entity_checked(index) {
-                    None => Err(rlua::Error::FromLuaConversionError {
-                        from: "ScriptEntity",
-                        to: "EntityState",
-                        message: Some(
-                            "ScriptEntity refers to an entity that no longer exists.".to_string(),
-                        ),
-                    }),
+                match mgr.entity_by_id(id) {
+                    None => Err(ScriptError::EntityNotFound(
+                        "ScriptEntity refers to an entity that no longer exists.".to_string(),
+                    )
+                    .into()),
                     Some(entity) => Ok(entity),
                 }
             }
@@ -751,9 +810,9 @@ impl UserData for ScriptEntity {
 
         methods.add_method("is_valid", |_, entity, ()| {
             let mgr = GameState::turn_manager();
-            match entity.index {
+            match entity.id {
                 None => Ok(false),
-                Some(index) => Ok(mgr.borrow().has_entity(index)),
+                Some(id) => Ok(mgr.borrow().entity_by_id(id).is_some()),
             }
         });
 
@@ -775,9 +834,9 @@ impl UserData for ScriptEntity {
                         return Ok(false);
                     }
                 }
-                let index = parent.borrow().index();
+                let id = parent.borrow().id();
                 let func = get_on_activate_fn(parent.borrow().is_party_member(), ability.ai_data());
-                Script::ability_on_activate(index, func, &ability.to_ability());
+                Script::ability_on_activate(id, func, &ability.to_ability());
                 Ok(true)
             },
         );
@@ -811,8 +870,8 @@ impl UserData for ScriptEntity {
         methods.add_method("targets", targets);
 
         methods.add_method("targets_from", |_, entity, targets: Vec<ScriptEntity>| {
-            let parent = entity.try_unwrap_index()?;
-            let indices = targets.into_iter().map(|target| target.index).collect();
+            let parent = entity.try_unwrap_id()?;
+            let indices = targets.into_iter().map(|target| target.id).collect();
             let targets = ScriptEntitySet {
                 parent,
                 selected_point: None,
@@ -862,6 +921,27 @@ impl UserData for ScriptEntity {
             Ok(result)
         });
 
+        methods.add_method("ai_behavior", |_, entity, ()| {
+            let entity = entity.try_unwrap()?;
+            let behavior = entity.borrow().ai_behavior();
+            let mut table = HashMap::new();
+            table.insert("defensive", behavior.defensive);
+            table.insert("conserve_abilities", behavior.conserve_abilities);
+            Ok(table)
+        });
+
+        methods.add_method("service_cost", |_, entity, service: String| {
+            let service = parse_service(&service)?;
+            let entity = entity.try_unwrap()?;
+            Ok(services::cost(service, &entity))
+        });
+
+        methods.add_method("purchase_service", |_, entity, service: String| {
+            let service = parse_service(&service)?;
+            let entity = entity.try_unwrap()?;
+            Ok(services::purchase(service, &entity))
+        });
+
         methods.add_method("has_effect_with_tag", |_, entity, tag: String| {
             let entity = entity.try_unwrap()?;
             let entity = entity.borrow();
@@ -897,7 +977,8 @@ impl UserData for ScriptEntity {
 
         methods.add_method(
             "create_surface",
-            |_, _, (name, points, duration): (String, Vec<HashMap<String, i32>>, Option<u32>)| {
+            |_, entity, args: (String, Vec<HashMap<String, i32>>, Option<u32>)| {
+                let (name, points, duration) = args;
                 let duration = match duration {
                     None => ExtInt::Infinity,
                     Some(dur) => ExtInt::Int(dur),
@@ -910,7 +991,8 @@ impl UserData for ScriptEntity {
                         (*x, *y)
                     })
                     .collect();
-                Ok(ScriptEffect::new_surface(points, &name, duration))
+                let owner = entity.try_unwrap()?.borrow().actor.faction();
+                Ok(ScriptEffect::new_surface(points, &name, duration, owner))
             },
         );
 
@@ -974,6 +1056,14 @@ impl UserData for ScriptEntity {
             },
         );
 
+        methods.add_method("set_tint", set_tint);
+        methods.add_method("clear_tint", |_, entity, ()| {
+            let entity = entity.try_unwrap()?;
+            entity.borrow_mut().set_tint(color::WHITE);
+            Ok(())
+        });
+        methods.add_method("fade_out", fade_out);
+
         methods.add_method(
             "create_particle_generator",
             |_, entity, args: (String, Option<f32>)| {
@@ -1011,13 +1101,13 @@ impl UserData for ScriptEntity {
         );
 
         methods.add_method("create_targeter", |_, entity, ability: ScriptAbility| {
-            let index = entity.try_unwrap_index()?;
-            Ok(TargeterData::new_ability(index, &ability.id))
+            let id = entity.try_unwrap_id()?;
+            Ok(TargeterData::new_ability(id, &ability.id))
         });
 
         methods.add_method("create_targeter_for_item", |_, entity, item: ScriptItem| {
-            let index = entity.try_unwrap_index()?;
-            Ok(TargeterData::new_item(index, item.kind()))
+            let id = entity.try_unwrap_id()?;
+            Ok(TargeterData::new_item(id, item.kind()))
         });
 
         methods.add_method(
@@ -1090,6 +1180,13 @@ impl UserData for ScriptEntity {
             Ok(result)
         });
 
+        methods.add_method("can_use", |_, entity, item: ScriptItem| {
+            let parent = entity.try_unwrap()?;
+            let item = item.try_item_state()?;
+            let result = parent.borrow().actor.meets_item_requirements(&item);
+            Ok(result)
+        });
+
         methods.add_method("teleport_to", |_, entity, dest: HashMap<String, i32>| {
             let (x, y) = unwrap_point(dest)?;
             let entity = entity.try_unwrap()?;
@@ -1571,6 +1668,35 @@ impl UserData for ScriptEntity {
             let id = entity.borrow().location.area_id.to_string();
             Ok(id)
         });
+        methods.add_method("patrol_route", |lua, entity, ()| {
+            let entity = entity.try_unwrap()?;
+            let route = lua.create_table()?;
+            for (i, p) in entity.borrow().patrol_route().iter().enumerate() {
+                let point = lua.create_table()?;
+                point.set("x", p.x)?;
+                point.set("y", p.y)?;
+                route.set(i + 1, point)?;
+            }
+            Ok(route)
+        });
+        methods.add_method("set_avoid_hazards", |_, entity, avoid: bool| {
+            let entity = entity.try_unwrap()?;
+            entity.borrow_mut().set_avoid_hazards(avoid);
+            Ok(())
+        });
+        methods.add_method("facing", |_, entity, ()| {
+            let entity = entity.try_unwrap()?;
+            let facing = entity.borrow().facing();
+            Ok(facing.to_str())
+        });
+        methods.add_method("set_facing", |_, entity, dir: String| {
+            let entity = entity.try_unwrap()?;
+            match Facing::option_from_str(&dir) {
+                None => warn!("Invalid facing '{}' in script", dir),
+                Some(facing) => entity.borrow_mut().set_facing(facing),
+            }
+            Ok(())
+        });
         methods.add_method("x", |_, entity, ()| {
             let entity = entity.try_unwrap()?;
             let x = entity.borrow().location.x;
@@ -1624,7 +1750,7 @@ impl UserData for ScriptEntity {
             let entity = entity.try_unwrap()?;
             let entity = entity.borrow();
 
-            let target = target.index.unwrap_or(std::usize::MAX);
+            let target = target.index().unwrap_or(std::usize::MAX);
             Ok(entity.actor.p_stats().is_threatened_by(target))
         });
     }
@@ -1778,6 +1904,7 @@ fn create_stats_table<'a>(
     stats.set("is_abilities_disabled", src.abilities_disabled)?;
     stats.set("is_attack_disabled", src.attack_disabled)?;
     stats.set("is_move_disabled", src.move_disabled)?;
+    stats.set("is_water_walking", src.water_walking)?;
 
     if let Some(image) = src.get_ranged_projectile() {
         stats.set("ranged_projectile", image.id())?;
@@ -1811,12 +1938,12 @@ fn targets(_lua: Context, parent: &ScriptEntity, _args: ()) -> Result<ScriptEnti
             continue;
         }
 
-        indices.push(Some(entity.index()));
+        indices.push(Some(entity.id()));
     }
 
-    let parent_index = parent.borrow().index();
+    let parent_id = parent.borrow().id();
     Ok(ScriptEntitySet {
-        parent: parent_index,
+        parent: parent_id,
         indices,
         selected_point: None,
         affected_points: Vec::new(),
@@ -1833,3 +1960,79 @@ fn get_on_activate_fn(is_party_member: bool, ai_data: &AIData) -> String {
         "on_activate".to_string()
     }
 }
+
+fn parse_service(service: &str) -> Result<Service> {
+    Service::from_str(service).map_err(|e| rlua::Error::FromLuaConversionError {
+        from: "String",
+        to: "Service",
+        message: Some(e.to_string()),
+    })
+}
+
+fn set_tint(
+    _lua: Context,
+    entity: &ScriptEntity,
+    (r, g, b, a, duration): (f32, f32, f32, f32, Option<f32>),
+) -> Result<()> {
+    let parent = entity.try_unwrap()?;
+
+    match duration {
+        None => {
+            parent.borrow_mut().set_tint(Color::new(r, g, b, a));
+        }
+        Some(secs) => {
+            let duration = ExtInt::Int((secs * 1000.0) as u32);
+            let color = [
+                Param::fixed(r),
+                Param::fixed(g),
+                Param::fixed(b),
+                Param::fixed(a),
+            ];
+            let anim = Anim::new_entity_color(&parent, duration, color, no_color_sec());
+            GameState::add_animation(anim);
+        }
+    }
+
+    Ok(())
+}
+
+fn fade_out(_lua: Context, entity: &ScriptEntity, time: f32) -> Result<()> {
+    let parent = entity.try_unwrap()?;
+
+    let time = time.max(0.001);
+    let color = [
+        Param::fixed(1.0),
+        Param::fixed(1.0),
+        Param::fixed(1.0),
+        Param::with_speed(1.0, -1.0 / time),
+    ];
+    let duration = ExtInt::Int((time * 1000.0) as u32);
+    let mut anim = Anim::new_entity_color(&parent, duration, color, no_color_sec());
+    anim.add_completion_callback(Box::new(FadeOutCallback { entity: parent }));
+    GameState::add_animation(anim);
+
+    Ok(())
+}
+
+fn no_color_sec() -> [Param; 4] {
+    [
+        Param::fixed(0.0),
+        Param::fixed(0.0),
+        Param::fixed(0.0),
+        Param::fixed(0.0),
+    ]
+}
+
+/// Leaves the entity transparent once its fade out animation completes, as the
+/// color animation itself resets the entity color when removed
+struct FadeOutCallback {
+    entity: Rc<RefCell<EntityState>>,
+}
+
+impl ScriptCallback for FadeOutCallback {
+    fn on_anim_complete(&self) {
+        let mut entity = self.entity.borrow_mut();
+        let tint = entity.tint();
+        entity.set_tint(Color::new(tint.r, tint.g, tint.b, 0.0));
+    }
+}
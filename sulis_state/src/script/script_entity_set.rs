@@ -15,6 +15,7 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::io::Error;
 use std::rc::Rc;
@@ -23,9 +24,10 @@ use rlua::{self, Context, UserData, UserDataMethods};
 
 use crate::script::{Result, ScriptActiveSurface, ScriptEntity};
 use crate::{
-    is_threat, is_within, is_within_attack_dist, is_within_touch_dist, EntityState, GameState,
+    dist, is_threat, is_within, is_within_attack_dist, is_within_touch_dist, EntityId, EntityState,
+    GameState,
 };
-use sulis_core::util::{gen_rand, invalid_data_error};
+use sulis_core::util::{gen_rand, invalid_data_error, shuffle};
 use sulis_module::Faction;
 
 /// Represents a set of ScriptEntities, which can be created from a variety of
@@ -127,13 +129,37 @@ use sulis_module::Faction;
 /// Creates a new ScriptEntitySet with all the data from this set, except only targets
 /// which can hit the parent with a melee weapon currently or in the future without moving
 /// are present.
+///
+/// # `sort_by_distance(from: ScriptEntity (Optional)) -> ScriptEntitySet`
+/// Creates a new ScriptEntitySet with all the data from this set, except the targets are
+/// sorted from nearest to furthest from `from`, or from the parent if it is not specified.
+/// Invalid targets are removed.
+///
+/// # `closest() -> ScriptEntity`
+/// Returns the target in this set closest to the parent.  Throws an error if the set has
+/// no valid targets.
+///
+/// # `random(n: Int) -> ScriptEntitySet`
+/// Creates a new ScriptEntitySet with all the data from this set, except only up to `n`
+/// randomly chosen targets are present.
+///
+/// # `lowest_hp() -> ScriptEntity`
+/// Returns the target in this set with the lowest current hit points.  Throws an error if
+/// the set has no valid targets.
+/// ## Examples
+/// ```lua
+///   allies = targets:friendly():visible()
+///   if not allies:is_empty() then
+///     heal_target = allies:lowest_hp()
+///   end
+/// ```
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct ScriptEntitySet {
-    pub parent: usize,
+    pub parent: EntityId,
     pub selected_point: Option<(i32, i32)>,
     pub affected_points: Vec<(i32, i32)>,
-    pub indices: Vec<Option<usize>>,
+    pub indices: Vec<Option<EntityId>>,
 
     // surface is set when passing into script as argument, but should
     // never be saved as part of a callback
@@ -146,31 +172,26 @@ impl ScriptEntitySet {
         &mut self,
         entities: &HashMap<usize, Rc<RefCell<EntityState>>>,
     ) -> ::std::result::Result<(), Error> {
-        match entities.get(&self.parent) {
+        match entities.get(&self.parent.index) {
             None => {
                 return invalid_data_error(&format!(
                     "Invalid parent {} for ScriptEntitySet",
                     self.parent
                 ));
             }
-            Some(entity) => self.parent = entity.borrow().index(),
+            Some(entity) => self.parent = entity.borrow().id(),
         }
 
-        let mut indices = Vec::new();
-        for index in self.indices.drain(..) {
-            match index {
-                None => indices.push(None),
-                Some(index) => match entities.get(&index) {
-                    None => {
-                        return invalid_data_error(&format!(
-                            "Invalid target {index} for ScriptEntitySet"
-                        ));
-                    }
-                    Some(entity) => indices.push(Some(entity.borrow().index())),
-                },
-            }
+        // targets that were removed before the save no longer resolve, rather
+        // than resolving to the entity that reused their slot
+        for id in self.indices.iter_mut() {
+            let entity = id.and_then(|id| {
+                entities
+                    .get(&id.index)
+                    .filter(|entity| entity.borrow().id() == id)
+            });
+            *id = entity.map(|entity| entity.borrow().id());
         }
-        self.indices = indices;
 
         Ok(())
     }
@@ -183,7 +204,7 @@ impl ScriptEntitySet {
         self.surface = other.surface.clone();
     }
 
-    pub fn with_parent(parent: usize) -> ScriptEntitySet {
+    pub fn with_parent(parent: EntityId) -> ScriptEntitySet {
         ScriptEntitySet {
             parent,
             indices: Vec::new(),
@@ -197,8 +218,8 @@ impl ScriptEntitySet {
         parent: &Rc<RefCell<EntityState>>,
         target: &Rc<RefCell<EntityState>>,
     ) -> ScriptEntitySet {
-        let parent = parent.borrow().index();
-        let indices = vec![Some(target.borrow().index())];
+        let parent = parent.borrow().id();
+        let indices = vec![Some(target.borrow().id())];
 
         ScriptEntitySet {
             parent,
@@ -213,11 +234,11 @@ impl ScriptEntitySet {
         parent: &Rc<RefCell<EntityState>>,
         entities: &[Option<Rc<RefCell<EntityState>>>],
     ) -> ScriptEntitySet {
-        let parent = parent.borrow().index();
+        let parent = parent.borrow().id();
 
         let indices = entities
             .iter()
-            .map(|e| e.as_ref().map(|e| e.borrow().index()))
+            .map(|e| e.as_ref().map(|e| e.borrow().id()))
             .collect();
         ScriptEntitySet {
             parent,
@@ -237,7 +258,7 @@ impl UserData for ScriptEntitySet {
             let table: Vec<ScriptEntity> = set
                 .indices
                 .iter()
-                .map(|i| ScriptEntity { index: *i })
+                .map(|i| ScriptEntity::with_id(*i))
                 .collect();
 
             Ok(table)
@@ -336,6 +357,88 @@ impl UserData for ScriptEntitySet {
         methods.add_method("touchable", touchable);
         methods.add_method("attackable", attackable);
         methods.add_method("threatening", threatening);
+        methods.add_method("sort_by_distance", sort_by_distance);
+        methods.add_method("closest", |_, set, ()| {
+            let parent = ScriptEntity::new(set.parent).try_unwrap()?;
+            let parent = &*parent.borrow();
+            let entity = valid_entities(set)
+                .into_iter()
+                .min_by(|a, b| {
+                    let a = dist(parent, &*a.1.borrow());
+                    let b = dist(parent, &*b.1.borrow());
+                    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+                })
+                .ok_or_else(|| empty_set_error("closest"))?;
+            Ok(ScriptEntity::new(entity.0))
+        });
+        methods.add_method("random", |_, set, n: usize| {
+            let mut entities = valid_entities(set);
+            shuffle(&mut entities);
+            entities.truncate(n);
+            Ok(with_targets(set, entities))
+        });
+        methods.add_method("lowest_hp", |_, set, ()| {
+            let entity = valid_entities(set)
+                .into_iter()
+                .min_by_key(|(_, entity)| entity.borrow().actor.hp())
+                .ok_or_else(|| empty_set_error("lowest_hp"))?;
+            Ok(ScriptEntity::new(entity.0))
+        });
+    }
+}
+
+fn sort_by_distance(
+    _lua: Context,
+    set: &ScriptEntitySet,
+    from: Option<ScriptEntity>,
+) -> Result<ScriptEntitySet> {
+    let from = from.unwrap_or_else(|| ScriptEntity::new(set.parent));
+    let from = from.try_unwrap()?;
+    let from = &*from.borrow();
+
+    let mut entities: Vec<_> = valid_entities(set)
+        .into_iter()
+        .map(|(index, entity)| {
+            let dist = dist(from, &*entity.borrow());
+            (dist, (index, entity))
+        })
+        .collect();
+    entities.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    let entities = entities.into_iter().map(|(_, entity)| entity).collect();
+    Ok(with_targets(set, entities))
+}
+
+fn valid_entities(set: &ScriptEntitySet) -> Vec<(EntityId, Rc<RefCell<EntityState>>)> {
+    let mgr = GameState::turn_manager();
+    let mgr = mgr.borrow();
+
+    set.indices
+        .iter()
+        .flatten()
+        .filter_map(|index| mgr.entity_by_id(*index).map(|entity| (*index, entity)))
+        .collect()
+}
+
+fn with_targets(
+    set: &ScriptEntitySet,
+    entities: Vec<(EntityId, Rc<RefCell<EntityState>>)>,
+) -> ScriptEntitySet {
+    ScriptEntitySet {
+        parent: set.parent,
+        indices: entities.into_iter().map(|(index, _)| Some(index)).collect(),
+        selected_point: set.selected_point,
+        affected_points: set.affected_points.clone(),
+        surface: set.surface.clone(),
+    }
+}
+
+fn empty_set_error(method: &str) -> rlua::Error {
+    warn!("Attempted to get {} of EntitySet that has no valid entities", method);
+    rlua::Error::FromLuaConversionError {
+        from: "ScriptEntitySet",
+        to: "ScriptEntity",
+        message: Some("EntitySet is empty".to_string()),
     }
 }
 
@@ -445,7 +548,7 @@ fn filter_entities<T: Copy>(
     for index in set.indices.iter() {
         let entity = match index {
             None => continue,
-            Some(index) => mgr.entity_checked(*index),
+            Some(index) => mgr.entity_by_id(*index),
         };
 
         let entity = match entity {
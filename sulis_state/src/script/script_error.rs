@@ -0,0 +1,93 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::error::Error;
+use std::fmt;
+
+use rlua::{Context, Table};
+
+use crate::script::Result;
+
+/// A structured error raised by a script API call.  Scripts calling the API
+/// through `game:pcall` receive these as a table with `code` and `message`
+/// fields, where `code` is the name of the variant.  Errors not raised as a
+/// `ScriptError` have the code `ScriptError`.
+#[derive(Debug, Clone)]
+pub enum ScriptError {
+    EntityNotFound(String),
+    NotInCombat(String),
+    OutOfRange(String),
+    InvalidArgument(String),
+}
+
+impl ScriptError {
+    pub fn code(&self) -> &'static str {
+        use ScriptError::*;
+        match self {
+            EntityNotFound(_) => "EntityNotFound",
+            NotInCombat(_) => "NotInCombat",
+            OutOfRange(_) => "OutOfRange",
+            InvalidArgument(_) => "InvalidArgument",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        use ScriptError::*;
+        match self {
+            EntityNotFound(msg) | NotInCombat(msg) | OutOfRange(msg) | InvalidArgument(msg) => msg,
+        }
+    }
+
+    /// Finds the `ScriptError` that caused `error`, if any, looking through
+    /// any callbacks the error was propagated through
+    pub fn find(error: &rlua::Error) -> Option<&ScriptError> {
+        match error {
+            rlua::Error::CallbackError { cause, .. } => ScriptError::find(cause),
+            rlua::Error::ExternalError(e) => e.downcast_ref::<ScriptError>(),
+            _ => None,
+        }
+    }
+
+    /// Converts `error` into a Lua table with `code` and `message` fields
+    pub fn to_table<'lua>(lua: Context<'lua>, error: &rlua::Error) -> Result<Table<'lua>> {
+        let table = lua.create_table()?;
+        match ScriptError::find(error) {
+            Some(e) => {
+                table.set("code", e.code())?;
+                table.set("message", e.message())?;
+            }
+            None => {
+                table.set("code", "ScriptError")?;
+                table.set("message", error.to_string())?;
+            }
+        }
+        Ok(table)
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message())
+    }
+}
+
+impl Error for ScriptError {}
+
+impl From<ScriptError> for rlua::Error {
+    fn from(error: ScriptError) -> rlua::Error {
+        rlua::Error::external(error)
+    }
+}
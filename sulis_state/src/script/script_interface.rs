@@ -15,14 +15,21 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
-use rlua::{self, UserData, UserDataMethods};
+use rlua::{self, Function, MultiValue, UserData, UserDataMethods, Value};
 
 use crate::script::*;
-use crate::{animation::Anim, AreaState, EntityState, GameState, Location};
-use sulis_core::{config::Config};
-use sulis_module::on_trigger::{self, QuestEntryState};
+use crate::{
+    animation::Anim, supplies, AchievementState, AreaState, AutoPause, AutoPauseTrigger, CombatLog,
+    EntityState, GameMessage, GameState, Location, MapPin, MessageChannel,
+};
+use sulis_core::{config::Config, io::InputActionKind, resource::ResourceSet};
+use sulis_module::on_trigger::{
+    self, EpilogueSlide, QuestEntryState, ScrollViewData, ShakeViewData, TutorialContinue,
+    TutorialStepData,
+};
 use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 
 /// The ScriptInterface, accessible in all Lua scripts as the global `game`.
@@ -35,11 +42,40 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// # `is_combat_active() -> Bool`
 /// Returns true if the game is currently in combat mode, false otherwise
 ///
+/// # `pcall(func: Function, args...) -> Bool, ...`
+/// Calls `func` with the specified `args` in protected mode.  Returns true followed
+/// by the results of `func` if it succeeds.  Otherwise, returns false and an error
+/// table with a `code` and `message`.  The `code` is one of `EntityNotFound`,
+/// `NotInCombat`, `OutOfRange`, or `InvalidArgument` for errors raised by the script
+/// API, or `ScriptError` for any other error.
+/// ## Examples
+/// ```lua
+///   ok, err = game:pcall(game.delay_turn, game, entity)
+///   if not ok and err.code == "NotInCombat" then
+///     game:log("Not delaying, combat is over")
+///   end
+/// ```
+///
 /// # `current_round() -> Int`
 /// Returns the current round, or the total number of rounds of playtime that have elapsed.
 /// This number increases by 1 for every complete round of combat, or by 1 for every 5 seconds
 /// of out of combat play.
 ///
+/// # `start_combat(entities: Table<ScriptEntity>)`
+/// Starts combat with the specified entities.  Only the listed entities are made AI active,
+/// regardless of whether they can see any hostiles, so this can be used to set up surprise
+/// rounds.  If combat is already active, the entities simply join it.
+///
+/// # `end_combat()`
+/// Immediately ends combat, if it is active.  All AI entities become inactive.
+///
+/// # `delay_turn(entity: ScriptEntity, after: ScriptEntity (Optional)) -> Bool`
+/// Delays the turn of the specified `entity`, which must currently have the active turn.
+/// The entity will act directly after `after`, or the next active entity in the current
+/// round if it is not specified, keeping any AP it has remaining.  Returns false if the
+/// turn could not be delayed, such as when the entity is the last to act in the round.
+/// Raises a `NotInCombat` error if combat is not active.
+///
 /// # `add_time(days: Int, hours: Int (Optional), rounds: Int (Optional))`
 /// Adds the specified days, hours, and rounds to the current time.
 ///
@@ -132,6 +168,12 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// is not needed when scripts cause movement, as it is called automatically
 /// in those cases.  The entity should be the one whose state has changed.
 ///
+/// # `auto_pause(trigger: String)`
+/// Pauses the game, if the specified auto pause trigger is enabled in the
+/// player's options.  Valid triggers are `low_health`, `enemy_sighted`,
+/// `trap_detected`, and `spell_cast`.  Most triggers fire automatically, but
+/// scripts revealing traps should call this with `trap_detected`.
+///
 /// # `fade_out_in()`
 /// Causes the main view to fade out, then back in again.  This duration of the
 /// fades is defined in the theme for the `WindowFade` widget.
@@ -139,7 +181,12 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// # `init_party_day()`
 /// Starts a new day for the player character and party.  This resets all skill
 /// uses and sets maximum hit points.  This is normally used in a script when the
-/// party rests.
+/// party rests.  If the rules use supplies, each party member first eats the
+/// rules `rations_per_rest`, and starts starving if there are not enough.
+///
+/// # `num_rations() -> Int`
+/// Returns the number of rations in the party stash, or nil if the rules do not
+/// use supplies.
 ///
 /// # `create_menu_selection(value: String)`
 /// Creates a ScriptMenuSelection object with the specified value.  Useful to
@@ -163,7 +210,9 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// for debugging purposes.
 ///
 /// # `debug(message: String)`
-/// Logs the specified string to game output at debug level.
+/// Logs the specified string to game output at debug level.  The string is also added
+/// to the debug channel of the message log, which is hidden unless the player enables it
+/// in the message history.
 ///
 /// # `trace(message: String)`
 /// Logs the specified string to game output at trace level.
@@ -211,6 +260,113 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// # `get_quest_entry_state(quest: String, entry: String)`
 /// Returns the current `state` of the specified `entry` in the given `quest`.
 ///
+/// # `unlock_lore(id: String)`
+/// Unlocks the lore entry with the specified `id`, making it visible in the
+/// records window.  `id` must be the ID of a valid lore entry definition.
+/// Unlocking an entry that is already unlocked has no effect.
+///
+/// # `is_lore_unlocked(id: String) -> Bool`
+/// Returns true if the lore entry with the specified `id` has been unlocked,
+/// false otherwise.
+///
+/// # `export_combat_log() -> String`
+/// Writes the log of the most recently finished combat encounter, including
+/// attack rolls, damage breakdowns, and effects applied, to text and JSON
+/// files in the `combat_logs` user directory.  Returns the path of the text
+/// file, or nil if no encounter has finished or the files could not be written.
+///
+/// # `award_achievement(id: String) -> Bool`
+/// Awards the achievement with the specified `id` to the player and shows a
+/// notification.  Achievements are stored with the user profile rather than
+/// the save, so they remain awarded in later games.  `id` must be the ID of a
+/// valid achievement definition.  Returns false if the achievement was already
+/// awarded.
+///
+/// # `is_achievement_awarded(id: String) -> Bool`
+/// Returns true if the achievement with the specified `id` has been awarded
+/// in this campaign for the current user profile, false otherwise.
+///
+/// # `approval(companion: String) -> Int`
+/// Returns the party's approval with the companion with the specified actor ID.
+/// Approval starts at zero.
+///
+/// # `add_approval(companion: String, amount: Int)`
+/// Adds the specified `amount`, which may be negative, to the party's approval with
+/// the companion with the specified actor ID.  If the companion is in the party, they
+/// are granted any of their `approval_abilities` whose threshold is now reached.
+///
+/// # `witnessed_thefts(owner: String) -> Int`
+/// Returns the number of times the party has been seen taking items from props
+/// belonging to the specified `owner`.
+///
+/// # `bounty() -> Int`
+/// Returns the party's current bounty.  Each witnessed theft adds the rules
+/// `theft_bounty`.  Once the bounty reaches the rules `arrest_bounty`, a guard that
+/// sees the party will approach and start the rules `arrest_conversation`.
+///
+/// # `add_bounty(amount: Int)`
+/// Adds the specified `amount`, which may be negative, to the party's bounty.  Use
+/// a negative amount to clear the bounty when a fine is paid or a sentence served.
+///
+/// # `disarm_party()`
+/// Removes all weapons and shields held by party members, including their alternate
+/// weapon sets.  The items are kept until `return_confiscated_items` is called.
+/// Use with `transition_party_to` to move the party to a jail.
+///
+/// # `confiscate_party_items()`
+/// Removes all equipped and quick slot items from every party member, along with
+/// the entire contents of the party stash.  The items are kept until
+/// `return_confiscated_items` is called.  Use from the campaign
+/// `on_party_defeated_script` along with `transition_party_to` for a capture scenario.
+///
+/// # `return_confiscated_items()`
+/// Places all items removed by `disarm_party` or `confiscate_party_items` in the
+/// party stash.
+///
+/// # `add_housing_upgrade(id: String) -> Bool`
+/// Adds the upgrade with the specified `id` from the campaign `housing`, removing
+/// and placing props in the housing area as it specifies.  Each upgrade is only
+/// added once.  Returns true if the upgrade was added.
+///
+/// # `has_housing_upgrade(id: String) -> Bool`
+/// Returns true if the housing upgrade with the specified `id` has been added.
+///
+/// # `send_to_housing(id: String) -> Bool`
+/// Removes the entity with the specified unique `id` from the party, if they are a
+/// member, and moves them to a free idle location in the campaign housing area.
+/// They wait there until added back to the party.  Returns false if the campaign
+/// has no housing or the entity does not exist.
+///
+/// # `cancel_world_event(id: String)`
+/// Prevents the campaign world event with the specified `id` from firing again.
+/// World events fire their script at the game date `at`, and again after each
+/// `repeat` interval, if set.
+///
+/// # `world_event_cancelled(id: String) -> Bool`
+/// Returns true if the world event with the specified `id` has been cancelled.
+///
+/// # `swap_area(id: String, replacement: String (Optional))`
+/// Causes all transitions to the area with the specified `id` to lead to the
+/// `replacement` area instead, for example a town overrun by an invasion.  If no
+/// `replacement` is specified, the original area is restored.  Both areas keep their
+/// state, and the swap takes effect the next time the party enters the area.
+///
+/// # `transition_weather(id: String, duration: Float, intensity: Float (Optional))`
+/// Smoothly changes the weather to the weather with the specified `id` over
+/// `duration` seconds of game time.  The weather must be defined in the rules, and
+/// tints the ambient light of outdoor areas in proportion to its `intensity`, which
+/// defaults to 1.0.  The transition pauses along with the game clock during combat.
+///
+/// # `clear_weather(duration: Float)`
+/// Smoothly clears the current weather over `duration` seconds of game time.
+///
+/// # `weather() -> String`
+/// Returns the ID of the current weather, or the weather being transitioned to.
+/// Returns nil if the weather is clear or clearing.
+///
+/// # `weather_intensity() -> Float`
+/// Returns the current intensity of the weather, between 0.0 and 1.0.
+///
 /// # `set_world_map_location_visible(location: String, visible: Bool)`
 /// Sets the specified `location` in the world map to the specified `visible`.  The
 /// location must be defined in the world_map section of the campaign definition file.
@@ -220,6 +376,14 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// viewing the world map cannot travel to that location.  The location  must be defined
 /// in the world_map section of the campaign definition file.
 ///
+/// # `add_map_pin(id: String, label: String, x: Int, y: Int, area: String (Optional))`
+/// Places a pin with the specified `label` on the map at `x`, `y` in `area`, or in
+/// the current area if not specified.  Any existing pin with the same `id` is
+/// replaced.  The player may click the pin to center the view on it.
+///
+/// # `remove_map_pin(id: String) -> Bool`
+/// Removes the map pin with the specified `id`, returning true if it existed.
+///
 /// # `is_passable(entity: ScriptEntity, x: Int, y: Int) -> Bool`
 /// Returns true if the specified coordinates in the current area are passable for
 /// the entity, false otherwise.
@@ -261,11 +425,28 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// Toggles the enabled / disabled state of the prop at `x`, `y`.  See `enable_prop_at` and
 /// `disable_prop_at`
 ///
+/// # `prop_at(x: Int, y: Int, area_id: String (Optional)) -> ScriptProp`
+/// Returns the prop at `x`, `y` in the current area, or the specified area,
+/// or nil if there is no prop there.
+///
 /// # `say_line(line: String, target: ScriptEntity (Optional))`
 /// The specified `target`, or the player if no target is specified, will say the line
 /// of text specified by `line`.  This is represented by the text appearing on the main
 /// area view overhead of the target entity.  The text fades away after several seconds.
 ///
+/// # `add_message(text: String, entity: ScriptEntity (Optional))`
+/// Adds the specified `text` to the message log shown in the status bar.  If `entity`
+/// is specified, the message begins with the entity's name, which the player may click
+/// to select or find the entity.
+///
+/// # `notify(text: String)`
+/// Adds the specified `text` to the notification channel of the message log, shown to
+/// the player in a distinct color.  Unlike `log`, this is intended for the player.
+///
+/// # `warn_player(text: String)`
+/// Adds the specified `text` to the warning channel of the message log, shown to the
+/// player in a distinct color.  Unlike `warn`, this is intended for the player.
+///
 /// # `start_conversation(id: String, target: ScriptEntity (Optional))`
 /// Starts the conversation with the specified `id`, with the `target` or the player if the
 /// target is not specified.  The conversation is defined in the conversation data file
@@ -281,6 +462,30 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// and loading a new campaign/module.  Once it is set up, the new campaign is loaded
 /// by calling `activate` on the returned object.
 ///
+/// # `create_world_export() -> ScriptWorldExport`
+/// Creates a `ScriptWorldExport` object, which records the player's decisions in
+/// this campaign for a sequel campaign to import.  The export is written by calling
+/// `save` on the returned object.
+///
+/// # `world_import() -> Table`
+/// Returns the world export imported from an earlier campaign when this game was
+/// started, or nil if there is none.  The table contains `campaign`, `date`, and
+/// `player` strings, `flags` and `choices` tables mapping IDs to values, and a
+/// `companions` list.  Each companion is a table with `actor`, `name`, `level`,
+/// `alive`, `in_party`, `approval`, and `flags` entries.
+/// ## Examples
+/// ```lua
+///   import = game:world_import()
+///   if import ~= nil and import.choices["throne"] == "restored_king" then
+///     game:log("The king remembers your help.")
+///   end
+/// ```
+///
+/// # `area(area_id: String (Optional)) -> ScriptArea`
+/// Returns the loaded area with the specified `area_id`, or the current area if
+/// no ID is specified.  Scripts may change the tiles, passability, and props of
+/// the returned area, and the changes are saved.
+///
 /// # `player -> ScriptEntity`
 /// Returns a reference to the player character ScriptEntity.
 ///
@@ -293,10 +498,55 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// # `exit_to_menu()`
 /// Causes the game to exit to the main menu.
 ///
-/// # `scroll_view(x: Int, y: Int)`
+/// # `scroll_view(x: Int, y: Int, time: Float (Optional))`
 /// Causes the view of the current area to scroll to the specified `x`, `y` coordinates.
-/// This done using a smooth scroll effect.  The scroll begins on the next frame, so the
-/// remainder of the current script will continue to execute immediately.
+/// This done using a smooth scroll effect.  If `time` is specified, the scroll takes that
+/// many seconds, otherwise it moves at the user's scroll speed.  The scroll begins on the
+/// next frame, so the remainder of the current script will continue to execute immediately.
+///
+/// # `lock_view(entity: ScriptEntity)`
+/// Locks the view of the current area onto the specified `entity`, so that it follows the
+/// entity as it moves.  The player is unable to scroll the view while it is locked.  The
+/// lock lasts until `unlock_view` is called or the entity dies.
+///
+/// # `unlock_view()`
+/// Releases any current view lock set with `lock_view`.
+///
+/// # `shake_view(intensity: Float, duration: Float)`
+/// Shakes the view of the current area for approximately `duration` seconds.  An
+/// `intensity` of 1.0 is the standard shake used for critical hits.
+///
+/// # `show_tutorial_step(step: Table)`
+/// Shows a tutorial step to the player, replacing any step currently shown.  The
+/// screen is dimmed and input is blocked, except for an optional highlighted widget
+/// or region.  `step` has the following entries:
+/// - `text`: The text to display.
+/// - `widget`: (Optional) The theme ID of a widget to highlight, such as `end_turn_button`.
+/// - `region`: (Optional) A table with `x`, `y`, `w`, `h` screen coordinates to
+///   highlight, used when no `widget` is given.
+/// - `action`: (Optional) The one keybinding action, such as `ToggleInventory`, that is
+///   allowed while the step is shown.
+/// - `continue`: How the step is completed.  `Button` (the default) shows a continue
+///   button, `Action` waits for the `action`, `Click` waits for a click on the highlight,
+///   and `Script` waits for a call to `end_tutorial` or another `show_tutorial_step`.
+/// - `id`, `func`: (Optional) The script and function to call when the step completes.
+///
+/// # `end_tutorial()`
+/// Removes any tutorial step currently shown, without calling its completion script.
+///
+/// # `add_epilogue_slide(slide: Table)`
+/// Queues a slide for the epilogue shown at the end of the campaign.  Queued slides are
+/// kept in the save file, so they may be added as the campaign progresses.  `slide` has
+/// the entries `text`, an optional `image` ID, and optional `flags` and `not_flags`
+/// lists.  The slide is only shown if the player has each of the `flags` and none of the
+/// `not_flags` when the epilogue is shown.
+///
+/// # `show_epilogue()`
+/// Shows each queued epilogue slide whose flag conditions are met, one after another,
+/// and then exits to the main menu.  The queue is cleared.
+///
+/// # `clear_epilogue()`
+/// Removes all queued epilogue slides.
 ///
 /// # `num_effects_with_tag(tag: String) -> Int`
 /// Returns the number of currently active effects, in any area, with the specified effect
@@ -337,7 +587,20 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// Creates an item with the specified `id`, and `adjective`, if specified.  If there is
 /// no item definition with this ID or the adjective is specified but there is no
 /// adjective with that ID, throws an error.  Otherwise, the item is added to the party
-/// stash.  Returns a `ScriptStashItem` representing the added item.
+/// stash.  Returns a `ScriptStashItem` representing the added item.  Key items are
+/// added to the party keyring instead, and the returned `ScriptStashItem` is invalid.
+///
+/// # `add_party_key(id: String)`
+/// Adds the key item with the specified `id` to the party keyring.  Throws an error if
+/// there is no item with this ID or it is not a key item.
+///
+/// # `remove_party_key(id: String) -> Bool`
+/// Removes one key item with the specified `id` from the party keyring.  Returns true if
+/// the party held the key, false otherwise.
+///
+/// # `has_party_key(id: String) -> Bool`
+/// Returns true if the party keyring holds a key item with the specified `id`, false
+/// otherwise.
 ///
 /// # `add_party_xp(amount: Int)`
 /// Adds the specified amount of XP to the party.  Each current party member is given
@@ -367,6 +630,21 @@ impl UserData for ScriptInterface {
             Ok(())
         });
 
+        methods.add_method("pcall", |lua, _, (func, args): (Function, MultiValue)| {
+            let mut result = vec![];
+            match func.call::<_, MultiValue>(args) {
+                Ok(values) => {
+                    result.push(Value::Boolean(true));
+                    result.extend(values);
+                }
+                Err(e) => {
+                    result.push(Value::Boolean(false));
+                    result.push(Value::Table(ScriptError::to_table(lua, &e)?));
+                }
+            }
+            Ok(MultiValue::from_vec(result))
+        });
+
         methods.add_method("is_combat_active", |_, _, ()| {
             let mgr = GameState::turn_manager();
             let result = mgr.borrow().is_combat_active();
@@ -379,6 +657,43 @@ impl UserData for ScriptInterface {
             Ok(round)
         });
 
+        methods.add_method("start_combat", |_, _, entities: Vec<ScriptEntity>| {
+            let entities = entities
+                .iter()
+                .map(|entity| entity.try_unwrap())
+                .collect::<Result<Vec<_>>>()?;
+            let area = GameState::area_state();
+            let mgr = GameState::turn_manager();
+            mgr.borrow_mut().start_combat(&entities, &mut area.borrow_mut());
+            Ok(())
+        });
+
+        methods.add_method("end_combat", |_, _, ()| {
+            let mgr = GameState::turn_manager();
+            mgr.borrow_mut().stop_combat();
+            Ok(())
+        });
+
+        methods.add_method(
+            "delay_turn",
+            |_, _, (entity, after): (ScriptEntity, Option<ScriptEntity>)| {
+                let entity = entity.try_unwrap()?;
+                let mgr = GameState::turn_manager();
+                if !mgr.borrow().is_combat_active() {
+                    let msg = "Turns may only be delayed in combat".to_string();
+                    return Err(ScriptError::NotInCombat(msg).into());
+                }
+                let result = match after {
+                    None => mgr.borrow_mut().delay_turn(&entity),
+                    Some(after) => {
+                        let after = after.try_unwrap()?;
+                        mgr.borrow_mut().delay_turn_after(&entity, &after)
+                    }
+                };
+                Ok(result)
+            },
+        );
+
         methods.add_method(
             "add_time",
             |_, _, (day, hour, round): (u32, Option<u32>, Option<u32>)| {
@@ -486,6 +801,14 @@ impl UserData for ScriptInterface {
             Ok(())
         });
 
+        methods.add_method("auto_pause", |_, _, trigger: String| {
+            match AutoPauseTrigger::from_id(&trigger) {
+                None => warn!("Invalid auto pause trigger '{}'", trigger),
+                Some(trigger) => AutoPause::trigger(trigger),
+            }
+            Ok(())
+        });
+
         methods.add_method("fade_out_in", |_, _, ()| {
             let pc = GameState::player();
             let cb = OnTrigger::FadeOutIn;
@@ -494,12 +817,12 @@ impl UserData for ScriptInterface {
         });
 
         methods.add_method("init_party_day", |_, _, ()| {
-            for member in GameState::party() {
-                member.borrow_mut().actor.init_day();
-            }
+            GameState::init_party_day();
             Ok(())
         });
 
+        methods.add_method("num_rations", |_, _, ()| Ok(supplies::num_rations()));
+
         methods.add_method("create_menu_selection", |_, _, value: String| {
             let out = ScriptMenuSelection { value };
             Ok(out)
@@ -541,6 +864,8 @@ impl UserData for ScriptInterface {
 
         methods.add_method("debug", |_, _, val: String| {
             debug!("[LUA]: {}", val);
+            let message = GameMessage::new(&val).channel(MessageChannel::Debug);
+            GameState::add_message(message);
             Ok(())
         });
 
@@ -572,7 +897,7 @@ impl UserData for ScriptInterface {
             "run_script_delayed",
             |_, _, (script, func, delay): (String, String, f32)| {
                 let player = GameState::player();
-                let parent = player.borrow().index();
+                let parent = player.borrow().id();
                 let mut cb_data = CallbackData::new_trigger(parent, script);
                 cb_data.add_func(FuncKind::OnAnimComplete, func);
 
@@ -587,8 +912,8 @@ impl UserData for ScriptInterface {
         methods.add_method(
             "create_callback",
             |_, _, (parent, script): (ScriptEntity, String)| {
-                let index = parent.try_unwrap_index()?;
-                let cb_data = CallbackData::new_trigger(index, script);
+                let id = parent.try_unwrap_id()?;
+                let cb_data = CallbackData::new_trigger(id, script);
                 Ok(cb_data)
             },
         );
@@ -654,6 +979,133 @@ impl UserData for ScriptInterface {
             },
         );
 
+        methods.add_method("unlock_lore", |_, _, id: String| {
+            if Module::lore_entry(&id).is_none() {
+                warn!("Unlock lore for invalid lore entry '{}'", id);
+            }
+            GameState::unlock_lore(&id);
+            Ok(())
+        });
+
+        methods.add_method("is_lore_unlocked", |_, _, id: String| {
+            if Module::lore_entry(&id).is_none() {
+                warn!("Requested state for invalid lore entry '{}'", id);
+            }
+            Ok(GameState::is_lore_unlocked(&id))
+        });
+
+        methods.add_method("export_combat_log", |_, _, ()| {
+            match CombatLog::export_last() {
+                Ok(path) => Ok(Some(path.to_string_lossy().to_string())),
+                Err(e) => {
+                    warn!("Unable to export combat log: {}", e);
+                    Ok(None)
+                }
+            }
+        });
+
+        methods.add_method("award_achievement", |_, _, id: String| {
+            Ok(AchievementState::award(&id))
+        });
+
+        methods.add_method("is_achievement_awarded", |_, _, id: String| {
+            if Module::achievement(&id).is_none() {
+                warn!("Requested state for invalid achievement '{}'", id);
+            }
+            Ok(AchievementState::is_awarded(&id))
+        });
+
+        methods.add_method("approval", |_, _, companion: String| {
+            Ok(GameState::approval(&companion))
+        });
+
+        methods.add_method("add_approval", |_, _, (companion, amount): (String, i32)| {
+            GameState::add_approval(&companion, amount);
+            Ok(())
+        });
+
+        methods.add_method("witnessed_thefts", |_, _, owner: String| {
+            Ok(GameState::witnessed_thefts(&owner))
+        });
+
+        methods.add_method("bounty", |_, _, ()| Ok(GameState::crime().bounty()));
+
+        methods.add_method("add_bounty", |_, _, amount: i32| {
+            GameState::add_bounty(amount);
+            Ok(())
+        });
+
+        methods.add_method("disarm_party", |_, _, ()| {
+            GameState::disarm_party();
+            Ok(())
+        });
+
+        methods.add_method("confiscate_party_items", |_, _, ()| {
+            GameState::confiscate_party_items();
+            Ok(())
+        });
+
+        methods.add_method("return_confiscated_items", |_, _, ()| {
+            GameState::return_confiscated_items();
+            Ok(())
+        });
+
+        methods.add_method("add_housing_upgrade", |_, _, id: String| {
+            Ok(GameState::add_housing_upgrade(&id))
+        });
+
+        methods.add_method("has_housing_upgrade", |_, _, id: String| {
+            Ok(GameState::housing().has_upgrade(&id))
+        });
+
+        methods.add_method("cancel_world_event", |_, _, id: String| {
+            GameState::cancel_world_event(&id);
+            Ok(())
+        });
+
+        methods.add_method("world_event_cancelled", |_, _, id: String| {
+            Ok(GameState::world_events().is_cancelled(&id))
+        });
+
+        methods.add_method(
+            "swap_area",
+            |_, _, (id, replacement): (String, Option<String>)| {
+                GameState::swap_area(&id, replacement);
+                Ok(())
+            },
+        );
+
+        methods.add_method("send_to_housing", |_, _, id: String| {
+            match entity_with_id(id) {
+                None => Ok(false),
+                Some(entity) => Ok(GameState::send_to_housing(&entity)),
+            }
+        });
+
+        methods.add_method(
+            "transition_weather",
+            |_, _, (id, duration, intensity): (String, f32, Option<f32>)| {
+                let millis = (duration.max(0.0) * 1000.0) as usize;
+                GameState::transition_weather(Some(id), intensity.unwrap_or(1.0), millis);
+                Ok(())
+            },
+        );
+
+        methods.add_method("clear_weather", |_, _, duration: f32| {
+            let millis = (duration.max(0.0) * 1000.0) as usize;
+            GameState::transition_weather(None, 0.0, millis);
+            Ok(())
+        });
+
+        methods.add_method("weather", |_, _, ()| {
+            Ok(GameState::weather().id().map(|id| id.to_string()))
+        });
+
+        methods.add_method("weather_intensity", |_, _, ()| {
+            let millis = GameState::turn_manager().borrow().total_elapsed_millis();
+            Ok(GameState::weather().intensity(millis))
+        });
+
         methods.add_method(
             "set_world_map_location_visible",
             |_, _, (location, vis): (String, bool)| {
@@ -670,6 +1122,23 @@ impl UserData for ScriptInterface {
             },
         );
 
+        methods.add_method(
+            "add_map_pin",
+            |_, _, (id, label, x, y, area): (String, String, i32, i32, Option<String>)| {
+                let area = match area {
+                    Some(area) => area,
+                    None => GameState::area_state().borrow().area.area.id.to_string(),
+                };
+                let note = false;
+                GameState::add_map_pin(MapPin { id, area, x, y, label, note });
+                Ok(())
+            },
+        );
+
+        methods.add_method("remove_map_pin", |_, _, id: String| {
+            Ok(GameState::remove_map_pin(&id))
+        });
+
         methods.add_method(
             "is_passable",
             |_, _, (entity, x, y): (ScriptEntity, i32, i32)| {
@@ -719,7 +1188,11 @@ impl UserData for ScriptInterface {
                     .borrow_mut()
                     .add_actor(actor, location, None, false, None)
                 {
-                    Ok(index) => ScriptEntity::new(index),
+                    Ok(index) => {
+                        let mgr = GameState::turn_manager();
+                        let id = mgr.borrow().entity_id(index);
+                        ScriptEntity::with_id(id)
+                    }
                     Err(e) => {
                         warn!("Error spawning actor in area: {}", e);
                         return Ok(ScriptEntity::invalid());
@@ -828,6 +1301,20 @@ impl UserData for ScriptInterface {
             },
         );
 
+        methods.add_method(
+            "prop_at",
+            |_, _, (x, y, id): (i32, i32, Option<String>)| {
+                let area_state = get_area(id)?;
+                let area_state = area_state.borrow();
+                let index = match area_state.props().index_at(x, y) {
+                    None => return Ok(None),
+                    Some(index) => index,
+                };
+                let prop_id = &area_state.props().get(index).prop.id;
+                Ok(Some(ScriptProp::new(&area_state.area.area.id, index, prop_id)))
+            },
+        );
+
         methods.add_method(
             "say_line",
             |_, _, (line, target): (String, Option<ScriptEntity>)| {
@@ -843,6 +1330,33 @@ impl UserData for ScriptInterface {
             },
         );
 
+        methods.add_method(
+            "add_message",
+            |_, _, (text, entity): (String, Option<ScriptEntity>)| {
+                let message = match entity {
+                    None => GameMessage::new(&text),
+                    Some(entity) => {
+                        let entity = entity.try_unwrap()?;
+                        GameMessage::default().entity(&entity).text(" ").text(&text)
+                    }
+                };
+                GameState::add_message(message);
+                Ok(())
+            },
+        );
+
+        methods.add_method("notify", |_, _, text: String| {
+            let message = GameMessage::new(&text).channel(MessageChannel::Notify);
+            GameState::add_message(message);
+            Ok(())
+        });
+
+        methods.add_method("warn_player", |_, _, text: String| {
+            let message = GameMessage::new(&text).channel(MessageChannel::Warning);
+            GameState::add_message(message);
+            Ok(())
+        });
+
         methods.add_method(
             "start_conversation",
             |_, _, (id, target): (String, Option<ScriptEntity>)| {
@@ -869,6 +1383,43 @@ impl UserData for ScriptInterface {
             Ok(ModuleExport::new(id))
         });
 
+        methods.add_method("create_world_export", |_, _, ()| Ok(ScriptWorldExport::new()));
+
+        methods.add_method("world_import", |lua, _, ()| {
+            let import = match GameState::world_import() {
+                None => return Ok(None),
+                Some(import) => import,
+            };
+
+            let companions = lua.create_table()?;
+            for (index, companion) in import.companions.into_iter().enumerate() {
+                let table = lua.create_table()?;
+                table.set("actor", companion.actor)?;
+                table.set("name", companion.name)?;
+                table.set("level", companion.level)?;
+                table.set("alive", companion.alive)?;
+                table.set("in_party", companion.in_party)?;
+                table.set("approval", companion.approval)?;
+                table.set("flags", companion.flags)?;
+                companions.set(index + 1, table)?;
+            }
+
+            let table = lua.create_table()?;
+            table.set("campaign", import.campaign)?;
+            table.set("date", import.date)?;
+            table.set("player", import.player)?;
+            table.set("flags", import.flags)?;
+            table.set("choices", import.choices)?;
+            table.set("companions", companions)?;
+            Ok(Some(table))
+        });
+
+        methods.add_method("area", |_, _, id: Option<String>| {
+            let area = get_area(id)?;
+            let id = area.borrow().area.area.id.to_string();
+            Ok(ScriptArea::new(id))
+        });
+
         methods.add_method("player", |_, _, ()| {
             Ok(ScriptEntity::from(&GameState::player()))
         });
@@ -887,13 +1438,92 @@ impl UserData for ScriptInterface {
             Ok(())
         });
 
-        methods.add_method("scroll_view", |_, _, (x, y): (i32, i32)| {
+        methods.add_method("scroll_view", |_, _, (x, y, time): (i32, i32, Option<f32>)| {
+            let pc = GameState::player();
+            let cb = match time {
+                None => OnTrigger::ScrollView(x, y),
+                Some(time) => {
+                    let millis = (time.max(0.0) * 1000.0) as u32;
+                    OnTrigger::ScrollViewTimed(ScrollViewData { x, y, millis })
+                }
+            };
+            GameState::add_ui_callback(vec![cb], &pc, &pc);
+            Ok(())
+        });
+
+        methods.add_method("lock_view", |_, _, entity: ScriptEntity| {
+            let entity = entity.try_unwrap()?;
+            let pc = GameState::player();
+            GameState::add_ui_callback(vec![OnTrigger::LockView], &pc, &entity);
+            Ok(())
+        });
+
+        methods.add_method("unlock_view", |_, _, ()| {
+            let pc = GameState::player();
+            GameState::add_ui_callback(vec![OnTrigger::UnlockView], &pc, &pc);
+            Ok(())
+        });
+
+        methods.add_method("shake_view", |_, _, (intensity, time): (f32, f32)| {
             let pc = GameState::player();
-            let cb = OnTrigger::ScrollView(x, y);
+            let millis = (time.max(0.0) * 1000.0) as u32;
+            let cb = OnTrigger::ShakeView(ShakeViewData { intensity, millis });
             GameState::add_ui_callback(vec![cb], &pc, &pc);
             Ok(())
         });
 
+        methods.add_method("show_tutorial_step", |_, _, step: rlua::Table| {
+            let data = create_tutorial_step(step)?;
+            let pc = GameState::player();
+            GameState::add_ui_callback(vec![OnTrigger::TutorialStep(data)], &pc, &pc);
+            Ok(())
+        });
+
+        methods.add_method("end_tutorial", |_, _, ()| {
+            let pc = GameState::player();
+            GameState::add_ui_callback(vec![OnTrigger::EndTutorial], &pc, &pc);
+            Ok(())
+        });
+
+        methods.add_method("add_epilogue_slide", |_, _, slide: rlua::Table| {
+            let text: String = slide.get("text")?;
+            let image: Option<String> = slide.get("image")?;
+            if let Some(ref id) = image {
+                if ResourceSet::image(id).is_none() {
+                    let msg = format!("Invalid epilogue image '{id}'");
+                    return Err(ScriptError::InvalidArgument(msg).into());
+                }
+            }
+
+            let flags: Option<Vec<String>> = slide.get("flags")?;
+            let not_flags: Option<Vec<String>> = slide.get("not_flags")?;
+            let conditions = flags
+                .unwrap_or_default()
+                .into_iter()
+                .map(OnTrigger::PlayerFlag)
+                .chain(not_flags.unwrap_or_default().into_iter().map(OnTrigger::NotPlayerFlag))
+                .collect();
+
+            GameState::add_epilogue_slide(EpilogueSlide {
+                image,
+                text,
+                conditions,
+            });
+            Ok(())
+        });
+
+        methods.add_method("show_epilogue", |_, _, ()| {
+            let slides = GameState::take_epilogue();
+            let pc = GameState::player();
+            GameState::add_ui_callback(vec![OnTrigger::ShowEpilogue(slides)], &pc, &pc);
+            Ok(())
+        });
+
+        methods.add_method("clear_epilogue", |_, _, ()| {
+            GameState::take_epilogue();
+            Ok(())
+        });
+
         methods.add_method("num_effects_with_tag", |_, _, tag: String| {
             let mgr = GameState::turn_manager();
             let mgr = mgr.borrow();
@@ -926,14 +1556,11 @@ impl UserData for ScriptInterface {
             "add_party_member",
             |_, _, (id, show_portrait): (String, Option<bool>)| {
                 let show_portrait = show_portrait.unwrap_or(true);
-                let entity = match entity_with_id(id) {
+                let entity = match entity_with_id(id.clone()) {
                     Some(entity) => entity,
                     None => {
-                        return Err(rlua::Error::ToLuaConversionError {
-                            from: "ID",
-                            to: "ScriptENtity",
-                            message: Some("Entity with specified id does not exist".to_string()),
-                        });
+                        let msg = format!("Entity with id '{id}' does not exist");
+                        return Err(ScriptError::EntityNotFound(msg).into());
                     }
                 };
 
@@ -972,6 +1599,7 @@ impl UserData for ScriptInterface {
                 format!("Lost {} coins", (-amount as f32 / factor) as i32)
             };
 
+            GameState::add_message(GameMessage::new(&line));
             let cb = OnTrigger::SayLine(line);
             GameState::add_ui_callback(vec![cb], &pc, &pc);
 
@@ -1026,6 +1654,33 @@ impl UserData for ScriptInterface {
             Ok(ScriptStashItem { index })
         });
 
+        methods.add_method("add_party_key", |_, _, id: String| {
+            let item = match Module::item(&id) {
+                Some(item) if item.key => item,
+                _ => return Err(rlua::Error::FromLuaConversionError {
+                    from: "String",
+                    to: "Item",
+                    message: Some(format!("Key item '{id}' does not exist")),
+                }),
+            };
+
+            let stash = GameState::party_stash();
+            stash.borrow_mut().add_item(1, ItemState::new(item, None));
+            Ok(())
+        });
+
+        methods.add_method("remove_party_key", |_, _, id: String| {
+            let stash = GameState::party_stash();
+            let result = stash.borrow_mut().remove_key(&id);
+            Ok(result.is_some())
+        });
+
+        methods.add_method("has_party_key", |_, _, id: String| {
+            let stash = GameState::party_stash();
+            let result = stash.borrow().has_key(&id);
+            Ok(result)
+        });
+
         methods.add_method("add_party_xp", |_, _, amount: u32| {
             for member in GameState::party().iter() {
                 member.borrow_mut().add_xp(amount);
@@ -1070,16 +1725,76 @@ impl UserData for ScriptInterface {
 fn get_area(id: Option<String>) -> Result<Rc<RefCell<AreaState>>> {
     match id {
         None => Ok(GameState::area_state()),
-        Some(id) => GameState::get_area_state(&id).ok_or(rlua::Error::FromLuaConversionError {
-            from: "String",
-            to: "AreaState",
-            message: Some(format!(
-                "The area '{id}' does not exist or is not loaded."
-            )),
+        Some(id) => GameState::get_area_state(&id).ok_or_else(|| {
+            let msg = format!("The area '{id}' does not exist or is not loaded.");
+            ScriptError::InvalidArgument(msg).into()
         }),
     }
 }
 
+fn create_tutorial_step(step: rlua::Table) -> Result<TutorialStepData> {
+    let invalid = |msg: String| -> rlua::Error { ScriptError::InvalidArgument(msg).into() };
+
+    let text: String = step.get("text")?;
+    let highlight_widget: Option<String> = step.get("widget")?;
+
+    let highlight_region = match step.get::<_, Option<HashMap<String, i32>>>("region")? {
+        None => None,
+        Some(region) => {
+            let mut coords = [0; 4];
+            for (coord, key) in coords.iter_mut().zip(["x", "y", "w", "h"]) {
+                *coord = *region
+                    .get(key)
+                    .ok_or_else(|| invalid(format!("Tutorial region must specify '{key}'")))?;
+            }
+            Some(coords)
+        }
+    };
+
+    let allowed_action = match step.get::<_, Option<String>>("action")? {
+        None => None,
+        Some(action) => match action.parse::<InputActionKind>() {
+            Ok(action) => Some(action),
+            Err(_) => return Err(invalid(format!("Invalid tutorial action '{action}'"))),
+        },
+    };
+
+    let continue_on = match step.get::<_, Option<String>>("continue")?.as_deref() {
+        None | Some("Button") => TutorialContinue::Button,
+        Some("Action") => TutorialContinue::Action,
+        Some("Click") => TutorialContinue::Click,
+        Some("Script") => TutorialContinue::Script,
+        Some(other) => return Err(invalid(format!("Invalid tutorial continue '{other}'"))),
+    };
+
+    if continue_on == TutorialContinue::Action && allowed_action.is_none() {
+        return Err(invalid("Tutorial step continuing on Action needs an action".to_string()));
+    }
+
+    if continue_on == TutorialContinue::Click
+        && highlight_widget.is_none()
+        && highlight_region.is_none()
+    {
+        return Err(invalid(
+            "Tutorial step continuing on Click needs a widget or region".to_string(),
+        ));
+    }
+
+    let on_continue = match (step.get("id")?, step.get("func")?) {
+        (Some(id), Some(func)) => Some(on_trigger::ScriptData { id, func }),
+        _ => None,
+    };
+
+    Ok(TutorialStepData {
+        text,
+        highlight_widget,
+        highlight_region,
+        allowed_action,
+        continue_on,
+        on_continue,
+    })
+}
+
 fn entities_with_ids(ids: Vec<String>) -> Vec<ScriptEntity> {
     let mut result = Vec::new();
 
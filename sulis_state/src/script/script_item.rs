@@ -20,7 +20,7 @@ use std::rc::Rc;
 use rlua::{self, Context, UserData, UserDataMethods};
 
 use crate::script::*;
-use crate::{area_feedback_text::ColorKind, AreaFeedbackText, EntityState, GameState};
+use crate::{area_feedback_text::ColorKind, AreaFeedbackText, EntityId, EntityState, GameState};
 use sulis_module::{ability, Item, ItemState, Module};
 
 /// A kind of Item, represented by its owner (Stash, QuickSlot, or a generic
@@ -95,7 +95,7 @@ impl ScriptItemKind {
 /// defined in its resource file.
 #[derive(Clone)]
 pub struct ScriptItem {
-    parent: usize,
+    parent: EntityId,
     kind: ScriptItemKind,
     id: String,
     name: String,
@@ -121,7 +121,7 @@ impl ScriptItem {
         };
 
         Ok(ScriptItem {
-            parent: parent.borrow().index(),
+            parent: parent.borrow().id(),
             kind,
             id: item.item.id.to_string(),
             name: item.item.name.to_string(),
@@ -134,6 +134,11 @@ impl ScriptItem {
     }
 
     pub fn try_item(&self) -> Result<Rc<Item>> {
+        let item_state = self.try_item_state()?;
+        Ok(item_state.item)
+    }
+
+    pub fn try_item_state(&self) -> Result<ItemState> {
         let parent = ScriptEntity::new(self.parent).try_unwrap()?;
         let item = self.kind.item_checked(&parent);
 
@@ -146,7 +151,7 @@ impl ScriptItem {
                     self.id
                 )),
             }),
-            Some(item_state) => Ok(Rc::clone(&item_state.item)),
+            Some(item_state) => Ok(item_state),
         }
     }
 }
@@ -166,8 +171,8 @@ impl UserData for ScriptItem {
             }
         });
         methods.add_method("create_callback", |_, item, parent: ScriptEntity| {
-            let index = parent.try_unwrap_index()?;
-            let cb_data = CallbackData::new_item(index, item.id.to_string());
+            let id = parent.try_unwrap_id()?;
+            let cb_data = CallbackData::new_item(id, item.id.to_string());
             Ok(cb_data)
         });
     }
@@ -0,0 +1,157 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::sync::{Arc, Mutex};
+
+use rlua::{UserData, UserDataMethods};
+
+use sulis_module::{ItemListEntrySaveState, ItemSaveState, ItemState, Module};
+
+/// The loot generated for a container or creature drop, before it is
+/// added to the game.  Passed to the campaign's `on_loot_generated`
+/// script, which may modify the items.  Since loot is generated while
+/// the area is being updated, the script should only interact with
+/// this object.
+///
+/// # `level() -> Int`
+/// Returns the level of the area the loot was generated in.
+///
+/// # `item_ids() -> Table`
+/// Returns a table of the IDs of each generated item, in order.
+///
+/// # `quantity(id: String) -> Int`
+/// Returns the total quantity of items with the given `id`.
+///
+/// # `set_quantity(id: String, quantity: Int)`
+/// Sets the total quantity of items with the given `id`.  A quantity of
+/// zero removes the items.  Does nothing if no such item was generated.
+///
+/// # `add(id: String, quantity: Int (Optional))`
+/// Adds the specified `quantity`, or one if not specified, of a new item
+/// with the given `id`.
+///
+/// # `remove(id: String)`
+/// Removes all items with the given `id`.
+#[derive(Clone)]
+pub struct ScriptLoot {
+    level: u32,
+    items: Arc<Mutex<Vec<ItemListEntrySaveState>>>,
+}
+
+impl ScriptLoot {
+    pub fn new(level: u32, items: &[(u32, ItemState)]) -> ScriptLoot {
+        let items = items
+            .iter()
+            .map(|(qty, item)| ItemListEntrySaveState::new(*qty, item))
+            .collect();
+
+        ScriptLoot {
+            level,
+            items: Arc::new(Mutex::new(items)),
+        }
+    }
+
+    pub fn create_items(&self) -> Vec<(u32, ItemState)> {
+        let mut result = Vec::new();
+        for entry in self.items.lock().unwrap().iter() {
+            let item = &entry.item;
+            match Module::create_get_item(&item.id, &item.adjectives) {
+                None => warn!(
+                    "Unable to create loot item '{}' with '{:?}'",
+                    item.id, item.adjectives
+                ),
+                Some(created) => {
+                    result.push((entry.quantity, ItemState::new(created, item.variant)));
+                }
+            }
+        }
+        result
+    }
+}
+
+impl UserData for ScriptLoot {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("level", |_, loot, ()| Ok(loot.level));
+
+        methods.add_method("item_ids", |_, loot, ()| {
+            let ids: Vec<String> = loot
+                .items
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|entry| entry.item.id.to_string())
+                .collect();
+            Ok(ids)
+        });
+
+        methods.add_method("quantity", |_, loot, id: String| {
+            let quantity: u32 = loot
+                .items
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|entry| entry.item.id == id)
+                .map(|entry| entry.quantity)
+                .sum();
+            Ok(quantity)
+        });
+
+        methods.add_method("set_quantity", |_, loot, (id, quantity): (String, u32)| {
+            let mut items = loot.items.lock().unwrap();
+            let index = match items.iter().position(|entry| entry.item.id == id) {
+                None => {
+                    warn!("Unable to set quantity of '{}', not in the loot", id);
+                    return Ok(());
+                }
+                Some(index) => index,
+            };
+
+            let mut entry = items.remove(index);
+            items.retain(|entry| entry.item.id != id);
+            if quantity > 0 {
+                entry.quantity = quantity;
+                items.insert(index, entry);
+            }
+            Ok(())
+        });
+
+        methods.add_method("add", |_, loot, (id, quantity): (String, Option<u32>)| {
+            if Module::item(&id).is_none() {
+                warn!("Unable to add item '{}' to loot, it does not exist", id);
+                return Ok(());
+            }
+
+            let quantity = quantity.unwrap_or(1);
+            if quantity > 0 {
+                let item = ItemSaveState {
+                    id,
+                    adjectives: Vec::new(),
+                    variant: None,
+                };
+                loot.items
+                    .lock()
+                    .unwrap()
+                    .push(ItemListEntrySaveState { quantity, item });
+            }
+            Ok(())
+        });
+
+        methods.add_method("remove", |_, loot, id: String| {
+            loot.items.lock().unwrap().retain(|entry| entry.item.id != id);
+            Ok(())
+        });
+    }
+}
@@ -94,7 +94,7 @@ impl UserData for ScriptMenu {
                 choices,
                 cb_func: func,
                 cb_kind: menu.callback.kind(),
-                cb_parent: menu.callback.parent(),
+                cb_parent: menu.callback.parent().index,
             };
 
             let pc = GameState::player();
@@ -0,0 +1,181 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rlua::{UserData, UserDataMethods};
+
+use crate::script::{Result, ScriptEntity, ScriptError};
+use crate::{AreaState, GameState};
+
+/// A prop placed in an area, such as a door, container, or piece of
+/// scenery.  Obtained with `game:prop_at`.
+///
+/// # `id() -> String`
+/// Returns the ID of the prop definition.
+///
+/// # `name() -> String`
+/// Returns the displayed name of the prop.
+///
+/// # `x() -> Int`
+/// Returns the x coordinate of the prop's top left corner.
+///
+/// # `y() -> Int`
+/// Returns the y coordinate of the prop's top left corner.
+///
+/// # `state() -> String`
+/// Returns the ID of the prop's current visual state, or nil if the prop
+/// does not define any states.
+///
+/// # `states() -> Table`
+/// Returns a table of the IDs of all visual states defined for the prop.
+///
+/// # `set_state(id: String)`
+/// Switches the prop to the visual state with the given `id`, playing the
+/// state's transition animation if it has one.  Passability and
+/// visibility are updated to match the new state.
+///
+/// # `hp() -> Int`
+/// Returns the remaining hit points of a destructible prop, or nil if the
+/// prop cannot be destroyed.
+///
+/// # `damage(amount: Int, attacker: ScriptEntity)`
+/// Deals `amount` damage, reduced by the prop's armor, to a destructible
+/// prop, such as from an explosion.  If this destroys the prop, it is
+/// replaced by its debris and its `on_prop_destroyed` script is called with
+/// `attacker`.  Does nothing for props that cannot be destroyed.
+///
+/// # `is_locked() -> Bool`
+/// Returns true if the prop is disabled by a lock that a key could open.
+///
+/// # `lock_key() -> String`
+/// Returns the ID of the key item that opens the prop's lock, or nil if
+/// the prop has no lock.
+#[derive(Clone, Debug)]
+pub struct ScriptProp {
+    area_id: String,
+    index: usize,
+    id: String,
+}
+
+impl ScriptProp {
+    pub fn new(area_id: &str, index: usize, id: &str) -> ScriptProp {
+        ScriptProp {
+            area_id: area_id.to_string(),
+            index,
+            id: id.to_string(),
+        }
+    }
+
+    pub fn try_unwrap(&self) -> Result<Rc<RefCell<AreaState>>> {
+        let invalid = |message: &str| ScriptError::InvalidArgument(message.to_string()).into();
+
+        let area_state = match GameState::get_area_state(&self.area_id) {
+            None => return Err(invalid("The prop's area is no longer loaded")),
+            Some(area_state) => area_state,
+        };
+
+        {
+            let area = area_state.borrow();
+            let props = area.props();
+            if !props.index_valid(self.index) || props.get(self.index).prop.id != self.id {
+                return Err(invalid("The prop no longer exists"));
+            }
+        }
+
+        Ok(area_state)
+    }
+}
+
+impl UserData for ScriptProp {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("id", |_, prop, ()| Ok(prop.id.clone()));
+
+        methods.add_method("name", |_, prop, ()| {
+            let area_state = prop.try_unwrap()?;
+            let area_state = area_state.borrow();
+            Ok(area_state.props().get(prop.index).name().to_string())
+        });
+
+        methods.add_method("x", |_, prop, ()| {
+            let area_state = prop.try_unwrap()?;
+            let area_state = area_state.borrow();
+            Ok(area_state.props().get(prop.index).location.x)
+        });
+
+        methods.add_method("y", |_, prop, ()| {
+            let area_state = prop.try_unwrap()?;
+            let area_state = area_state.borrow();
+            Ok(area_state.props().get(prop.index).location.y)
+        });
+
+        methods.add_method("state", |_, prop, ()| {
+            let area_state = prop.try_unwrap()?;
+            let area_state = area_state.borrow();
+            let state = area_state.props().get(prop.index).visual_state();
+            Ok(state.map(|state| state.id.to_string()))
+        });
+
+        methods.add_method("states", |_, prop, ()| {
+            let area_state = prop.try_unwrap()?;
+            let area_state = area_state.borrow();
+            let states: Vec<String> = area_state
+                .props()
+                .get(prop.index)
+                .prop
+                .states
+                .iter()
+                .map(|state| state.id.to_string())
+                .collect();
+            Ok(states)
+        });
+
+        methods.add_method("hp", |_, prop, ()| {
+            let area_state = prop.try_unwrap()?;
+            let area_state = area_state.borrow();
+            Ok(area_state.props().get(prop.index).hp())
+        });
+
+        methods.add_method("damage", |_, prop, (amount, attacker): (u32, ScriptEntity)| {
+            let area_state = prop.try_unwrap()?;
+            let attacker = attacker.try_unwrap()?;
+            AreaState::damage_prop(&area_state, &attacker, prop.index, amount);
+            Ok(())
+        });
+
+        methods.add_method("is_locked", |_, prop, ()| {
+            let area_state = prop.try_unwrap()?;
+            let area_state = area_state.borrow();
+            Ok(area_state.props().get(prop.index).is_locked())
+        });
+
+        methods.add_method("lock_key", |_, prop, ()| {
+            let area_state = prop.try_unwrap()?;
+            let area_state = area_state.borrow();
+            let lock = area_state.props().get(prop.index).lock();
+            Ok(lock.map(|lock| lock.key.to_string()))
+        });
+
+        methods.add_method("set_state", |_, prop, id: String| {
+            let area_state = prop.try_unwrap()?;
+            if !area_state.borrow_mut().set_prop_state(prop.index, &id) {
+                warn!("Prop '{}' has no state '{}'", prop.id, id);
+            }
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,126 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+
+use std::rc::Rc;
+
+use rlua::{self, Context, UserData, UserDataMethods};
+
+use crate::script::ScriptEntity;
+use crate::world_export::CompanionExport;
+use crate::{GameState, WorldExport};
+use sulis_module::Module;
+
+/// Builds a summary of the player's decisions in this campaign, which a sequel
+/// campaign may import when starting a new game.  See `WorldExport` for the
+/// format of the written file.  This is created with
+/// `game:create_world_export()`.
+///
+/// # `set_flag(flag: String, value: String (Optional))`
+/// Sets a `flag` which will be stored on the player character in the importing
+/// campaign.  If the value is not specified, it is set to "true".
+///
+/// # `set_choice(id: String, value: String)`
+/// Records that the option `value` was chosen for the decision `id`.
+///
+/// # `add_companion(entity: ScriptEntity)`
+/// Records the state of the specified companion, including its level, whether it
+/// is alive, whether it is in the party, the party's approval with it, and its
+/// custom flags.
+///
+/// # `add_party()`
+/// Records the state of each current party member other than the player character,
+/// as with `add_companion`.
+///
+/// # `save()`
+/// Writes this export to the user directory, replacing any previous export from
+/// this campaign.
+/// ## Examples
+/// ```lua
+///   export = game:create_world_export()
+///   export:set_flag("saved_the_village")
+///   export:set_choice("throne", "restored_king")
+///   export:add_party()
+///   export:save()
+/// ```
+#[derive(Clone)]
+pub struct ScriptWorldExport {
+    export: WorldExport,
+}
+
+impl ScriptWorldExport {
+    pub(crate) fn new() -> ScriptWorldExport {
+        let player = GameState::player();
+        let player = player.borrow();
+        let campaign = Module::campaign();
+        ScriptWorldExport {
+            export: WorldExport::new(&campaign.id, &player.actor.actor.name),
+        }
+    }
+
+    fn add(&mut self, entity: ScriptEntity) -> Result<(), rlua::Error> {
+        let entity = entity.try_unwrap()?;
+        let entity = entity.borrow();
+        let approval = GameState::approval(&entity.actor.actor.id);
+        let companion = CompanionExport::new(&entity, approval);
+
+        self.export.companions.retain(|c| c.actor != companion.actor);
+        self.export.companions.push(companion);
+        Ok(())
+    }
+}
+
+impl UserData for ScriptWorldExport {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method_mut(
+            "set_flag",
+            |_, export, (flag, value): (String, Option<String>)| {
+                let val = value.unwrap_or_else(|| "true".to_string());
+                export.export.flags.insert(flag, val);
+                Ok(())
+            },
+        );
+
+        methods.add_method_mut("set_choice", |_, export, (id, value): (String, String)| {
+            export.export.choices.insert(id, value);
+            Ok(())
+        });
+
+        methods.add_method_mut("add_companion", |_, export, entity: ScriptEntity| {
+            export.add(entity)
+        });
+
+        methods.add_method_mut("add_party", |_, export, ()| {
+            let pc = GameState::player();
+            for member in GameState::party() {
+                if Rc::ptr_eq(&member, &pc) {
+                    continue;
+                }
+                export.add(ScriptEntity::from(&member))?;
+            }
+            Ok(())
+        });
+
+        methods.add_method_mut("save", save);
+    }
+}
+
+fn save(_lua: Context, export: &mut ScriptWorldExport, _: ()) -> Result<(), rlua::Error> {
+    export.export.save().map_err(|e| {
+        warn!("Unable to write world export: {}", e);
+        rlua::Error::RuntimeError(format!("Unable to write world export: {e}"))
+    })
+}
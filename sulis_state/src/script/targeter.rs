@@ -16,11 +16,12 @@
 
 use rlua::{self, Context, UserData, UserDataMethods};
 
-use sulis_module::{Module, OnTrigger};
+use sulis_core::ui::Color;
+use sulis_module::{DamageKind, Module, OnTrigger};
 
 use crate::script::area_targeter::Shape;
 use crate::script::{AreaTargeter, Result, ScriptEntity, ScriptEntitySet, ScriptItemKind};
-use crate::GameState;
+use crate::{EntityId, GameState};
 
 #[derive(Clone)]
 pub enum Kind {
@@ -86,6 +87,11 @@ pub enum SelectionArea {
 /// Sets whether a visibility blocking tile blocks further affected points in a line extending
 /// from the targeter center outwards.  Defaults to false
 ///
+/// # `set_penetrates_walls(penetrates: bool)`
+/// Circle and cone shapes do not extend through walls and other tiles that block visibility,
+/// as if `invis_blocks_affected_points` were set for them.  Setting this to true allows these
+/// shapes to affect points behind walls.  Defaults to false
+///
 /// # `allow_affected_points_impass(allow: bool)`
 /// Sets whether or not to allow affected points to be terrain impassable.  defaults to true
 ///
@@ -104,6 +110,12 @@ pub enum SelectionArea {
 /// # `set_max_effectable(max: Int)`
 /// Sets the maximum number of targets that this targeter may affect and return.
 ///
+/// # `set_multi_select(max: Int)`
+/// Allows the player to select up to `max` distinct targets, one after another, before the
+/// targeter completes.  Clicking a target that is already selected completes the targeter
+/// early, and right clicking removes the most recent selection.  The callback is passed all
+/// targets affected by any of the selections.  Has no effect in free select mode.
+///
 /// # `set_shape_circle(radius: Float, min_radius: Float (Optional))`
 /// Sets the shape of this targeter to a circle with the specified `radius`, in tiles.
 /// If `min_radius` is specified, instead creates a ring shape with the specified minimum
@@ -141,13 +153,31 @@ pub enum SelectionArea {
 /// # `set_selection_attackable()`
 /// Sets the selection area to attackable targets.  See `set_selection_radius`.
 ///
+/// # `set_selectable_color(r: Float, g: Float, b: Float, a: Float (Optional))`
+/// Sets the color used to highlight entities that may be selected by this targeter.
+/// Components are between 0.0 and 1.0.  By default, selectable entities are not tinted.
+///
+/// # `set_effectable_color(r: Float, g: Float, b: Float, a: Float (Optional))`
+/// Sets the color used to highlight the entities and tiles that will be affected by this
+/// targeter.  By default, affected entities are highlighted in red.
+///
+/// # `set_invalid_message(message: String)`
+/// Sets a `message` that is displayed to the player when they click on a location that is not
+/// a valid target, such as "Target must be undead".
+///
+/// # `set_damage_kind(kind: String)`
+/// Sets the kind of damage this targeter's ability or item deals, such as "Fire".  When
+/// hovering over a target whose resistances are known to the player, the mouseover
+/// shows whether it resists or is vulnerable to this damage.
+///
 #[derive(Clone)]
 pub struct TargeterData {
     pub kind: Kind,
-    pub parent: usize,
-    pub selectable: Vec<Option<usize>>,
-    pub effectable: Vec<Option<usize>>,
+    pub parent: EntityId,
+    pub selectable: Vec<Option<EntityId>>,
+    pub effectable: Vec<Option<EntityId>>,
     pub max_effectable: Option<usize>,
+    pub multi_select: Option<usize>,
     pub shape: Shape,
     pub show_mouseover: bool,
     pub free_select: Option<f32>,
@@ -157,18 +187,24 @@ pub struct TargeterData {
     pub impass_blocks_affected_points: bool,
     pub invis_blocks_affected_points: bool,
     pub allow_affected_points_invis: bool,
+    pub penetrates_walls: bool,
     pub on_target_select_func: String,
-    pub on_target_select_custom_target: Option<usize>,
+    pub on_target_select_custom_target: Option<EntityId>,
+    pub selectable_color: Option<Color>,
+    pub effectable_color: Option<Color>,
+    pub invalid_message: Option<String>,
+    pub damage_kind: Option<DamageKind>,
 }
 
 impl TargeterData {
-    fn new(parent: usize, kind: Kind) -> TargeterData {
+    fn new(parent: EntityId, kind: Kind) -> TargeterData {
         TargeterData {
             parent,
             kind,
             selectable: Vec::new(),
             effectable: Vec::new(),
             max_effectable: None,
+            multi_select: None,
             shape: Shape::Single,
             show_mouseover: true,
             selection_area: SelectionArea::None,
@@ -180,14 +216,19 @@ impl TargeterData {
             impass_blocks_affected_points: false,
             invis_blocks_affected_points: false,
             allow_affected_points_invis: false,
+            penetrates_walls: false,
+            selectable_color: None,
+            effectable_color: None,
+            invalid_message: None,
+            damage_kind: None,
         }
     }
 
-    pub fn new_item(parent: usize, kind: ScriptItemKind) -> TargeterData {
+    pub fn new_item(parent: EntityId, kind: ScriptItemKind) -> TargeterData {
         TargeterData::new(parent, Kind::Item(kind))
     }
 
-    pub fn new_ability(parent: usize, ability_id: &str) -> TargeterData {
+    pub fn new_ability(parent: EntityId, ability_id: &str) -> TargeterData {
         TargeterData::new(parent, Kind::Ability(ability_id.to_string()))
     }
 }
@@ -202,8 +243,8 @@ impl UserData for TargeterData {
         methods.add_method_mut(
             "set_callback_custom_target",
             |_, targeter, target: ScriptEntity| {
-                let index = target.try_unwrap_index()?;
-                targeter.on_target_select_custom_target = Some(index);
+                let id = target.try_unwrap_id()?;
+                targeter.on_target_select_custom_target = Some(id);
                 Ok(())
             },
         );
@@ -215,8 +256,8 @@ impl UserData for TargeterData {
             },
         );
         methods.add_method_mut("add_selectable", |_, targeter, target: ScriptEntity| {
-            let index = target.try_unwrap_index()?;
-            targeter.selectable.push(Some(index));
+            let id = target.try_unwrap_id()?;
+            targeter.selectable.push(Some(id));
             Ok(())
         });
         methods.add_method_mut("set_show_mouseover", |_, targeter, val: bool| {
@@ -244,6 +285,11 @@ impl UserData for TargeterData {
             },
         );
 
+        methods.add_method_mut("set_penetrates_walls", |_, targeter, penetrates: bool| {
+            targeter.penetrates_walls = penetrates;
+            Ok(())
+        });
+
         methods.add_method_mut(
             "allow_affected_points_impass",
             |_, targeter, allow: bool| {
@@ -283,14 +329,18 @@ impl UserData for TargeterData {
             },
         );
         methods.add_method_mut("add_effectable", |_, targeter, target: ScriptEntity| {
-            let index = target.try_unwrap_index()?;
-            targeter.effectable.push(Some(index));
+            let id = target.try_unwrap_id()?;
+            targeter.effectable.push(Some(id));
             Ok(())
         });
         methods.add_method_mut("set_max_effectable", |_, targeter, max: usize| {
             targeter.max_effectable = Some(max);
             Ok(())
         });
+        methods.add_method_mut("set_multi_select", |_, targeter, max: usize| {
+            targeter.multi_select = Some(max.max(1));
+            Ok(())
+        });
         methods.add_method_mut(
             "set_shape_circle",
             |_, targeter, (radius, min_radius): (f32, Option<f32>)| {
@@ -385,6 +435,32 @@ impl UserData for TargeterData {
             targeter.selection_area = SelectionArea::Attackable;
             Ok(())
         });
+
+        methods.add_method_mut(
+            "set_selectable_color",
+            |_, targeter, (r, g, b, a): (f32, f32, f32, Option<f32>)| {
+                targeter.selectable_color = Some(Color::new(r, g, b, a.unwrap_or(1.0)));
+                Ok(())
+            },
+        );
+
+        methods.add_method_mut(
+            "set_effectable_color",
+            |_, targeter, (r, g, b, a): (f32, f32, f32, Option<f32>)| {
+                targeter.effectable_color = Some(Color::new(r, g, b, a.unwrap_or(1.0)));
+                Ok(())
+            },
+        );
+
+        methods.add_method_mut("set_invalid_message", |_, targeter, message: String| {
+            targeter.invalid_message = Some(message);
+            Ok(())
+        });
+
+        methods.add_method_mut("set_damage_kind", |_, targeter, kind: String| {
+            targeter.damage_kind = Some(DamageKind::unwrap_from_str(&kind));
+            Ok(())
+        });
     }
 }
 
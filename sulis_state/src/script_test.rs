@@ -0,0 +1,341 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+//! Headless runner for ability script scenarios.  A scenario file describes
+//! a set of entities placed in an area, and a list of tests.  Each test
+//! starts from a fresh game state, activates an ability for a caster,
+//! selects the given target if the ability creates a targeter, runs the
+//! game for a time, and then checks the resulting state of the entities.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io::Error;
+use std::path::Path;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use sulis_core::resource::read_single_resource_path;
+use sulis_core::util::{invalid_data_error, Point};
+use sulis_module::{actor::Faction, Module};
+
+use crate::{EntityState, GameState, Location, Script};
+
+const PLAYER_ID: &str = "player";
+const UPDATE_STEP_MILLIS: u32 = 16;
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub player: String,
+
+    #[serde(default)]
+    pub area: Option<String>,
+
+    #[serde(default)]
+    pub location: Option<Point>,
+
+    #[serde(default)]
+    pub entities: Vec<ScenarioEntity>,
+
+    pub tests: Vec<ScenarioTest>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioEntity {
+    pub id: String,
+    pub actor: String,
+    pub location: Point,
+
+    #[serde(default)]
+    pub faction: Option<Faction>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioTest {
+    pub name: String,
+
+    #[serde(default = "default_caster")]
+    pub caster: String,
+    pub ability: String,
+
+    #[serde(default)]
+    pub target: Option<String>,
+
+    #[serde(default)]
+    pub target_point: Option<Point>,
+
+    #[serde(default = "default_run_millis")]
+    pub run_millis: u32,
+
+    #[serde(default)]
+    pub expect: Vec<Expectation>,
+}
+
+fn default_caster() -> String {
+    PLAYER_ID.to_string()
+}
+
+fn default_run_millis() -> u32 {
+    5000
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Expectation {
+    pub entity: String,
+
+    #[serde(default)]
+    pub hp: Option<i32>,
+
+    #[serde(default)]
+    pub hp_below: Option<i32>,
+
+    #[serde(default)]
+    pub hp_at_least: Option<i32>,
+
+    #[serde(default)]
+    pub dead: Option<bool>,
+
+    #[serde(default)]
+    pub has_effect: Option<String>,
+
+    #[serde(default)]
+    pub lacks_effect: Option<String>,
+}
+
+pub struct TestResult {
+    pub name: String,
+    pub failures: Vec<String>,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Reads the scenario at the specified path and runs all of its tests.
+/// The module must already be loaded.
+pub fn run_file(path: &Path) -> Result<Vec<TestResult>, Error> {
+    let scenario: Scenario = read_single_resource_path(path)?;
+    run(&scenario)
+}
+
+pub fn run(scenario: &Scenario) -> Result<Vec<TestResult>, Error> {
+    let mut results = Vec::new();
+    for test in scenario.tests.iter() {
+        info!("Running script test '{}'", test.name);
+        let entities = setup(scenario)?;
+
+        let failures = match run_test(test, &entities) {
+            Err(e) => vec![e],
+            Ok(()) => check(test, &entities),
+        };
+
+        results.push(TestResult {
+            name: test.name.to_string(),
+            failures,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Formats the results as a human readable summary
+pub fn summary(results: &[TestResult]) -> String {
+    let mut out = String::new();
+    for result in results {
+        if result.passed() {
+            let _ = writeln!(out, "PASS {}", result.name);
+        } else {
+            let _ = writeln!(out, "FAIL {}", result.name);
+            for failure in result.failures.iter() {
+                let _ = writeln!(out, "    {failure}");
+            }
+        }
+    }
+
+    let passed = results.iter().filter(|r| r.passed()).count();
+    let _ = writeln!(out, "{} of {} tests passed", passed, results.len());
+    out
+}
+
+type EntityMap = HashMap<String, Rc<RefCell<EntityState>>>;
+
+fn setup(scenario: &Scenario) -> Result<EntityMap, Error> {
+    let pc_actor = match Module::actor(&scenario.player) {
+        None => return invalid_data_error(&format!("Invalid player actor '{}'", scenario.player)),
+        Some(actor) => actor,
+    };
+
+    GameState::init(pc_actor, Vec::new(), HashMap::new())?;
+
+    if scenario.area.is_some() || scenario.location.is_some() {
+        GameState::transition_to(
+            scenario.area.as_deref(),
+            scenario.location,
+            Point::new(0, 0),
+            Default::default(),
+        );
+    }
+
+    let mut entities = HashMap::new();
+    entities.insert(PLAYER_ID.to_string(), GameState::player());
+
+    let area_state = GameState::area_state();
+    let mgr = GameState::turn_manager();
+    for data in scenario.entities.iter() {
+        let actor = match Module::actor(&data.actor) {
+            None => return invalid_data_error(&format!("Invalid actor '{}'", data.actor)),
+            Some(actor) => actor,
+        };
+
+        let (x, y) = (data.location.x, data.location.y);
+        if !area_state.borrow().is_passable_size(&actor.race.size, x, y) {
+            return invalid_data_error(&format!("'{}' location {},{} is blocked", data.id, x, y));
+        }
+
+        let location = Location::new(x, y, &area_state.borrow().area.area);
+        let index = area_state
+            .borrow_mut()
+            .add_actor(actor, location, Some(data.id.to_string()), false, None)?;
+        let entity = mgr.borrow().entity(index);
+        if let Some(faction) = data.faction {
+            entity.borrow_mut().actor.set_faction(faction);
+        }
+
+        mgr.borrow_mut()
+            .check_ai_activation(&entity, &mut area_state.borrow_mut());
+        entities.insert(data.id.to_string(), entity);
+    }
+
+    Ok(entities)
+}
+
+fn get<'a>(entities: &'a EntityMap, id: &str) -> Result<&'a Rc<RefCell<EntityState>>, String> {
+    entities
+        .get(id)
+        .ok_or_else(|| format!("No entity with id '{id}' in scenario"))
+}
+
+fn run_test(test: &ScenarioTest, entities: &EntityMap) -> Result<(), String> {
+    let caster = get(entities, &test.caster)?;
+    let ability = Module::ability(&test.ability)
+        .ok_or_else(|| format!("Invalid ability '{}'", test.ability))?;
+    if ability.active.is_none() {
+        return Err(format!("Ability '{}' is not an active ability", ability.id));
+    }
+
+    let id = caster.borrow().id();
+    Script::ability_on_activate(id, "on_activate".to_string(), &ability);
+
+    let targeter = GameState::area_state().borrow().targeter();
+    if let Some(targeter) = targeter {
+        let point = match (&test.target, test.target_point) {
+            (Some(id), _) => {
+                let target = get(entities, id)?.borrow();
+                Point::new(target.location.x, target.location.y)
+            }
+            (None, Some(point)) => point,
+            (None, None) => {
+                return Err(format!("Ability '{}' requires a target", ability.id));
+            }
+        };
+
+        let mut targeter = targeter.borrow_mut();
+        targeter.on_mouse_move(point.x, point.y);
+        if !targeter.is_valid_to_activate() {
+            targeter.on_cancel();
+            return Err(format!("Target at {},{} is not valid", point.x, point.y));
+        }
+        targeter.on_activate();
+    }
+
+    let mut elapsed = 0;
+    while elapsed < test.run_millis {
+        // UI callbacks such as conversations and cutscenes are not
+        // supported without a view and are discarded
+        let _ = GameState::update(UPDATE_STEP_MILLIS);
+        elapsed += UPDATE_STEP_MILLIS;
+    }
+
+    Ok(())
+}
+
+fn check(test: &ScenarioTest, entities: &EntityMap) -> Vec<String> {
+    let mut failures = Vec::new();
+    let mgr = GameState::turn_manager();
+    let mgr = mgr.borrow();
+
+    for expect in test.expect.iter() {
+        let entity = match get(entities, &expect.entity) {
+            Err(e) => {
+                failures.push(e);
+                continue;
+            }
+            Ok(entity) => entity.borrow(),
+        };
+        let id = &expect.entity;
+        let hp = entity.actor.hp();
+
+        if let Some(value) = expect.hp {
+            if hp != value {
+                failures.push(format!("{id}: expected hp {value}, was {hp}"));
+            }
+        }
+
+        if let Some(value) = expect.hp_below {
+            if hp >= value {
+                failures.push(format!("{id}: expected hp below {value}, was {hp}"));
+            }
+        }
+
+        if let Some(value) = expect.hp_at_least {
+            if hp < value {
+                failures.push(format!("{id}: expected hp at least {value}, was {hp}"));
+            }
+        }
+
+        if let Some(dead) = expect.dead {
+            if entity.actor.is_dead() != dead {
+                failures.push(format!("{id}: expected dead to be {dead}"));
+            }
+        }
+
+        let effects: Vec<String> = entity
+            .actor
+            .effects_iter()
+            .filter_map(|index| mgr.effect_checked(*index))
+            .map(|effect| effect.name.to_string())
+            .collect();
+
+        if let Some(name) = &expect.has_effect {
+            if !effects.contains(name) {
+                failures.push(format!("{id}: expected effect '{name}', had {effects:?}"));
+            }
+        }
+
+        if let Some(name) = &expect.lacks_effect {
+            if effects.contains(name) {
+                failures.push(format!("{id}: expected no effect '{name}'"));
+            }
+        }
+    }
+
+    failures
+}
@@ -0,0 +1,164 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::io::{Error, ErrorKind};
+use std::rc::Rc;
+use std::str::FromStr;
+
+use sulis_module::Module;
+
+use crate::{durability, EntityState, GameState};
+
+/// A paid service that NPCs may offer the party, usually from a dialogue script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    /// Restores all hit points
+    Healing,
+
+    /// Removes all injury effects
+    InjuryRemoval,
+
+    /// Returns a party member to level one, so their levels may be chosen again
+    Retraining,
+
+    /// Repairs all wear on equipped items, if durability is enabled
+    Repair,
+}
+
+impl FromStr for Service {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let val = match s {
+            "healing" => Service::Healing,
+            "injury_removal" => Service::InjuryRemoval,
+            "retraining" => Service::Retraining,
+            "repair" => Service::Repair,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unable to parse Service from '{s}'"),
+                ));
+            }
+        };
+
+        Ok(val)
+    }
+}
+
+/// Returns the cost in coins of performing the `service` on `entity`, or
+/// `None` if the service would have no effect on them
+pub fn cost(service: Service, entity: &Rc<RefCell<EntityState>>) -> Option<i32> {
+    let rules = Module::rules();
+    let rules = &rules.services;
+    let entity = entity.borrow();
+
+    let cost = match service {
+        Service::Healing => {
+            let missing = entity.actor.stats.max_hp - entity.actor.hp();
+            if missing <= 0 {
+                return None;
+            }
+            missing as u32 * rules.healing_cost_per_hp
+        }
+        Service::InjuryRemoval => {
+            let injuries = num_injuries(&entity);
+            if injuries == 0 {
+                return None;
+            }
+            injuries * rules.injury_cost
+        }
+        Service::Retraining => {
+            let level = entity.actor.actor.total_level;
+            if !entity.is_party_member() || level <= 1 {
+                return None;
+            }
+            rules.retrain_base_cost + (level - 1) * rules.retrain_cost_per_level
+        }
+        Service::Repair => {
+            let wear = durability::equipped_wear(&entity);
+            if !durability::is_enabled() || wear == 0 {
+                return None;
+            }
+            wear * rules.repair_cost
+        }
+    };
+
+    Some(cost as i32)
+}
+
+/// Charges the party coins and performs the `service` on `entity`.  Returns
+/// false without doing anything if the service would have no effect or the
+/// party cannot afford it
+pub fn purchase(service: Service, entity: &Rc<RefCell<EntityState>>) -> bool {
+    let cost = match cost(service, entity) {
+        None => return false,
+        Some(cost) => cost,
+    };
+
+    if GameState::party_coins() < cost {
+        return false;
+    }
+
+    info!(
+        "Purchasing {:?} for '{}' at {} coins",
+        service,
+        entity.borrow().actor.actor.name,
+        cost
+    );
+    GameState::add_party_coins(-cost);
+    let stash = GameState::party_stash();
+    let stash = &stash.borrow();
+    stash.listeners.notify(stash);
+
+    match service {
+        Service::Healing => {
+            let mut entity = entity.borrow_mut();
+            let max_hp = entity.actor.stats.max_hp;
+            entity.actor.add_hp(max_hp as u32);
+        }
+        Service::InjuryRemoval => remove_injuries(entity),
+        Service::Retraining => entity.borrow_mut().actor.retrain(),
+        Service::Repair => durability::repair(entity, usize::MAX),
+    }
+
+    true
+}
+
+fn num_injuries(entity: &EntityState) -> u32 {
+    let rules = Module::rules();
+    let mgr = GameState::turn_manager();
+    let mgr = mgr.borrow();
+    let injuries = entity
+        .actor
+        .effects_iter()
+        .filter(|index| mgr.effect(**index).tag == rules.services.injury_tag)
+        .count();
+    injuries as u32
+}
+
+fn remove_injuries(entity: &Rc<RefCell<EntityState>>) {
+    let rules = Module::rules();
+    let mgr = GameState::turn_manager();
+    let mut mgr = mgr.borrow_mut();
+    for index in entity.borrow().actor.effects_iter() {
+        let effect = mgr.effect_mut(*index);
+        if effect.tag == rules.services.injury_tag {
+            effect.mark_for_removal();
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::util::ExtInt;
+use sulis_module::{ItemState, Module};
+
+use crate::{Effect, EntityState, GameMessage, GameState};
+
+const STARVATION_TAG: &str = "starvation";
+
+/// Returns the number of rations in the party stash, or `None` if the rules do
+/// not use supplies
+pub fn num_rations() -> Option<u32> {
+    let ration = ration_item()?;
+    let stash = GameState::party_stash();
+    let stash = stash.borrow();
+    let count = stash
+        .items()
+        .iter()
+        .filter(|(_, item)| *item == ration)
+        .map(|(qty, _)| *qty)
+        .sum();
+    Some(count)
+}
+
+/// Returns the number of rations each party member eats while traveling the
+/// world map for the specified number of `hours`
+pub fn travel_rations(hours: u32) -> u32 {
+    let per_ration = Module::rules().supplies.travel_hours_per_ration;
+    if per_ration == 0 || ration_item().is_none() {
+        return 0;
+    }
+
+    hours.div_ceil(per_ration)
+}
+
+/// Removes `per_member` rations from the party stash for each party member.
+/// Members that cannot be fed begin starving, while members that are fed
+/// recover from starvation
+pub(crate) fn consume_rations(per_member: u32) {
+    if per_member == 0 {
+        return;
+    }
+
+    let ration = match ration_item() {
+        None => return,
+        Some(item) => item,
+    };
+
+    let stash = GameState::party_stash();
+    for member in GameState::party() {
+        let mut fed = true;
+        for _ in 0..per_member {
+            let index = stash.borrow().items().find_index(&ration);
+            let eaten = match index {
+                None => None,
+                Some(index) => stash.borrow_mut().remove_item(index),
+            };
+
+            if eaten.is_none() {
+                fed = false;
+                break;
+            }
+        }
+
+        if fed {
+            end_starvation(&member);
+        } else {
+            start_starvation(&member);
+        }
+    }
+}
+
+fn ration_item() -> Option<ItemState> {
+    let rules = Module::rules();
+    let id = rules.supplies.ration_item.as_ref()?;
+    match Module::create_get_item(id, &[]) {
+        None => {
+            warn!("Ration item '{}' not found", id);
+            None
+        }
+        Some(item) => Some(ItemState::new(item, None)),
+    }
+}
+
+fn is_starving(entity: &Rc<RefCell<EntityState>>) -> bool {
+    let mgr = GameState::turn_manager();
+    let mgr = mgr.borrow();
+    let entity = entity.borrow();
+    let starving = entity
+        .actor
+        .effects_iter()
+        .any(|index| mgr.effect(*index).tag == STARVATION_TAG);
+    starving
+}
+
+fn start_starvation(entity: &Rc<RefCell<EntityState>>) {
+    if is_starving(entity) {
+        return;
+    }
+
+    info!("'{}' is starving", entity.borrow().actor.actor.name);
+    GameState::add_message(GameMessage::default().entity(entity).text(" is starving"));
+    let rules = Module::rules();
+    let supplies = &rules.supplies;
+    let mut effect = Effect::new(
+        &supplies.starvation_name,
+        STARVATION_TAG,
+        ExtInt::Infinity,
+        supplies.starvation_bonuses.clone(),
+        None,
+    );
+    if let Some(icon) = &supplies.starvation_icon {
+        effect.set_icon(icon.to_string(), supplies.starvation_name.to_string());
+    }
+    effect.set_owning_entity(entity.borrow().index());
+
+    let mgr = GameState::turn_manager();
+    mgr.borrow_mut()
+        .add_effect(effect, entity, Vec::new(), Vec::new());
+}
+
+fn end_starvation(entity: &Rc<RefCell<EntityState>>) {
+    let mgr = GameState::turn_manager();
+    let mut mgr = mgr.borrow_mut();
+    for index in entity.borrow().actor.effects_iter() {
+        let effect = mgr.effect_mut(*index);
+        if effect.tag == STARVATION_TAG {
+            effect.mark_for_removal();
+        }
+    }
+}
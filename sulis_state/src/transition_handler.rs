@@ -17,6 +17,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::area_state::AreaScriptFunc;
 use crate::{AreaState, EntityState, GameState, Location, TurnManager};
 use sulis_core::{util::Point};
 use sulis_module::{
@@ -25,6 +26,8 @@ use sulis_module::{
 };
 
 pub(crate) fn transition_to(area_id: Option<&str>, p: Option<Point>, offset: Point, time: Time) {
+    let area_id = area_id.map(GameState::swapped_area_id);
+    let area_id = area_id.as_deref();
     info!("Area transition to {:?}: {:?}", area_id, p);
 
     if let Some(id) = area_id {
@@ -52,10 +55,19 @@ pub(crate) fn transition_to(area_id: Option<&str>, p: Option<Point>, offset: Poi
 
     // Point of no return - we are actually transitioning now
 
+    let old_area = GameState::area_state();
     let new_area = GameState::set_current_area(&area);
+    if new_area {
+        old_area.borrow().fire_script(AreaScriptFunc::Exit);
+    }
     GameState::set_clear_anims(); // cleanup anims and surfaces
 
     let mgr = GameState::turn_manager();
+    if area_id.is_some() {
+        // the party has fled to another area
+        mgr.borrow_mut().stop_combat();
+    }
+
     let party = GameState::party();
     let area = GameState::area_state(); // it changed above in set_current_area
 
@@ -81,8 +93,13 @@ pub(crate) fn transition_to(area_id: Option<&str>, p: Option<Point>, offset: Poi
 
     area.update_view_visibility();
 
+    if new_area {
+        area.fire_script(AreaScriptFunc::Load);
+    }
+
     if !area.on_load_fired {
         area.on_load_fired = true;
+        area.fire_script(AreaScriptFunc::FirstEnter);
         GameState::add_ui_callbacks_of_kind(
             &area.area.area.triggers,
             TriggerKind::OnAreaLoad,
@@ -108,6 +125,21 @@ pub(crate) fn transition_to(area_id: Option<&str>, p: Option<Point>, offset: Poi
     }
 }
 
+/// Moves a single entity from its current area to the point `p` in `area`,
+/// which must already be loaded, carrying any auras with it
+pub(crate) fn move_entity_to(
+    entity: &Rc<RefCell<EntityState>>,
+    area: &Rc<RefCell<AreaState>>,
+    p: Point,
+) {
+    let mgr = GameState::turn_manager();
+    let entities = [Rc::clone(entity)];
+
+    remove_party_from_surfaces(&mut mgr.borrow_mut(), &entities);
+    remove_party_auras(&mut mgr.borrow_mut(), &entities);
+    transition_party(&mgr, area, p, &entities);
+}
+
 fn transition_party(
     mgr: &Rc<RefCell<TurnManager>>,
     area: &Rc<RefCell<AreaState>>,
@@ -117,7 +149,9 @@ fn transition_party(
     let base_location = Location::new(p.x, p.y, &area.borrow().area.area);
 
     for entity in party {
-        entity.borrow_mut().clear_pc_vis();
+        if entity.borrow().is_party_member() {
+            entity.borrow_mut().clear_pc_vis();
+        }
         let mut cur_location = base_location.clone();
         find_transition_location(&mut cur_location, &entity.borrow().size, &area.borrow());
 
@@ -186,7 +220,12 @@ fn add_member_auras(mgr: &mut TurnManager, area: &mut AreaState, index: usize, d
             p.y -= dy;
         }
 
-        let to_add = area.add_surface(aura_index, &surface.points);
+        let to_add = area.add_surface(
+            aura_index,
+            &surface.points,
+            surface.hazard,
+            surface.hidden_by,
+        );
         for entity in to_add {
             mgr.add_to_surface(entity, aura_index);
         }
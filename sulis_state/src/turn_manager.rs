@@ -17,10 +17,14 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{vec_deque::Iter, HashMap, HashSet, VecDeque};
 use std::rc::Rc;
-
-use crate::script::{CallbackData, FuncKind, TriggeredCallback};
-use crate::{AreaState, ChangeListener, ChangeListenerList, Effect, EntityState, GameState};
-use sulis_core::{config::Config, util::{gen_rand, Point}};
+use std::time::Instant;
+
+use crate::script::{CallbackData, FuncKind, TriggeredCallback, TriggeredCallbackSaveState};
+use crate::{
+    AreaState, AutoPause, AutoPauseTrigger, ChangeListener, ChangeListenerList, CombatLog, Effect,
+    EntityId, EntityState, GameState,
+};
+use sulis_core::{config::Config, ui::Narrator, util::{gen_rand, Point}};
 use sulis_module::{Faction, Module, Time, ROUND_TIME_MILLIS, OnTrigger};
 
 fn add_campaign_elapsed_callback(cbs: &mut Vec<Rc<CallbackData>>) {
@@ -31,14 +35,34 @@ fn add_campaign_elapsed_callback(cbs: &mut Vec<Rc<CallbackData>>) {
 
     let player = GameState::player();
 
-    let mut cb = CallbackData::new_trigger(player.borrow().index(), script_data.id);
+    let mut cb = CallbackData::new_trigger(player.borrow().id(), script_data.id);
     cb.add_func(FuncKind::OnRoundElapsed, script_data.func);
 
     cbs.push(Rc::new(cb));
 }
 
-#[derive(Clone, Copy)]
-enum Entry {
+fn add_area_elapsed_callback(cbs: &mut Vec<Rc<CallbackData>>) {
+    let area_state = GameState::area_state();
+    let area_state = area_state.borrow();
+    let (id, func) = match &area_state.area.area.script {
+        Some(script) => match &script.on_round_elapsed {
+            None => return,
+            Some(func) => (script.id.to_string(), func.to_string()),
+        },
+        None => return,
+    };
+
+    let player = GameState::player();
+
+    let mut cb = CallbackData::new_trigger(player.borrow().id(), id);
+    cb.add_func(FuncKind::OnRoundElapsed, func);
+
+    cbs.push(Rc::new(cb));
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+pub(crate) enum Entry {
     Entity(usize),
     Effect(usize),
     TurnChange,
@@ -54,6 +78,8 @@ pub struct EncounterRef {
 #[derive(Default)]
 pub struct TurnManager {
     entities: Vec<Option<Rc<RefCell<EntityState>>>>,
+    generations: Vec<u32>,
+    free_entity_slots: Vec<usize>,
     pub(crate) effects: Vec<Option<Effect>>,
     surfaces: Vec<usize>,
     auras: HashMap<usize, Vec<usize>>,
@@ -61,6 +87,8 @@ pub struct TurnManager {
     entities_move_callback_next_update: HashSet<usize>,
     triggered_cbs_next_update: Vec<TriggeredCallback>,
     combat_active: bool,
+    delayed_turns: HashSet<usize>,
+    engaged_groups: HashSet<usize>,
 
     pub time_listeners: ChangeListenerList<Time>,
     pub listeners: ChangeListenerList<TurnManager>,
@@ -126,12 +154,16 @@ impl TurnManager {
 
     pub(crate) fn load(&mut self, total_elapsed_millis: usize) {
         self.entities.clear();
+        self.generations.clear();
+        self.free_entity_slots.clear();
         self.effects.clear();
         self.surfaces.clear();
         self.auras.clear();
         self.effects_remove_next_update.clear();
         self.triggered_cbs_next_update.clear();
         self.combat_active = false;
+        self.delayed_turns.clear();
+        self.engaged_groups.clear();
         self.listeners = ChangeListenerList::default();
         self.time_listeners = ChangeListenerList::default();
         self.order.clear();
@@ -229,6 +261,69 @@ impl TurnManager {
         self.entities[index].clone()
     }
 
+    /// Returns the entity with the specified `id`, if that entity is still
+    /// present.  Unlike `entity_checked`, this will not return a different
+    /// entity that has since been placed in the same slot.
+    pub fn entity_by_id(&self, id: EntityId) -> Option<Rc<RefCell<EntityState>>> {
+        if self.generations.get(id.index) != Some(&id.generation) {
+            return None;
+        }
+
+        self.entity_checked(id.index)
+    }
+
+    /// Returns the ID of the entity currently at `index`, if any
+    pub fn entity_id(&self, index: usize) -> Option<EntityId> {
+        match self.entities.get(index) {
+            Some(Some(_)) => Some(EntityId::new(index, self.generations[index])),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn entity_generations(&self) -> Vec<u32> {
+        self.generations.clone()
+    }
+
+    /// Sets the slot generations from a save, prior to loading the saved
+    /// entities with `load_entity`.  Slots without a saved entity will be
+    /// reused for new entities, with a generation newer than any saved
+    /// reference to that slot.
+    pub(crate) fn load_entity_generations(&mut self, generations: Vec<u32>) {
+        self.entities = vec![None; generations.len()];
+        self.free_entity_slots = (0..generations.len()).rev().collect();
+        self.generations = generations.into_iter().map(|gen| gen + 1).collect();
+    }
+
+    fn next_entity_id(&mut self) -> EntityId {
+        match self.free_entity_slots.pop() {
+            Some(index) => EntityId::new(index, self.generations[index]),
+            None => {
+                self.entities.push(None);
+                self.generations.push(0);
+                EntityId::new(self.entities.len() - 1, 0)
+            }
+        }
+    }
+
+    fn reserve_entity_id(&mut self, id: EntityId) -> EntityId {
+        if id.index < self.entities.len() && self.entities[id.index].is_some() {
+            warn!("Entity slot {} is already in use, assigning a new ID", id.index);
+            return self.next_entity_id();
+        }
+
+        // slots skipped over here may be referenced by saves without
+        // generations, so start them past the default generation
+        while self.entities.len() <= id.index {
+            self.entities.push(None);
+            self.generations.push(1);
+            self.free_entity_slots.insert(0, self.entities.len() - 1);
+        }
+
+        self.free_entity_slots.retain(|index| *index != id.index);
+        self.generations[id.index] = id.generation;
+        id
+    }
+
     pub fn entity(&self, index: usize) -> Rc<RefCell<EntityState>> {
         Rc::clone(self.entities[index].as_ref().unwrap())
     }
@@ -239,10 +334,14 @@ impl TurnManager {
         for index in self.surfaces.iter() {
             let effect = self.effects[*index].as_mut().unwrap();
             for (cb, entity_index) in effect.update_on_moved_in_surface() {
+                let target = match self.entity_id(entity_index) {
+                    None => continue,
+                    Some(id) => id,
+                };
                 result.push(TriggeredCallback::with_target(
                     cb,
                     FuncKind::OnMovedInSurface,
-                    entity_index,
+                    target,
                 ));
             }
         }
@@ -260,7 +359,10 @@ impl TurnManager {
 
         let indices: Vec<_> = self.entities_move_callback_next_update.drain().collect();
         for index in indices {
-            let entity = self.entity(index);
+            let entity = match self.entity_checked(index) {
+                None => continue,
+                Some(entity) => entity,
+            };
             cbs.append(&mut entity.borrow().callbacks(self));
         }
 
@@ -289,6 +391,7 @@ impl TurnManager {
         if new_round {
             let mut cec_cbs = Vec::new();
             add_campaign_elapsed_callback(&mut cec_cbs);
+            add_area_elapsed_callback(&mut cec_cbs);
             for cb in cec_cbs {
                 cbs.push(TriggeredCallback::new(cb, FuncKind::OnRoundElapsed));
             }
@@ -324,6 +427,17 @@ impl TurnManager {
         cbs
     }
 
+    /// Drops the composed images of entities that have not been drawn
+    /// recently.  They are composed again the next time they are drawn.
+    pub fn release_unused_images(&self) {
+        let now = Instant::now();
+        for entity in self.entities.iter().flatten() {
+            if let Ok(mut entity) = entity.try_borrow_mut() {
+                entity.actor.release_unused_image(now);
+            }
+        }
+    }
+
     #[must_use]
     fn update_effect(
         &mut self,
@@ -408,10 +522,15 @@ impl TurnManager {
         }
 
         let mut current = current.borrow_mut();
-        current.actor.init_turn();
-        current.actor.elapse_time(ROUND_TIME_MILLIS, &self.effects);
+
+        // a delayed entity resumes the turn it already started this round
+        if !self.delayed_turns.remove(&current.index()) {
+            current.actor.init_turn();
+            current.actor.elapse_time(ROUND_TIME_MILLIS, &self.effects);
+        }
 
         debug!("'{}' now has the active turn", current.actor.actor.name);
+        Narrator::say(&format!("{}'s turn", current.actor.actor.name));
     }
 
     pub fn current(&self) -> Option<Rc<RefCell<EntityState>>> {
@@ -428,10 +547,102 @@ impl TurnManager {
         }
     }
 
+    /// Moves the turn of the specified entity, which must currently be active,
+    /// to just after the next active entity in the current round.  The entity
+    /// keeps its remaining AP when its turn comes back around.  Returns false
+    /// if the turn could not be delayed.
+    pub fn delay_turn(&mut self, entity: &Rc<RefCell<EntityState>>) -> bool {
+        let mut next = None;
+        for entry in self.order.iter().skip(1) {
+            match entry {
+                Entry::TurnChange => break,
+                Entry::Effect(_) => (),
+                Entry::Entity(other) => {
+                    if self.is_active_entity(*other) {
+                        next = Some(*other);
+                        break;
+                    }
+                }
+            }
+        }
+
+        match next {
+            None => false,
+            Some(other) => self.delay_turn_to(entity, other),
+        }
+    }
+
+    /// Moves the turn of the specified entity, which must currently be active,
+    /// to just after `other`, which must still be due to act in the current
+    /// round.  The new order is kept for subsequent rounds.  Returns false if
+    /// the turn could not be delayed.
+    pub fn delay_turn_after(
+        &mut self,
+        entity: &Rc<RefCell<EntityState>>,
+        other: &Rc<RefCell<EntityState>>,
+    ) -> bool {
+        let other = other.borrow().index();
+        self.delay_turn_to(entity, other)
+    }
+
+    fn delay_turn_to(&mut self, entity: &Rc<RefCell<EntityState>>, other: usize) -> bool {
+        if !self.combat_active || !self.is_active_entity(other) {
+            return false;
+        }
+
+        let index = entity.borrow().index();
+        match self.order.front() {
+            Some(Entry::Entity(front)) if *front == index => (),
+            _ => return false,
+        }
+
+        let mut target = None;
+        for (pos, entry) in self.order.iter().enumerate().skip(1) {
+            match entry {
+                Entry::TurnChange => break,
+                Entry::Effect(_) => (),
+                Entry::Entity(cur) => {
+                    if *cur == other {
+                        target = Some(pos);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let target = match target {
+            None => return false,
+            Some(target) => target,
+        };
+
+        // removing the front shifts the target down by one, so inserting at the
+        // old target position places the delayed entity directly after it
+        let entry = self.order.pop_front().unwrap();
+        self.order.insert(target, entry);
+        self.delayed_turns.insert(index);
+
+        for cb in self.advance_to_active_entity(true) {
+            self.triggered_cbs_next_update.push(TriggeredCallback::new(
+                Rc::clone(&cb),
+                FuncKind::OnRoundElapsed,
+            ));
+            self.triggered_cbs_next_update
+                .push(TriggeredCallback::new(cb, FuncKind::OnSurfaceRoundElapsed));
+        }
+
+        self.init_turn_for_current_entity(&mut GameState::area_state().borrow_mut());
+        self.listeners.notify(self);
+        true
+    }
+
     #[must_use]
     fn iterate_to_next_entity(&mut self) -> Vec<Rc<CallbackData>> {
+        self.advance_to_active_entity(false)
+    }
+
+    #[must_use]
+    fn advance_to_active_entity(&mut self, mut current_ended: bool) -> Vec<Rc<CallbackData>> {
         let mut cbs = Vec::new();
-        let mut current_ended = false;
 
         loop {
             if current_ended && self.current_is_active_entity() {
@@ -465,9 +676,13 @@ impl TurnManager {
                     current_ended = true;
                 }
                 Entry::TurnChange => {
+                    if self.combat_active {
+                        CombatLog::next_round();
+                    }
                     self.add_millis(ROUND_TIME_MILLIS);
                     self.order.push_back(Entry::TurnChange);
                     add_campaign_elapsed_callback(&mut cbs);
+                    add_area_elapsed_callback(&mut cbs);
                 }
             }
         }
@@ -476,14 +691,36 @@ impl TurnManager {
     }
 
     fn current_is_active_entity(&self) -> bool {
-        if let Some(Entry::Entity(index)) = self.order.front() {
-            if let Some(entity) = &self.entities[*index] {
+        match self.order.front() {
+            Some(Entry::Entity(index)) => self.is_active_entity(*index),
+            _ => false,
+        }
+    }
+
+    fn is_active_entity(&self, index: usize) -> bool {
+        match &self.entities[index] {
+            None => false,
+            Some(entity) => {
                 let entity = entity.borrow();
-                return entity.is_party_member() || entity.is_ai_active();
+                if entity.is_party_member() {
+                    self.is_engaged(&entity)
+                } else {
+                    entity.is_ai_active()
+                }
             }
         }
+    }
 
-        false
+    /// Returns true if the specified entity takes part in the current combat.
+    /// When the party is split, only the groups seen by hostiles are engaged,
+    /// while the other groups wait for combat to end.  If no groups have been
+    /// engaged, such as when a script starts combat, the whole party is
+    pub fn is_engaged(&self, entity: &EntityState) -> bool {
+        if !self.combat_active || !entity.is_party_member() {
+            return true;
+        }
+
+        self.engaged_groups.is_empty() || self.engaged_groups.contains(&entity.party_group())
     }
 
     pub fn check_ai_activation_for_party(&mut self, area_state: &mut AreaState) {
@@ -503,6 +740,10 @@ impl TurnManager {
 
         let mut groups_to_activate: HashSet<usize> = HashSet::new();
         let mut state_changed = false;
+        let mut creatures_seen = Vec::new();
+        let mut enemy_sighted = false;
+        let mut party_seen = false;
+        let mut groups_seen = HashSet::new();
 
         for entity in self.entities.iter() {
             let entity = match entity {
@@ -524,18 +765,25 @@ impl TurnManager {
             if !entity.location.is_in(area_state) {
                 continue;
             }
-            if entity.actor.actor.ai.is_none() && !entity.is_party_member() {
+            if entity.own_ai_template().is_none() && !entity.is_party_member() {
                 continue;
             }
 
             let mover = mover.borrow();
-            if !area_state.has_visibility(&mover, &entity)
-                && !area_state.has_visibility(&entity, &mover)
-            {
+            if !area_state.has_visibility_either(&mover, &entity) {
                 continue;
             }
 
             log::warn!("Found ai activation entity: {} at {:?}", entity.unique_id(), entity.location);
+            if !entity.is_party_member() {
+                creatures_seen.push(entity.actor.actor.id.to_string());
+                if mover.is_party_member() && !entity.is_ai_active() {
+                    enemy_sighted = true;
+                }
+            } else {
+                party_seen = true;
+                groups_seen.insert(entity.party_group());
+            }
             self.activate_entity_ai(&mut entity, &mut groups_to_activate);
             state_changed = true;
         }
@@ -544,6 +792,26 @@ impl TurnManager {
             return;
         }
 
+        if mover.borrow().is_party_member() {
+            groups_seen.insert(mover.borrow().party_group());
+        }
+        if !self.combat_active || !self.engaged_groups.is_empty() {
+            self.engaged_groups.extend(groups_seen);
+        }
+
+        if !mover.borrow().is_party_member() {
+            creatures_seen.push(mover.borrow().actor.actor.id.to_string());
+            if party_seen && !mover.borrow().is_ai_active() {
+                enemy_sighted = true;
+            }
+        }
+        if enemy_sighted {
+            AutoPause::trigger(AutoPauseTrigger::EnemySighted);
+        }
+        for id in creatures_seen {
+            GameState::record_creature_seen(&id);
+        }
+
         self.activate_entity_ai(&mut mover.borrow_mut(), &mut groups_to_activate);
 
         for entity in self.entities.iter() {
@@ -587,23 +855,67 @@ impl TurnManager {
                 let group = &self.ai_groups[i];
                 group.encounter_index
             }).collect();
-            area_state.update_music(true, Some(&enc_indices));
+            self.begin_combat(area_state, &enc_indices);
+        }
 
-            self.set_combat_active(true);
-            loop {
-                if self.current_is_active_entity() {
-                    break;
-                }
-                let front = self.order.pop_front().unwrap();
-                self.order.push_back(front);
+        self.listeners.notify(self);
+    }
+
+    /// Starts combat with the specified entities, regardless of whether they
+    /// can see any hostiles.  Only the listed entities are activated, allowing
+    /// scripts to set up surprise rounds.  If combat is already active, the
+    /// entities simply join it.
+    pub fn start_combat(
+        &mut self,
+        entities: &[Rc<RefCell<EntityState>>],
+        area_state: &mut AreaState,
+    ) {
+        let mut groups = HashSet::new();
+        for entity in entities {
+            let mut entity = entity.borrow_mut();
+            if entity.actor.is_dead() {
+                continue;
             }
-            crate::party_bump_handler::bump_party_overlap(area_state, self);
-            self.init_turn_for_current_entity(area_state);
+            self.activate_entity_ai(&mut entity, &mut groups);
+        }
+
+        if !self.combat_active {
+            let enc_indices: Vec<usize> = groups
+                .iter()
+                .filter_map(|i| self.ai_groups.get(i))
+                .map(|group| group.encounter_index)
+                .collect();
+            self.begin_combat(area_state, &enc_indices);
         }
 
         self.listeners.notify(self);
     }
 
+    /// Immediately ends combat, if it is active, deactivating all AI
+    pub fn stop_combat(&mut self) {
+        if !self.combat_active {
+            return;
+        }
+
+        self.set_combat_active(false);
+        self.listeners.notify(self);
+    }
+
+    fn begin_combat(&mut self, area_state: &mut AreaState, enc_indices: &[usize]) {
+        area_state.update_music(true, Some(enc_indices));
+
+        self.set_combat_active(true);
+        loop {
+            if self.current_is_active_entity() {
+                break;
+            }
+            let front = self.order.pop_front().unwrap();
+            self.order.push_back(front);
+        }
+        crate::party_bump_handler::bump_party_overlap(area_state, self);
+        self.init_turn_for_current_entity(area_state);
+    }
+
     fn activate_entity_ai(&self, entity: &mut EntityState, groups: &mut HashSet<usize>) {
         if entity.is_party_member() {
             return;
@@ -631,10 +943,18 @@ impl TurnManager {
 
         info!("Setting combat mode active = {}", active);
         self.combat_active = active;
+        self.delayed_turns.clear();
+        if !active {
+            self.engaged_groups.clear();
+        }
 
         if !active {
+            Narrator::say("Combat ended");
             self.end_combat();
+            CombatLog::finish();
         } else {
+            Narrator::say("Combat started");
+            CombatLog::start();
             self.initiate_combat();
         }
     }
@@ -643,8 +963,22 @@ impl TurnManager {
         let run_away_dist = Module::rules().combat_run_away_vis_factor
             * GameState::area_state().borrow().area.area.vis_dist as f32;
 
+        !self.is_ai_active_within(run_away_dist)
+    }
+
+    /// Returns true if the party may leave the area during the current combat,
+    /// based on the distance to the nearest active hostile
+    pub fn can_flee(&self) -> bool {
+        match Module::rules().transitions.flee_hostile_distance {
+            None => false,
+            Some(dist) => !self.is_ai_active_within(dist),
+        }
+    }
+
+    fn is_ai_active_within(&self, dist: f32) -> bool {
         let party_pos: Vec<_> = GameState::party()
             .into_iter()
+            .filter(|e| self.is_engaged(&e.borrow()))
             .map(|e| e.borrow().location.to_point())
             .collect();
 
@@ -659,13 +993,13 @@ impl TurnManager {
             }
 
             for p in &party_pos {
-                if p.dist(entity.location.to_point()) < run_away_dist {
-                    return false;
+                if p.dist(entity.location.to_point()) < dist {
+                    return true;
                 }
             }
         }
 
-        true
+        false
     }
 
     fn end_combat(&mut self) {
@@ -694,7 +1028,7 @@ impl TurnManager {
                     continue;
                 }
 
-                let mut cb = CallbackData::new_ability(entity.index(), id);
+                let mut cb = CallbackData::new_ability(entity.id(), id);
                 cb.add_func(FuncKind::OnDeactivated, "on_deactivate".to_string());
                 let cb = TriggeredCallback::new(Rc::new(cb), FuncKind::OnDeactivated);
                 self.triggered_cbs_next_update.push(cb);
@@ -763,6 +1097,66 @@ impl TurnManager {
         GameState::set_clear_anims();
     }
 
+    pub(crate) fn save_order(&self) -> Vec<Entry> {
+        self.order.iter().copied().collect()
+    }
+
+    pub(crate) fn save_engaged_groups(&self) -> Vec<usize> {
+        let mut groups: Vec<usize> = self.engaged_groups.iter().copied().collect();
+        groups.sort_unstable();
+        groups
+    }
+
+    pub(crate) fn save_delayed_turns(&self) -> Vec<usize> {
+        let mut delayed: Vec<usize> = self.delayed_turns.iter().copied().collect();
+        delayed.sort_unstable();
+        delayed
+    }
+
+    pub(crate) fn save_triggered_cbs(&self) -> Vec<TriggeredCallbackSaveState> {
+        self.triggered_cbs_next_update.iter().map(|cb| cb.save()).collect()
+    }
+
+    pub(crate) fn load_triggered_cbs(&mut self, cbs: Vec<TriggeredCallback>) {
+        self.triggered_cbs_next_update.extend(cbs);
+    }
+
+    /// Restores combat from a save, replacing the turn order built up while
+    /// loading entities and effects.  Effect entries are mapped to their new
+    /// indices with `effects`, and entries no longer present are dropped.
+    pub(crate) fn load_combat(
+        &mut self,
+        order: Vec<Entry>,
+        delayed_turns: Vec<usize>,
+        engaged_groups: Vec<usize>,
+        effects: &HashMap<usize, usize>,
+    ) {
+        let order: VecDeque<Entry> = order
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Entry::Entity(index) if self.has_entity(index) => Some(entry),
+                Entry::Entity(_) => None,
+                Entry::Effect(index) => effects.get(&index).map(|index| Entry::Effect(*index)),
+                Entry::TurnChange => Some(entry),
+            })
+            .collect();
+
+        if !order.iter().any(|entry| matches!(entry, Entry::Entity(_))) {
+            warn!("Saved combat has no valid entities in the turn order, ignoring");
+            return;
+        }
+
+        self.order = order;
+        self.combat_active = true;
+        self.delayed_turns = delayed_turns.into_iter().collect();
+        self.engaged_groups = engaged_groups.into_iter().collect();
+        self.listeners.notify(self);
+    }
+
+    pub(crate) fn add_triggered_cb(&mut self, cb: TriggeredCallback) {
+        self.triggered_cbs_next_update.push(cb);
+    }
+
     pub(crate) fn fire_on_moved_next_update(&mut self, entity_index: usize) {
         self.entities_move_callback_next_update.insert(entity_index);
     }
@@ -790,11 +1184,12 @@ impl TurnManager {
             .add_effect(surface_index, surface.bonuses().clone());
         surface.increment_squares_moved(entity_index);
 
+        let target = entity.borrow().id();
         for cb in surface.callbacks.iter() {
             let cb = TriggeredCallback::with_target(
                 Rc::clone(cb),
                 FuncKind::OnEnteredSurface,
-                entity_index,
+                target,
             );
             self.triggered_cbs_next_update.push(cb);
         }
@@ -813,11 +1208,12 @@ impl TurnManager {
         );
         entity.borrow_mut().actor.remove_effect(surface_index);
 
+        let target = entity.borrow().id();
         for cb in surface.callbacks.iter() {
             let cb = TriggeredCallback::with_target(
                 Rc::clone(cb),
                 FuncKind::OnExitedSurface,
-                entity_index,
+                target,
             );
             self.triggered_cbs_next_update.push(cb);
         }
@@ -829,6 +1225,26 @@ impl TurnManager {
     }
 
     pub fn add_entity(&mut self, entity: &Rc<RefCell<EntityState>>, is_dead: bool) -> usize {
+        self.insert_entity(entity, None, is_dead)
+    }
+
+    /// Adds an entity from a save, keeping its saved `id` so that references
+    /// to it remain valid
+    pub(crate) fn load_entity(
+        &mut self,
+        entity: &Rc<RefCell<EntityState>>,
+        id: EntityId,
+        is_dead: bool,
+    ) -> usize {
+        self.insert_entity(entity, Some(id), is_dead)
+    }
+
+    fn insert_entity(
+        &mut self,
+        entity: &Rc<RefCell<EntityState>>,
+        id: Option<EntityId>,
+        is_dead: bool,
+    ) -> usize {
         {
             let entity = entity.borrow();
             let uid = entity.unique_id();
@@ -841,9 +1257,12 @@ impl TurnManager {
             }
         }
 
-        let entity_to_add = Rc::clone(entity);
-        self.entities.push(Some(entity_to_add));
-        let index = self.entities.len() - 1;
+        let id = match id {
+            None => self.next_entity_id(),
+            Some(id) => self.reserve_entity_id(id),
+        };
+        let index = id.index;
+        self.entities[index] = Some(Rc::clone(entity));
 
         if !is_dead {
             self.order.push_back(Entry::Entity(index));
@@ -854,7 +1273,7 @@ impl TurnManager {
             );
         }
 
-        entity.borrow_mut().set_index(index);
+        entity.borrow_mut().set_id(id);
         entity.borrow_mut().actor.init_turn();
         self.listeners.notify(self);
 
@@ -905,13 +1324,15 @@ impl TurnManager {
             None => None,
             Some(surface) => surface.aura,
         };
+        let hazard = effect.is_hazard();
+        let hidden_by = effect.hazard_hidden_by();
         let index = self.add_effect_internal(effect, cbs, removal_markers);
         self.surfaces.push(index);
         if let Some(aura_parent) = aura_parent {
             let auras_for_parent = self.auras.entry(aura_parent).or_default();
             (*auras_for_parent).push(index);
         }
-        let entities = area_state.borrow_mut().add_surface(index, &points);
+        let entities = area_state.borrow_mut().add_surface(index, &points, hazard, hidden_by);
 
         for entity in entities {
             self.add_to_surface(entity, index);
@@ -929,7 +1350,10 @@ impl TurnManager {
     ) -> usize {
         let index = self.add_effect_internal(effect, cbs, removal_markers);
 
-        let bonuses = self.effect(index).bonuses().clone();
+        let effect = self.effect(index);
+        CombatLog::record_effect(&entity.borrow(), effect.name(), effect.total_duration_rounds());
+
+        let bonuses = effect.bonuses().clone();
         entity.borrow_mut().actor.add_effect(index, bonuses);
 
         index
@@ -1033,10 +1457,8 @@ impl TurnManager {
             // to zero
             entity.borrow_mut().actor.remove_hp(cur_hp as u32);
         }
-        // don't actually remove the entity from the backing vec, to allow
-        // scripts to continue to reference it
-        // self.entities[index] = None;
         entity.borrow_mut().marked_for_removal = false;
+        self.delayed_turns.remove(&index);
 
         // can't do this with a collect because of lifetime issues
         let mut effects_to_remove = Vec::new();
@@ -1072,8 +1494,25 @@ impl TurnManager {
                 .fire_on_encounter_cleared(enc_ref.encounter_index, &entity);
         }
 
+        for effect in effects_to_remove {
+            self.queue_remove_effect(effect);
+        }
+
+        // the slot may be reused before the queued effect removals run, so
+        // don't leave this entity's auras for the next occupant
+        self.auras.remove(&index);
+        self.free_entity_slot(index);
+
         self.listeners.notify(self);
     }
+
+    // free the slot for reuse.  the new generation invalidates any
+    // references to this entity still held by scripts or callbacks
+    fn free_entity_slot(&mut self, index: usize) {
+        self.entities[index] = None;
+        self.generations[index] += 1;
+        self.free_entity_slots.push(index);
+    }
 }
 
 pub struct ActiveEntityIterator<'a> {
@@ -1094,9 +1533,8 @@ impl<'a> Iterator for ActiveEntityIterator<'a> {
                 Some(ref entry) => match entry {
                     Entry::Effect(_) => (),
                     Entry::Entity(index) => {
-                        let entity = self.mgr.entities[*index].as_ref().unwrap();
-                        if entity.borrow().is_party_member() || entity.borrow().is_ai_active() {
-                            return Some(entity);
+                        if self.mgr.is_active_entity(*index) {
+                            return self.mgr.entities[*index].as_ref();
                         }
                     }
                     Entry::TurnChange => (),
@@ -1152,3 +1590,47 @@ impl<'a> Iterator for EffectIterator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn freed_slot_is_reused_with_new_generation() {
+        let mut mgr = TurnManager::default();
+        let first = mgr.next_entity_id();
+        let second = mgr.next_entity_id();
+        assert_eq!(first, EntityId::new(0, 0));
+        assert_eq!(second, EntityId::new(1, 0));
+
+        mgr.free_entity_slot(first.index);
+        let reused = mgr.next_entity_id();
+        assert_eq!(reused, EntityId::new(0, 1));
+        assert_ne!(reused, first);
+        assert!(mgr.entity_by_id(first).is_none());
+
+        assert_eq!(mgr.next_entity_id(), EntityId::new(2, 0));
+    }
+
+    #[test]
+    fn loaded_generations_are_newer_than_saved_ids() {
+        let mut mgr = TurnManager::default();
+        mgr.load_entity_generations(vec![0, 3]);
+
+        assert_eq!(mgr.next_entity_id(), EntityId::new(0, 1));
+        assert_eq!(mgr.next_entity_id(), EntityId::new(1, 4));
+        assert_eq!(mgr.next_entity_id(), EntityId::new(2, 0));
+    }
+
+    #[test]
+    fn reserved_id_pads_skipped_slots() {
+        let mut mgr = TurnManager::default();
+        let id = EntityId::new(2, 5);
+        assert_eq!(mgr.reserve_entity_id(id), id);
+        assert_eq!(mgr.entity_generations(), vec![1, 1, 5]);
+
+        assert_eq!(mgr.next_entity_id(), EntityId::new(0, 1));
+        assert_eq!(mgr.next_entity_id(), EntityId::new(1, 1));
+        assert_eq!(mgr.next_entity_id(), EntityId::new(3, 0));
+    }
+}
@@ -0,0 +1,125 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use sulis_core::ui::{color, Color};
+use sulis_module::Module;
+
+/// The current weather and any transition to it that is in progress.
+/// Transitions are measured against the total elapsed game time, so they
+/// pause along with the game clock during combat.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct WeatherState {
+    id: Option<String>,
+    from_light: Color,
+    to_light: Color,
+    from_intensity: f32,
+    to_intensity: f32,
+    start_millis: usize,
+    duration_millis: usize,
+}
+
+impl Default for WeatherState {
+    fn default() -> Self {
+        WeatherState {
+            id: None,
+            from_light: color::WHITE,
+            to_light: color::WHITE,
+            from_intensity: 0.0,
+            to_intensity: 0.0,
+            start_millis: 0,
+            duration_millis: 0,
+        }
+    }
+}
+
+impl WeatherState {
+    /// Returns the ID of the weather being transitioned to, or `None` if
+    /// the weather is clear or clearing
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns the intensity of the current weather, between 0.0 and 1.0,
+    /// at the specified total elapsed game `millis`
+    pub fn intensity(&self, millis: usize) -> f32 {
+        let frac = self.frac(millis);
+        self.from_intensity * (1.0 - frac) + self.to_intensity * frac
+    }
+
+    /// Returns the color multiplied with outdoor ambient light at the
+    /// specified total elapsed game `millis`
+    pub fn light(&self, millis: usize) -> Color {
+        lerp(self.from_light, self.to_light, self.frac(millis))
+    }
+
+    /// Begins a transition from the weather as of `millis` to the weather
+    /// with the specified `id`, or clear weather if `None`, reaching the
+    /// specified `intensity` after `duration_millis`
+    pub fn transition(
+        &mut self,
+        id: Option<String>,
+        intensity: f32,
+        duration_millis: usize,
+        millis: usize,
+    ) {
+        let intensity = intensity.clamp(0.0, 1.0);
+        let to_light = match &id {
+            None => color::WHITE,
+            Some(id) => match Module::rules().weather.get(id) {
+                None => {
+                    warn!("Unable to transition to invalid weather '{}'", id);
+                    return;
+                }
+                Some(weather) => lerp(color::WHITE, weather.light, intensity),
+            },
+        };
+
+        // a new kind of weather builds up from nothing, while changing the
+        // intensity of or clearing the current weather starts from where it is
+        let from_intensity = if id.is_none() || id == self.id {
+            self.intensity(millis)
+        } else {
+            0.0
+        };
+
+        self.from_light = self.light(millis);
+        self.from_intensity = from_intensity;
+        self.to_light = to_light;
+        self.to_intensity = if id.is_none() { 0.0 } else { intensity };
+        self.id = id;
+        self.start_millis = millis;
+        self.duration_millis = duration_millis;
+    }
+
+    fn frac(&self, millis: usize) -> f32 {
+        if self.duration_millis == 0 {
+            return 1.0;
+        }
+
+        let elapsed = millis.saturating_sub(self.start_millis);
+        (elapsed as f32 / self.duration_millis as f32).min(1.0)
+    }
+}
+
+fn lerp(from: Color, to: Color, frac: f32) -> Color {
+    Color {
+        r: from.r + (to.r - from.r) * frac,
+        g: from.g + (to.g - from.g) * frac,
+        b: from.b + (to.b - from.b) * frac,
+        a: from.a + (to.a - from.a) * frac,
+    }
+}
@@ -0,0 +1,93 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::{HashMap, HashSet};
+
+use sulis_module::{campaign::WorldEvent, on_trigger::QuestEntryState, Module};
+
+use crate::QuestStateSet;
+
+/// Tracks the campaign world events that have fired, along with those
+/// cancelled by scripts.  Times are measured against the total elapsed
+/// game millis.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct WorldEventState {
+    last_fired: HashMap<String, usize>,
+    cancelled: HashSet<String>,
+}
+
+impl WorldEventState {
+    pub fn is_cancelled(&self, id: &str) -> bool {
+        self.cancelled.contains(id)
+    }
+
+    pub(crate) fn cancel(&mut self, id: &str) {
+        self.cancelled.insert(id.to_string());
+    }
+
+    /// Returns the events that are due at the specified `millis`, marking
+    /// them as fired.  An event that has missed several repeats, such as while
+    /// the party rests, only fires once.
+    pub(crate) fn take_due(&mut self, millis: usize, quests: &QuestStateSet) -> Vec<WorldEvent> {
+        let mut due = Vec::new();
+        for event in Module::campaign().world_events.iter() {
+            if let Some(ref quest) = event.unless_quest_complete {
+                if quests.state(quest) == QuestEntryState::Complete {
+                    continue;
+                }
+            }
+
+            let next = match self.next_millis(event) {
+                None => continue,
+                Some(next) => next,
+            };
+
+            if next > millis {
+                continue;
+            }
+
+            let repeat = event.repeat.map_or(0, |time| Module::rules().compute_millis(time));
+            let fired = match (millis - next).checked_div(repeat) {
+                None => next,
+                Some(intervals) => next + intervals * repeat,
+            };
+
+            self.last_fired.insert(event.id.to_string(), fired);
+            due.push(event.clone());
+        }
+
+        due
+    }
+
+    fn next_millis(&self, event: &WorldEvent) -> Option<usize> {
+        if self.cancelled.contains(&event.id) {
+            return None;
+        }
+
+        let rules = Module::rules();
+        match self.last_fired.get(&event.id) {
+            None => Some(rules.compute_millis(event.at)),
+            Some(last) => match event.repeat {
+                None => None,
+                Some(repeat) => match rules.compute_millis(repeat) {
+                    0 => None,
+                    repeat => Some(last + repeat),
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+
+use std::collections::HashMap;
+use std::io::Error;
+use std::path::PathBuf;
+
+use chrono::prelude::*;
+
+use sulis_core::config;
+use sulis_core::resource::{read_single_resource_path, write_to_file};
+
+use crate::EntityState;
+
+/// A summary of the player's decisions at the end of a campaign, which a
+/// sequel campaign can import when starting a new game.  Exports are written
+/// by scripts with `game:create_world_export()` and stored in the user
+/// directory as `exports/<campaign id>.yml`, one per campaign, with each new
+/// export replacing the last.  A campaign imports the first export found
+/// among the campaign IDs listed in its `new_game` `import_from`.
+///
+/// The file is a YAML map with the following entries:
+///
+/// - `campaign`: the ID of the exporting campaign
+/// - `date`: when the export was written, in the local time format
+/// - `player`: the name of the player character
+/// - `flags`: a map of flag names to values.  These are set as custom flags
+///   on the player character in the importing campaign, unless a flag of the
+///   same name was already passed by a module export.
+/// - `choices`: a map of choice IDs to the option chosen, for decisions
+///   the importing campaign should honor
+/// - `companions`: a list of companions, each with the companion's `actor`
+///   ID, `name`, `level`, whether it is `alive`, whether it was `in_party`
+///   when exported, the party's `approval` with it, and its custom `flags`
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct WorldExport {
+    pub campaign: String,
+    pub date: String,
+    pub player: String,
+
+    #[serde(default)]
+    pub flags: HashMap<String, String>,
+
+    #[serde(default)]
+    pub choices: HashMap<String, String>,
+
+    #[serde(default)]
+    pub companions: Vec<CompanionExport>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CompanionExport {
+    pub actor: String,
+    pub name: String,
+    pub level: u32,
+    pub alive: bool,
+    pub in_party: bool,
+
+    #[serde(default)]
+    pub approval: i32,
+
+    #[serde(default)]
+    pub flags: HashMap<String, String>,
+}
+
+impl CompanionExport {
+    pub fn new(entity: &EntityState, approval: i32) -> CompanionExport {
+        let actor = &entity.actor.actor;
+        CompanionExport {
+            actor: actor.id.to_string(),
+            name: actor.name.to_string(),
+            level: actor.total_level,
+            alive: !entity.actor.is_dead(),
+            in_party: entity.is_party_member(),
+            approval,
+            flags: entity
+                .custom_flags()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+}
+
+fn file_path(campaign: &str) -> PathBuf {
+    let mut path = config::USER_DIR.clone();
+    path.push("exports");
+    path.push(format!("{campaign}.yml"));
+    path
+}
+
+impl WorldExport {
+    pub fn new(campaign: &str, player: &str) -> WorldExport {
+        WorldExport {
+            campaign: campaign.to_string(),
+            date: String::new(),
+            player: player.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Writes this export to the user directory, replacing any previous
+    /// export from the same campaign
+    pub fn save(&mut self) -> Result<(), Error> {
+        self.date = Local::now().format("%c").to_string();
+        let path = file_path(&self.campaign);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        info!("Writing world export to '{:?}'", path);
+        write_to_file(path, &*self)
+    }
+
+    /// Reads the export written by the specified campaign, if there is one
+    pub fn load(campaign: &str) -> Option<WorldExport> {
+        let path = file_path(campaign);
+        if !path.is_file() {
+            return None;
+        }
+
+        match read_single_resource_path(&path) {
+            Ok(export) => Some(export),
+            Err(e) => {
+                warn!("Error reading world export from '{:?}'", path);
+                warn!("{}", e);
+                None
+            }
+        }
+    }
+
+    /// Finds the first available export from the specified campaigns
+    pub fn find(campaigns: &[String]) -> Option<WorldExport> {
+        campaigns.iter().find_map(|id| WorldExport::load(id))
+    }
+}
@@ -525,6 +525,7 @@ struct AbilityButton {
     newly_added: bool,
     range_indicator: Option<RangeIndicator>,
     key: Option<Key>,
+    preview: Option<String>,
 }
 
 impl AbilityButton {
@@ -546,6 +547,7 @@ impl AbilityButton {
             newly_added,
             range_indicator,
             key,
+            preview: None,
         }))
     }
 }
@@ -629,6 +631,7 @@ impl WidgetKind for AbilityButton {
                 .range_indicators()
                 .add(self.range_indicator.clone());
         }
+        self.preview = Script::ability_preview(&self.entity, &self.ability);
         self.super_on_mouse_enter(widget);
         true
     }
@@ -653,6 +656,19 @@ impl WidgetKind for AbilityButton {
             self.key,
             disabled_reason,
         );
+        add_computed_text_args(
+            &mut hover.borrow_mut().state,
+            &self.entity,
+            &self.ability,
+            self.preview.as_deref(),
+        );
+
+        if disabled_reason == DisabledReason::RequirementsNotMet {
+            let actor = &self.entity.borrow().actor.actor;
+            if let Some(reason) = self.ability.unmet_requirement(actor) {
+                hover.borrow_mut().state.add_text_arg("disabled", &reason);
+            }
+        }
 
         if self.newly_added {
             hover.borrow_mut().state.add_text_arg("newly_added", "true");
@@ -675,18 +691,18 @@ impl WidgetKind for AbilityButton {
     }
 }
 
-fn activate_ability(entity: &Rc<RefCell<EntityState>>, ability: &Rc<Ability>) -> bool {
+pub(crate) fn activate_ability(entity: &Rc<RefCell<EntityState>>, ability: &Rc<Ability>) -> bool {
     let can_activate = entity.borrow().actor.can_activate(&ability.id);
     if can_activate {
-        let index = entity.borrow().index();
-        Script::ability_on_activate(index, "on_activate".to_string(), ability);
+        let id = entity.borrow().id();
+        Script::ability_on_activate(id, "on_activate".to_string(), ability);
         return true;
     }
 
     let can_toggle = entity.borrow().actor.can_toggle(&ability.id);
     if can_toggle == DisabledReason::Enabled {
-        let index = entity.borrow().index();
-        Script::ability_on_deactivate(index, ability);
+        let id = entity.borrow().id();
+        Script::ability_on_deactivate(id, ability);
     }
 
     true
@@ -700,15 +716,15 @@ pub fn add_hover_text_args(
     disabled_reason: DisabledReason,
 ) {
     state.disable();
-    state.add_text_arg("name", &ability.name);
-    state.add_text_arg("description", &ability.description);
+    state.add_display_text_arg("name", &ability.name);
+    state.add_display_text_arg("description", &ability.description);
 
     if let Some(key) = key {
         state.add_text_arg("keybinding", &key.short_name());
     }
 
     for (index, upgrade) in ability.upgrades.iter().enumerate() {
-        state.add_text_arg(&format!("upgrade{}", index + 1), &upgrade.description);
+        state.add_display_text_arg(&format!("upgrade{}", index + 1), &upgrade.description);
     }
 
     let mut class_stat: Option<&str> = None;
@@ -722,7 +738,7 @@ pub fn add_hover_text_args(
                     continue;
                 }
                 if let Some(amount) = class_stats.get(&stat.id) {
-                    state.add_text_arg("class_stat_name", &stat.name);
+                    state.add_display_text_arg("class_stat_name", &stat.name);
                     state.add_text_arg("class_stat_amount", &amount.to_string());
                 }
                 class_stat = Some(&stat.name);
@@ -740,12 +756,42 @@ pub fn add_hover_text_args(
             state.add_text_arg("cooldown", &active.cooldown.to_string());
         }
 
-        state.add_text_arg("short_description", &active.short_description);
+        state.add_display_text_arg("short_description", &active.short_description);
 
         add_disabled_text_arg(state, class_stat, disabled_reason);
     }
 }
 
+/// Adds the values of the `ability` as computed for the current stats of the
+/// `entity`, replacing the base values where present
+fn add_computed_text_args(
+    state: &mut WidgetState,
+    entity: &Rc<RefCell<EntityState>>,
+    ability: &Ability,
+    preview: Option<&str>,
+) {
+    let active = match &ability.active {
+        None => return,
+        Some(active) => active,
+    };
+
+    let ap = entity.borrow().actor.ability_ap_cost(ability);
+    let ap = Module::rules().to_display_ap(ap as i32);
+    state.add_text_arg("activate_ap", &ap.to_string());
+
+    match active.range {
+        ability::Range::None | ability::Range::Personal => (),
+        _ => {
+            let range = RangeIndicator::ability_range(entity, ability);
+            state.add_text_arg("range", &format!("{range:.1}"));
+        }
+    }
+
+    if let Some(preview) = preview {
+        state.add_text_arg("preview", preview);
+    }
+}
+
 fn add_disabled_text_arg(
     state: &mut WidgetState,
     class_stat_name: Option<&str>,
@@ -763,6 +809,7 @@ fn add_disabled_text_arg(
             state.add_text_arg("disabled", &text);
             return;
         }
+        RequirementsNotMet => "Requirements not met",
         RequiresShield => "Equip a shield",
         RequiresMelee => "Equip a melee weapon",
         RequiresRanged => "Equip a ranged weapon",
@@ -80,11 +80,11 @@ impl WidgetKind for AbilityPane {
 
 pub fn add_ability_text_args(state: &mut WidgetState, ability: &Rc<Ability>) {
     state.clear_text_args();
-    state.add_text_arg("name", &ability.name);
-    state.add_text_arg("description", &ability.description);
+    state.add_display_text_arg("name", &ability.name);
+    state.add_display_text_arg("description", &ability.description);
 
     for (index, upgrade) in ability.upgrades.iter().enumerate() {
-        state.add_text_arg(&format!("upgrade{}", index + 1), &upgrade.description);
+        state.add_display_text_arg(&format!("upgrade{}", index + 1), &upgrade.description);
     }
 
     if let Some(ref active) = ability.active {
@@ -99,7 +99,7 @@ pub fn add_ability_text_args(state: &mut WidgetState, ability: &Rc<Ability>) {
                     continue;
                 }
                 if let Some(amount) = stats.get(&stat.id) {
-                    state.add_text_arg("class_stat_name", &stat.name);
+                    state.add_display_text_arg("class_stat_name", &stat.name);
                     state.add_text_arg("class_stat_amount", &amount.to_string());
                 }
             }
@@ -0,0 +1,144 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label, ScrollDirection, ScrollPane, TextArea};
+use sulis_module::{Achievement, Module};
+use sulis_state::AchievementState;
+
+pub const NAME: &str = "achievements_window";
+
+pub const TOAST_NAME: &str = "achievement_toast";
+
+/// Lists all achievements defined by the current campaign, along with the
+/// date each was awarded for the current profile
+pub struct AchievementsWindow {}
+
+impl AchievementsWindow {
+    pub fn new() -> Rc<RefCell<AchievementsWindow>> {
+        Rc::new(RefCell::new(AchievementsWindow {}))
+    }
+}
+
+impl WidgetKind for AchievementsWindow {
+    widget_kind!(NAME);
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<AchievementsWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let mut achievements: Vec<_> = Module::all_achievements()
+            .into_iter()
+            .map(|a| {
+                let awarded = AchievementState::awarded(&a.id);
+                (a, awarded)
+            })
+            .collect();
+        achievements.sort_by(|(a, a_awarded), (b, b_awarded)| {
+            b_awarded
+                .is_some()
+                .cmp(&a_awarded.is_some())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let count = achievements.iter().filter(|(_, awarded)| awarded.is_some()).count();
+        let summary = Widget::with_theme(Label::empty(), "summary");
+        {
+            let state = &mut summary.borrow_mut().state;
+            state.add_text_arg("awarded", &count.to_string());
+            state.add_text_arg("total", &achievements.len().to_string());
+        }
+
+        let pane = ScrollPane::new(ScrollDirection::Vertical);
+        let entries = Widget::with_theme(pane.clone(), "entries");
+        for (achievement, awarded) in achievements {
+            let entry = Widget::with_theme(TextArea::empty(), "entry");
+            {
+                let state = &mut entry.borrow_mut().state;
+                match awarded {
+                    Some(awarded) => {
+                        state.add_text_arg("awarded", &awarded.date);
+                        state.add_text_arg("icon", &achievement.icon);
+                        state.add_display_text_arg("name", &achievement.name);
+                        state.add_display_text_arg("description", &achievement.description);
+                    }
+                    None if achievement.hidden => state.add_text_arg("hidden", "true"),
+                    None => {
+                        state.add_text_arg("icon", &achievement.icon);
+                        state.add_display_text_arg("name", &achievement.name);
+                        state.add_display_text_arg("description", &achievement.description);
+                    }
+                }
+            }
+            pane.borrow().add_to_content(entry);
+        }
+
+        vec![close, summary, entries]
+    }
+}
+
+/// A notification shown briefly when an achievement is awarded
+pub struct AchievementToast {
+    achievement: Rc<Achievement>,
+    elapsed_millis: u32,
+}
+
+impl AchievementToast {
+    pub fn new(achievement: Rc<Achievement>) -> Rc<RefCell<AchievementToast>> {
+        Rc::new(RefCell::new(AchievementToast {
+            achievement,
+            elapsed_millis: 0,
+        }))
+    }
+}
+
+impl WidgetKind for AchievementToast {
+    widget_kind!(TOAST_NAME);
+
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, millis: u32) {
+        self.elapsed_millis += millis;
+
+        let display_millis = widget
+            .borrow()
+            .theme
+            .get_custom_or_default("display_millis", 4000);
+        if self.elapsed_millis > display_millis {
+            widget.borrow_mut().mark_for_removal();
+        }
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let text = Widget::with_theme(TextArea::empty(), "text");
+        {
+            let state = &mut text.borrow_mut().state;
+            state.add_text_arg("icon", &self.achievement.icon);
+            state.add_display_text_arg("name", &self.achievement.name);
+            state.add_display_text_arg("description", &self.achievement.description);
+        }
+
+        vec![text]
+    }
+}
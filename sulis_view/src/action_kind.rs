@@ -19,8 +19,9 @@ use std::cmp;
 use std::rc::Rc;
 
 use crate::RootView;
-use sulis_core::ui::{animation_state, Widget};
+use sulis_core::ui::{animation_state, Callback, Widget};
 use sulis_core::util::Point;
+use sulis_core::widgets::ConfirmationWindow;
 use sulis_module::{
     area::{Destination, ToKind},
     Faction, Module, ObjectSize, OnTrigger, Time, MOVE_TO_THRESHOLD,
@@ -48,6 +49,10 @@ pub fn get_action(x_f32: f32, y_f32: f32) -> Box<dyn ActionKind> {
         return action;
     }
 
+    if let Some(action) = TraversalAction::create_if_valid(x, y) {
+        return action;
+    }
+
     if let Some(action) = get_prop_or_transition_action(x, y) {
         return action;
     }
@@ -72,6 +77,14 @@ fn get_prop_or_transition_action(x: i32, y: i32) -> Option<Box<dyn ActionKind>>
     // an enabled container or a closed door (regardless of enabled) blocks a transition.
     // an open door (regardless of enabled) does not block a transition
 
+    if let Some(action) = UnlockPropAction::create_if_valid(index, prop) {
+        return Some(action);
+    }
+
+    if let Some(action) = AttackPropAction::create_if_valid(index, prop) {
+        return Some(action);
+    }
+
     if prop.is_container() && prop.is_enabled() {
         return LootPropAction::create_if_valid(index, prop);
     }
@@ -423,6 +436,82 @@ impl ActionKind for LootPropAction {
     }
 }
 
+struct UnlockPropAction {
+    index: usize,
+}
+
+impl UnlockPropAction {
+    fn create_if_valid(index: usize, prop_state: &PropState) -> Option<Box<dyn ActionKind>> {
+        if !prop_state.is_door() && !prop_state.is_container() {
+            return None;
+        }
+
+        let lock = match prop_state.lock() {
+            Some(lock) if prop_state.is_locked() => lock,
+            _ => return None,
+        };
+
+        if !GameState::party_stash().borrow().has_key(&lock.key) {
+            return None;
+        }
+
+        let max_dist = Module::rules().max_prop_distance;
+        let pc = match GameState::selected().first() {
+            None => return None,
+            Some(pc) => Rc::clone(pc),
+        };
+        if !is_within(&*pc.borrow(), prop_state, max_dist) {
+            let cb_action = Box::new(UnlockPropAction { index });
+            return MoveThenAction::create_if_valid(
+                &pc,
+                prop_state.location.to_point(),
+                &prop_state.prop.size,
+                max_dist,
+                cb_action,
+                animation_state::Kind::MouseInteract,
+            );
+        }
+
+        Some(Box::new(UnlockPropAction { index }))
+    }
+}
+
+impl ActionKind for UnlockPropAction {
+    fn cursor_state(&self) -> animation_state::Kind {
+        animation_state::Kind::MouseInteract
+    }
+
+    fn get_hover_info(&self) -> Option<ActionHoverInfo> {
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let prop = area_state.props().get(self.index);
+        let point = prop.location.to_point();
+        Some(ActionHoverInfo::new(&prop.prop.size, point))
+    }
+
+    fn fire_action(&mut self, widget: &Rc<RefCell<Widget>>) -> bool {
+        let is_door = {
+            let area_state = GameState::area_state();
+            let mut area_state = area_state.borrow_mut();
+            if !area_state.unlock_prop(self.index) {
+                return false;
+            }
+
+            let is_door = area_state.props().get(self.index).is_door();
+            if is_door {
+                area_state.toggle_prop_active(self.index);
+            }
+            is_door
+        };
+
+        if is_door || GameState::is_combat_active() {
+            return false;
+        }
+
+        LootPropAction { index: self.index }.fire_action(widget)
+    }
+}
+
 struct TransitionAction {
     x: i32,
     y: i32,
@@ -431,7 +520,7 @@ struct TransitionAction {
 
 impl TransitionAction {
     fn create_if_valid(x: i32, y: i32, area_state: &AreaState) -> Option<Box<dyn ActionKind>> {
-        if GameState::is_combat_active() {
+        if !GameState::can_use_transitions() {
             return None;
         }
 
@@ -465,6 +554,28 @@ impl TransitionAction {
 
         Some(cb_action)
     }
+
+    /// The names of party members too far from the transition to be
+    /// considered part of the group using it
+    fn stragglers(&self) -> Vec<String> {
+        let dist = match Module::rules().transitions.split_party_distance {
+            None => return Vec::new(),
+            Some(dist) => dist,
+        };
+
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let transition = match area_state.get_transition_at(self.x, self.y) {
+            None => return Vec::new(),
+            Some(transition) => transition,
+        };
+
+        GameState::party()
+            .iter()
+            .filter(|member| !is_within(&*member.borrow(), transition, dist))
+            .map(|member| member.borrow().actor.actor.name.to_string())
+            .collect()
+    }
 }
 
 impl ActionKind for TransitionAction {
@@ -485,38 +596,145 @@ impl ActionKind for TransitionAction {
 
     fn fire_action(&mut self, widget: &Rc<RefCell<Widget>>) -> bool {
         trace!("Firing transition callback.");
-        let time = Time {
-            day: 0,
-            hour: 0,
-            round: 0,
-            millis: 0,
+        let stragglers = self.stragglers();
+        if stragglers.is_empty() {
+            return fire_transition(&self.to, widget);
+        }
+
+        let to = self.to.clone();
+        let cb = Callback::new(Rc::new(move |widget, _| {
+            let (parent, _) = Widget::parent::<ConfirmationWindow>(widget);
+            parent.borrow_mut().mark_for_removal();
+            fire_transition(&to, widget);
+        }));
+        let window = ConfirmationWindow::new(cb);
+        window
+            .borrow()
+            .title()
+            .borrow_mut()
+            .state
+            .add_text_arg("stragglers", &stragglers.join(", "));
+
+        let root = Widget::get_root(widget);
+        let window = Widget::with_theme(window, "transition_confirmation");
+        window.borrow_mut().state.set_modal(true);
+        Widget::add_child_to(&root, window);
+        true
+    }
+}
+
+fn fire_transition(to: &ToKind, widget: &Rc<RefCell<Widget>>) -> bool {
+    let time = Time {
+        day: 0,
+        hour: 0,
+        round: 0,
+        millis: 0,
+    };
+    match to {
+        ToKind::Area { ref id, x, y } => {
+            GameState::transition_to(Some(id), Some(Point::new(*x, *y)), Point::default(), time);
+            let root = Widget::get_root(widget);
+            root.borrow_mut().invalidate_children();
+        }
+        ToKind::CurArea { x, y } => {
+            GameState::transition_to(None, Some(Point::new(*x, *y)), Point::default(), time);
+        }
+        ToKind::WorldMap => {
+            let (root, view) = Widget::parent_mut::<RootView>(widget);
+            view.set_map_window(&root, true, true);
+            return true;
+        }
+        ToKind::FindLink {
+            ref id,
+            x_offset,
+            y_offset,
+        } => {
+            let offset = Point::new(*x_offset, *y_offset);
+            GameState::transition_to(Some(id), None, offset, time);
+            let root = Widget::get_root(widget);
+            root.borrow_mut().invalidate_children();
+        }
+    }
+    false
+}
+
+struct TraversalAction {
+    pc: Rc<RefCell<EntityState>>,
+    index: usize,
+    start: Point,
+    dest: Point,
+    ap: i32,
+}
+
+impl TraversalAction {
+    fn create_if_valid(x: i32, y: i32) -> Option<Box<dyn ActionKind>> {
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let (index, start) = area_state.get_traversal_at(x, y)?;
+        let traversal = &area_state.area.area.traversals[index];
+
+        let pc = match GameState::selected().first() {
+            None => return None,
+            Some(pc) => Rc::clone(pc),
         };
-        match self.to {
-            ToKind::Area { ref id, x, y } => {
-                GameState::transition_to(Some(id), Some(Point::new(x, y)), Point::default(), time);
-                let root = Widget::get_root(widget);
-                root.borrow_mut().invalidate_children();
-            }
-            ToKind::CurArea { x, y } => {
-                GameState::transition_to(None, Some(Point::new(x, y)), Point::default(), time);
-            }
-            ToKind::WorldMap => {
-                let (root, view) = Widget::parent_mut::<RootView>(widget);
-                view.set_map_window(&root, true, true);
-                return true;
-            }
-            ToKind::FindLink {
-                ref id,
-                x_offset,
-                y_offset,
-            } => {
-                GameState::transition_to(Some(id), None, Point::new(x_offset, y_offset), time);
-                let root = Widget::get_root(widget);
-                root.borrow_mut().invalidate_children();
-            }
+
+        if !pc.borrow().actor.can_traverse(traversal) {
+            return None;
+        }
+
+        let ap = if GameState::is_combat_active() {
+            Module::rules().traversal_ap.ap(traversal.kind) as i32
+        } else {
+            0
+        };
+
+        let cb_action = Box::new(TraversalAction {
+            pc: Rc::clone(&pc),
+            index,
+            start,
+            dest: traversal.other_end(start),
+            ap,
+        });
+
+        let max_dist = Module::rules().max_prop_distance;
+        if !is_within(&*pc.borrow(), &start, max_dist) {
+            let size = Rc::clone(&pc.borrow().size);
+            return MoveThenAction::create_if_valid(
+                &pc,
+                start,
+                &size,
+                max_dist,
+                cb_action,
+                animation_state::Kind::MouseInteract,
+            );
         }
+
+        Some(cb_action)
+    }
+}
+
+impl ActionKind for TraversalAction {
+    fn cursor_state(&self) -> animation_state::Kind {
+        animation_state::Kind::MouseInteract
+    }
+
+    fn get_hover_info(&self) -> Option<ActionHoverInfo> {
+        let pc = self.pc.borrow();
+        let point = Point::new(
+            self.dest.x - (pc.size.width - 1) / 2,
+            self.dest.y - (pc.size.height - 1) / 2,
+        );
+        Some(ActionHoverInfo::with_ap(&pc, point, pc.actor.ap() as i32, self.ap))
+    }
+
+    fn fire_action(&mut self, _widget: &Rc<RefCell<Widget>>) -> bool {
+        GameState::traverse(&self.pc, self.index, self.start);
         false
     }
+
+    fn ap(&self) -> i32 {
+        self.ap
+    }
 }
 
 struct AttackAction {
@@ -617,6 +835,86 @@ impl ActionKind for AttackAction {
     }
 }
 
+struct AttackPropAction {
+    pc: Rc<RefCell<EntityState>>,
+    index: usize,
+    ap: i32,
+}
+
+impl AttackPropAction {
+    fn create_if_valid(index: usize, prop_state: &PropState) -> Option<Box<dyn ActionKind>> {
+        if !prop_state.is_destructible() {
+            return None;
+        }
+
+        // enabled containers and doors are used rather than attacked, but locked
+        // ones may be broken open
+        if (prop_state.is_container() || prop_state.is_door()) && prop_state.is_enabled() {
+            return None;
+        }
+
+        let pc = match GameState::selected().first() {
+            None => return None,
+            Some(pc) => Rc::clone(pc),
+        };
+
+        let ap = {
+            let pc = pc.borrow();
+            if !pc.actor.has_ap_to_attack() || pc.actor.stats.attack_disabled {
+                return None;
+            }
+            pc.actor.stats.attack_cost
+        };
+
+        let dist = pc.borrow().actor.stats.attack_distance();
+        if !is_within(&*pc.borrow(), prop_state, dist) {
+            let cb_action = Box::new(AttackPropAction {
+                pc: Rc::clone(&pc),
+                index,
+                ap,
+            });
+            return MoveThenAction::create_if_valid(
+                &pc,
+                prop_state.location.to_point(),
+                &prop_state.prop.size,
+                dist,
+                cb_action,
+                animation_state::Kind::MouseAttack,
+            );
+        }
+
+        Some(Box::new(AttackPropAction { pc, index, ap }))
+    }
+}
+
+impl ActionKind for AttackPropAction {
+    fn cursor_state(&self) -> animation_state::Kind {
+        animation_state::Kind::MouseAttack
+    }
+
+    fn get_hover_info(&self) -> Option<ActionHoverInfo> {
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let prop = area_state.props().get(self.index);
+        let point = prop.location.to_point();
+        Some(ActionHoverInfo::new(&prop.prop.size, point))
+    }
+
+    fn fire_action(&mut self, _widget: &Rc<RefCell<Widget>>) -> bool {
+        trace!("Firing attack prop action.");
+        if !self.pc.borrow().actor.has_ap_to_attack() {
+            return false;
+        }
+
+        EntityState::attack_prop(&self.pc, self.index, true);
+        false
+    }
+
+    fn ap(&self) -> i32 {
+        self.ap
+    }
+}
+
 struct ActionCallback {
     action: Rc<RefCell<Box<dyn ActionKind>>>,
     widget: Rc<RefCell<Widget>>,
@@ -0,0 +1,166 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use sulis_core::ui::{animation_state, Widget};
+use sulis_state::{EntityState, GameMessage, GameState};
+
+use crate::action_kind;
+
+const MAX_QUEUED: usize = 4;
+
+struct QueuedAction {
+    x: f32,
+    y: f32,
+    cursor: animation_state::Kind,
+}
+
+/// A short sequence of actions for the selected party member, queued by
+/// clicking while the queue key is held.  Each action is recreated from its
+/// position once the previous one has finished, and the whole queue is
+/// cancelled if the action is no longer the same kind, for example an attack
+/// on a target that has since died.
+#[derive(Default)]
+pub struct ActionQueue {
+    held: bool,
+    entity: Option<Rc<RefCell<EntityState>>>,
+    actions: VecDeque<QueuedAction>,
+    combat_active: bool,
+}
+
+impl ActionQueue {
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    pub fn set_held(&mut self, held: bool) {
+        self.held = held;
+    }
+
+    /// Queues the action at the specified area position for the selected
+    /// party member.  Returns false if there is no valid action there or the
+    /// queue is full.  Selecting another party member may not be queued
+    pub fn push(&mut self, x: f32, y: f32) -> bool {
+        let entity = match GameState::selected().first() {
+            None => return false,
+            Some(entity) => Rc::clone(entity),
+        };
+
+        let same_entity = self.entity.as_ref().is_some_and(|e| Rc::ptr_eq(e, &entity));
+        if !same_entity {
+            self.actions.clear();
+        }
+
+        if self.actions.len() >= MAX_QUEUED {
+            return false;
+        }
+
+        use animation_state::Kind::*;
+        let cursor = action_kind::get_action(x, y).cursor_state();
+        if cursor == MouseInvalid || cursor == MouseSelect {
+            return false;
+        }
+
+        self.entity = Some(entity);
+        self.combat_active = GameState::is_combat_active();
+        self.actions.push_back(QueuedAction { x, y, cursor });
+        true
+    }
+
+    /// Removes all queued actions, returning true if there were any
+    pub fn clear(&mut self) -> bool {
+        self.entity = None;
+        let had_actions = !self.actions.is_empty();
+        self.actions.clear();
+        had_actions
+    }
+
+    /// Fires the next queued action once the entity has finished its current
+    /// one
+    pub fn update(&mut self, widget: &Rc<RefCell<Widget>>) {
+        let entity = match self.entity {
+            None => return,
+            Some(ref entity) => Rc::clone(entity),
+        };
+
+        if !self.is_still_valid(&entity) {
+            self.cancel(&entity);
+            return;
+        }
+
+        if GameState::has_blocking_or_pending_animations(&entity) {
+            return;
+        }
+
+        let queued = match self.actions.pop_front() {
+            None => {
+                self.entity = None;
+                return;
+            }
+            Some(queued) => queued,
+        };
+
+        let mut action = action_kind::get_action(queued.x, queued.y);
+        if action.cursor_state() != queued.cursor {
+            self.cancel(&entity);
+            return;
+        }
+
+        action.fire_action(widget);
+    }
+
+    fn is_still_valid(&self, entity: &Rc<RefCell<EntityState>>) -> bool {
+        if entity.borrow().actor.is_dead() {
+            return false;
+        }
+
+        match GameState::selected().first() {
+            None => return false,
+            Some(selected) => {
+                if !Rc::ptr_eq(selected, entity) {
+                    return false;
+                }
+            }
+        }
+
+        if GameState::is_combat_active() != self.combat_active {
+            return false;
+        }
+
+        if self.combat_active {
+            let mgr = GameState::turn_manager();
+            let current = mgr.borrow().current();
+            match current {
+                None => return false,
+                Some(current) => return Rc::ptr_eq(&current, entity),
+            }
+        }
+
+        true
+    }
+
+    fn cancel(&mut self, entity: &Rc<RefCell<EntityState>>) {
+        if self.clear() {
+            let message = GameMessage::default()
+                .entity(entity)
+                .text("'s queued actions were cancelled");
+            GameState::add_message(message);
+        }
+    }
+}
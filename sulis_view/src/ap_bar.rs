@@ -23,8 +23,9 @@ use sulis_core::io::event::ClickKind;
 use sulis_core::ui::{Widget, WidgetKind};
 use sulis_core::widgets::ProgressBar;
 use sulis_module::{Module, OnTrigger};
-use sulis_state::{ChangeListener, EntityState, GameState};
+use sulis_state::{EntityState, GameState};
 
+use crate::binding::{self, Ap, BindTarget};
 use crate::RootView;
 
 pub const NAME: &str = "ap_bar";
@@ -96,36 +97,29 @@ impl WidgetKind for ApBar {
     }
 
     fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        if let Some(entity) = GameState::selected().first() {
+            self.entity = Rc::clone(entity);
+        }
+
         let visible = GameState::is_current(&self.entity);
+        widget.borrow_mut().state.set_visible(visible);
 
-        let mut entity = self.entity.borrow_mut();
+        binding::bind::<Ap>(NAME, widget, BindTarget::Selected, |widget, entity, _| {
+            let bar = Widget::kind_mut::<ApBar>(widget);
+            if !Rc::ptr_eq(&bar.entity, entity) {
+                bar.entity = Rc::clone(entity);
+            }
 
-        widget.borrow_mut().state.set_visible(visible);
+            widget.borrow_mut().invalidate_children();
+        });
 
-        let widget_ref = Rc::clone(widget);
         let player_ref = GameState::player();
-        entity.actor.listeners.add(ChangeListener::new(
-            NAME,
-            Box::new(move |_| {
-                widget_ref.borrow_mut().invalidate_children();
-                let cb = OnTrigger::CheckEndTurn;
-                GameState::add_ui_callback(vec![cb], &player_ref, &player_ref);
-            }),
-        ));
-
-        let widget_ref = Rc::clone(widget);
-        GameState::add_party_listener(ChangeListener::new(
-            NAME,
-            Box::new(move |entity| {
-                let bar = Widget::kind_mut::<ApBar>(&widget_ref);
-
-                if let Some(entity) = entity {
-                    bar.entity = Rc::clone(entity);
-                }
-
-                widget_ref.borrow_mut().invalidate_children();
-            }),
-        ));
+        binding::on_change(NAME, BindTarget::Selected, move |_| {
+            let cb = OnTrigger::CheckEndTurn;
+            GameState::add_ui_callback(vec![cb], &player_ref, &player_ref);
+        });
+
+        let entity = self.entity.borrow();
 
         let rules = Module::rules();
         let ap_per_ball = rules.display_ap;
@@ -22,14 +22,22 @@ use sulis_core::io::{event::ClickKind, GraphicsRenderer};
 use sulis_core::ui::{Widget, WidgetKind, WidgetState};
 use sulis_core::util::Point;
 use sulis_core::widgets::TextArea;
+use sulis_module::area::{ToKind, Transition};
+use sulis_module::{DamageKind, Module};
 use sulis_state::{ChangeListener, EntityState, GameState};
 
+use crate::records_window::RESISTANCE_REVEAL_LEVEL;
+
 const NAME: &str = "area_mouseover";
 
 enum Kind {
     Entity(Rc<RefCell<EntityState>>),
     Prop(usize),
-    Transition(String),
+    Transition {
+        name: String,
+        to: ToKind,
+        thumbnail: Option<String>,
+    },
 }
 
 impl PartialEq for AreaMouseover {
@@ -43,8 +51,11 @@ impl PartialEq for AreaMouseover {
                 Kind::Prop(other_index) => other_index == index,
                 _ => false,
             },
-            Kind::Transition(ref name) => match &other.kind {
-                Kind::Transition(ref other_name) => other_name == name,
+            Kind::Transition { ref name, .. } => match &other.kind {
+                Kind::Transition {
+                    name: ref other_name,
+                    ..
+                } => other_name == name,
                 _ => false,
             },
         }
@@ -53,6 +64,7 @@ impl PartialEq for AreaMouseover {
 
 pub struct AreaMouseover {
     kind: Kind,
+    damage_kind: Option<DamageKind>,
     text_area: Rc<RefCell<TextArea>>,
 }
 
@@ -61,17 +73,35 @@ impl AreaMouseover {
         AreaMouseover::new(Kind::Entity(Rc::clone(entity)))
     }
 
+    /// Creates a mouseover for an entity under a targeter dealing the specified
+    /// `damage_kind`, showing the entity's resistance to it if the player knows it.
+    pub fn new_targeted_entity(
+        entity: &Rc<RefCell<EntityState>>,
+        damage_kind: Option<DamageKind>,
+    ) -> Rc<RefCell<AreaMouseover>> {
+        let mouseover = AreaMouseover::new(Kind::Entity(Rc::clone(entity)));
+        mouseover.borrow_mut().damage_kind = damage_kind;
+        mouseover
+    }
+
     pub fn new_prop(index: usize) -> Rc<RefCell<AreaMouseover>> {
         AreaMouseover::new(Kind::Prop(index))
     }
 
-    pub fn new_transition(name: &str) -> Rc<RefCell<AreaMouseover>> {
-        AreaMouseover::new(Kind::Transition(name.to_string()))
+    /// Creates a mouseover for a transition, showing its hover text along with
+    /// the destination and thumbnail, if any
+    pub fn new_transition(transition: &Transition) -> Rc<RefCell<AreaMouseover>> {
+        AreaMouseover::new(Kind::Transition {
+            name: transition.hover_text.to_string(),
+            to: transition.to.clone(),
+            thumbnail: transition.thumbnail.clone(),
+        })
     }
 
     fn new(kind: Kind) -> Rc<RefCell<AreaMouseover>> {
         Rc::new(RefCell::new(AreaMouseover {
             kind,
+            damage_kind: None,
             text_area: TextArea::empty(),
         }))
     }
@@ -82,9 +112,13 @@ impl AreaMouseover {
         match self.kind {
             Kind::Entity(ref entity) => {
                 let actor = &entity.borrow().actor;
-                state.add_text_arg("name", &actor.actor.name);
+                state.add_display_text_arg("name", &actor.actor.name);
                 state.add_text_arg("cur_hp", &actor.hp().to_string());
                 state.add_text_arg("max_hp", &actor.stats.max_hp.to_string());
+
+                if let Some(kind) = self.damage_kind {
+                    add_resistance_text_args(&entity.borrow(), kind, state);
+                }
             }
             Kind::Prop(index) => {
                 let area_state = GameState::area_state();
@@ -99,14 +133,22 @@ impl AreaMouseover {
                 if !prop.is_hover() && !prop.might_contain_items() {
                     state.add_text_arg("empty", "true");
                 }
-                state.add_text_arg("name", prop.name());
+                state.add_display_text_arg("name", prop.name());
 
                 if let Some(ref text) = prop.prop.status_text {
-                    state.add_text_arg("status", text);
+                    state.add_display_text_arg("status", text);
                 }
             }
-            Kind::Transition(ref name) => {
-                state.add_text_arg("name", name);
+            Kind::Transition {
+                ref name,
+                ref to,
+                ref thumbnail,
+            } => {
+                state.add_display_text_arg("name", name);
+                add_destination_text_args(to, state);
+                if let Some(ref thumbnail) = thumbnail {
+                    state.add_text_arg("thumbnail", thumbnail);
+                }
             }
         }
 
@@ -114,6 +156,41 @@ impl AreaMouseover {
     }
 }
 
+fn add_destination_text_args(to: &ToKind, state: &mut WidgetState) {
+    let id = match to {
+        ToKind::Area { ref id, .. } | ToKind::FindLink { ref id, .. } => id,
+        ToKind::WorldMap => {
+            state.add_text_arg("world_map", "true");
+            return;
+        }
+        ToKind::CurArea { .. } => return,
+    };
+
+    if let Some(area) = Module::area(id) {
+        state.add_display_text_arg("destination", &area.name);
+    }
+}
+
+fn add_resistance_text_args(entity: &EntityState, kind: DamageKind, state: &mut WidgetState) {
+    if !entity.is_party_member() {
+        let records = GameState::records();
+        let kills = records
+            .creature(&entity.actor.actor.id)
+            .map_or(0, |record| record.kills);
+        if Module::rules().bestiary_reveal_level(kills) < RESISTANCE_REVEAL_LEVEL {
+            return;
+        }
+    }
+
+    let amount = entity.actor.stats.resistance.amount(kind);
+    state.add_text_arg("damage_kind", kind.to_str());
+    if amount > 0 {
+        state.add_text_arg("resists", &amount.to_string());
+    } else if amount < 0 {
+        state.add_text_arg("vulnerable", &(-amount).to_string());
+    }
+}
+
 impl WidgetKind for AreaMouseover {
     widget_kind!(NAME);
 
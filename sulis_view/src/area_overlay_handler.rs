@@ -123,10 +123,11 @@ impl AreaOverlayHandler {
 
         if let Some(ref targeter) = targeter {
             let mut targeter = targeter.borrow_mut();
+            let damage_kind = targeter.damage_kind();
             let mouse_over = targeter.on_mouse_move(x, y);
 
             if let Some(entity) = mouse_over {
-                return Some(AreaMouseover::new_entity(entity));
+                return Some(AreaMouseover::new_targeted_entity(entity, damage_kind));
             } else {
                 return None;
             }
@@ -143,7 +144,7 @@ impl AreaOverlayHandler {
         } else if self.check_closed_door(x, y, &area_state).is_some() {
             None
         } else if let Some(transition) = area_state.get_transition_at(x, y) {
-            Some(AreaMouseover::new_transition(&transition.hover_text))
+            Some(AreaMouseover::new_transition(transition))
         } else if let Some(index) = area_state.props().index_at(x, y) {
             let interactive = {
                 let prop = area_state.props().get(index);
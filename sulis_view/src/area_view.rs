@@ -16,7 +16,7 @@
 
 use std::any::Any;
 use std::cell::{RefCell, RefMut};
-use std::cmp::{self, Ordering};
+use std::cmp;
 use std::mem;
 use std::rc::Rc;
 use std::time;
@@ -28,17 +28,24 @@ use sulis_core::io::event::ClickKind;
 use sulis_core::io::*;
 use sulis_core::resource::{ResourceSet, Sprite};
 use sulis_core::ui::{animation_state, compute_area_scaling};
-use sulis_core::ui::{color, Color, Cursor, Scrollable, Widget, WidgetKind};
+use sulis_core::ui::{color, Color, Cursor, LineRenderer, Scrollable, Widget, WidgetKind};
 use sulis_core::util::{self, Offset, Point, Rect, Scale};
 use sulis_core::widgets::Label;
 use sulis_module::{
-    area::{Layer, Tile},
+    area::{Layer, LocationKind, Tile},
     DamageKind, Module,
 };
-use sulis_state::{area_feedback_text, area_state::PCVisRedraw, RangeIndicatorImageSet};
-use sulis_state::{AreaDrawable, AreaState, EntityState, EntityTextureCache, GameState};
+use sulis_state::area_feedback_text;
+use sulis_state::area_state::{InteractableKind, PCVisRedraw};
+use sulis_state::{RangeIndicator, RangeIndicatorImageSet};
+use sulis_state::{
+    AreaDrawable, AreaState, AutoPause, DebugTime, EntityState, EntityTextureCache, GameState,
+};
 
-use crate::{action_kind, window_fade, AreaOverlayHandler, ScreenShake, WindowFade};
+use crate::action_queue::ActionQueue;
+use crate::{
+    action_kind, window_fade, AreaOverlayHandler, Camera, OverLayers, ScreenShake, WindowFade,
+};
 
 struct Range {
     min_x: i32,
@@ -47,16 +54,67 @@ struct Range {
     max_y: i32,
 }
 
+/// A tile from a layer above the entity layer.  These are drawn each frame,
+/// sorted together with entities and props, rather than cached to a texture.
+struct AerialTile {
+    layer: usize,
+    location: Point,
+    tile: Rc<Tile>,
+}
+
+enum SortedDrawable<'a> {
+    Area(&'a dyn AreaDrawable),
+    Tile(&'a AerialTile),
+}
+
+impl SortedDrawable<'_> {
+    /// The key to draw by, in ascending order.  Drawables are drawn row by
+    /// row from their bottom edge, with aerial creatures drawn above all
+    /// else.  Within a row, entities and props are drawn below tiles, and
+    /// tiles keep their layer order.
+    fn sort_key(&self) -> (bool, i32, usize, i32) {
+        match self {
+            SortedDrawable::Area(drawable) => {
+                let loc = drawable.location();
+                let size = drawable.size();
+                (
+                    drawable.aerial(),
+                    loc.y + size.height,
+                    0,
+                    loc.x + size.width / 2,
+                )
+            }
+            SortedDrawable::Tile(aerial) => {
+                let loc = aerial.location;
+                let tile = &aerial.tile;
+                (
+                    false,
+                    loc.y + tile.height,
+                    aerial.layer + 1,
+                    loc.x + tile.width / 2,
+                )
+            }
+        }
+    }
+}
+
 const NAME: &str = "area";
 
 pub struct AreaView {
     scale: (f32, f32),
     cache_invalid: bool,
     layers: Vec<String>,
+    aerial_tiles: Vec<AerialTile>,
+    over_layers: OverLayers,
+    over_layer_alpha: f32,
+    over_layer_fade_millis: u32,
     entity_texture_cache: EntityTextureCache,
 
     targeter_label: Rc<RefCell<Widget>>,
     targeter_tile: Option<Rc<dyn Image>>,
+    debug_tile: Option<Rc<dyn Image>>,
+    debug_overlay: bool,
+    debug_text_scale: f32,
     range_indicator_image_set: Option<RangeIndicatorImageSet>,
 
     scroll: Scrollable,
@@ -64,10 +122,15 @@ pub struct AreaView {
     feedback_text_params: area_feedback_text::Params,
     entity_see_through_alpha: f32,
 
-    scroll_target: Option<(f32, f32)>,
-    screen_shake: Option<ScreenShake>,
+    camera: Camera,
 
     overlay_handler: AreaOverlayHandler,
+    action_queue: ActionQueue,
+
+    highlight_interactables: bool,
+    highlight_tile: Option<Rc<dyn Image>>,
+    highlight_text_scale: f32,
+    highlight_colors: [Color; 3],
 }
 
 const TILE_CACHE_TEXTURE_SIZE: u32 = 2048;
@@ -77,7 +140,6 @@ const TEX_COORDS: [f32; 8] = [0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0];
 const ENTITY_TEX_ID: &str = "__entities__";
 const VISIBILITY_TEX_ID: &str = "__visibility__";
 const BASE_LAYER_ID: &str = "__base_layer__";
-const AERIAL_LAYER_ID: &str = "__aerial_layer__";
 
 impl AreaView {
     pub fn new(scroll: Scrollable) -> Rc<RefCell<AreaView>> {
@@ -91,18 +153,39 @@ impl AreaView {
                 TILE_SIZE,
             ),
             layers: Vec::new(),
+            aerial_tiles: Vec::new(),
+            over_layers: OverLayers::default(),
+            over_layer_alpha: 0.0,
+            over_layer_fade_millis: 300,
             scroll,
             targeter_tile: None,
+            debug_tile: None,
+            debug_overlay: Config::debug().entity_overlay,
+            debug_text_scale: 6.0,
             range_indicator_image_set: None,
             active_entity: None,
             entity_see_through_alpha: 0.2,
             feedback_text_params: area_feedback_text::Params::default(),
-            scroll_target: None,
-            screen_shake: None,
+            camera: Camera::default(),
             overlay_handler: AreaOverlayHandler::default(),
+            action_queue: ActionQueue::default(),
+            highlight_interactables: false,
+            highlight_tile: None,
+            highlight_text_scale: 0.8,
+            highlight_colors: [color::YELLOW, color::CYAN, color::GREEN],
         }))
     }
 
+    /// Sets whether the props, transitions, and NPCs the player may interact
+    /// with are outlined and labeled, while the highlight key is held
+    pub fn set_highlight_interactables(&mut self, highlight: bool) {
+        self.highlight_interactables = highlight;
+    }
+
+    pub(crate) fn action_queue_mut(&mut self) -> &mut ActionQueue {
+        &mut self.action_queue
+    }
+
     pub fn clear_mouse_state(&mut self) {
         self.overlay_handler.clear_mouse_state();
     }
@@ -150,10 +233,32 @@ impl AreaView {
 
     pub fn get_scroll(&self) -> Scrollable { self.scroll }
 
+    pub fn debug_overlay(&self) -> bool {
+        self.debug_overlay
+    }
+
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        self.debug_overlay = enabled;
+    }
+
     pub fn screen_shake(&mut self) {
         if !Config::crit_screen_shake() { return; }
 
-        self.screen_shake = Some(ScreenShake::new());
+        self.camera.shake(ScreenShake::new());
+    }
+
+    pub fn shake_view(&mut self, intensity: f32, millis: u32) {
+        self.camera.shake(ScreenShake::with_intensity(intensity, millis));
+    }
+
+    /// Keeps the view centered on the specified entity as it moves, until
+    /// `unlock_view` is called.  The player may not scroll while locked
+    pub fn lock_view(&mut self, entity: Rc<RefCell<EntityState>>) {
+        self.camera.lock(entity);
+    }
+
+    pub fn unlock_view(&mut self) {
+        self.camera.unlock();
     }
 
     pub fn delayed_scroll_to_point(
@@ -166,13 +271,33 @@ impl AreaView {
     ) {
         let (x, y) = self.center_scroll_on_point(x, y, area_width, area_height, widget);
         let (x, y) = self.scroll.bound(x, y);
-        self.scroll_target = Some((x, y));
+        self.camera.scroll_to((x, y));
+    }
+
+    /// Scrolls the view so it is centered on the specified point, taking
+    /// `millis` to complete the scroll
+    pub fn timed_scroll_to_point(
+        &mut self,
+        x: f32,
+        y: f32,
+        millis: u32,
+        area_width: i32,
+        area_height: i32,
+        widget: &Widget,
+    ) {
+        let (x, y) = self.center_scroll_on_point(x, y, area_width, area_height, widget);
+        let (x, y) = self.scroll.bound(x, y);
+        let start = (self.scroll.x(), self.scroll.y());
+        self.camera.scroll_over(start, (x, y), millis);
     }
 
     fn get_cursor_pos(&self, widget: &Rc<RefCell<Widget>>) -> (f32, f32) {
         let pos = widget.borrow().state.inner_position();
         let (x, y) = self.get_cursor_pos_scaled(pos.x, pos.y);
-        ((x + self.scroll.x()), (y + self.scroll.y()))
+        let (x, y) = (x + self.scroll.x(), y + self.scroll.y());
+
+        // shift the cursor into the hex containing it on the shifted odd columns
+        (x, y - Module::rules().grid_y_offset(x.floor() as i32))
     }
 
     fn get_cursor_pos_scaled(&self, pos_x: i32, pos_y: i32) -> (f32, f32) {
@@ -207,19 +332,41 @@ impl AreaView {
         // their ordering otherwise
         tiles.sort_by(|a, b| (a.1 + a.2.height).cmp(&(b.1 + b.2.height)));
 
-        let mut draw_list = DrawList::empty_sprite();
-        for (x, y, tile) in tiles {
-            let rect = Rect {
-                x: x as f32,
-                y: y as f32,
-                w: tile.width as f32,
-                h: tile.height as f32,
-            };
-            draw_list.append(&mut DrawList::from_sprite(&tile.image_display, rect));
-        }
+        // the tiles themselves are not Send, so pull out just the quad data
+        // and generate the vertices in parallel
+        let sheet_id = match tiles.first() {
+            None => return,
+            Some((_, _, tile)) => tile.image_display.sheet_id.to_string(),
+        };
+        let rules = Module::rules();
+        let quads: Vec<TexturedQuad> = tiles
+            .iter()
+            .map(|(x, y, tile)| TexturedQuad {
+                tex_coords: tile.image_display.tex_coords,
+                rect: Rect {
+                    x: *x as f32,
+                    y: *y as f32 + rules.grid_y_offset(*x),
+                    w: tile.width as f32,
+                    h: tile.height as f32,
+                },
+            })
+            .collect();
+
+        let draw_list = DrawList::from_texture_quads(&sheet_id, &quads);
+        AreaView::draw_list_to_texture(renderer, draw_list, texture_id);
+    }
 
-        if !draw_list.is_empty() {
-            AreaView::draw_list_to_texture(renderer, draw_list, texture_id);
+    fn add_aerial_tiles(&mut self, index: usize, layer: &Layer) {
+        for y in 0..layer.height {
+            for x in 0..layer.width {
+                for tile in layer.tiles_at(x, y) {
+                    self.aerial_tiles.push(AerialTile {
+                        layer: index,
+                        location: Point::new(x, y),
+                        tile: Rc::clone(tile),
+                    });
+                }
+            }
         }
     }
 
@@ -252,13 +399,29 @@ impl AreaView {
             );
 
             let scale = TILE_SIZE as i32;
-            renderer.clear_texture_region(
-                VISIBILITY_TEX_ID,
-                min_x * scale,
-                min_y * scale,
-                max_x * scale,
-                max_y * scale,
-            );
+            if Module::rules().is_hex_grid() {
+                // odd columns are drawn half a tile lower, so clear each
+                // column over the same rows that are redrawn for it
+                for x in min_x..max_x {
+                    let (start_y, offset) = AreaView::vis_column_start(x, min_y);
+                    let offset = (offset * scale as f32) as i32;
+                    renderer.clear_texture_region(
+                        VISIBILITY_TEX_ID,
+                        x * scale,
+                        start_y * scale + offset,
+                        (x + 1) * scale,
+                        max_y * scale + offset,
+                    );
+                }
+            } else {
+                renderer.clear_texture_region(
+                    VISIBILITY_TEX_ID,
+                    min_x * scale,
+                    min_y * scale,
+                    max_x * scale,
+                    max_y * scale,
+                );
+            }
             let range = Range {
                 min_x,
                 max_x,
@@ -283,20 +446,16 @@ impl AreaView {
     ) {
         let mut draw_list = DrawList::empty_sprite();
 
-        // info!("======");
-        for tile_y in range.min_y..range.max_y {
-            // let mut cur_line = "".to_string();
-            for tile_x in range.min_x..range.max_x {
+        for tile_x in range.min_x..range.max_x {
+            let (min_y, offset) = AreaView::vis_column_start(tile_x, range.min_y);
+            for tile_y in min_y..range.max_y {
                 if area_state.is_pc_visible(tile_x, tile_y) {
-                    // cur_line.push('x');
                     continue;
-                } else {
-                    // cur_line.push(' ');
                 }
 
                 let rect = Rect {
                     x: tile_x as f32,
-                    y: tile_y as f32,
+                    y: tile_y as f32 + offset,
                     w: 1.0,
                     h: 1.0,
                 };
@@ -307,7 +466,6 @@ impl AreaView {
                 }
                 draw_list.append(&mut DrawList::from_sprite(explored_sprite, rect));
             }
-            // info!("{}|", cur_line);
         }
 
         if draw_list.is_empty() {
@@ -316,6 +474,29 @@ impl AreaView {
         AreaView::draw_list_to_texture(renderer, draw_list, VISIBILITY_TEX_ID);
     }
 
+    /// Returns the first row redrawn in column `x` when the rows from `min_y`
+    /// are redrawn, along with the column's grid y offset.  A lowered hex
+    /// column starts a row earlier so the redrawn tiles cover `min_y`.
+    fn vis_column_start(x: i32, min_y: i32) -> (i32, f32) {
+        let offset = Module::rules().grid_y_offset(x);
+        if offset > 0.0 {
+            ((min_y - 1).max(0), offset)
+        } else {
+            (min_y, offset)
+        }
+    }
+
+    /// Returns the first and last tile coordinates within the currently
+    /// scrolled view
+    fn visible_tile_bounds(&self, widget: &Widget) -> (Point, Point) {
+        let (scale_x, scale_y) = self.scale;
+        let min_x = self.scroll.x() as i32;
+        let min_y = self.scroll.y() as i32;
+        let max_x = (self.scroll.x() + widget.state.inner_width() as f32 / scale_x).ceil();
+        let max_y = (self.scroll.y() + widget.state.inner_height() as f32 / scale_y).ceil();
+        (Point::new(min_x, min_y), Point::new(max_x as i32, max_y as i32))
+    }
+
     fn draw_list_to_texture(
         renderer: &mut dyn GraphicsRenderer,
         draw_list: DrawList,
@@ -360,6 +541,10 @@ impl AreaView {
         renderer.draw(draw_list);
     }
 
+    /// Draws the props and visible entities in the area.  If `with_tiles` is
+    /// set, the on screen tiles above the entity layer are drawn as well, with
+    /// everything sorted so that nearer objects are drawn over farther ones.
+    #[allow(clippy::too_many_arguments)]
     fn draw_entities_props(
         &mut self,
         renderer: &mut dyn GraphicsRenderer,
@@ -368,12 +553,13 @@ impl AreaView {
         widget: &Widget,
         state: &AreaState,
         millis: u32,
+        with_tiles: bool,
     ) {
         // let start_time = time::Instant::now();
-        let mut to_draw: Vec<&dyn AreaDrawable> = Vec::new();
+        let mut to_draw: Vec<SortedDrawable> = Vec::new();
 
         for prop_state in state.props().iter() {
-            to_draw.push(prop_state);
+            to_draw.push(SortedDrawable::Area(prop_state));
         }
 
         let mgr = GameState::turn_manager();
@@ -392,37 +578,288 @@ impl AreaView {
 
             let entity = unsafe { mem::transmute::<&EntityState, &'static EntityState>(&*entity) };
 
-            to_draw.push(entity);
+            to_draw.push(SortedDrawable::Area(entity));
         }
 
-        to_draw.sort_by(|a, b| {
-            if a.aerial() && !b.aerial() {
-                std::cmp::Ordering::Greater
-            } else if !a.aerial() && b.aerial() {
-                std::cmp::Ordering::Less
-            } else {
-                let a_y = a.location().y + a.size().height;
-                let a_x = a.location().x + a.size().width / 2;
-                let b_y = b.location().y + b.size().height;
-                let b_x = b.location().x + b.size().width / 2;
-
-                if a_y < b_y { Ordering::Less }
-                else if a_y > b_y { Ordering::Greater }
-                else if a_x < b_x { Ordering::Less }
-                else if a_x > b_x { Ordering::Greater }
-                else { Ordering::Equal }
+        if with_tiles {
+            let (min, max) = self.visible_tile_bounds(widget);
+            for aerial in self.aerial_tiles.iter() {
+                let loc = aerial.location;
+                if loc.x + aerial.tile.width < min.x || loc.y + aerial.tile.height < min.y {
+                    continue;
+                }
+                if loc.x > max.x || loc.y > max.y {
+                    continue;
+                }
+                to_draw.push(SortedDrawable::Tile(aerial));
             }
-        });
+        }
 
+        to_draw.sort_by_key(|drawable| drawable.sort_key());
+
+        let (x, y) = widget.state.inner_position().as_tuple();
+        let (x, y) = (x as f32 - self.scroll.x(), y as f32 - self.scroll.y());
+
+        // consecutive tiles sharing a spritesheet are submitted together
+        let rules = Module::rules();
+        let mut quads: Vec<TexturedQuad> = Vec::new();
+        let mut sheet_id = "";
         for drawable in to_draw {
-            let (x, y) = widget.state.inner_position().as_tuple();
-            let (x, y) = (x as f32 - self.scroll.x(), y as f32 - self.scroll.y());
-            drawable.draw(renderer, scale, x, y, millis, color);
+            let aerial = match drawable {
+                SortedDrawable::Area(drawable) => {
+                    AreaView::draw_tile_quads(renderer, sheet_id, &mut quads, scale, color);
+                    drawable.draw(renderer, scale, x, y, millis, color);
+                    continue;
+                }
+                SortedDrawable::Tile(aerial) => aerial,
+            };
+
+            let image = &aerial.tile.image_display;
+            if image.sheet_id != sheet_id {
+                AreaView::draw_tile_quads(renderer, sheet_id, &mut quads, scale, color);
+                sheet_id = &image.sheet_id;
+            }
+
+            let loc = aerial.location;
+            quads.push(TexturedQuad {
+                tex_coords: image.tex_coords,
+                rect: Rect {
+                    x: x + loc.x as f32,
+                    y: y + loc.y as f32 + rules.grid_y_offset(loc.x),
+                    w: aerial.tile.width as f32,
+                    h: aerial.tile.height as f32,
+                },
+            });
         }
+        AreaView::draw_tile_quads(renderer, sheet_id, &mut quads, scale, color);
 
         // info!("Entity & Prop draw time: {}", util::format_elapsed_secs(start_time.elapsed()));
     }
 
+    fn draw_tile_quads(
+        renderer: &mut dyn GraphicsRenderer,
+        sheet_id: &str,
+        quads: &mut Vec<TexturedQuad>,
+        scale: Scale,
+        color: Color,
+    ) {
+        if quads.is_empty() {
+            return;
+        }
+
+        let mut draw_list = DrawList::from_texture_quads(sheet_id, quads);
+        draw_list.set_scale(scale);
+        draw_list.set_color(color);
+        renderer.draw(draw_list);
+        quads.clear();
+    }
+
+    /// Draws attack ranges for all hostile entities, along with the remaining
+    /// move path and destination of any moving entity
+    fn draw_debug_overlay(
+        &self,
+        renderer: &mut dyn GraphicsRenderer,
+        state: &AreaState,
+        offset: Offset,
+        scale: Scale,
+        millis: u32,
+    ) {
+        let player = GameState::player();
+        let mgr = GameState::turn_manager();
+        let mgr = mgr.borrow();
+
+        if let Some(ref image_set) = self.range_indicator_image_set {
+            let range_offset = Offset {
+                x: self.scroll.x(),
+                y: self.scroll.y(),
+            };
+            for index in state.entity_iter() {
+                let entity = mgr.entity(*index);
+                {
+                    let entity = entity.borrow();
+                    if entity.actor.is_dead() || !entity.is_hostile(&player.borrow()) {
+                        continue;
+                    }
+                }
+
+                let indicator = RangeIndicator::attack(&entity);
+                let mut draw_list = indicator.get_draw_list(image_set, range_offset, millis);
+                draw_list.set_scale(scale);
+                draw_list.set_color(Color::from_string("F448"));
+                renderer.draw(draw_list);
+            }
+        }
+
+        let tile = match self.debug_tile {
+            None => return,
+            Some(ref tile) => tile,
+        };
+
+        for (entity, path) in GameState::move_paths() {
+            let entity = entity.borrow();
+            if entity.location.area_id != state.area.area.id || path.is_empty() {
+                continue;
+            }
+
+            let color = if entity.is_party_member() {
+                "0F0"
+            } else if entity.is_hostile(&player.borrow()) {
+                "F00"
+            } else {
+                "FF0"
+            };
+
+            let w = entity.size.width as f32;
+            let h = entity.size.height as f32;
+
+            let mut draw_list = DrawList::empty_sprite();
+            for p in path.iter() {
+                let rect = Rect {
+                    x: offset.x + p.x as f32 + w / 2.0 - 0.25,
+                    y: offset.y + p.y as f32 + h / 2.0 - 0.25,
+                    w: 0.5,
+                    h: 0.5,
+                };
+                tile.append_to_draw_list(&mut draw_list, &animation_state::NORMAL, rect, millis);
+            }
+            draw_list.set_scale(scale);
+            draw_list.set_color(Color::from_string(&format!("{color}A")));
+            renderer.draw(draw_list);
+
+            let target = path[path.len() - 1];
+            let rect = Rect {
+                x: offset.x + target.x as f32,
+                y: offset.y + target.y as f32,
+                w,
+                h,
+            };
+            let mut draw_list = DrawList::empty_sprite();
+            tile.append_to_draw_list(&mut draw_list, &animation_state::NORMAL, rect, millis);
+            draw_list.set_scale(scale);
+            draw_list.set_color(Color::from_string(&format!("{color}4")));
+            renderer.draw(draw_list);
+        }
+    }
+
+    fn draw_interactables(
+        &self,
+        renderer: &mut dyn GraphicsRenderer,
+        state: &AreaState,
+        widget: &Widget,
+        offset: Offset,
+        scale: Scale,
+        millis: u32,
+    ) {
+        let tile = match self.highlight_tile {
+            None => return,
+            Some(ref tile) => tile,
+        };
+
+        let (scale_x, scale_y) = self.scale;
+        let min = Point::new(self.scroll.x() as i32, self.scroll.y() as i32);
+        let max = Point::new(
+            (self.scroll.x() + widget.state.inner_width() as f32 / scale_x).ceil() as i32,
+            (self.scroll.y() + widget.state.inner_height() as f32 / scale_y).ceil() as i32,
+        );
+
+        let font = &self.feedback_text_params.font;
+        let font_rend = LineRenderer::new(font);
+        let text_scale = self.highlight_text_scale;
+        for interactable in state.interactables_in(min, max) {
+            let color = match interactable.kind {
+                InteractableKind::Prop => self.highlight_colors[0],
+                InteractableKind::Transition => self.highlight_colors[1],
+                InteractableKind::Npc => self.highlight_colors[2],
+            };
+
+            let x = offset.x + interactable.x as f32;
+            let y = offset.y + interactable.y as f32;
+            let w = interactable.w as f32;
+            let h = interactable.h as f32;
+            let t = 0.1;
+            let edges = [
+                Rect { x, y, w, h: t },
+                Rect { x, y: y + h - t, w, h: t },
+                Rect { x, y, w: t, h },
+                Rect { x: x + w - t, y, w: t, h },
+            ];
+
+            let mut draw_list = DrawList::empty_sprite();
+            for rect in edges {
+                tile.append_to_draw_list(&mut draw_list, &animation_state::NORMAL, rect, millis);
+            }
+            draw_list.set_scale(scale);
+            draw_list.set_color(color);
+            renderer.draw(draw_list);
+
+            let text_width =
+                font.get_width(&interactable.name) as f32 / font.line_height as f32 * text_scale;
+            let text_offset = Offset {
+                x: x + (w - text_width) / 2.0,
+                y: y - text_scale,
+            };
+            let (mut draw_list, _) =
+                font_rend.get_draw_list(&interactable.name, text_offset, text_scale);
+            draw_list.set_scale(scale);
+            draw_list.set_color(color);
+            renderer.draw(draw_list);
+        }
+    }
+
+    /// Draws the renderer texture cache usage in the corner of the area
+    fn draw_texture_stats(&self, renderer: &mut dyn GraphicsRenderer, widget: &Widget) {
+        let stats = renderer.texture_cache_stats();
+        let mb = |bytes: usize| bytes as f32 / (1024.0 * 1024.0);
+        let text = format!(
+            "Textures: {}, {:.1} / {:.0} MB, {} evicted",
+            stats.textures,
+            mb(stats.bytes),
+            mb(stats.budget),
+            stats.evictions
+        );
+
+        let offset = Offset {
+            x: widget.state.inner_left() as f32,
+            y: widget.state.inner_top() as f32,
+        };
+        let font_rend = LineRenderer::new(&self.feedback_text_params.font);
+        let (mut draw_list, _) = font_rend.get_draw_list(&text, offset, self.debug_text_scale);
+        draw_list.set_color(self.feedback_text_params.info_color);
+        renderer.draw(draw_list);
+    }
+
+    fn draw_debug_time_status(
+        &self,
+        renderer: &mut dyn GraphicsRenderer,
+        widget: &Widget,
+        status: &str,
+    ) {
+        // draw below the texture stats line
+        let offset = Offset {
+            x: widget.state.inner_left() as f32,
+            y: widget.state.inner_top() as f32 + self.debug_text_scale,
+        };
+        let font_rend = LineRenderer::new(&self.feedback_text_params.font);
+        let (mut draw_list, _) = font_rend.get_draw_list(status, offset, self.debug_text_scale);
+        draw_list.set_color(self.feedback_text_params.info_color);
+        renderer.draw(draw_list);
+    }
+
+    fn draw_pause_status(&self, renderer: &mut dyn GraphicsRenderer, widget: &Widget, text: &str) {
+        let text = format!("Paused: {}", text);
+        let font_rend = LineRenderer::new(&self.feedback_text_params.font);
+        let (_, width) = font_rend.get_draw_list(&text, Offset::default(), self.debug_text_scale);
+
+        // draw centered along the top of the area
+        let offset = Offset {
+            x: widget.state.inner_left() as f32 + (widget.state.inner_width() as f32 - width) / 2.0,
+            y: widget.state.inner_top() as f32 + 2.0 * self.debug_text_scale,
+        };
+        let (mut draw_list, _) = font_rend.get_draw_list(&text, offset, self.debug_text_scale);
+        draw_list.set_color(self.feedback_text_params.info_color);
+        renderer.draw(draw_list);
+    }
+
     fn draw_selection(
         &mut self,
         selected: &Rc<RefCell<EntityState>>,
@@ -437,8 +874,9 @@ impl AreaView {
         let selected = selected.borrow();
         let w = selected.size.width as f32;
         let h = selected.size.height as f32;
+        let grid_y = Module::rules().grid_y_offset(selected.location.x);
         let x = x_base + selected.location.x as f32 + selected.sub_pos.0;
-        let y = y_base + selected.location.y as f32 + selected.sub_pos.1;
+        let y = y_base + selected.location.y as f32 + selected.sub_pos.1 + grid_y;
 
         let rect = Rect { x, y, w, h };
         let mut draw_list = DrawList::empty_sprite();
@@ -454,6 +892,10 @@ impl AreaView {
     }
 
     pub fn scroll(&mut self, delta_x: f32, delta_y: f32, millis: u32) {
+        if self.camera.is_locked() {
+            return;
+        }
+
         let speed = Config::scroll_speed() * millis as f32 / 33.0;
         let delta_x = speed * delta_x / self.scale.0;
         let delta_y = speed * delta_y / self.scale.1;
@@ -487,7 +929,6 @@ impl AreaView {
         let texture_ids = vec![
             VISIBILITY_TEX_ID,
             BASE_LAYER_ID,
-            AERIAL_LAYER_ID,
             ENTITY_TEX_ID,
         ];
         for texture_id in texture_ids {
@@ -503,15 +944,22 @@ impl AreaView {
             }
         }
 
+        let over_layers = &state.area.area.over_layers;
+        self.over_layers = OverLayers::new(&state.area.layer_set, over_layers);
+
+        self.aerial_tiles.clear();
         for (index, layer) in state.area.layer_set.layers.iter().enumerate() {
-            let texture_id = if index <= state.area.layer_set.entity_layer_index {
-                BASE_LAYER_ID
-            } else {
-                AERIAL_LAYER_ID
-            };
+            if over_layers.contains(&layer.id) {
+                continue;
+            }
+
+            if index > state.area.layer_set.entity_layer_index {
+                self.add_aerial_tiles(index, layer);
+                continue;
+            }
             trace!("Caching layer '{}'", layer.id);
 
-            self.draw_layer_to_texture(renderer, layer, texture_id);
+            self.draw_layer_to_texture(renderer, layer, BASE_LAYER_ID);
         }
 
         self.entity_texture_cache.invalidate();
@@ -524,48 +972,23 @@ impl AreaView {
 impl WidgetKind for AreaView {
     widget_kind!(NAME);
 
-    fn update(&mut self, _widget: &Rc<RefCell<Widget>>, millis: u32) {
-        if let Some(shake) = self.screen_shake.as_mut() {
-            let result = shake.shake(millis);
-
-            if let Some(scroll) = result.scroll {
-                let factor = GameState::user_zoom();
-                self.scroll.change(scroll.x / factor, scroll.y / factor);
-            }
-
-            if result.done {
-                self.screen_shake = None;
-            }
-        }
-
-        let (dest_x, dest_y) = match self.scroll_target {
-            None => return,
-            Some((x, y)) => (x, y),
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, millis: u32) {
+        let locked_pos = {
+            let area = GameState::area_state();
+            let area = area.borrow();
+            self.camera
+                .locked_entity_moved(&area.area.area.id)
+                .map(|(x, y)| (x, y, area.area.width, area.area.height))
         };
 
-        let (sign_x, sign_y) = (
-            (self.scroll.x() - dest_x).signum(),
-            (self.scroll.y() - dest_y).signum(),
-        );
-
-        let speed = Config::scroll_speed() * 4.0 * millis as f32 / 33.3;
-        let (speed_x, speed_y) = (speed / self.scale.0, speed / self.scale.1);
-
-        let (cur_x, cur_y) = (self.scroll.x(), self.scroll.y());
-
-        let (dir_x, dir_y) = (cur_x - dest_x, cur_y - dest_y);
-
-        let mag = dir_y.hypot(dir_x);
-        let (delta_x, delta_y) = (dir_x * speed_x / mag, dir_y * speed_y / mag);
+        if let Some((x, y, width, height)) = locked_pos {
+            self.delayed_scroll_to_point(x, y, width, height, &widget.borrow());
+        }
 
-        self.scroll.change(delta_x, delta_y);
+        let zoom = GameState::user_zoom();
+        self.camera.update(&mut self.scroll, self.scale, zoom, millis);
 
-        if (self.scroll.x() - dest_x).signum() != sign_x
-            || (self.scroll.y() - dest_y).signum() != sign_y
-        {
-            self.scroll.set(dest_x, dest_y);
-            self.scroll_target = None;
-        }
+        self.action_queue.update(widget);
     }
 
     fn layout(&mut self, widget: &mut Widget) {
@@ -587,7 +1010,24 @@ impl WidgetKind for AreaView {
             self.targeter_tile = ResourceSet::image(image_id);
         }
 
+        if let Some(image_id) = theme.custom.get("debug_tile") {
+            self.debug_tile = ResourceSet::image(image_id);
+        }
+
+        if let Some(image_id) = theme.custom.get("highlight_tile") {
+            self.highlight_tile = ResourceSet::image(image_id);
+        }
+        self.highlight_text_scale = theme.get_custom_or_default("highlight_text_scale", 0.8);
+        self.highlight_colors = [
+            theme.get_custom_or_default("highlight_prop_color", color::YELLOW),
+            theme.get_custom_or_default("highlight_transition_color", color::CYAN),
+            theme.get_custom_or_default("highlight_npc_color", color::GREEN),
+        ];
+        self.debug_text_scale = theme.get_custom_or_default("debug_text_scale", 6.0);
+
         self.entity_see_through_alpha = theme.get_custom_or_default("entity_see_through_alpha", 0.2);
+        self.over_layer_alpha = theme.get_custom_or_default("over_layer_alpha", 0.0);
+        self.over_layer_fade_millis = theme.get_custom_or_default("over_layer_fade_millis", 300);
         self.feedback_text_params.scale = theme.get_custom_or_default("feedback_text_scale", 1.0);
         self.feedback_text_params.ap_scale =
             theme.get_custom_or_default("ap_hover_text_scale", 1.0);
@@ -727,6 +1167,10 @@ impl WidgetKind for AreaView {
             self.center_scroll_on(&entity, state.area.width, state.area.height, widget)
         }
 
+        if state.take_terrain_changed() {
+            self.cache_invalid = true;
+        }
+
         if self.cache_invalid {
             self.cache_textures(renderer, &mut state);
         }
@@ -769,7 +1213,14 @@ impl WidgetKind for AreaView {
         let rules = Module::rules();
         let mgr = GameState::turn_manager();
         let time = mgr.borrow().current_time();
-        let area_color = rules.get_area_color(state.area.area.location_kind, time);
+        let mut area_color = rules.get_area_color(state.area.area.location_kind, time);
+        if state.area.area.location_kind == LocationKind::Outdoors {
+            let light = GameState::weather().light(mgr.borrow().total_elapsed_millis());
+            area_color.r *= light.r;
+            area_color.g *= light.g;
+            area_color.b *= light.b;
+            area_color.a *= light.a;
+        }
 
         let scale = Scale {
             x: scale_x,
@@ -844,13 +1295,42 @@ impl WidgetKind for AreaView {
             }
         }
 
-        self.draw_entities_props(renderer, scale, area_color, widget, &state, millis);
+        self.draw_entities_props(renderer, scale, area_color, widget, &state, millis, true);
         let offset = Offset {
             x: p.x as f32 - self.scroll.x(),
             y: p.y as f32 - self.scroll.y(),
         };
         GameState::draw_above_entities(renderer, offset, scale, millis);
-        self.draw_layer(renderer, scale, widget, AERIAL_LAYER_ID, area_color);
+
+        let party_points = GameState::party()
+            .iter()
+            .flat_map(|member| member.borrow().location_points().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        self.over_layers.update(
+            party_points.into_iter(),
+            millis,
+            self.over_layer_alpha,
+            self.over_layer_fade_millis,
+        );
+        let (min, max) = self.visible_tile_bounds(widget);
+        self.over_layers.draw(renderer, offset, scale, area_color, min, max);
+
+        if self.highlight_interactables {
+            self.draw_interactables(renderer, &state, widget, offset, scale, millis);
+        }
+
+        if self.debug_overlay {
+            self.draw_debug_overlay(renderer, &state, offset, scale, millis);
+            self.draw_texture_stats(renderer, widget);
+        }
+
+        if let Some(status) = DebugTime::status() {
+            self.draw_debug_time_status(renderer, widget, &status);
+        }
+
+        if let Some(reason) = AutoPause::reason() {
+            self.draw_pause_status(renderer, widget, reason.description());
+        }
 
         if let Some(hover) = self.overlay_handler.hover_sprite() {
             let rect = Rect {
@@ -890,7 +1370,7 @@ impl WidgetKind for AreaView {
 
         let color = Color::new(area_color.r, area_color.g, area_color.b,
             self.entity_see_through_alpha * area_color.a);
-        self.draw_entities_props(renderer, scale, color, widget, &state, millis);
+        self.draw_entities_props(renderer, scale, color, widget, &state, millis, false);
 
         if Config::debug().limit_line_of_sight {
             self.draw_layer(renderer, scale, widget, VISIBILITY_TEX_ID, color::WHITE);
@@ -925,7 +1405,7 @@ impl WidgetKind for AreaView {
         if let Some(targeter) = targeter {
             match kind {
                 ClickKind::Primary => targeter.borrow_mut().on_activate(),
-                ClickKind::Secondary => targeter.borrow_mut().on_cancel(),
+                ClickKind::Secondary => targeter.borrow_mut().on_cancel_last(),
                 _ => (),
             }
         } else {
@@ -934,10 +1414,17 @@ impl WidgetKind for AreaView {
                 ClickKind::Primary => self
                     .overlay_handler
                     .handle_left_click(widget, self.scale, scroll),
+                ClickKind::Secondary => {
+                    self.action_queue.clear();
+                    GameState::stop_party_moves();
+                    false
+                }
                 _ => false,
             };
 
-            if fire_action {
+            if fire_action && self.action_queue.is_held() {
+                self.action_queue.push(x, y);
+            } else if fire_action {
                 let mut action = action_kind::get_action(x, y);
                 let clear_mouse_state = action.fire_action(widget);
 
@@ -0,0 +1,148 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use sulis_core::util::gen_rand;
+use sulis_module::{Banter, Module};
+use sulis_state::{area_feedback_text::ColorKind, AreaFeedbackText, EntityState, GameState};
+
+use crate::trigger_activator::is_match;
+
+const LINE_BASE_MILLIS: u32 = 2000;
+const LINE_MILLIS_PER_CHAR: u32 = 40;
+
+/// Starts companion banters while the party is exploring, checking at most
+/// once per round, and shows the lines of the current banter over each
+/// speaker in turn.
+#[derive(Default)]
+pub struct BanterScheduler {
+    last_check_round: Option<u32>,
+    lines: VecDeque<(Rc<RefCell<EntityState>>, String)>,
+    line_millis_left: u32,
+}
+
+impl BanterScheduler {
+    pub fn update(&mut self, has_modal: bool, millis: u32) {
+        if GameState::is_combat_active() {
+            self.lines.clear();
+            return;
+        }
+
+        if !self.lines.is_empty() {
+            self.update_lines(millis);
+            return;
+        }
+
+        if has_modal {
+            return;
+        }
+
+        let round = GameState::turn_manager().borrow().current_round();
+        if self.last_check_round == Some(round) {
+            return;
+        }
+        self.last_check_round = Some(round);
+
+        if !GameState::banters().is_ready(round) {
+            return;
+        }
+
+        self.start_banter(round);
+    }
+
+    fn update_lines(&mut self, millis: u32) {
+        if self.line_millis_left > millis {
+            self.line_millis_left -= millis;
+            return;
+        }
+
+        let (speaker, text) = match self.lines.pop_front() {
+            None => return,
+            Some(line) => line,
+        };
+
+        if speaker.borrow().actor.is_dead() {
+            self.lines.clear();
+            return;
+        }
+
+        let area = GameState::area_state();
+        let mut feedback = AreaFeedbackText::with_target(&speaker.borrow(), &area.borrow());
+        self.line_millis_left = LINE_BASE_MILLIS + LINE_MILLIS_PER_CHAR * text.len() as u32;
+        feedback.add_entry(text, ColorKind::Info);
+        area.borrow_mut().add_feedback_text(feedback);
+    }
+
+    fn start_banter(&mut self, round: u32) {
+        let pc = GameState::player();
+        let area_id = GameState::area_state().borrow().area.area.id.to_string();
+        let state = GameState::banters();
+
+        let mut candidates: Vec<_> = Module::all_banters()
+            .into_iter()
+            .filter(|banter| banter.allowed_in_area(&area_id))
+            .filter(|banter| state.is_available(banter, round))
+            .filter(|banter| is_match(&banter.to_view, &pc, &pc))
+            .filter_map(|banter| route_speakers(&banter, &pc).map(|lines| (banter, lines)))
+            .collect();
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        let index = gen_rand(0, candidates.len());
+        let (banter, lines) = candidates.swap_remove(index);
+        info!("Starting banter '{}'", banter.id);
+        GameState::set_banter_played(&banter.id);
+        self.lines = lines;
+        self.line_millis_left = 0;
+    }
+}
+
+/// Finds the party member speaking each line of the banter.  Returns None
+/// if any speaker is not currently an active party member
+fn route_speakers(
+    banter: &Banter,
+    pc: &Rc<RefCell<EntityState>>,
+) -> Option<VecDeque<(Rc<RefCell<EntityState>>, String)>> {
+    if banter.lines.is_empty() {
+        return None;
+    }
+
+    let party = GameState::party();
+    let mut lines = VecDeque::new();
+    for line in banter.lines.iter() {
+        let speaker = if line.speaker == "player" {
+            Rc::clone(pc)
+        } else {
+            let member = party
+                .iter()
+                .find(|member| member.borrow().actor.actor.id == line.speaker)?;
+            Rc::clone(member)
+        };
+
+        if speaker.borrow().actor.is_dead() {
+            return None;
+        }
+
+        lines.push_back((speaker, line.text.to_string()));
+    }
+
+    Some(lines)
+}
@@ -44,7 +44,7 @@ impl WidgetKind for BasicMouseover {
 
     fn layout(&mut self, widget: &mut Widget) {
         widget.state.clear_text_args();
-        widget.state.add_text_arg("name", &self.text);
+        widget.state.add_display_text_arg("name", &self.text);
 
         self.text_area.borrow_mut().layout(widget);
     }
@@ -0,0 +1,212 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+//! Binds HUD widgets to values read from an entity's actor state.  A widget
+//! declares the `ActorSource` it displays, and the binding registers the
+//! needed change listeners, following the selected party member if asked to,
+//! and calls the widget's update function only when the bound value changes.
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use sulis_core::ui::Widget;
+use sulis_state::{ActorState, ChangeListener, EntityState, GameState};
+
+/// A typed value read from an actor that widgets may bind to
+pub trait ActorSource: 'static {
+    type Value: PartialEq + 'static;
+
+    /// Distinguishes listeners for different sources bound by the same widget
+    const NAME: &'static str;
+
+    fn read(actor: &ActorState) -> Self::Value;
+}
+
+/// The current action points
+pub struct Ap;
+
+impl ActorSource for Ap {
+    type Value = u32;
+    const NAME: &'static str = "ap";
+
+    fn read(actor: &ActorState) -> u32 {
+        actor.ap()
+    }
+}
+
+pub enum BindTarget {
+    /// A specific entity, for the lifetime of the widget
+    Entity(Rc<RefCell<EntityState>>),
+
+    /// The first selected party member, switching entities as the selection
+    /// changes
+    Selected,
+}
+
+type UpdateFn<S> =
+    dyn Fn(&Rc<RefCell<Widget>>, &Rc<RefCell<EntityState>>, &<S as ActorSource>::Value);
+
+struct Binding<S: ActorSource> {
+    id: String,
+    widget: Rc<RefCell<Widget>>,
+    entity: RefCell<Weak<RefCell<EntityState>>>,
+    last: RefCell<Option<S::Value>>,
+    update: Box<UpdateFn<S>>,
+}
+
+impl<S: ActorSource> Binding<S> {
+    /// Moves the actor listener for this binding to `entity`
+    fn attach(binding: &Rc<Binding<S>>, entity: &Rc<RefCell<EntityState>>) {
+        if let Some(old) = binding.entity.borrow().upgrade() {
+            if !Rc::ptr_eq(&old, entity) {
+                old.borrow_mut().actor.listeners.remove(&binding.id);
+            }
+        }
+        binding.entity.replace(Rc::downgrade(entity));
+
+        let binding_ref = Rc::clone(binding);
+        entity.borrow_mut().actor.listeners.add(ChangeListener::new(
+            &binding.id,
+            Box::new(move |actor| binding_ref.changed(actor, false)),
+        ));
+    }
+
+    fn changed(&self, actor: &ActorState, force: bool) {
+        let value = S::read(actor);
+        if !force && self.last.borrow().as_ref() == Some(&value) {
+            return;
+        }
+
+        let entity = match self.entity.borrow().upgrade() {
+            None => return,
+            Some(entity) => entity,
+        };
+
+        (self.update)(&self.widget, &entity, &value);
+        self.last.replace(Some(value));
+    }
+}
+
+/// Binds `widget` to the value of `S` for the `target` entity.  `update` is
+/// called with the widget, the bound entity, and the new value each time the
+/// value changes, and when a `Selected` target switches entities.  The entity
+/// may be mutably borrowed when `update` is called, so `update` should use
+/// the passed value rather than borrowing the entity.  `id` must be unique
+/// among widgets binding the same source, and is usually the widget name.
+pub fn bind<S: ActorSource>(
+    id: &str,
+    widget: &Rc<RefCell<Widget>>,
+    target: BindTarget,
+    update: impl Fn(&Rc<RefCell<Widget>>, &Rc<RefCell<EntityState>>, &S::Value) + 'static,
+) {
+    let id = format!("{}_{}", id, S::NAME);
+    let entity = match target {
+        BindTarget::Entity(ref entity) => Rc::clone(entity),
+        BindTarget::Selected => match GameState::selected().first() {
+            None => GameState::player(),
+            Some(entity) => Rc::clone(entity),
+        },
+    };
+
+    let binding = Rc::new(Binding::<S> {
+        id: id.clone(),
+        widget: Rc::clone(widget),
+        entity: RefCell::new(Weak::new()),
+        last: RefCell::new(Some(S::read(&entity.borrow().actor))),
+        update: Box::new(update),
+    });
+    Binding::attach(&binding, &entity);
+
+    if let BindTarget::Selected = target {
+        GameState::add_party_listener(ChangeListener::new(
+            &id,
+            Box::new(move |entity| {
+                let entity = match entity {
+                    None => return,
+                    Some(entity) => entity,
+                };
+                Binding::attach(&binding, entity);
+                binding.changed(&entity.borrow().actor, true);
+            }),
+        ));
+    }
+}
+
+type ChangeFn = dyn Fn(&Rc<RefCell<EntityState>>);
+
+struct Watch {
+    id: String,
+    entity: RefCell<Weak<RefCell<EntityState>>>,
+    on_change: Box<ChangeFn>,
+}
+
+impl Watch {
+    fn attach(watch: &Rc<Watch>, entity: &Rc<RefCell<EntityState>>) {
+        if let Some(old) = watch.entity.borrow().upgrade() {
+            if !Rc::ptr_eq(&old, entity) {
+                old.borrow_mut().actor.listeners.remove(&watch.id);
+            }
+        }
+        watch.entity.replace(Rc::downgrade(entity));
+
+        let watch_ref = Rc::clone(watch);
+        entity.borrow_mut().actor.listeners.add(ChangeListener::new(
+            &watch.id,
+            Box::new(move |_| {
+                if let Some(entity) = watch_ref.entity.borrow().upgrade() {
+                    (watch_ref.on_change)(&entity);
+                }
+            }),
+        ));
+    }
+}
+
+/// Calls `on_change` with the `target` entity each time any part of its actor
+/// state changes, rather than only when a bound value does.  Unlike `bind`, it
+/// is not called when a `Selected` target switches entities.  The same borrow
+/// rules as for `bind` apply.
+pub fn on_change(
+    id: &str,
+    target: BindTarget,
+    on_change: impl Fn(&Rc<RefCell<EntityState>>) + 'static,
+) {
+    let id = format!("{}_changed", id);
+    let entity = match target {
+        BindTarget::Entity(ref entity) => Rc::clone(entity),
+        BindTarget::Selected => match GameState::selected().first() {
+            None => GameState::player(),
+            Some(entity) => Rc::clone(entity),
+        },
+    };
+
+    let watch = Rc::new(Watch {
+        id: id.clone(),
+        entity: RefCell::new(Weak::new()),
+        on_change: Box::new(on_change),
+    });
+    Watch::attach(&watch, &entity);
+
+    if let BindTarget::Selected = target {
+        GameState::add_party_listener(ChangeListener::new(
+            &id,
+            Box::new(move |entity| {
+                if let Some(entity) = entity {
+                    Watch::attach(&watch, entity);
+                }
+            }),
+        ));
+    }
+}
@@ -18,7 +18,9 @@ use std::fmt::Display;
 
 use sulis_core::ui::WidgetState;
 use sulis_module::bonus::{AttackBuilder, AttackKindBuilder, Contingent};
-use sulis_module::{Armor, Bonus, BonusList, DamageKind, Module, PrereqList};
+use sulis_module::{
+    Armor, ArmorKind, Bonus, BonusList, DamageKind, ItemSet, Module, PrereqList,
+};
 
 pub fn format_bonus_or_penalty(amount: i32) -> String {
     if amount >= 0 {
@@ -35,6 +37,14 @@ pub fn add_attack_text_args(attack: &AttackBuilder, widget_state: &mut WidgetSta
         widget_state.add_text_arg("armor_penetration", &attack.damage.ap.to_string());
     }
     add_if_present(widget_state, "damage_kind", attack.damage.kind);
+    if let Some(ap) = attack.ap {
+        let ap = Module::rules().to_display_ap(ap as i32);
+        widget_state.add_text_arg("attack_ap", &ap.to_string());
+    }
+    add_if_present(widget_state, "attack_crit_range", attack.crit_range);
+    if let Some(crit_multiplier) = attack.crit_multiplier {
+        widget_state.add_text_arg("attack_crit_multiplier", &format!("{crit_multiplier:.2}"));
+    }
 
     match attack.kind {
         AttackKindBuilder::Melee { reach } => {
@@ -235,6 +245,34 @@ fn add_bonus(
         FlankedImmunity => add(state, "flanked_immunity", true),
         SneakAttackImmunity => add(state, "sneak_attack_immunity", true),
         CritImmunity => add(state, "crit_immunity", true),
+        WaterWalking => add(state, "water_walking", true),
+    }
+}
+
+pub fn add_armor_category_text_args(kind: ArmorKind, state: &mut WidgetState) {
+    let rules = Module::rules();
+    let category = rules.armor_category(kind);
+    add(state, "armor_category", format!("{kind:?}"));
+    if let Some(max_dex) = category.max_dex_bonus {
+        add(state, "armor_max_dex", max_dex);
+    }
+    if category.movement_ap > 0 && rules.movement_ap > 0 {
+        add(state, "armor_movement_ap", category.movement_ap * 100 / rules.movement_ap);
+    }
+}
+
+/// Adds the name and tiers of the item `set`, where `owned` and `equipped`
+/// are the number of distinct set pieces owned and equipped by the viewer
+pub fn add_item_set_text_args(set: &ItemSet, owned: u32, equipped: u32, state: &mut WidgetState) {
+    add(state, "item_set", &set.name);
+    add(state, "item_set_owned", owned);
+    add(state, "item_set_equipped", equipped);
+    for (index, tier) in set.tiers.iter().enumerate() {
+        add(state, &format!("item_set_tier_{index}"), &tier.description);
+        add(state, &format!("item_set_tier_{index}_pieces"), tier.pieces);
+        if tier.pieces <= equipped {
+            add(state, &format!("item_set_tier_{index}_active"), true);
+        }
     }
 }
 
@@ -258,7 +296,7 @@ pub fn add_prereq_text_args(prereqs: &PrereqList, state: &mut WidgetState) {
             }
             Some(class) => class,
         };
-        state.add_text_arg(&format!("prereq_class_{index}"), &class.name);
+        state.add_display_text_arg(&format!("prereq_class_{index}"), &class.name);
         state.add_text_arg(&format!("prereq_level_{index}"), &level.to_string());
     }
 
@@ -272,7 +310,7 @@ pub fn add_prereq_text_args(prereqs: &PrereqList, state: &mut WidgetState) {
                 warn!("Invalid race '{}' in prereq list", race_id);
             }
             Some(race) => {
-                state.add_text_arg("prereq_race", &race.name);
+                state.add_display_text_arg("prereq_race", &race.name);
             }
         }
     }
@@ -286,7 +324,7 @@ pub fn add_prereq_text_args(prereqs: &PrereqList, state: &mut WidgetState) {
             Some(ability) => ability,
         };
 
-        state.add_text_arg(&format!("prereq_ability_{index}"), &ability.name);
+        state.add_display_text_arg(&format!("prereq_ability_{index}"), &ability.name);
     }
 }
 
@@ -0,0 +1,194 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::config::Config;
+use sulis_core::ui::Scrollable;
+use sulis_state::EntityState;
+
+use crate::ScreenShake;
+
+enum Motion {
+    // move at a multiple of the configured scroll speed
+    Speed,
+    // move from the start position to the destination over a fixed time
+    Timed {
+        start: (f32, f32),
+        elapsed: u32,
+        duration: u32,
+    },
+}
+
+struct Target {
+    dest: (f32, f32),
+    motion: Motion,
+}
+
+/// Handles movement of the area view that is not directly driven by the
+/// player - smooth scrolling to a point, following a locked entity, and
+/// screen shakes.  All positions are in scroll coordinates.
+#[derive(Default)]
+pub struct Camera {
+    target: Option<Target>,
+    locked: Option<Rc<RefCell<EntityState>>>,
+    locked_pos: Option<(f32, f32)>,
+    shake: Option<ScreenShake>,
+}
+
+impl Camera {
+    pub fn scroll_to(&mut self, dest: (f32, f32)) {
+        self.target = Some(Target {
+            dest,
+            motion: Motion::Speed,
+        });
+    }
+
+    pub fn scroll_over(&mut self, start: (f32, f32), dest: (f32, f32), millis: u32) {
+        if millis == 0 {
+            self.scroll_to(dest);
+            return;
+        }
+
+        self.target = Some(Target {
+            dest,
+            motion: Motion::Timed {
+                start,
+                elapsed: 0,
+                duration: millis,
+            },
+        });
+    }
+
+    pub fn lock(&mut self, entity: Rc<RefCell<EntityState>>) {
+        self.locked = Some(entity);
+        self.locked_pos = None;
+    }
+
+    pub fn unlock(&mut self) {
+        self.locked = None;
+        self.locked_pos = None;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked.is_some()
+    }
+
+    pub fn shake(&mut self, shake: ScreenShake) {
+        self.shake = Some(shake);
+    }
+
+    /// Returns the center of the locked entity, if it is in the specified
+    /// area and has moved since the last call.  The lock is released if the
+    /// entity is no longer valid
+    pub fn locked_entity_moved(&mut self, area_id: &str) -> Option<(f32, f32)> {
+        let pos = {
+            let entity = self.locked.as_ref()?.borrow();
+            if entity.actor.is_dead() {
+                None
+            } else if entity.location.area_id != area_id {
+                return None;
+            } else {
+                let x = entity.location.x as f32 + entity.sub_pos.0;
+                let y = entity.location.y as f32 + entity.sub_pos.1;
+                Some((
+                    x + entity.size.width as f32 / 2.0,
+                    y + entity.size.height as f32 / 2.0,
+                ))
+            }
+        };
+
+        let pos = match pos {
+            None => {
+                self.unlock();
+                return None;
+            }
+            Some(pos) => pos,
+        };
+
+        if self.locked_pos == Some(pos) {
+            return None;
+        }
+
+        self.locked_pos = Some(pos);
+        Some(pos)
+    }
+
+    pub fn update(&mut self, scroll: &mut Scrollable, scale: (f32, f32), zoom: f32, millis: u32) {
+        self.update_target(scroll, scale, millis);
+
+        if let Some(shake) = self.shake.as_mut() {
+            let result = shake.shake(millis);
+
+            if let Some(offset) = result.scroll {
+                scroll.change(offset.x / zoom, offset.y / zoom);
+            }
+
+            if result.done {
+                self.shake = None;
+            }
+        }
+    }
+
+    fn update_target(&mut self, scroll: &mut Scrollable, scale: (f32, f32), millis: u32) {
+        let target = match self.target.as_mut() {
+            None => return,
+            Some(target) => target,
+        };
+        let (dest_x, dest_y) = target.dest;
+
+        let done = match target.motion {
+            Motion::Timed {
+                start,
+                ref mut elapsed,
+                duration,
+            } => {
+                *elapsed += millis;
+                let frac = (*elapsed as f32 / duration as f32).min(1.0);
+                // ease in and out of the motion
+                let frac = frac * frac * (3.0 - 2.0 * frac);
+                let x = start.0 + (dest_x - start.0) * frac;
+                let y = start.1 + (dest_y - start.1) * frac;
+                scroll.set(x, y);
+                *elapsed >= duration
+            }
+            Motion::Speed => {
+                let (cur_x, cur_y) = (scroll.x(), scroll.y());
+                let (dir_x, dir_y) = (cur_x - dest_x, cur_y - dest_y);
+                let mag = dir_y.hypot(dir_x);
+
+                let speed = Config::scroll_speed() * 4.0 * millis as f32 / 33.3;
+                let (speed_x, speed_y) = (speed / scale.0, speed / scale.1);
+
+                if mag <= speed_x.min(speed_y) {
+                    true
+                } else {
+                    let (sign_x, sign_y) = (dir_x.signum(), dir_y.signum());
+                    scroll.change(dir_x * speed_x / mag, dir_y * speed_y / mag);
+
+                    (scroll.x() - dest_x).signum() != sign_x
+                        || (scroll.y() - dest_y).signum() != sign_y
+                }
+            }
+        };
+
+        if done {
+            scroll.set(dest_x, dest_y);
+            self.target = None;
+        }
+    }
+}
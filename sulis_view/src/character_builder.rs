@@ -89,6 +89,8 @@ pub struct CharacterBuilder {
     pub hue: Option<f32>,
     pub skin_color: Option<Color>,
     pub hair_color: Option<Color>,
+    pub custom_images: HashMap<String, String>,
+    pub custom_colors: HashMap<String, Color>,
     pub portrait: Option<String>,
 
     pub abilities: Vec<Rc<Ability>>,
@@ -154,6 +156,8 @@ impl CharacterBuilder {
             hue: None,
             skin_color: None,
             hair_color: None,
+            custom_images: HashMap::new(),
+            custom_colors: HashMap::new(),
             attributes: None,
             inventory: None,
             portrait: None,
@@ -321,12 +325,16 @@ impl BuilderSet for CharacterCreator {
             hue: builder.hue,
             hair_color: builder.hair_color,
             skin_color: builder.skin_color,
+            custom_images: builder.custom_images.clone(),
+            custom_colors: builder.custom_colors.clone(),
             inventory,
             levels,
             xp: None,
             reward: None,
             abilities,
+            approval_abilities: Vec::new(),
             ai: None,
+            script: None,
         };
 
         if let Err(e) = write_character_to_file(&filename, &actor) {
@@ -158,7 +158,7 @@ impl WidgetKind for AttributeSelectorPane {
             kit_button
                 .borrow_mut()
                 .state
-                .add_text_arg("name", &kit.name);
+                .add_display_text_arg("name", &kit.name);
             if let Some(selected_index) = self.selected_kit {
                 kit_button
                     .borrow_mut()
@@ -190,11 +190,11 @@ impl WidgetKind for AttributeSelectorPane {
         kit_area
             .borrow_mut()
             .state
-            .add_text_arg("description", &selected_kit.description);
+            .add_display_text_arg("description", &selected_kit.description);
         kit_area
             .borrow_mut()
             .state
-            .add_text_arg("name", &selected_kit.name);
+            .add_display_text_arg("name", &selected_kit.name);
         children.push(kit_area);
 
         let starting_abilities = Widget::empty("starting_abilities");
@@ -108,13 +108,13 @@ impl WidgetKind for ClassSelectorPane {
             let class_button = Widget::with_theme(Button::empty(), "class_button");
             {
                 let state = &mut class_button.borrow_mut().state;
-                state.add_text_arg("name", &class.name);
+                state.add_display_text_arg("name", &class.name);
                 state.add_text_arg("level", &format!("{}", self.level));
             }
             class_button
                 .borrow_mut()
                 .state
-                .add_text_arg("name", &class.name);
+                .add_display_text_arg("name", &class.name);
             if let Some(ref selected_class) = self.selected_class {
                 class_button
                     .borrow_mut()
@@ -242,7 +242,7 @@ fn build_upgrades_data(state: &mut WidgetState, class: &Class, level: u32) {
     }
 
     for (i, list) in class.ability_choices(level).iter().enumerate() {
-        state.add_text_arg(&format!("choice_{i}_name"), &list.name);
+        state.add_display_text_arg(&format!("choice_{i}_name"), &list.name);
     }
 }
 
@@ -44,6 +44,8 @@ pub struct CosmeticSelectorPane {
     beard_index: Option<usize>,
     hair_color: Option<Color>,
     skin_color: Option<Color>,
+    custom_indices: HashMap<String, usize>,
+    custom_colors: HashMap<String, Color>,
     hue: Option<f32>,
     portrait: Option<Rc<dyn Image>>,
 
@@ -63,6 +65,8 @@ impl CosmeticSelectorPane {
             hue: Some(0.0),
             hair_color: None,
             skin_color: None,
+            custom_indices: HashMap::new(),
+            custom_colors: HashMap::new(),
             portrait: None,
             preview,
             preview_image: None,
@@ -108,8 +112,13 @@ impl CosmeticSelectorPane {
             });
         }
 
-        let images_list =
-            image_layers.get_list_with(self.sex, race, self.hair_color, self.skin_color, insert);
+        let appearance = race.appearance(
+            self.hair_color,
+            self.skin_color,
+            &self.build_custom_images(),
+            &self.custom_colors,
+        );
+        let images_list = image_layers.get_list_with(self.sex, race, &appearance, insert);
         self.preview_image = Some(Rc::new(LayeredImage::new(images_list, self.hue)));
     }
 
@@ -134,6 +143,54 @@ impl CosmeticSelectorPane {
         images
     }
 
+    fn build_custom_images(&self) -> HashMap<String, String> {
+        let mut images = HashMap::new();
+
+        let race = match self.race {
+            None => return images,
+            Some(ref race) => race,
+        };
+
+        for layer in race.custom_layers.iter() {
+            if let Some(index) = self.custom_indices.get(&layer.id) {
+                images.insert(layer.id.to_string(), layer.selections[*index].to_string());
+            }
+        }
+
+        images
+    }
+
+    /// Moves the selection for the custom layer with the specified index
+    /// forwards or backwards, passing through no selection for optional layers
+    fn cycle_custom_layer(&mut self, layer_index: usize, forward: bool) {
+        let race = match self.race {
+            None => return,
+            Some(ref race) => race,
+        };
+
+        let layer = &race.custom_layers[layer_index];
+        let len = layer.selections.len();
+        if len == 0 {
+            return;
+        }
+
+        let cur = self.custom_indices.get(&layer.id).copied();
+        let next = match (cur, forward) {
+            (None, true) => Some(0),
+            (None, false) => Some(len - 1),
+            (Some(index), true) if index + 1 < len => Some(index + 1),
+            (Some(index), false) if index > 0 => Some(index - 1),
+            (Some(_), true) if !layer.optional => Some(0),
+            (Some(_), false) if !layer.optional => Some(len - 1),
+            (Some(_), _) => None,
+        };
+
+        match next {
+            None => self.custom_indices.remove(&layer.id),
+            Some(index) => self.custom_indices.insert(layer.id.to_string(), index),
+        };
+    }
+
     fn set_finish_enabled(&self, widget: &Rc<RefCell<Widget>>) {
         let (_, builder) = Widget::parent_mut::<CharacterBuilder>(widget);
         builder
@@ -164,6 +221,20 @@ impl BuilderPane for CosmeticSelectorPane {
             } else {
                 self.skin_color = None;
             }
+
+            self.custom_indices.clear();
+            for layer in race.custom_layers.iter() {
+                if !layer.optional && !layer.selections.is_empty() {
+                    self.custom_indices.insert(layer.id.to_string(), 0);
+                }
+            }
+
+            self.custom_colors.clear();
+            for channel in race.color_channels.iter() {
+                if let Some(color) = channel.colors.first() {
+                    self.custom_colors.insert(channel.id.to_string(), *color);
+                }
+            }
         }
 
         self.items.clear();
@@ -189,6 +260,8 @@ impl BuilderPane for CosmeticSelectorPane {
         builder.hue = self.hue;
         builder.skin_color = self.skin_color;
         builder.hair_color = self.hair_color;
+        builder.custom_images = self.build_custom_images();
+        builder.custom_colors = self.custom_colors.clone();
         builder.portrait = self.portrait.as_ref().map(|i| i.id());
         builder.next(&widget);
     }
@@ -485,6 +558,48 @@ impl WidgetKind for CosmeticSelectorPane {
             Widget::add_child_to(&hair_color_panel, color_button);
         }
 
+        let custom_panel = Widget::empty("custom_panel");
+        for (index, layer) in race.custom_layers.iter().enumerate() {
+            let row = Widget::empty("custom_layer");
+            let label = Widget::with_theme(Label::new(&layer.name), "label");
+            let next = Widget::with_theme(Button::empty(), "next");
+            let prev = Widget::with_theme(Button::empty(), "prev");
+            for (button, forward) in [(&next, true), (&prev, false)] {
+                let mut button = button.borrow_mut();
+                button.state.set_enabled(!layer.selections.is_empty());
+                button.state.add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let (parent, pane) = Widget::parent_mut::<CosmeticSelectorPane>(widget);
+                    pane.cycle_custom_layer(index, forward);
+                    parent.borrow_mut().invalidate_children();
+                })));
+            }
+            Widget::add_children_to(&row, vec![label, next, prev]);
+            Widget::add_child_to(&custom_panel, row);
+        }
+
+        for channel in race.color_channels.iter() {
+            let row = Widget::empty("color_channel");
+            let label = Widget::with_theme(Label::new(&channel.name), "label");
+            let colors = Widget::empty("colors");
+            for color in channel.colors.iter() {
+                let color = *color;
+                let id = channel.id.to_string();
+                let color_button = Widget::with_defaults(ColorButton::new(color));
+                color_button
+                    .borrow_mut()
+                    .state
+                    .add_callback(Callback::new(Rc::new(move |widget, _| {
+                        let (parent, cosmetic_pane) =
+                            Widget::parent_mut::<CosmeticSelectorPane>(widget);
+                        cosmetic_pane.custom_colors.insert(id.to_string(), color);
+                        parent.borrow_mut().invalidate_children();
+                    })));
+                Widget::add_child_to(&colors, color_button);
+            }
+            Widget::add_children_to(&row, vec![label, colors]);
+            Widget::add_child_to(&custom_panel, row);
+        }
+
         let portrait_label = Widget::with_theme(Label::empty(), "portrait_label");
 
         let portrait_button = Widget::with_theme(Button::empty(), "portrait_button");
@@ -563,6 +678,7 @@ impl WidgetKind for CosmeticSelectorPane {
             hair_color_panel,
             portrait_button,
             portrait_label,
+            custom_panel,
         ]
     }
 }
@@ -90,7 +90,7 @@ impl WidgetKind for LevelUpFinishPane {
 
         {
             let state = &mut details.borrow_mut().state;
-            state.add_text_arg("class", &class.name);
+            state.add_display_text_arg("class", &class.name);
             state.add_text_arg("level", &format!("{}", self.level));
         }
 
@@ -91,7 +91,7 @@ impl WidgetKind for RaceSelectorPane {
             race_button
                 .borrow_mut()
                 .state
-                .add_text_arg("name", &race.name);
+                .add_display_text_arg("name", &race.name);
             if let Some(ref selected_race) = self.selected_race {
                 race_button
                     .borrow_mut()
@@ -254,12 +254,16 @@ fn export_character(pc: &ActorState) {
         hue: pc.actor.hue,
         hair_color: pc.actor.hair_color,
         skin_color: pc.actor.skin_color,
+        custom_images: pc.actor.custom_images.clone(),
+        custom_colors: pc.actor.custom_colors.clone(),
         abilities,
         levels,
         inventory,
         xp: Some(pc.xp()),
         reward: None,
+        approval_abilities: Vec::new(),
         ai: None,
+        script: None,
     };
 
     if let Err(e) = write_character_to_file(&filename, &actor) {
@@ -329,7 +333,7 @@ pub fn create_abilities_pane(pc: &ActorState, show_passive: bool) -> Rc<RefCell<
         {
             let state = &mut button.borrow_mut().state;
             state.add_text_arg("icon", &ability.icon.id());
-            state.add_text_arg("name", &ability.name);
+            state.add_display_text_arg("name", &ability.name);
         }
 
         let ability_ref = Rc::clone(ability);
@@ -386,7 +390,7 @@ pub fn create_effects_pane(pc: &mut ActorState) -> Rc<RefCell<Widget>> {
 }
 
 fn add_effect_text_args(effect: &Effect, widget_state: &mut WidgetState) {
-    widget_state.add_text_arg("name", effect.name());
+    widget_state.add_display_text_arg("name", effect.name());
 
     if effect.is_aura() {
         widget_state.add_text_arg("is_aura", "true");
@@ -440,8 +444,8 @@ pub fn create_details_text_box(pc: &ActorState, is_pc: bool) -> Rc<RefCell<Widge
         let state = &mut details.borrow_mut().state;
         let stats = &pc.stats;
 
-        state.add_text_arg("name", &pc.actor.name);
-        state.add_text_arg("race", &pc.actor.race.name);
+        state.add_display_text_arg("name", &pc.actor.name);
+        state.add_display_text_arg("race", &pc.actor.race.name);
         state.add_text_arg("sex", &pc.actor.sex.to_string());
 
         if let Some(ref portrait) = pc.actor.portrait {
@@ -456,7 +460,7 @@ pub fn create_details_text_box(pc: &ActorState, is_pc: bool) -> Rc<RefCell<Widge
         }
 
         for (index, &(ref class, level)) in pc.actor.levels.iter().enumerate() {
-            state.add_text_arg(&format!("class_{index}"), &class.name);
+            state.add_display_text_arg(&format!("class_{index}"), &class.name);
             state.add_text_arg(&format!("level_{index}"), &level.to_string());
         }
 
@@ -66,8 +66,8 @@ impl WidgetKind for ClassPane {
         let details = Widget::with_theme(TextArea::empty(), "details");
         {
             let state = &mut details.borrow_mut().state;
-            state.add_text_arg("name", &class.name);
-            state.add_text_arg("description", &class.description);
+            state.add_display_text_arg("name", &class.name);
+            state.add_display_text_arg("description", &class.description);
         }
         vec![details]
     }
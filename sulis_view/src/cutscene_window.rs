@@ -85,7 +85,7 @@ impl WidgetKind for CutsceneWindow {
 
         let text_area = Widget::with_defaults(TextArea::empty());
 
-        text_area.borrow_mut().state.add_text_arg("0", &frame.text);
+        text_area.borrow_mut().state.add_display_text_arg("0", &frame.text);
 
         vec![close, text_area, next_button]
     }
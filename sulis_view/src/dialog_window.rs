@@ -20,7 +20,7 @@ use std::rc::Rc;
 
 use sulis_core::io::{event, InputActionKind};
 use sulis_core::ui::{theme, Widget, WidgetKind};
-use sulis_core::widgets::TextArea;
+use sulis_core::widgets::{Label, TextArea};
 use sulis_module::{conversation::Response, Conversation, OnTrigger};
 use sulis_state::{
     area_feedback_text::ColorKind, script::entity_with_id, AreaFeedbackText, ChangeListener,
@@ -28,13 +28,14 @@ use sulis_state::{
 };
 
 use crate::trigger_activator::{activate, is_match, scroll_view};
-use crate::{AreaView, RootView};
+use crate::{AreaView, GeneratedPortrait, RootView};
 
 pub const NAME: &str = "dialog_window";
 
 pub struct DialogWindow {
     pc: Rc<RefCell<EntityState>>,
     entity: Rc<RefCell<EntityState>>,
+    speaker: Rc<RefCell<EntityState>>,
     convo: Rc<Conversation>,
     cur_node: String,
 
@@ -52,11 +53,32 @@ impl DialogWindow {
         Rc::new(RefCell::new(DialogWindow {
             pc: Rc::clone(pc),
             entity: Rc::clone(entity),
+            speaker: Rc::clone(entity),
             convo,
             node: TextArea::empty(),
             cur_node,
         }))
     }
+
+    /// Moves to the first interjection of the current node whose party member
+    /// is present and whose conditions match, routing the node to that speaker
+    fn check_interjection(&mut self) {
+        let interjection =
+            find_interjection(&self.convo, &self.cur_node, &self.pc, &self.entity);
+        let (to, speaker) = match interjection {
+            None => return,
+            Some(interjection) => interjection,
+        };
+
+        let (x, y) = {
+            let loc = &speaker.borrow().location;
+            (loc.x, loc.y)
+        };
+        GameState::add_ui_callback(vec![OnTrigger::ScrollView(x, y)], &self.pc, &speaker);
+
+        self.cur_node = to;
+        self.speaker = speaker;
+    }
 }
 
 impl WidgetKind for DialogWindow {
@@ -86,6 +108,8 @@ impl WidgetKind for DialogWindow {
             .listeners
             .add(ChangeListener::invalidate(NAME, widget));
 
+        self.check_interjection();
+
         let cur_text = self.convo.text(&self.cur_node);
         let responses = self.convo.responses(&self.cur_node);
 
@@ -98,7 +122,11 @@ impl WidgetKind for DialogWindow {
             }
 
             node.add_text_arg("player_name", &self.pc.borrow().actor.actor.name);
-            node.add_text_arg("target_name", &entity.actor.actor.name);
+            node.add_display_text_arg("target_name", &entity.actor.actor.name);
+            node.add_display_text_arg("speaker_name", &self.speaker.borrow().actor.actor.name);
+            if !Rc::ptr_eq(&self.speaker, &self.entity) {
+                node.add_text_arg("switched_speaker", "true");
+            }
         }
 
         let cur_text = theme::expand_text_args(cur_text, &node_widget.borrow().state);
@@ -107,7 +135,8 @@ impl WidgetKind for DialogWindow {
             widget.borrow_mut().mark_for_removal();
 
             let area = GameState::area_state();
-            let mut feedback = AreaFeedbackText::with_target(&self.entity.borrow(), &area.borrow());
+            let mut feedback =
+                AreaFeedbackText::with_target(&self.speaker.borrow(), &area.borrow());
             feedback.add_entry(cur_text, ColorKind::Info);
             area.borrow_mut().add_feedback_text(feedback);
             return Vec::new();
@@ -135,7 +164,17 @@ impl WidgetKind for DialogWindow {
             }
         }
 
-        vec![node_widget, responses_widget]
+        let portrait = Widget::with_theme(Label::empty(), "portrait");
+        match &self.speaker.borrow().actor.actor.portrait {
+            Some(image) => portrait.borrow_mut().state.foreground = Some(Rc::clone(image)),
+            None => {
+                let generated = GeneratedPortrait::new(&self.speaker);
+                let generated = Widget::with_theme(generated, "generated_portrait");
+                Widget::add_child_to(&portrait, generated);
+            }
+        }
+
+        vec![portrait, node_widget, responses_widget]
     }
 }
 
@@ -162,16 +201,20 @@ impl ResponseButton {
         }))
     }
 
-    fn check_switch_speaker(&self, node: &str, area: &Rc<RefCell<AreaView>>) {
+    fn check_switch_speaker(
+        &self,
+        node: &str,
+        area: &Rc<RefCell<AreaView>>,
+    ) -> Option<Rc<RefCell<EntityState>>> {
         let speaker = match self.convo.switch_speaker(node) {
-            None => return,
+            None => return None,
             Some(ref speaker) => speaker,
         };
 
         let speaker = match entity_with_id(speaker.to_string()) {
             None => {
                 warn!("Attempted to switch to invalid speaker '{}'", speaker);
-                return;
+                return None;
             }
             Some(speaker) => speaker,
         };
@@ -184,6 +227,7 @@ impl ResponseButton {
         GameState::add_ui_callback(vec![cb], &self.pc, &speaker);
         area.borrow_mut()
             .set_active_entity(Some(Rc::clone(&speaker)));
+        Some(speaker)
     }
 }
 
@@ -226,7 +270,8 @@ impl WidgetKind for ResponseButton {
                 area.borrow_mut().set_active_entity(None);
             }
             Some(ref to) => {
-                self.check_switch_speaker(to, &area);
+                let speaker = self.check_switch_speaker(to, &area);
+                window.speaker = speaker.unwrap_or_else(|| Rc::clone(&window.entity));
                 window.cur_node = to.to_string();
                 parent.borrow_mut().invalidate_children()
             }
@@ -285,6 +330,39 @@ pub fn get_initial_node(
     cur_node.to_string()
 }
 
+/// Returns the node and speaking party member for the first interjection of
+/// `node` that applies, if any
+fn find_interjection(
+    convo: &Rc<Conversation>,
+    node: &str,
+    pc: &Rc<RefCell<EntityState>>,
+    entity: &Rc<RefCell<EntityState>>,
+) -> Option<(String, Rc<RefCell<EntityState>>)> {
+    let party = GameState::party();
+    for interjection in convo.interjections(node) {
+        let speaker = party
+            .iter()
+            .find(|member| member.borrow().actor.actor.id == interjection.speaker);
+        let speaker = match speaker {
+            None => continue,
+            Some(speaker) => speaker,
+        };
+
+        // don't let the target or player interject into their own conversation
+        if Rc::ptr_eq(speaker, entity) || Rc::ptr_eq(speaker, pc) {
+            continue;
+        }
+
+        if speaker.borrow().actor.is_dead() || !is_match(&interjection.to_view, pc, entity) {
+            continue;
+        }
+
+        return Some((interjection.to.to_string(), Rc::clone(speaker)));
+    }
+
+    None
+}
+
 pub fn is_viewable(
     response: &Response,
     pc: &Rc<RefCell<EntityState>>,
@@ -0,0 +1,108 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::io::InputActionKind;
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label, TextArea};
+use sulis_module::on_trigger::EpilogueSlide;
+use sulis_state::NextGameStep;
+
+use crate::RootView;
+
+pub const NAME: &str = "epilogue_window";
+
+/// Shows the slides of the campaign epilogue in turn, and then returns to the
+/// main menu
+pub struct EpilogueWindow {
+    slides: Vec<EpilogueSlide>,
+    slide_index: usize,
+}
+
+impl EpilogueWindow {
+    pub fn new(slides: Vec<EpilogueSlide>) -> Rc<RefCell<EpilogueWindow>> {
+        Rc::new(RefCell::new(EpilogueWindow {
+            slides,
+            slide_index: 0,
+        }))
+    }
+
+    fn next(&mut self, widget: &Rc<RefCell<Widget>>) {
+        self.slide_index += 1;
+        widget.borrow_mut().invalidate_children();
+    }
+}
+
+fn finish(widget: &Rc<RefCell<Widget>>) {
+    widget.borrow_mut().mark_for_removal();
+    let (_, view) = Widget::parent_mut::<RootView>(widget);
+    view.next_step = Some(NextGameStep::MainMenu);
+}
+
+impl WidgetKind for EpilogueWindow {
+    widget_kind!(NAME);
+
+    fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let slide = match self.slides.get(self.slide_index) {
+            None => {
+                finish(widget);
+                return Vec::new();
+            }
+            Some(slide) => slide,
+        };
+
+        let image = Widget::with_theme(Label::empty(), "image");
+        match slide.image {
+            None => image.borrow_mut().state.set_visible(false),
+            Some(ref id) => image.borrow_mut().state.add_text_arg("image", id),
+        }
+
+        let text = Widget::with_theme(TextArea::empty(), "text");
+        text.borrow_mut().state.add_display_text_arg("text", &slide.text);
+
+        let next_button = Widget::with_theme(Button::empty(), "next_button");
+        next_button
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, window) = Widget::parent_mut::<EpilogueWindow>(widget);
+                window.next(&parent);
+            })));
+
+        let skip_button = Widget::with_theme(Button::empty(), "skip_button");
+        skip_button
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<EpilogueWindow>(widget);
+                finish(&parent);
+            })));
+
+        vec![image, text, next_button, skip_button]
+    }
+
+    fn on_key_press(&mut self, widget: &Rc<RefCell<Widget>>, key: InputActionKind) -> bool {
+        match key {
+            InputActionKind::ActivateFocused => self.next(widget),
+            InputActionKind::Back => finish(widget),
+            _ => (),
+        }
+        true
+    }
+}
@@ -20,12 +20,16 @@ use std::rc::Rc;
 
 use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::widgets::{Button, Label, TextArea};
+use sulis_state::save_file;
+
+use crate::LoadWindow;
 
 const NAME: &str = "game_over_window";
 
 pub struct GameOverWindow {
     menu_callback: Callback,
     content_text: String,
+    show_load: bool,
 }
 
 impl GameOverWindow {
@@ -33,8 +37,14 @@ impl GameOverWindow {
         Rc::new(RefCell::new(GameOverWindow {
             menu_callback,
             content_text,
+            show_load: false,
         }))
     }
+
+    /// Adds a button to load a saved game to this window
+    pub fn show_load(&mut self) {
+        self.show_load = true;
+    }
 }
 
 impl WidgetKind for GameOverWindow {
@@ -50,6 +60,24 @@ impl WidgetKind for GameOverWindow {
             .state
             .add_callback(self.menu_callback.clone());
 
-        vec![label, exit, content]
+        let mut children = vec![label, exit, content];
+
+        if self.show_load {
+            let load = Widget::with_theme(Button::empty(), "load");
+            load.borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(|widget, _| {
+                    let root = Widget::get_root(widget);
+                    let window = Widget::with_defaults(LoadWindow::new(false));
+                    window.borrow_mut().state.set_modal(true);
+                    Widget::add_child_to(&root, window);
+                })));
+            load.borrow_mut()
+                .state
+                .set_enabled(save_file::has_available_save_files());
+            children.push(load);
+        }
+
+        children
     }
 }
@@ -0,0 +1,167 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use sulis_core::config::Config;
+use sulis_core::extern_image::ImageBuffer;
+use sulis_core::io::{DrawList, GraphicsRenderer, TextureMagFilter, TextureMinFilter};
+use sulis_core::ui::{Widget, WidgetKind};
+use sulis_core::util::{Offset, Point, Rect, Scale};
+use sulis_state::EntityState;
+
+pub const NAME: &str = "generated_portrait";
+
+/// The width and height in pixels of each generated portrait texture
+const PORTRAIT_SIZE: u32 = 128;
+
+/// The maximum number of portraits kept at once.  When all are in use, the
+/// least recently drawn portrait is replaced
+const MAX_PORTRAITS: usize = 16;
+
+const TEX_COORDS: [f32; 8] = [0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0];
+
+thread_local! {
+    static CACHE: RefCell<PortraitCache> = RefCell::new(PortraitCache::default());
+}
+
+struct CachedPortrait {
+    entity: Weak<RefCell<EntityState>>,
+    image_generation: u32,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct PortraitCache {
+    portraits: Vec<CachedPortrait>,
+    frame: u64,
+}
+
+impl PortraitCache {
+    /// Returns the texture ID holding the portrait of the specified entity,
+    /// first drawing it if it is not cached or the entity's appearance changed
+    fn texture(
+        &mut self,
+        renderer: &mut dyn GraphicsRenderer,
+        entity_ref: &Rc<RefCell<EntityState>>,
+    ) -> String {
+        self.frame += 1;
+        let entity = entity_ref.borrow();
+        let generation = entity.actor.image_generation();
+
+        let existing = self.portraits.iter().position(|portrait| {
+            portrait
+                .entity
+                .upgrade()
+                .is_some_and(|other| Rc::ptr_eq(&other, entity_ref))
+        });
+
+        let index = match existing {
+            Some(index) => index,
+            None if self.portraits.len() < MAX_PORTRAITS => {
+                self.portraits.push(CachedPortrait {
+                    entity: Weak::new(),
+                    image_generation: 0,
+                    last_used: 0,
+                });
+                self.portraits.len() - 1
+            }
+            None => {
+                let (index, _) = self
+                    .portraits
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, portrait)| portrait.last_used)
+                    .unwrap();
+                index
+            }
+        };
+
+        let texture_id = format!("{NAME}_{index}");
+        let portrait = &mut self.portraits[index];
+        portrait.last_used = self.frame;
+
+        let current = existing.is_some() && portrait.image_generation == generation;
+        if !current || !renderer.has_texture(&texture_id) {
+            draw_portrait(renderer, &texture_id, &entity);
+            portrait.entity = Rc::downgrade(entity_ref);
+            portrait.image_generation = generation;
+        }
+
+        texture_id
+    }
+}
+
+fn draw_portrait(renderer: &mut dyn GraphicsRenderer, texture_id: &str, entity: &EntityState) {
+    if renderer.has_texture(texture_id) {
+        renderer.clear_texture(texture_id);
+    } else {
+        renderer.register_texture(
+            texture_id,
+            ImageBuffer::new(PORTRAIT_SIZE, PORTRAIT_SIZE),
+            TextureMinFilter::Linear,
+            TextureMagFilter::Linear,
+        );
+    }
+
+    let (x, y, size) = entity.actor.actor.race.portrait_region;
+    let (ui_x, ui_y) = Config::ui_size();
+    let offset = Offset { x: -x, y: -y };
+    let scale = Scale {
+        x: ui_x as f32 / size,
+        y: ui_y as f32 / size,
+    };
+    entity.actor.draw_to_texture(renderer, texture_id, offset, scale);
+}
+
+/// Shows a portrait of an entity drawn from its layered image, for entities
+/// without a portrait image.  Portraits are cached as textures and redrawn
+/// when the entity's appearance changes
+pub struct GeneratedPortrait {
+    entity: Rc<RefCell<EntityState>>,
+}
+
+impl GeneratedPortrait {
+    pub fn new(entity: &Rc<RefCell<EntityState>>) -> Rc<RefCell<GeneratedPortrait>> {
+        Rc::new(RefCell::new(GeneratedPortrait {
+            entity: Rc::clone(entity),
+        }))
+    }
+}
+
+impl WidgetKind for GeneratedPortrait {
+    widget_kind!(NAME);
+
+    fn draw(
+        &mut self,
+        renderer: &mut dyn GraphicsRenderer,
+        _pixel_size: Point,
+        widget: &Widget,
+        _millis: u32,
+    ) {
+        let texture_id = CACHE.with(|cache| cache.borrow_mut().texture(renderer, &self.entity));
+
+        let rect = Rect {
+            x: widget.state.inner_left() as f32,
+            y: widget.state.inner_top() as f32,
+            w: widget.state.inner_width() as f32,
+            h: widget.state.inner_height() as f32,
+        };
+        renderer.draw(DrawList::from_texture_id(&texture_id, &TEX_COORDS, rect));
+    }
+}
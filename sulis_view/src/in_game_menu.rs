@@ -22,7 +22,7 @@ use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::widgets::{Button, ConfirmationWindow};
 use sulis_state::GameState;
 
-use crate::{LoadWindow, RootView};
+use crate::{AchievementsWindow, LoadWindow, RootView};
 
 const NAME: &str = "in_game_menu";
 
@@ -66,7 +66,7 @@ impl WidgetKind for InGameMenu {
             })));
         save.borrow_mut()
             .state
-            .set_enabled(!GameState::is_combat_active());
+            .set_enabled(GameState::can_save());
 
         let load = Widget::with_theme(Button::empty(), "load");
         load.borrow_mut()
@@ -81,6 +81,20 @@ impl WidgetKind for InGameMenu {
                 Widget::add_child_to(&root, window);
             })));
 
+        let achievements = Widget::with_theme(Button::empty(), "achievements");
+        achievements
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<InGameMenu>(widget);
+                parent.borrow_mut().mark_for_removal();
+
+                let root = Widget::get_root(widget);
+                let window = Widget::with_defaults(AchievementsWindow::new());
+                window.borrow_mut().state.set_modal(true);
+                Widget::add_child_to(&root, window);
+            })));
+
         let menu = Widget::with_theme(Button::empty(), "menu");
         let menu_cb = self.menu_callback.clone();
         menu.borrow_mut()
@@ -111,6 +125,6 @@ impl WidgetKind for InGameMenu {
                 Widget::add_child_to(&root, window);
             })));
 
-        vec![back, save, load, menu, exit]
+        vec![back, save, load, achievements, menu, exit]
     }
 }
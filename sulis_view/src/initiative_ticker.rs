@@ -26,11 +26,16 @@ use sulis_state::{ChangeListener, EntityState, GameState};
 
 pub const NAME: &str = "initiative_ticker";
 
-pub struct InitiativeTicker {}
+/// Shows the turn order of the current combat.  The party member whose turn
+/// it is may be dragged onto a later entry to delay their turn until after
+/// that entry.
+pub struct InitiativeTicker {
+    dragging: Option<Rc<RefCell<EntityState>>>,
+}
 
 impl InitiativeTicker {
     pub fn new() -> Rc<RefCell<InitiativeTicker>> {
-        Rc::new(RefCell::new(InitiativeTicker {}))
+        Rc::new(RefCell::new(InitiativeTicker { dragging: None }))
     }
 }
 
@@ -42,6 +47,12 @@ impl WidgetKind for InitiativeTicker {
         false
     }
 
+    fn on_mouse_exit(&mut self, widget: &Rc<RefCell<Widget>>) -> bool {
+        self.super_on_mouse_exit(widget);
+        self.dragging = None;
+        false
+    }
+
     fn on_mouse_press(&mut self, widget: &Rc<RefCell<Widget>>, kind: ClickKind) -> bool {
         self.super_on_mouse_press(widget, kind);
         false
@@ -49,6 +60,16 @@ impl WidgetKind for InitiativeTicker {
 
     fn on_mouse_release(&mut self, widget: &Rc<RefCell<Widget>>, kind: ClickKind) -> bool {
         self.super_on_mouse_release(widget, kind);
+        // released outside of any entry
+        self.dragging.take().is_some()
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _widget: &Rc<RefCell<Widget>>,
+        _delta_x: f32,
+        _delta_y: f32,
+    ) -> bool {
         false
     }
 
@@ -59,11 +80,11 @@ impl WidgetKind for InitiativeTicker {
         _delta_x: f32,
         _delta_y: f32,
     ) -> bool {
-        false
+        self.dragging.is_some()
     }
 
     fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
-        widget.borrow_mut().state.set_enabled(false);
+        self.dragging = None;
 
         let mgr = GameState::turn_manager();
         mgr.borrow_mut()
@@ -74,7 +95,7 @@ impl WidgetKind for InitiativeTicker {
         let mut first = true;
         for entity in mgr.borrow().active_iter() {
             let theme = if first { "current_entry" } else { "entry" };
-            let widget = Widget::with_theme(TickerLabel::new(entity), theme);
+            let widget = Widget::with_theme(TickerLabel::new(entity, first), theme);
             Widget::add_child_to(&pane, widget);
             first = false;
         }
@@ -85,19 +106,83 @@ impl WidgetKind for InitiativeTicker {
 
 struct TickerLabel {
     entity: Rc<RefCell<EntityState>>,
+    current: bool,
 }
 
 impl TickerLabel {
-    fn new(entity: &Rc<RefCell<EntityState>>) -> Rc<RefCell<TickerLabel>> {
+    fn new(entity: &Rc<RefCell<EntityState>>, current: bool) -> Rc<RefCell<TickerLabel>> {
         Rc::new(RefCell::new(TickerLabel {
             entity: Rc::clone(entity),
+            current,
         }))
     }
+
+    fn can_delay(&self) -> bool {
+        self.current
+            && self.entity.borrow().is_party_member()
+            && !GameState::has_any_blocking_animations()
+    }
 }
 
 impl WidgetKind for TickerLabel {
     widget_kind!(NAME);
 
+    fn on_mouse_enter(&mut self, widget: &Rc<RefCell<Widget>>) -> bool {
+        self.super_on_mouse_enter(widget);
+        let (_, ticker) = Widget::parent_mut::<InitiativeTicker>(widget);
+        if ticker.dragging.is_some() && !self.current {
+            widget.borrow_mut().state.set_active(true);
+        }
+        false
+    }
+
+    fn on_mouse_exit(&mut self, widget: &Rc<RefCell<Widget>>) -> bool {
+        self.super_on_mouse_exit(widget);
+        widget.borrow_mut().state.set_active(false);
+        false
+    }
+
+    fn on_mouse_press(&mut self, widget: &Rc<RefCell<Widget>>, kind: ClickKind) -> bool {
+        if kind != ClickKind::Primary || !self.can_delay() {
+            return false;
+        }
+
+        self.super_on_mouse_press(widget, kind);
+        let (_, ticker) = Widget::parent_mut::<InitiativeTicker>(widget);
+        ticker.dragging = Some(Rc::clone(&self.entity));
+        true
+    }
+
+    fn on_mouse_release(&mut self, widget: &Rc<RefCell<Widget>>, kind: ClickKind) -> bool {
+        self.super_on_mouse_release(widget, kind);
+        widget.borrow_mut().state.set_active(false);
+
+        let (_, ticker) = Widget::parent_mut::<InitiativeTicker>(widget);
+        let dragged = match ticker.dragging.take() {
+            None => return false,
+            Some(entity) => entity,
+        };
+
+        if !self.current {
+            let mgr = GameState::turn_manager();
+            if !mgr.borrow_mut().delay_turn_after(&dragged, &self.entity) {
+                info!("Unable to delay turn after '{}'", self.entity.borrow().actor.actor.name);
+            }
+        }
+        true
+    }
+
+    fn on_mouse_drag(
+        &mut self,
+        widget: &Rc<RefCell<Widget>>,
+        _kind: ClickKind,
+        _delta_x: f32,
+        _delta_y: f32,
+    ) -> bool {
+        let (_, ticker) = Widget::parent_mut::<InitiativeTicker>(widget);
+        ticker.dragging.is_some()
+    }
+
     fn draw(
         &mut self,
         renderer: &mut dyn GraphicsRenderer,
@@ -23,7 +23,8 @@ use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::util;
 use sulis_core::widgets::{Button, Label};
 use sulis_module::{QuickSlot, Slot};
-use sulis_state::{script::ScriptItemKind, ChangeListener, EntityState, GameState};
+use sulis_state::script::{ScriptCallback, ScriptItemKind};
+use sulis_state::{ChangeListener, EntityState, GameState, Loadout};
 
 use crate::{item_callback_handler::*, item_list_pane::Filter, ItemButton, ItemListPane};
 
@@ -170,13 +171,74 @@ impl WidgetKind for InventoryWindow {
             }
         }
 
+        let loadouts = Widget::empty("loadouts");
+        for loadout in Loadout::iter() {
+            let loadout = *loadout;
+
+            let apply = Widget::with_theme(Button::empty(), "apply_loadout");
+            let entity_ref = Rc::clone(&self.entity);
+            {
+                let mut apply = apply.borrow_mut();
+                apply.state.add_text_arg("loadout", &format!("{loadout:?}"));
+                apply.state.set_enabled(actor.can_apply_loadout(loadout));
+                apply.state.add_callback(Callback::with(Box::new(move || {
+                    let held = |entity: &Rc<RefCell<EntityState>>| {
+                        let entity = entity.borrow();
+                        let inv = entity.actor.inventory();
+                        [Slot::HeldMain, Slot::HeldOff].map(|slot| inv.equipped(slot).cloned())
+                    };
+
+                    let held_before = held(&entity_ref);
+                    let stash = GameState::party_stash();
+                    entity_ref
+                        .borrow_mut()
+                        .actor
+                        .apply_loadout(loadout, &mut stash.borrow_mut());
+
+                    if held(&entity_ref) != held_before {
+                        let mgr = GameState::turn_manager();
+                        let cbs = entity_ref.borrow().callbacks(&mgr.borrow());
+                        cbs.iter().for_each(|cb| cb.on_held_changed());
+                    }
+                })));
+            }
+
+            let save = Widget::with_theme(Button::empty(), "save_loadout");
+            let entity_ref = Rc::clone(&self.entity);
+            save.borrow_mut()
+                .state
+                .add_callback(Callback::with(Box::new(move || {
+                    entity_ref.borrow_mut().actor.save_loadout(loadout);
+                })));
+
+            Widget::add_child_to(&loadouts, apply);
+            Widget::add_child_to(&loadouts, save);
+        }
+
         let stash_title = Widget::with_theme(Label::empty(), "stash_title");
 
+        let keyring = Widget::with_theme(Button::empty(), "keyring");
+        let keys: Vec<String> = stash
+            .borrow()
+            .keys()
+            .iter()
+            .map(|(qty, item)| match qty {
+                1 => item.item.name.to_string(),
+                _ => format!("{} x{}", item.item.name, qty),
+            })
+            .collect();
+        let keys = if keys.is_empty() {
+            "No keys held".to_string()
+        } else {
+            keys.join("\n")
+        };
+        keyring.borrow_mut().state.add_text_arg("keys", &keys);
+
         trace!(
             "Inventory window creation time: {}",
             util::format_elapsed_secs(start_time.elapsed())
         );
 
-        vec![close, equipped_area, item_list_pane, stash_title]
+        vec![close, equipped_area, loadouts, item_list_pane, stash_title, keyring]
     }
 }
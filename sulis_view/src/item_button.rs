@@ -16,10 +16,12 @@
 
 use std::any::Any;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::rc::Rc;
 
 use crate::bonus_text_arg_handler::{
-    add_attack_text_args, add_bonus_text_args, add_prereq_text_args,
+    add_armor_category_text_args, add_attack_text_args, add_bonus_text_args,
+    add_item_set_text_args, add_prereq_text_args,
 };
 use crate::item_callback_handler::sell_item_cb;
 use crate::{ItemActionMenu, MerchantWindow, RootView};
@@ -29,7 +31,7 @@ use sulis_core::widgets::{Label, TextArea};
 use sulis_module::{
     ability,
     item::{format_item_value, format_item_weight},
-    Module,
+    ItemKind, Module,
 };
 use sulis_module::{ItemState, QuickSlot, Slot};
 use sulis_state::{inventory::has_proficiency, EntityState, GameState};
@@ -43,6 +45,10 @@ enum Kind {
         id: String,
         item_index: usize,
     },
+    BuyBack {
+        id: String,
+        item_index: usize,
+    },
     Inventory {
         item_index: usize,
     },
@@ -54,6 +60,9 @@ enum Kind {
         player: Rc<RefCell<EntityState>>,
         quick: QuickSlot,
     },
+    Display {
+        item: ItemState,
+    },
 }
 
 struct ButtonAction {
@@ -135,6 +144,24 @@ impl ItemButton {
         )
     }
 
+    /// Creates a button for an item previously sold to the merchant, which
+    /// may be bought back at the price it was sold for
+    pub fn buy_back(
+        item: &ItemState,
+        item_index: usize,
+        merchant_id: &str,
+    ) -> Rc<RefCell<ItemButton>> {
+        let id = merchant_id.to_string();
+        ItemButton::new(item, 1, Kind::BuyBack { id, item_index })
+    }
+
+    /// Creates a button that only shows the details of the `item` on hover,
+    /// without it being in any inventory
+    pub fn display(item: &ItemState) -> Rc<RefCell<ItemButton>> {
+        let kind = Kind::Display { item: item.clone() };
+        ItemButton::new(item, 1, kind)
+    }
+
     fn new(item: &ItemState, quantity: u32, kind: Kind) -> Rc<RefCell<ItemButton>> {
         let icon = item.icon().id();
         let adjective_icons = item.item.adjective_icons();
@@ -230,6 +257,11 @@ impl ItemButton {
 
                 merchant.items().get(item_index).map(|(_, item)| item.clone())
             }
+            Kind::BuyBack { ref id, item_index } => {
+                let merchant = area_state.get_merchant(id)?;
+                merchant.buy_back_items().get(item_index).map(|(_, item)| item.clone())
+            }
+            Kind::Display { ref item } => Some(item.clone()),
         }
     }
 
@@ -275,6 +307,15 @@ impl ItemButton {
                         .add_text_arg("price", &format_item_value(value));
                 }
             }
+            Kind::BuyBack { ref id, item_index } => {
+                let merchant = area_state.get_merchant(id);
+                let entry = merchant.and_then(|m| m.buy_back_items().get(item_index).cloned());
+                if let Some((value, _)) = entry {
+                    item_window
+                        .state
+                        .add_text_arg("price", &format_item_value(value));
+                }
+            }
             Kind::Inventory { .. } | Kind::Equipped { .. } => {
                 let root_view = Widget::kind_mut::<RootView>(root);
                 let merch_window = match root_view.get_merchant_window(root) {
@@ -293,6 +334,46 @@ impl ItemButton {
             _ => (),
         }
     }
+
+    fn add_item_set_text_arg(&self, item_window: &mut Widget, item_state: &ItemState) {
+        let set_id = match &item_state.item.set {
+            None => return,
+            Some(set_id) => set_id,
+        };
+        let set = match Module::item_set(set_id) {
+            None => return,
+            Some(set) => set,
+        };
+
+        let player = match self.kind {
+            Kind::Equipped { ref player, .. } | Kind::Quick { ref player, .. } => {
+                Some(Rc::clone(player))
+            }
+            _ => GameState::selected().first().cloned(),
+        };
+
+        // count distinct pieces, as duplicates of a set item do not stack
+        let mut owned = HashSet::new();
+        let mut equipped = 0;
+        if let Some(player) = player {
+            let player = player.borrow();
+            equipped = player.actor.equipped_set_pieces().get(set_id).copied().unwrap_or(0);
+            for item in player.actor.inventory().equipped_iter() {
+                if item.item.set.as_ref() == Some(set_id) {
+                    owned.insert(item.item.original_id.to_string());
+                }
+            }
+        }
+
+        let stash = GameState::party_stash();
+        for (_, item) in stash.borrow().items().iter() {
+            if item.item.set.as_ref() == Some(set_id) {
+                owned.insert(item.item.original_id.to_string());
+            }
+        }
+
+        add_item_set_text_args(&set, owned.len() as u32, equipped, &mut item_window.state);
+    }
 }
 
 impl WidgetKind for ItemButton {
@@ -361,18 +442,21 @@ impl WidgetKind for ItemButton {
             }
 
             match self.kind {
-                Kind::Prop { .. } | Kind::Inventory { .. } | Kind::Merchant { .. } => {
+                Kind::Prop { .. }
+                | Kind::Inventory { .. }
+                | Kind::Merchant { .. }
+                | Kind::BuyBack { .. } => {
                     let player = GameState::selected();
                     if !player.is_empty() {
                         if !has_proficiency(&item_state, &player[0].borrow().actor.stats) {
                             item_window.state.add_text_arg("prof_not_met", "true");
                         }
 
-                        if !item_state
+                        if let Some(reason) = item_state
                             .item
-                            .meets_prereqs(&player[0].borrow().actor.actor)
+                            .unmet_prereq(&player[0].borrow().actor.actor)
                         {
-                            item_window.state.add_text_arg("prereqs_not_met", "true");
+                            item_window.state.add_text_arg("prereqs_not_met", &reason);
                         }
 
                         if let Some(ref equip) = item_state.item.equippable {
@@ -397,7 +481,7 @@ impl WidgetKind for ItemButton {
 
             item_window
                 .state
-                .add_text_arg("name", &item_state.item.name);
+                .add_display_text_arg("name", &item_state.item.name);
             item_window
                 .state
                 .add_text_arg("value", &format_item_value(item_state.item.value));
@@ -410,6 +494,10 @@ impl WidgetKind for ItemButton {
                 add_prereq_text_args(prereqs, &mut item_window.state);
             }
 
+            if let ItemKind::Armor { kind } = item_state.item.kind {
+                add_armor_category_text_args(kind, &mut item_window.state);
+            }
+
             match &item_state.item.usable {
                 None => (),
                 Some(usable) => {
@@ -430,7 +518,7 @@ impl WidgetKind for ItemButton {
                             state.add_text_arg("usable_permanent", "true")
                         }
                     }
-                    state.add_text_arg("usable_description", &usable.short_description);
+                    state.add_display_text_arg("usable_description", &usable.short_description);
                 }
             }
 
@@ -443,6 +531,8 @@ impl WidgetKind for ItemButton {
                     add_bonus_text_args(&equippable.bonuses, &mut item_window.state);
                 }
             }
+
+            self.add_item_set_text_arg(&mut item_window, &item_state);
         }
         Widget::add_child_to(&root, Rc::clone(&item_window));
         self.item_window = Some(item_window);
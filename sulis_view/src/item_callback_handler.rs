@@ -76,13 +76,35 @@ pub fn use_item_cb(entity: &Rc<RefCell<EntityState>>, kind: ScriptItemKind) -> C
     }))
 }
 
-pub fn take_item_cb(prop_index: usize, index: usize) -> Callback {
+pub fn take_item_cb(
+    entity: &Rc<RefCell<EntityState>>,
+    prop_index: usize,
+    index: usize,
+) -> Callback {
+    let entity = Rc::clone(entity);
     Callback::with(Box::new(move || {
+        let owner = prop_owner(prop_index);
         let stash = GameState::party_stash();
         stash.borrow_mut().take(prop_index, index);
+
+        if let Some(owner) = owner {
+            GameState::report_theft(&entity, &owner);
+        }
     }))
 }
 
+/// Returns the owner of the specified prop, if it is owned and there
+/// are items in it to take
+pub fn prop_owner(prop_index: usize) -> Option<String> {
+    let area_state = GameState::area_state();
+    let area_state = area_state.borrow();
+    let prop = area_state.props().get(prop_index);
+    match prop.items() {
+        Some(items) if !items.is_empty() => prop.owner.clone(),
+        _ => None,
+    }
+}
+
 pub fn equip_item_cb(entity: &Rc<RefCell<EntityState>>, index: usize) -> Callback {
     let entity = Rc::clone(entity);
     Callback::with(Box::new(move || {
@@ -141,6 +163,35 @@ pub fn buy_item_cb(merchant_id: &str, index: usize) -> Callback {
     }))
 }
 
+pub fn buy_back_item_cb(merchant_id: &str, index: usize) -> Callback {
+    let merchant_id = merchant_id.to_string();
+    Callback::with(Box::new(move || {
+        let area_state = GameState::area_state();
+        let mut area_state = area_state.borrow_mut();
+
+        let mut merchant = area_state.get_merchant_mut(&merchant_id);
+        let merchant = match merchant {
+            None => return,
+            Some(ref mut merchant) => merchant,
+        };
+
+        let value = match merchant.buy_back_items().get(index) {
+            None => return,
+            Some((value, _)) => *value,
+        };
+
+        if GameState::party_coins() < value {
+            return;
+        }
+
+        if let Some((_, item_state)) = merchant.remove_buy_back(index) {
+            GameState::add_party_coins(-value);
+            let stash = GameState::party_stash();
+            stash.borrow_mut().add_item(1, item_state);
+        }
+    }))
+}
+
 pub fn sell_item_cb(entity: &Rc<RefCell<EntityState>>, index: usize) -> Callback {
     let entity = Rc::clone(entity);
     Callback::new(Rc::new(move |widget, _| {
@@ -164,9 +215,8 @@ pub fn sell_item_cb(entity: &Rc<RefCell<EntityState>>, index: usize) -> Callback
         let stash = GameState::party_stash();
         let item_state = stash.borrow_mut().remove_item(index);
         if let Some(item_state) = item_state {
-            let value = merchant.get_sell_price(&item_state);
+            let value = merchant.sell(item_state);
             GameState::add_party_coins(value);
-            merchant.add(item_state);
         }
 
         let actor = &entity.borrow().actor;
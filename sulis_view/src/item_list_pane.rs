@@ -30,6 +30,7 @@ pub const NAME: &str = "item_list_pane";
 enum Kind {
     Entity,
     Merchant(String),
+    BuyBack(String),
     Prop(usize),
 }
 
@@ -105,6 +106,14 @@ impl ItemListPane {
         ItemListPane::new(entity, Kind::Merchant(merchant_id), cur_filter)
     }
 
+    pub fn new_buy_back(
+        entity: &Rc<RefCell<EntityState>>,
+        merchant_id: String,
+        cur_filter: &Rc<Cell<Filter>>,
+    ) -> Rc<RefCell<ItemListPane>> {
+        ItemListPane::new(entity, Kind::BuyBack(merchant_id), cur_filter)
+    }
+
     fn set_filter(&mut self, filter: Filter, widget: &Rc<RefCell<Widget>>) {
         self.cur_filter.set(filter);
         widget.borrow_mut().invalidate_children();
@@ -139,6 +148,34 @@ impl ItemListPane {
         list_content
     }
 
+    fn create_content_buy_back(&self, merchant_id: &str) -> Rc<RefCell<Widget>> {
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let merchant = match area_state.get_merchant(merchant_id) {
+            None => return Widget::empty("none"),
+            Some(merchant) => merchant,
+        };
+
+        let scrollpane = ScrollPane::new(ScrollDirection::Vertical);
+        let list_content = Widget::with_theme(scrollpane.clone(), "items_list");
+        for (index, (_, item)) in merchant.buy_back_items().iter().enumerate() {
+            if !self.cur_filter.get().is_allowed(&item.item) {
+                continue;
+            }
+
+            let item_button = ItemButton::buy_back(item, index, merchant_id);
+            item_button
+                .borrow_mut()
+                .add_action("Buy Back", buy_back_item_cb(merchant_id, index), true);
+
+            scrollpane
+                .borrow()
+                .add_to_content(Widget::with_defaults(item_button));
+        }
+
+        list_content
+    }
+
     fn create_content_prop(&self, prop_index: usize) -> Rc<RefCell<Widget>> {
         let combat_active = GameState::is_combat_active();
 
@@ -158,9 +195,10 @@ impl ItemListPane {
 
                     let item_button = ItemButton::prop(item, qty, index, prop_index);
                     if !combat_active {
+                        let text = if prop.owner.is_some() { "Steal" } else { "Take" };
                         item_button.borrow_mut().add_action(
-                            "Take",
-                            take_item_cb(prop_index, index),
+                            text,
+                            take_item_cb(&self.entity, prop_index, index),
                             true,
                         );
                     }
@@ -238,6 +276,7 @@ impl WidgetKind for ItemListPane {
             Kind::Entity => self.create_content_inventory(),
             Kind::Prop(index) => self.create_content_prop(*index),
             Kind::Merchant(id) => self.create_content_merchant(id),
+            Kind::BuyBack(id) => self.create_content_buy_back(id),
         };
         children.push(content);
 
@@ -28,9 +28,14 @@ pub use self::abilities_bar::AbilitiesBar;
 mod ability_pane;
 pub use self::ability_pane::AbilityPane;
 
+pub mod achievements_window;
+pub use self::achievements_window::{AchievementToast, AchievementsWindow};
+
 mod action_kind;
 pub use self::action_kind::ActionKind;
 
+mod action_queue;
+
 mod ap_bar;
 pub use self::ap_bar::ApBar;
 
@@ -43,11 +48,18 @@ pub use self::area_view::AreaView;
 mod basic_mouseover;
 pub use self::basic_mouseover::BasicMouseover;
 
+mod banter_scheduler;
+
+pub mod binding;
+
 mod bonus_text_arg_handler;
 
 pub mod character_builder;
 pub use self::character_builder::CharacterBuilder;
 
+mod camera;
+pub use self::camera::Camera;
+
 mod character_window;
 pub use self::character_window::CharacterWindow;
 
@@ -63,12 +75,18 @@ pub use self::cutscene_window::CutsceneWindow;
 mod dialog_window;
 pub use self::dialog_window::DialogWindow;
 
+mod epilogue_window;
+pub use self::epilogue_window::EpilogueWindow;
+
 mod formation_window;
 pub use self::formation_window::FormationWindow;
 
 mod game_over_window;
 pub use self::game_over_window::GameOverWindow;
 
+mod generated_portrait;
+pub use self::generated_portrait::GeneratedPortrait;
+
 mod in_game_menu;
 pub use self::in_game_menu::InGameMenu;
 
@@ -95,9 +113,15 @@ pub use self::loading_screen::LoadingScreen;
 mod load_window;
 pub use self::load_window::LoadWindow;
 
+mod log_window;
+pub use self::log_window::LogWindow;
+
 mod merchant_window;
 pub use self::merchant_window::MerchantWindow;
 
+mod over_layers;
+pub use self::over_layers::OverLayers;
+
 mod portrait_view;
 pub use self::portrait_view::PortraitView;
 
@@ -112,9 +136,18 @@ use self::quick_item_bar::QuickItemBar;
 
 pub mod main_menu;
 
+mod radial_menu;
+pub use self::radial_menu::RadialMenu;
+
 mod race_pane;
 pub use self::race_pane::RacePane;
 
+mod readout_pane;
+pub use self::readout_pane::ReadoutPane;
+
+mod records_window;
+pub use self::records_window::RecordsWindow;
+
 mod root_view;
 pub use self::root_view::RootView;
 
@@ -124,8 +157,14 @@ pub use self::screen_shake::ScreenShake;
 mod script_menu;
 pub use self::script_menu::ScriptMenu;
 
+mod status_bar;
+pub use self::status_bar::{MessageHistoryWindow, StatusBar};
+
 pub mod trigger_activator;
 
+mod tutorial_overlay;
+pub use self::tutorial_overlay::TutorialOverlay;
+
 mod window_fade;
 pub use self::window_fade::WindowFade;
 
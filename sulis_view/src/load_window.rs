@@ -74,28 +74,27 @@ impl LoadWindow {
                 error!("{}", e);
             }
             Ok(state) => {
-                self.set_load_step(state, root);
+                if state.rules_changed() {
+                    self.confirm_rules(state, root);
+                } else {
+                    set_load_step(self.main_menu_mode, state, root);
+                }
             }
         }
     }
 
-    pub fn set_load_step(&self, save_state: SaveState, root: &Rc<RefCell<Widget>>) {
-        // TODO remove the bool flag passed in the constructor
-        if self.main_menu_mode {
-            let main_menu = Widget::kind_mut::<MainMenu>(root);
-            main_menu.next_step = Some(NextGameStep::LoadCampaign {
-                save_state: Box::new(save_state),
-            });
-        } else {
-            let root_view = Widget::kind_mut::<RootView>(root);
-            root_view.next_step = Some(NextGameStep::LoadCampaign {
-                save_state: Box::new(save_state),
-            });
-        }
-
-        let loading_screen = Widget::with_defaults(LoadingScreen::new());
-        loading_screen.borrow_mut().state.set_modal(true);
-        Widget::add_child_to(root, loading_screen);
+    /// Asks whether to keep the rules stored in the save or opt into the
+    /// module's updated rules, then loads the game
+    fn confirm_rules(&self, save_state: SaveState, root: &Rc<RefCell<Widget>>) {
+        let save_state = Rc::new(RefCell::new(Some(save_state)));
+        let accept = load_callback(self.main_menu_mode, Rc::clone(&save_state), true);
+        let cancel = load_callback(self.main_menu_mode, save_state, false);
+
+        let window = ConfirmationWindow::new(accept);
+        window.borrow_mut().set_cancel_callback(cancel);
+        let window = Widget::with_theme(window, "rules_changed_confirmation");
+        window.borrow_mut().state.set_modal(true);
+        Widget::add_child_to(root, window);
     }
 
     pub fn delete_save(&mut self) {
@@ -187,14 +186,14 @@ impl WidgetKind for LoadWindow {
                 let area = &mut text_area.borrow_mut().state;
                 area.add_text_arg("player_name", &meta.player_name);
                 area.add_text_arg("datetime", &meta.datetime);
-                area.add_text_arg("current_area_name", &meta.current_area_name);
+                area.add_display_text_arg("current_area_name", &meta.current_area_name);
 
                 if let Some(level) = meta.level {
                     area.add_text_arg("level", &format!("{level}"));
                 }
 
                 if let Some(class) = &meta.class {
-                    area.add_text_arg("class", class);
+                    area.add_display_text_arg("class", class);
                 }
 
                 if let Some(error) = &meta.error {
@@ -240,3 +239,41 @@ impl WidgetKind for LoadWindow {
         ]
     }
 }
+
+fn load_callback(
+    main_menu_mode: bool,
+    save_state: Rc<RefCell<Option<SaveState>>>,
+    use_module_rules: bool,
+) -> Callback {
+    Callback::new(Rc::new(move |widget, _| {
+        let (parent, _) = Widget::parent::<ConfirmationWindow>(widget);
+        parent.borrow_mut().mark_for_removal();
+
+        let Some(mut save_state) = save_state.borrow_mut().take() else { return };
+        if use_module_rules {
+            save_state.use_module_rules();
+        }
+
+        let root = Widget::get_root(widget);
+        set_load_step(main_menu_mode, save_state, &root);
+    }))
+}
+
+fn set_load_step(main_menu_mode: bool, save_state: SaveState, root: &Rc<RefCell<Widget>>) {
+    // TODO remove the bool flag passed in the constructor
+    if main_menu_mode {
+        let main_menu = Widget::kind_mut::<MainMenu>(root);
+        main_menu.next_step = Some(NextGameStep::LoadCampaign {
+            save_state: Box::new(save_state),
+        });
+    } else {
+        let root_view = Widget::kind_mut::<RootView>(root);
+        root_view.next_step = Some(NextGameStep::LoadCampaign {
+            save_state: Box::new(save_state),
+        });
+    }
+
+    let loading_screen = Widget::with_defaults(LoadingScreen::new());
+    loading_screen.borrow_mut().state.set_modal(true);
+    Widget::add_child_to(root, loading_screen);
+}
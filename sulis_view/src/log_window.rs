@@ -0,0 +1,170 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use log::Level;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::util::{self, log_buffer, LogEntry};
+use sulis_core::widgets::{Button, InputField, Label, ScrollDirection, ScrollPane, TextArea};
+
+pub const NAME: &str = "log_window";
+
+const MAX_DISPLAYED: usize = 200;
+const REFRESH_MILLIS: u32 = 500;
+
+pub struct LogWindow {
+    max_level: Level,
+    search: String,
+    search_widget: Rc<RefCell<Widget>>,
+    generation: u64,
+    last_refresh: Instant,
+}
+
+impl LogWindow {
+    pub fn new() -> Rc<RefCell<LogWindow>> {
+        let search_field = InputField::new("");
+        let search_widget = Widget::with_theme(search_field, "search");
+        search_widget
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, kind| {
+                let field = match kind.as_any().downcast_ref::<InputField>() {
+                    None => return,
+                    Some(field) => field,
+                };
+
+                let (parent, window) = Widget::parent_mut::<LogWindow>(widget);
+                window.search = field.text();
+                parent.borrow_mut().invalidate_children();
+            })));
+
+        Rc::new(RefCell::new(LogWindow {
+            max_level: Level::Info,
+            search: String::new(),
+            search_widget,
+            generation: log_buffer::generation(),
+            last_refresh: Instant::now(),
+        }))
+    }
+
+    fn create_level_button(&self, theme: &str, level: Level) -> Rc<RefCell<Widget>> {
+        let button = Widget::with_theme(Button::empty(), theme);
+        button.borrow_mut().state.set_active(self.max_level == level);
+        button
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let (parent, window) = Widget::parent_mut::<LogWindow>(widget);
+                window.max_level = level;
+                parent.borrow_mut().invalidate_children();
+            })));
+        button
+    }
+}
+
+impl WidgetKind for LogWindow {
+    widget_kind!(NAME);
+
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, _millis: u32) {
+        if util::get_elapsed_millis(self.last_refresh.elapsed()) < REFRESH_MILLIS {
+            return;
+        }
+
+        let generation = log_buffer::generation();
+        if generation == self.generation {
+            return;
+        }
+
+        self.generation = generation;
+        self.last_refresh = Instant::now();
+        widget.borrow_mut().invalidate_children();
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<LogWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let error = self.create_level_button("error_button", Level::Error);
+        let warn = self.create_level_button("warn_button", Level::Warn);
+        let info = self.create_level_button("info_button", Level::Info);
+        let debug = self.create_level_button("debug_button", Level::Debug);
+
+        let search_label = Widget::with_theme(Label::empty(), "search_label");
+
+        let entries = log_buffer::entries(self.max_level, &self.search);
+        let start = entries.len().saturating_sub(MAX_DISPLAYED);
+        let text: Vec<_> = entries[start..].iter().map(format_entry).collect();
+
+        let scroll = ScrollPane::new(ScrollDirection::Vertical);
+        let entries_widget = Widget::with_theme(scroll.clone(), "entries");
+        let text_area = Widget::with_theme(TextArea::empty(), "text");
+        text_area
+            .borrow_mut()
+            .state
+            .add_text_arg("entries", &text.join("\n"));
+        scroll.borrow().add_to_content(text_area);
+
+        vec![
+            close,
+            error,
+            warn,
+            info,
+            debug,
+            search_label,
+            Rc::clone(&self.search_widget),
+            entries_widget,
+        ]
+    }
+}
+
+fn format_entry(entry: &LogEntry) -> String {
+    let color = match entry.level {
+        Level::Error => "f00",
+        Level::Warn => "ff0",
+        Level::Info => "fff",
+        Level::Debug | Level::Trace => "888",
+    };
+
+    format!(
+        "[c={}|{} {}: {}]",
+        color,
+        entry.level,
+        escape(&entry.target),
+        escape(&entry.message)
+    )
+}
+
+// escape characters that would otherwise be parsed as markup
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | '|') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
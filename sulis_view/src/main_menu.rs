@@ -17,6 +17,9 @@
 pub mod character_selector;
 pub use self::character_selector::CharacterSelector;
 
+mod credits_pane;
+use self::credits_pane::CreditsPane;
+
 mod links_pane;
 use self::links_pane::LinksPane;
 
@@ -26,6 +29,9 @@ pub use self::module_selector::ModuleSelector;
 mod mods_selector;
 use self::mods_selector::ModsSelector;
 
+pub mod new_game_window;
+pub use self::new_game_window::NewGameWindow;
+
 pub mod options;
 pub use self::options::Options;
 
@@ -44,7 +50,7 @@ use sulis_core::widgets::{Button, ConfirmationWindow, TextArea};
 use sulis_module::{modification, Module};
 use sulis_state::{save_file, NextGameStep};
 
-use crate::{CharacterBuilder, LoadWindow};
+use crate::{CharacterBuilder, LoadWindow, ReadoutPane};
 
 enum Mode {
     New,
@@ -53,6 +59,7 @@ enum Mode {
     Mods,
     Options,
     Links,
+    Credits,
     NoChoice,
 }
 
@@ -152,7 +159,7 @@ impl WidgetKind for MainMenu {
             module_title
                 .borrow_mut()
                 .state
-                .add_text_arg("module", &campaign.name);
+                .add_display_text_arg("module", &campaign.name);
             if let Some(group) = &campaign.group {
                 module_title
                     .borrow_mut()
@@ -182,7 +189,7 @@ impl WidgetKind for MainMenu {
 
                 parent.borrow_mut().invalidate_children();
                 starter.mode = Mode::New;
-                starter.content = Widget::with_defaults(CharacterSelector::new(parent));
+                starter.content = Widget::with_defaults(NewGameWindow::new());
             })));
 
         let load = Widget::with_theme(Button::empty(), "load");
@@ -261,6 +268,19 @@ impl WidgetKind for MainMenu {
                 parent.borrow_mut().invalidate_children();
             })));
 
+        let credits = Widget::with_theme(Button::empty(), "credits");
+        credits
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, window) = Widget::parent_mut::<MainMenu>(widget);
+                window.mode = Mode::Credits;
+
+                window.content = Widget::with_defaults(CreditsPane::new());
+
+                parent.borrow_mut().invalidate_children();
+            })));
+
         let exit = Widget::with_theme(Button::empty(), "exit");
         exit.borrow_mut()
             .state
@@ -276,6 +296,7 @@ impl WidgetKind for MainMenu {
             Mode::Module => module.borrow_mut().state.set_active(true),
             Mode::Options => options.borrow_mut().state.set_active(true),
             Mode::Links => links.borrow_mut().state.set_active(true),
+            Mode::Credits => credits.borrow_mut().state.set_active(true),
             Mode::NoChoice => (),
         }
 
@@ -291,7 +312,7 @@ impl WidgetKind for MainMenu {
 
         Widget::add_children_to(
             &menu_pane,
-            vec![module, cont, new, load, mods, options, links],
+            vec![module, cont, new, load, mods, options, credits, links],
         );
 
         let mut children = vec![background, title, module_title, menu_pane, exit];
@@ -299,12 +320,13 @@ impl WidgetKind for MainMenu {
         if let Some(tip_text) = &self.tip_text {
             let tip_pane = Widget::empty("tip_pane");
             let tip = Widget::with_theme(TextArea::empty(), "tip");
-            tip.borrow_mut().state.add_text_arg("tip", tip_text);
+            tip.borrow_mut().state.add_display_text_arg("tip", tip_text);
             Widget::add_child_to(&tip_pane, tip);
             children.push(tip_pane);
         }
 
         children.push(self.content.clone());
+        children.push(Widget::with_defaults(ReadoutPane::new()));
 
         if let Some(builder) = self.char_builder_to_add.take() {
             children.push(Widget::with_defaults(builder));
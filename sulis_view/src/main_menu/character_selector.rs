@@ -23,10 +23,11 @@ use sulis_core::widgets::{
     Button, ConfirmationWindow, Label, ScrollDirection, ScrollPane, TextArea,
 };
 use sulis_module::{Actor, Module};
-use sulis_state::{ActorState, NextGameStep};
+use sulis_state::ActorState;
 
 use crate::character_window::create_details_text_box;
-use crate::{main_menu::MainMenu, CharacterBuilder, LoadingScreen};
+use crate::main_menu::{new_game_window, MainMenu};
+use crate::CharacterBuilder;
 
 pub struct CharacterSelector {
     selected: Option<Rc<Actor>>,
@@ -192,12 +193,7 @@ impl WidgetKind for CharacterSelector {
                     Some(ref selected) => Rc::clone(selected),
                 };
 
-                let (root, window) = Widget::parent_mut::<MainMenu>(&parent);
-                window.next_step = Some(NextGameStep::NewCampaign { pc_actor: selected });
-
-                let loading_screen = Widget::with_defaults(LoadingScreen::new());
-                loading_screen.borrow_mut().state.set_modal(true);
-                Widget::add_child_to(&root, loading_screen);
+                new_game_window::start_new_game(&parent, selected);
             })));
 
         let details = if let Some(ref actor) = self.selected {
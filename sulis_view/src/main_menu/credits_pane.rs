@@ -0,0 +1,152 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use sulis_core::config::Config;
+use sulis_core::ui::{Widget, WidgetKind};
+use sulis_core::widgets::{Label, ScrollDirection, ScrollPane, TextArea};
+
+const ATTRIBUTION_FILE: &str = "attribution.csv";
+
+/// Shows the game credits, built from the attribution file in the `docs`
+/// directory next to the data directory.
+pub struct CreditsPane {
+    credits: String,
+}
+
+impl CreditsPane {
+    pub fn new() -> Rc<RefCell<CreditsPane>> {
+        Rc::new(RefCell::new(CreditsPane {
+            credits: read_credits(),
+        }))
+    }
+}
+
+impl WidgetKind for CreditsPane {
+    widget_kind!("credits_pane");
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let title = Widget::with_theme(Label::empty(), "title");
+
+        let scrollpane = ScrollPane::new(ScrollDirection::Vertical);
+        let list = Widget::with_theme(scrollpane.clone(), "list");
+
+        let credits = Widget::with_theme(TextArea::empty(), "credits");
+        if !self.credits.is_empty() {
+            credits
+                .borrow_mut()
+                .state
+                .add_text_arg("credits", &self.credits);
+        }
+        scrollpane.borrow().add_to_content(credits);
+
+        vec![title, list]
+    }
+}
+
+/// Reads the attribution file and groups the listed assets by author and
+/// license.  Returns an empty string if the file could not be read.
+fn read_credits() -> String {
+    let data_dir = Config::resources_config().directory;
+    let docs_dir = Path::new(&data_dir)
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join("docs");
+    let path = docs_dir.join(ATTRIBUTION_FILE);
+
+    let data = match fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(e) => {
+            warn!("Unable to read credits from {:?}: {}", path, e);
+            return String::new();
+        }
+    };
+
+    let mut authors: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for line in data.lines().skip(1) {
+        let fields = split_csv_line(line);
+        if fields.len() < 5 {
+            continue;
+        }
+
+        let author = if fields[3].is_empty() {
+            "Unknown Author"
+        } else {
+            &fields[3]
+        };
+
+        let asset = if fields[6].is_empty() {
+            &fields[0]
+        } else {
+            &fields[6]
+        };
+
+        let assets = authors
+            .entry((author.to_string(), fields[4].to_string()))
+            .or_default();
+        if !asset.is_empty() && !assets.iter().any(|a| a == asset) {
+            assets.push(asset.to_string());
+        }
+    }
+
+    let mut out = String::new();
+    for ((author, license), assets) in authors {
+        out.push_str(&format!("[s=7.0|{}] ({})\n", author, license));
+        for asset in assets {
+            out.push_str(&format!("  {}\n", asset));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Splits a single line of comma separated values, honoring double quoted
+/// fields and stripping text markup characters.  Missing trailing fields
+/// are returned as empty strings.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut cur = String::new();
+    let mut quoted = false;
+
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cur.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut cur)),
+            // these characters have special meaning in text area markup
+            '[' => cur.push('('),
+            ']' => cur.push(')'),
+            '|' | '#' => (),
+            _ => cur.push(c),
+        }
+    }
+    fields.push(cur);
+
+    while fields.len() < 9 {
+        fields.push(String::new());
+    }
+    fields
+}
@@ -21,7 +21,8 @@ use std::rc::Rc;
 use sulis_core::ui::*;
 use sulis_core::util::ActiveResources;
 use sulis_core::widgets::{Button, Label, ScrollDirection, ScrollPane, TextArea};
-use sulis_module::ModificationInfo;
+use sulis_module::modification::compatibility_warnings;
+use sulis_module::{ModificationInfo, Module};
 use sulis_state::NextGameStep;
 
 use crate::main_menu::MainMenu;
@@ -30,6 +31,7 @@ use crate::LoadingScreen;
 pub struct ModsSelector {
     available_mods: Vec<ModificationInfo>,
     active_mods: Vec<ModificationInfo>,
+    campaign: Option<String>,
 }
 
 impl ModsSelector {
@@ -46,11 +48,37 @@ impl ModsSelector {
             }
         }
 
+        let campaign = if Module::is_initialized() {
+            Some(Module::campaign().id.to_string())
+        } else {
+            None
+        };
+
         Rc::new(RefCell::new(ModsSelector {
             available_mods,
             active_mods,
+            campaign,
         }))
     }
+
+    /// Activates the available mod at the specified index, first activating any
+    /// available mods it requires so they are loaded before it
+    fn activate(&mut self, index: usize) {
+        let modif = self.available_mods.remove(index);
+
+        for required in modif.requires.iter() {
+            if self.active_mods.iter().any(|m| &m.id == required) {
+                continue;
+            }
+
+            if let Some(req_index) = self.available_mods.iter().position(|m| &m.id == required) {
+                let required = self.available_mods.remove(req_index);
+                self.active_mods.push(required);
+            }
+        }
+
+        self.active_mods.push(modif);
+    }
 }
 
 impl WidgetKind for ModsSelector {
@@ -82,6 +110,15 @@ impl WidgetKind for ModsSelector {
             active_pane.borrow().add_to_content(widget);
         }
 
+        let warnings = compatibility_warnings(&self.active_mods, self.campaign.as_deref());
+        let warnings_area = Widget::with_theme(TextArea::empty(), "warnings");
+        if !warnings.is_empty() {
+            warnings_area
+                .borrow_mut()
+                .state
+                .add_text_arg("warnings", &warnings.join("\n"));
+        }
+
         let clear = Widget::with_theme(Button::empty(), "clear");
         clear
             .borrow_mut()
@@ -135,6 +172,7 @@ impl WidgetKind for ModsSelector {
             active_title,
             available,
             active,
+            warnings_area,
             clear,
             cancel,
             apply,
@@ -176,6 +214,11 @@ impl WidgetKind for ModPane {
             state.add_text_arg("name", &self.modif.name);
             state.add_text_arg("description", &self.modif.description);
             state.add_text_arg("dir", &self.modif.dir);
+            let source = if self.modif.is_archive() { "Archive" } else { "Directory" };
+            state.add_text_arg("source", source);
+            if !self.modif.requires.is_empty() {
+                state.add_text_arg("requires", &self.modif.requires.join(", "));
+            }
         }
 
         let toggle = Widget::with_theme(Button::empty(), "toggle");
@@ -192,8 +235,7 @@ impl WidgetKind for ModPane {
                     let modif = sel.active_mods.remove(index);
                     sel.available_mods.push(modif);
                 } else {
-                    let modif = sel.available_mods.remove(index);
-                    sel.active_mods.push(modif);
+                    sel.activate(index);
                 }
 
                 parent.borrow_mut().invalidate_children();
@@ -21,8 +21,11 @@ use std::rc::Rc;
 
 use sulis_core::ui::*;
 use sulis_core::util::ActiveResources;
-use sulis_core::widgets::{Button, Label, ScrollDirection, ScrollPane, TextArea};
-use sulis_module::ModuleInfo;
+use sulis_core::widgets::{
+    Button, ConfirmationWindow, Label, ScrollDirection, ScrollPane, TextArea,
+};
+use sulis_module::modification::{self, compatibility_warnings};
+use sulis_module::{ModificationInfo, ModuleInfo};
 use sulis_state::NextGameStep;
 
 use crate::main_menu::MainMenu;
@@ -31,15 +34,42 @@ use crate::LoadingScreen;
 pub struct ModuleSelector {
     modules: Vec<ModuleInfo>,
     selected_module: Option<usize>,
+    active_mods: Vec<ModificationInfo>,
 }
 
 impl ModuleSelector {
     pub fn new(modules: Vec<ModuleInfo>) -> Rc<RefCell<ModuleSelector>> {
+        let active = ActiveResources::read();
+        let available_mods = modification::get_available_modifications();
+        let active_mods = active
+            .mods
+            .iter()
+            .filter_map(|dir| available_mods.iter().find(|m| &m.dir == dir))
+            .cloned()
+            .collect();
+
         Rc::new(RefCell::new(ModuleSelector {
             modules,
             selected_module: None,
+            active_mods,
         }))
     }
+
+    fn warnings(&self, index: usize) -> Vec<String> {
+        compatibility_warnings(&self.active_mods, Some(&self.modules[index].id))
+    }
+}
+
+fn start_module(widget: &Rc<RefCell<Widget>>, module: ModuleInfo) {
+    let (root, menu) = Widget::parent_mut::<MainMenu>(widget);
+    let mut active = ActiveResources::read();
+    active.campaign = Some(module.dir);
+    active.write();
+    menu.next_step = Some(NextGameStep::MainMenuReloadResources);
+
+    let loading_screen = Widget::with_defaults(LoadingScreen::new());
+    loading_screen.borrow_mut().state.set_modal(true);
+    Widget::add_child_to(&root, loading_screen);
 }
 
 impl WidgetKind for ModuleSelector {
@@ -53,10 +83,13 @@ impl WidgetKind for ModuleSelector {
         let details = Widget::with_theme(TextArea::empty(), "details");
 
         if let Some(index) = self.selected_module {
-            details
-                .borrow_mut()
-                .state
-                .add_text_arg("description", &self.modules[index].description);
+            let state = &mut details.borrow_mut().state;
+            state.add_display_text_arg("description", &self.modules[index].description);
+
+            let warnings = self.warnings(index);
+            if !warnings.is_empty() {
+                state.add_text_arg("warnings", &warnings.join("\n"));
+            }
         }
 
         let mut groups = HashMap::new();
@@ -87,7 +120,7 @@ impl WidgetKind for ModuleSelector {
                 button
                     .borrow_mut()
                     .state
-                    .add_text_arg("module", &module.name);
+                    .add_display_text_arg("module", &module.name);
                 if let Some(selected_index) = self.selected_module {
                     button
                         .borrow_mut()
@@ -113,7 +146,7 @@ impl WidgetKind for ModuleSelector {
                 let name = Widget::with_theme(Label::empty(), "name_label");
                 name.borrow_mut()
                     .state
-                    .add_text_arg("name", &campaign_group.name);
+                    .add_display_text_arg("name", &campaign_group.name);
                 Widget::add_child_to_front(&group_widget, name);
             }
             scrollpane.borrow().add_to_content(group_widget);
@@ -122,25 +155,39 @@ impl WidgetKind for ModuleSelector {
         play.borrow_mut()
             .state
             .add_callback(Callback::new(Rc::new(|widget, _| {
-                let module = {
+                let (module, warnings) = {
                     let (_, module_selector) = Widget::parent_mut::<ModuleSelector>(widget);
 
                     let index = match module_selector.selected_module {
                         None => return,
                         Some(index) => index,
                     };
-                    module_selector.modules[index].clone()
+                    (
+                        module_selector.modules[index].clone(),
+                        module_selector.warnings(index),
+                    )
                 };
 
-                let (root, menu) = Widget::parent_mut::<MainMenu>(widget);
-                let mut active = ActiveResources::read();
-                active.campaign = Some(module.dir);
-                active.write();
-                menu.next_step = Some(NextGameStep::MainMenuReloadResources);
+                if warnings.is_empty() {
+                    start_module(widget, module);
+                    return;
+                }
 
-                let loading_screen = Widget::with_defaults(LoadingScreen::new());
-                loading_screen.borrow_mut().state.set_modal(true);
-                Widget::add_child_to(&root, loading_screen);
+                let count = warnings.len().to_string();
+                let accept = Callback::new(Rc::new(move |widget, _| {
+                    let (window, _) = Widget::parent::<ConfirmationWindow>(widget);
+                    window.borrow_mut().mark_for_removal();
+                    start_module(widget, module.clone());
+                }));
+
+                let root = Widget::get_root(widget);
+                let window = Widget::with_theme(
+                    ConfirmationWindow::new(accept),
+                    "mod_warnings_confirmation",
+                );
+                window.borrow_mut().state.add_text_arg("custom", &count);
+                window.borrow_mut().state.set_modal(true);
+                Widget::add_child_to(&root, window);
             })));
         play.borrow_mut()
             .state
@@ -0,0 +1,112 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label, TextArea};
+use sulis_module::{Actor, Module};
+use sulis_state::NextGameStep;
+
+use crate::main_menu::{CharacterSelector, MainMenu, Mode};
+use crate::LoadingScreen;
+
+/// Shows the campaign's starting conditions and leads into either character
+/// selection or, for campaigns with a fixed player character, the game itself
+pub struct NewGameWindow {}
+
+impl NewGameWindow {
+    pub fn new() -> Rc<RefCell<NewGameWindow>> {
+        Rc::new(RefCell::new(NewGameWindow {}))
+    }
+}
+
+impl WidgetKind for NewGameWindow {
+    widget_kind!("new_game_window");
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let campaign = Module::campaign();
+        let new_game = &campaign.new_game;
+
+        let title = Widget::with_theme(Label::empty(), "title");
+        title
+            .borrow_mut()
+            .state
+            .add_display_text_arg("name", &campaign.name);
+
+        let description = Widget::with_theme(TextArea::empty(), "description");
+        description
+            .borrow_mut()
+            .state
+            .add_display_text_arg("description", &campaign.description);
+
+        let mut party: Vec<&str> = Vec::new();
+        if let Some(ref player) = new_game.player {
+            party.push(&player.name);
+        }
+        party.extend(new_game.party.iter().map(|actor| actor.name.as_str()));
+
+        let party_box = Widget::with_theme(TextArea::empty(), "party");
+        party_box
+            .borrow_mut()
+            .state
+            .add_text_arg("party", &party.join(", "));
+        party_box.borrow_mut().state.set_visible(!party.is_empty());
+
+        let begin = match new_game.player {
+            None => Widget::with_theme(Button::empty(), "choose_character"),
+            Some(_) => Widget::with_theme(Button::empty(), "begin"),
+        };
+        begin
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let player = Module::campaign().new_game.player.clone();
+                match player {
+                    Some(player) => start_new_game(widget, player),
+                    None => {
+                        let (parent, menu) = Widget::parent_mut::<MainMenu>(widget);
+                        menu.mode = Mode::New;
+                        menu.content = Widget::with_defaults(CharacterSelector::new(
+                            Rc::clone(&parent),
+                        ));
+                        parent.borrow_mut().invalidate_children();
+                    }
+                }
+            })));
+
+        vec![title, description, party_box, begin]
+    }
+}
+
+/// Starts a new game of the current campaign with the specified player
+/// character, along with the campaign's starting party.  `widget` must
+/// be a descendant of the `MainMenu`.
+pub fn start_new_game(widget: &Rc<RefCell<Widget>>, pc_actor: Rc<Actor>) {
+    let party_actors = Module::campaign().new_game.party.clone();
+
+    let (root, menu) = Widget::parent_mut::<MainMenu>(widget);
+    menu.next_step = Some(NextGameStep::NewCampaign {
+        pc_actor,
+        party_actors,
+    });
+
+    let loading_screen = Widget::with_defaults(LoadingScreen::new());
+    loading_screen.borrow_mut().state.set_modal(true);
+    Widget::add_child_to(&root, loading_screen);
+}
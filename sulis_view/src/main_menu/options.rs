@@ -20,7 +20,7 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::rc::Rc;
 
-use sulis_core::config::DisplayMode;
+use sulis_core::config::{AutoPauseConfig, Difficulty, DisplayMode};
 use sulis_core::config::{self, Config, RawClick};
 use sulis_core::io::{event::ClickKind, keyboard_event::Key, DisplayConfiguration, InputActionKind};
 use sulis_core::ui::{Callback, Widget, WidgetKind};
@@ -54,6 +54,10 @@ pub struct Options {
 
     cur_crit_screen_shake: bool,
     cur_scroll_to_active: bool,
+    cur_difficulty: Difficulty,
+    cur_auto_pause: AutoPauseConfig,
+    cur_combat_anim_speed: u32,
+    cur_avoid_hazards: bool,
 
     audio_devices: Vec<String>,
     cur_audio_device: Option<usize>,
@@ -118,6 +122,10 @@ impl Options {
 
             cur_crit_screen_shake: config.input.crit_screen_shake,
             cur_scroll_to_active: config.display.scroll_to_active,
+            cur_difficulty: config.gameplay.difficulty,
+            cur_auto_pause: config.gameplay.auto_pause,
+            cur_combat_anim_speed: config.gameplay.combat_animation_speed,
+            cur_avoid_hazards: config.gameplay.avoid_hazards,
 
             audio_devices,
             cur_audio_device,
@@ -170,6 +178,10 @@ impl Options {
 
         config.input.crit_screen_shake = self.cur_crit_screen_shake;
         config.display.scroll_to_active = self.cur_scroll_to_active;
+        config.gameplay.difficulty = self.cur_difficulty;
+        config.gameplay.auto_pause = self.cur_auto_pause;
+        config.gameplay.combat_animation_speed = self.cur_combat_anim_speed;
+        config.gameplay.avoid_hazards = self.cur_avoid_hazards;
 
         config.audio.device = self.cur_audio_device.unwrap_or(0);
         config.audio.master_volume = self.master_volume;
@@ -532,6 +544,85 @@ impl Options {
         Widget::add_child_to(&scroll_to_active_content, scroll_to_active_on);
         Widget::add_child_to(&scroll_to_active_content, scroll_to_active_off);
 
+        let avoid_hazards_on = Widget::with_theme(Button::empty(), "on");
+        avoid_hazards_on.borrow_mut().state.add_callback(Callback::new(Rc::new(|widget, _| {
+            let (parent, options) = Widget::parent_mut::<Options>(widget);
+            options.cur_avoid_hazards = true;
+            parent.borrow_mut().invalidate_children();
+        })));
+
+        let avoid_hazards_off = Widget::with_theme(Button::empty(), "off");
+        avoid_hazards_off.borrow_mut().state.add_callback(Callback::new(Rc::new(|widget, _| {
+            let (parent, options) = Widget::parent_mut::<Options>(widget);
+            options.cur_avoid_hazards = false;
+            parent.borrow_mut().invalidate_children();
+        })));
+        if self.cur_avoid_hazards {
+            avoid_hazards_on.borrow_mut().state.set_active(true);
+        } else {
+            avoid_hazards_off.borrow_mut().state.set_active(true);
+        }
+
+        let avoid_hazards_content = Widget::empty("avoid_hazards_content");
+        Widget::add_child_to(&avoid_hazards_content, avoid_hazards_on);
+        Widget::add_child_to(&avoid_hazards_content, avoid_hazards_off);
+
+        let difficulty_content = Widget::empty("difficulty_content");
+        for difficulty in Difficulty::iter() {
+            let difficulty = *difficulty;
+            let button = Widget::with_theme(Button::empty(), &format!("{difficulty:?}"));
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let (parent, options) = Widget::parent_mut::<Options>(widget);
+                    options.cur_difficulty = difficulty;
+                    parent.borrow_mut().invalidate_children();
+                })));
+            button
+                .borrow_mut()
+                .state
+                .set_active(difficulty == self.cur_difficulty);
+            Widget::add_child_to(&difficulty_content, button);
+        }
+
+        let auto_pause_content = Widget::empty("auto_pause_content");
+        for (id, trigger) in AUTO_PAUSE_TRIGGERS.iter() {
+            let trigger = *trigger;
+            let button = Widget::with_theme(Button::empty(), id);
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let (parent, options) = Widget::parent_mut::<Options>(widget);
+                    let enabled = trigger(&mut options.cur_auto_pause);
+                    *enabled = !*enabled;
+                    parent.borrow_mut().invalidate_children();
+                })));
+            let active = *trigger(&mut self.cur_auto_pause);
+            button.borrow_mut().state.set_active(active);
+            Widget::add_child_to(&auto_pause_content, button);
+        }
+
+        let combat_speed_content = Widget::empty("combat_speed_content");
+        for speed in COMBAT_ANIM_SPEEDS.iter() {
+            let speed = *speed;
+            let button = Widget::with_theme(Button::empty(), &format!("speed_{speed}"));
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let (parent, options) = Widget::parent_mut::<Options>(widget);
+                    options.cur_combat_anim_speed = speed;
+                    parent.borrow_mut().invalidate_children();
+                })));
+            button
+                .borrow_mut()
+                .state
+                .set_active(speed == self.cur_combat_anim_speed.max(1));
+            Widget::add_child_to(&combat_speed_content, button);
+        }
+
         let zoom_content = Widget::empty("default_zoom_content");
         let mut zoom_found = false;
         for zoom in DEFAULT_ZOOMS.iter() {
@@ -600,6 +691,10 @@ impl Options {
             anim_speed_content,
             zoom_content,
             scroll_to_active_content,
+            difficulty_content,
+            auto_pause_content,
+            combat_speed_content,
+            avoid_hazards_content,
         ]
     }
 
@@ -753,9 +848,18 @@ const VOLUME_LEVELS: [f32; 11] = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0
 const UI_SCALE_NORMAL: (i32, i32) = (320, 180);
 const UI_SCALE_SMALL: (i32, i32) = (368, 207);
 const ANIM_SPEEDS: [u32; 5] = [75, 50, 35, 25, 15];
+const COMBAT_ANIM_SPEEDS: [u32; 3] = [1, 2, 4];
 const DEFAULT_ZOOMS: [f32; 5] = [1.0, 1.2, 1.4, 1.6, 1.8];
 const SCROLL_SPEEDS: [f32; 7] = [0.75, 1.0, 1.5, 2.25, 3.5, 5.0, 7.0];
 
+type AutoPauseField = fn(&mut AutoPauseConfig) -> &mut bool;
+const AUTO_PAUSE_TRIGGERS: [(&str, AutoPauseField); 4] = [
+    ("low_health", |c| &mut c.low_health),
+    ("enemy_sighted", |c| &mut c.enemy_sighted),
+    ("trap_detected", |c| &mut c.trap_detected),
+    ("spell_cast", |c| &mut c.spell_cast),
+];
+
 impl WidgetKind for Options {
     widget_kind!("options_window");
 
@@ -19,8 +19,8 @@ use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use sulis_core::ui::{Callback, Widget, WidgetKind};
-use sulis_core::widgets::Button;
-use sulis_state::{ChangeListener, EntityState, GameState};
+use sulis_core::widgets::{Button, TextArea};
+use sulis_state::{ChangeListener, EntityState, GameMessage, GameState, MessageChannel};
 
 use crate::{item_list_pane::Filter, ItemListPane};
 
@@ -30,6 +30,7 @@ pub struct MerchantWindow {
     merchant_id: String,
     player: Rc<RefCell<EntityState>>,
     filter: Rc<Cell<Filter>>,
+    buy_back: bool,
 }
 
 impl MerchantWindow {
@@ -38,6 +39,7 @@ impl MerchantWindow {
             merchant_id: merchant_id.to_string(),
             player,
             filter: Rc::new(Cell::new(Filter::All)),
+            buy_back: false,
         }))
     }
 
@@ -67,7 +69,7 @@ impl WidgetKind for MerchantWindow {
     }
 
     fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
-        {
+        let (can_haggle, haggle_frac) = {
             let area_state = GameState::area_state();
             let mut area_state = area_state.borrow_mut();
 
@@ -80,7 +82,8 @@ impl WidgetKind for MerchantWindow {
             merchant
                 .listeners
                 .add(ChangeListener::invalidate(NAME, widget));
-        }
+            (merchant.can_haggle(), merchant.haggle_frac())
+        };
 
         let close = Widget::with_theme(Button::empty(), "close");
         close
@@ -91,12 +94,62 @@ impl WidgetKind for MerchantWindow {
                 parent.borrow_mut().mark_for_removal();
             })));
 
-        let item_list_pane = Widget::with_defaults(ItemListPane::new_merchant(
-            &self.player,
-            self.merchant_id.to_string(),
-            &self.filter,
-        ));
+        let buy_tab = Widget::with_theme(Button::empty(), "buy_tab");
+        buy_tab.borrow_mut().state.set_active(!self.buy_back);
+        buy_tab.borrow_mut().state.add_callback(Callback::new(Rc::new(|widget, _| {
+            let (parent, window) = Widget::parent_mut::<MerchantWindow>(widget);
+            window.buy_back = false;
+            parent.borrow_mut().invalidate_children();
+        })));
+
+        let buy_back_tab = Widget::with_theme(Button::empty(), "buy_back_tab");
+        buy_back_tab.borrow_mut().state.set_active(self.buy_back);
+        buy_back_tab.borrow_mut().state.add_callback(Callback::new(Rc::new(|widget, _| {
+            let (parent, window) = Widget::parent_mut::<MerchantWindow>(widget);
+            window.buy_back = true;
+            parent.borrow_mut().invalidate_children();
+        })));
+
+        let haggle = Widget::with_theme(Button::empty(), "haggle");
+        haggle.borrow_mut().state.set_enabled(can_haggle);
+        haggle.borrow_mut().state.add_callback(Callback::new(Rc::new(|widget, _| {
+            let (_, window) = Widget::parent_mut::<MerchantWindow>(widget);
+            let area_state = GameState::area_state();
+            let mut area_state = area_state.borrow_mut();
+            let merchant = match area_state.get_merchant_mut(&window.merchant_id) {
+                None => return,
+                Some(merchant) => merchant,
+            };
+
+            let frac = match merchant.haggle(&window.player.borrow()) {
+                None => return,
+                Some(frac) => frac,
+            };
+
+            let name = window.player.borrow().actor.actor.name.to_string();
+            let text = if frac > 0.0 {
+                format!("{} haggles for better prices.", name)
+            } else if frac < 0.0 {
+                format!("{} offends the merchant, who raises their prices.", name)
+            } else {
+                format!("{} fails to haggle for better prices.", name)
+            };
+            GameState::add_message(GameMessage::new(&text).channel(MessageChannel::Notify));
+        })));
+
+        let haggle_label = Widget::with_theme(TextArea::empty(), "haggle_label");
+        if haggle_frac != 0.0 {
+            let percent = (haggle_frac * 100.0).round() as i32;
+            haggle_label.borrow_mut().state.add_text_arg("haggle_percent", &percent.to_string());
+        }
+
+        let item_list_pane = if self.buy_back {
+            ItemListPane::new_buy_back(&self.player, self.merchant_id.to_string(), &self.filter)
+        } else {
+            ItemListPane::new_merchant(&self.player, self.merchant_id.to_string(), &self.filter)
+        };
+        let item_list_pane = Widget::with_defaults(item_list_pane);
 
-        vec![close, item_list_pane]
+        vec![close, buy_tab, buy_back_tab, haggle, haggle_label, item_list_pane]
     }
 }
@@ -0,0 +1,228 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::rc::Rc;
+
+use sulis_core::io::{DrawList, GraphicsRenderer, TexturedQuad};
+use sulis_core::ui::Color;
+use sulis_core::util::{Offset, Point, Rect, Scale};
+use sulis_module::area::{LayerSet, Tile};
+use sulis_module::Module;
+
+struct OverTile {
+    region: usize,
+    layer: usize,
+    location: Point,
+    tile: Rc<Tile>,
+}
+
+/// The tiles of an area's over layers, such as roofs and bridges.  These are
+/// drawn above all creatures, with each connected group of tiles fading out
+/// while a party member stands beneath it.
+#[derive(Default)]
+pub struct OverLayers {
+    width: i32,
+    tiles: Vec<OverTile>,
+    regions: Vec<Option<usize>>,
+    alpha: Vec<f32>,
+    last_millis: Option<u32>,
+}
+
+impl OverLayers {
+    /// Collects the tiles in the layers of `layer_set` named in `over_layers`
+    /// and splits them into connected regions
+    pub fn new(layer_set: &LayerSet, over_layers: &[String]) -> OverLayers {
+        let width = layer_set.width;
+        let height = layer_set.height;
+        let mut covered = vec![false; (width * height) as usize];
+        let mut tiles = Vec::new();
+        for (index, layer) in layer_set.layers.iter().enumerate() {
+            if !over_layers.contains(&layer.id) {
+                continue;
+            }
+
+            for y in 0..layer.height {
+                for x in 0..layer.width {
+                    for tile in layer.tiles_at(x, y) {
+                        for tile_y in y..(y + tile.height).min(height) {
+                            for tile_x in x..(x + tile.width).min(width) {
+                                covered[(tile_x + tile_y * width) as usize] = true;
+                            }
+                        }
+
+                        tiles.push(OverTile {
+                            region: 0,
+                            layer: index,
+                            location: Point::new(x, y),
+                            tile: Rc::clone(tile),
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut regions = vec![None; covered.len()];
+        let mut region_count = 0;
+        for start in 0..covered.len() {
+            if !covered[start] || regions[start].is_some() {
+                continue;
+            }
+
+            let mut stack = vec![start as i32];
+            regions[start] = Some(region_count);
+            while let Some(index) = stack.pop() {
+                let (x, y) = (index % width, index / width);
+                for (next_x, next_y) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+                    if next_x < 0 || next_y < 0 || next_x >= width || next_y >= height {
+                        continue;
+                    }
+
+                    let next = (next_x + next_y * width) as usize;
+                    if covered[next] && regions[next].is_none() {
+                        regions[next] = Some(region_count);
+                        stack.push(next as i32);
+                    }
+                }
+            }
+            region_count += 1;
+        }
+
+        for over_tile in tiles.iter_mut() {
+            let loc = over_tile.location;
+            over_tile.region = regions[(loc.x + loc.y * width) as usize].unwrap_or(0);
+        }
+
+        // overlapping tiles are always in the same region, so this keeps the
+        // layer ordering wherever it matters
+        tiles.sort_by_key(|over_tile| (over_tile.region, over_tile.layer));
+
+        OverLayers {
+            width,
+            tiles,
+            regions,
+            alpha: vec![1.0; region_count],
+            last_millis: None,
+        }
+    }
+
+    /// Fades each region towards `faded_alpha` if any of `points` are beneath
+    /// it, or back to opaque otherwise, taking `fade_millis` for a full fade.
+    pub fn update(
+        &mut self,
+        points: impl Iterator<Item = Point>,
+        millis: u32,
+        faded_alpha: f32,
+        fade_millis: u32,
+    ) {
+        if self.alpha.is_empty() {
+            return;
+        }
+
+        let elapsed = match self.last_millis {
+            None => fade_millis,
+            Some(last) => millis.saturating_sub(last),
+        };
+        self.last_millis = Some(millis);
+
+        let mut beneath = vec![false; self.alpha.len()];
+        for p in points {
+            let index = (p.x + p.y * self.width) as usize;
+            if let Some(Some(region)) = self.regions.get(index) {
+                beneath[*region] = true;
+            }
+        }
+
+        let step = if fade_millis == 0 {
+            1.0
+        } else {
+            elapsed as f32 / fade_millis as f32
+        };
+        for (alpha, beneath) in self.alpha.iter_mut().zip(beneath) {
+            let target = if beneath { faded_alpha } else { 1.0 };
+            if *alpha < target {
+                *alpha = (*alpha + step).min(target);
+            } else {
+                *alpha = (*alpha - step).max(target);
+            }
+        }
+    }
+
+    /// Draws the tiles overlapping the tile coordinates from `min` to `max`
+    pub fn draw(
+        &self,
+        renderer: &mut dyn GraphicsRenderer,
+        offset: Offset,
+        scale: Scale,
+        color: Color,
+        min: Point,
+        max: Point,
+    ) {
+        let rules = Module::rules();
+        let mut quads: Vec<TexturedQuad> = Vec::new();
+        let mut cur: Option<(usize, &str)> = None;
+        for over_tile in self.tiles.iter() {
+            let loc = over_tile.location;
+            let tile = &over_tile.tile;
+            if loc.x + tile.width < min.x || loc.y + tile.height < min.y {
+                continue;
+            }
+            if loc.x > max.x || loc.y > max.y {
+                continue;
+            }
+
+            let image = &over_tile.tile.image_display;
+            let key = (over_tile.region, image.sheet_id.as_str());
+            if cur != Some(key) {
+                self.draw_quads(renderer, cur, &mut quads, scale, color);
+                cur = Some(key);
+            }
+
+            quads.push(TexturedQuad {
+                tex_coords: image.tex_coords,
+                rect: Rect {
+                    x: offset.x + loc.x as f32,
+                    y: offset.y + loc.y as f32 + rules.grid_y_offset(loc.x),
+                    w: over_tile.tile.width as f32,
+                    h: over_tile.tile.height as f32,
+                },
+            });
+        }
+        self.draw_quads(renderer, cur, &mut quads, scale, color);
+    }
+
+    fn draw_quads(
+        &self,
+        renderer: &mut dyn GraphicsRenderer,
+        cur: Option<(usize, &str)>,
+        quads: &mut Vec<TexturedQuad>,
+        scale: Scale,
+        mut color: Color,
+    ) {
+        let (region, sheet_id) = match cur {
+            None => return,
+            Some(cur) => cur,
+        };
+
+        color.a *= self.alpha[region];
+        if color.a > 0.0 {
+            let mut draw_list = DrawList::from_texture_quads(sheet_id, quads);
+            draw_list.set_scale(scale);
+            draw_list.set_color(color);
+            renderer.draw(draw_list);
+        }
+        quads.clear();
+    }
+}
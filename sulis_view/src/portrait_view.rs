@@ -17,29 +17,195 @@
 use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use sulis_core::io::event;
-use sulis_core::ui::{Callback, Widget, WidgetKind};
-use sulis_core::widgets::{Button, Label, ProgressBar};
-use sulis_state::{ChangeListener, EntityState, GameState};
+use sulis_core::ui::{animation_state, Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, ConfirmationWindow, Label, ProgressBar};
+use sulis_state::auto_resolve::{self, AutoResolveSummary};
+use sulis_state::{AIBehavior, ChangeListener, EntityState, GameState};
 
-use crate::CharacterBuilder;
+use crate::{CharacterBuilder, GameOverWindow, GeneratedPortrait, ItemActionMenu, RootView};
 
 pub const NAME: &str = "portrait_view";
 
+/// Holding the primary button on a portrait this long opens the radial menu
+const LONG_PRESS_TIME: Duration = Duration::from_millis(500);
+
 pub struct PortraitView {
     entity: Rc<RefCell<EntityState>>,
+    press_start: Option<Instant>,
 }
 
 impl PortraitView {
     pub fn new(entity: Rc<RefCell<EntityState>>) -> Rc<RefCell<PortraitView>> {
-        Rc::new(RefCell::new(PortraitView { entity }))
+        Rc::new(RefCell::new(PortraitView {
+            entity,
+            press_start: None,
+        }))
+    }
+
+    fn show_ai_menu(&self, widget: &Rc<RefCell<Widget>>) {
+        let menu = ItemActionMenu::new();
+
+        let assisted = GameState::is_assisted_combat();
+        let label = if assisted {
+            "Disable Assisted Combat"
+        } else {
+            "Enable Assisted Combat"
+        };
+        menu.borrow_mut().add_action(
+            label,
+            Callback::new(Rc::new(move |_, _| {
+                GameState::set_assisted_combat(!assisted);
+            })),
+        );
+
+        if !GameState::is_combat_active() {
+            let party_len = GameState::party().len();
+            let selected = GameState::selected();
+            if !selected.is_empty() && selected.len() < party_len {
+                menu.borrow_mut().add_action(
+                    "Split Off Selected",
+                    Callback::new(Rc::new(|_, _| {
+                        GameState::split_party(&GameState::selected());
+                    })),
+                );
+            }
+
+            if GameState::is_party_split() {
+                menu.borrow_mut().add_action(
+                    "Rejoin Party",
+                    Callback::new(Rc::new(|_, _| GameState::rejoin_party())),
+                );
+            }
+        }
+
+        if auto_resolve::can_auto_resolve() {
+            menu.borrow_mut().add_action(
+                "Auto-Resolve Combat",
+                Callback::new(Rc::new(|widget, _| {
+                    show_auto_resolve_confirmation(widget);
+                })),
+            );
+        }
+
+        if !Rc::ptr_eq(&self.entity, &GameState::player()) {
+            let behavior = self.entity.borrow().ai_behavior();
+            let stance = if behavior.defensive {
+                "Play Aggressively"
+            } else {
+                "Play Defensively"
+            };
+            self.add_behavior_action(&menu, stance, |b| b.defensive = !b.defensive);
+
+            let abilities = if behavior.conserve_abilities {
+                "Use Abilities Freely"
+            } else {
+                "Conserve Abilities"
+            };
+            self.add_behavior_action(&menu, abilities, |b| {
+                b.conserve_abilities = !b.conserve_abilities
+            });
+
+            let manual = if behavior.manual_only {
+                "Allow Assisted Control"
+            } else {
+                "Manual Control Only"
+            };
+            self.add_behavior_action(&menu, manual, |b| b.manual_only = !b.manual_only);
+        }
+
+        let menu = Widget::with_theme(menu, "ai_behavior_menu");
+        menu.borrow_mut().state.set_modal(true);
+        menu.borrow_mut().state.modal_remove_on_click_outside = true;
+        let root = Widget::get_root(widget);
+        Widget::add_child_to(&root, menu);
+    }
+
+    fn add_behavior_action(
+        &self,
+        menu: &Rc<RefCell<ItemActionMenu>>,
+        label: &str,
+        toggle: fn(&mut AIBehavior),
+    ) {
+        let entity = Rc::clone(&self.entity);
+        menu.borrow_mut().add_action(
+            label,
+            Callback::new(Rc::new(move |_, _| {
+                let mut behavior = entity.borrow().ai_behavior();
+                toggle(&mut behavior);
+                entity.borrow_mut().set_ai_behavior(behavior);
+            })),
+        );
+    }
+}
+
+fn show_auto_resolve_confirmation(widget: &Rc<RefCell<Widget>>) {
+    let cb = Callback::new(Rc::new(|widget, _| {
+        let (parent, _) = Widget::parent::<ConfirmationWindow>(widget);
+        parent.borrow_mut().mark_for_removal();
+
+        if let Some(summary) = auto_resolve::resolve() {
+            show_auto_resolve_summary(widget, &summary);
+        }
+    }));
+
+    let window = ConfirmationWindow::new(cb);
+    let cost = (auto_resolve::hp_cost() * 100.0).round() as i32;
+    window
+        .borrow()
+        .title()
+        .borrow_mut()
+        .state
+        .add_text_arg("hp_cost", &cost.to_string());
+
+    let window = Widget::with_theme(window, "auto_resolve_confirmation");
+    window.borrow_mut().state.set_modal(true);
+    let root = Widget::get_root(widget);
+    Widget::add_child_to(&root, window);
+}
+
+fn show_auto_resolve_summary(widget: &Rc<RefCell<Widget>>, summary: &AutoResolveSummary) {
+    let mut text = format!("Defeated {}.\n", summary.defeated.join(", "));
+    if summary.xp > 0 {
+        text.push_str(&format!("Each party member gained {} XP.\n", summary.xp));
+    }
+    for (name, hp) in summary.hp_lost.iter().filter(|(_, hp)| *hp > 0) {
+        text.push_str(&format!("{} lost {} hit points.\n", name, hp));
     }
+
+    let cb = Callback::new(Rc::new(|widget, _| {
+        let (parent, _) = Widget::parent::<GameOverWindow>(widget);
+        parent.borrow_mut().mark_for_removal();
+    }));
+    let window = Widget::with_theme(GameOverWindow::new(cb, text), "auto_resolve_summary");
+    let root = Widget::get_root(widget);
+    Widget::add_child_to(&root, window);
 }
 
 impl WidgetKind for PortraitView {
     widget_kind!(NAME);
 
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, _millis: u32) {
+        match self.press_start {
+            Some(start) if start.elapsed() >= LONG_PRESS_TIME => (),
+            _ => return,
+        }
+        self.press_start = None;
+
+        // the radial menu is modal, so this widget will not see the release
+        widget
+            .borrow_mut()
+            .state
+            .animation_state
+            .remove(animation_state::Kind::Pressed);
+
+        GameState::set_selected_party_member(Rc::clone(&self.entity));
+        let (root, view) = Widget::parent_mut::<RootView>(widget);
+        view.show_radial_menu(&root, Rc::clone(&self.entity));
+    }
+
     fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
         let mut entity = self.entity.borrow_mut();
         entity
@@ -53,6 +219,10 @@ impl WidgetKind for PortraitView {
                 .borrow_mut()
                 .state
                 .add_text_arg("image", &image.id());
+        } else {
+            let generated = GeneratedPortrait::new(&self.entity);
+            let generated = Widget::with_theme(generated, "generated_portrait");
+            Widget::add_child_to(&portrait, generated);
         }
 
         let frac = entity.actor.hp() as f32 / entity.actor.stats.max_hp as f32;
@@ -82,7 +252,7 @@ impl WidgetKind for PortraitView {
                     let state = &mut bar.borrow_mut().state;
                     state.add_text_arg("cur_stat", &cur.to_string());
                     state.add_text_arg("max_stat", &max.to_string());
-                    state.add_text_arg("stat_name", &stat.name);
+                    state.add_display_text_arg("stat_name", &stat.name);
                 }
                 bar
             }
@@ -109,6 +279,14 @@ impl WidgetKind for PortraitView {
             .state
             .set_enabled(!GameState::is_combat_active());
 
+        let group = Widget::with_theme(Label::empty(), "group");
+        if GameState::is_party_split() {
+            let group_text = (entity.party_group() + 1).to_string();
+            group.borrow_mut().state.add_text_arg("group", &group_text);
+        } else {
+            group.borrow_mut().state.set_visible(false);
+        }
+
         widget
             .borrow_mut()
             .state
@@ -136,11 +314,11 @@ impl WidgetKind for PortraitView {
             icon_widget
                 .borrow_mut()
                 .state
-                .add_text_arg("text", &icon.text);
+                .add_display_text_arg("text", &icon.text);
             Widget::add_child_to(&icons, icon_widget);
         }
 
-        vec![portrait, hp_bar, class_stat_bar, level_up, icons]
+        vec![portrait, hp_bar, class_stat_bar, level_up, group, icons]
     }
 
     fn on_mouse_enter(&mut self, widget: &Rc<RefCell<Widget>>) -> bool {
@@ -159,8 +337,25 @@ impl WidgetKind for PortraitView {
         true
     }
 
+    fn on_mouse_exit(&mut self, widget: &Rc<RefCell<Widget>>) -> bool {
+        self.super_on_mouse_exit(widget);
+        self.press_start = None;
+        true
+    }
+
+    fn on_mouse_press(&mut self, widget: &Rc<RefCell<Widget>>, kind: event::ClickKind) -> bool {
+        self.super_on_mouse_press(widget, kind);
+
+        let targeter = GameState::area_state().borrow_mut().targeter();
+        if kind == event::ClickKind::Primary && targeter.is_none() {
+            self.press_start = Some(Instant::now());
+        }
+        true
+    }
+
     fn on_mouse_release(&mut self, widget: &Rc<RefCell<Widget>>, kind: event::ClickKind) -> bool {
         self.super_on_mouse_release(widget, kind);
+        self.press_start = None;
 
         let area_state = GameState::area_state();
         let targeter = area_state.borrow_mut().targeter();
@@ -168,6 +363,8 @@ impl WidgetKind for PortraitView {
         if let Some(targeter) = targeter {
             let mut targeter = targeter.borrow_mut();
             targeter.on_activate();
+        } else if kind != event::ClickKind::Primary {
+            self.show_ai_menu(widget);
         } else {
             GameState::set_selected_party_member(Rc::clone(&self.entity));
         }
@@ -18,7 +18,7 @@ use std::any::Any;
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
-use crate::{item_list_pane::Filter, ItemListPane, RootView};
+use crate::{item_callback_handler::prop_owner, item_list_pane::Filter, ItemListPane, RootView};
 use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::widgets::{Button, Label};
 use sulis_state::{ChangeListener, EntityState, GameState};
@@ -102,6 +102,7 @@ impl WidgetKind for PropWindow {
                 })));
 
             let prop_index = self.prop_index;
+            let player = Rc::clone(&self.player);
             take_all
                 .borrow_mut()
                 .state
@@ -109,9 +110,14 @@ impl WidgetKind for PropWindow {
                     let (parent, _) = Widget::parent::<PropWindow>(widget);
                     parent.borrow_mut().mark_for_removal();
 
+                    let owner = prop_owner(prop_index);
                     let stash = GameState::party_stash();
                     stash.borrow_mut().take_all(prop_index);
 
+                    if let Some(owner) = owner {
+                        GameState::report_theft(&player, &owner);
+                    }
+
                     let (root, view) = Widget::parent_mut::<RootView>(&parent);
                     view.set_inventory_window(&root, false);
                 })));
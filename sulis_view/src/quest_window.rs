@@ -124,7 +124,7 @@ impl WidgetKind for QuestWindow {
             text_area
                 .borrow_mut()
                 .state
-                .add_text_arg("name", &quest.name);
+                .add_display_text_arg("name", &quest.name);
 
             if let QuestEntryState::Complete = quests.state(&quest.id) {
                 text_area
@@ -158,7 +158,7 @@ impl WidgetKind for QuestWindow {
                         state.set_active(active);
 
                         if let Some(quest_data) = quest.entries.get(id) {
-                            state.add_text_arg("description", &quest_data.description);
+                            state.add_display_text_arg("description", &quest_data.description);
                         }
                     }
 
@@ -0,0 +1,298 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::f32::consts::PI;
+use std::rc::Rc;
+
+use sulis_core::io::{event::ClickKind, InputActionKind};
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label};
+use sulis_module::QuickSlot;
+use sulis_state::{ability_state::DisabledReason, script::ScriptItemKind, EntityState, GameState};
+
+use crate::{abilities_bar::activate_ability, item_callback_handler::use_item_cb, RootView};
+
+pub const NAME: &str = "radial_menu";
+
+struct Entry {
+    widget: Rc<RefCell<Widget>>,
+    name: String,
+}
+
+/// A ring of the abilities, quick items, and end turn action available to an entity,
+/// as an alternative to the bottom bar.  Entries are selected by pointing with the
+/// mouse or with the scroll direction keys, and activated by clicking or with the
+/// `ActivateFocused` keybinding.
+pub struct RadialMenu {
+    entity: Rc<RefCell<EntityState>>,
+    entries: Vec<Entry>,
+    selected: Option<usize>,
+    hovered: Option<usize>,
+    name_label: Rc<RefCell<Widget>>,
+    radius: f32,
+}
+
+impl RadialMenu {
+    pub fn new(entity: Rc<RefCell<EntityState>>) -> Rc<RefCell<RadialMenu>> {
+        let name_label = Widget::with_theme(Label::empty(), "name");
+        name_label.borrow_mut().state.set_enabled(false);
+
+        Rc::new(RefCell::new(RadialMenu {
+            entity,
+            entries: Vec::new(),
+            selected: None,
+            hovered: None,
+            name_label,
+            radius: 30.0,
+        }))
+    }
+
+    fn add_entry(
+        &mut self,
+        theme_id: &str,
+        name: &str,
+        icon: Option<String>,
+        enabled: bool,
+        action: Callback,
+    ) {
+        let widget = Widget::with_theme(Button::empty(), theme_id);
+        widget.borrow_mut().state.set_enabled(enabled);
+        widget
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, kind| {
+                Widget::direct_parent(widget).borrow_mut().mark_for_removal();
+                action.call(widget, kind);
+            })));
+
+        if let Some(icon) = icon {
+            let icon_widget = Widget::empty("icon");
+            icon_widget.borrow_mut().state.add_text_arg("icon", &icon);
+            icon_widget.borrow_mut().state.set_enabled(false);
+            Widget::add_child_to(&widget, icon_widget);
+        }
+
+        self.entries.push(Entry {
+            widget,
+            name: name.to_string(),
+        });
+    }
+
+    fn add_ability_entries(&mut self) {
+        let abilities = self.entity.borrow().actor.actor.abilities.clone();
+        for owned in abilities {
+            let ability = owned.ability;
+            if ability.active.is_none() {
+                continue;
+            }
+
+            let enabled = {
+                let actor = &self.entity.borrow().actor;
+                actor.can_activate(&ability.id)
+                    || actor.can_toggle(&ability.id) == DisabledReason::Enabled
+            };
+
+            let entity = Rc::clone(&self.entity);
+            let action = Callback::new(Rc::new({
+                let ability = Rc::clone(&ability);
+                move |_, _| {
+                    activate_ability(&entity, &ability);
+                }
+            }));
+            let icon = Some(ability.icon.id());
+            self.add_entry("ability_entry", &ability.name, icon, enabled, action);
+        }
+    }
+
+    fn add_item_entries(&mut self) {
+        for slot in QuickSlot::usable_iter() {
+            let (name, icon, enabled) = {
+                let actor = &self.entity.borrow().actor;
+                match actor.inventory().quick(*slot) {
+                    None => continue,
+                    Some(item) => (
+                        item.item.name.clone(),
+                        item.icon().id(),
+                        actor.can_use_quick(*slot),
+                    ),
+                }
+            };
+
+            let action = use_item_cb(&self.entity, ScriptItemKind::Quick(*slot));
+            self.add_entry("item_entry", &name, Some(icon), enabled, action);
+        }
+    }
+
+    /// The offset of the specified entry from the center of the ring, in units of
+    /// the radius.  The first entry is at the top, with the rest following clockwise.
+    fn offset(&self, index: usize) -> (f32, f32) {
+        let angle = 2.0 * PI * index as f32 / self.entries.len() as f32 - PI / 2.0;
+        (angle.cos(), angle.sin())
+    }
+
+    fn set_selected(&mut self, selected: Option<usize>) {
+        if let Some(index) = self.selected {
+            self.entries[index].widget.borrow_mut().state.set_active(false);
+        }
+
+        self.selected = selected;
+        let name = match selected {
+            None => "",
+            Some(index) => {
+                let entry = &self.entries[index];
+                entry.widget.borrow_mut().state.set_active(true);
+                &entry.name
+            }
+        };
+
+        let mut label = self.name_label.borrow_mut();
+        label.state.add_display_text_arg("name", name);
+        label.invalidate_layout();
+    }
+
+    /// Moves the selection to the enabled entry that best lies in the specified
+    /// direction from the current selection, or from the center of the ring if
+    /// there is no selection.
+    fn select_toward(&mut self, dir_x: f32, dir_y: f32) {
+        let (origin_x, origin_y) = match self.selected {
+            None => (0.0, 0.0),
+            Some(index) => self.offset(index),
+        };
+
+        let mut best: Option<(usize, f32)> = None;
+        for (index, entry) in self.entries.iter().enumerate() {
+            if Some(index) == self.selected || !entry.widget.borrow().state.is_enabled() {
+                continue;
+            }
+
+            let (x, y) = self.offset(index);
+            let (delta_x, delta_y) = (x - origin_x, y - origin_y);
+            let dist = (delta_x * delta_x + delta_y * delta_y).sqrt();
+            if dist < f32::EPSILON {
+                continue;
+            }
+
+            // only consider entries within 60 degrees of the direction
+            let cos = (delta_x * dir_x + delta_y * dir_y) / dist;
+            if cos < 0.5 {
+                continue;
+            }
+
+            let score = dist / cos;
+            match best {
+                Some((_, best_score)) if best_score <= score => (),
+                _ => best = Some((index, score)),
+            }
+        }
+
+        if let Some((index, _)) = best {
+            self.set_selected(Some(index));
+        }
+    }
+
+    fn activate_selected(&self) {
+        let entry = match self.selected {
+            None => return,
+            Some(index) => &self.entries[index].widget,
+        };
+
+        let kind = Rc::clone(&entry.borrow().kind);
+        Widget::fire_callback(entry, &mut *kind.borrow_mut());
+    }
+}
+
+impl WidgetKind for RadialMenu {
+    widget_kind!(NAME);
+
+    fn update(&mut self, _widget: &Rc<RefCell<Widget>>, _millis: u32) {
+        let hovered = self
+            .entries
+            .iter()
+            .position(|entry| entry.widget.borrow().state.mouse_is_inside);
+
+        // the mouse only takes over the selection when it moves onto a new entry
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            if hovered.is_some() {
+                self.set_selected(hovered);
+            }
+        }
+    }
+
+    fn layout(&mut self, widget: &mut Widget) {
+        self.radius = widget.theme.get_custom_or_default("radius", 30.0);
+
+        widget.do_self_layout();
+        widget.do_children_layout();
+
+        let center = widget.state.inner_position();
+        let center_x = center.x + widget.state.inner_width() / 2;
+        let center_y = center.y + widget.state.inner_height() / 2;
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let (x, y) = self.offset(index);
+            let x = center_x + (x * self.radius).round() as i32;
+            let y = center_y + (y * self.radius).round() as i32;
+            entry.widget.borrow_mut().state.set_position_centered(x, y);
+        }
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        self.entries.clear();
+        self.selected = None;
+        self.hovered = None;
+
+        self.add_ability_entries();
+        self.add_item_entries();
+
+        if GameState::is_combat_active() && GameState::is_pc_current() {
+            let action = Callback::new(Rc::new(|widget, _| {
+                let (_, view) = Widget::parent_mut::<RootView>(widget);
+                view.end_turn();
+            }));
+            self.add_entry("end_turn_entry", "End Turn", None, true, action);
+        }
+
+        self.set_selected(None);
+
+        let mut children = vec![Rc::clone(&self.name_label)];
+        children.extend(self.entries.iter().map(|entry| Rc::clone(&entry.widget)));
+        children
+    }
+
+    fn on_mouse_press(&mut self, widget: &Rc<RefCell<Widget>>, _kind: ClickKind) -> bool {
+        // presses that miss every entry close the menu
+        widget.borrow_mut().mark_for_removal();
+        true
+    }
+
+    fn on_key_press(&mut self, widget: &Rc<RefCell<Widget>>, key: InputActionKind) -> bool {
+        use InputActionKind::*;
+        match key {
+            ScrollUp => self.select_toward(0.0, -1.0),
+            ScrollDown => self.select_toward(0.0, 1.0),
+            ScrollLeft => self.select_toward(-1.0, 0.0),
+            ScrollRight => self.select_toward(1.0, 0.0),
+            ActivateFocused => self.activate_selected(),
+            Back | ToggleRadialMenu => widget.borrow_mut().mark_for_removal(),
+            _ => (),
+        }
+
+        true
+    }
+}
@@ -0,0 +1,78 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::io::event::ClickKind;
+use sulis_core::ui::{Narrator, Widget, WidgetKind};
+use sulis_core::widgets::TextArea;
+
+pub const NAME: &str = "readout_pane";
+
+const MAX_LINES: usize = 6;
+
+/// Displays the most recent accessibility descriptions from the `Narrator`.
+/// Only visible when the screen reader is enabled.
+pub struct ReadoutPane {
+    generation: u64,
+}
+
+impl ReadoutPane {
+    pub fn new() -> Rc<RefCell<ReadoutPane>> {
+        Rc::new(RefCell::new(ReadoutPane {
+            generation: Narrator::generation(),
+        }))
+    }
+}
+
+impl WidgetKind for ReadoutPane {
+    widget_kind!(NAME);
+
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, _millis: u32) {
+        let generation = Narrator::generation();
+        if generation == self.generation {
+            return;
+        }
+
+        self.generation = generation;
+        widget.borrow_mut().invalidate_children();
+    }
+
+    fn on_mouse_press(&mut self, _widget: &Rc<RefCell<Widget>>, _kind: ClickKind) -> bool {
+        false
+    }
+
+    fn on_mouse_release(&mut self, _widget: &Rc<RefCell<Widget>>, _kind: ClickKind) -> bool {
+        false
+    }
+
+    fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let enabled = Narrator::is_enabled();
+        widget.borrow_mut().state.set_visible(enabled);
+        if !enabled {
+            return Vec::new();
+        }
+
+        let text = Widget::with_theme(TextArea::empty(), "text");
+        let lines = Narrator::recent_lines(MAX_LINES);
+        text.borrow_mut()
+            .state
+            .add_text_arg("lines", &lines.join("\n"));
+
+        vec![text]
+    }
+}
@@ -0,0 +1,251 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind, WidgetState};
+use sulis_core::widgets::{Button, ScrollDirection, ScrollPane, TextArea};
+use sulis_module::{Attribute, DamageKind, Module};
+use sulis_state::{ActorState, ChangeListener, GameState, RecordsState};
+
+pub const NAME: &str = "records_window";
+
+/// The bestiary reveal level at which a creature's defenses and resistances are shown
+pub const RESISTANCE_REVEAL_LEVEL: usize = 2;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Tab {
+    Creatures,
+    Items,
+    Lore,
+}
+
+pub struct RecordsWindow {
+    tab: Tab,
+    selected: Option<String>,
+}
+
+impl RecordsWindow {
+    pub fn new() -> Rc<RefCell<RecordsWindow>> {
+        Rc::new(RefCell::new(RecordsWindow {
+            tab: Tab::Creatures,
+            selected: None,
+        }))
+    }
+}
+
+impl WidgetKind for RecordsWindow {
+    widget_kind!(NAME);
+
+    fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        GameState::add_records_change_listener(ChangeListener::invalidate(NAME, widget));
+
+        let records = GameState::records();
+
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<RecordsWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let creatures_tab = self.create_tab_button("creatures_tab", Tab::Creatures);
+        let items_tab = self.create_tab_button("items_tab", Tab::Items);
+        let lore_tab = self.create_tab_button("lore_tab", Tab::Lore);
+
+        let entries = match self.tab {
+            Tab::Creatures => creature_entries(&records),
+            Tab::Items => item_entries(&records),
+            Tab::Lore => lore_entries(&records),
+        };
+
+        if let Some(ref selected) = self.selected {
+            if !entries.iter().any(|(id, _)| id == selected) {
+                self.selected = None;
+            }
+        }
+
+        let list_pane = ScrollPane::new(ScrollDirection::Vertical);
+        let list_widget = Widget::with_theme(list_pane.clone(), "entry_list");
+        for (id, name) in entries {
+            let button = Widget::with_theme(Button::empty(), "entry_button");
+            {
+                let state = &mut button.borrow_mut().state;
+                state.add_display_text_arg("name", &name);
+                state.set_active(self.selected.as_ref() == Some(&id));
+            }
+
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let (parent, window) = Widget::parent_mut::<RecordsWindow>(widget);
+                    window.selected = Some(id.clone());
+                    parent.borrow_mut().invalidate_children();
+                })));
+
+            list_pane.borrow().add_to_content(button);
+        }
+
+        let details = Widget::with_theme(TextArea::empty(), "details");
+        if let Some(ref id) = self.selected {
+            let state = &mut details.borrow_mut().state;
+            match self.tab {
+                Tab::Creatures => add_creature_text_args(state, &records, id),
+                Tab::Items => add_item_text_args(state, id),
+                Tab::Lore => add_lore_text_args(state, id),
+            }
+        }
+
+        vec![close, creatures_tab, items_tab, lore_tab, list_widget, details]
+    }
+}
+
+impl RecordsWindow {
+    fn create_tab_button(&self, theme: &str, tab: Tab) -> Rc<RefCell<Widget>> {
+        let button = Widget::with_theme(Button::empty(), theme);
+        button.borrow_mut().state.set_active(self.tab == tab);
+        button
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let (parent, window) = Widget::parent_mut::<RecordsWindow>(widget);
+                window.tab = tab;
+                window.selected = None;
+                parent.borrow_mut().invalidate_children();
+            })));
+        button
+    }
+}
+
+fn creature_entries(records: &RecordsState) -> Vec<(String, String)> {
+    let mut entries: Vec<_> = records
+        .creatures_iter()
+        .filter_map(|(id, _)| {
+            Module::actor(id).map(|actor| (id.to_string(), actor.name.to_string()))
+        })
+        .collect();
+    entries.sort_by(|a, b| a.1.cmp(&b.1));
+    entries
+}
+
+fn item_entries(records: &RecordsState) -> Vec<(String, String)> {
+    let mut entries: Vec<_> = records
+        .items()
+        .iter()
+        .filter_map(|id| Module::item(id).map(|item| (id.to_string(), item.name.to_string())))
+        .collect();
+    entries.sort_by(|a, b| a.1.cmp(&b.1));
+    entries
+}
+
+fn lore_entries(records: &RecordsState) -> Vec<(String, String)> {
+    let mut entries: Vec<_> = records
+        .lore()
+        .iter()
+        .filter_map(|id| Module::lore_entry(id))
+        .map(|entry| {
+            let name = format!("{}: {}", entry.category, entry.name);
+            (entry.id.to_string(), name)
+        })
+        .collect();
+    entries.sort_by(|a, b| a.1.cmp(&b.1));
+    entries
+}
+
+fn add_creature_text_args(state: &mut WidgetState, records: &RecordsState, id: &str) {
+    let actor = match Module::actor(id) {
+        None => return,
+        Some(actor) => actor,
+    };
+
+    let kills = records.creature(id).map_or(0, |record| record.kills);
+    let rules = Module::rules();
+    let reveal = rules.bestiary_reveal_level(kills);
+
+    state.add_display_text_arg("name", &actor.name);
+    state.add_display_text_arg("race", &actor.race.name);
+    state.add_text_arg("kills", &kills.to_string());
+
+    if reveal == 0 {
+        return;
+    }
+
+    let actor_state = ActorState::new(actor);
+    let stats = &actor_state.stats;
+    state.add_text_arg("level", &actor_state.actor.total_level.to_string());
+    state.add_text_arg("max_hp", &stats.max_hp.to_string());
+    for attribute in Attribute::iter() {
+        state.add_text_arg(
+            attribute.short_name(),
+            &stats.attributes.get(*attribute).to_string(),
+        );
+    }
+
+    if reveal < RESISTANCE_REVEAL_LEVEL {
+        return;
+    }
+
+    state.add_text_arg("defense", &stats.defense.to_string());
+    state.add_text_arg("fortitude", &stats.fortitude.to_string());
+    state.add_text_arg("reflex", &stats.reflex.to_string());
+    state.add_text_arg("will", &stats.will.to_string());
+    state.add_text_arg("armor", &stats.armor.base().to_string());
+    for kind in DamageKind::iter() {
+        let amount = stats.resistance.amount(*kind);
+        if amount == 0 {
+            continue;
+        }
+        state.add_text_arg(
+            &format!("resistance_{}", kind.to_str().to_lowercase()),
+            &amount.to_string(),
+        );
+    }
+}
+
+fn add_item_text_args(state: &mut WidgetState, id: &str) {
+    let item = match Module::item(id) {
+        None => return,
+        Some(item) => item,
+    };
+
+    let rules = Module::rules();
+    state.add_display_text_arg("name", &item.name);
+    state.add_text_arg("icon", &item.icon(None).id());
+    state.add_text_arg(
+        "value",
+        &((item.value as f32 / rules.item_value_display_factor) as i32).to_string(),
+    );
+    state.add_text_arg(
+        "weight",
+        &format!("{:.2}", item.weight as f32 / rules.item_weight_display_factor),
+    );
+}
+
+fn add_lore_text_args(state: &mut WidgetState, id: &str) {
+    let entry = match Module::lore_entry(id) {
+        None => return,
+        Some(entry) => entry,
+    };
+
+    state.add_display_text_arg("name", &entry.name);
+    state.add_display_text_arg("category", &entry.category);
+    state.add_display_text_arg("text", &entry.text);
+}
@@ -15,42 +15,45 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use std::collections::HashMap;
-use std::{any::Any, cell::RefCell, rc::Rc, time::Instant};
+use std::{any::Any, cell::RefCell, rc::Rc};
 
+use crate::banter_scheduler::BanterScheduler;
+use crate::trigger_activator::scroll_view;
 use crate::{
-    character_window, formation_window, inventory_window, merchant_window, prop_window,
-    quest_window, world_map_window, AbilitiesBar, ApBar, AreaView, CharacterWindow, ConsoleWindow,
-    FormationWindow, GameOverWindow, InGameMenu, InitiativeTicker, InventoryWindow, MerchantWindow,
-    PortraitPane, PropWindow, QuestWindow, QuickItemBar, WorldMapWindow,
+    achievements_window, character_window, formation_window, inventory_window, log_window,
+    merchant_window, prop_window, quest_window, radial_menu, records_window, tutorial_overlay,
+    world_map_window, AbilitiesBar, AchievementToast, ApBar, AreaView, CharacterWindow,
+    ConsoleWindow, FormationWindow, GameOverWindow, InGameMenu, InitiativeTicker, InventoryWindow,
+    LogWindow, MerchantWindow, PortraitPane, PropWindow, QuestWindow, QuickItemBar, RadialMenu,
+    ReadoutPane, RecordsWindow, StatusBar, TutorialOverlay, WorldMapWindow,
 };
 use sulis_core::config::Config;
 use sulis_core::io::{keyboard_event::Key, InputActionKind};
 use sulis_core::ui::{Callback, Cursor, Scrollable, Widget, WidgetKind};
-use sulis_core::util;
 use sulis_core::widgets::{Button, ConfirmationWindow, Label};
-use sulis_module::{area::OnRest, Module};
+use sulis_module::{area::OnRest, on_trigger::TutorialStepData, Module};
 use sulis_state::{
     area_feedback_text::ColorKind, save_file::create_save, script::script_callback,
-    script::ScriptEntity, AreaFeedbackText, ChangeListener, EntityState, GameState, NextGameStep,
-    Script,
+    script::ScriptEntity, AchievementState, AreaFeedbackText, ChangeListener, DebugTime,
+    AutoPause, EntityState, GameMessage, GameState, NextGameStep, Script,
 };
 
-const WINDOW_NAMES: [&str; 7] = [
+const WINDOW_NAMES: [&str; 9] = [
     self::formation_window::NAME,
     self::inventory_window::NAME,
     self::character_window::NAME,
     self::quest_window::NAME,
+    self::records_window::NAME,
     self::world_map_window::NAME,
     self::merchant_window::NAME,
     self::prop_window::NAME,
+    self::log_window::NAME,
 ];
 
 const NAME: &str = "game";
 
 pub struct RootView {
     pub(crate) next_step: Option<NextGameStep>,
-    status: Rc<RefCell<Widget>>,
-    status_added: Option<Instant>,
     area_view: Rc<RefCell<AreaView>>,
     area_view_widget: Rc<RefCell<Widget>>,
     console: Rc<RefCell<ConsoleWindow>>,
@@ -59,8 +62,10 @@ pub struct RootView {
     quick_item_bar: Option<Rc<RefCell<Widget>>>,
     abilities_bar: Option<Rc<RefCell<Widget>>>,
     area: String,
+    banter_scheduler: BanterScheduler,
 
     scroll_keys_down: Vec<InputActionKind>,
+    tutorial_step: Option<TutorialStepData>,
 }
 
 impl RootView {
@@ -80,8 +85,7 @@ impl RootView {
     }
 
     pub fn add_status_text(&mut self, text: &str) {
-        self.status.borrow_mut().state.text = text.to_string();
-        self.status_added = Some(Instant::now());
+        GameState::add_message(GameMessage::new(text));
     }
 
     pub fn new() -> Rc<RefCell<RootView>> {
@@ -93,8 +97,6 @@ impl RootView {
 
         Rc::new(RefCell::new(RootView {
             next_step: None,
-            status: Widget::with_theme(Label::empty(), "status_text"),
-            status_added: None,
             area_view,
             area_view_widget,
             area: "".to_string(),
@@ -102,10 +104,33 @@ impl RootView {
             console_widget,
             quick_item_bar: None,
             abilities_bar: None,
+            banter_scheduler: BanterScheduler::default(),
             scroll_keys_down: Vec::new(),
+            tutorial_step: None,
         }))
     }
 
+    /// Shows the specified tutorial step, replacing any current step.  The
+    /// step is kept when the root view is rebuilt, until it is completed or
+    /// the tutorial is ended
+    pub fn show_tutorial_step(&mut self, widget: &Rc<RefCell<Widget>>, step: TutorialStepData) {
+        self.end_tutorial(widget);
+        let overlay = Widget::with_defaults(TutorialOverlay::new(step.clone()));
+        self.tutorial_step = Some(step);
+        Widget::add_child_to(widget, overlay);
+    }
+
+    pub fn end_tutorial(&mut self, widget: &Rc<RefCell<Widget>>) {
+        if let Some(overlay) = Widget::get_child_with_name(widget, tutorial_overlay::NAME) {
+            overlay.borrow_mut().mark_for_removal();
+        }
+        self.tutorial_step = None;
+    }
+
+    pub(crate) fn clear_tutorial_step(&mut self) {
+        self.tutorial_step = None;
+    }
+
     /// Gets the merchant window if it is currently opened
     pub fn get_merchant_window(&self, widget: &Rc<RefCell<Widget>>) -> Option<Rc<RefCell<Widget>>> {
         Widget::get_child_with_name(widget, merchant_window::NAME).map(|w| Rc::clone(&w))
@@ -182,6 +207,18 @@ impl RootView {
         });
     }
 
+    pub fn set_records_window(&mut self, widget: &Rc<RefCell<Widget>>, desired_state: bool) {
+        self.set_window(widget, self::records_window::NAME, desired_state, &|| {
+            Some(RecordsWindow::new())
+        });
+    }
+
+    pub fn set_log_window(&mut self, widget: &Rc<RefCell<Widget>>, desired_state: bool) {
+        self.set_window(widget, self::log_window::NAME, desired_state, &|| {
+            Some(LogWindow::new())
+        });
+    }
+
     pub fn set_formation_window(&mut self, widget: &Rc<RefCell<Widget>>, desired_state: bool) {
         self.set_window(widget, self::formation_window::NAME, desired_state, &|| {
             Some(FormationWindow::new())
@@ -266,11 +303,42 @@ impl RootView {
         self.set_quest_window(widget, desired_state);
     }
 
+    pub fn toggle_records_window(&mut self, widget: &Rc<RefCell<Widget>>) {
+        let desired_state = !Widget::has_child_with_name(widget, self::records_window::NAME);
+        self.set_records_window(widget, desired_state);
+    }
+
+    pub fn toggle_log_window(&mut self, widget: &Rc<RefCell<Widget>>) {
+        let desired_state = !Widget::has_child_with_name(widget, self::log_window::NAME);
+        self.set_log_window(widget, desired_state);
+    }
+
     pub fn toggle_map_window(&mut self, widget: &Rc<RefCell<Widget>>) {
         let desired_state = !Widget::has_child_with_name(widget, self::world_map_window::NAME);
         self.set_map_window(widget, desired_state, false);
     }
 
+    pub fn toggle_radial_menu(&mut self, widget: &Rc<RefCell<Widget>>) {
+        if let Some(menu) = Widget::get_child_with_name(widget, radial_menu::NAME) {
+            menu.borrow_mut().mark_for_removal();
+            return;
+        }
+
+        if let Some(entity) = GameState::selected().first() {
+            self.show_radial_menu(widget, Rc::clone(entity));
+        }
+    }
+
+    pub fn show_radial_menu(
+        &mut self,
+        widget: &Rc<RefCell<Widget>>,
+        entity: Rc<RefCell<EntityState>>,
+    ) {
+        let menu = Widget::with_defaults(RadialMenu::new(entity));
+        menu.borrow_mut().state.set_modal(true);
+        Widget::add_child_to(widget, menu);
+    }
+
     pub fn show_menu(&mut self, widget: &Rc<RefCell<Widget>>) {
         let exit_cb = Callback::new(Rc::new(|widget, _| {
             let (_, root_view) = Widget::parent_mut::<RootView>(widget);
@@ -336,8 +404,8 @@ impl RootView {
     }
 
     pub fn save(&mut self) {
-        if GameState::is_combat_active() {
-            self.add_status_text("Cannot save during combat.");
+        if !GameState::can_save() {
+            self.add_status_text("Cannot save until the current turn action completes.");
             return;
         }
 
@@ -350,6 +418,21 @@ impl RootView {
         }
     }
 
+    /// Splits the selected party members off into their own group, or brings
+    /// the party back together if it is already split
+    pub fn toggle_split_party(&mut self) {
+        if GameState::is_party_split() {
+            GameState::rejoin_party();
+            self.add_status_text("Party rejoined.");
+        } else if GameState::split_party(&GameState::selected()) {
+            self.add_status_text("Party split.");
+        } else if GameState::is_combat_active() {
+            self.add_status_text("Cannot split the party during combat.");
+        } else {
+            self.add_status_text("Select some, but not all, party members to split off.");
+        }
+    }
+
     pub fn select_party_member(&self, index: usize) {
         let party = GameState::party();
 
@@ -371,17 +454,17 @@ impl WidgetKind for RootView {
             root.borrow_mut().invalidate_children();
         }
 
-        if let Some(instant) = self.status_added {
-            let elapsed = util::get_elapsed_millis(instant.elapsed());
-            if elapsed > 5000 {
-                self.status_added = None;
-                self.status.borrow_mut().state.text = String::new();
+        let root = Widget::get_root(widget);
+        if !Widget::has_child_with_name(&root, achievements_window::TOAST_NAME) {
+            if let Some(achievement) = AchievementState::take_new_award() {
+                let toast = Widget::with_defaults(AchievementToast::new(achievement));
+                Widget::add_child_to(&root, toast);
             }
         }
 
-        let root = Widget::get_root(widget);
         let has_modal = root.borrow().has_modal();
         GameState::set_modal_locked(has_modal);
+        self.banter_scheduler.update(has_modal, millis);
 
         let (cx, cy) = (Cursor::get_x(), Cursor::get_y());
         let mut area_view_updated = false;
@@ -400,6 +483,15 @@ impl WidgetKind for RootView {
                     }
                 }
 
+                // the tutorial overlay lets the highlighted area through
+                let passes_through = match child.borrow().kind.borrow().as_any().downcast_ref() {
+                    Some(overlay) => TutorialOverlay::passes_through(overlay, cx, cy),
+                    None => false,
+                };
+                if passes_through {
+                    continue;
+                }
+
                 if Rc::ptr_eq(&child, &self.area_view_widget) {
                     self.area_view
                         .borrow_mut()
@@ -451,6 +543,14 @@ impl WidgetKind for RootView {
             self.scroll_keys_down.remove(index);
         }
 
+        if key == InputActionKind::QueueAction {
+            self.area_view.borrow_mut().action_queue_mut().set_held(false);
+        }
+
+        if key == InputActionKind::HighlightInteractables {
+            self.area_view.borrow_mut().set_highlight_interactables(false);
+        }
+
         true
     }
 
@@ -459,20 +559,42 @@ impl WidgetKind for RootView {
         use sulis_core::io::InputActionKind::*;
         match key {
             Back => {
-                if !self.close_all_windows(widget) {
+                if self.close_all_windows(widget) {
+                    return true;
+                }
+                let queued = self.area_view.borrow_mut().action_queue_mut().clear();
+                if !GameState::stop_party_moves() && !queued {
                     self.show_menu(widget);
                 }
             }
+            QueueAction => self.area_view.borrow_mut().action_queue_mut().set_held(true),
+            HighlightInteractables => {
+                self.area_view.borrow_mut().set_highlight_interactables(true);
+            }
             ToggleConsole => self.toggle_console_window(widget),
             ToggleInventory => self.toggle_inventory_window(widget),
             ToggleCharacter => self.toggle_character_window(widget),
             ToggleMap => self.toggle_map_window(widget),
             ToggleJournal => self.toggle_quest_window(widget),
+            ToggleRecords => self.toggle_records_window(widget),
+            ToggleLog => self.toggle_log_window(widget),
+            ToggleDebugOverlay => {
+                let mut area_view = self.area_view.borrow_mut();
+                let enabled = !area_view.debug_overlay();
+                area_view.set_debug_overlay(enabled);
+            }
+            TogglePause => AutoPause::toggle(),
+            DebugPause => DebugTime::toggle_pause(),
+            DebugStepTurn => DebugTime::step_turn(),
+            DebugSlowMotion => DebugTime::cycle_time_scale(),
             ToggleFormation => self.toggle_formation_window(widget),
+            ToggleRadialMenu => self.toggle_radial_menu(widget),
             EndTurn => self.end_turn(),
             Rest => self.rest(),
             Exit => self.show_exit(widget),
             SelectAll => GameState::select_party_members(GameState::party()),
+            SplitParty => self.toggle_split_party(),
+            SelectNextGroup => select_next_party_group(widget),
             QuickSave => self.save(),
             ScrollUp | ScrollDown | ScrollRight | ScrollLeft => {
                 self.scroll_keys_down.push(key);
@@ -512,7 +634,9 @@ impl WidgetKind for RootView {
         self.console_widget.borrow_mut().state.set_visible(false);
 
         let prev_scroll = self.area_view.borrow().get_scroll();
+        let prev_debug_overlay = self.area_view.borrow().debug_overlay();
         self.area_view = AreaView::new(prev_scroll);
+        self.area_view.borrow_mut().set_debug_overlay(prev_debug_overlay);
         self.area_view_widget = Widget::with_defaults(self.area_view.clone());
 
         let bot_pane = Widget::empty("bottom_pane");
@@ -616,6 +740,16 @@ impl WidgetKind for RootView {
                 }),
             );
 
+            let records_button = create_button(
+                &keys,
+                ToggleRecords,
+                "records_button",
+                Rc::new(|widget, _| {
+                    let (root, view) = Widget::parent_mut::<RootView>(widget);
+                    view.toggle_records_window(&root);
+                }),
+            );
+
             let men_button = create_button(
                 &keys,
                 Back,
@@ -634,6 +768,7 @@ impl WidgetKind for RootView {
                     cha_button,
                     map_button,
                     log_button,
+                    records_button,
                     men_button,
                 ],
             );
@@ -712,7 +847,12 @@ impl WidgetKind for RootView {
         GameState::add_party_death_listener(ChangeListener::new(
             NAME,
             Box::new(move |party| {
-                if !is_defeated(party) {
+                if !GameState::is_defeated(party) {
+                    return;
+                }
+
+                // the game state revives the party and fires the defeat script
+                if Module::campaign().on_party_defeated_script.is_some() {
                     return;
                 }
 
@@ -724,7 +864,9 @@ impl WidgetKind for RootView {
                     let (_, view) = Widget::parent_mut::<RootView>(widget);
                     view.next_step = Some(NextGameStep::MainMenu);
                 }));
-                let menu = Widget::with_defaults(GameOverWindow::new(menu_cb, String::new()));
+                let window = GameOverWindow::new(menu_cb, String::new());
+                window.borrow_mut().show_load();
+                let menu = Widget::with_defaults(window);
                 Widget::add_child_to(&widget_ref, menu);
             }),
         ));
@@ -733,20 +875,45 @@ impl WidgetKind for RootView {
 
         let ticker = Widget::with_defaults(InitiativeTicker::new());
 
+        let readout = Widget::with_defaults(ReadoutPane::new());
+
+        let status_bar = Widget::with_defaults(StatusBar::new());
+
         // area widget must be the first entry in the children list
-        vec![
+        let mut children = vec![
             Rc::clone(&self.area_view_widget),
             bot_pane,
             ap_bar,
             ticker,
-            self.status.clone(),
+            readout,
+            status_bar,
             Rc::clone(&self.console_widget),
-        ]
+        ];
+
+        if let Some(ref step) = self.tutorial_step {
+            children.push(Widget::with_defaults(TutorialOverlay::new(step.clone())));
+        }
+
+        children
     }
 }
 
 type CB = dyn Fn(&Rc<RefCell<Widget>>, &mut dyn WidgetKind);
 
+/// Selects the next group of the split party and centers the view on it
+fn select_next_party_group(widget: &Rc<RefCell<Widget>>) {
+    let entity = match GameState::select_next_party_group() {
+        None => return,
+        Some(entity) => entity,
+    };
+
+    let (x, y) = {
+        let loc = &entity.borrow().location;
+        (loc.x, loc.y)
+    };
+    scroll_view(widget, x, y);
+}
+
 fn create_button(
     keybindings: &HashMap<InputActionKind, Key>,
     action: InputActionKind,
@@ -764,24 +931,3 @@ fn create_button(
 
     button
 }
-
-fn is_defeated(party: &[Rc<RefCell<EntityState>>]) -> bool {
-    if party.is_empty() {
-        return true;
-    }
-
-    {
-        let player = &party[0].borrow().actor;
-        if player.is_dead() && !player.is_disabled() {
-            return true;
-        }
-    }
-
-    for member in party.iter() {
-        if !member.borrow().actor.is_dead() {
-            return false;
-        }
-    }
-
-    true
-}
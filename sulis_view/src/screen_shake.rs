@@ -16,7 +16,7 @@
 
 use sulis_core::util::{gen_rand, Offset};
 
-const CLAMP_SHAKES: u32 = 4;
+const CLAMP_SHAKES: u32 = 3;
 const TOTAL_SHAKES: u32 = 7;
 const SHAKE_MILLIS: u32 = 110;
 
@@ -59,14 +59,24 @@ impl Default for ScreenShake {
 
 pub struct ScreenShake {
     total_shakes: u32,
+    max_shakes: u32,
+    intensity: f32,
     last_millis: u32,
     last_scroll: Offset,
 }
 
 impl ScreenShake {
     pub fn new() -> ScreenShake {
+        ScreenShake::with_intensity(1.0, TOTAL_SHAKES * SHAKE_MILLIS)
+    }
+
+    /// Creates a shake lasting approximately `millis`, with the shake distance
+    /// scaled by `intensity`.  The standard shake has an intensity of 1.0
+    pub fn with_intensity(intensity: f32, millis: u32) -> ScreenShake {
         ScreenShake {
             total_shakes: 0,
+            max_shakes: (millis / SHAKE_MILLIS).max(1),
+            intensity: intensity.max(0.0),
             last_millis: 0,
             last_scroll: Offset { x: 1.0, y: 0.0 },
         }
@@ -76,20 +86,23 @@ impl ScreenShake {
         self.last_millis += delta_millis;
 
         if self.last_millis >= SHAKE_MILLIS {
-            if self.total_shakes >= TOTAL_SHAKES {
+            if self.total_shakes >= self.max_shakes {
                 return ShakeResult::done(self);
             }
 
             self.total_shakes += 1;
             self.last_millis -= SHAKE_MILLIS;
 
+            let dist = gen_rand(1.0, 1.8) * self.intensity;
             let mut scroll = Offset {
-                x: -1.0 * self.last_scroll.x.signum() * gen_rand(1.0, 1.8) - self.last_scroll.x,
-                y: gen_rand(-0.1, 0.1) - self.last_scroll.y,
+                x: -1.0 * self.last_scroll.x.signum() * dist - self.last_scroll.x,
+                y: gen_rand(-0.1, 0.1) * self.intensity - self.last_scroll.y,
             };
 
-            if self.total_shakes > CLAMP_SHAKES {
-                let clamp_factor = 1.0 - (self.total_shakes - CLAMP_SHAKES) as f32 * 0.2;
+            // dampen the final few shakes
+            let clamp_start = self.max_shakes.saturating_sub(CLAMP_SHAKES);
+            if self.total_shakes > clamp_start {
+                let clamp_factor = 1.0 - (self.total_shakes - clamp_start) as f32 * 0.2;
                 scroll.x *= clamp_factor;
             }
 
@@ -68,7 +68,7 @@ impl WidgetKind for ScriptMenu {
             text_area
                 .borrow_mut()
                 .state
-                .add_text_arg("choice", &choice.display);
+                .add_display_text_arg("choice", &choice.display);
 
             let widget = Widget::with_theme(Button::empty(), "entry");
 
@@ -92,7 +92,7 @@ impl WidgetKind for ScriptMenu {
         }
 
         let title = Widget::with_theme(TextArea::empty(), "title");
-        title.borrow_mut().state.add_text_arg("title", &self.title);
+        title.borrow_mut().state.add_display_text_arg("title", &self.title);
 
         vec![title, entries, cancel]
     }
@@ -0,0 +1,186 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label, ScrollDirection, ScrollPane};
+use sulis_state::message_log::MessageRef;
+use sulis_state::{ChangeListener, EntityState, GameMessage, GameState, MessageChannel};
+
+use crate::{trigger_activator, ItemButton};
+
+pub const NAME: &str = "status_bar";
+
+/// Shows the most recent game message, with a button to show the message history
+pub struct StatusBar {}
+
+impl StatusBar {
+    pub fn new() -> Rc<RefCell<StatusBar>> {
+        Rc::new(RefCell::new(StatusBar {}))
+    }
+}
+
+impl WidgetKind for StatusBar {
+    widget_kind!(NAME);
+
+    fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let log = GameState::message_log();
+        log.borrow_mut()
+            .listeners
+            .add(ChangeListener::invalidate(NAME, widget));
+
+        let history = Widget::with_theme(Button::empty(), "history");
+        history
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let root = Widget::get_root(widget);
+                let window = Widget::with_defaults(MessageHistoryWindow::new());
+                window.borrow_mut().state.set_modal(true);
+                window.borrow_mut().state.modal_remove_on_click_outside = true;
+                Widget::add_child_to(&root, window);
+            })));
+
+        let log = log.borrow();
+        match log.latest() {
+            None => vec![history],
+            Some(message) => vec![history, message_row(message)],
+        }
+    }
+}
+
+const HISTORY_NAME: &str = "message_history_window";
+
+/// Lists the game messages in the log, newest first, with buttons to show or
+/// hide each message channel
+pub struct MessageHistoryWindow {}
+
+impl MessageHistoryWindow {
+    pub fn new() -> Rc<RefCell<MessageHistoryWindow>> {
+        Rc::new(RefCell::new(MessageHistoryWindow {}))
+    }
+}
+
+impl WidgetKind for MessageHistoryWindow {
+    widget_kind!(HISTORY_NAME);
+
+    fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let log = GameState::message_log();
+        log.borrow_mut()
+            .listeners
+            .add(ChangeListener::invalidate(HISTORY_NAME, widget));
+
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<MessageHistoryWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let filters = Widget::empty("filters");
+        for channel in MessageChannel::iter() {
+            let channel = *channel;
+            let button = Widget::with_theme(Button::empty(), channel.id());
+            let shown = !log.borrow().is_hidden(channel);
+            button.borrow_mut().state.set_active(shown);
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |_, _| {
+                    let log = GameState::message_log();
+                    let hidden = log.borrow().is_hidden(channel);
+                    log.borrow_mut().set_hidden(channel, !hidden);
+                })));
+            Widget::add_child_to(&filters, button);
+        }
+
+        let scroll = ScrollPane::new(ScrollDirection::Vertical);
+        let entries = Widget::with_theme(scroll.clone(), "entries");
+        for message in log.borrow().iter().rev() {
+            scroll.borrow().add_to_content(message_row(message));
+        }
+
+        vec![close, filters, entries]
+    }
+}
+
+/// Creates a widget showing the text of the `message`, colored by its channel,
+/// followed by buttons for each of its references
+fn message_row(message: &GameMessage) -> Rc<RefCell<Widget>> {
+    let row = Widget::empty("message");
+    let theme = format!("{}_text", message.channel.id());
+    let text = Widget::with_theme(Label::new(&message.plain_text()), &theme);
+
+    let references = Widget::empty("references");
+    for reference in message.references() {
+        let button = match reference {
+            MessageRef::Entity(entity) => match entity.upgrade() {
+                None => continue,
+                Some(entity) => entity_button(entity),
+            },
+            MessageRef::Item(item) => Widget::with_theme(ItemButton::display(item), "item"),
+        };
+        Widget::add_child_to(&references, button);
+    }
+
+    Widget::add_children_to(&row, vec![text, references]);
+    row
+}
+
+fn entity_button(entity: Rc<RefCell<EntityState>>) -> Rc<RefCell<Widget>> {
+    let name = entity.borrow().actor.actor.name.to_string();
+    let gone = is_gone(&entity.borrow());
+    let button = Widget::with_theme(Button::with_text(&name), "entity");
+    button
+        .borrow_mut()
+        .state
+        .add_callback(Callback::new(Rc::new(move |widget, _| {
+            find_entity(widget, &entity);
+        })));
+
+    button.borrow_mut().state.set_enabled(!gone);
+    button
+}
+
+fn is_gone(entity: &EntityState) -> bool {
+    !entity.is_party_member() && entity.actor.is_dead()
+}
+
+/// Selects the `entity` if it is in the party, and scrolls the view to it if
+/// it is in the current area
+fn find_entity(widget: &Rc<RefCell<Widget>>, entity: &Rc<RefCell<EntityState>>) {
+    if is_gone(&entity.borrow()) {
+        return;
+    }
+
+    if entity.borrow().is_party_member() {
+        GameState::set_selected_party_member(Rc::clone(entity));
+    }
+
+    let area = GameState::area_state();
+    let entity = entity.borrow();
+    if entity.location.area_id != area.borrow().area.area.id {
+        return;
+    }
+
+    let (x, y) = (entity.location.x, entity.location.y);
+    trigger_activator::scroll_view(widget, x, y);
+}
@@ -19,7 +19,9 @@ use std::rc::Rc;
 
 use sulis_core::ui::{Callback, Widget};
 use sulis_module::{
-    on_trigger::{self, Kind, ModuleLoadData, QuestStateData},
+    on_trigger::{
+        self, EpilogueSlide, Kind, ModuleLoadData, QuestStateData, ScrollViewData, ShakeViewData,
+    },
     Actor, ItemState, MerchantData, Module, OnTrigger,
 };
 use sulis_state::{
@@ -30,7 +32,7 @@ use sulis_state::{
 
 use crate::{
     ap_bar, character_window, dialog_window, window_fade, ConfirmationWindow, CutsceneWindow,
-    GameOverWindow, LoadingScreen, RootView, ScriptMenu, UIBlocker, WindowFade,
+    EpilogueWindow, GameOverWindow, LoadingScreen, RootView, ScriptMenu, UIBlocker, WindowFade,
 };
 
 pub fn is_match(
@@ -133,6 +135,16 @@ pub fn is_match(
                     return false;
                 }
             }
+            Approval(ref data) => {
+                if GameState::approval(&data.companion) < data.amount {
+                    return false;
+                }
+            }
+            NotApproval(ref data) => {
+                if GameState::approval(&data.companion) >= data.amount {
+                    return false;
+                }
+            }
             _ => {
                 warn!("Unsupported OnTrigger kind '{:?}' in validator", trigger);
             }
@@ -159,6 +171,15 @@ pub fn activate(
             CheckEndTurn => {
                 ap_bar::check_end_turn(widget);
             }
+            TutorialStep(ref data) => {
+                let (root, view) = Widget::parent_mut::<RootView>(widget);
+                view.show_tutorial_step(&root, data.clone());
+            }
+            EndTutorial => {
+                let (root, view) = Widget::parent_mut::<RootView>(widget);
+                view.end_tutorial(&root);
+            }
+            ShowEpilogue(ref slides) => show_epilogue(widget, slides, pc),
             PlayerAbility(ref ability_id) => {
                 let ability = match Module::ability(ability_id) {
                     None => {
@@ -187,6 +208,9 @@ pub fn activate(
             PlayerCoins(amount) => {
                 GameState::add_party_coins(*amount);
             }
+            Approval(ref data) => {
+                GameState::add_approval(&data.companion, data.amount);
+            }
             PartyMember(ref id) => match entity_with_id(id.to_string()) {
                 None => warn!(
                     "Attempted to add party member '{}' but entity does not exist",
@@ -241,7 +265,11 @@ pub fn activate(
             GameOverWindow(ref text) => game_over_window(widget, text.to_string()),
             ExitToMenu => exit_to_menu(widget),
             ScrollView(x, y) => scroll_view(widget, *x, *y),
+            ScrollViewTimed(ref data) => scroll_view_timed(widget, data),
+            LockView => lock_view(widget, target),
+            UnlockView => unlock_view(widget),
             ScreenShake => screen_shake(widget),
+            ShakeView(ref data) => shake_view(widget, data),
             LoadModule(ref module_data) => load_module(widget, module_data),
             ShowConfirm(ref data) => show_confirm(widget, data),
             ShowMenu(ref data) => show_menu(widget, data),
@@ -262,6 +290,9 @@ pub fn activate(
             NotQuestState(_) => {
                 warn!("NotQuestState invalid for trigger/dialog on_activate");
             }
+            NotApproval(_) => {
+                warn!("NotApproval invalid for trigger/dialog on_activate");
+            }
         }
     }
 }
@@ -285,11 +316,19 @@ fn verify_quest(data: &QuestStateData) {
 fn show_menu(widget: &Rc<RefCell<Widget>>, data: &on_trigger::MenuData) {
     let root = Widget::get_root(widget);
 
+    let parent = match GameState::turn_manager().borrow().entity_id(data.cb_parent) {
+        None => {
+            warn!("Menu callback parent {} no longer exists", data.cb_parent);
+            return;
+        }
+        Some(id) => id,
+    };
+
     let mut script_cb = match &data.cb_kind {
-        Kind::Ability(ref id) => CallbackData::new_ability(data.cb_parent, id),
-        Kind::Item(id) => CallbackData::new_item(data.cb_parent, id.to_string()),
-        Kind::Entity => CallbackData::new_entity(data.cb_parent),
-        Kind::Script(id) => CallbackData::new_trigger(data.cb_parent, id.to_string()),
+        Kind::Ability(ref id) => CallbackData::new_ability(parent, id),
+        Kind::Item(id) => CallbackData::new_item(parent, id.to_string()),
+        Kind::Entity => CallbackData::new_entity(parent),
+        Kind::Script(id) => CallbackData::new_trigger(parent, id.to_string()),
     };
     script_cb.add_func(FuncKind::OnMenuSelect, data.cb_func.to_string());
 
@@ -320,7 +359,7 @@ fn show_confirm(widget: &Rc<RefCell<Widget>>, data: &on_trigger::DialogData) {
         title
             .borrow_mut()
             .state
-            .add_text_arg("message", &data.message);
+            .add_display_text_arg("message", &data.message);
         window
             .borrow()
             .add_accept_text_arg("text", &data.accept_text);
@@ -428,6 +467,52 @@ pub fn scroll_view(widget: &Rc<RefCell<Widget>>, x: i32, y: i32) {
     );
 }
 
+pub fn scroll_view_timed(widget: &Rc<RefCell<Widget>>, data: &ScrollViewData) {
+    let root = Widget::get_root(widget);
+
+    let (area_view, area_view_widget) = {
+        let view = Widget::kind_mut::<RootView>(&root);
+        view.area_view()
+    };
+
+    let (width, height) = {
+        let area = GameState::area_state();
+        let area = area.borrow();
+        (area.area.width, area.area.height)
+    };
+
+    area_view.borrow_mut().timed_scroll_to_point(
+        data.x as f32,
+        data.y as f32,
+        data.millis,
+        width,
+        height,
+        &area_view_widget.borrow(),
+    );
+}
+
+pub fn lock_view(widget: &Rc<RefCell<Widget>>, target: &Rc<RefCell<EntityState>>) {
+    let root = Widget::get_root(widget);
+
+    let (area_view, _) = {
+        let view = Widget::kind_mut::<RootView>(&root);
+        view.area_view()
+    };
+
+    area_view.borrow_mut().lock_view(Rc::clone(target));
+}
+
+pub fn unlock_view(widget: &Rc<RefCell<Widget>>) {
+    let root = Widget::get_root(widget);
+
+    let (area_view, _) = {
+        let view = Widget::kind_mut::<RootView>(&root);
+        view.area_view()
+    };
+
+    area_view.borrow_mut().unlock_view();
+}
+
 pub fn screen_shake(widget: &Rc<RefCell<Widget>>) {
     let root = Widget::get_root(widget);
 
@@ -439,6 +524,17 @@ pub fn screen_shake(widget: &Rc<RefCell<Widget>>) {
     area_view.borrow_mut().screen_shake();
 }
 
+pub fn shake_view(widget: &Rc<RefCell<Widget>>, data: &ShakeViewData) {
+    let root = Widget::get_root(widget);
+
+    let (area_view, _) = {
+        let view = Widget::kind_mut::<RootView>(&root);
+        view.area_view()
+    };
+
+    area_view.borrow_mut().shake_view(data.intensity, data.millis);
+}
+
 fn exit_to_menu(widget: &Rc<RefCell<Widget>>) {
     let (_, view) = Widget::parent_mut::<RootView>(widget);
     view.next_step = Some(NextGameStep::MainMenu);
@@ -501,6 +597,25 @@ fn show_merchant(widget: &Rc<RefCell<Widget>>, merch: &MerchantData) {
     view.set_merchant_window(&root, true, id);
 }
 
+fn show_epilogue(
+    widget: &Rc<RefCell<Widget>>,
+    slides: &[EpilogueSlide],
+    pc: &Rc<RefCell<EntityState>>,
+) {
+    let slides: Vec<_> = slides
+        .iter()
+        .filter(|slide| is_match(&slide.conditions, pc, pc))
+        .cloned()
+        .collect();
+
+    info!("Showing epilogue with {} slides.", slides.len());
+
+    let root = Widget::get_root(widget);
+    let window = Widget::with_defaults(EpilogueWindow::new(slides));
+    window.borrow_mut().state.set_modal(true);
+    Widget::add_child_to(&root, window);
+}
+
 fn show_cutscene(widget: &Rc<RefCell<Widget>>, cutscene_id: &str) {
     let cutscene = match Module::cutscene(cutscene_id) {
         None => {
@@ -0,0 +1,278 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::image::Image;
+use sulis_core::io::{event::ClickKind, DrawList, GraphicsRenderer, InputActionKind};
+use sulis_core::resource::ResourceSet;
+use sulis_core::ui::{animation_state, Callback, Cursor, Widget, WidgetKind};
+use sulis_core::util::{Point, Rect};
+use sulis_core::widgets::{Button, TextArea};
+use sulis_module::on_trigger::{TutorialContinue, TutorialStepData};
+use sulis_state::{script::ScriptEntity, GameState, Script};
+
+use crate::RootView;
+
+pub const NAME: &str = "tutorial_overlay";
+
+/// Shows one step of a script driven tutorial.  Everything outside of the
+/// highlighted widget or region is dimmed, and all input is blocked except
+/// for mouse input within the highlight and the step's allowed action.
+pub struct TutorialOverlay {
+    step: TutorialStepData,
+    highlight: Option<Rect>,
+    fill: Option<Rc<dyn Image>>,
+    frame: Option<Rc<dyn Image>>,
+    frame_border: f32,
+    complete: bool,
+}
+
+impl TutorialOverlay {
+    pub fn new(step: TutorialStepData) -> Rc<RefCell<TutorialOverlay>> {
+        Rc::new(RefCell::new(TutorialOverlay {
+            step,
+            highlight: None,
+            fill: None,
+            frame: None,
+            frame_border: 0.0,
+            complete: false,
+        }))
+    }
+
+    /// Returns true if mouse input at the specified position should be
+    /// passed on to the widgets below this overlay
+    pub fn passes_through(&self, x: i32, y: i32) -> bool {
+        let rect = match self.highlight {
+            None => return false,
+            Some(rect) => rect,
+        };
+
+        let (x, y) = (x as f32, y as f32);
+        x >= rect.x && y >= rect.y && x < rect.x + rect.w && y < rect.y + rect.h
+    }
+
+    fn cursor_passes_through(&self) -> bool {
+        self.passes_through(Cursor::get_x(), Cursor::get_y())
+    }
+
+    fn complete_step(&mut self, widget: &Rc<RefCell<Widget>>) {
+        if self.complete {
+            return;
+        }
+        self.complete = true;
+
+        widget.borrow_mut().mark_for_removal();
+        let (_, view) = Widget::parent_mut::<RootView>(widget);
+        view.clear_tutorial_step();
+
+        if let Some(ref script) = self.step.on_continue {
+            let pc = GameState::player();
+            Script::trigger(
+                &script.id,
+                &script.func,
+                (ScriptEntity::from(&pc), ScriptEntity::from(&pc)),
+            );
+        }
+    }
+}
+
+fn find_widget(
+    parent: &Rc<RefCell<Widget>>,
+    overlay: &Rc<RefCell<Widget>>,
+    id: &str,
+) -> Option<Rect> {
+    for child in parent.borrow().children.iter() {
+        if Rc::ptr_eq(child, overlay) {
+            continue;
+        }
+
+        let state_rect = {
+            let child = child.borrow();
+            if !child.state.visible {
+                continue;
+            }
+
+            let theme_id = child.theme_id();
+            if theme_id == id || theme_id.ends_with(&format!(".{id}")) {
+                let state = &child.state;
+                Some(Rect {
+                    x: state.left() as f32,
+                    y: state.top() as f32,
+                    w: state.width() as f32,
+                    h: state.height() as f32,
+                })
+            } else {
+                None
+            }
+        };
+
+        if state_rect.is_some() {
+            return state_rect;
+        }
+
+        if let Some(rect) = find_widget(child, overlay, id) {
+            return Some(rect);
+        }
+    }
+
+    None
+}
+
+impl WidgetKind for TutorialOverlay {
+    widget_kind!(NAME);
+
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, _millis: u32) {
+        self.highlight = match self.step.highlight_widget {
+            Some(ref id) => {
+                let root = Widget::get_root(widget);
+                find_widget(&root, widget, id)
+            }
+            None => self.step.highlight_region.map(|[x, y, w, h]| Rect {
+                x: x as f32,
+                y: y as f32,
+                w: w as f32,
+                h: h as f32,
+            }),
+        };
+    }
+
+    fn layout(&mut self, widget: &mut Widget) {
+        let theme = &widget.theme;
+        if let Some(image_id) = theme.custom.get("fill_image") {
+            self.fill = ResourceSet::image(image_id);
+        }
+        if let Some(image_id) = theme.custom.get("frame_image") {
+            self.frame = ResourceSet::image(image_id);
+        }
+        self.frame_border = theme.get_custom_or_default("frame_border", 0.0);
+        widget.do_base_layout();
+    }
+
+    fn draw(
+        &mut self,
+        renderer: &mut dyn GraphicsRenderer,
+        _pixel_size: Point,
+        widget: &Widget,
+        millis: u32,
+    ) {
+        let left = widget.state.left() as f32;
+        let top = widget.state.top() as f32;
+        let right = widget.state.right() as f32;
+        let bottom = widget.state.bottom() as f32;
+
+        if let Some(ref fill) = self.fill {
+            let rects = match self.highlight {
+                None => vec![Rect { x: left, y: top, w: right - left, h: bottom - top }],
+                Some(h) => vec![
+                    Rect { x: left, y: top, w: right - left, h: h.y - top },
+                    Rect { x: left, y: h.y + h.h, w: right - left, h: bottom - h.y - h.h },
+                    Rect { x: left, y: h.y, w: h.x - left, h: h.h },
+                    Rect { x: h.x + h.w, y: h.y, w: right - h.x - h.w, h: h.h },
+                ],
+            };
+
+            let mut draw_list = DrawList::empty_sprite();
+            for rect in rects {
+                if rect.w <= 0.0 || rect.h <= 0.0 {
+                    continue;
+                }
+                fill.append_to_draw_list(&mut draw_list, &animation_state::NORMAL, rect, millis);
+            }
+            renderer.draw(draw_list);
+        }
+
+        if let (Some(frame), Some(h)) = (self.frame.as_ref(), self.highlight) {
+            let border = self.frame_border;
+            let rect = Rect {
+                x: h.x - border,
+                y: h.y - border,
+                w: h.w + 2.0 * border,
+                h: h.h + 2.0 * border,
+            };
+            frame.draw(renderer, &animation_state::NORMAL, rect, millis);
+        }
+    }
+
+    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let step = Widget::empty("step");
+
+        let text = Widget::with_theme(TextArea::empty(), "text");
+        text.borrow_mut().state.add_display_text_arg("text", &self.step.text);
+        Widget::add_child_to(&step, text);
+
+        if self.step.continue_on == TutorialContinue::Button {
+            let button = Widget::with_theme(Button::empty(), "continue_button");
+            button
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(|widget, _| {
+                    let (parent, overlay) = Widget::parent_mut::<TutorialOverlay>(widget);
+                    overlay.complete_step(&parent);
+                })));
+            Widget::add_child_to(&step, button);
+        }
+
+        vec![step]
+    }
+
+    fn on_mouse_press(&mut self, _widget: &Rc<RefCell<Widget>>, _kind: ClickKind) -> bool {
+        !self.cursor_passes_through()
+    }
+
+    fn on_mouse_release(&mut self, widget: &Rc<RefCell<Widget>>, _kind: ClickKind) -> bool {
+        if !self.cursor_passes_through() {
+            return true;
+        }
+
+        if self.step.continue_on == TutorialContinue::Click {
+            self.complete_step(widget);
+        }
+        false
+    }
+
+    fn on_mouse_drag(
+        &mut self,
+        _widget: &Rc<RefCell<Widget>>,
+        _kind: ClickKind,
+        _delta_x: f32,
+        _delta_y: f32,
+    ) -> bool {
+        !self.cursor_passes_through()
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _widget: &Rc<RefCell<Widget>>,
+        _delta_x: f32,
+        _delta_y: f32,
+    ) -> bool {
+        false
+    }
+
+    fn on_key_press(&mut self, widget: &Rc<RefCell<Widget>>, key: InputActionKind) -> bool {
+        if self.step.allowed_action != Some(key) {
+            return true;
+        }
+
+        if self.step.continue_on == TutorialContinue::Action {
+            self.complete_step(widget);
+        }
+        false
+    }
+}
@@ -20,9 +20,11 @@ use std::rc::Rc;
 
 use sulis_core::ui::{animation_state, Callback, Widget, WidgetKind};
 use sulis_core::util::Point;
-use sulis_core::widgets::{Button, TextArea};
+use sulis_core::widgets::{Button, InputField, Label, ScrollDirection, ScrollPane, TextArea};
 use sulis_module::{campaign::WorldMapLocation, Module, Time};
-use sulis_state::GameState;
+use sulis_state::{supplies, GameState, MapPin};
+
+use crate::trigger_activator::scroll_view;
 
 pub const NAME: &str = "world_map_window";
 
@@ -38,18 +40,124 @@ pub struct WorldMapWindow {
     offset: (f32, f32),
     content: Rc<RefCell<Widget>>,
     transition_enabled: bool,
+    note_field: Rc<RefCell<InputField>>,
+    note_widget: Rc<RefCell<Widget>>,
 }
 
 impl WorldMapWindow {
     pub fn new(transition_enabled: bool) -> Rc<RefCell<WorldMapWindow>> {
+        let note_field = InputField::new("");
+        let note_widget = Widget::with_theme(note_field.clone(), "note_field");
+
         Rc::new(RefCell::new(WorldMapWindow {
             entries: Vec::new(),
             size: (0.0, 0.0),
             offset: (0.0, 0.0),
             content: Widget::empty("content"),
             transition_enabled,
+            note_field,
+            note_widget,
         }))
     }
+
+    fn add_note(&mut self) {
+        let text = self.note_field.borrow().text();
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        let leader = match GameState::selected().first() {
+            Some(entity) => Rc::clone(entity),
+            None => GameState::player(),
+        };
+        let (x, y) = {
+            let leader = leader.borrow();
+            (leader.location.x, leader.location.y)
+        };
+        GameState::add_map_note(x, y, text);
+
+        self.note_field.borrow_mut().clear(&self.note_widget);
+    }
+
+    fn create_pins_pane(&self, cur_area_id: &str) -> Rc<RefCell<Widget>> {
+        let pins_pane = Widget::empty("pins_pane");
+
+        let title = Widget::with_theme(Label::empty(), "title");
+
+        let pin_list = ScrollPane::new(ScrollDirection::Vertical);
+        let pin_list_widget = Widget::with_theme(pin_list.clone(), "pin_list");
+
+        let map_pins = GameState::map_pins();
+        let pins = map_pins
+            .in_area(cur_area_id)
+            .chain(map_pins.iter().filter(|pin| pin.area != cur_area_id));
+        for pin in pins {
+            pin_list.borrow().add_to_content(create_pin_button(pin, cur_area_id));
+        }
+
+        let add_note = Widget::with_theme(Button::empty(), "add_note");
+        add_note
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, window) = Widget::parent_mut::<WorldMapWindow>(widget);
+                window.add_note();
+                parent.borrow_mut().invalidate_children();
+            })));
+
+        Widget::add_child_to(&pins_pane, title);
+        Widget::add_child_to(&pins_pane, pin_list_widget);
+        Widget::add_child_to(&pins_pane, Rc::clone(&self.note_widget));
+        Widget::add_child_to(&pins_pane, add_note);
+        pins_pane
+    }
+}
+
+fn create_pin_button(pin: &MapPin, cur_area_id: &str) -> Rc<RefCell<Widget>> {
+    let button = Widget::with_theme(Button::empty(), "pin_button");
+
+    let text_area = Widget::with_defaults(TextArea::empty());
+    text_area
+        .borrow_mut()
+        .state
+        .add_display_text_arg("label", &pin.label);
+
+    if pin.area == cur_area_id {
+        let (x, y) = (pin.x, pin.y);
+        button
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                let (parent, _) = Widget::parent::<WorldMapWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+                scroll_view(widget, x, y);
+            })));
+    } else {
+        let area_name = Module::area(&pin.area).map_or(pin.area.to_string(), |a| a.name.clone());
+        text_area
+            .borrow_mut()
+            .state
+            .add_display_text_arg("area", &area_name);
+        button.borrow_mut().state.set_enabled(false);
+    }
+    Widget::add_child_to(&button, text_area);
+
+    if pin.note {
+        let remove = Widget::with_theme(Button::empty(), "remove");
+        let id = pin.id.to_string();
+        remove
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(move |widget, _| {
+                GameState::remove_map_pin(&id);
+                let (parent, _) = Widget::parent::<WorldMapWindow>(widget);
+                parent.borrow_mut().invalidate_children();
+            })));
+        Widget::add_child_to(&button, remove);
+    }
+
+    button
 }
 
 impl WidgetKind for WorldMapWindow {
@@ -106,13 +214,15 @@ impl WidgetKind for WorldMapWindow {
 
         let area_state = GameState::area_state();
         let cur_location_id = area_state.borrow().area.area.world_map_location.clone();
+        let cur_area_id = area_state.borrow().area.area.id.to_string();
+        let pin_counts = GameState::map_pins().counts_by_area();
 
         for location in map.locations.iter() {
             let button = Widget::with_theme(Button::empty(), "location");
 
             let (add_callback, label) = {
                 let state = &mut button.borrow_mut().state;
-                state.add_text_arg("name", &location.name);
+                state.add_display_text_arg("name", &location.name);
                 state.add_text_arg("icon", &location.icon.id());
 
                 let is_active = if let Some(ref location_id) = &cur_location_id {
@@ -138,9 +248,17 @@ impl WidgetKind for WorldMapWindow {
                 label
                     .borrow_mut()
                     .state
-                    .add_text_arg("name", &location.name);
+                    .add_display_text_arg("name", &location.name);
                 label.borrow_mut().state.set_visible(is_visible);
 
+                let pins = location.linked_area.as_ref().and_then(|id| pin_counts.get(id));
+                if let Some(pins) = pins {
+                    label
+                        .borrow_mut()
+                        .state
+                        .add_text_arg("pins", &pins.to_string());
+                }
+
                 (
                     self.transition_enabled && is_enabled && is_visible && !is_active,
                     label,
@@ -166,7 +284,9 @@ impl WidgetKind for WorldMapWindow {
             Widget::add_child_to(&self.content, Rc::clone(&entry.label));
         }
 
-        vec![bg, close, labels, Rc::clone(&self.content)]
+        let pins_pane = self.create_pins_pane(&cur_area_id);
+
+        vec![bg, close, labels, Rc::clone(&self.content), pins_pane]
     }
 }
 
@@ -194,6 +314,14 @@ fn add_travel_callback(
         .state
         .add_text_arg("travel_time", &travel_time.to_string());
 
+    let rations = supplies::travel_rations(hours);
+    if rations > 0 {
+        label
+            .borrow_mut()
+            .state
+            .add_text_arg("rations", &rations.to_string());
+    }
+
     let (x, y) = (location.linked_area_pos.x, location.linked_area_pos.y);
     let area_id = match &location.linked_area {
         None => return false,
@@ -203,18 +331,13 @@ fn add_travel_callback(
     button
         .borrow_mut()
         .state
-        .add_callback(travel_callback(area_id, x, y, travel_time));
+        .add_callback(travel_callback(area_id, x, y, hours));
     true
 }
 
-fn travel_callback(area_id: String, x: i32, y: i32, travel_time: Time) -> Callback {
+fn travel_callback(area_id: String, x: i32, y: i32, hours: u32) -> Callback {
     Callback::new(Rc::new(move |widget, _| {
-        GameState::transition_to(
-            Some(&area_id),
-            Some(Point::new(x, y)),
-            Point::default(),
-            travel_time,
-        );
+        GameState::travel_to(&area_id, Point::new(x, y), hours);
         let root = Widget::get_root(widget);
         root.borrow_mut().invalidate_children();
     }))